@@ -56,4 +56,8 @@ pub mod keygen;
 pub mod locations;
 pub mod organizations;
 pub mod products;
+pub mod purchase_orders;
+pub mod records;
 pub mod schemas;
+#[cfg(feature = "workflow")]
+pub mod workflows;
@@ -0,0 +1,365 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    fs,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use grid_sdk::{
+    pike::addressing::PIKE_NAMESPACE,
+    protocol::purchase_order::payload::{
+        Action, CreatePurchaseOrderPayload, CreateVersionPayload, PayloadRevision,
+        PayloadRevisionBuilder, PurchaseOrderPayloadBuilder, UpdatePurchaseOrderPayload,
+        UpdateVersionPayload,
+    },
+    protos::IntoProto,
+    purchase_order::addressing::GRID_PURCHASE_ORDER_NAMESPACE,
+};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::actions::Paging;
+use crate::error::CliError;
+use crate::http::submit_batches;
+use crate::transaction::purchase_order_batch_builder;
+
+pub fn do_create_purchase_order(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    payload: CreatePurchaseOrderPayload,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    submit_payloads(url, key, wait, vec![Action::CreatePo(payload)], service_id)
+}
+
+pub fn do_update_purchase_order(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    payload: UpdatePurchaseOrderPayload,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    submit_payloads(url, key, wait, vec![Action::UpdatePo(payload)], service_id)
+}
+
+pub fn do_create_version(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    payload: CreateVersionPayload,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    submit_payloads(
+        url,
+        key,
+        wait,
+        vec![Action::CreateVersion(payload)],
+        service_id,
+    )
+}
+
+pub fn do_update_version(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    payload: UpdateVersionPayload,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    submit_payloads(
+        url,
+        key,
+        wait,
+        vec![Action::UpdateVersion(payload)],
+        service_id,
+    )
+}
+
+pub fn do_list_purchase_orders(url: &str, service_id: Option<&str>) -> Result<(), CliError> {
+    let client = Client::new();
+    let mut final_url = format!("{}/purchase_order", url);
+    if let Some(service_id) = service_id {
+        final_url = format!("{}?service_id={}", final_url, service_id);
+    }
+
+    let mut purchase_orders = Vec::new();
+
+    loop {
+        let mut response = client.get(&final_url).send()?;
+
+        if !response.status().is_success() {
+            return Err(CliError::DaemonError(response.text()?));
+        }
+
+        let mut purchase_order_list = response.json::<PurchaseOrderListSlice>()?;
+
+        purchase_orders.append(&mut purchase_order_list.data);
+
+        if let Some(next) = purchase_order_list.paging.next {
+            final_url = format!("{}{}", url, next);
+        } else {
+            break;
+        }
+    }
+
+    display_purchase_orders(&purchase_orders);
+    Ok(())
+}
+
+pub fn do_show_purchase_order(
+    url: &str,
+    uid: &str,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    let purchase_order: PurchaseOrderSlice =
+        get(&format!("{}/purchase_order/{}", url, uid), service_id)?;
+
+    display_purchase_order(&purchase_order);
+    Ok(())
+}
+
+pub fn do_list_versions(url: &str, uid: &str, service_id: Option<&str>) -> Result<(), CliError> {
+    let versions: Vec<PurchaseOrderVersionSlice> = get(
+        &format!("{}/purchase_order/{}/version", url, uid),
+        service_id,
+    )?;
+
+    display_versions(&versions);
+    Ok(())
+}
+
+pub fn do_list_revisions(
+    url: &str,
+    uid: &str,
+    version_id: &str,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    let revisions: Vec<PurchaseOrderRevisionSlice> = get(
+        &format!(
+            "{}/purchase_order/{}/version/{}/revision",
+            url, uid, version_id
+        ),
+        service_id,
+    )?;
+
+    display_revisions(&revisions);
+    Ok(())
+}
+
+pub fn do_show_revision(
+    url: &str,
+    uid: &str,
+    version_id: &str,
+    revision_id: &str,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    let revision: PurchaseOrderRevisionSlice = get(
+        &format!(
+            "{}/purchase_order/{}/version/{}/revision/{}",
+            url, uid, version_id, revision_id
+        ),
+        service_id,
+    )?;
+
+    println!(
+        "Revision ID: {}\nSubmitter: {}\nCreated At: {}\n{}",
+        revision.revision_id, revision.submitter, revision.created_at, revision.order_xml_v3_4
+    );
+    Ok(())
+}
+
+/// Fetches a version of a purchase order from the REST API
+pub fn get_version(
+    url: &str,
+    uid: &str,
+    version_id: &str,
+    service_id: Option<&str>,
+) -> Result<PurchaseOrderVersionSlice, CliError> {
+    get(
+        &format!("{}/purchase_order/{}/version/{}", url, uid, version_id),
+        service_id,
+    )
+}
+
+/// Builds a revision from a file containing a GS1 Order XML v3.4 document
+pub fn make_revision(revision_id: u64, order_file: &str) -> Result<PayloadRevision, CliError> {
+    let order_xml = fs::read_to_string(order_file).map_err(|err| {
+        CliError::UserError(format!("Unable to read order file {}: {}", order_file, err))
+    })?;
+
+    PayloadRevisionBuilder::new()
+        .with_revision_id(revision_id)
+        .with_created_at(current_timestamp()?)
+        .with_order_xml_v3_4(order_xml)
+        .build()
+        .map_err(|err| CliError::UserError(format!("{}", err)))
+}
+
+/// Returns the current time in seconds since the epoch
+pub fn current_timestamp() -> Result<u64, CliError> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .map_err(|err| CliError::PayloadError(format!("{}", err)))
+}
+
+fn get<T: serde::de::DeserializeOwned>(url: &str, service_id: Option<&str>) -> Result<T, CliError> {
+    let client = Client::new();
+    let mut final_url = url.to_string();
+    if let Some(service_id) = service_id {
+        final_url = format!("{}?service_id={}", final_url, service_id);
+    }
+
+    let mut response = client.get(&final_url).send()?;
+
+    if !response.status().is_success() {
+        return Err(CliError::DaemonError(response.text()?));
+    }
+
+    Ok(response.json::<T>()?)
+}
+
+fn submit_payloads(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    actions: Vec<Action>,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    let mut builder = purchase_order_batch_builder(key);
+
+    for action in actions {
+        let timestamp = current_timestamp()?;
+
+        let action = PurchaseOrderPayloadBuilder::new()
+            .with_action(action)
+            .with_timestamp(timestamp)
+            .build()
+            .map_err(|err| CliError::PayloadError(format!("{}", err)))?;
+
+        builder.add_transaction(
+            &action.into_proto()?,
+            &[
+                PIKE_NAMESPACE.to_string(),
+                GRID_PURCHASE_ORDER_NAMESPACE.to_string(),
+            ],
+            &[GRID_PURCHASE_ORDER_NAMESPACE.to_string()],
+        )?;
+    }
+
+    let batches = builder.create_batch_list();
+
+    submit_batches(url, wait, &batches, service_id)
+}
+
+fn display_purchase_orders(purchase_orders: &[PurchaseOrderSlice]) {
+    println!(
+        "{:<20} {:<12} {:<20} {:<20} {:<8}",
+        "UID", "STATUS", "BUYER", "SELLER", "VERSIONS"
+    );
+    purchase_orders.iter().for_each(|purchase_order| {
+        println!(
+            "{:<20} {:<12} {:<20} {:<20} {:<8}",
+            purchase_order.purchase_order_uid,
+            purchase_order.workflow_status,
+            purchase_order.buyer_org_id,
+            purchase_order.seller_org_id,
+            purchase_order.versions.len()
+        )
+    });
+}
+
+fn display_purchase_order(purchase_order: &PurchaseOrderSlice) {
+    println!(
+        "Purchase Order UID: {}\nStatus: {}\nBuyer: {}\nSeller: {}\nClosed: {}\n\
+         Accepted Version: {}\nCreated At: {}",
+        purchase_order.purchase_order_uid,
+        purchase_order.workflow_status,
+        purchase_order.buyer_org_id,
+        purchase_order.seller_org_id,
+        purchase_order.is_closed,
+        purchase_order
+            .accepted_version_id
+            .as_deref()
+            .unwrap_or("none"),
+        purchase_order.created_at,
+    );
+    println!("Versions:");
+    display_versions(&purchase_order.versions);
+}
+
+fn display_versions(versions: &[PurchaseOrderVersionSlice]) {
+    println!(
+        "{:<12} {:<12} {:<6} {:<8}",
+        "VERSION", "STATUS", "DRAFT", "REVISION"
+    );
+    versions.iter().for_each(|version| {
+        println!(
+            "{:<12} {:<12} {:<6} {:<8}",
+            version.version_id,
+            version.workflow_status,
+            version.is_draft,
+            version.current_revision_id
+        )
+    });
+}
+
+fn display_revisions(revisions: &[PurchaseOrderRevisionSlice]) {
+    println!("{:<8} {:<66} {:<12}", "REVISION", "SUBMITTER", "CREATED AT");
+    revisions.iter().for_each(|revision| {
+        println!(
+            "{:<8} {:<66} {:<12}",
+            revision.revision_id, revision.submitter, revision.created_at
+        )
+    });
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PurchaseOrderSlice {
+    pub purchase_order_uid: String,
+    pub workflow_status: String,
+    pub buyer_org_id: String,
+    pub seller_org_id: String,
+    pub is_closed: bool,
+    pub accepted_version_id: Option<String>,
+    pub versions: Vec<PurchaseOrderVersionSlice>,
+    pub created_at: i64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PurchaseOrderListSlice {
+    pub data: Vec<PurchaseOrderSlice>,
+    pub paging: Paging,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PurchaseOrderVersionSlice {
+    pub version_id: String,
+    pub workflow_status: String,
+    pub is_draft: bool,
+    pub current_revision_id: i64,
+    pub revisions: Vec<PurchaseOrderRevisionSlice>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PurchaseOrderRevisionSlice {
+    pub revision_id: i64,
+    pub order_xml_v3_4: String,
+    pub submitter: String,
+    pub created_at: i64,
+}
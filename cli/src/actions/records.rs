@@ -0,0 +1,188 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use grid_sdk::{
+    pike::addressing::PIKE_NAMESPACE,
+    protocol::track_and_trace::payload::{
+        Action, ReauthorizeReporterAction, RevokeReporterAction, TrackAndTracePayloadBuilder,
+    },
+    protos::IntoProto,
+    track_and_trace::addressing::TRACK_AND_TRACE_NAMESPACE,
+};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::error::CliError;
+use crate::http::submit_batches;
+use crate::transaction::track_and_trace_batch_builder;
+
+pub fn do_revoke_reporter(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    action: RevokeReporterAction,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    submit_payloads(
+        url,
+        key,
+        wait,
+        vec![Action::RevokeReporter(action)],
+        service_id,
+    )
+}
+
+pub fn do_reauthorize_reporter(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    action: ReauthorizeReporterAction,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    submit_payloads(
+        url,
+        key,
+        wait,
+        vec![Action::ReauthorizeReporter(action)],
+        service_id,
+    )
+}
+
+pub fn do_list_reporters(
+    url: &str,
+    record_id: &str,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    let client = Client::new();
+    let mut final_url = format!("{}/record/{}/reporters", url, record_id);
+    if let Some(service_id) = service_id {
+        final_url = format!("{}?service_id={}", final_url, service_id);
+    }
+
+    let mut response = client.get(&final_url).send()?;
+
+    if !response.status().is_success() {
+        return Err(CliError::DaemonError(response.text()?));
+    }
+
+    let reporters = response.json::<RecordReportersSlice>()?;
+
+    display_reporters(&reporters);
+
+    Ok(())
+}
+
+fn submit_payloads(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    actions: Vec<Action>,
+    service_id: Option<&str>,
+) -> Result<(), CliError> {
+    let mut builder = track_and_trace_batch_builder(key);
+
+    for action in actions {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .map_err(|err| CliError::PayloadError(format!("{}", err)))?;
+
+        let action = TrackAndTracePayloadBuilder::new()
+            .with_action(action)
+            .with_timestamp(timestamp)
+            .build()
+            .map_err(|err| CliError::PayloadError(format!("{}", err)))?;
+
+        builder.add_transaction(
+            &action.into_proto()?,
+            &[
+                PIKE_NAMESPACE.to_string(),
+                TRACK_AND_TRACE_NAMESPACE.to_string(),
+            ],
+            &[TRACK_AND_TRACE_NAMESPACE.to_string()],
+        )?;
+    }
+
+    let batches = builder.create_batch_list();
+
+    submit_batches(url, wait, &batches, service_id)
+}
+
+fn display_reporters(reporters: &RecordReportersSlice) {
+    println!("Record ID: {}", reporters.record_id);
+    reporters.properties.iter().for_each(|property| {
+        println!("Property: {}", property.name);
+        property.reporters.iter().for_each(|reporter| {
+            println!(
+                "\tReporter {} ({}): {}",
+                reporter.reporter_index,
+                reporter.public_key,
+                display_authorized(reporter.authorized)
+            );
+            reporter.history.iter().for_each(|period| {
+                let end = period
+                    .end_commit_num
+                    .map(|end| end.to_string())
+                    .unwrap_or_else(|| "current".to_string());
+                println!(
+                    "\t\t{:<12} commits {} to {}",
+                    display_authorized(period.authorized),
+                    period.start_commit_num,
+                    end
+                );
+            });
+        });
+    });
+}
+
+fn display_authorized(authorized: bool) -> &'static str {
+    if authorized {
+        "authorized"
+    } else {
+        "revoked"
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordReportersSlice {
+    pub record_id: String,
+    pub properties: Vec<PropertyReportersSlice>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PropertyReportersSlice {
+    pub name: String,
+    pub reporters: Vec<ReporterHistorySlice>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReporterHistorySlice {
+    pub public_key: String,
+    pub reporter_index: i32,
+    pub authorized: bool,
+    pub history: Vec<ReporterAuthorizationSlice>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReporterAuthorizationSlice {
+    pub authorized: bool,
+    pub start_commit_num: i64,
+    pub end_commit_num: Option<i64>,
+}
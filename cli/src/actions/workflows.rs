@@ -0,0 +1,62 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::CliError;
+
+use grid_sdk::workflow::{get_workflow, registered_workflows};
+
+pub fn do_list_workflows() {
+    for name in registered_workflows() {
+        println!("{}", name);
+    }
+}
+
+/// Prints a diagram of a registered workflow, or of one of its subworkflows
+///
+/// # Arguments
+///
+/// * `name` - The name the workflow is registered with
+/// * `subworkflow` - The subworkflow to print, or `None` to print every subworkflow
+/// * `format` - The diagram language, either `dot` or `mermaid`
+pub fn do_show_workflow(
+    name: &str,
+    subworkflow: Option<&str>,
+    format: &str,
+) -> Result<(), CliError> {
+    let workflow = get_workflow(name)
+        .ok_or_else(|| CliError::UserError(format!("Workflow {} is not registered", name)))?;
+
+    let diagram = match subworkflow {
+        Some(subworkflow_name) => {
+            let subworkflow = workflow.subworkflow(subworkflow_name).ok_or_else(|| {
+                CliError::UserError(format!(
+                    "Workflow {} has no subworkflow {}",
+                    name, subworkflow_name
+                ))
+            })?;
+            match format {
+                "mermaid" => subworkflow.to_mermaid(),
+                _ => subworkflow.to_dot(),
+            }
+        }
+        None => match format {
+            "mermaid" => workflow.to_mermaid(),
+            _ => workflow.to_dot(),
+        },
+    };
+
+    print!("{}", diagram);
+
+    Ok(())
+}
@@ -50,16 +50,26 @@ use grid_sdk::protocol::{
         },
         state::ProductNamespace,
     },
+    purchase_order::payload::{
+        CreatePurchaseOrderPayloadBuilder, CreateVersionPayloadBuilder,
+        UpdatePurchaseOrderPayloadBuilder, UpdateVersionPayloadBuilder,
+    },
     schema::state::{LatLongBuilder, PropertyValue, PropertyValueBuilder},
+    track_and_trace::payload::{ReauthorizeReporterActionBuilder, RevokeReporterActionBuilder},
 };
 use log::Record;
 
 use crate::error::CliError;
 
-use actions::{agents, database, keygen, locations, organizations as orgs, products, schemas};
+use actions::{
+    agents, database, keygen, locations, organizations as orgs, products, purchase_orders, records,
+    schemas,
+};
 
 #[cfg(feature = "admin-keygen")]
 use actions::admin;
+#[cfg(feature = "workflow")]
+use actions::workflows;
 
 const APP_NAME: &str = env!("CARGO_PKG_NAME");
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -813,6 +823,439 @@ fn run() -> Result<(), CliError> {
         );
     }
 
+    #[cfg(feature = "track-and-trace")]
+    {
+        use clap::{Arg, SubCommand};
+
+        app = app.subcommand(
+            SubCommand::with_name("record")
+                .about("Manage the reporters of track and trace records")
+                .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+                .arg(
+                    Arg::with_name("service_id")
+                        .long("service-id")
+                        .takes_value(true)
+                        .help(
+                            "The ID of the service the payload should be \
+                     sent to; required if running on Splinter. Format \
+                     <circuit-id>::<service-id>",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("url")
+                        .long("url")
+                        .takes_value(true)
+                        .help("URL for the REST API"),
+                )
+                .subcommand(
+                    SubCommand::with_name("reporters")
+                        .about("Show the authorization history of a record's reporters")
+                        .arg(
+                            Arg::with_name("record_id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Unique identifier for record"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("revoke-reporter")
+                        .about("Revoke a reporter's authorization to report on properties")
+                        .arg(
+                            Arg::with_name("record_id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Unique identifier for record"),
+                        )
+                        .arg(
+                            Arg::with_name("reporter_id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Public key of the reporter"),
+                        )
+                        .arg(
+                            Arg::with_name("property")
+                                .long("property")
+                                .takes_value(true)
+                                .multiple(true)
+                                .required(true)
+                                .help("Name of a property the reporter is revoked from"),
+                        )
+                        .arg(
+                            Arg::with_name("key")
+                                .long("key")
+                                .short("k")
+                                .takes_value(true)
+                                .help("Base name for private signing key file"),
+                        )
+                        .arg(
+                            Arg::with_name("wait")
+                                .long("wait")
+                                .takes_value(true)
+                                .help("How long to wait for transaction to be committed"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("reauthorize-reporter")
+                        .about(
+                            "Reauthorize a revoked reporter, keeping its original reporter index",
+                        )
+                        .arg(
+                            Arg::with_name("record_id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Unique identifier for record"),
+                        )
+                        .arg(
+                            Arg::with_name("reporter_id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Public key of the reporter"),
+                        )
+                        .arg(
+                            Arg::with_name("property")
+                                .long("property")
+                                .takes_value(true)
+                                .multiple(true)
+                                .required(true)
+                                .help("Name of a property the reporter is reauthorized for"),
+                        )
+                        .arg(
+                            Arg::with_name("key")
+                                .long("key")
+                                .short("k")
+                                .takes_value(true)
+                                .help("Base name for private signing key file"),
+                        )
+                        .arg(
+                            Arg::with_name("wait")
+                                .long("wait")
+                                .takes_value(true)
+                                .help("How long to wait for transaction to be committed"),
+                        ),
+                ),
+        );
+    }
+
+    #[cfg(feature = "purchase-order")]
+    {
+        use clap::{Arg, SubCommand};
+
+        app =
+            app.subcommand(
+                SubCommand::with_name("po")
+                    .about("Create, update or show purchase orders and their versions")
+                    .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+                    .arg(
+                        Arg::with_name("service_id")
+                            .long("service-id")
+                            .takes_value(true)
+                            .help(
+                                "The ID of the service the payload should be \
+                     sent to; required if running on Splinter. Format \
+                     <circuit-id>::<service-id>",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("url")
+                            .long("url")
+                            .takes_value(true)
+                            .help("URL for the REST API"),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("create")
+                            .about("Create a purchase order")
+                            .arg(
+                                Arg::with_name("uid")
+                                    .takes_value(true)
+                                    .required(true)
+                                    .help("Unique identifier for the purchase order"),
+                            )
+                            .arg(
+                                Arg::with_name("buyer_org")
+                                    .long("buyer-org")
+                                    .takes_value(true)
+                                    .required(true)
+                                    .help("Pike organization ID of the buyer"),
+                            )
+                            .arg(
+                                Arg::with_name("seller_org")
+                                    .long("seller-org")
+                                    .takes_value(true)
+                                    .required(true)
+                                    .help("Pike organization ID of the seller"),
+                            )
+                            .arg(
+                                Arg::with_name("workflow_status")
+                                    .long("workflow-status")
+                                    .takes_value(true)
+                                    .default_value("issued")
+                                    .help("Starting workflow state of the purchase order"),
+                            )
+                            .arg(
+                                Arg::with_name("key")
+                                    .long("key")
+                                    .short("k")
+                                    .takes_value(true)
+                                    .help("Base name for private signing key file"),
+                            )
+                            .arg(
+                                Arg::with_name("wait")
+                                    .long("wait")
+                                    .takes_value(true)
+                                    .help("How long to wait for transaction to be committed"),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("update")
+                            .about("Update a purchase order")
+                            .arg(
+                                Arg::with_name("uid")
+                                    .takes_value(true)
+                                    .required(true)
+                                    .help("Unique identifier for the purchase order"),
+                            )
+                            .arg(
+                                Arg::with_name("workflow_status")
+                                    .long("workflow-status")
+                                    .takes_value(true)
+                                    .required(true)
+                                    .help("Workflow state of the purchase order"),
+                            )
+                            .arg(
+                                Arg::with_name("close")
+                                    .long("close")
+                                    .help("Close the purchase order (requires the closed state)"),
+                            )
+                            .arg(
+                                Arg::with_name("accepted_version")
+                                    .long("accepted-version")
+                                    .takes_value(true)
+                                    .help("ID of the version accepted by the buyer and seller"),
+                            )
+                            .arg(
+                                Arg::with_name("key")
+                                    .long("key")
+                                    .short("k")
+                                    .takes_value(true)
+                                    .help("Base name for private signing key file"),
+                            )
+                            .arg(
+                                Arg::with_name("wait")
+                                    .long("wait")
+                                    .takes_value(true)
+                                    .help("How long to wait for transaction to be committed"),
+                            ),
+                    )
+                    .subcommand(SubCommand::with_name("list").about("List purchase orders"))
+                    .subcommand(
+                        SubCommand::with_name("show")
+                            .about("Show the purchase order specified by UID argument")
+                            .arg(
+                                Arg::with_name("uid")
+                                    .takes_value(true)
+                                    .required(true)
+                                    .help("Unique identifier for the purchase order"),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("version")
+                            .about("Create, update or list the versions of a purchase order")
+                            .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+                            .subcommand(
+                                SubCommand::with_name("create")
+                                    .about("Create a version of a purchase order")
+                                    .arg(
+                                        Arg::with_name("uid")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Unique identifier for the purchase order"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("version_id")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Identifier for the version"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("order_file")
+                                            .long("order-file")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Path to a GS1 Order XML v3.4 document"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("draft")
+                                            .long("draft")
+                                            .help("Mark the version as a draft"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("workflow_status")
+                                            .long("workflow-status")
+                                            .takes_value(true)
+                                            .default_value("proposed")
+                                            .help("Starting workflow state of the version"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("key")
+                                            .long("key")
+                                            .short("k")
+                                            .takes_value(true)
+                                            .help("Base name for private signing key file"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("wait").long("wait").takes_value(true).help(
+                                            "How long to wait for transaction to be committed",
+                                        ),
+                                    ),
+                            )
+                            .subcommand(
+                                SubCommand::with_name("update")
+                                    .about(
+                                        "Update a version of a purchase order, optionally adding \
+                                     a revision",
+                                    )
+                                    .arg(
+                                        Arg::with_name("uid")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Unique identifier for the purchase order"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("version_id")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Identifier for the version"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("workflow_status")
+                                            .long("workflow-status")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Workflow state of the version"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("order_file")
+                                            .long("order-file")
+                                            .takes_value(true)
+                                            .help(
+                                                "Path to a GS1 Order XML v3.4 document to add as \
+                                             the next revision",
+                                            ),
+                                    )
+                                    .arg(
+                                        Arg::with_name("draft")
+                                            .long("draft")
+                                            .help("Mark the version as a draft"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("key")
+                                            .long("key")
+                                            .short("k")
+                                            .takes_value(true)
+                                            .help("Base name for private signing key file"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("wait").long("wait").takes_value(true).help(
+                                            "How long to wait for transaction to be committed",
+                                        ),
+                                    ),
+                            )
+                            .subcommand(
+                                SubCommand::with_name("list")
+                                    .about("List the versions of a purchase order")
+                                    .arg(
+                                        Arg::with_name("uid")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Unique identifier for the purchase order"),
+                                    ),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("revision")
+                            .about("List or show the revisions of a purchase order version")
+                            .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+                            .subcommand(
+                                SubCommand::with_name("list")
+                                    .about("List the revisions of a version")
+                                    .arg(
+                                        Arg::with_name("uid")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Unique identifier for the purchase order"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("version_id")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Identifier for the version"),
+                                    ),
+                            )
+                            .subcommand(
+                                SubCommand::with_name("show")
+                                    .about("Show a revision of a version")
+                                    .arg(
+                                        Arg::with_name("uid")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Unique identifier for the purchase order"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("version_id")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Identifier for the version"),
+                                    )
+                                    .arg(
+                                        Arg::with_name("revision_id")
+                                            .takes_value(true)
+                                            .required(true)
+                                            .help("Identifier for the revision"),
+                                    ),
+                            ),
+                    ),
+            );
+    }
+
+    #[cfg(feature = "workflow")]
+    {
+        use clap::{Arg, SubCommand};
+
+        app = app.subcommand(
+            SubCommand::with_name("workflow")
+                .about("List registered workflows or print their state diagrams")
+                .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(SubCommand::with_name("list").about("List registered workflows"))
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about(
+                            "Print a diagram of a workflow, with the permission aliases \
+                             allowed on each transition",
+                        )
+                        .arg(
+                            Arg::with_name("name")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Name of the workflow"),
+                        )
+                        .arg(
+                            Arg::with_name("subworkflow")
+                                .long("subworkflow")
+                                .takes_value(true)
+                                .help("Only print the named subworkflow"),
+                        )
+                        .arg(
+                            Arg::with_name("format")
+                                .long("format")
+                                .takes_value(true)
+                                .possible_values(&["dot", "mermaid"])
+                                .default_value("dot")
+                                .help("Diagram language to print"),
+                        ),
+                ),
+        );
+    }
+
     let matches = app.get_matches();
 
     let log_level = if matches.is_present("quiet") {
@@ -1387,6 +1830,262 @@ fn run() -> Result<(), CliError> {
                 _ => return Err(CliError::UserError("Subcommand not recognized".into())),
             }
         }
+        ("record", Some(m)) => {
+            let url = m
+                .value_of("url")
+                .map(String::from)
+                .or_else(|| env::var(GRID_DAEMON_ENDPOINT).ok())
+                .unwrap_or_else(|| String::from("http://localhost:8000"));
+
+            let service_id = m
+                .value_of("service_id")
+                .map(String::from)
+                .or_else(|| env::var(GRID_SERVICE_ID).ok());
+
+            match m.subcommand() {
+                ("reporters", Some(m)) => records::do_list_reporters(
+                    &url,
+                    m.value_of("record_id").unwrap(),
+                    service_id.as_deref(),
+                )?,
+                ("revoke-reporter", Some(m)) => {
+                    let key = m
+                        .value_of("key")
+                        .map(String::from)
+                        .or_else(|| env::var(GRID_DAEMON_KEY).ok());
+
+                    let wait = value_t!(m, "wait", u64).unwrap_or(0);
+
+                    let action = RevokeReporterActionBuilder::new()
+                        .with_record_id(m.value_of("record_id").unwrap().into())
+                        .with_reporter_id(m.value_of("reporter_id").unwrap().into())
+                        .with_properties(
+                            m.values_of("property")
+                                .unwrap_or_default()
+                                .map(String::from)
+                                .collect(),
+                        )
+                        .build()
+                        .map_err(|err| CliError::UserError(format!("{}", err)))?;
+
+                    info!("Submitting request to revoke reporter...");
+                    records::do_revoke_reporter(&url, key, wait, action, service_id.as_deref())?;
+                }
+                ("reauthorize-reporter", Some(m)) => {
+                    let key = m
+                        .value_of("key")
+                        .map(String::from)
+                        .or_else(|| env::var(GRID_DAEMON_KEY).ok());
+
+                    let wait = value_t!(m, "wait", u64).unwrap_or(0);
+
+                    let action = ReauthorizeReporterActionBuilder::new()
+                        .with_record_id(m.value_of("record_id").unwrap().into())
+                        .with_reporter_id(m.value_of("reporter_id").unwrap().into())
+                        .with_properties(
+                            m.values_of("property")
+                                .unwrap_or_default()
+                                .map(String::from)
+                                .collect(),
+                        )
+                        .build()
+                        .map_err(|err| CliError::UserError(format!("{}", err)))?;
+
+                    info!("Submitting request to reauthorize reporter...");
+                    records::do_reauthorize_reporter(
+                        &url,
+                        key,
+                        wait,
+                        action,
+                        service_id.as_deref(),
+                    )?;
+                }
+                _ => return Err(CliError::UserError("Subcommand not recognized".into())),
+            }
+        }
+        ("po", Some(m)) => {
+            let url = m
+                .value_of("url")
+                .map(String::from)
+                .or_else(|| env::var(GRID_DAEMON_ENDPOINT).ok())
+                .unwrap_or_else(|| String::from("http://localhost:8000"));
+
+            let service_id = m
+                .value_of("service_id")
+                .map(String::from)
+                .or_else(|| env::var(GRID_SERVICE_ID).ok());
+
+            match m.subcommand() {
+                ("create", Some(m)) => {
+                    let key = m
+                        .value_of("key")
+                        .map(String::from)
+                        .or_else(|| env::var(GRID_DAEMON_KEY).ok());
+
+                    let wait = value_t!(m, "wait", u64).unwrap_or(0);
+
+                    let payload = CreatePurchaseOrderPayloadBuilder::new()
+                        .with_uid(m.value_of("uid").unwrap().into())
+                        .with_created_at(purchase_orders::current_timestamp()?)
+                        .with_buyer_org_id(m.value_of("buyer_org").unwrap().into())
+                        .with_seller_org_id(m.value_of("seller_org").unwrap().into())
+                        .with_workflow_status(m.value_of("workflow_status").unwrap().into())
+                        .build()
+                        .map_err(|err| CliError::UserError(format!("{}", err)))?;
+
+                    info!("Submitting request to create purchase order...");
+                    purchase_orders::do_create_purchase_order(
+                        &url,
+                        key,
+                        wait,
+                        payload,
+                        service_id.as_deref(),
+                    )?;
+                }
+                ("update", Some(m)) => {
+                    let key = m
+                        .value_of("key")
+                        .map(String::from)
+                        .or_else(|| env::var(GRID_DAEMON_KEY).ok());
+
+                    let wait = value_t!(m, "wait", u64).unwrap_or(0);
+
+                    let mut builder = UpdatePurchaseOrderPayloadBuilder::new()
+                        .with_uid(m.value_of("uid").unwrap().into())
+                        .with_workflow_status(m.value_of("workflow_status").unwrap().into())
+                        .with_is_closed(m.is_present("close"));
+                    if let Some(version_id) = m.value_of("accepted_version") {
+                        builder = builder.with_accepted_version_number(version_id.into());
+                    }
+                    let payload = builder
+                        .build()
+                        .map_err(|err| CliError::UserError(format!("{}", err)))?;
+
+                    info!("Submitting request to update purchase order...");
+                    purchase_orders::do_update_purchase_order(
+                        &url,
+                        key,
+                        wait,
+                        payload,
+                        service_id.as_deref(),
+                    )?;
+                }
+                ("list", Some(_)) => {
+                    purchase_orders::do_list_purchase_orders(&url, service_id.as_deref())?
+                }
+                ("show", Some(m)) => purchase_orders::do_show_purchase_order(
+                    &url,
+                    m.value_of("uid").unwrap(),
+                    service_id.as_deref(),
+                )?,
+                ("version", Some(m)) => match m.subcommand() {
+                    ("create", Some(m)) => {
+                        let key = m
+                            .value_of("key")
+                            .map(String::from)
+                            .or_else(|| env::var(GRID_DAEMON_KEY).ok());
+
+                        let wait = value_t!(m, "wait", u64).unwrap_or(0);
+
+                        let payload = CreateVersionPayloadBuilder::new()
+                            .with_version_id(m.value_of("version_id").unwrap().into())
+                            .with_po_uid(m.value_of("uid").unwrap().into())
+                            .with_is_draft(m.is_present("draft"))
+                            .with_workflow_status(m.value_of("workflow_status").unwrap().into())
+                            .with_revision(purchase_orders::make_revision(
+                                1,
+                                m.value_of("order_file").unwrap(),
+                            )?)
+                            .build()
+                            .map_err(|err| CliError::UserError(format!("{}", err)))?;
+
+                        info!("Submitting request to create purchase order version...");
+                        purchase_orders::do_create_version(
+                            &url,
+                            key,
+                            wait,
+                            payload,
+                            service_id.as_deref(),
+                        )?;
+                    }
+                    ("update", Some(m)) => {
+                        let key = m
+                            .value_of("key")
+                            .map(String::from)
+                            .or_else(|| env::var(GRID_DAEMON_KEY).ok());
+
+                        let wait = value_t!(m, "wait", u64).unwrap_or(0);
+
+                        let uid = m.value_of("uid").unwrap();
+                        let version_id = m.value_of("version_id").unwrap();
+
+                        let mut builder = UpdateVersionPayloadBuilder::new()
+                            .with_version_id(version_id.into())
+                            .with_po_uid(uid.into())
+                            .with_workflow_status(m.value_of("workflow_status").unwrap().into())
+                            .with_is_draft(m.is_present("draft"));
+                        if let Some(order_file) = m.value_of("order_file") {
+                            // New revisions must directly follow the current revision
+                            let version = purchase_orders::get_version(
+                                &url,
+                                uid,
+                                version_id,
+                                service_id.as_deref(),
+                            )?;
+                            builder = builder.with_revision(purchase_orders::make_revision(
+                                version.current_revision_id as u64 + 1,
+                                order_file,
+                            )?);
+                        }
+                        let payload = builder
+                            .build()
+                            .map_err(|err| CliError::UserError(format!("{}", err)))?;
+
+                        info!("Submitting request to update purchase order version...");
+                        purchase_orders::do_update_version(
+                            &url,
+                            key,
+                            wait,
+                            payload,
+                            service_id.as_deref(),
+                        )?;
+                    }
+                    ("list", Some(m)) => purchase_orders::do_list_versions(
+                        &url,
+                        m.value_of("uid").unwrap(),
+                        service_id.as_deref(),
+                    )?,
+                    _ => return Err(CliError::UserError("Subcommand not recognized".into())),
+                },
+                ("revision", Some(m)) => match m.subcommand() {
+                    ("list", Some(m)) => purchase_orders::do_list_revisions(
+                        &url,
+                        m.value_of("uid").unwrap(),
+                        m.value_of("version_id").unwrap(),
+                        service_id.as_deref(),
+                    )?,
+                    ("show", Some(m)) => purchase_orders::do_show_revision(
+                        &url,
+                        m.value_of("uid").unwrap(),
+                        m.value_of("version_id").unwrap(),
+                        m.value_of("revision_id").unwrap(),
+                        service_id.as_deref(),
+                    )?,
+                    _ => return Err(CliError::UserError("Subcommand not recognized".into())),
+                },
+                _ => return Err(CliError::UserError("Subcommand not recognized".into())),
+            }
+        }
+        #[cfg(feature = "workflow")]
+        ("workflow", Some(m)) => match m.subcommand() {
+            ("list", Some(_)) => workflows::do_list_workflows(),
+            ("show", Some(m)) => workflows::do_show_workflow(
+                m.value_of("name").unwrap(),
+                m.value_of("subworkflow"),
+                m.value_of("format").unwrap(),
+            )?,
+            _ => return Err(CliError::UserError("Subcommand not recognized".into())),
+        },
         _ => return Err(CliError::UserError("Subcommand not recognized".into())),
     }
 
@@ -44,6 +44,12 @@ const GRID_SCHEMA_FAMILY_VERSION: &str = "1";
 const GRID_PRODUCT_FAMILY_NAME: &str = "grid_product";
 const GRID_PRODUCT_FAMILY_VERSION: &str = "1";
 
+const GRID_PURCHASE_ORDER_FAMILY_NAME: &str = "grid_purchase_order";
+const GRID_PURCHASE_ORDER_FAMILY_VERSION: &str = "1";
+
+const GRID_TRACK_AND_TRACE_FAMILY_NAME: &str = "grid_track_and_trace";
+const GRID_TRACK_AND_TRACE_FAMILY_VERSION: &str = "1";
+
 const SABRE_FAMILY_NAME: &str = "sabre";
 const SABRE_FAMILY_VERSION: &str = "0.5";
 const SABRE_NAMESPACE_REGISTRY_PREFIX: &str = "00ec00";
@@ -66,6 +72,22 @@ pub fn location_batch_builder(key: Option<String>) -> BatchBuilder {
     BatchBuilder::new("grid_location", "1", key)
 }
 
+pub fn purchase_order_batch_builder(key: Option<String>) -> BatchBuilder {
+    BatchBuilder::new(
+        GRID_PURCHASE_ORDER_FAMILY_NAME,
+        GRID_PURCHASE_ORDER_FAMILY_VERSION,
+        key,
+    )
+}
+
+pub fn track_and_trace_batch_builder(key: Option<String>) -> BatchBuilder {
+    BatchBuilder::new(
+        GRID_TRACK_AND_TRACE_FAMILY_NAME,
+        GRID_TRACK_AND_TRACE_FAMILY_VERSION,
+        key,
+    )
+}
+
 #[derive(Clone)]
 pub struct BatchBuilder {
     family_name: String,
@@ -0,0 +1,1054 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        use sabre_sdk::ApplyError;
+        use sabre_sdk::TransactionContext;
+        use sabre_sdk::TransactionHandler;
+        use sabre_sdk::TpProcessRequest;
+        use sabre_sdk::{WasmPtr, execute_entrypoint};
+    } else {
+        use sawtooth_sdk::processor::handler::ApplyError;
+        use sawtooth_sdk::processor::handler::TransactionContext;
+        use sawtooth_sdk::processor::handler::TransactionHandler;
+        use sawtooth_sdk::messages::processor::TpProcessRequest;
+    }
+}
+
+use grid_sdk::{
+    protocol::purchase_order::{
+        payload::{
+            Action, CreatePurchaseOrderPayload, CreateVersionPayload, PayloadRevision,
+            PurchaseOrderPayload, UpdatePurchaseOrderPayload, UpdateVersionPayload,
+        },
+        state::{
+            PurchaseOrderBuilder, PurchaseOrderRevision, PurchaseOrderRevisionBuilder,
+            PurchaseOrderVersionBuilder,
+        },
+    },
+    protos::FromBytes,
+    purchase_order::{
+        addressing::GRID_PURCHASE_ORDER_NAMESPACE,
+        workflow::{
+            purchase_order_aliases, purchase_order_workflow, PERMISSION_CREATE_PO,
+            PERMISSION_CREATE_VERSION, PERMISSION_UPDATE_PO, PERMISSION_UPDATE_VERSION,
+            PO_SUBWORKFLOW, VERSION_SUBWORKFLOW,
+        },
+    },
+    workflow::{ConstraintFields, SubWorkflow, WorkflowState},
+};
+
+use crate::state::PurchaseOrderState;
+
+/// The `po` state that closes a purchase order
+const CLOSED_STATE: &str = "closed";
+/// The `version` state of a version that may be recorded as the accepted version
+const ACCEPTED_STATE: &str = "accepted";
+
+#[cfg(target_arch = "wasm32")]
+fn apply(
+    request: &TpProcessRequest,
+    context: &mut dyn TransactionContext,
+) -> Result<bool, ApplyError> {
+    let handler = PurchaseOrderTransactionHandler::new();
+    match handler.apply(request, context) {
+        Ok(_) => Ok(true),
+        Err(err) => {
+            info!("{} received {}", handler.family_name(), err);
+            Err(err)
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[no_mangle]
+pub unsafe fn entrypoint(payload: WasmPtr, signer: WasmPtr, signature: WasmPtr) -> i32 {
+    execute_entrypoint(payload, signer, signature, apply)
+}
+
+#[derive(Default)]
+pub struct PurchaseOrderTransactionHandler {
+    family_name: String,
+    family_versions: Vec<String>,
+    namespaces: Vec<String>,
+}
+
+impl PurchaseOrderTransactionHandler {
+    pub fn new() -> Self {
+        Self {
+            family_name: "grid_purchase_order".to_string(),
+            family_versions: vec!["1".to_string()],
+            namespaces: vec![GRID_PURCHASE_ORDER_NAMESPACE.to_string()],
+        }
+    }
+}
+
+impl TransactionHandler for PurchaseOrderTransactionHandler {
+    fn family_name(&self) -> String {
+        self.family_name.clone()
+    }
+
+    fn family_versions(&self) -> Vec<String> {
+        self.family_versions.clone()
+    }
+
+    fn namespaces(&self) -> Vec<String> {
+        self.namespaces.clone()
+    }
+
+    fn apply(
+        &self,
+        request: &TpProcessRequest,
+        context: &mut dyn TransactionContext,
+    ) -> Result<(), ApplyError> {
+        let payload = PurchaseOrderPayload::from_bytes(request.get_payload()).map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build purchase order payload: {}", err))
+        })?;
+
+        info!(
+            "Purchase Order Payload {:?} {}",
+            payload.action(),
+            payload.timestamp(),
+        );
+
+        let signer = request.get_header().get_signer_public_key();
+        let mut state = PurchaseOrderState::new(context);
+
+        match payload.action() {
+            Action::CreatePo(payload) => create_purchase_order(&payload, &mut state, signer)?,
+            Action::UpdatePo(payload) => update_purchase_order(&payload, &mut state, signer)?,
+            Action::CreateVersion(payload) => create_version(&payload, &mut state, signer)?,
+            Action::UpdateVersion(payload) => update_version(&payload, &mut state, signer)?,
+        }
+        Ok(())
+    }
+}
+
+fn create_purchase_order(
+    payload: &CreatePurchaseOrderPayload,
+    state: &mut PurchaseOrderState,
+    signer: &str,
+) -> Result<(), ApplyError> {
+    if payload.uid().is_empty() {
+        return Err(ApplyError::InvalidTransaction(
+            "Purchase order uid cannot be empty".into(),
+        ));
+    }
+
+    // check if purchase order already exists
+    if state.get_purchase_order(payload.uid())?.is_some() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "A purchase order with uid {} already exists",
+            payload.uid()
+        )));
+    }
+
+    // check if the buyer and seller organizations exist
+    for org_id in &[payload.buyer_org_id(), payload.seller_org_id()] {
+        if state.get_organization(org_id)?.is_none() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Organization {} is not registered with Pike",
+                org_id
+            )));
+        }
+    }
+
+    let aliases = get_signer_aliases(
+        state,
+        signer,
+        payload.buyer_org_id(),
+        payload.seller_org_id(),
+    )?;
+
+    let subworkflow = get_subworkflow(PO_SUBWORKFLOW)?;
+    if !subworkflow
+        .starting_states()
+        .iter()
+        .any(|s| s == payload.workflow_status())
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "{} is not a starting state of the purchase order workflow",
+            payload.workflow_status()
+        )));
+    }
+
+    let workflow_state = get_workflow_state(&subworkflow, payload.workflow_status())?;
+    check_permission(&workflow_state, &aliases, PERMISSION_CREATE_PO, signer)?;
+
+    let purchase_order = PurchaseOrderBuilder::new()
+        .with_uid(payload.uid().to_string())
+        .with_workflow_status(payload.workflow_status().to_string())
+        .with_created_at(payload.created_at())
+        .with_buyer_org_id(payload.buyer_org_id().to_string())
+        .with_seller_org_id(payload.seller_org_id().to_string())
+        .build()
+        .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+
+    check_constraints(&workflow_state, &purchase_order)?;
+
+    state.set_purchase_order(purchase_order)
+}
+
+fn update_purchase_order(
+    payload: &UpdatePurchaseOrderPayload,
+    state: &mut PurchaseOrderState,
+    signer: &str,
+) -> Result<(), ApplyError> {
+    let purchase_order = if let Some(purchase_order) = state.get_purchase_order(payload.uid())? {
+        purchase_order
+    } else {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "A purchase order with uid {} does not exist",
+            payload.uid()
+        )));
+    };
+
+    if purchase_order.is_closed() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Purchase order {} is closed",
+            payload.uid()
+        )));
+    }
+
+    let aliases = get_signer_aliases(
+        state,
+        signer,
+        purchase_order.buyer_org_id(),
+        purchase_order.seller_org_id(),
+    )?;
+
+    let subworkflow = get_subworkflow(PO_SUBWORKFLOW)?;
+    let workflow_state = get_workflow_state(&subworkflow, purchase_order.workflow_status())?;
+    check_permission(&workflow_state, &aliases, PERMISSION_UPDATE_PO, signer)?;
+
+    if payload.workflow_status() != purchase_order.workflow_status()
+        && !workflow_state.can_transition(payload.workflow_status().to_string(), aliases)
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Agent {} cannot transition purchase order {} from {} to {}",
+            signer,
+            payload.uid(),
+            purchase_order.workflow_status(),
+            payload.workflow_status()
+        )));
+    }
+
+    let is_closed = payload.workflow_status() == CLOSED_STATE;
+    if payload.is_closed() != is_closed {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Purchase order {} may only be closed by entering the {} state",
+            payload.uid(),
+            CLOSED_STATE
+        )));
+    }
+
+    if let Some(version_id) = payload.accepted_version_number() {
+        let version = if let Some(version) = purchase_order
+            .versions()
+            .iter()
+            .find(|v| v.version_id() == version_id)
+        {
+            version
+        } else {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Purchase order {} does not have a version {}",
+                payload.uid(),
+                version_id
+            )));
+        };
+
+        if purchase_order.accepted_version_number() != Some(version_id)
+            && version.workflow_status() != ACCEPTED_STATE
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Version {} of purchase order {} has not been accepted",
+                version_id,
+                payload.uid()
+            )));
+        }
+    }
+
+    let mut builder = purchase_order
+        .clone()
+        .into_builder()
+        .with_workflow_status(payload.workflow_status().to_string())
+        .with_is_closed(is_closed);
+    if let Some(version_id) = payload.accepted_version_number() {
+        builder = builder.with_accepted_version_number(version_id.to_string());
+    }
+
+    let updated_purchase_order = builder
+        .build()
+        .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+
+    if payload.workflow_status() != purchase_order.workflow_status() {
+        let new_state = get_workflow_state(&subworkflow, payload.workflow_status())?;
+        check_constraints(&new_state, &updated_purchase_order)?;
+    }
+
+    state.set_purchase_order(updated_purchase_order)
+}
+
+fn create_version(
+    payload: &CreateVersionPayload,
+    state: &mut PurchaseOrderState,
+    signer: &str,
+) -> Result<(), ApplyError> {
+    let purchase_order = if let Some(purchase_order) = state.get_purchase_order(payload.po_uid())? {
+        purchase_order
+    } else {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "A purchase order with uid {} does not exist",
+            payload.po_uid()
+        )));
+    };
+
+    if purchase_order.is_closed() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Purchase order {} is closed",
+            payload.po_uid()
+        )));
+    }
+
+    let aliases = get_signer_aliases(
+        state,
+        signer,
+        purchase_order.buyer_org_id(),
+        purchase_order.seller_org_id(),
+    )?;
+
+    let po_subworkflow = get_subworkflow(PO_SUBWORKFLOW)?;
+    let po_state = get_workflow_state(&po_subworkflow, purchase_order.workflow_status())?;
+    check_permission(&po_state, &aliases, PERMISSION_CREATE_VERSION, signer)?;
+
+    if payload.version_id().is_empty() {
+        return Err(ApplyError::InvalidTransaction(
+            "Version id cannot be empty".into(),
+        ));
+    }
+
+    if purchase_order
+        .versions()
+        .iter()
+        .any(|v| v.version_id() == payload.version_id())
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Purchase order {} already has a version {}",
+            payload.po_uid(),
+            payload.version_id()
+        )));
+    }
+
+    let version_subworkflow = get_subworkflow(VERSION_SUBWORKFLOW)?;
+    if !version_subworkflow
+        .starting_states()
+        .iter()
+        .any(|s| s == payload.workflow_status())
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "{} is not a starting state of the version workflow",
+            payload.workflow_status()
+        )));
+    }
+
+    if payload.revision().revision_id() != 1 {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "The first revision of version {} must have id 1",
+            payload.version_id()
+        )));
+    }
+
+    let revision = build_revision(payload.revision(), signer)?;
+
+    let version = PurchaseOrderVersionBuilder::new()
+        .with_version_id(payload.version_id().to_string())
+        .with_workflow_status(payload.workflow_status().to_string())
+        .with_is_draft(payload.is_draft())
+        .with_current_revision_id(revision.revision_id())
+        .with_revisions(vec![revision])
+        .build()
+        .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+
+    let version_state = get_workflow_state(&version_subworkflow, payload.workflow_status())?;
+    check_constraints(&version_state, &version)?;
+
+    let mut versions = purchase_order.versions().to_vec();
+    versions.push(version);
+
+    let purchase_order = purchase_order
+        .into_builder()
+        .with_versions(versions)
+        .build()
+        .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+
+    state.set_purchase_order(purchase_order)
+}
+
+fn update_version(
+    payload: &UpdateVersionPayload,
+    state: &mut PurchaseOrderState,
+    signer: &str,
+) -> Result<(), ApplyError> {
+    let purchase_order = if let Some(purchase_order) = state.get_purchase_order(payload.po_uid())? {
+        purchase_order
+    } else {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "A purchase order with uid {} does not exist",
+            payload.po_uid()
+        )));
+    };
+
+    if purchase_order.is_closed() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Purchase order {} is closed",
+            payload.po_uid()
+        )));
+    }
+
+    let version = if let Some(version) = purchase_order
+        .versions()
+        .iter()
+        .find(|v| v.version_id() == payload.version_id())
+    {
+        version.clone()
+    } else {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Purchase order {} does not have a version {}",
+            payload.po_uid(),
+            payload.version_id()
+        )));
+    };
+
+    let aliases = get_signer_aliases(
+        state,
+        signer,
+        purchase_order.buyer_org_id(),
+        purchase_order.seller_org_id(),
+    )?;
+
+    let po_subworkflow = get_subworkflow(PO_SUBWORKFLOW)?;
+    let po_state = get_workflow_state(&po_subworkflow, purchase_order.workflow_status())?;
+    check_permission(&po_state, &aliases, PERMISSION_UPDATE_VERSION, signer)?;
+
+    let subworkflow = get_subworkflow(VERSION_SUBWORKFLOW)?;
+    let workflow_state = get_workflow_state(&subworkflow, version.workflow_status())?;
+    check_permission(&workflow_state, &aliases, PERMISSION_UPDATE_VERSION, signer)?;
+
+    if payload.workflow_status() != version.workflow_status()
+        && !workflow_state.can_transition(payload.workflow_status().to_string(), aliases)
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Agent {} cannot transition version {} from {} to {}",
+            signer,
+            payload.version_id(),
+            version.workflow_status(),
+            payload.workflow_status()
+        )));
+    }
+
+    let mut current_revision_id = version.current_revision_id();
+    let mut revisions = version.revisions().to_vec();
+    if let Some(revision) = payload.revision() {
+        if revision.revision_id() != current_revision_id + 1 {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Revision {} of version {} must follow revision {}",
+                revision.revision_id(),
+                payload.version_id(),
+                current_revision_id
+            )));
+        }
+        current_revision_id = revision.revision_id();
+        revisions.push(build_revision(revision, signer)?);
+    }
+
+    let updated_version = version
+        .clone()
+        .into_builder()
+        .with_workflow_status(payload.workflow_status().to_string())
+        .with_is_draft(payload.is_draft())
+        .with_current_revision_id(current_revision_id)
+        .with_revisions(revisions)
+        .build()
+        .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+
+    if payload.workflow_status() != version.workflow_status() {
+        let new_state = get_workflow_state(&subworkflow, payload.workflow_status())?;
+        check_constraints(&new_state, &updated_version)?;
+    }
+
+    let versions = purchase_order
+        .versions()
+        .iter()
+        .map(|v| {
+            if v.version_id() == payload.version_id() {
+                updated_version.clone()
+            } else {
+                v.clone()
+            }
+        })
+        .collect();
+
+    let purchase_order = purchase_order
+        .into_builder()
+        .with_versions(versions)
+        .build()
+        .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+
+    state.set_purchase_order(purchase_order)
+}
+
+/// Returns the workflow roles held by the signer that apply to the given purchase order
+fn get_signer_aliases(
+    state: &PurchaseOrderState,
+    signer: &str,
+    buyer_org_id: &str,
+    seller_org_id: &str,
+) -> Result<Vec<String>, ApplyError> {
+    let agent = if let Some(agent) = state.get_agent(signer)? {
+        agent
+    } else {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "The signer is not an agent: {}",
+            signer
+        )));
+    };
+
+    if !agent.active() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Agent {} is not active",
+            signer
+        )));
+    }
+
+    Ok(purchase_order_aliases(
+        agent.org_id(),
+        agent.roles(),
+        buyer_org_id,
+        seller_org_id,
+    ))
+}
+
+fn get_subworkflow(name: &str) -> Result<SubWorkflow, ApplyError> {
+    purchase_order_workflow().subworkflow(name).ok_or_else(|| {
+        ApplyError::InternalError(format!("Workflow does not define subworkflow {}", name))
+    })
+}
+
+fn get_workflow_state(subworkflow: &SubWorkflow, name: &str) -> Result<WorkflowState, ApplyError> {
+    subworkflow.state(name).ok_or_else(|| {
+        ApplyError::InvalidTransaction(format!(
+            "{} is not a state of the {} workflow",
+            name,
+            subworkflow.name()
+        ))
+    })
+}
+
+fn check_permission(
+    workflow_state: &WorkflowState,
+    aliases: &[String],
+    permission: &str,
+    signer: &str,
+) -> Result<(), ApplyError> {
+    if workflow_state
+        .expand_permissions(aliases)
+        .iter()
+        .any(|p| p == permission)
+    {
+        Ok(())
+    } else {
+        Err(ApplyError::InvalidTransaction(format!(
+            "Agent {} does not have permission {} in state {}",
+            signer,
+            permission,
+            workflow_state.name()
+        )))
+    }
+}
+
+/// Checks that an entity entering a workflow state satisfies the state's constraints
+fn check_constraints(
+    workflow_state: &WorkflowState,
+    entity: &dyn ConstraintFields,
+) -> Result<(), ApplyError> {
+    workflow_state.check_constraints(entity).map_err(|err| {
+        ApplyError::InvalidTransaction(format!(
+            "Cannot enter state {}: {}",
+            workflow_state.name(),
+            err
+        ))
+    })
+}
+
+fn build_revision(
+    revision: &PayloadRevision,
+    signer: &str,
+) -> Result<PurchaseOrderRevision, ApplyError> {
+    PurchaseOrderRevisionBuilder::new()
+        .with_revision_id(revision.revision_id())
+        .with_submitter(signer.to_string())
+        .with_created_at(revision.created_at())
+        .with_order_xml_v3_4(revision.order_xml_v3_4().to_string())
+        .build()
+        .map_err(|err| ApplyError::InternalError(format!("{}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use grid_sdk::{
+        pike::addressing::{compute_agent_address, compute_organization_address},
+        protocol::{
+            pike::state::{
+                AgentBuilder, AgentListBuilder, OrganizationBuilder, OrganizationListBuilder,
+            },
+            purchase_order::payload::{
+                CreatePurchaseOrderPayloadBuilder, CreateVersionPayloadBuilder,
+                PayloadRevisionBuilder, UpdatePurchaseOrderPayloadBuilder,
+                UpdateVersionPayloadBuilder,
+            },
+        },
+        protos::IntoBytes,
+        purchase_order::workflow::{BUYER_ALIAS, SELLER_ALIAS},
+    };
+
+    use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
+
+    const PO_UID: &str = "PO-00000001";
+
+    #[derive(Default, Debug)]
+    struct MockTransactionContext {
+        state: RefCell<HashMap<String, Vec<u8>>>,
+    }
+
+    impl MockTransactionContext {
+        fn new() -> Self {
+            let mut entries = Vec::new();
+
+            for org_id in &["buyer_org", "seller_org"] {
+                let org = OrganizationBuilder::new()
+                    .with_org_id(org_id.to_string())
+                    .with_name("test_org_name".to_string())
+                    .with_address("test_org_address".to_string())
+                    .build()
+                    .unwrap();
+                let org_list = OrganizationListBuilder::new()
+                    .with_organizations(vec![org])
+                    .build()
+                    .unwrap();
+                entries.push((
+                    compute_organization_address(org_id),
+                    org_list.into_bytes().unwrap(),
+                ));
+            }
+
+            // the rogue agent belongs to the seller organization but claims the buyer role
+            for (public_key, org_id, role) in &[
+                ("buyer_agent", "buyer_org", BUYER_ALIAS),
+                ("seller_agent", "seller_org", SELLER_ALIAS),
+                ("rogue_agent", "seller_org", BUYER_ALIAS),
+            ] {
+                let agent = AgentBuilder::new()
+                    .with_org_id(org_id.to_string())
+                    .with_public_key(public_key.to_string())
+                    .with_active(true)
+                    .with_roles(vec![role.to_string()])
+                    .build()
+                    .unwrap();
+                let agent_list = AgentListBuilder::new()
+                    .with_agents(vec![agent])
+                    .build()
+                    .unwrap();
+                entries.push((
+                    compute_agent_address(public_key),
+                    agent_list.into_bytes().unwrap(),
+                ));
+            }
+
+            let mock = MockTransactionContext::default();
+            mock.set_state_entries(entries).unwrap();
+
+            mock
+        }
+    }
+
+    impl TransactionContext for MockTransactionContext {
+        fn get_state_entries(
+            &self,
+            addresses: &[String],
+        ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
+            let mut results = Vec::new();
+            for addr in addresses {
+                let data = match self.state.borrow().get(addr) {
+                    Some(data) => data.clone(),
+                    None => Vec::new(),
+                };
+                results.push((addr.to_string(), data));
+            }
+            Ok(results)
+        }
+
+        fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
+            for (addr, data) in entries {
+                self.state.borrow_mut().insert(addr, data);
+            }
+            Ok(())
+        }
+
+        /// this is not needed for these tests
+        fn delete_state_entries(&self, _addresses: &[String]) -> Result<Vec<String>, ContextError> {
+            unimplemented!()
+        }
+
+        /// this is not needed for these tests
+        fn add_receipt_data(&self, _data: &[u8]) -> Result<(), ContextError> {
+            unimplemented!()
+        }
+
+        /// this is not needed for these tests
+        fn add_event(
+            &self,
+            _event_type: String,
+            _attributes: Vec<(String, String)>,
+            _data: &[u8],
+        ) -> Result<(), ContextError> {
+            unimplemented!()
+        }
+    }
+
+    fn create_payload(workflow_status: &str) -> CreatePurchaseOrderPayload {
+        CreatePurchaseOrderPayloadBuilder::new()
+            .with_uid(PO_UID.to_string())
+            .with_created_at(1)
+            .with_buyer_org_id("buyer_org".to_string())
+            .with_seller_org_id("seller_org".to_string())
+            .with_workflow_status(workflow_status.to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn update_payload(workflow_status: &str) -> UpdatePurchaseOrderPayload {
+        UpdatePurchaseOrderPayloadBuilder::new()
+            .with_uid(PO_UID.to_string())
+            .with_workflow_status(workflow_status.to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn revision(revision_id: u64) -> PayloadRevision {
+        PayloadRevisionBuilder::new()
+            .with_revision_id(revision_id)
+            .with_created_at(revision_id)
+            .with_order_xml_v3_4(format!("<order revision=\"{}\"/>", revision_id))
+            .build()
+            .unwrap()
+    }
+
+    fn create_version_payload(version_id: &str) -> CreateVersionPayload {
+        CreateVersionPayloadBuilder::new()
+            .with_version_id(version_id.to_string())
+            .with_po_uid(PO_UID.to_string())
+            .with_is_draft(false)
+            .with_workflow_status("proposed".to_string())
+            .with_revision(revision(1))
+            .build()
+            .unwrap()
+    }
+
+    fn update_version_payload(version_id: &str, workflow_status: &str) -> UpdateVersionPayload {
+        UpdateVersionPayloadBuilder::new()
+            .with_version_id(version_id.to_string())
+            .with_po_uid(PO_UID.to_string())
+            .with_workflow_status(workflow_status.to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn create_default_purchase_order(state: &mut PurchaseOrderState) {
+        create_purchase_order(&create_payload("issued"), state, "buyer_agent")
+            .expect("Unable to create purchase order");
+    }
+
+    #[test]
+    /// Test that a buyer agent can create a purchase order in a starting state
+    fn test_create_purchase_order_valid() {
+        let mock_context = MockTransactionContext::new();
+        let mut state = PurchaseOrderState::new(&mock_context);
+
+        create_default_purchase_order(&mut state);
+
+        let purchase_order = state
+            .get_purchase_order(PO_UID)
+            .unwrap()
+            .expect("Purchase order was not stored");
+        assert_eq!(purchase_order.workflow_status(), "issued");
+        assert_eq!(purchase_order.buyer_org_id(), "buyer_org");
+        assert!(!purchase_order.is_closed());
+    }
+
+    #[test]
+    /// Test that the seller cannot create a purchase order, and that a buyer role held by an
+    /// agent outside the buyer organization is ignored
+    fn test_create_purchase_order_no_permission() {
+        let mock_context = MockTransactionContext::new();
+        let mut state = PurchaseOrderState::new(&mock_context);
+
+        for signer in &["seller_agent", "rogue_agent"] {
+            match create_purchase_order(&create_payload("issued"), &mut state, signer) {
+                Ok(_) => panic!("{} should not be able to create a purchase order", signer),
+                Err(ApplyError::InvalidTransaction(err)) => assert!(err.contains(&format!(
+                    "Agent {} does not have permission po.create",
+                    signer
+                ))),
+                Err(err) => panic!("Unexpected error {}", err),
+            }
+        }
+    }
+
+    #[test]
+    /// Test that a purchase order cannot be created in a state that is not a starting state, nor
+    /// created twice
+    fn test_create_purchase_order_invalid() {
+        let mock_context = MockTransactionContext::new();
+        let mut state = PurchaseOrderState::new(&mock_context);
+
+        assert!(
+            create_purchase_order(&create_payload("confirmed"), &mut state, "buyer_agent").is_err()
+        );
+
+        create_default_purchase_order(&mut state);
+        assert!(
+            create_purchase_order(&create_payload("issued"), &mut state, "buyer_agent").is_err()
+        );
+    }
+
+    #[test]
+    /// Test that only the seller can confirm an issued purchase order, and that once closed it
+    /// rejects further changes
+    fn test_update_purchase_order_transitions() {
+        let mock_context = MockTransactionContext::new();
+        let mut state = PurchaseOrderState::new(&mock_context);
+        create_default_purchase_order(&mut state);
+
+        assert!(
+            update_purchase_order(&update_payload("confirmed"), &mut state, "buyer_agent").is_err()
+        );
+        update_purchase_order(&update_payload("confirmed"), &mut state, "seller_agent")
+            .expect("Seller should be able to confirm");
+        assert_eq!(
+            state
+                .get_purchase_order(PO_UID)
+                .unwrap()
+                .unwrap()
+                .workflow_status(),
+            "confirmed"
+        );
+
+        let close = UpdatePurchaseOrderPayloadBuilder::new()
+            .with_uid(PO_UID.to_string())
+            .with_workflow_status("closed".to_string())
+            .with_is_closed(true)
+            .build()
+            .unwrap();
+        update_purchase_order(&close, &mut state, "buyer_agent")
+            .expect("Buyer should be able to close");
+
+        match create_version(&create_version_payload("1"), &mut state, "buyer_agent") {
+            Ok(_) => panic!("A closed purchase order should not accept new versions"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert_eq!(err, format!("Purchase order {} is closed", PO_UID))
+            }
+            Err(err) => panic!("Unexpected error {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a purchase order is closed only by entering the closed state, so the seller
+    /// cannot close an issued purchase order
+    fn test_update_purchase_order_is_closed() {
+        let mock_context = MockTransactionContext::new();
+        let mut state = PurchaseOrderState::new(&mock_context);
+        create_default_purchase_order(&mut state);
+
+        let payload = |workflow_status: &str, is_closed| {
+            UpdatePurchaseOrderPayloadBuilder::new()
+                .with_uid(PO_UID.to_string())
+                .with_workflow_status(workflow_status.to_string())
+                .with_is_closed(is_closed)
+                .build()
+                .unwrap()
+        };
+
+        for (workflow_status, is_closed, signer) in &[
+            ("issued", true, "seller_agent"),
+            ("closed", false, "buyer_agent"),
+        ] {
+            match update_purchase_order(&payload(workflow_status, *is_closed), &mut state, signer) {
+                Ok(_) => panic!("is_closed must agree with the {} state", workflow_status),
+                Err(ApplyError::InvalidTransaction(err)) => assert_eq!(
+                    err,
+                    format!(
+                        "Purchase order {} may only be closed by entering the closed state",
+                        PO_UID
+                    )
+                ),
+                Err(err) => panic!("Unexpected error {}", err),
+            }
+        }
+
+        let purchase_order = state.get_purchase_order(PO_UID).unwrap().unwrap();
+        assert_eq!(purchase_order.workflow_status(), "issued");
+        assert!(!purchase_order.is_closed());
+    }
+
+    #[test]
+    /// Test that a version must be accepted before the purchase order records it as accepted
+    fn test_update_purchase_order_accepted_version() {
+        let mock_context = MockTransactionContext::new();
+        let mut state = PurchaseOrderState::new(&mock_context);
+        create_default_purchase_order(&mut state);
+        create_version(&create_version_payload("1"), &mut state, "buyer_agent").unwrap();
+
+        let accept_version = UpdatePurchaseOrderPayloadBuilder::new()
+            .with_uid(PO_UID.to_string())
+            .with_workflow_status("confirmed".to_string())
+            .with_accepted_version_number("1".to_string())
+            .build()
+            .unwrap();
+
+        match update_purchase_order(&accept_version, &mut state, "seller_agent") {
+            Ok(_) => panic!("A proposed version should not be recorded as accepted"),
+            Err(ApplyError::InvalidTransaction(err)) => assert_eq!(
+                err,
+                format!(
+                    "Version 1 of purchase order {} has not been accepted",
+                    PO_UID
+                )
+            ),
+            Err(err) => panic!("Unexpected error {}", err),
+        }
+
+        update_version(
+            &update_version_payload("1", "accepted"),
+            &mut state,
+            "seller_agent",
+        )
+        .expect("Seller should be able to accept");
+        update_purchase_order(&accept_version, &mut state, "seller_agent")
+            .expect("An accepted version should be recorded");
+
+        let purchase_order = state.get_purchase_order(PO_UID).unwrap().unwrap();
+        assert_eq!(purchase_order.workflow_status(), "confirmed");
+        assert_eq!(purchase_order.accepted_version_number(), Some("1"));
+    }
+
+    #[test]
+    /// Test that a version can be created by the buyer and accepted by the seller, but not
+    /// accepted by the buyer
+    fn test_version_accept() {
+        let mock_context = MockTransactionContext::new();
+        let mut state = PurchaseOrderState::new(&mock_context);
+        create_default_purchase_order(&mut state);
+
+        assert!(create_version(&create_version_payload("1"), &mut state, "seller_agent").is_err());
+        create_version(&create_version_payload("1"), &mut state, "buyer_agent")
+            .expect("Buyer should be able to create a version");
+        assert!(create_version(&create_version_payload("1"), &mut state, "buyer_agent").is_err());
+
+        assert!(update_version(
+            &update_version_payload("1", "accepted"),
+            &mut state,
+            "buyer_agent"
+        )
+        .is_err());
+        update_version(
+            &update_version_payload("1", "accepted"),
+            &mut state,
+            "seller_agent",
+        )
+        .expect("Seller should be able to accept");
+
+        let purchase_order = state.get_purchase_order(PO_UID).unwrap().unwrap();
+        assert_eq!(purchase_order.versions()[0].workflow_status(), "accepted");
+        assert_eq!(
+            purchase_order.versions()[0].revisions()[0].submitter(),
+            "buyer_agent"
+        );
+    }
+
+    #[test]
+    /// Test that the seller cannot accept a draft version, since it does not satisfy the
+    /// constraints of the accepted state
+    fn test_version_accept_draft() {
+        let mock_context = MockTransactionContext::new();
+        let mut state = PurchaseOrderState::new(&mock_context);
+        create_default_purchase_order(&mut state);
+        create_version(&create_version_payload("1"), &mut state, "buyer_agent").unwrap();
+
+        let accept_draft = UpdateVersionPayloadBuilder::new()
+            .with_version_id("1".to_string())
+            .with_po_uid(PO_UID.to_string())
+            .with_workflow_status("accepted".to_string())
+            .with_is_draft(true)
+            .build()
+            .unwrap();
+
+        match update_version(&accept_draft, &mut state, "seller_agent") {
+            Ok(_) => panic!("A draft version should not be accepted"),
+            Err(ApplyError::InvalidTransaction(err)) => assert_eq!(
+                err,
+                "Cannot enter state accepted: Constraint \"is_draft = false\" is not satisfied"
+            ),
+            Err(err) => panic!("Unexpected error {}", err),
+        }
+
+        let purchase_order = state.get_purchase_order(PO_UID).unwrap().unwrap();
+        assert_eq!(purchase_order.versions()[0].workflow_status(), "proposed");
+    }
+
+    #[test]
+    /// Test that new revisions must follow the current revision of the version
+    fn test_version_revisions() {
+        let mock_context = MockTransactionContext::new();
+        let mut state = PurchaseOrderState::new(&mock_context);
+        create_default_purchase_order(&mut state);
+        create_version(&create_version_payload("1"), &mut state, "buyer_agent").unwrap();
+
+        let with_revision = |revision_id| {
+            UpdateVersionPayloadBuilder::new()
+                .with_version_id("1".to_string())
+                .with_po_uid(PO_UID.to_string())
+                .with_workflow_status("proposed".to_string())
+                .with_revision(revision(revision_id))
+                .build()
+                .unwrap()
+        };
+
+        // the seller may update the version without transitioning it
+        assert!(update_version(&with_revision(3), &mut state, "seller_agent").is_err());
+        update_version(&with_revision(2), &mut state, "seller_agent")
+            .expect("Revision 2 should follow revision 1");
+
+        let purchase_order = state.get_purchase_order(PO_UID).unwrap().unwrap();
+        let version = &purchase_order.versions()[0];
+        assert_eq!(version.current_revision_id(), 2);
+        assert_eq!(version.revisions().len(), 2);
+        assert_eq!(version.revisions()[1].submitter(), "seller_agent");
+    }
+}
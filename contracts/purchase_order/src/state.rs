@@ -0,0 +1,162 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        use sabre_sdk::ApplyError;
+        use sabre_sdk::TransactionContext;
+    } else {
+        use sawtooth_sdk::processor::handler::ApplyError;
+        use sawtooth_sdk::processor::handler::TransactionContext;
+    }
+}
+
+use grid_sdk::{
+    pike::addressing::{compute_agent_address, compute_organization_address},
+    protocol::{
+        pike::state::{Agent, AgentList, Organization, OrganizationList},
+        purchase_order::state::{PurchaseOrder, PurchaseOrderList, PurchaseOrderListBuilder},
+    },
+    protos::{FromBytes, IntoBytes},
+    purchase_order::addressing::compute_purchase_order_address,
+};
+
+pub struct PurchaseOrderState<'a> {
+    context: &'a dyn TransactionContext,
+}
+
+impl<'a> PurchaseOrderState<'a> {
+    pub fn new(context: &'a dyn TransactionContext) -> Self {
+        Self { context }
+    }
+
+    pub fn get_purchase_order(&self, uid: &str) -> Result<Option<PurchaseOrder>, ApplyError> {
+        let address = compute_purchase_order_address(uid);
+        match self.context.get_state_entry(&address)? {
+            Some(packed) => {
+                let purchase_orders = match PurchaseOrderList::from_bytes(packed.as_slice()) {
+                    Ok(purchase_orders) => purchase_orders,
+                    Err(err) => {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Cannot deserialize purchase order list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                for purchase_order in purchase_orders.purchase_orders() {
+                    if purchase_order.uid() == uid {
+                        return Ok(Some(purchase_order.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_purchase_order(&self, purchase_order: PurchaseOrder) -> Result<(), ApplyError> {
+        let address = compute_purchase_order_address(purchase_order.uid());
+        let mut purchase_orders = match self.context.get_state_entry(&address)? {
+            Some(packed) => match PurchaseOrderList::from_bytes(packed.as_slice()) {
+                Ok(purchase_order_list) => purchase_order_list.purchase_orders().to_vec(),
+                Err(err) => {
+                    return Err(ApplyError::InternalError(format!(
+                        "Cannot deserialize purchase order list: {:?}",
+                        err
+                    )));
+                }
+            },
+            None => vec![],
+        };
+
+        purchase_orders.retain(|po| po.uid() != purchase_order.uid());
+        purchase_orders.push(purchase_order);
+        purchase_orders.sort_by_key(|po| po.uid().to_string());
+
+        let purchase_order_list = PurchaseOrderListBuilder::new()
+            .with_purchase_orders(purchase_orders)
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!(
+                    "Cannot build purchase order list: {:?}",
+                    err
+                ))
+            })?;
+
+        let serialized = match purchase_order_list.into_bytes() {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Cannot serialize purchase order list: {:?}",
+                    err
+                )));
+            }
+        };
+        self.context
+            .set_state_entry(address, serialized)
+            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        Ok(())
+    }
+
+    pub fn get_agent(&self, public_key: &str) -> Result<Option<Agent>, ApplyError> {
+        let address = compute_agent_address(public_key);
+        match self.context.get_state_entry(&address)? {
+            Some(packed) => {
+                let agents = match AgentList::from_bytes(packed.as_slice()) {
+                    Ok(agents) => agents,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize agent list: {:?}",
+                            err,
+                        )))
+                    }
+                };
+
+                for agent in agents.agents() {
+                    if agent.public_key() == public_key {
+                        return Ok(Some(agent.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_organization(&self, org_id: &str) -> Result<Option<Organization>, ApplyError> {
+        let address = compute_organization_address(org_id);
+        match self.context.get_state_entry(&address)? {
+            Some(packed) => {
+                let orgs: OrganizationList = match OrganizationList::from_bytes(packed.as_slice()) {
+                    Ok(orgs) => orgs,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize organization list: {:?}",
+                            err,
+                        )))
+                    }
+                };
+
+                for org in orgs.organizations() {
+                    if org.org_id() == org_id {
+                        return Ok(Some(org.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -36,20 +36,21 @@ use grid_sdk::{
         schema::state::{PropertyDefinition, PropertyValue},
         track_and_trace::{
             payload::{
-                Action, AnswerProposalAction, CreateProposalAction, CreateRecordAction,
-                FinalizeRecordAction, Response, RevokeReporterAction, TrackAndTracePayload,
-                UpdatePropertiesAction,
+                Action, AggregateRecordsAction, AnswerProposalAction, CreateProposalAction,
+                CreateRecordAction, DisaggregateRecordsAction, FinalizeRecordAction,
+                ReauthorizeReporterAction, Response, RevokeReporterAction, TrackAndTracePayload,
+                UpdatePropertiesAction, UpdateRecordLocationAction,
             },
             state::{
-                AssociatedAgentBuilder, PropertyBuilder, PropertyPageBuilder, ProposalBuilder,
-                ProposalListBuilder, RecordBuilder, ReportedValueBuilder, ReporterBuilder, Role,
-                Status,
+                AssociatedAgent, AssociatedAgentBuilder, Property, PropertyBuilder,
+                PropertyPageBuilder, ProposalBuilder, ProposalListBuilder, Record, RecordBuilder,
+                ReportedValueBuilder, ReporterBuilder, Role, Status,
             },
         },
     },
     protos::FromBytes,
     schemas::addressing::GRID_NAMESPACE,
-    track_and_trace::addressing::TRACK_AND_TRACE_NAMESPACE,
+    track_and_trace::addressing::{next_page_number, TRACK_AND_TRACE_NAMESPACE},
 };
 
 use crate::payload::validate_payload;
@@ -154,6 +155,22 @@ impl TrackAndTraceTransactionHandler {
             };
         }
 
+        let product_id = payload.product_id();
+        if !product_id.is_empty() && state.get_product(product_id)?.is_none() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Product does not exist: {}",
+                product_id
+            )));
+        }
+
+        let location_id = payload.location_id();
+        if !location_id.is_empty() && state.get_location(location_id)?.is_none() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Location does not exist: {}",
+                location_id
+            )));
+        }
+
         let owner = AssociatedAgentBuilder::new()
             .with_agent_id(signer.to_string())
             .with_timestamp(timestamp)
@@ -166,6 +183,8 @@ impl TrackAndTraceTransactionHandler {
             .with_field_final(false)
             .with_owners(vec![owner.clone()])
             .with_custodians(vec![owner])
+            .with_product_id(product_id.to_string())
+            .with_location_id(location_id.to_string())
             .build()
             .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
 
@@ -264,6 +283,22 @@ impl TrackAndTraceTransactionHandler {
             )));
         }
 
+        // A final record can't be disaggregated, so it must not be part of an aggregation
+        if !final_record.parent().is_empty() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record {} is aggregated into {} and must be disaggregated before it is finalized",
+                record_id,
+                final_record.parent()
+            )));
+        }
+        if !final_record.children().is_empty() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record {} contains aggregated records that must be disaggregated before it is \
+                 finalized",
+                record_id
+            )));
+        }
+
         let updated_record = final_record
             .clone()
             .into_builder()
@@ -378,11 +413,7 @@ impl TrackAndTraceTransactionHandler {
             )?;
 
             if updated_property_page.reported_values().len() >= PROPERTY_PAGE_MAX_LENGTH {
-                let new_page_number = if *page_number > PROPERTY_PAGE_MAX_LENGTH as u32 {
-                    1
-                } else {
-                    page_number + 1
-                };
+                let (new_page_number, wrapped) = advance_property_page(&prop);
 
                 let new_page = match state.get_property_page(record_id, name, new_page_number)? {
                     Some(new_page) => new_page
@@ -400,7 +431,6 @@ impl TrackAndTraceTransactionHandler {
 
                 state.set_property_page(record_id, name, new_page_number, new_page)?;
 
-                let wrapped = new_page_number == 1 && !prop.wrapped();
                 let new_property = prop
                     .clone()
                     .into_builder()
@@ -798,7 +828,8 @@ impl TrackAndTraceTransactionHandler {
                                 })?;
 
                             let mut record_custodians = proposal_record.custodians().to_vec();
-                            record_custodians.push(new_custodian);
+                            record_custodians.push(new_custodian.clone());
+                            let contents = proposal_record.children().to_vec();
                             let updated_record = proposal_record
                                 .into_builder()
                                 .with_custodians(record_custodians)
@@ -807,6 +838,16 @@ impl TrackAndTraceTransactionHandler {
 
                             state.set_record(record_id, updated_record)?;
 
+                            update_contents(state, &contents, |child_record| {
+                                let mut custodians = child_record.custodians().to_vec();
+                                custodians.push(new_custodian.clone());
+                                child_record
+                                    .into_builder()
+                                    .with_custodians(custodians)
+                                    .build()
+                                    .map_err(|err| map_builder_error_to_apply_error(err, "Record"))
+                            })?;
+
                             updated_proposal_builder =
                                 updated_proposal_builder.with_status(Status::Accepted);
                         }
@@ -980,6 +1021,334 @@ impl TrackAndTraceTransactionHandler {
 
         Ok(())
     }
+
+    fn _reauthorize_reporter(
+        &self,
+        payload: &ReauthorizeReporterAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.record_id();
+        let reporter_id = payload.reporter_id();
+        let record = get_unfinalized_record(state, record_id)?;
+
+        let is_owner = record
+            .owners()
+            .last()
+            .map(|owner| owner.agent_id() == signer)
+            .unwrap_or(false);
+        if !is_owner {
+            return Err(ApplyError::InvalidTransaction(
+                "Must be owner to reauthorize reporters".to_string(),
+            ));
+        }
+
+        for prop_name in payload.properties() {
+            let prop = match state.get_property(record_id, prop_name)? {
+                Some(prop) => prop,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Property does not exist: {}",
+                        prop_name
+                    )));
+                }
+            };
+
+            match prop
+                .reporters()
+                .iter()
+                .find(|reporter| reporter.public_key() == reporter_id)
+            {
+                Some(reporter) if *reporter.authorized() => {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Reporter is already authorized.".to_string(),
+                    ));
+                }
+                Some(_) => (),
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "{} not a reporter for property {}",
+                        reporter_id, prop_name
+                    )));
+                }
+            }
+
+            // The reporter keeps its index so that the values it reported before it was
+            // revoked are still attributed to it
+            let new_reporters = prop
+                .reporters()
+                .iter()
+                .map(|reporter| {
+                    if reporter.public_key() == reporter_id {
+                        reporter
+                            .clone()
+                            .into_builder()
+                            .with_authorized(true)
+                            .build()
+                            .map_err(|err| map_builder_error_to_apply_error(err, "Reporter"))
+                    } else {
+                        Ok(reporter.clone())
+                    }
+                })
+                .collect::<Result<Vec<_>, ApplyError>>()?;
+
+            let updated_property = prop
+                .into_builder()
+                .with_reporters(new_reporters)
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Property"))?;
+
+            state.set_property(record_id, prop_name, updated_property)?;
+        }
+
+        Ok(())
+    }
+
+    fn _aggregate_records(
+        &self,
+        payload: &AggregateRecordsAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.record_id();
+        let parent_record = get_unfinalized_record(state, record_id)?;
+        check_owner_or_custodian(&parent_record, signer)?;
+
+        // Collect the records that already contain the parent so that a record cannot end up
+        // inside one of its own contents.
+        let mut ancestors = vec![];
+        let mut ancestor_id = parent_record.parent().to_string();
+        while !ancestor_id.is_empty() {
+            let ancestor = match state.get_record(&ancestor_id)? {
+                Some(ancestor) => ancestor,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Record does not exist: {}",
+                        ancestor_id
+                    )));
+                }
+            };
+            ancestors.push(ancestor_id);
+            ancestor_id = ancestor.parent().to_string();
+        }
+
+        let mut children = parent_record.children().to_vec();
+        for child_record_id in payload.child_record_ids() {
+            let child_record = get_unfinalized_record(state, child_record_id)?;
+            check_owner_or_custodian(&child_record, signer)?;
+
+            if !child_record.parent().is_empty() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} is already aggregated into {}",
+                    child_record_id,
+                    child_record.parent()
+                )));
+            }
+
+            if ancestors.contains(child_record_id) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} cannot be aggregated into {} because it contains it",
+                    child_record_id, record_id
+                )));
+            }
+
+            let updated_child = child_record
+                .into_builder()
+                .with_parent(record_id.to_string())
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+            state.set_record(child_record_id, updated_child)?;
+
+            children.push(child_record_id.to_string());
+        }
+
+        let updated_parent = parent_record
+            .into_builder()
+            .with_children(children)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+        state.set_record(record_id, updated_parent)?;
+
+        Ok(())
+    }
+
+    fn _disaggregate_records(
+        &self,
+        payload: &DisaggregateRecordsAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.record_id();
+        let parent_record = get_unfinalized_record(state, record_id)?;
+        check_owner_or_custodian(&parent_record, signer)?;
+
+        let mut children = parent_record.children().to_vec();
+        for child_record_id in payload.child_record_ids() {
+            if !children.contains(child_record_id) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} is not aggregated into {}",
+                    child_record_id, record_id
+                )));
+            }
+
+            let child_record = get_unfinalized_record(state, child_record_id)?;
+            check_owner_or_custodian(&child_record, signer)?;
+
+            let updated_child = child_record
+                .into_builder()
+                .with_parent(String::new())
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+            state.set_record(child_record_id, updated_child)?;
+
+            children.retain(|child| child != child_record_id);
+        }
+
+        let updated_parent = parent_record
+            .into_builder()
+            .with_children(children)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+        state.set_record(record_id, updated_parent)?;
+
+        Ok(())
+    }
+
+    fn _update_record_location(
+        &self,
+        payload: &UpdateRecordLocationAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.record_id();
+        let record = get_unfinalized_record(state, record_id)?;
+
+        let is_custodian = record
+            .custodians()
+            .last()
+            .map(|custodian| custodian.agent_id() == signer)
+            .unwrap_or(false);
+        if !is_custodian {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Must be custodian of record {} to update its location",
+                record_id
+            )));
+        }
+
+        let location_id = payload.location_id();
+        if state.get_location(location_id)?.is_none() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Location does not exist: {}",
+                location_id
+            )));
+        }
+
+        let children = record.children().to_vec();
+        let updated_record = record
+            .into_builder()
+            .with_location_id(location_id.to_string())
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+        state.set_record(record_id, updated_record)?;
+
+        // Aggregated records travel with the record that contains them
+        update_contents(state, &children, |child_record| {
+            child_record
+                .into_builder()
+                .with_location_id(location_id.to_string())
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))
+        })
+    }
+}
+
+/// Returns the page a property moves to once its current page is full, along with the
+/// property's new wrapped flag. Once the ring of pages has wrapped, the page being moved to
+/// holds the earliest reported values and is overwritten.
+fn advance_property_page(property: &Property) -> (u32, bool) {
+    let new_page_number = next_page_number(*property.current_page());
+    let wrapped = *property.wrapped() || new_page_number == 1;
+    (new_page_number, wrapped)
+}
+
+fn get_unfinalized_record(
+    state: &mut TrackAndTraceState,
+    record_id: &str,
+) -> Result<Record, ApplyError> {
+    let record = match state.get_record(record_id)? {
+        Some(record) => record,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record does not exist: {}",
+                record_id
+            )));
+        }
+    };
+
+    if *record.field_final() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Record is final: {}",
+            record_id
+        )));
+    }
+
+    Ok(record)
+}
+
+fn check_owner_or_custodian(record: &Record, signer: &str) -> Result<(), ApplyError> {
+    let is_owner = record
+        .owners()
+        .last()
+        .map(|owner| owner.agent_id() == signer)
+        .unwrap_or(false);
+    let is_custodian = record
+        .custodians()
+        .last()
+        .map(|custodian| custodian.agent_id() == signer)
+        .unwrap_or(false);
+
+    if !is_owner && !is_custodian {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Must be owner or custodian of record {}",
+            record.record_id()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Applies an update to every record contained, directly or through nested aggregation, in
+/// a record, as records aggregated into another follow it through custody and location changes.
+/// Final records never change, so they are left as they are.
+fn update_contents<F>(
+    state: &mut TrackAndTraceState,
+    contents: &[String],
+    update: F,
+) -> Result<(), ApplyError>
+where
+    F: Fn(Record) -> Result<Record, ApplyError>,
+{
+    let mut pending = contents.to_vec();
+    while let Some(child_record_id) = pending.pop() {
+        let child_record = match state.get_record(&child_record_id)? {
+            Some(record) => record,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record does not exist: {}",
+                    child_record_id
+                )));
+            }
+        };
+        pending.extend(child_record.children().iter().cloned());
+
+        if *child_record.field_final() {
+            continue;
+        }
+
+        state.set_record(&child_record_id, update(child_record)?)?;
+    }
+
+    Ok(())
 }
 
 fn map_builder_error_to_apply_error(err: BuilderError, protocol_name: &str) -> ApplyError {
@@ -1042,6 +1411,18 @@ impl TransactionHandler for TrackAndTraceTransactionHandler {
             Action::RevokeReporter(action_payload) => {
                 self._revoke_reporter(action_payload, &mut state, signer)?
             }
+            Action::AggregateRecords(action_payload) => {
+                self._aggregate_records(action_payload, &mut state, signer)?
+            }
+            Action::DisaggregateRecords(action_payload) => {
+                self._disaggregate_records(action_payload, &mut state, signer)?
+            }
+            Action::UpdateRecordLocation(action_payload) => {
+                self._update_record_location(action_payload, &mut state, signer)?
+            }
+            Action::ReauthorizeReporter(action_payload) => {
+                self._reauthorize_reporter(action_payload, &mut state, signer)?
+            }
         }
         Ok(())
     }
@@ -1077,19 +1458,25 @@ mod tests {
     use std::collections::HashMap;
 
     use grid_sdk::{
+        locations::addressing::compute_gs1_location_address,
         pike::addressing::compute_agent_address,
+        products::addressing::compute_gs1_product_address,
         protocol::{
+            location::state::{LocationBuilder, LocationListBuilder, LocationNamespace},
             pike::state::{AgentBuilder, AgentListBuilder},
+            product::state::{ProductBuilder, ProductListBuilder, ProductNamespace},
             schema::state::{
                 DataType, PropertyDefinitionBuilder, PropertyValueBuilder, SchemaBuilder,
                 SchemaListBuilder,
             },
             track_and_trace::{
                 payload::{
-                    AnswerProposalActionBuilder, CreateProposalActionBuilder,
-                    CreateRecordActionBuilder, FinalizeRecordActionBuilder,
-                    RevokeReporterActionBuilder, UpdatePropertiesAction,
-                    UpdatePropertiesActionBuilder,
+                    AggregateRecordsActionBuilder, AnswerProposalActionBuilder,
+                    CreateProposalActionBuilder, CreateRecordActionBuilder,
+                    DisaggregateRecordsActionBuilder, FinalizeRecordActionBuilder,
+                    ReauthorizeReporterActionBuilder, RevokeReporterActionBuilder,
+                    UpdatePropertiesAction, UpdatePropertiesActionBuilder,
+                    UpdateRecordLocationActionBuilder,
                 },
                 state::{
                     Property, PropertyListBuilder, PropertyPage, PropertyPageListBuilder, Proposal,
@@ -1100,7 +1487,8 @@ mod tests {
         protos::IntoBytes,
         schemas::addressing::compute_schema_address,
         track_and_trace::addressing::{
-            make_property_address, make_proposal_address, make_record_address,
+            earliest_page_number, make_property_address, make_proposal_address,
+            make_record_address, page_ring_position, MAX_PROPERTY_PAGE,
         },
     };
 
@@ -1108,10 +1496,14 @@ mod tests {
 
     const TIMESTAMP: u64 = 1;
     const RECORD_ID: &str = "test_record_action";
+    const CHILD_RECORD_ID: &str = "test_child_record";
     const PUBLIC_KEY: &str = "agent_public_key";
     const OPTIONAL_PROPERTY_NAME: &str = "test_optional";
     const REQUIRED_PROPERTY_NAME: &str = "test_required";
     const SCHEMA_NAME: &str = "test_schema";
+    const GTIN: &str = "00012345600012";
+    const GLN: &str = "0123456789012";
+    const OTHER_GLN: &str = "0123456789029";
 
     #[derive(Default, Debug)]
     /// A MockTransactionContext that can be used to test TrackAndTraceState
@@ -1181,6 +1573,44 @@ mod tests {
             self.set_state_entry(agent_address, agent_bytes).unwrap();
         }
 
+        fn add_product(&self, gtin: &str) {
+            let product = ProductBuilder::new()
+                .with_product_id(gtin.to_string())
+                .with_product_namespace(ProductNamespace::GS1)
+                .with_owner("test_org".to_string())
+                .with_properties(vec![])
+                .build()
+                .unwrap();
+
+            let product_list = ProductListBuilder::new()
+                .with_products(vec![product])
+                .build()
+                .unwrap();
+            let product_bytes = product_list.into_bytes().unwrap();
+            let product_address = compute_gs1_product_address(gtin);
+            self.set_state_entry(product_address, product_bytes)
+                .unwrap();
+        }
+
+        fn add_location(&self, gln: &str) {
+            let location = LocationBuilder::new()
+                .with_location_id(gln.to_string())
+                .with_namespace(LocationNamespace::GS1)
+                .with_owner("test_org".to_string())
+                .with_properties(vec![])
+                .build()
+                .unwrap();
+
+            let location_list = LocationListBuilder::new()
+                .with_locations(vec![location])
+                .build()
+                .unwrap();
+            let location_bytes = location_list.into_bytes().unwrap();
+            let location_address = compute_gs1_location_address(gln);
+            self.set_state_entry(location_address, location_bytes)
+                .unwrap();
+        }
+
         fn add_schema(&self) {
             let builder = SchemaBuilder::new();
             let schema = builder
@@ -1211,6 +1641,44 @@ mod tests {
             self.set_state_entry(record_address, record_bytes).unwrap();
         }
 
+        fn add_child_record(&self, record_id: &str, agent_key: &str) {
+            let record_list = RecordListBuilder::new()
+                .with_records(vec![make_child_record(record_id, agent_key)])
+                .build()
+                .unwrap();
+            let record_bytes = record_list.into_bytes().unwrap();
+            let record_address = make_record_address(record_id);
+            self.set_state_entry(record_address, record_bytes).unwrap();
+        }
+
+        fn add_aggregated_records(&self) {
+            self.add_aggregated_records_with_child_final(false);
+        }
+
+        fn add_aggregated_records_with_child_final(&self, child_final: bool) {
+            let parent = make_record()
+                .into_builder()
+                .with_children(vec![CHILD_RECORD_ID.to_string()])
+                .build()
+                .unwrap();
+            let child = make_child_record(CHILD_RECORD_ID, PUBLIC_KEY)
+                .into_builder()
+                .with_parent(RECORD_ID.to_string())
+                .with_field_final(child_final)
+                .build()
+                .unwrap();
+
+            for record in vec![parent, child] {
+                let record_address = make_record_address(record.record_id());
+                let record_list = RecordListBuilder::new()
+                    .with_records(vec![record])
+                    .build()
+                    .unwrap();
+                self.set_state_entry(record_address, record_list.into_bytes().unwrap())
+                    .unwrap();
+            }
+        }
+
         fn add_property(&self, property_name: &str, property_definition: PropertyDefinition) {
             let property_list = PropertyListBuilder::new()
                 .with_properties(vec![make_property(property_name, property_definition)])
@@ -1682,29 +2150,87 @@ mod tests {
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction is valid an OK is returned and new value is added
-    /// to the record's PropertyPage
-    fn test_update_properties_valid() {
+    /// Test that the FinalizeRecordAction fails if the record contains aggregated records
+    fn test_finalize_record_handler_record_has_contents() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
         transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_record();
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+        transaction_context.add_aggregated_records();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
-        assert!(transaction_handler
-            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, TIMESTAMP,)
-            .is_ok());
+        match transaction_handler._finalize_record(
+            &create_finalize_record(),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => panic!("Record has contents, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record {} contains aggregated records", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
 
-        let page = state
-            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
-            .expect("Failed to get property page from state")
-            .expect("Property page is none, it should be some");
+    #[test]
+    /// Test that the FinalizeRecordAction fails if the record is aggregated into another
+    fn test_finalize_record_handler_record_aggregated() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_aggregated_records();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let payload = FinalizeRecordActionBuilder::new()
+            .with_record_id(CHILD_RECORD_ID.to_string())
+            .build()
+            .expect("Failed to build FinalizeRecordAction");
+
+        match transaction_handler._finalize_record(&payload, &mut state, PUBLIC_KEY) {
+            Ok(()) => panic!("Record is aggregated, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Record {} is aggregated into {}",
+                    CHILD_RECORD_ID, RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        let child = state
+            .get_record(CHILD_RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert!(!child.field_final());
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction is valid an OK is returned and new value is added
+    /// to the record's PropertyPage
+    fn test_update_properties_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        assert!(transaction_handler
+            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, TIMESTAMP,)
+            .is_ok());
+
+        let page = state
+            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
+            .expect("Failed to get property page from state")
+            .expect("Property page is none, it should be some");
 
         assert_eq!(page.reported_values().len(), 2);
         assert_eq!(page.reported_values()[1].value(), &updated_property_value());
@@ -1899,6 +2425,100 @@ mod tests {
         assert!(new_page.is_some());
     }
 
+    #[test]
+    /// Test that the UpdatePropertiesAction wraps around to the first page once the last page
+    /// of the ring is full, clearing out the earliest reported values stored there and marking
+    /// the property as wrapped.
+    fn test_update_properties_wraps_page_ring() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let property = make_property(REQUIRED_PROPERTY_NAME, required_property_definition())
+            .into_builder()
+            .with_current_page(MAX_PROPERTY_PAGE)
+            .build()
+            .expect("Failed to build property");
+        state
+            .set_property(RECORD_ID, REQUIRED_PROPERTY_NAME, property)
+            .expect("Failed to set property");
+        for page_number in &[1, MAX_PROPERTY_PAGE] {
+            state
+                .set_property_page(
+                    RECORD_ID,
+                    REQUIRED_PROPERTY_NAME,
+                    *page_number,
+                    make_property_page(REQUIRED_PROPERTY_NAME, required_property_value()),
+                )
+                .expect("Failed to set property page");
+        }
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let updates = std::iter::repeat(updated_property_value())
+            .take(PROPERTY_PAGE_MAX_LENGTH - 1)
+            .collect::<Vec<_>>();
+
+        assert!(transaction_handler
+            ._update_properties(
+                &update_property_action(updates),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP
+            )
+            .is_ok());
+
+        let property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to fetch property")
+            .expect("Property not found");
+        assert_eq!(*property.current_page(), 1);
+        assert!(*property.wrapped());
+
+        let last_page = state
+            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, MAX_PROPERTY_PAGE)
+            .expect("Failed to fetch property page")
+            .expect("Property page not found");
+        assert_eq!(last_page.reported_values().len(), PROPERTY_PAGE_MAX_LENGTH);
+
+        let first_page = state
+            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
+            .expect("Failed to fetch property page")
+            .expect("Property page not found");
+        assert!(first_page.reported_values().is_empty());
+    }
+
+    #[test]
+    /// Test that advancing a property through more than 65,536 pages walks the page ring in
+    /// order, wraps from the last page back to the first, and keeps the property marked as
+    /// wrapped on every later lap.
+    fn test_property_page_ring_simulation() {
+        let mut property = make_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+        let advances = 2 * MAX_PROPERTY_PAGE + 10;
+
+        for advance in 1..=advances {
+            let (page_number, wrapped) = advance_property_page(&property);
+            assert_eq!(page_number, advance % MAX_PROPERTY_PAGE + 1);
+            assert_eq!(wrapped, advance >= MAX_PROPERTY_PAGE);
+
+            // The current page is always the latest page on the ring
+            let earliest_page = earliest_page_number(page_number, wrapped);
+            assert_eq!(
+                page_ring_position(page_number, earliest_page),
+                std::cmp::min(advance, MAX_PROPERTY_PAGE - 1)
+            );
+
+            property = property
+                .into_builder()
+                .with_current_page(page_number)
+                .with_wrapped(wrapped)
+                .build()
+                .expect("Failed to build property");
+        }
+    }
+
     #[test]
     /// Test that if the CreateProposalAction, with role set to Owner, is valid an OK is returned
     /// and new proposal is added state
@@ -3164,144 +3784,844 @@ mod tests {
         }
     }
 
-    fn optional_property_value() -> PropertyValue {
-        PropertyValueBuilder::new()
-            .with_name(OPTIONAL_PROPERTY_NAME.to_string())
-            .with_data_type(DataType::Enum)
-            .with_enum_value(1)
-            .build()
-            .expect("Failed to build property value")
-    }
+    #[test]
+    /// Test that if the ReauthorizeReporterAction is valid an OK is returned and that the
+    /// revoked reporter is authorized again with its original index
+    fn test_reauthorize_reporter_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        let reporter_key = "reporter_key";
+        transaction_context.add_record();
 
-    fn required_property_value() -> PropertyValue {
-        PropertyValueBuilder::new()
-            .with_name(REQUIRED_PROPERTY_NAME.to_string())
-            .with_data_type(DataType::String)
-            .with_string_value("required_field".to_string())
-            .build()
-            .expect("Failed to build property value")
-    }
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
 
-    fn create_record_action_with_properties(properties: Vec<PropertyValue>) -> CreateRecordAction {
-        CreateRecordActionBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
-            .with_schema(SCHEMA_NAME.to_string())
-            .with_properties(properties)
+        let owner = ReporterBuilder::new()
+            .with_public_key(PUBLIC_KEY.to_string())
+            .with_authorized(true)
+            .with_index(0)
             .build()
-            .expect("Failed to build CreateRecordAction")
-    }
-
-    fn create_finalize_record() -> FinalizeRecordAction {
-        FinalizeRecordActionBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
+            .expect("Failed to build Reporter");
+        let revoked_reporter = ReporterBuilder::new()
+            .with_public_key(reporter_key.to_string())
+            .with_authorized(false)
+            .with_index(1)
             .build()
-            .expect("Failed to build FinalizeRecordAction")
-    }
-
-    fn updated_property_value() -> PropertyValue {
-        PropertyValueBuilder::new()
-            .with_name(REQUIRED_PROPERTY_NAME.to_string())
-            .with_data_type(DataType::String)
-            .with_string_value("updated_required_field".to_string())
+            .expect("Failed to build Reporter");
+        let property = make_property(REQUIRED_PROPERTY_NAME, required_property_definition())
+            .into_builder()
+            .with_reporters(vec![owner, revoked_reporter])
             .build()
-            .expect("Failed to build property value")
-    }
+            .expect("Failed to build property");
+        state
+            .set_property(RECORD_ID, REQUIRED_PROPERTY_NAME, property)
+            .expect("Failed to set property");
 
-    fn update_property_action(properties: Vec<PropertyValue>) -> UpdatePropertiesAction {
-        UpdatePropertiesActionBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
-            .with_properties(properties)
-            .build()
-            .expect("Failed to build UpdatePropertiesAction")
-    }
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-    fn create_proposal_action(role: Role, receiving_agent_key: &str) -> CreateProposalAction {
-        CreateProposalActionBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
-            .with_properties(vec![
-                OPTIONAL_PROPERTY_NAME.to_string(),
-                REQUIRED_PROPERTY_NAME.to_string(),
-            ])
-            .with_receiving_agent(receiving_agent_key.to_string())
-            .with_role(role)
-            .with_terms("".to_string())
-            .build()
-            .expect("Failed to build CreateProposalAction")
-    }
+        let payload =
+            reauthorize_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
 
-    fn create_proposal_no_props_action(
-        role: Role,
-        receiving_agent_key: &str,
-    ) -> CreateProposalAction {
-        CreateProposalActionBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
-            .with_properties(vec![])
-            .with_receiving_agent(receiving_agent_key.to_string())
-            .with_role(role)
-            .with_terms("".to_string())
-            .build()
-            .expect("Failed to build CreateProposalAction")
-    }
+        assert!(transaction_handler
+            ._reauthorize_reporter(&payload, &mut state, PUBLIC_KEY)
+            .is_ok());
 
-    fn answer_proposal_action(
-        role: Role,
-        receiving_agent_key: &str,
-        response: Response,
-    ) -> AnswerProposalAction {
-        AnswerProposalActionBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
-            .with_receiving_agent(receiving_agent_key.to_string())
-            .with_role(role)
-            .with_response(response)
-            .build()
-            .expect("Failed to build AnswerProposalAction")
-    }
+        let required_property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to fetch required property")
+            .expect("Required property not found");
 
-    fn revoke_reporter_action(reporter_id: &str, properties: Vec<String>) -> RevokeReporterAction {
-        RevokeReporterActionBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
-            .with_reporter_id(reporter_id.to_string())
-            .with_properties(properties)
-            .build()
-            .expect("Failed to build RevokeReporterAction")
+        let reporter = required_property
+            .reporters()
+            .iter()
+            .find(|reporter| reporter.public_key() == reporter_key)
+            .expect("Reporter not found");
+        assert!(*reporter.authorized());
+        assert_eq!(*reporter.index(), 1);
+        assert_eq!(required_property.reporters().len(), 2);
     }
 
-    fn optional_property_definition() -> PropertyDefinition {
-        PropertyDefinitionBuilder::new()
-            .with_name(OPTIONAL_PROPERTY_NAME.to_string())
-            .with_data_type(DataType::Enum)
-            .with_description("Optional".to_string())
-            .with_enum_options(vec![
-                "One".to_string(),
-                "Two".to_string(),
-                "Three".to_string(),
-            ])
-            .build()
-            .expect("Failed to build property definition")
-    }
+    #[test]
+    /// Test that the ReauthorizeReporterAction fails if the signer is not the owner
+    fn test_reauthorize_reporter_signer_not_owner() {
+        let mut transaction_context = MockTransactionContext::default();
+        let reporter_key = "reporter_key";
+        transaction_context.add_record();
+        transaction_context.add_property_with_reporter(
+            REQUIRED_PROPERTY_NAME,
+            reporter_key,
+            false,
+            required_property_definition(),
+        );
 
-    fn required_property_definition() -> PropertyDefinition {
-        PropertyDefinitionBuilder::new()
-            .with_name(REQUIRED_PROPERTY_NAME.to_string())
-            .with_data_type(DataType::String)
-            .with_description("Required".to_string())
-            .with_required(true)
-            .build()
-            .expect("Failed to build property definition")
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let payload =
+            reauthorize_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
+
+        match transaction_handler._reauthorize_reporter(&payload, &mut state, reporter_key) {
+            Ok(()) => panic!("Signer is not owner, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Must be owner to reauthorize reporters"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
     }
 
-    fn make_record() -> Record {
-        let associated_agent = AssociatedAgentBuilder::new()
-            .with_agent_id(PUBLIC_KEY.to_string())
-            .with_timestamp(TIMESTAMP)
-            .build()
-            .expect("Failed to build AssociatedAgent");
+    #[test]
+    /// Test that the ReauthorizeReporterAction fails if the reporter is still authorized
+    fn test_reauthorize_reporter_already_authorized() {
+        let mut transaction_context = MockTransactionContext::default();
+        let reporter_key = "reporter_key";
+        transaction_context.add_record();
+        transaction_context.add_property_with_reporter(
+            REQUIRED_PROPERTY_NAME,
+            reporter_key,
+            true,
+            required_property_definition(),
+        );
 
-        RecordBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
-            .with_schema(SCHEMA_NAME.to_string())
-            .with_owners(vec![associated_agent.clone()])
-            .with_custodians(vec![associated_agent.clone()])
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let payload =
+            reauthorize_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
+
+        match transaction_handler._reauthorize_reporter(&payload, &mut state, PUBLIC_KEY) {
+            Ok(()) => panic!("Reporter already authorized, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Reporter is already authorized."));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the ReauthorizeReporterAction fails if the agent was never a reporter for
+    /// the property
+    fn test_reauthorize_reporter_not_a_reporter() {
+        let mut transaction_context = MockTransactionContext::default();
+        let reporter_key = "reporter_key";
+        transaction_context.add_record();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let payload =
+            reauthorize_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
+
+        match transaction_handler._reauthorize_reporter(&payload, &mut state, PUBLIC_KEY) {
+            Ok(()) => panic!("Agent is not a reporter, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "{} not a reporter for property {}",
+                    reporter_key, REQUIRED_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the AggregateRecordsAction is valid an OK is returned and that the parent
+    /// record lists the child and the child references the parent
+    fn test_aggregate_records_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_child_record(CHILD_RECORD_ID, PUBLIC_KEY);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        assert!(transaction_handler
+            ._aggregate_records(
+                &aggregate_records_action(RECORD_ID, vec![CHILD_RECORD_ID.to_string()]),
+                &mut state,
+                PUBLIC_KEY
+            )
+            .is_ok());
+
+        let parent = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert_eq!(parent.children(), &[CHILD_RECORD_ID.to_string()]);
+
+        let child = state
+            .get_record(CHILD_RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert_eq!(child.parent(), RECORD_ID);
+    }
+
+    #[test]
+    /// Test that the AggregateRecordsAction fails if a child record does not exist
+    fn test_aggregate_records_child_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._aggregate_records(
+            &aggregate_records_action(RECORD_ID, vec![CHILD_RECORD_ID.to_string()]),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => panic!("Child record does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record does not exist: {}", CHILD_RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the AggregateRecordsAction fails if the signer is neither the owner nor the
+    /// custodian of a child record
+    fn test_aggregate_records_signer_not_owner_nor_custodian_of_child() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_child_record(CHILD_RECORD_ID, "other_agent_key");
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._aggregate_records(
+            &aggregate_records_action(RECORD_ID, vec![CHILD_RECORD_ID.to_string()]),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => panic!("Signer does not hold child, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Must be owner or custodian of record {}",
+                    CHILD_RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the AggregateRecordsAction fails if a child record is already aggregated into
+    /// another record
+    fn test_aggregate_records_child_already_aggregated() {
+        let mut transaction_context = MockTransactionContext::default();
+        let other_parent_id = "other_parent_record";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_aggregated_records();
+        transaction_context.add_child_record(other_parent_id, PUBLIC_KEY);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._aggregate_records(
+            &aggregate_records_action(other_parent_id, vec![CHILD_RECORD_ID.to_string()]),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => panic!("Child is already aggregated, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Record {} is already aggregated into {}",
+                    CHILD_RECORD_ID, RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the AggregateRecordsAction fails if the child record already contains the
+    /// parent record
+    fn test_aggregate_records_cycle() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_aggregated_records();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._aggregate_records(
+            &aggregate_records_action(CHILD_RECORD_ID, vec![RECORD_ID.to_string()]),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => {
+                panic!("Aggregation would create a cycle, InvalidTransaction should be returned")
+            }
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Record {} cannot be aggregated into {} because it contains it",
+                    RECORD_ID, CHILD_RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the DisaggregateRecordsAction is valid an OK is returned and that the
+    /// child is removed from the parent record
+    fn test_disaggregate_records_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_aggregated_records();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        assert!(transaction_handler
+            ._disaggregate_records(
+                &disaggregate_records_action(vec![CHILD_RECORD_ID.to_string()]),
+                &mut state,
+                PUBLIC_KEY
+            )
+            .is_ok());
+
+        let parent = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert!(parent.children().is_empty());
+
+        let child = state
+            .get_record(CHILD_RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert_eq!(child.parent(), "");
+    }
+
+    #[test]
+    /// Test that the DisaggregateRecordsAction fails if the child record is not aggregated
+    /// into the parent record
+    fn test_disaggregate_records_child_not_contained() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_child_record(CHILD_RECORD_ID, PUBLIC_KEY);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._disaggregate_records(
+            &disaggregate_records_action(vec![CHILD_RECORD_ID.to_string()]),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => panic!("Child is not aggregated, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Record {} is not aggregated into {}",
+                    CHILD_RECORD_ID, RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that when a custodian proposal for a record with contents is accepted, the new
+    /// custodian is also added to the contained records
+    fn test_answer_proposal_accept_custodian_transfers_contents() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_aggregated_records();
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Custodian,
+            Status::Open,
+        );
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let payload =
+            answer_proposal_action(Role::Custodian, receiving_agent_key, Response::Accept);
+
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP)
+            .is_ok());
+
+        let child = state
+            .get_record(CHILD_RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+
+        assert_eq!(
+            child
+                .custodians()
+                .last()
+                .expect("Custodian not found")
+                .agent_id(),
+            receiving_agent_key
+        );
+    }
+
+    #[test]
+    /// Test that when a custodian proposal for a record with contents is accepted, a final
+    /// record among the contents, left over from before aggregated records could not be
+    /// finalized, keeps its custodians
+    fn test_answer_proposal_accept_custodian_skips_final_contents() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_aggregated_records_with_child_final(true);
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Custodian,
+            Status::Open,
+        );
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let child_before = state
+            .get_record(CHILD_RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let payload =
+            answer_proposal_action(Role::Custodian, receiving_agent_key, Response::Accept);
+
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP)
+            .is_ok());
+
+        let child = state
+            .get_record(CHILD_RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert_eq!(child, child_before);
+    }
+
+    #[test]
+    /// Test that a record created with a GTIN and GLN that exist in state is linked to the
+    /// product and location
+    fn test_create_record_with_product_and_location() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_product(GTIN);
+        transaction_context.add_location(GLN);
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_record_action = create_record_action_with_product_and_location(GTIN, GLN);
+
+        assert!(transaction_handler
+            ._create_record(&create_record_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(record.product_id(), GTIN);
+        assert_eq!(record.location_id(), GLN);
+    }
+
+    #[test]
+    /// Test that the CreateRecordAction is invalid if the product does not exist
+    fn test_create_record_product_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_location(GLN);
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_record_action = create_record_action_with_product_and_location(GTIN, GLN);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Product does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Product does not exist: {}", GTIN)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the CreateRecordAction is invalid if the location does not exist
+    fn test_create_record_location_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_product(GTIN);
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_record_action = create_record_action_with_product_and_location(GTIN, GLN);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Location does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Location does not exist: {}", GLN)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the UpdateRecordLocationAction moves the record and its contents to the
+    /// new location
+    fn test_update_record_location_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_aggregated_records();
+        transaction_context.add_location(OTHER_GLN);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        assert!(transaction_handler
+            ._update_record_location(
+                &update_record_location_action(OTHER_GLN),
+                &mut state,
+                PUBLIC_KEY
+            )
+            .is_ok());
+
+        let parent = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert_eq!(parent.location_id(), OTHER_GLN);
+
+        let child = state
+            .get_record(CHILD_RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert_eq!(child.location_id(), OTHER_GLN);
+    }
+
+    #[test]
+    /// Test that the UpdateRecordLocationAction moves the record but leaves a final record
+    /// among its contents where it is
+    fn test_update_record_location_skips_final_contents() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_aggregated_records_with_child_final(true);
+        transaction_context.add_location(OTHER_GLN);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let child_before = state
+            .get_record(CHILD_RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        assert!(transaction_handler
+            ._update_record_location(
+                &update_record_location_action(OTHER_GLN),
+                &mut state,
+                PUBLIC_KEY
+            )
+            .is_ok());
+
+        let parent = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert_eq!(parent.location_id(), OTHER_GLN);
+
+        let child = state
+            .get_record(CHILD_RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert_eq!(child, child_before);
+    }
+
+    #[test]
+    /// Test that the UpdateRecordLocationAction fails if the signer is not the custodian
+    fn test_update_record_location_not_custodian() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_location(OTHER_GLN);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._update_record_location(
+            &update_record_location_action(OTHER_GLN),
+            &mut state,
+            "not_the_custodian",
+        ) {
+            Ok(()) => panic!("Signer is not custodian, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Must be custodian of record {} to update its location",
+                    RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the UpdateRecordLocationAction fails if the location does not exist
+    fn test_update_record_location_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._update_record_location(
+            &update_record_location_action(OTHER_GLN),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => panic!("Location does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Location does not exist: {}", OTHER_GLN)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    fn optional_property_value() -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(OPTIONAL_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::Enum)
+            .with_enum_value(1)
+            .build()
+            .expect("Failed to build property value")
+    }
+
+    fn required_property_value() -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(REQUIRED_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::String)
+            .with_string_value("required_field".to_string())
+            .build()
+            .expect("Failed to build property value")
+    }
+
+    fn create_record_action_with_properties(properties: Vec<PropertyValue>) -> CreateRecordAction {
+        CreateRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_schema(SCHEMA_NAME.to_string())
+            .with_properties(properties)
+            .build()
+            .expect("Failed to build CreateRecordAction")
+    }
+
+    fn create_record_action_with_product_and_location(gtin: &str, gln: &str) -> CreateRecordAction {
+        CreateRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_schema(SCHEMA_NAME.to_string())
+            .with_properties(vec![required_property_value()])
+            .with_product_id(gtin.to_string())
+            .with_location_id(gln.to_string())
+            .build()
+            .expect("Failed to build CreateRecordAction")
+    }
+
+    fn create_finalize_record() -> FinalizeRecordAction {
+        FinalizeRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .build()
+            .expect("Failed to build FinalizeRecordAction")
+    }
+
+    fn updated_property_value() -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(REQUIRED_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::String)
+            .with_string_value("updated_required_field".to_string())
+            .build()
+            .expect("Failed to build property value")
+    }
+
+    fn update_property_action(properties: Vec<PropertyValue>) -> UpdatePropertiesAction {
+        UpdatePropertiesActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_properties(properties)
+            .build()
+            .expect("Failed to build UpdatePropertiesAction")
+    }
+
+    fn create_proposal_action(role: Role, receiving_agent_key: &str) -> CreateProposalAction {
+        CreateProposalActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_properties(vec![
+                OPTIONAL_PROPERTY_NAME.to_string(),
+                REQUIRED_PROPERTY_NAME.to_string(),
+            ])
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(role)
+            .with_terms("".to_string())
+            .build()
+            .expect("Failed to build CreateProposalAction")
+    }
+
+    fn create_proposal_no_props_action(
+        role: Role,
+        receiving_agent_key: &str,
+    ) -> CreateProposalAction {
+        CreateProposalActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_properties(vec![])
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(role)
+            .with_terms("".to_string())
+            .build()
+            .expect("Failed to build CreateProposalAction")
+    }
+
+    fn answer_proposal_action(
+        role: Role,
+        receiving_agent_key: &str,
+        response: Response,
+    ) -> AnswerProposalAction {
+        AnswerProposalActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(role)
+            .with_response(response)
+            .build()
+            .expect("Failed to build AnswerProposalAction")
+    }
+
+    fn revoke_reporter_action(reporter_id: &str, properties: Vec<String>) -> RevokeReporterAction {
+        RevokeReporterActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_reporter_id(reporter_id.to_string())
+            .with_properties(properties)
+            .build()
+            .expect("Failed to build RevokeReporterAction")
+    }
+
+    fn reauthorize_reporter_action(
+        reporter_id: &str,
+        properties: Vec<String>,
+    ) -> ReauthorizeReporterAction {
+        ReauthorizeReporterActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_reporter_id(reporter_id.to_string())
+            .with_properties(properties)
+            .build()
+            .expect("Failed to build ReauthorizeReporterAction")
+    }
+
+    fn aggregate_records_action(
+        record_id: &str,
+        child_record_ids: Vec<String>,
+    ) -> AggregateRecordsAction {
+        AggregateRecordsActionBuilder::new()
+            .with_record_id(record_id.to_string())
+            .with_child_record_ids(child_record_ids)
+            .build()
+            .expect("Failed to build AggregateRecordsAction")
+    }
+
+    fn disaggregate_records_action(child_record_ids: Vec<String>) -> DisaggregateRecordsAction {
+        DisaggregateRecordsActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_child_record_ids(child_record_ids)
+            .build()
+            .expect("Failed to build DisaggregateRecordsAction")
+    }
+
+    fn update_record_location_action(location_id: &str) -> UpdateRecordLocationAction {
+        UpdateRecordLocationActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_location_id(location_id.to_string())
+            .build()
+            .expect("Failed to build UpdateRecordLocationAction")
+    }
+
+    fn optional_property_definition() -> PropertyDefinition {
+        PropertyDefinitionBuilder::new()
+            .with_name(OPTIONAL_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::Enum)
+            .with_description("Optional".to_string())
+            .with_enum_options(vec![
+                "One".to_string(),
+                "Two".to_string(),
+                "Three".to_string(),
+            ])
+            .build()
+            .expect("Failed to build property definition")
+    }
+
+    fn required_property_definition() -> PropertyDefinition {
+        PropertyDefinitionBuilder::new()
+            .with_name(REQUIRED_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::String)
+            .with_description("Required".to_string())
+            .with_required(true)
+            .build()
+            .expect("Failed to build property definition")
+    }
+
+    fn make_record() -> Record {
+        let associated_agent = AssociatedAgentBuilder::new()
+            .with_agent_id(PUBLIC_KEY.to_string())
+            .with_timestamp(TIMESTAMP)
+            .build()
+            .expect("Failed to build AssociatedAgent");
+
+        RecordBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_schema(SCHEMA_NAME.to_string())
+            .with_owners(vec![associated_agent.clone()])
+            .with_custodians(vec![associated_agent.clone()])
+            .with_field_final(false)
+            .build()
+            .expect("Failed to build new_record")
+    }
+
+    fn make_child_record(record_id: &str, agent_key: &str) -> Record {
+        let associated_agent = AssociatedAgentBuilder::new()
+            .with_agent_id(agent_key.to_string())
+            .with_timestamp(TIMESTAMP)
+            .build()
+            .expect("Failed to build AssociatedAgent");
+
+        RecordBuilder::new()
+            .with_record_id(record_id.to_string())
+            .with_schema(SCHEMA_NAME.to_string())
+            .with_owners(vec![associated_agent.clone()])
+            .with_custodians(vec![associated_agent])
             .with_field_final(false)
             .build()
             .expect("Failed to build new_record")
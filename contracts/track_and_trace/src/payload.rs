@@ -21,13 +21,28 @@ cfg_if! {
 }
 
 use grid_sdk::protocol::track_and_trace::payload::{
-    Action, CreateRecordAction, TrackAndTracePayload,
+    Action, CreateRecordAction, ReauthorizeReporterAction, TrackAndTracePayload,
+    UpdateRecordLocationAction,
 };
 
 pub fn validate_payload(payload: &TrackAndTracePayload) -> Result<(), ApplyError> {
     validate_timestamp(*payload.timestamp())?;
     match payload.action() {
         Action::CreateRecord(action_payload) => validate_record_create_action(action_payload),
+        Action::AggregateRecords(action_payload) => validate_record_contents(
+            action_payload.record_id(),
+            action_payload.child_record_ids(),
+        ),
+        Action::DisaggregateRecords(action_payload) => validate_record_contents(
+            action_payload.record_id(),
+            action_payload.child_record_ids(),
+        ),
+        Action::UpdateRecordLocation(action_payload) => {
+            validate_update_record_location_action(action_payload)
+        }
+        Action::ReauthorizeReporter(action_payload) => {
+            validate_reauthorize_reporter_action(action_payload)
+        }
         _ => Ok(()),
     }
 }
@@ -49,6 +64,84 @@ fn validate_record_create_action(
     Ok(())
 }
 
+fn validate_update_record_location_action(
+    update_record_location_action: &UpdateRecordLocationAction,
+) -> Result<(), ApplyError> {
+    if update_record_location_action.record_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Record id cannot be empty string",
+        )));
+    }
+
+    if update_record_location_action.location_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Location id cannot be empty string",
+        )));
+    }
+    Ok(())
+}
+
+fn validate_reauthorize_reporter_action(
+    reauthorize_reporter_action: &ReauthorizeReporterAction,
+) -> Result<(), ApplyError> {
+    if reauthorize_reporter_action.record_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Record id cannot be empty string",
+        )));
+    }
+
+    if reauthorize_reporter_action.reporter_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Reporter id cannot be empty string",
+        )));
+    }
+
+    if reauthorize_reporter_action.properties().is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Properties cannot be empty",
+        )));
+    }
+    Ok(())
+}
+
+fn validate_record_contents(
+    record_id: &str,
+    child_record_ids: &[String],
+) -> Result<(), ApplyError> {
+    if record_id == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Record id cannot be empty string",
+        )));
+    }
+
+    if child_record_ids.is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Child record ids cannot be empty",
+        )));
+    }
+
+    for (i, child_record_id) in child_record_ids.iter().enumerate() {
+        if child_record_id == "" {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Child record id cannot be empty string",
+            )));
+        }
+        if child_record_id == record_id {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record {} cannot contain itself",
+                record_id
+            )));
+        }
+        if child_record_ids[..i].contains(child_record_id) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Child record {} is listed more than once",
+                child_record_id
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn validate_timestamp(timestamp: u64) -> Result<(), ApplyError> {
     match timestamp {
         0 => Err(ApplyError::InvalidTransaction(String::from(
@@ -63,9 +156,12 @@ mod tests {
     use super::*;
 
     use grid_sdk::protos::track_and_trace_payload::{
+        AggregateRecordsAction as AggregateRecordsActionProto,
         CreateRecordAction as CreateRecordActionProto,
+        ReauthorizeReporterAction as ReauthorizeReporterActionProto,
         TrackAndTracePayload as TrackAndTracePayloadProto,
         TrackAndTracePayload_Action as ActionProto,
+        UpdateRecordLocationAction as UpdateRecordLocationActionProto,
     };
     use grid_sdk::protos::IntoNative;
 
@@ -169,4 +265,82 @@ mod tests {
             "Payload should be valid"
         );
     }
+
+    #[test]
+    /// Test that an error is returned if the payload with AggregateRecordsAction has no
+    /// child record ids.
+    fn test_validate_payload_aggregate_children_missing() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::AGGREGATE_RECORDS);
+        payload_proto.set_timestamp(2);
+        let mut action = AggregateRecordsActionProto::new();
+        action.set_record_id("my_pallet".to_string());
+        payload_proto.set_aggregate_records(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload missing child record ids, should return error"),
+            Err(err) => assert!(err.to_string().contains("Child record ids cannot be empty")),
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if the payload with AggregateRecordsAction lists the
+    /// parent record as one of its children.
+    fn test_validate_payload_aggregate_contains_itself() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::AGGREGATE_RECORDS);
+        payload_proto.set_timestamp(2);
+        let mut action = AggregateRecordsActionProto::new();
+        action.set_record_id("my_pallet".to_string());
+        action.set_child_record_ids(vec!["my_pallet".to_string()].into());
+        payload_proto.set_aggregate_records(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload aggregating a record into itself, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("Record my_pallet cannot contain itself")),
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if the payload with UpdateRecordLocationAction is missing
+    /// the location id.
+    fn test_validate_payload_update_location_missing() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::UPDATE_RECORD_LOCATION);
+        payload_proto.set_timestamp(2);
+        let mut action = UpdateRecordLocationActionProto::new();
+        action.set_record_id("my_record".to_string());
+        payload_proto.set_update_record_location(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload missing location id, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("Location id cannot be empty string")),
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if the payload with ReauthorizeReporterAction does not
+    /// list any properties.
+    fn test_validate_payload_reauthorize_properties_missing() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::REAUTHORIZE_REPORTER);
+        payload_proto.set_timestamp(2);
+        let mut action = ReauthorizeReporterActionProto::new();
+        action.set_record_id("my_record".to_string());
+        action.set_reporter_id("reporter_key".to_string());
+        payload_proto.set_reauthorize_reporter(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload missing properties, should return error"),
+            Err(err) => assert!(err.to_string().contains("Properties cannot be empty")),
+        }
+    }
 }
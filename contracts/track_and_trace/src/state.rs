@@ -23,9 +23,13 @@ cfg_if! {
 }
 
 use grid_sdk::{
+    locations::addressing::compute_gs1_location_address,
     pike::addressing::compute_agent_address,
+    products::addressing::compute_gs1_product_address,
     protocol::{
+        location::state::{Location, LocationList},
         pike::state::{Agent, AgentList},
+        product::state::{Product, ProductList},
         schema::state::{Schema, SchemaList},
         track_and_trace::state::{
             Property, PropertyList, PropertyListBuilder, PropertyPage, PropertyPageList,
@@ -179,6 +183,64 @@ impl<'a> TrackAndTraceState<'a> {
         }
     }
 
+    /// Gets a GS1 product by its GTIN. Handles retrieving the correct product from a
+    /// ProductList.
+    pub fn get_product(&self, product_id: &str) -> Result<Option<Product>, ApplyError> {
+        let address = compute_gs1_product_address(product_id);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let products = match ProductList::from_bytes(packed.as_slice()) {
+                    Ok(products) => products,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize product list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the product with the correct gtin
+                for product in products.products() {
+                    if product.product_id() == product_id {
+                        return Ok(Some(product.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Gets a GS1 location by its GLN. Handles retrieving the correct location from a
+    /// LocationList.
+    pub fn get_location(&self, location_id: &str) -> Result<Option<Location>, ApplyError> {
+        let address = compute_gs1_location_address(location_id);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let locations = match LocationList::from_bytes(packed.as_slice()) {
+                    Ok(locations) => locations,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize location list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the location with the correct gln
+                for location in locations.locations() {
+                    if location.location_id() == location_id {
+                        return Ok(Some(location.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
     pub fn get_property(
         &self,
         record_id: &str,
@@ -0,0 +1,127 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex};
+
+use futures::channel::mpsc::{channel, Receiver, Sender};
+use grid_sdk::change_log::ChangeLogEntry;
+
+/// The number of commits that may be waiting to be sent to a subscriber. A subscriber that falls
+/// further behind is disconnected, and has to catch up from the change log when it reconnects.
+const SUBSCRIBER_BUFFER_SIZE: usize = 64;
+
+/// The changes of a single commit, in order
+pub type CommitChanges = Arc<Vec<ChangeLogEntry>>;
+
+/// Publishes the changes of each commit to every subscriber
+#[derive(Clone, Default)]
+pub struct ChangeBroadcaster {
+    subscribers: Arc<Mutex<Vec<Sender<CommitChanges>>>>,
+}
+
+impl ChangeBroadcaster {
+    pub fn new() -> Self {
+        ChangeBroadcaster::default()
+    }
+
+    /// Returns a receiver of the changes of every commit published from now on
+    pub fn subscribe(&self) -> Receiver<CommitChanges> {
+        let (sender, receiver) = channel(SUBSCRIBER_BUFFER_SIZE);
+        match self.subscribers.lock() {
+            Ok(mut subscribers) => subscribers.push(sender),
+            // The receiver ends at once, as the sender has been dropped
+            Err(_) => error!("Unable to subscribe to changes: mutex lock poisoned"),
+        }
+        receiver
+    }
+
+    /// Sends the changes of a commit to every subscriber, dropping the subscribers that have
+    /// disconnected or fallen behind
+    pub fn publish(&self, changes: Vec<ChangeLogEntry>) {
+        let changes = Arc::new(changes);
+        let mut subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers,
+            Err(_) => {
+                error!("Unable to publish changes: mutex lock poisoned");
+                return;
+            }
+        };
+        let count = subscribers.len();
+        *subscribers = subscribers
+            .drain(..)
+            .filter_map(
+                |mut subscriber| match subscriber.try_send(changes.clone()) {
+                    Ok(()) => Some(subscriber),
+                    Err(_) => None,
+                },
+            )
+            .collect();
+        if subscribers.len() < count {
+            debug!("Dropped {} change subscribers", count - subscribers.len());
+        }
+    }
+
+    /// Ends the stream of every subscriber
+    pub fn close(&self) {
+        match self.subscribers.lock() {
+            Ok(mut subscribers) => subscribers.clear(),
+            Err(_) => error!("Unable to close change subscribers: mutex lock poisoned"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::executor::block_on;
+    use futures::stream::StreamExt;
+
+    fn entry(commit_num: i64) -> ChangeLogEntry {
+        ChangeLogEntry {
+            commit_id: format!("commit-{}", commit_num),
+            commit_num,
+            service_id: None,
+            entity_type: "product".to_string(),
+            entity_id: "762111906130".to_string(),
+            event_type: "product.created".to_string(),
+            payload: "{}".to_string(),
+        }
+    }
+
+    #[test]
+    // Test that each subscriber receives the commits published after it subscribed, and that a
+    // subscriber that falls behind is disconnected rather than holding up the others
+    fn test_publish_to_subscribers() {
+        let broadcaster = ChangeBroadcaster::new();
+        broadcaster.publish(vec![entry(1)]);
+
+        let mut receiver = broadcaster.subscribe();
+        broadcaster.publish(vec![entry(2)]);
+        let changes = block_on(receiver.next()).expect("Receiver ended");
+        assert_eq!(*changes, vec![entry(2)]);
+
+        let slow = broadcaster.subscribe();
+        for commit_num in 3..(3 + SUBSCRIBER_BUFFER_SIZE as i64 + 2) {
+            broadcaster.publish(vec![entry(commit_num)]);
+            block_on(receiver.next()).expect("Receiver ended");
+        }
+        let received = block_on(slow.collect::<Vec<_>>());
+        assert!(received.len() <= SUBSCRIBER_BUFFER_SIZE + 1);
+        assert_eq!(received[0][0].commit_num, 3);
+
+        broadcaster.close();
+        assert!(block_on(receiver.next()).is_none());
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use grid_sdk::change_log::{ChangeLogEntry, ChangeLogStore};
+use grid_sdk::commits::CommitStore;
+use grid_sdk::store::StoreFactory;
+
+use crate::event::entity::EntityChangeReader;
+use crate::event::{CommitEvent, EventError, EventHandler};
+
+use super::ChangeBroadcaster;
+
+/// Records the entity changes of each commit in the change log and publishes them to the
+/// subscribers of the change stream
+///
+/// The handler reads the changes from the stores, so it must come after the handler that indexes
+/// the commit.
+#[derive(Clone)]
+pub struct ChangeLogEventHandler {
+    reader: EntityChangeReader,
+    commit_store: Arc<dyn CommitStore>,
+    change_log_store: Arc<dyn ChangeLogStore>,
+    broadcaster: ChangeBroadcaster,
+}
+
+impl ChangeLogEventHandler {
+    pub fn new(
+        store_factory: &dyn StoreFactory,
+        change_log_store: Arc<dyn ChangeLogStore>,
+        broadcaster: ChangeBroadcaster,
+    ) -> Self {
+        ChangeLogEventHandler {
+            reader: EntityChangeReader::new(store_factory),
+            commit_store: store_factory.get_grid_commit_store().into(),
+            change_log_store,
+            broadcaster,
+        }
+    }
+}
+
+impl EventHandler for ChangeLogEventHandler {
+    fn name(&self) -> &str {
+        "change-log"
+    }
+
+    fn handle_event(&self, event: &CommitEvent) -> Result<(), EventError> {
+        let commit = self
+            .commit_store
+            .get_commit_by_commit_id(&event.id)?
+            .ok_or_else(|| EventError(format!("Commit {} has not been indexed", event.id)))?;
+
+        let entries = self
+            .reader
+            .read_changes(event)?
+            .into_iter()
+            .map(|change| {
+                Ok(ChangeLogEntry {
+                    commit_id: change.commit_id.clone(),
+                    commit_num: change.commit_num,
+                    service_id: change.service_id.clone(),
+                    entity_type: change.entity_type.to_string(),
+                    entity_id: change.entity_id.clone(),
+                    event_type: change.event_type.clone(),
+                    payload: serde_json::to_string(&change).map_err(|err| {
+                        EventError(format!("Unable to serialize change: {}", err))
+                    })?,
+                })
+            })
+            .collect::<Result<Vec<_>, EventError>>()?;
+
+        // Commits without changes are still recorded, as they replace any abandoned by a fork
+        self.change_log_store.add_entries(
+            commit.commit_num,
+            event.service_id.as_deref(),
+            entries.clone(),
+        )?;
+
+        if !entries.is_empty() {
+            debug!("Publishing {} changes for {}", entries.len(), event);
+            self.broadcaster.publish(entries);
+        }
+
+        Ok(())
+    }
+
+    fn cloned_box(&self) -> Box<dyn EventHandler> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::executor::block_on;
+    use futures::stream::StreamExt;
+    use grid_sdk::change_log::MemoryChangeLogStore;
+    use grid_sdk::commits::store::Commit;
+    use grid_sdk::products::addressing::compute_gs1_product_address;
+    use grid_sdk::products::store::Product;
+    use grid_sdk::protocol::product::state::{
+        ProductBuilder, ProductListBuilder, ProductNamespace,
+    };
+    use grid_sdk::protos::IntoBytes;
+    use grid_sdk::store::memory::MemoryStoreFactory;
+    use serde_json::Value as JsonValue;
+
+    use crate::event::StateChange;
+
+    const GTIN: &str = "762111906130";
+
+    #[test]
+    // Test that the changes of a commit are recorded in the change log and published, and that
+    // handling the commit again replaces them rather than recording them twice
+    fn test_changes_are_recorded_and_published() {
+        let factory = MemoryStoreFactory::new();
+        let change_log_store = MemoryChangeLogStore::new();
+        let broadcaster = ChangeBroadcaster::new();
+        let mut receiver = broadcaster.subscribe();
+        let handler =
+            ChangeLogEventHandler::new(&factory, Arc::new(change_log_store.clone()), broadcaster);
+
+        let created = index_product(&factory, "commit-1", 1);
+        handler
+            .handle_event(&created)
+            .expect("Failed to handle event");
+        handler
+            .handle_event(&created)
+            .expect("Failed to handle event");
+
+        let entries = change_log_store
+            .list_entries(0, None, 10)
+            .expect("Failed to list entries");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].commit_id, "commit-1");
+        assert_eq!(entries[0].entity_type, "product");
+        assert_eq!(entries[0].entity_id, GTIN);
+        assert_eq!(entries[0].event_type, "product.created");
+
+        let payload: JsonValue =
+            serde_json::from_str(&entries[0].payload).expect("Invalid payload");
+        assert_eq!(payload["event_type"], "product.created");
+        assert_eq!(payload["data"]["product_id"], GTIN);
+
+        let published = block_on(receiver.next()).expect("Receiver ended");
+        assert_eq!(*published, entries);
+    }
+
+    #[test]
+    // Test that an event is not handled until its commit has been indexed, so that it is retried
+    fn test_unindexed_commit_fails() {
+        let factory = MemoryStoreFactory::new();
+        let handler = ChangeLogEventHandler::new(
+            &factory,
+            Arc::new(MemoryChangeLogStore::new()),
+            ChangeBroadcaster::new(),
+        );
+
+        let event = CommitEvent {
+            service_id: None,
+            id: "commit-1".to_string(),
+            height: Some(1),
+            state_changes: vec![],
+        };
+        assert!(handler.handle_event(&event).is_err());
+    }
+
+    /// Stores a product and its commit as the database handler would, returning the event
+    fn index_product(
+        factory: &MemoryStoreFactory,
+        commit_id: &str,
+        commit_num: i64,
+    ) -> CommitEvent {
+        let address = compute_gs1_product_address(GTIN);
+
+        factory
+            .get_grid_commit_store()
+            .add_commit(Commit {
+                commit_id: commit_id.to_string(),
+                commit_num,
+                service_id: None,
+            })
+            .expect("Failed to add commit");
+        factory
+            .get_grid_product_store()
+            .add_product(Product {
+                product_id: GTIN.to_string(),
+                product_address: address.clone(),
+                product_namespace: "GS1".to_string(),
+                owner: "org-1".to_string(),
+                start_commit_num: commit_num,
+                end_commit_num: i64::MAX,
+                service_id: None,
+                properties: vec![],
+            })
+            .expect("Failed to add product");
+
+        let product = ProductBuilder::new()
+            .with_product_id(GTIN.to_string())
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_owner("org-1".to_string())
+            .with_properties(vec![])
+            .build()
+            .expect("Failed to build product");
+        let value = ProductListBuilder::new()
+            .with_products(vec![product])
+            .build()
+            .expect("Failed to build product list")
+            .into_bytes()
+            .expect("Failed to serialize product list");
+
+        CommitEvent {
+            service_id: None,
+            id: commit_id.to_string(),
+            height: Some(commit_num as u64),
+            state_changes: vec![StateChange::Set {
+                key: address,
+                value,
+            }],
+        }
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Live entity changes.
+//!
+//! `ChangeLogEventHandler` records the entity-level changes of each indexed commit in a
+//! `ChangeLogStore` and then publishes them through a `ChangeBroadcaster`, which the REST API
+//! subscribes to when a client opens the change stream. A client that reconnects catches up on
+//! the commits it missed from the change log before it is sent live changes again.
+
+mod broadcaster;
+mod handler;
+
+pub use broadcaster::ChangeBroadcaster;
+pub use handler::ChangeLogEventHandler;
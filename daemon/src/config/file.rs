@@ -0,0 +1,120 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The layout of the gridd configuration file
+//!
+//! Every setting is optional; a missing setting falls back to the default, and may still be
+//! overridden by an environment variable or a command line flag. Sections for features that
+//! gridd was built without are accepted, but ignored.
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    /// The Sawtooth or Splinter endpoint, optionally prefixed with `sawtooth:` or `splinter:`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<DatabaseSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log: Option<LogSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rest_api: Option<RestApiSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<EventSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub splinter: Option<SplinterSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integration: Option<IntegrationSection>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DatabaseSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The most connections each of gridd's connection pools opens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool_size: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LogSection {
+    /// The level of every module without its own level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// Levels by module name, such as `hyper = "warn"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modules: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RestApiSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bind: Option<String>,
+    /// The number of threads that run the REST API's database queries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_executor_threads: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_auth: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_token_file: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EventSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// Either `halt` or `continue`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_policy: Option<String>,
+    /// The number of commits the commit store may trail the ledger while gridd is ready
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_commit_lag: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TlsSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cert: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_ca: Option<String>,
+    /// Scopes by client certificate common name, such as `acme-gateway = "org:acme001"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_scopes: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SplinterSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_key_dir: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IntegrationSection {
+    /// The base name of the private signing key file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
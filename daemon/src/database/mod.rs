@@ -26,6 +26,9 @@ use diesel::{
 
 pub use super::database::error::{ConnectionError, DatabaseError};
 
+/// The most connections a pool holds by default, matching r2d2's default
+pub const DEFAULT_POOL_SIZE: u32 = 10;
+
 pub struct Connection(PooledConnection<ConnectionManager<PgConnection>>);
 
 impl Deref for Connection {
@@ -41,14 +44,16 @@ pub struct ConnectionPool<C: diesel::Connection + 'static> {
 }
 
 impl<C: diesel::Connection> ConnectionPool<C> {
-    pub fn new(database_url: &str) -> Result<Self, DatabaseError> {
+    /// Builds a pool that holds at most `max_size` connections to the database
+    pub fn with_max_size(database_url: &str, max_size: u32) -> Result<Self, DatabaseError> {
         Ok(ConnectionPool {
-            pool: Pool::new(ConnectionManager::<C>::new(database_url)).map_err(|err| {
-                DatabaseError::ConnectionError {
+            pool: Pool::builder()
+                .max_size(max_size)
+                .build(ConnectionManager::<C>::new(database_url))
+                .map_err(|err| DatabaseError::ConnectionError {
                     context: "Failed to build connection pool".to_string(),
                     source: Box::new(err),
-                }
-            })?,
+                })?,
         })
     }
 
@@ -109,6 +109,8 @@ impl From<flexi_logger::FlexiLoggerError> for DaemonError {
 #[derive(Debug, PartialEq)]
 pub enum ConfigurationError {
     MissingValue(String),
+    InvalidValue(String),
+    InvalidFile(String),
 }
 
 impl Error for ConfigurationError {}
@@ -119,6 +121,10 @@ impl fmt::Display for ConfigurationError {
             ConfigurationError::MissingValue(config_field_name) => {
                 write!(f, "Missing configuration for {}", config_field_name)
             }
+            ConfigurationError::InvalidValue(msg) => write!(f, "Invalid configuration: {}", msg),
+            ConfigurationError::InvalidFile(msg) => {
+                write!(f, "Invalid configuration file: {}", msg)
+            }
         }
     }
 }
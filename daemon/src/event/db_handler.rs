@@ -23,7 +23,8 @@ use grid_sdk::protocol::schema::state::PropertyValue;
     feature = "pike",
     feature = "schema",
     feature = "product",
-    feature = "location"
+    feature = "location",
+    feature = "purchase-order"
 ))]
 use grid_sdk::protos::FromBytes;
 #[cfg(feature = "location")]
@@ -36,6 +37,8 @@ use grid_sdk::{
     protocol::location::state::LocationList,
 };
 
+#[cfg(feature = "search")]
+use grid_sdk::search::{store::SearchEntry, DieselSearchStore, SearchStore};
 #[cfg(feature = "pike")]
 use grid_sdk::{
     pike::{
@@ -56,6 +59,18 @@ use grid_sdk::{
     },
     protocol::product::state::ProductList,
 };
+#[cfg(feature = "purchase-order")]
+use grid_sdk::{
+    protocol::purchase_order::state::{
+        PurchaseOrder as StatePurchaseOrder, PurchaseOrderList,
+        PurchaseOrderVersion as StatePurchaseOrderVersion,
+    },
+    purchase_order::{
+        addressing::GRID_PURCHASE_ORDER_NAMESPACE,
+        store::{PurchaseOrder, PurchaseOrderVersion, PurchaseOrderVersionRevision},
+        DieselPurchaseOrderStore, PurchaseOrderStore,
+    },
+};
 #[cfg(feature = "track-and-trace")]
 use grid_sdk::{
     protocol::schema::state::DataType,
@@ -64,12 +79,12 @@ use grid_sdk::{
     },
     track_and_trace::{
         addressing::{
-            TRACK_AND_TRACE_PROPERTY_NAMESPACE, TRACK_AND_TRACE_PROPOSAL_NAMESPACE,
-            TRACK_AND_TRACE_RECORD_NAMESPACE,
+            page_ring_position, TRACK_AND_TRACE_PROPERTY_NAMESPACE,
+            TRACK_AND_TRACE_PROPOSAL_NAMESPACE, TRACK_AND_TRACE_RECORD_NAMESPACE,
         },
         store::{
             AssociatedAgent, LatLongValue as TntLatLongValue, Property, Proposal, Record,
-            ReportedValue as StoreReportedValue, Reporter,
+            ReportedValue as StoreReportedValue, Reporter, TrackAndTraceStoreError,
         },
         DieselTrackAndTraceStore, TrackAndTraceStore,
     },
@@ -83,7 +98,7 @@ use grid_sdk::{
         DieselSchemaStore, SchemaStore,
     },
 };
-#[cfg(feature = "pike")]
+#[cfg(any(feature = "pike", feature = "track-and-trace"))]
 use std::collections::HashMap;
 use std::i64;
 
@@ -95,10 +110,14 @@ use super::{CommitEvent, EventError, EventHandler, StateChange, IGNORED_NAMESPAC
     feature = "pike",
     feature = "schema",
     feature = "product",
-    feature = "location"
+    feature = "location",
+    feature = "purchase-order"
 ))]
 pub const MAX_COMMIT_NUM: i64 = i64::MAX;
 
+/// The name the database handler's dead letters are recorded under
+pub const DATABASE_HANDLER_NAME: &str = "database";
+
 pub struct DatabaseEventHandler<C: diesel::Connection + 'static> {
     connection_pool: ConnectionPool<C>,
     commit_store: DieselCommitStore<C>,
@@ -108,8 +127,12 @@ pub struct DatabaseEventHandler<C: diesel::Connection + 'static> {
     location_store: DieselLocationStore<C>,
     #[cfg(feature = "product")]
     product_store: DieselProductStore<C>,
+    #[cfg(feature = "purchase-order")]
+    purchase_order_store: DieselPurchaseOrderStore<C>,
     #[cfg(feature = "schema")]
     schema_store: DieselSchemaStore<C>,
+    #[cfg(feature = "search")]
+    search_store: DieselSearchStore<C>,
     #[cfg(feature = "track-and-trace")]
     tnt_store: DieselTrackAndTraceStore<C>,
 }
@@ -123,8 +146,12 @@ impl DatabaseEventHandler<diesel::pg::PgConnection> {
         let location_store = DieselLocationStore::new(connection_pool.pool.clone());
         #[cfg(feature = "product")]
         let product_store = DieselProductStore::new(connection_pool.pool.clone());
+        #[cfg(feature = "purchase-order")]
+        let purchase_order_store = DieselPurchaseOrderStore::new(connection_pool.pool.clone());
         #[cfg(feature = "schema")]
         let schema_store = DieselSchemaStore::new(connection_pool.pool.clone());
+        #[cfg(feature = "search")]
+        let search_store = DieselSearchStore::new(connection_pool.pool.clone());
         #[cfg(feature = "track-and-trace")]
         let tnt_store = DieselTrackAndTraceStore::new(connection_pool.pool.clone());
 
@@ -137,8 +164,12 @@ impl DatabaseEventHandler<diesel::pg::PgConnection> {
             location_store,
             #[cfg(feature = "product")]
             product_store,
+            #[cfg(feature = "purchase-order")]
+            purchase_order_store,
             #[cfg(feature = "schema")]
             schema_store,
+            #[cfg(feature = "search")]
+            search_store,
             #[cfg(feature = "track-and-trace")]
             tnt_store,
         }
@@ -146,6 +177,10 @@ impl DatabaseEventHandler<diesel::pg::PgConnection> {
 }
 
 impl EventHandler for DatabaseEventHandler<diesel::pg::PgConnection> {
+    fn name(&self) -> &str {
+        DATABASE_HANDLER_NAME
+    }
+
     fn handle_event(&self, event: &CommitEvent) -> Result<(), EventError> {
         debug!("Received commit event: {}", event);
 
@@ -213,6 +248,10 @@ impl EventHandler for DatabaseEventHandler<diesel::pg::PgConnection> {
                     #[cfg(feature = "pike")]
                     DbInsertOperation::Organizations(orgs) => {
                         debug!("Inserting {} organizations", orgs.len());
+                        #[cfg(feature = "search")]
+                        orgs.iter().try_for_each(|org| {
+                            self.search_store.index_entry(SearchEntry::from(org))
+                        })?;
                         orgs.into_iter()
                             .try_for_each(|org| self.pike_store.add_organization(org))?;
                     }
@@ -225,6 +264,7 @@ impl EventHandler for DatabaseEventHandler<diesel::pg::PgConnection> {
                     }
                     #[cfg(feature = "track-and-trace")]
                     DbInsertOperation::Properties(properties, reporters) => {
+                        prune_overwritten_pages(&self.tnt_store, &properties)?;
                         debug!("Inserting {} properties", properties.len());
                         self.tnt_store.add_properties(properties)?;
                         debug!("Inserting {} reporters", reporters.len());
@@ -250,27 +290,46 @@ impl EventHandler for DatabaseEventHandler<diesel::pg::PgConnection> {
                     #[cfg(feature = "location")]
                     DbInsertOperation::Locations(locations) => {
                         debug!("Inserting {} locations", locations.len());
+                        #[cfg(feature = "search")]
+                        locations.iter().try_for_each(|location| {
+                            self.search_store.index_entry(SearchEntry::from(location))
+                        })?;
                         locations
                             .into_iter()
                             .try_for_each(|location| self.location_store.add_location(location))?;
                     }
                     #[cfg(feature = "location")]
                     DbInsertOperation::RemoveLocation(ref address, current_commit_num) => {
+                        #[cfg(feature = "search")]
+                        self.search_store.remove_entry(address)?;
                         self.location_store
                             .delete_location(address, current_commit_num)?;
                     }
                     #[cfg(feature = "product")]
                     DbInsertOperation::Products(products) => {
                         debug!("Inserting {} products", products.len());
+                        #[cfg(feature = "search")]
+                        products.iter().try_for_each(|product| {
+                            self.search_store.index_entry(SearchEntry::from(product))
+                        })?;
                         products
                             .into_iter()
                             .try_for_each(|product| self.product_store.add_product(product))?;
                     }
                     #[cfg(feature = "product")]
                     DbInsertOperation::RemoveProduct(ref address, current_commit_num) => {
+                        #[cfg(feature = "search")]
+                        self.search_store.remove_entry(address)?;
                         self.product_store
                             .delete_product(address, current_commit_num)?;
                     }
+                    #[cfg(feature = "purchase-order")]
+                    DbInsertOperation::PurchaseOrders(purchase_orders) => {
+                        debug!("Inserting {} purchase orders", purchase_orders.len());
+                        purchase_orders.into_iter().try_for_each(|purchase_order| {
+                            self.purchase_order_store.add_purchase_order(purchase_order)
+                        })?;
+                    }
                 };
             }
 
@@ -294,8 +353,12 @@ impl DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
         let location_store = DieselLocationStore::new(connection_pool.pool.clone());
         #[cfg(feature = "product")]
         let product_store = DieselProductStore::new(connection_pool.pool.clone());
+        #[cfg(feature = "purchase-order")]
+        let purchase_order_store = DieselPurchaseOrderStore::new(connection_pool.pool.clone());
         #[cfg(feature = "schema")]
         let schema_store = DieselSchemaStore::new(connection_pool.pool.clone());
+        #[cfg(feature = "search")]
+        let search_store = DieselSearchStore::new(connection_pool.pool.clone());
         #[cfg(feature = "track-and-trace")]
         let tnt_store = DieselTrackAndTraceStore::new(connection_pool.pool.clone());
 
@@ -308,8 +371,12 @@ impl DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
             location_store,
             #[cfg(feature = "product")]
             product_store,
+            #[cfg(feature = "purchase-order")]
+            purchase_order_store,
             #[cfg(feature = "schema")]
             schema_store,
+            #[cfg(feature = "search")]
+            search_store,
             #[cfg(feature = "track-and-trace")]
             tnt_store,
         }
@@ -317,6 +384,10 @@ impl DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
 }
 
 impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
+    fn name(&self) -> &str {
+        DATABASE_HANDLER_NAME
+    }
+
     fn handle_event(&self, event: &CommitEvent) -> Result<(), EventError> {
         debug!("Received commit event: {}", event);
 
@@ -384,6 +455,10 @@ impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
                     #[cfg(feature = "pike")]
                     DbInsertOperation::Organizations(orgs) => {
                         debug!("Inserting {} organizations", orgs.len());
+                        #[cfg(feature = "search")]
+                        orgs.iter().try_for_each(|org| {
+                            self.search_store.index_entry(SearchEntry::from(org))
+                        })?;
                         orgs.into_iter()
                             .try_for_each(|org| self.pike_store.add_organization(org))?;
                     }
@@ -397,6 +472,7 @@ impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
                     }
                     #[cfg(feature = "track-and-trace")]
                     DbInsertOperation::Properties(properties, reporters) => {
+                        prune_overwritten_pages(&self.tnt_store, &properties)?;
                         debug!("Inserting {} properties", properties.len());
                         self.tnt_store.add_properties(properties)?;
                         debug!("Inserting {} reporters", reporters.len());
@@ -422,27 +498,46 @@ impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
                     #[cfg(feature = "location")]
                     DbInsertOperation::Locations(locations) => {
                         debug!("Inserting {} locations", locations.len());
+                        #[cfg(feature = "search")]
+                        locations.iter().try_for_each(|location| {
+                            self.search_store.index_entry(SearchEntry::from(location))
+                        })?;
                         locations
                             .into_iter()
                             .try_for_each(|location| self.location_store.add_location(location))?;
                     }
                     #[cfg(feature = "location")]
                     DbInsertOperation::RemoveLocation(ref address, current_commit_num) => {
+                        #[cfg(feature = "search")]
+                        self.search_store.remove_entry(address)?;
                         self.location_store
                             .delete_location(address, current_commit_num)?;
                     }
                     #[cfg(feature = "product")]
                     DbInsertOperation::Products(products) => {
                         debug!("Inserting {} products", products.len());
+                        #[cfg(feature = "search")]
+                        products.iter().try_for_each(|product| {
+                            self.search_store.index_entry(SearchEntry::from(product))
+                        })?;
                         products
                             .into_iter()
                             .try_for_each(|product| self.product_store.add_product(product))?;
                     }
                     #[cfg(feature = "product")]
                     DbInsertOperation::RemoveProduct(ref address, current_commit_num) => {
+                        #[cfg(feature = "search")]
+                        self.search_store.remove_entry(address)?;
                         self.product_store
                             .delete_product(address, current_commit_num)?;
                     }
+                    #[cfg(feature = "purchase-order")]
+                    DbInsertOperation::PurchaseOrders(purchase_orders) => {
+                        debug!("Inserting {} purchase orders", purchase_orders.len());
+                        purchase_orders.into_iter().try_for_each(|purchase_order| {
+                            self.purchase_order_store.add_purchase_order(purchase_order)
+                        })?;
+                    }
                 };
             }
 
@@ -455,19 +550,137 @@ impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
     }
 }
 
-fn create_db_operations_from_state_changes(
+pub(super) fn create_db_operations_from_state_changes(
     state_changes: &[StateChange],
     commit_num: i64,
     service_id: Option<&String>,
 ) -> Result<Vec<DbInsertOperation>, EventError> {
+    #[cfg(feature = "track-and-trace")]
+    let state_changes = order_property_pages(state_changes);
+    #[cfg(not(feature = "track-and-trace"))]
+    let state_changes = state_changes.iter().collect::<Vec<_>>();
+
     state_changes
-        .iter()
+        .into_iter()
         .filter_map(|state_change| {
             state_change_to_db_operation(state_change, commit_num, service_id).transpose()
         })
         .collect::<Result<Vec<DbInsertOperation>, EventError>>()
 }
 
+/// Orders the property page changes of a commit so that the pages of each property are
+/// applied from the earliest to the latest page on the property page ring. State changes
+/// arrive sorted by address, which puts the first pages of a ring that has wrapped ahead of
+/// the last pages, and the reported values of the last pages would otherwise be stored as the
+/// current values.
+#[cfg(feature = "track-and-trace")]
+fn order_property_pages(state_changes: &[StateChange]) -> Vec<&StateChange> {
+    let mut ordered: Vec<Vec<(u32, &StateChange)>> = Vec::new();
+    let mut property_indexes: HashMap<&str, usize> = HashMap::new();
+
+    for state_change in state_changes {
+        let page = match state_change {
+            StateChange::Set { key, .. } if key.starts_with(TRACK_AND_TRACE_PROPERTY_NAMESPACE) => {
+                key.get(66..)
+                    .and_then(|page| u32::from_str_radix(page, 16).ok())
+                    .filter(|page| *page != 0)
+                    .map(|page| (&key[..66], page))
+            }
+            _ => None,
+        };
+
+        match page {
+            Some((property_address, page)) => match property_indexes.get(property_address) {
+                Some(index) => ordered[*index].push((page, state_change)),
+                None => {
+                    property_indexes.insert(property_address, ordered.len());
+                    ordered.push(vec![(page, state_change)]);
+                }
+            },
+            None => ordered.push(vec![(0, state_change)]),
+        }
+    }
+
+    ordered
+        .into_iter()
+        .flat_map(|mut pages| {
+            let earliest_page =
+                earliest_page_in_run(&pages.iter().map(|(page, _)| *page).collect::<Vec<_>>());
+            pages.sort_by_key(|(page, _)| page_ring_position(*page, earliest_page));
+            pages.into_iter().map(|(_, state_change)| state_change)
+        })
+        .collect()
+}
+
+/// Finds the earliest page of a run of pages on the property page ring. The pages written in
+/// a single commit only cover a small part of the ring, so the run starts right after the
+/// largest gap between neighbouring pages.
+#[cfg(feature = "track-and-trace")]
+fn earliest_page_in_run(pages: &[u32]) -> u32 {
+    let mut pages = pages.to_vec();
+    pages.sort_unstable();
+
+    let mut earliest_page = pages[0];
+    let mut largest_gap = 0;
+    for (i, page) in pages.iter().enumerate() {
+        let next_page = pages[(i + 1) % pages.len()];
+        let gap = page_ring_position(next_page, *page);
+        if gap > largest_gap {
+            largest_gap = gap;
+            earliest_page = next_page;
+        }
+    }
+
+    earliest_page
+}
+
+/// Prunes the reported values a page held on the previous pass around a property's page ring
+/// when the property moves on to the page after the ring has wrapped: either back to the first
+/// page, or on to any later page once the ring is marked as wrapped. The pruning runs before
+/// the property and the values reported in the same commit are stored, so a page that is
+/// cleared and refilled by one commit keeps its new values.
+///
+/// The pruned values are no longer returned by reads as of an earlier commit; like the
+/// contract's state, the store only holds the values that are still on the page ring.
+#[cfg(feature = "track-and-trace")]
+fn prune_overwritten_pages(
+    store: &dyn TrackAndTraceStore,
+    properties: &[Property],
+) -> Result<(), TrackAndTraceStoreError> {
+    for property in properties {
+        let previous_page = store
+            .fetch_property_with_data_type(
+                &property.record_id,
+                &property.name,
+                None,
+                property.service_id.as_deref(),
+            )?
+            .map(|(previous, _)| previous.current_page);
+
+        // Wrapping back to the first page, which sets the wrapped flag, moves to a lower page
+        // number; every later move on the wrapped ring lands on a page that held values before
+        let overwrites_page = match previous_page {
+            Some(previous_page) => property.wrapped && property.current_page != previous_page,
+            None => false,
+        };
+
+        if overwrites_page {
+            debug!(
+                "Pruning page {} of property {} of record {}",
+                property.current_page, property.name, property.record_id
+            );
+            store.prune_property_page(
+                &property.record_id,
+                &property.name,
+                property.current_page,
+                property.service_id.as_deref(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 #[allow(unused_variables)]
 fn state_change_to_db_operation(
     state_change: &StateChange,
@@ -687,6 +900,18 @@ fn state_change_to_db_operation(
                             .iter()
                             .map(|x| x.agent_id().to_string())
                             .collect(),
+                        parent: match record.parent() {
+                            "" => None,
+                            parent => Some(parent.to_string()),
+                        },
+                        product_id: match record.product_id() {
+                            "" => None,
+                            product_id => Some(product_id.to_string()),
+                        },
+                        location_id: match record.location_id() {
+                            "" => None,
+                            location_id => Some(location_id.to_string()),
+                        },
                         start_commit_num: commit_num,
                         end_commit_num: MAX_COMMIT_NUM,
                         service_id: service_id.cloned(),
@@ -784,6 +1009,21 @@ fn state_change_to_db_operation(
 
                 Ok(Some(DbInsertOperation::Products(products)))
             }
+            #[cfg(feature = "purchase-order")]
+            GRID_PURCHASE_ORDER_NAMESPACE => {
+                let purchase_orders = PurchaseOrderList::from_bytes(&value)
+                    .map_err(|err| {
+                        EventError(format!("Failed to parse purchase order list {}", err))
+                    })?
+                    .purchase_orders()
+                    .iter()
+                    .map(|purchase_order| {
+                        make_purchase_order(commit_num, service_id, purchase_order)
+                    })
+                    .collect();
+
+                Ok(Some(DbInsertOperation::PurchaseOrders(purchase_orders)))
+            }
             _ => {
                 let ignore_state_change = IGNORED_NAMESPACES
                     .iter()
@@ -814,7 +1054,7 @@ fn state_change_to_db_operation(
 }
 
 #[derive(Debug)]
-enum DbInsertOperation {
+pub(super) enum DbInsertOperation {
     #[cfg(feature = "pike")]
     Agents(Vec<Agent>),
     #[cfg(feature = "pike")]
@@ -837,6 +1077,55 @@ enum DbInsertOperation {
     RemoveLocation(String, i64),
     #[cfg(feature = "product")]
     RemoveProduct(String, i64),
+    #[cfg(feature = "purchase-order")]
+    PurchaseOrders(Vec<PurchaseOrder>),
+}
+
+#[cfg(feature = "purchase-order")]
+fn make_purchase_order(
+    start_commit_num: i64,
+    service_id: Option<&String>,
+    purchase_order: &StatePurchaseOrder,
+) -> PurchaseOrder {
+    PurchaseOrder {
+        purchase_order_uid: purchase_order.uid().to_string(),
+        workflow_status: purchase_order.workflow_status().to_string(),
+        buyer_org_id: purchase_order.buyer_org_id().to_string(),
+        seller_org_id: purchase_order.seller_org_id().to_string(),
+        is_closed: purchase_order.is_closed(),
+        accepted_version_id: purchase_order
+            .accepted_version_number()
+            .map(|version_id| version_id.to_string()),
+        versions: purchase_order
+            .versions()
+            .iter()
+            .map(make_purchase_order_version)
+            .collect(),
+        created_at: purchase_order.created_at() as i64,
+        start_commit_num,
+        end_commit_num: MAX_COMMIT_NUM,
+        service_id: service_id.cloned(),
+    }
+}
+
+#[cfg(feature = "purchase-order")]
+fn make_purchase_order_version(version: &StatePurchaseOrderVersion) -> PurchaseOrderVersion {
+    PurchaseOrderVersion {
+        version_id: version.version_id().to_string(),
+        is_draft: version.is_draft(),
+        current_revision_id: version.current_revision_id() as i64,
+        workflow_status: version.workflow_status().to_string(),
+        revisions: version
+            .revisions()
+            .iter()
+            .map(|revision| PurchaseOrderVersionRevision {
+                revision_id: revision.revision_id() as i64,
+                order_xml_v3_4: revision.order_xml_v3_4().to_string(),
+                submitter: revision.submitter().to_string(),
+                created_at: revision.created_at() as i64,
+            })
+            .collect(),
+    }
 }
 
 #[cfg(feature = "track-and-trace")]
@@ -1023,3 +1312,257 @@ fn make_location_attributes(
 
     attrs
 }
+
+#[cfg(all(test, feature = "track-and-trace"))]
+mod tests {
+    use super::*;
+
+    use grid_sdk::migrations::run_sqlite_migrations;
+    use grid_sdk::protocol::schema::state::{PropertyDefinitionBuilder, PropertyValueBuilder};
+    use grid_sdk::protocol::track_and_trace::state::{
+        PropertyBuilder, PropertyListBuilder, PropertyPageBuilder, PropertyPageListBuilder,
+        ReportedValueBuilder,
+    };
+    use grid_sdk::protos::IntoBytes;
+
+    use crate::database::DEFAULT_POOL_SIZE;
+    use grid_sdk::track_and_trace::addressing::{make_property_address, MAX_PROPERTY_PAGE};
+
+    const RECORD_ID: &str = "test_record";
+    const PROPERTY_NAME: &str = "temperature";
+
+    /// Verify that the pages of a property whose page ring has wrapped are applied from the
+    /// earliest to the latest page, even though their addresses sort the other way around.
+    #[test]
+    fn property_pages_ordered_across_ring_wrap() {
+        let mut pages = vec![MAX_PROPERTY_PAGE - 1, MAX_PROPERTY_PAGE, 1, 2];
+        let mut state_changes = pages
+            .iter()
+            .map(|page| page_state_change(*page))
+            .collect::<Vec<_>>();
+        state_changes.sort_by(|a, b| state_change_key(a).cmp(state_change_key(b)));
+
+        let reported = reported_string_values(&state_changes);
+
+        assert_eq!(
+            reported,
+            pages
+                .iter()
+                .map(|page| format!("page {}", page))
+                .collect::<Vec<_>>()
+        );
+
+        // Without a wrap the pages are applied in address order
+        pages = vec![41, 42, 43];
+        state_changes = pages.iter().map(|page| page_state_change(*page)).collect();
+        state_changes.reverse();
+
+        assert_eq!(
+            reported_string_values(&state_changes),
+            vec!["page 41", "page 42", "page 43"]
+        );
+    }
+
+    /// Verify that the earliest page of a run is found on either side of the ring's end
+    #[test]
+    fn earliest_page_of_run() {
+        assert_eq!(earliest_page_in_run(&[7]), 7);
+        assert_eq!(earliest_page_in_run(&[3, 1, 2]), 1);
+        assert_eq!(
+            earliest_page_in_run(&[1, MAX_PROPERTY_PAGE]),
+            MAX_PROPERTY_PAGE
+        );
+        assert_eq!(
+            earliest_page_in_run(&[2, MAX_PROPERTY_PAGE - 3, 1, MAX_PROPERTY_PAGE]),
+            MAX_PROPERTY_PAGE - 3
+        );
+    }
+
+    /// Verify that once a property's page ring wraps, the database handler prunes the values
+    /// each page held on the previous pass as the property moves back on to it, and keeps both
+    /// the values of the other pages and those reported to a page in the commit that moves on
+    /// to it
+    #[test]
+    fn property_page_ring_wrap_pruned() {
+        let database_path =
+            std::env::temp_dir().join(format!("grid_ring_wrap_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&database_path);
+        let connection_pool = ConnectionPool::<diesel::sqlite::SqliteConnection>::with_max_size(
+            database_path.to_str().expect("Invalid database path"),
+            DEFAULT_POOL_SIZE,
+        )
+        .expect("Failed to build connection pool");
+        run_sqlite_migrations(&connection_pool.get().expect("Failed to get connection"))
+            .expect("Failed to run migrations");
+        let handler = DatabaseEventHandler::from_sqlite_pool(connection_pool.clone());
+        let store = DieselTrackAndTraceStore::new(connection_pool.pool.clone());
+
+        let values = || {
+            store
+                .list_reported_value_reporter_to_agent_metadata(
+                    RECORD_ID,
+                    PROPERTY_NAME,
+                    None,
+                    None,
+                )
+                .expect("Failed to list reported values")
+                .into_iter()
+                .map(|value| value.string_value.expect("Missing string value"))
+                .collect::<Vec<_>>()
+        };
+
+        // The property moves on to the next page with every commit, reporting a value to it.
+        // The pages in the middle of the ring are skipped, as they are never revisited here.
+        let pages = [1, 2, MAX_PROPERTY_PAGE - 1, MAX_PROPERTY_PAGE];
+        for (commit_num, page) in (1..).zip(pages.iter()) {
+            handler
+                .handle_event(&page_commit(
+                    commit_num,
+                    *page,
+                    false,
+                    &format!("page {}", page),
+                ))
+                .expect("Failed to handle commit");
+        }
+
+        // The ring wraps, and the first page is cleared and refilled in the same commit
+        handler
+            .handle_event(&page_commit(5, 1, true, "page 1 rewritten"))
+            .expect("Failed to handle commit");
+        let wrapped_values = values();
+
+        // Every later page on the wrapped ring is overwritten in the same way
+        handler
+            .handle_event(&page_commit(6, 2, true, "page 2 rewritten"))
+            .expect("Failed to handle commit");
+        let rewritten_values = values();
+        let current = store
+            .fetch_reported_value_reporter_to_agent_metadata(RECORD_ID, PROPERTY_NAME, None, None)
+            .expect("Failed to fetch reported value")
+            .and_then(|value| value.string_value);
+
+        std::fs::remove_file(&database_path).expect("Failed to remove database");
+
+        assert_eq!(
+            wrapped_values,
+            vec![
+                "page 2".to_string(),
+                format!("page {}", MAX_PROPERTY_PAGE - 1),
+                format!("page {}", MAX_PROPERTY_PAGE),
+                "page 1 rewritten".to_string(),
+            ]
+        );
+        assert_eq!(
+            rewritten_values,
+            vec![
+                format!("page {}", MAX_PROPERTY_PAGE - 1),
+                format!("page {}", MAX_PROPERTY_PAGE),
+                "page 1 rewritten".to_string(),
+                "page 2 rewritten".to_string(),
+            ]
+        );
+        assert_eq!(current.as_deref(), Some("page 2 rewritten"));
+    }
+
+    /// A commit that moves the property on to a page and reports a single value to the page
+    fn page_commit(commit_num: u64, page: u32, wrapped: bool, value: &str) -> CommitEvent {
+        let property_definition = PropertyDefinitionBuilder::new()
+            .with_name(PROPERTY_NAME.to_string())
+            .with_data_type(DataType::String)
+            .build()
+            .expect("Failed to build property definition");
+        let property = PropertyBuilder::new()
+            .with_name(PROPERTY_NAME.to_string())
+            .with_record_id(RECORD_ID.to_string())
+            .with_property_definition(property_definition)
+            .with_reporters(vec![])
+            .with_current_page(page)
+            .with_wrapped(wrapped)
+            .build()
+            .expect("Failed to build property");
+        let property_list = PropertyListBuilder::new()
+            .with_properties(vec![property])
+            .build()
+            .expect("Failed to build property list");
+
+        CommitEvent {
+            service_id: None,
+            id: format!("commit-{}", commit_num),
+            height: Some(commit_num),
+            state_changes: vec![
+                StateChange::Set {
+                    key: make_property_address(RECORD_ID, PROPERTY_NAME, 0),
+                    value: property_list
+                        .into_bytes()
+                        .expect("Failed to serialize property list"),
+                },
+                property_page_state_change(page, vec![string_reported_value(value, commit_num)]),
+            ],
+        }
+    }
+
+    fn page_state_change(page: u32) -> StateChange {
+        property_page_state_change(
+            page,
+            vec![string_reported_value(
+                &format!("page {}", page),
+                u64::from(page),
+            )],
+        )
+    }
+
+    fn string_reported_value(string_value: &str, timestamp: u64) -> ReportedValue {
+        let value = PropertyValueBuilder::new()
+            .with_name(PROPERTY_NAME.to_string())
+            .with_data_type(DataType::String)
+            .with_string_value(string_value.to_string())
+            .build()
+            .expect("Failed to build property value");
+
+        ReportedValueBuilder::new()
+            .with_reporter_index(0)
+            .with_timestamp(timestamp)
+            .with_value(value)
+            .build()
+            .expect("Failed to build reported value")
+    }
+
+    fn property_page_state_change(page: u32, reported_values: Vec<ReportedValue>) -> StateChange {
+        let property_page = PropertyPageBuilder::new()
+            .with_name(PROPERTY_NAME.to_string())
+            .with_record_id(RECORD_ID.to_string())
+            .with_reported_values(reported_values)
+            .build()
+            .expect("Failed to build property page");
+        let property_page_list = PropertyPageListBuilder::new()
+            .with_property_pages(vec![property_page])
+            .build()
+            .expect("Failed to build property page list");
+
+        StateChange::Set {
+            key: make_property_address(RECORD_ID, PROPERTY_NAME, page),
+            value: property_page_list
+                .into_bytes()
+                .expect("Failed to serialize property page list"),
+        }
+    }
+
+    fn state_change_key(state_change: &StateChange) -> &str {
+        match state_change {
+            StateChange::Set { key, .. } => key,
+            StateChange::Delete { key } => key,
+        }
+    }
+
+    fn reported_string_values(state_changes: &[StateChange]) -> Vec<String> {
+        create_db_operations_from_state_changes(state_changes, 1, None)
+            .expect("Failed to create db operations")
+            .into_iter()
+            .flat_map(|op| match op {
+                DbInsertOperation::ReportedValues(values) => values,
+                op => panic!("Unexpected db operation {:?}", op),
+            })
+            .map(|value| value.string_value.expect("Missing string value"))
+            .collect()
+    }
+}
@@ -0,0 +1,484 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Entity-level changes derived from commit events.
+//!
+//! A commit event only carries raw state changes. The reader in this module works out which
+//! agents, organizations, schemas, products, locations and records a commit touched and compares
+//! each of them as of the commit with how it was at the commit before, which tells apart
+//! creations, updates and deletions. It must run after the commit has been indexed.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use grid_sdk::commits::CommitStore;
+use grid_sdk::locations::LocationStore;
+use grid_sdk::pike::PikeStore;
+use grid_sdk::products::ProductStore;
+use grid_sdk::schemas::SchemaStore;
+use grid_sdk::store::StoreFactory;
+use grid_sdk::track_and_trace::TrackAndTraceStore;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::rest_api::{
+    fetch_property_value_slice, fetch_record_slice, AgentSlice, GridSchemaSlice, LocationSlice,
+    OrganizationSlice, ProductSlice,
+};
+
+use super::db_handler::{create_db_operations_from_state_changes, DbInsertOperation};
+use super::{CommitEvent, EventError};
+
+/// The kinds of entity that changes are reported for
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    Agent,
+    Organization,
+    Schema,
+    Product,
+    Location,
+    Record,
+}
+
+impl EntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityType::Agent => "agent",
+            EntityType::Organization => "organization",
+            EntityType::Schema => "schema",
+            EntityType::Product => "product",
+            EntityType::Location => "location",
+            EntityType::Record => "record",
+        }
+    }
+}
+
+impl fmt::Display for EntityType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EntityType {
+    type Err = EventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "agent" => Ok(EntityType::Agent),
+            "organization" => Ok(EntityType::Organization),
+            "schema" => Ok(EntityType::Schema),
+            "product" => Ok(EntityType::Product),
+            "location" => Ok(EntityType::Location),
+            "record" => Ok(EntityType::Record),
+            _ => Err(EventError(format!(
+                "Invalid entity type: {}; expected agent, organization, schema, product, \
+                 location or record",
+                s
+            ))),
+        }
+    }
+}
+
+/// A change to a single entity made by a commit
+#[derive(Clone, Debug, Serialize)]
+pub struct EntityChange {
+    /// The kind of change, such as `product.created` or `record.property_reported`
+    pub event_type: String,
+    pub entity_type: EntityType,
+    pub entity_id: String,
+    /// The organization that owns the entity, if it is known
+    pub owner: Option<String>,
+    pub service_id: Option<String>,
+    pub commit_id: String,
+    pub commit_num: i64,
+    /// The entity as the REST API returns it; deletions carry the entity as it was before
+    pub data: JsonValue,
+}
+
+/// An entity as of some commit, along with the organization that owns it
+struct Snapshot {
+    data: JsonValue,
+    owner: Option<String>,
+}
+
+/// Reads the entity-level changes of indexed commits
+#[derive(Clone)]
+pub struct EntityChangeReader {
+    commit_store: Arc<dyn CommitStore>,
+    location_store: Arc<dyn LocationStore>,
+    pike_store: Arc<dyn PikeStore>,
+    product_store: Arc<dyn ProductStore>,
+    schema_store: Arc<dyn SchemaStore>,
+    tnt_store: Arc<dyn TrackAndTraceStore>,
+}
+
+impl EntityChangeReader {
+    pub fn new(store_factory: &dyn StoreFactory) -> Self {
+        EntityChangeReader {
+            commit_store: store_factory.get_grid_commit_store().into(),
+            location_store: store_factory.get_grid_location_store().into(),
+            pike_store: store_factory.get_grid_pike_store().into(),
+            product_store: store_factory.get_grid_product_store().into(),
+            schema_store: store_factory.get_grid_schema_store().into(),
+            tnt_store: store_factory.get_grid_track_and_trace_store().into(),
+        }
+    }
+
+    /// Returns the changes a commit made, in the order its state changes touched the entities
+    ///
+    /// Fails if the commit has not been indexed yet.
+    pub fn read_changes(&self, event: &CommitEvent) -> Result<Vec<EntityChange>, EventError> {
+        let commit = self
+            .commit_store
+            .get_commit_by_commit_id(&event.id)?
+            .ok_or_else(|| EventError(format!("Commit {} has not been indexed", event.id)))?;
+        let service_id = event.service_id.as_deref();
+
+        let mut entities: Vec<(EntityType, Vec<String>)> = vec![];
+        let mut properties: Vec<(String, String)> = vec![];
+        for op in create_db_operations_from_state_changes(
+            &event.state_changes,
+            commit.commit_num,
+            event.service_id.as_ref(),
+        )? {
+            match op {
+                DbInsertOperation::Agents(agents) => entities.extend(
+                    agents
+                        .into_iter()
+                        .map(|agent| (EntityType::Agent, vec![agent.public_key])),
+                ),
+                DbInsertOperation::Organizations(orgs) => entities.extend(
+                    orgs.into_iter()
+                        .map(|org| (EntityType::Organization, vec![org.org_id])),
+                ),
+                DbInsertOperation::GridSchemas(schemas) => entities.extend(
+                    schemas
+                        .into_iter()
+                        .map(|schema| (EntityType::Schema, vec![schema.name])),
+                ),
+                DbInsertOperation::Products(products) => entities.extend(
+                    products
+                        .into_iter()
+                        .map(|product| (EntityType::Product, vec![product.product_id])),
+                ),
+                DbInsertOperation::RemoveProduct(address, _) => {
+                    entities.push((EntityType::Product, gtins_from_address(&address)))
+                }
+                DbInsertOperation::Locations(locations) => entities.extend(
+                    locations
+                        .into_iter()
+                        .map(|location| (EntityType::Location, vec![location.location_id])),
+                ),
+                DbInsertOperation::RemoveLocation(address, _) => {
+                    entities.push((EntityType::Location, gln_from_address(&address)))
+                }
+                DbInsertOperation::Records(records, _) => entities.extend(
+                    records
+                        .into_iter()
+                        .map(|record| (EntityType::Record, vec![record.record_id])),
+                ),
+                DbInsertOperation::Properties(props, _) => entities.extend(
+                    props
+                        .into_iter()
+                        .map(|property| (EntityType::Record, vec![property.record_id])),
+                ),
+                DbInsertOperation::ReportedValues(values) => properties.extend(
+                    values
+                        .into_iter()
+                        .map(|value| (value.record_id, value.property_name)),
+                ),
+                _ => (),
+            }
+        }
+        dedup(&mut entities);
+        dedup(&mut properties);
+
+        let mut changes = vec![];
+        let change = |event_type: String,
+                      entity_type: EntityType,
+                      entity_id: &str,
+                      snapshot: Snapshot| EntityChange {
+            event_type,
+            entity_type,
+            entity_id: entity_id.to_string(),
+            owner: snapshot.owner,
+            service_id: event.service_id.clone(),
+            commit_id: commit.commit_id.clone(),
+            commit_num: commit.commit_num,
+            data: snapshot.data,
+        };
+
+        for (entity_type, candidate_ids) in entities {
+            // Deletions only name the address of the entity, from which more than one identifier
+            // may be derived; the one that existed before the commit is the one that was deleted
+            let mut found = None;
+            for id in candidate_ids {
+                let before = self.fetch(entity_type, &id, commit.commit_num - 1, service_id)?;
+                let after = self.fetch(entity_type, &id, commit.commit_num, service_id)?;
+                if before.is_some() || after.is_some() {
+                    found = Some((id, before, after));
+                    break;
+                }
+            }
+
+            match found {
+                Some((id, None, Some(after))) => {
+                    changes.push(change(
+                        format!("{}.created", entity_type),
+                        entity_type,
+                        &id,
+                        after,
+                    ));
+                }
+                Some((id, Some(before), Some(after))) if before.data != after.data => {
+                    let roles_changed = entity_type == EntityType::Agent
+                        && before.data.get("roles") != after.data.get("roles");
+                    if roles_changed {
+                        changes.push(change(
+                            "agent.roles_changed".to_string(),
+                            entity_type,
+                            &id,
+                            Snapshot {
+                                data: after.data.clone(),
+                                owner: after.owner.clone(),
+                            },
+                        ));
+                    }
+                    changes.push(change(
+                        format!("{}.updated", entity_type),
+                        entity_type,
+                        &id,
+                        after,
+                    ));
+                }
+                Some((id, Some(before), None)) => {
+                    changes.push(change(
+                        format!("{}.deleted", entity_type),
+                        entity_type,
+                        &id,
+                        before,
+                    ));
+                }
+                _ => (),
+            }
+        }
+
+        for (record_id, property_name) in properties {
+            let value = |commit_height| {
+                fetch_property_value_slice(
+                    &self.tnt_store,
+                    &record_id,
+                    &property_name,
+                    Some(commit_height),
+                    service_id,
+                )
+                .map_err(|err| EventError(err.to_string()))?
+                .map(|value| serde_json::to_value(value).map_err(to_event_error))
+                .transpose()
+            };
+            let before = value(commit.commit_num - 1)?;
+            let after = value(commit.commit_num)?;
+
+            if let Some(after) = after {
+                if before.as_ref() != Some(&after) {
+                    let owner = self.record_owner(&record_id, commit.commit_num, service_id)?;
+                    changes.push(change(
+                        "record.property_reported".to_string(),
+                        EntityType::Record,
+                        &record_id,
+                        Snapshot {
+                            data: json!({
+                                "record_id": record_id,
+                                "property_name": property_name,
+                                "value": after,
+                            }),
+                            owner,
+                        },
+                    ));
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Fetches an entity as of a commit, as the REST API would return it
+    fn fetch(
+        &self,
+        entity_type: EntityType,
+        id: &str,
+        commit_height: i64,
+        service_id: Option<&str>,
+    ) -> Result<Option<Snapshot>, EventError> {
+        if commit_height < 0 {
+            return Ok(None);
+        }
+        let height = Some(commit_height);
+
+        let snapshot = match entity_type {
+            EntityType::Agent => match self.pike_store.fetch_agent(id, height, service_id)? {
+                Some(agent) => {
+                    let owner = Some(agent.org_id.clone());
+                    let slice =
+                        AgentSlice::try_from(agent).map_err(|err| EventError(err.to_string()))?;
+                    Some((serde_json::to_value(slice), owner))
+                }
+                None => None,
+            },
+            EntityType::Organization => {
+                match self.pike_store.fetch_organization(id, height, service_id)? {
+                    Some(org) => {
+                        let owner = Some(org.org_id.clone());
+                        let slice = OrganizationSlice::try_from(org)
+                            .map_err(|err| EventError(err.to_string()))?;
+                        Some((serde_json::to_value(slice), owner))
+                    }
+                    None => None,
+                }
+            }
+            EntityType::Schema => {
+                self.schema_store
+                    .fetch_schema(id, height, service_id)?
+                    .map(|schema| {
+                        let owner = Some(schema.owner.clone());
+                        (serde_json::to_value(GridSchemaSlice::from(schema)), owner)
+                    })
+            }
+            EntityType::Product => self
+                .product_store
+                .fetch_product(id, height, service_id)?
+                .map(|product| {
+                    let owner = Some(product.owner.clone());
+                    (serde_json::to_value(ProductSlice::from(product)), owner)
+                }),
+            EntityType::Location => self
+                .location_store
+                .fetch_location(id, height, service_id)?
+                .map(|location| {
+                    let owner = Some(location.owner.clone());
+                    (serde_json::to_value(LocationSlice::from(location)), owner)
+                }),
+            EntityType::Record => {
+                match fetch_record_slice(&self.tnt_store, id, height, service_id)
+                    .map_err(|err| EventError(err.to_string()))?
+                {
+                    Some(record) => {
+                        let owner = self.record_owner(id, commit_height, service_id)?;
+                        Some((serde_json::to_value(record), owner))
+                    }
+                    None => None,
+                }
+            }
+        };
+
+        snapshot
+            .map(|(data, owner)| {
+                Ok(Snapshot {
+                    data: data.map_err(to_event_error)?,
+                    owner,
+                })
+            })
+            .transpose()
+    }
+
+    /// Returns the organization of the agent that most recently became owner of a record
+    fn record_owner(
+        &self,
+        record_id: &str,
+        commit_height: i64,
+        service_id: Option<&str>,
+    ) -> Result<Option<String>, EventError> {
+        let owner = self
+            .tnt_store
+            .list_associated_agents(&[record_id.to_string()], Some(commit_height), service_id)?
+            .into_iter()
+            .filter(|agent| agent.role == "OWNER")
+            .max_by_key(|agent| agent.timestamp);
+
+        match owner {
+            Some(owner) => Ok(self
+                .pike_store
+                .fetch_agent(&owner.agent_id, Some(commit_height), service_id)?
+                .map(|agent| agent.org_id)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Removes repeated entries, keeping the first of each
+fn dedup<T: PartialEq>(items: &mut Vec<T>) {
+    let mut seen: Vec<T> = Vec::with_capacity(items.len());
+    for item in items.drain(..) {
+        if !seen.contains(&item) {
+            seen.push(item);
+        }
+    }
+    *items = seen;
+}
+
+/// Returns the GTINs a product address may have been computed from. Addresses pad GTINs to 14
+/// digits, so shorter GTINs can only be recovered by dropping the padding.
+fn gtins_from_address(address: &str) -> Vec<String> {
+    let padded = match address.get(address.len().saturating_sub(16)..address.len() - 2) {
+        Some(padded) => padded,
+        None => return vec![],
+    };
+
+    [14, 13, 12, 8]
+        .iter()
+        .filter(|len| padded[..14 - **len].chars().all(|c| c == '0'))
+        .map(|len| padded[14 - len..].to_string())
+        .collect()
+}
+
+/// Returns the GLN a location address was computed from
+fn gln_from_address(address: &str) -> Vec<String> {
+    address
+        .get(address.len().saturating_sub(15)..address.len() - 2)
+        .map(|gln| vec![gln.to_string()])
+        .unwrap_or_default()
+}
+
+fn to_event_error(err: serde_json::Error) -> EventError {
+    EventError(format!("Unable to serialize entity: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use grid_sdk::locations::addressing::compute_gs1_location_address;
+    use grid_sdk::products::addressing::compute_gs1_product_address;
+
+    #[test]
+    // Test that the identifiers of deleted products and locations are recovered from their
+    // addresses
+    fn ids_from_addresses() {
+        assert_eq!(
+            gtins_from_address(&compute_gs1_product_address("762111906130")),
+            vec!["00762111906130", "0762111906130", "762111906130"]
+        );
+        assert_eq!(
+            gtins_from_address(&compute_gs1_product_address("12345678901234")),
+            vec!["12345678901234"]
+        );
+        assert_eq!(
+            gln_from_address(&compute_gs1_location_address("1234567890123")),
+            vec!["1234567890123"]
+        );
+    }
+}
@@ -19,9 +19,12 @@ use std::error::Error;
 use std::fmt;
 
 use grid_sdk::{
-    commits::store::CommitStoreError, locations::store::LocationStoreError,
+    change_log::ChangeLogStoreError, commits::store::CommitStoreError,
+    dead_letters::DeadLetterStoreError, locations::store::LocationStoreError,
     pike::store::PikeStoreError, products::store::ProductStoreError,
-    schemas::store::SchemaStoreError, track_and_trace::store::TrackAndTraceStoreError,
+    purchase_order::store::PurchaseOrderStoreError, schemas::store::SchemaStoreError,
+    search::store::SearchStoreError, track_and_trace::store::TrackAndTraceStoreError,
+    webhooks::store::WebhookStoreError,
 };
 
 #[derive(Debug)]
@@ -46,12 +49,24 @@ impl fmt::Display for EventError {
     }
 }
 
+impl From<ChangeLogStoreError> for EventError {
+    fn from(err: ChangeLogStoreError) -> Self {
+        EventError(format!("{}", err))
+    }
+}
+
 impl From<CommitStoreError> for EventError {
     fn from(err: CommitStoreError) -> Self {
         EventError(format!("{}", err))
     }
 }
 
+impl From<DeadLetterStoreError> for EventError {
+    fn from(err: DeadLetterStoreError) -> Self {
+        EventError(format!("{}", err))
+    }
+}
+
 impl From<LocationStoreError> for EventError {
     fn from(err: LocationStoreError) -> Self {
         EventError(format!("{}", err))
@@ -70,18 +85,36 @@ impl From<ProductStoreError> for EventError {
     }
 }
 
+impl From<PurchaseOrderStoreError> for EventError {
+    fn from(err: PurchaseOrderStoreError) -> Self {
+        EventError(format!("{}", err))
+    }
+}
+
 impl From<SchemaStoreError> for EventError {
     fn from(err: SchemaStoreError) -> Self {
         EventError(format!("{}", err))
     }
 }
 
+impl From<SearchStoreError> for EventError {
+    fn from(err: SearchStoreError) -> Self {
+        EventError(format!("{}", err))
+    }
+}
+
 impl From<TrackAndTraceStoreError> for EventError {
     fn from(err: TrackAndTraceStoreError) -> Self {
         EventError(format!("{}", err))
     }
 }
 
+impl From<WebhookStoreError> for EventError {
+    fn from(err: WebhookStoreError) -> Self {
+        EventError(format!("{}", err))
+    }
+}
+
 impl From<diesel::result::Error> for EventError {
     fn from(err: diesel::result::Error) -> Self {
         EventError(format!("{}", err))
@@ -16,10 +16,19 @@
  */
 
 pub mod db_handler;
+#[cfg(any(feature = "change-stream", feature = "webhooks"))]
+pub mod entity;
 mod error;
 
-use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, MutexGuard, Weak,
+};
 use std::thread;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "pike")]
 use grid_sdk::pike::addressing::PIKE_NAMESPACE;
@@ -38,7 +47,13 @@ cfg_if! {
 use grid_sdk::track_and_trace::addressing::TRACK_AND_TRACE_NAMESPACE;
 
 use grid_sdk::commits::store::{CommitEvent as DbCommitEvent, StateChange as DbStateChange};
+use grid_sdk::commits::CommitStore;
+use grid_sdk::dead_letters::{DeadLetter, DeadLetterStore};
 
+#[cfg(feature = "metrics")]
+use crate::metrics;
+
+use self::db_handler::DATABASE_HANDLER_NAME;
 pub use self::error::{EventError, EventIoError, EventProcessorError};
 
 const ALL_GRID_NAMESPACES: &[&str] = &[
@@ -54,6 +69,20 @@ const SABRE_NAMESPACE: &str = "00ec";
 
 const IGNORED_NAMESPACES: &[&str] = &[SABRE_NAMESPACE];
 
+/// The number of times a failed event handler is retried before its event is dead-lettered
+pub const DEFAULT_EVENT_RETRIES: u32 = 5;
+
+/// The number of commits the commit store may trail the ledger before gridd reports that it is
+/// not ready
+pub const DEFAULT_MAX_COMMIT_LAG: u64 = 10;
+
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+const DEAD_LETTER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
 /// A notification that some source has committed a set of changes to state
 #[derive(Clone)]
 pub struct CommitEvent {
@@ -159,6 +188,11 @@ impl From<StateChange> for DbStateChange {
 }
 
 pub trait EventHandler: Send {
+    /// A name for the handler that is unique among the handlers of an event processor. Dead
+    /// letters record the name of the handler that failed, so the name should not change
+    /// between runs.
+    fn name(&self) -> &str;
+
     fn handle_event(&self, event: &CommitEvent) -> Result<(), EventError>;
 
     fn cloned_box(&self) -> Box<dyn EventHandler>;
@@ -188,6 +222,16 @@ pub trait EventConnection: Send {
         last_commit_id: Option<&str>,
     ) -> Result<Self::Unsubscriber, EventIoError>;
 
+    /// Replaces a connection that has failed with a new one, which must be subscribed before
+    /// events can be received again
+    fn reconnect(&mut self) -> Result<(), EventIoError>;
+
+    /// Returns the number of commit events received from the ledger that have not been read by
+    /// `recv` yet, if the connection keeps count
+    fn backlog(&self) -> Option<u64> {
+        None
+    }
+
     fn close(self) -> Result<(), EventIoError>;
 }
 
@@ -210,18 +254,234 @@ impl<EC: EventConnection> EventConnection for Box<EC> {
         (**self).subscribe(namespaces, last_commit_id)
     }
 
+    fn reconnect(&mut self) -> Result<(), EventIoError> {
+        (**self).reconnect()
+    }
+
+    fn backlog(&self) -> Option<u64> {
+        (**self).backlog()
+    }
+
     fn close(self) -> Result<(), EventIoError> {
         (*self).close()
     }
 }
 
+/// What the event processor does once an event handler has exhausted its retries
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FailureMode {
+    /// Hold back later events until the dead letter has been replayed or discarded, so events
+    /// are always handled in order
+    Halt,
+    /// Carry on with later events, leaving the dead letter to be replayed out of order
+    Continue,
+}
+
+impl FromStr for FailureMode {
+    type Err = EventProcessorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "halt" => Ok(FailureMode::Halt),
+            "continue" => Ok(FailureMode::Continue),
+            _ => Err(EventProcessorError(format!(
+                "Invalid failure mode: {}; expected halt or continue",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for FailureMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FailureMode::Halt => f.write_str("halt"),
+            FailureMode::Continue => f.write_str("continue"),
+        }
+    }
+}
+
+/// Determines how the event processor handles events that an event handler fails on
+///
+/// A failed handler is retried with exponential backoff. Once its retries are exhausted, the
+/// event is stored as a dead letter for the handler so that it can be replayed later. If there is
+/// no dead letter store, a halting processor stops rather than drop the event.
+#[derive(Clone)]
+pub struct FailurePolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    failure_mode: FailureMode,
+    dead_letter_store: Option<Arc<dyn DeadLetterStore>>,
+}
+
+impl FailurePolicy {
+    pub fn new(max_retries: u32, failure_mode: FailureMode) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            failure_mode,
+            dead_letter_store: None,
+        }
+    }
+
+    pub fn with_dead_letter_store(mut self, dead_letter_store: Arc<dyn DeadLetterStore>) -> Self {
+        self.dead_letter_store = Some(dead_letter_store);
+        self
+    }
+
+    #[cfg(test)]
+    fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    fn backoff(&self, retry: u32) -> Duration {
+        exponential_backoff(self.initial_backoff, self.max_backoff, retry)
+    }
+}
+
+impl Default for FailurePolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_RETRIES, FailureMode::Halt)
+    }
+}
+
+/// Where an event processor subscribes from when it starts and when it reconnects
+pub enum ResumeFrom {
+    /// The current commit in the commit store. Only suitable when the store holds the commits of
+    /// this connection alone, as it does for a Sawtooth validator.
+    CommitStore(Box<dyn CommitStore>),
+    /// The last event the processor received, starting from the given commit ID. Used when the
+    /// commit store is shared with other sources, such as the services of other circuits.
+    #[cfg_attr(not(feature = "splinter-support"), allow(dead_code))]
+    LastEvent(Option<String>),
+}
+
+impl ResumeFrom {
+    fn commit_id(&self) -> Result<Option<String>, EventProcessorError> {
+        match self {
+            ResumeFrom::CommitStore(commit_store) => {
+                commit_store.get_current_commit_id().map_err(|err| {
+                    EventProcessorError(format!("Unable to get current commit ID: {}", err))
+                })
+            }
+            ResumeFrom::LastEvent(commit_id) => Ok(commit_id.clone()),
+        }
+    }
+
+    fn received(&mut self, commit_id: &str) {
+        if let ResumeFrom::LastEvent(last_commit_id) = self {
+            *last_commit_id = Some(commit_id.to_string());
+        }
+    }
+}
+
+/// Tracks whether each event processor is subscribed to its event source
+///
+/// Indexing is live while every processor that has been started is subscribed. A processor that
+/// is reconnecting, or that has stopped because of an error, holds indexing back until it is
+/// subscribed again.
+///
+/// The backlog of each connection that keeps count of the commits it has received but not yet
+/// passed on is kept as well, as the number of commits the commit store is behind by, along
+/// with the dead letters that processors are halted on.
+#[derive(Clone, Default)]
+pub struct IndexingStatus {
+    connections: Arc<Mutex<BTreeMap<String, bool>>>,
+    backlogs: Arc<Mutex<BTreeMap<String, u64>>>,
+    halted_on: Arc<Mutex<BTreeSet<i64>>>,
+}
+
+impl IndexingStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if every event connection is subscribed
+    pub fn is_live(&self) -> bool {
+        self.lock().values().all(|live| *live)
+    }
+
+    /// Returns the name of each event connection and whether it is subscribed
+    pub fn connections(&self) -> Vec<(String, bool)> {
+        self.lock()
+            .iter()
+            .map(|(name, live)| (name.clone(), *live))
+            .collect()
+    }
+
+    /// Returns the number of commits received by the event connections that have not been
+    /// processed yet, if any connection keeps count
+    pub fn backlog(&self) -> Option<u64> {
+        let backlogs = self.lock_backlogs();
+        if backlogs.is_empty() {
+            None
+        } else {
+            Some(backlogs.values().sum())
+        }
+    }
+
+    /// Returns `true` if a processor is halted until the given dead letter is replayed or
+    /// discarded, in which case no later commit has been handled
+    pub fn is_halted_on(&self, dead_letter_id: i64) -> bool {
+        self.lock_halted_on().contains(&dead_letter_id)
+    }
+
+    fn set_halted_on(&self, dead_letter_id: i64, halted: bool) {
+        if halted {
+            self.lock_halted_on().insert(dead_letter_id);
+        } else {
+            self.lock_halted_on().remove(&dead_letter_id);
+        }
+    }
+
+    fn set_backlog(&self, name: &str, backlog: u64) {
+        self.lock_backlogs().insert(name.to_string(), backlog);
+    }
+
+    fn set_live(&self, name: &str, live: bool) {
+        self.lock().insert(name.to_string(), live);
+    }
+
+    fn remove(&self, name: &str) {
+        self.lock().remove(name);
+        self.lock_backlogs().remove(name);
+    }
+
+    fn lock_backlogs(&self) -> MutexGuard<'_, BTreeMap<String, u64>> {
+        self.backlogs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_halted_on(&self) -> MutexGuard<'_, BTreeSet<i64>> {
+        self.halted_on
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, bool>> {
+        // The map is always left consistent, so a poisoned lock is still safe to use
+        self.connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
 pub struct EventProcessorShutdownHandle<Unsubscriber: EventConnectionUnsubscriber> {
-    unsubscriber: RefCell<Option<Unsubscriber>>,
+    unsubscriber: Arc<Mutex<Option<Unsubscriber>>>,
+    shutdown: Arc<AtomicBool>,
 }
 
 impl<Unsubscriber: EventConnectionUnsubscriber> EventProcessorShutdownHandle<Unsubscriber> {
     pub fn shutdown(&self) -> Result<(), EventProcessorError> {
-        if let Some(unsubscriber) = self.unsubscriber.borrow_mut().take() {
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        let unsubscriber = lock_unsubscriber(&self.unsubscriber).take();
+        if let Some(unsubscriber) = unsubscriber {
             unsubscriber
                 .unsubscribe()
                 .map_err(|err| EventProcessorError(format!("Unable to unsubscribe: {}", err)))?;
@@ -233,31 +493,86 @@ impl<Unsubscriber: EventConnectionUnsubscriber> EventProcessorShutdownHandle<Uns
 
 pub struct EventProcessor<Conn: EventConnection> {
     join_handle: thread::JoinHandle<Result<(), EventProcessorError>>,
-    unsubscriber: Option<Conn::Unsubscriber>,
+    unsubscriber: Arc<Mutex<Option<Conn::Unsubscriber>>>,
+    shutdown: Arc<AtomicBool>,
 }
 
 impl<Conn: EventConnection + 'static> EventProcessor<Conn> {
+    /// Subscribes to the connection and handles its events on a new thread
+    ///
+    /// If the connection fails, the processor reconnects with exponential backoff and
+    /// resubscribes from the commit given by `resume_from`. Whether the connection is
+    /// subscribed is reported to `indexing_status` under the connection's name.
     pub fn start(
         mut connection: Conn,
-        last_known_commit_id: Option<&str>,
+        mut resume_from: ResumeFrom,
         event_handlers: Vec<Box<dyn EventHandler>>,
+        failure_policy: FailurePolicy,
+        indexing_status: IndexingStatus,
     ) -> Result<Self, EventProcessorError> {
+        let last_known_commit_id = resume_from.commit_id()?;
         let unsubscriber = connection
-            .subscribe(ALL_GRID_NAMESPACES, last_known_commit_id)
-            .map_err(|err| EventProcessorError(format!("Unable to unsubscribe: {}", err)))?;
+            .subscribe(ALL_GRID_NAMESPACES, last_known_commit_id.as_deref())
+            .map_err(|err| EventProcessorError(format!("Unable to subscribe: {}", err)))?;
+
+        let name = connection.name().to_string();
+        indexing_status.set_live(&name, true);
+
+        // The thread only holds a weak reference, so that the unsubscriber is dropped along
+        // with the shutdown controls if they are never taken
+        let unsubscriber = Arc::new(Mutex::new(Some(unsubscriber)));
+        let thread_unsubscriber = Arc::downgrade(&unsubscriber);
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let thread_shutdown = shutdown.clone();
 
         let join_handle = thread::Builder::new()
-            .name(format!("EventProcessor[{}]", connection.name()))
+            .name(format!("EventProcessor[{}]", name))
             .spawn(move || {
-                loop {
+                let mut result = Ok(());
+
+                while !thread_shutdown.load(Ordering::SeqCst) {
                     match connection.recv() {
-                        Ok(commit_event) => handle_message(commit_event, &event_handlers),
+                        Ok(commit_event) => {
+                            #[cfg(feature = "metrics")]
+                            metrics::record_event_received(&name, commit_event.height);
+                            if let Some(backlog) = connection.backlog() {
+                                indexing_status.set_backlog(&name, backlog);
+                            }
+
+                            let commit_id = commit_event.id.clone();
+                            if let Err(err) = handle_message(
+                                commit_event,
+                                &event_handlers,
+                                &failure_policy,
+                                &indexing_status,
+                                &thread_shutdown,
+                            ) {
+                                error!("{}; aborting", err);
+                                result = Err(err);
+                                break;
+                            }
+                            resume_from.received(&commit_id);
+                        }
                         Err(EventIoError::InvalidMessage(msg)) => {
                             warn!("{}; ignoring...", msg);
                         }
+                        Err(_) if thread_shutdown.load(Ordering::SeqCst) => break,
                         Err(err) => {
-                            error!("Failed to receive events; aborting: {}", err);
-                            break;
+                            error!("Lost connection to {}; reconnecting: {}", name, err);
+                            indexing_status.set_live(&name, false);
+
+                            if !reconnect(
+                                &mut connection,
+                                &resume_from,
+                                &thread_unsubscriber,
+                                &thread_shutdown,
+                            ) {
+                                break;
+                            }
+
+                            info!("Reconnected to {}; indexing is live", name);
+                            indexing_status.set_live(&name, true);
                         }
                     }
                 }
@@ -267,11 +582,17 @@ impl<Conn: EventConnection + 'static> EventProcessor<Conn> {
                     connection.name()
                 );
 
+                if result.is_ok() {
+                    indexing_status.remove(&name);
+                } else {
+                    indexing_status.set_live(&name, false);
+                }
+
                 if let Err(err) = connection.close() {
                     error!("Unable to close connection: {}", err);
                 }
 
-                Ok(())
+                result
             })
             .map_err(|err| {
                 EventProcessorError(format!("Unable to start EventProcessor thread: {}", err))
@@ -279,7 +600,8 @@ impl<Conn: EventConnection + 'static> EventProcessor<Conn> {
 
         Ok(Self {
             join_handle,
-            unsubscriber: Some(unsubscriber),
+            unsubscriber,
+            shutdown,
         })
     }
 
@@ -291,17 +613,845 @@ impl<Conn: EventConnection + 'static> EventProcessor<Conn> {
     ) {
         (
             EventProcessorShutdownHandle {
-                unsubscriber: RefCell::new(self.unsubscriber),
+                unsubscriber: self.unsubscriber,
+                shutdown: self.shutdown,
             },
             self.join_handle,
         )
     }
 }
 
-fn handle_message(event: CommitEvent, event_handlers: &[Box<dyn EventHandler>]) {
+/// Reconnects and resubscribes until it succeeds, backing off exponentially between attempts
+///
+/// Returns `false` if the processor is shut down first.
+fn reconnect<Conn: EventConnection>(
+    connection: &mut Conn,
+    resume_from: &ResumeFrom,
+    unsubscriber: &Weak<Mutex<Option<Conn::Unsubscriber>>>,
+    shutdown: &AtomicBool,
+) -> bool {
+    // The old subscription went with the failed connection
+    if let Some(unsubscriber) = unsubscriber.upgrade() {
+        lock_unsubscriber(&unsubscriber).take();
+    }
+
+    let mut attempts = 0;
+    loop {
+        let err = match resubscribe(connection, resume_from) {
+            Ok(new_unsubscriber) => {
+                let unsubscriber = match unsubscriber.upgrade() {
+                    Some(unsubscriber) => unsubscriber,
+                    None => return true,
+                };
+                let mut unsubscriber = lock_unsubscriber(&unsubscriber);
+                *unsubscriber = Some(new_unsubscriber);
+
+                // Shutdown may have been requested while there was nothing to unsubscribe
+                if shutdown.load(Ordering::SeqCst) {
+                    if let Some(unsubscriber) = unsubscriber.take() {
+                        if let Err(err) = unsubscriber.unsubscribe() {
+                            error!("Unable to unsubscribe: {}", err);
+                        }
+                    }
+                    return false;
+                }
+                return true;
+            }
+            Err(err) => err,
+        };
+
+        let backoff =
+            exponential_backoff(RECONNECT_INITIAL_BACKOFF, RECONNECT_MAX_BACKOFF, attempts);
+        attempts += 1;
+        warn!(
+            "Unable to reconnect to {}; retrying in {:?}: {}",
+            connection.name(),
+            backoff,
+            err
+        );
+        if !sleep_unless_shutdown(backoff, shutdown) {
+            return false;
+        }
+    }
+}
+
+fn resubscribe<Conn: EventConnection>(
+    connection: &mut Conn,
+    resume_from: &ResumeFrom,
+) -> Result<Conn::Unsubscriber, EventProcessorError> {
+    connection
+        .reconnect()
+        .map_err(|err| EventProcessorError(format!("Unable to reconnect: {}", err)))?;
+
+    let last_known_commit_id = resume_from.commit_id()?;
+    connection
+        .subscribe(ALL_GRID_NAMESPACES, last_known_commit_id.as_deref())
+        .map_err(|err| EventProcessorError(format!("Unable to subscribe: {}", err)))
+}
+
+fn lock_unsubscriber<U>(unsubscriber: &Mutex<Option<U>>) -> MutexGuard<'_, Option<U>> {
+    // An unsubscriber is only ever swapped in or out, so a poisoned lock is still safe to use
+    unsubscriber
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn exponential_backoff(initial_backoff: Duration, max_backoff: Duration, retry: u32) -> Duration {
+    initial_backoff
+        .checked_mul(2u32.saturating_pow(retry))
+        .map(|backoff| backoff.min(max_backoff))
+        .unwrap_or(max_backoff)
+}
+
+/// Passes an event to each handler, retrying the handlers that fail
+///
+/// Returns an error if the event could neither be handled nor stored as a dead letter and the
+/// failure policy is to halt.
+fn handle_message(
+    event: CommitEvent,
+    event_handlers: &[Box<dyn EventHandler>],
+    failure_policy: &FailurePolicy,
+    indexing_status: &IndexingStatus,
+    shutdown: &AtomicBool,
+) -> Result<(), EventProcessorError> {
+    let mut dead_letter_ids = vec![];
+
     for handler in event_handlers {
-        if let Err(err) = handler.handle_event(&event) {
-            error!("An error occurred while handling events: {}", err);
+        let (err, attempts) =
+            match handle_with_retries(&**handler, &event, failure_policy, shutdown) {
+                Ok(()) => continue,
+                Err(failure) => failure,
+            };
+
+        if shutdown.load(Ordering::SeqCst) {
+            warn!(
+                "Shutting down before handler {} handled event {}: {}",
+                handler.name(),
+                event,
+                err
+            );
+            return Ok(());
+        }
+
+        match &failure_policy.dead_letter_store {
+            Some(store) => {
+                let id = store
+                    .add_dead_letter(DeadLetter::new(
+                        handler.name(),
+                        DbCommitEvent::from(&event),
+                        &err.to_string(),
+                        attempts as i32,
+                    ))
+                    .map_err(|store_err| {
+                        EventProcessorError(format!(
+                            "Handler {} failed to handle event {} ({}) and the event could not \
+                             be stored as a dead letter: {}",
+                            handler.name(),
+                            event,
+                            err,
+                            store_err
+                        ))
+                    })?;
+                error!(
+                    "Handler {} failed to handle event {} after {} attempts; stored as dead \
+                     letter {}: {}",
+                    handler.name(),
+                    event,
+                    attempts,
+                    id,
+                    err
+                );
+                dead_letter_ids.push(id);
+            }
+            None if failure_policy.failure_mode == FailureMode::Halt => {
+                return Err(EventProcessorError(format!(
+                    "Handler {} failed to handle event {} after {} attempts: {}",
+                    handler.name(),
+                    event,
+                    attempts,
+                    err
+                )));
+            }
+            None => error!(
+                "Handler {} failed to handle event {} after {} attempts; dropping event: {}",
+                handler.name(),
+                event,
+                attempts,
+                err
+            ),
+        }
+    }
+
+    if failure_policy.failure_mode == FailureMode::Halt {
+        if let Some(store) = &failure_policy.dead_letter_store {
+            for id in dead_letter_ids {
+                indexing_status.set_halted_on(id, true);
+                wait_for_dead_letter(&**store, id, shutdown);
+                indexing_status.set_halted_on(id, false);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Calls a handler until it succeeds or has been retried as often as the policy allows,
+/// returning the last error and the number of attempts made if it never succeeds
+fn handle_with_retries(
+    handler: &dyn EventHandler,
+    event: &CommitEvent,
+    failure_policy: &FailurePolicy,
+    shutdown: &AtomicBool,
+) -> Result<(), (EventError, u32)> {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let err = match handler.handle_event(event) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+
+        #[cfg(feature = "metrics")]
+        metrics::record_handler_error(handler.name());
+
+        if attempts > failure_policy.max_retries {
+            return Err((err, attempts));
+        }
+
+        let backoff = failure_policy.backoff(attempts - 1);
+        warn!(
+            "Handler {} failed to handle event {}; retrying in {:?}: {}",
+            handler.name(),
+            event,
+            backoff,
+            err
+        );
+        if !sleep_unless_shutdown(backoff, shutdown) {
+            return Err((err, attempts));
+        }
+    }
+}
+
+/// Blocks until a dead letter has been replayed or discarded, or the processor is shut down
+fn wait_for_dead_letter(store: &dyn DeadLetterStore, id: i64, shutdown: &AtomicBool) {
+    warn!(
+        "Halting event processing until dead letter {} is replayed or discarded",
+        id
+    );
+
+    loop {
+        match store.fetch_dead_letter(id) {
+            Ok(None) => {
+                info!("Dead letter {} resolved; resuming event processing", id);
+                return;
+            }
+            Ok(Some(_)) => (),
+            Err(err) => warn!("Unable to check dead letter {}: {}", id, err),
+        }
+
+        if !sleep_unless_shutdown(DEAD_LETTER_POLL_INTERVAL, shutdown) {
+            return;
         }
     }
 }
+
+/// Sleeps for the given duration, returning early with `false` if the processor is shut down
+fn sleep_unless_shutdown(duration: Duration, shutdown: &AtomicBool) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if shutdown.load(Ordering::SeqCst) {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        thread::sleep((deadline - now).min(SHUTDOWN_CHECK_INTERVAL));
+    }
+}
+
+/// Replays a dead letter through the handler that failed it
+///
+/// The dead letter is removed if the handler succeeds. Otherwise the failed attempt is recorded
+/// against the dead letter and the handler's error is returned.
+///
+/// A dead letter of the database handler is only replayed while no later commit has been
+/// indexed, as its state would otherwise be written over the state of the commits that followed.
+/// That holds while a processor is halted on the dead letter, or while the commit store has not
+/// reached the event's height.
+pub fn replay_dead_letter(
+    store: &dyn DeadLetterStore,
+    commit_store: &dyn CommitStore,
+    indexing_status: &IndexingStatus,
+    event_handlers: &[Box<dyn EventHandler>],
+    id: i64,
+) -> Result<(), EventError> {
+    let dead_letter = store
+        .fetch_dead_letter(id)?
+        .ok_or_else(|| EventError(format!("Dead letter {} not found", id)))?;
+
+    if dead_letter.handler == DATABASE_HANDLER_NAME && !indexing_status.is_halted_on(id) {
+        let indexed_past = match dead_letter.event.height {
+            Some(height) => commit_store.get_next_commit_num()? > height as i64,
+            // A commit without a height is numbered as it is indexed, so there is no telling
+            // whether later commits have been indexed before it
+            None => true,
+        };
+        if indexed_past {
+            return Err(EventError(format!(
+                "Dead letter {} of the {} handler cannot be replayed, as later commits may \
+                 have been indexed",
+                id, DATABASE_HANDLER_NAME
+            )));
+        }
+    }
+
+    let handler = event_handlers
+        .iter()
+        .find(|handler| handler.name() == dead_letter.handler)
+        .ok_or_else(|| {
+            EventError(format!(
+                "Dead letter {} is for unknown handler {}",
+                id, dead_letter.handler
+            ))
+        })?;
+
+    match handler.handle_event(&CommitEvent::from(dead_letter.event)) {
+        Ok(()) => {
+            store.remove_dead_letter(id)?;
+            Ok(())
+        }
+        Err(err) => {
+            store.record_attempt(id, &err.to_string())?;
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::mpsc::{channel, Receiver, Sender};
+
+    use grid_sdk::commits::{store::Commit, MemoryCommitStore};
+    use grid_sdk::dead_letters::MemoryDeadLetterStore;
+
+    /// An event handler that fails a set number of times before succeeding
+    #[derive(Clone)]
+    struct FlakyHandler {
+        failures: Arc<AtomicUsize>,
+        calls: Arc<AtomicUsize>,
+        name: &'static str,
+    }
+
+    impl FlakyHandler {
+        fn new(failures: usize) -> Self {
+            Self {
+                failures: Arc::new(AtomicUsize::new(failures)),
+                calls: Arc::new(AtomicUsize::new(0)),
+                name: "flaky",
+            }
+        }
+
+        fn named(mut self, name: &'static str) -> Self {
+            self.name = name;
+            self
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    impl EventHandler for FlakyHandler {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn handle_event(&self, _event: &CommitEvent) -> Result<(), EventError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let failures = self.failures.load(Ordering::SeqCst);
+            if failures > 0 {
+                self.failures.store(failures - 1, Ordering::SeqCst);
+                Err(EventError("handler failed".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+
+        fn cloned_box(&self) -> Box<dyn EventHandler> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// An event connection that receives whatever is sent on its channel; sending an error other
+    /// than an invalid message simulates a lost connection
+    struct MockConnection {
+        events: Receiver<Result<CommitEvent, EventIoError>>,
+        reconnect_failures: usize,
+        reconnects: Arc<AtomicUsize>,
+        subscriptions: Arc<Mutex<Vec<Option<String>>>>,
+        backlog: Arc<AtomicUsize>,
+    }
+
+    impl MockConnection {
+        fn new(reconnect_failures: usize) -> (Self, Sender<Result<CommitEvent, EventIoError>>) {
+            let (sender, events) = channel();
+            (
+                Self {
+                    events,
+                    reconnect_failures,
+                    reconnects: Arc::new(AtomicUsize::new(0)),
+                    subscriptions: Arc::new(Mutex::new(vec![])),
+                    backlog: Arc::new(AtomicUsize::new(0)),
+                },
+                sender,
+            )
+        }
+    }
+
+    struct MockUnsubscriber;
+
+    impl EventConnectionUnsubscriber for MockUnsubscriber {
+        fn unsubscribe(self) -> Result<(), EventIoError> {
+            Ok(())
+        }
+    }
+
+    impl EventConnection for MockConnection {
+        type Unsubscriber = MockUnsubscriber;
+
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn recv(&self) -> Result<CommitEvent, EventIoError> {
+            self.events.recv().unwrap_or_else(|_| {
+                Err(EventIoError::ConnectionError(
+                    "channel disconnected".to_string(),
+                ))
+            })
+        }
+
+        fn subscribe(
+            &mut self,
+            _namespaces: &[&str],
+            last_commit_id: Option<&str>,
+        ) -> Result<Self::Unsubscriber, EventIoError> {
+            self.subscriptions
+                .lock()
+                .expect("Subscriptions lock poisoned")
+                .push(last_commit_id.map(String::from));
+            Ok(MockUnsubscriber)
+        }
+
+        fn reconnect(&mut self) -> Result<(), EventIoError> {
+            let attempt = self.reconnects.fetch_add(1, Ordering::SeqCst);
+            if attempt < self.reconnect_failures {
+                Err(EventIoError::ConnectionError("still down".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+
+        fn backlog(&self) -> Option<u64> {
+            Some(self.backlog.load(Ordering::SeqCst) as u64)
+        }
+
+        fn close(self) -> Result<(), EventIoError> {
+            Ok(())
+        }
+    }
+
+    /// Polls until the condition holds, panicking if it does not within a few seconds
+    fn wait_until<F: Fn() -> bool>(condition: F) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition() {
+            assert!(Instant::now() < deadline, "Timed out waiting for condition");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Shuts a processor down, sending a message to wake it if it is waiting for events
+    fn shut_down(
+        processor: EventProcessor<MockConnection>,
+        sender: &Sender<Result<CommitEvent, EventIoError>>,
+    ) {
+        let (shutdown_handle, join_handle) = processor.take_shutdown_controls();
+        shutdown_handle.shutdown().expect("Failed to shut down");
+        sender
+            .send(Err(EventIoError::InvalidMessage("wake up".to_string())))
+            .expect("Failed to send message");
+        join_handle
+            .join()
+            .expect("Event processor panicked")
+            .expect("Event processor failed");
+    }
+
+    fn commit(commit_id: &str, commit_num: i64) -> Commit {
+        Commit {
+            commit_id: commit_id.to_string(),
+            commit_num,
+            service_id: None,
+        }
+    }
+
+    fn event() -> CommitEvent {
+        CommitEvent {
+            service_id: None,
+            id: "commit-1".to_string(),
+            height: Some(1),
+            state_changes: vec![StateChange::Delete {
+                key: "621dee01".to_string(),
+            }],
+        }
+    }
+
+    fn policy(max_retries: u32, failure_mode: FailureMode) -> FailurePolicy {
+        FailurePolicy::new(max_retries, failure_mode)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(1))
+    }
+
+    #[test]
+    // Test that a handler is retried until it succeeds, without dead-lettering the event
+    fn test_retry_until_success() {
+        let handler = FlakyHandler::new(2);
+        let store = MemoryDeadLetterStore::new();
+
+        handle_message(
+            event(),
+            &[handler.cloned_box()],
+            &policy(2, FailureMode::Halt).with_dead_letter_store(Arc::new(store.clone())),
+            &IndexingStatus::new(),
+            &AtomicBool::new(false),
+        )
+        .expect("Failed to handle event");
+
+        assert_eq!(handler.calls(), 3);
+        assert_eq!(
+            store
+                .list_dead_letters(0, 10)
+                .expect("Failed to list dead letters")
+                .paging
+                .total,
+            0
+        );
+    }
+
+    #[test]
+    // Test that an event is dead-lettered once its handler exhausts its retries, and that a
+    // continuing processor moves on without waiting
+    fn test_dead_letter_and_continue() {
+        let handler = FlakyHandler::new(10);
+        let store = MemoryDeadLetterStore::new();
+
+        handle_message(
+            event(),
+            &[handler.cloned_box()],
+            &policy(1, FailureMode::Continue).with_dead_letter_store(Arc::new(store.clone())),
+            &IndexingStatus::new(),
+            &AtomicBool::new(false),
+        )
+        .expect("Failed to handle event");
+
+        assert_eq!(handler.calls(), 2);
+        let dead_letters = store
+            .list_dead_letters(0, 10)
+            .expect("Failed to list dead letters")
+            .data;
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].handler, "flaky");
+        assert_eq!(dead_letters[0].attempts, 2);
+        assert_eq!(dead_letters[0].event.id, "commit-1");
+    }
+
+    #[test]
+    // Test that a halting processor without a dead letter store stops instead of dropping a
+    // failed event
+    fn test_halt_without_dead_letter_store() {
+        let handler = FlakyHandler::new(10);
+
+        assert!(handle_message(
+            event(),
+            &[handler.cloned_box()],
+            &policy(1, FailureMode::Halt),
+            &IndexingStatus::new(),
+            &AtomicBool::new(false),
+        )
+        .is_err());
+    }
+
+    #[test]
+    // Test that a halting processor waits until the dead letter has been discarded
+    fn test_halt_until_dead_letter_discarded() {
+        let handler = FlakyHandler::new(10);
+        let store = MemoryDeadLetterStore::new();
+        let failure_policy =
+            policy(0, FailureMode::Halt).with_dead_letter_store(Arc::new(store.clone()));
+        let indexing_status = IndexingStatus::new();
+
+        let handlers = vec![handler.cloned_box()];
+        let thread_indexing_status = indexing_status.clone();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        thread::spawn(move || {
+            sender
+                .send(handle_message(
+                    event(),
+                    &handlers,
+                    &failure_policy,
+                    &thread_indexing_status,
+                    &AtomicBool::new(false),
+                ))
+                .expect("Failed to send result");
+        });
+
+        let id = loop {
+            let dead_letters = store
+                .list_dead_letters(0, 10)
+                .expect("Failed to list dead letters")
+                .data;
+            if let Some(dead_letter) = dead_letters.first() {
+                break dead_letter.id.expect("Dead letter has no id");
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        assert!(indexing_status.is_halted_on(id));
+
+        store
+            .remove_dead_letter(id)
+            .expect("Failed to remove dead letter");
+        receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("Event processing did not resume")
+            .expect("Failed to handle event");
+        assert!(!indexing_status.is_halted_on(id));
+    }
+
+    #[test]
+    // Test that a replayed dead letter records failed attempts and is removed once it succeeds
+    fn test_replay_dead_letter() {
+        let handler = FlakyHandler::new(1);
+        let handlers = vec![handler.cloned_box()];
+        let store = MemoryDeadLetterStore::new();
+        let id = store
+            .add_dead_letter(DeadLetter::new(
+                "flaky",
+                DbCommitEvent::from(&event()),
+                "handler failed",
+                1,
+            ))
+            .expect("Failed to add dead letter");
+
+        let commit_store = MemoryCommitStore::new();
+        let indexing_status = IndexingStatus::new();
+
+        assert!(
+            replay_dead_letter(&store, &commit_store, &indexing_status, &handlers, id).is_err()
+        );
+        assert_eq!(
+            store
+                .fetch_dead_letter(id)
+                .expect("Failed to fetch dead letter")
+                .expect("Dead letter not found")
+                .attempts,
+            2
+        );
+
+        replay_dead_letter(&store, &commit_store, &indexing_status, &handlers, id)
+            .expect("Failed to replay dead letter");
+        assert!(store
+            .fetch_dead_letter(id)
+            .expect("Failed to fetch dead letter")
+            .is_none());
+        assert_eq!(handler.calls(), 2);
+    }
+
+    #[test]
+    // Test that a dead letter of the database handler is only replayed while no later commit has
+    // been indexed: before the commit store reaches its height, or while a processor is halted on
+    // it
+    fn test_replay_database_dead_letter() {
+        let handler = FlakyHandler::new(0).named(DATABASE_HANDLER_NAME);
+        let handlers = vec![handler.cloned_box()];
+        let store = MemoryDeadLetterStore::new();
+        let commit_store = MemoryCommitStore::new();
+        commit_store
+            .add_commit(commit("commit-0", 0))
+            .expect("Failed to add commit");
+        let indexing_status = IndexingStatus::new();
+        let add_dead_letter = |event: CommitEvent| {
+            store
+                .add_dead_letter(DeadLetter::new(
+                    DATABASE_HANDLER_NAME,
+                    DbCommitEvent::from(&event),
+                    "handler failed",
+                    1,
+                ))
+                .expect("Failed to add dead letter")
+        };
+
+        let id = add_dead_letter(event());
+        commit_store
+            .add_commit(commit("commit-2", 2))
+            .expect("Failed to add commit");
+        assert!(
+            replay_dead_letter(&store, &commit_store, &indexing_status, &handlers, id).is_err()
+        );
+        assert_eq!(handler.calls(), 0);
+
+        indexing_status.set_halted_on(id, true);
+        replay_dead_letter(&store, &commit_store, &indexing_status, &handlers, id)
+            .expect("Failed to replay dead letter");
+        assert_eq!(handler.calls(), 1);
+
+        let commit_store = MemoryCommitStore::new();
+        commit_store
+            .add_commit(commit("commit-0", 0))
+            .expect("Failed to add commit");
+        let id = add_dead_letter(event());
+        replay_dead_letter(&store, &commit_store, &indexing_status, &handlers, id)
+            .expect("Failed to replay dead letter");
+        assert_eq!(handler.calls(), 2);
+
+        // A commit without a height may have been followed by any number of commits
+        let id = add_dead_letter(CommitEvent {
+            height: None,
+            ..event()
+        });
+        assert!(
+            replay_dead_letter(&store, &commit_store, &indexing_status, &handlers, id).is_err()
+        );
+        assert_eq!(handler.calls(), 2);
+    }
+
+    #[test]
+    // Test that a processor resuming from the commit store resubscribes from the store's current
+    // commit after losing its connection, and that it is removed from the indexing status once it
+    // shuts down
+    fn test_reconnect_from_commit_store() {
+        let (connection, sender) = MockConnection::new(0);
+        let subscriptions = connection.subscriptions.clone();
+        let commit_store = MemoryCommitStore::new();
+        commit_store
+            .add_commit(commit("commit-1", 1))
+            .expect("Failed to add commit");
+        let indexing_status = IndexingStatus::new();
+
+        let processor = EventProcessor::start(
+            connection,
+            ResumeFrom::CommitStore(Box::new(commit_store.clone())),
+            vec![FlakyHandler::new(0).cloned_box()],
+            FailurePolicy::default(),
+            indexing_status.clone(),
+        )
+        .expect("Failed to start event processor");
+        assert!(indexing_status.is_live());
+
+        commit_store
+            .add_commit(commit("commit-2", 2))
+            .expect("Failed to add commit");
+        sender
+            .send(Err(EventIoError::ConnectionError("lost".to_string())))
+            .expect("Failed to send error");
+        wait_until(|| subscriptions.lock().unwrap().len() == 2 && indexing_status.is_live());
+        assert_eq!(
+            *subscriptions.lock().unwrap(),
+            vec![Some("commit-1".to_string()), Some("commit-2".to_string())]
+        );
+
+        shut_down(processor, &sender);
+        assert!(indexing_status.connections().is_empty());
+    }
+
+    #[test]
+    // Test that a processor resuming from the last event resubscribes from the last event it
+    // received
+    fn test_reconnect_from_last_event() {
+        let (connection, sender) = MockConnection::new(0);
+        let subscriptions = connection.subscriptions.clone();
+        let handler = FlakyHandler::new(0);
+
+        let processor = EventProcessor::start(
+            connection,
+            ResumeFrom::LastEvent(None),
+            vec![handler.cloned_box()],
+            FailurePolicy::default(),
+            IndexingStatus::new(),
+        )
+        .expect("Failed to start event processor");
+
+        sender.send(Ok(event())).expect("Failed to send event");
+        sender
+            .send(Err(EventIoError::ConnectionError("lost".to_string())))
+            .expect("Failed to send error");
+        wait_until(|| subscriptions.lock().unwrap().len() == 2);
+
+        assert_eq!(
+            *subscriptions.lock().unwrap(),
+            vec![None, Some("commit-1".to_string())]
+        );
+        assert_eq!(handler.calls(), 1);
+
+        shut_down(processor, &sender);
+    }
+
+    #[test]
+    // Test that indexing is not live while the processor is failing to reconnect, and is live
+    // again once it has resubscribed
+    fn test_indexing_status_while_reconnecting() {
+        let (connection, sender) = MockConnection::new(1);
+        let reconnects = connection.reconnects.clone();
+        let indexing_status = IndexingStatus::new();
+
+        let processor = EventProcessor::start(
+            connection,
+            ResumeFrom::LastEvent(None),
+            vec![FlakyHandler::new(0).cloned_box()],
+            FailurePolicy::default(),
+            indexing_status.clone(),
+        )
+        .expect("Failed to start event processor");
+
+        sender
+            .send(Err(EventIoError::ConnectionError("lost".to_string())))
+            .expect("Failed to send error");
+        wait_until(|| reconnects.load(Ordering::SeqCst) == 1);
+        assert!(!indexing_status.is_live());
+        assert_eq!(
+            indexing_status.connections(),
+            vec![("mock".to_string(), false)]
+        );
+
+        wait_until(|| indexing_status.is_live());
+        assert_eq!(reconnects.load(Ordering::SeqCst), 2);
+
+        shut_down(processor, &sender);
+    }
+
+    #[test]
+    // Test that the indexing status reports the backlog of commits the connection has received
+    fn test_indexing_status_backlog() {
+        let (connection, sender) = MockConnection::new(0);
+        connection.backlog.store(3, Ordering::SeqCst);
+        let indexing_status = IndexingStatus::new();
+        assert_eq!(indexing_status.backlog(), None);
+
+        let processor = EventProcessor::start(
+            connection,
+            ResumeFrom::LastEvent(None),
+            vec![FlakyHandler::new(0).cloned_box()],
+            FailurePolicy::default(),
+            indexing_status.clone(),
+        )
+        .expect("Failed to start event processor");
+
+        sender.send(Ok(event())).expect("Failed to send event");
+        wait_until(|| indexing_status.backlog() == Some(3));
+
+        shut_down(processor, &sender);
+    }
+}
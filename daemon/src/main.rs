@@ -23,15 +23,22 @@ extern crate cfg_if;
 extern crate clap;
 extern crate diesel;
 extern crate diesel_migrations;
+#[cfg(feature = "metrics")]
+#[macro_use]
+extern crate lazy_static;
 #[macro_use]
 extern crate log;
+#[cfg(feature = "metrics")]
+#[macro_use]
+extern crate prometheus;
 #[cfg(feature = "serde_json")]
 #[macro_use]
 extern crate serde_json;
-#[cfg(feature = "serde")]
 #[macro_use]
 extern crate serde;
 
+#[cfg(feature = "change-stream")]
+mod changes;
 mod config;
 #[cfg(feature = "database")]
 mod database;
@@ -39,6 +46,8 @@ mod error;
 #[cfg(feature = "event")]
 #[macro_use]
 mod event;
+#[cfg(feature = "metrics")]
+mod metrics;
 #[cfg(feature = "rest-api")]
 mod rest_api;
 #[cfg(feature = "sawtooth-support")]
@@ -47,10 +56,14 @@ mod sawtooth;
 mod splinter;
 #[cfg(feature = "submitter")]
 mod submitter;
+#[cfg(feature = "webhooks")]
+mod webhooks;
+
+use std::path::Path;
 
 use flexi_logger::{LogSpecBuilder, Logger};
 
-use crate::config::{Backend, GridConfigBuilder};
+use crate::config::{Backend, GridConfigBuilder, CONFIG_FILE_ENV, DEFAULT_CONFIG_FILE};
 use crate::error::DaemonError;
 #[cfg(feature = "sawtooth-support")]
 use crate::sawtooth::run_sawtooth;
@@ -67,6 +80,8 @@ fn run() -> Result<(), DaemonError> {
         (version: VERSION)
         (author: "Contributors to Hyperledger Grid")
         (about: "Daemon Package for Hyperledger Grid")
+        (@arg config: -c --config +takes_value "configuration file; defaults to /etc/grid/gridd.toml if present")
+        (@arg print_config: --("print-config") "print the effective configuration and exit")
         (@arg connect: -C --connect +takes_value "connection endpoint for sawtooth or splinter")
         (@arg verbose: -v +multiple "Log verbosely")
         (@arg database_url: --("database-url") +takes_value
@@ -86,6 +101,116 @@ fn run() -> Result<(), DaemonError> {
         );
     }
 
+    #[cfg(feature = "event")]
+    {
+        use clap::Arg;
+        app = app
+            .arg(
+                Arg::with_name("event_retries")
+                    .long("event-retries")
+                    .takes_value(true)
+                    .value_name("count")
+                    .validator(|value| {
+                        value
+                            .parse::<u32>()
+                            .map(|_| ())
+                            .map_err(|_| format!("{} is not a valid retry count", value))
+                    })
+                    .help("Number of times to retry an event that fails to be handled"),
+            )
+            .arg(
+                Arg::with_name("event_failure_policy")
+                    .long("event-failure-policy")
+                    .takes_value(true)
+                    .possible_values(&["halt", "continue"])
+                    .help(
+                        "Whether to halt or continue processing events after an event has \
+                         exhausted its retries",
+                    ),
+            )
+            .arg(
+                Arg::with_name("max_commit_lag")
+                    .long("max-commit-lag")
+                    .takes_value(true)
+                    .value_name("commits")
+                    .validator(|value| {
+                        value
+                            .parse::<u64>()
+                            .map(|_| ())
+                            .map_err(|_| format!("{} is not a valid number of commits", value))
+                    })
+                    .help(
+                        "Number of commits the database may trail the ledger before gridd \
+                         reports that it is not ready",
+                    ),
+            );
+    }
+
+    #[cfg(feature = "rest-api")]
+    {
+        use clap::Arg;
+        app = app
+            .arg(Arg::with_name("require_auth").long("require-auth").help(
+                "Require REST API requests to carry an API token or a JSON web token \
+                         signed by a Pike agent",
+            ))
+            .arg(
+                Arg::with_name("api_token_file")
+                    .long("api-token-file")
+                    .takes_value(true)
+                    .value_name("file")
+                    .requires("require_auth")
+                    .help("File of API tokens, one per line, that grant access to all routes"),
+            );
+    }
+
+    #[cfg(feature = "tls")]
+    {
+        use clap::Arg;
+        app = app
+            .arg(
+                Arg::with_name("tls_cert")
+                    .long("tls-cert")
+                    .takes_value(true)
+                    .value_name("file")
+                    .requires("tls_key")
+                    .help("PEM certificate chain the REST API serves HTTPS with"),
+            )
+            .arg(
+                Arg::with_name("tls_key")
+                    .long("tls-key")
+                    .takes_value(true)
+                    .value_name("file")
+                    .requires("tls_cert")
+                    .help("PEM private key of the REST API's certificate"),
+            )
+            .arg(
+                Arg::with_name("tls_client_ca")
+                    .long("tls-client-ca")
+                    .takes_value(true)
+                    .value_name("file")
+                    .requires("tls_cert")
+                    .help(
+                        "PEM bundle of the CAs that client certificates, including those of \
+                         health probes, must be signed by",
+                    ),
+            )
+            .arg(
+                Arg::with_name("tls_client_scope")
+                    .long("tls-client-scope")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .value_name("common-name=scope")
+                    .requires("tls_client_ca")
+                    .help(
+                        "Limit clients whose certificates have the common name to a scope, \
+                         either org:<org_id> or service:<service_id>; clients whose \
+                         certificates are not mapped are then refused",
+                    ),
+            );
+    }
+
     let matches = app.get_matches();
 
     let log_level = match matches.occurrences_of("verbose") {
@@ -99,12 +224,30 @@ fn run() -> Result<(), DaemonError> {
     log_spec_builder.module("hyper", log::LevelFilter::Warn);
     log_spec_builder.module("tokio", log::LevelFilter::Warn);
 
-    Logger::with(log_spec_builder.build()).start()?;
+    let mut log_handle = Logger::with(log_spec_builder.build()).start()?;
 
-    let config = GridConfigBuilder::default()
+    let mut config_builder = GridConfigBuilder::default();
+    if let Some(config_file) = config_file(&matches) {
+        debug!("Reading configuration from {}", config_file);
+        config_builder = config_builder.with_config_file(&config_file)?;
+    }
+    let config = config_builder
+        .with_env_vars()?
         .with_cli_args(&matches)
         .build()?;
 
+    if matches.is_present("print_config") {
+        print!("{}", config.to_toml()?);
+        return Ok(());
+    }
+
+    let mut log_spec_builder = LogSpecBuilder::new();
+    log_spec_builder.default(config.log_level());
+    for (module, level) in config.log_modules() {
+        log_spec_builder.module(module, *level);
+    }
+    log_handle.set_new_spec(log_spec_builder.build());
+
     match config.endpoint().backend() {
         Backend::Sawtooth => {
             #[cfg(feature = "sawtooth-support")]
@@ -133,6 +276,22 @@ fn run() -> Result<(), DaemonError> {
     }
 }
 
+/// Returns the configuration file given by `--config` or `GRIDD_CONFIG`, or else the default file
+/// if it exists
+fn config_file(matches: &clap::ArgMatches<'_>) -> Option<String> {
+    matches
+        .value_of("config")
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(CONFIG_FILE_ENV).ok())
+        .or_else(|| {
+            if Path::new(DEFAULT_CONFIG_FILE).exists() {
+                Some(DEFAULT_CONFIG_FILE.to_owned())
+            } else {
+                None
+            }
+        })
+}
+
 fn main() {
     if let Err(e) = run() {
         error!("{}", e);
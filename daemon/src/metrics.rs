@@ -0,0 +1,246 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus metrics of gridd
+//!
+//! The metrics are kept in the default Prometheus registry, so that the components that record
+//! them need no handle to it. The REST API serves them at `/metrics`.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+use prometheus::{Encoder, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, TextEncoder};
+
+use crate::rest_api::error::RestApiResponseError;
+
+lazy_static! {
+    static ref REST_API_REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "grid_rest_api_requests_total",
+        "REST API requests by method, route and response status",
+        &["method", "route", "status"]
+    )
+    .expect("Unable to register grid_rest_api_requests_total");
+    static ref REST_API_REQUEST_DURATION: HistogramVec = register_histogram_vec!(
+        "grid_rest_api_request_duration_seconds",
+        "Time taken to respond to REST API requests, by method and route",
+        &["method", "route"]
+    )
+    .expect("Unable to register grid_rest_api_request_duration_seconds");
+    static ref DATABASE_POOL_CONNECTIONS: IntGauge = register_int_gauge!(
+        "grid_database_pool_connections",
+        "Connections open in the REST API's database connection pool"
+    )
+    .expect("Unable to register grid_database_pool_connections");
+    static ref DATABASE_POOL_IDLE_CONNECTIONS: IntGauge = register_int_gauge!(
+        "grid_database_pool_idle_connections",
+        "Open connections not in use in the REST API's database connection pool"
+    )
+    .expect("Unable to register grid_database_pool_idle_connections");
+    static ref DATABASE_POOL_MAX_CONNECTIONS: IntGauge = register_int_gauge!(
+        "grid_database_pool_max_connections",
+        "The most connections the REST API's database connection pool opens"
+    )
+    .expect("Unable to register grid_database_pool_max_connections");
+    static ref EVENTS_RECEIVED: IntCounterVec = register_int_counter_vec!(
+        "grid_events_received_total",
+        "Commit events received, by event connection",
+        &["connection"]
+    )
+    .expect("Unable to register grid_events_received_total");
+    static ref LATEST_RECEIVED_COMMIT: IntGauge = register_int_gauge!(
+        "grid_event_latest_received_commit",
+        "Height of the latest commit event received with a height"
+    )
+    .expect("Unable to register grid_event_latest_received_commit");
+    static ref LATEST_PERSISTED_COMMIT: IntGauge = register_int_gauge!(
+        "grid_event_latest_persisted_commit",
+        "Commit number of the latest commit in the commit store"
+    )
+    .expect("Unable to register grid_event_latest_persisted_commit");
+    static ref EVENT_PROCESSING_LAG: IntGauge = register_int_gauge!(
+        "grid_event_processing_lag",
+        "Commits received, but not yet in the commit store"
+    )
+    .expect("Unable to register grid_event_processing_lag");
+    static ref EVENT_HANDLER_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "grid_event_handler_errors_total",
+        "Failed attempts to handle a commit event, by event handler",
+        &["handler"]
+    )
+    .expect("Unable to register grid_event_handler_errors_total");
+    static ref BATCH_SUBMISSIONS: IntCounterVec = register_int_counter_vec!(
+        "grid_batch_submissions_total",
+        "Batch submissions by backend and outcome: accepted, rejected or failed",
+        &["backend", "outcome"]
+    )
+    .expect("Unable to register grid_batch_submissions_total");
+    static ref BATCH_SUBMISSIONS_PENDING: IntGaugeVec = register_int_gauge_vec!(
+        "grid_batch_submissions_pending",
+        "Batch submissions waiting on the backend's connection, by backend",
+        &["backend"]
+    )
+    .expect("Unable to register grid_batch_submissions_pending");
+}
+
+/// Renders every metric in the Prometheus text format
+pub fn render() -> Result<String, String> {
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&prometheus::gather(), &mut buffer)
+        .map_err(|err| format!("Unable to encode metrics: {}", err))?;
+    String::from_utf8(buffer).map_err(|err| format!("Unable to encode metrics: {}", err))
+}
+
+/// Records a REST API response
+pub fn record_request(method: &str, route: &str, status: u16, seconds: f64) {
+    REST_API_REQUESTS
+        .with_label_values(&[method, route, &status.to_string()])
+        .inc();
+    REST_API_REQUEST_DURATION
+        .with_label_values(&[method, route])
+        .observe(seconds);
+}
+
+/// Records the receipt of a commit event
+pub fn record_event_received(connection: &str, height: Option<u64>) {
+    EVENTS_RECEIVED.with_label_values(&[connection]).inc();
+    if let Some(height) = height {
+        LATEST_RECEIVED_COMMIT.set(height as i64);
+    }
+}
+
+/// Records the latest commit in the commit store, and how far it trails the latest commit
+/// received
+pub fn record_persisted_commit(commit_num: i64) {
+    LATEST_PERSISTED_COMMIT.set(commit_num);
+    EVENT_PROCESSING_LAG.set(processing_lag(LATEST_RECEIVED_COMMIT.get(), commit_num));
+}
+
+/// The commit store may be ahead of the latest commit received, such as before any commit has
+/// been received since gridd started, in which case nothing is outstanding
+fn processing_lag(latest_received: i64, latest_persisted: i64) -> i64 {
+    (latest_received - latest_persisted).max(0)
+}
+
+/// Records a failed attempt by an event handler
+pub fn record_handler_error(handler: &str) {
+    EVENT_HANDLER_ERRORS.with_label_values(&[handler]).inc();
+}
+
+/// The future result of a batch submission
+pub type Submission<T> = Pin<Box<dyn Future<Output = Result<T, RestApiResponseError>> + Send>>;
+
+/// Counts a batch submission as pending until its result is ready, then counts its outcome
+///
+/// The submission is started by `submit`, as a submitter may wait on its backend before
+/// returning the future.
+pub fn record_submission<T, F>(backend: &'static str, submit: F) -> Submission<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Submission<T>,
+{
+    let pending = BATCH_SUBMISSIONS_PENDING.with_label_values(&[backend]);
+    pending.inc();
+    let submission = submit();
+
+    Box::pin(async move {
+        let result = submission.await;
+        pending.dec();
+
+        let outcome = match &result {
+            Ok(_) => "accepted",
+            Err(RestApiResponseError::BadRequest(_)) => "rejected",
+            Err(_) => "failed",
+        };
+        BATCH_SUBMISSIONS
+            .with_label_values(&[backend, outcome])
+            .inc();
+
+        result
+    })
+}
+
+/// Samples the utilization of a database connection pool when metrics are fetched
+#[derive(Clone)]
+pub struct PoolMetrics {
+    sample: Arc<dyn Fn() -> (u32, u32, u32) + Send + Sync>,
+}
+
+impl PoolMetrics {
+    pub fn new<C>(pool: Pool<ConnectionManager<C>>) -> Self
+    where
+        C: diesel::Connection + 'static,
+    {
+        Self {
+            sample: Arc::new(move || {
+                let state = pool.state();
+                (state.connections, state.idle_connections, pool.max_size())
+            }),
+        }
+    }
+
+    /// Records the pool's current utilization
+    pub fn record(&self) {
+        let (connections, idle_connections, max_size) = (self.sample)();
+        DATABASE_POOL_CONNECTIONS.set(connections.into());
+        DATABASE_POOL_IDLE_CONNECTIONS.set(idle_connections.into());
+        DATABASE_POOL_MAX_CONNECTIONS.set(max_size.into());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use futures::executor::block_on;
+    use futures::future;
+
+    /// Verifies that a submission is counted as pending until it completes, and then by its
+    /// outcome.
+    #[test]
+    fn test_record_submission() {
+        let accepted = BATCH_SUBMISSIONS.with_label_values(&["test", "accepted"]);
+        let rejected = BATCH_SUBMISSIONS.with_label_values(&["test", "rejected"]);
+        let pending = BATCH_SUBMISSIONS_PENDING.with_label_values(&["test"]);
+
+        let submission = record_submission("test", || Box::pin(future::ready(Ok(()))));
+        assert_eq!(1, pending.get());
+        assert!(block_on(submission).is_ok());
+        assert_eq!(0, pending.get());
+        assert_eq!(1, accepted.get());
+
+        let submission = record_submission::<(), _>("test", || {
+            Box::pin(future::ready(Err(RestApiResponseError::BadRequest(
+                "invalid batch".into(),
+            ))))
+        });
+        assert!(block_on(submission).is_err());
+        assert_eq!(1, rejected.get());
+
+        let rendered = render().expect("Unable to render metrics");
+        assert!(rendered
+            .contains("grid_batch_submissions_total{backend=\"test\",outcome=\"accepted\"} 1"));
+    }
+
+    /// Verifies that the processing lag is the distance from the latest commit received to the
+    /// latest commit persisted, and is never negative.
+    #[test]
+    fn test_processing_lag() {
+        assert_eq!(3, processing_lag(12, 9));
+        assert_eq!(0, processing_lag(12, 12));
+        assert_eq!(0, processing_lag(0, 15));
+    }
+}
@@ -0,0 +1,590 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Authentication of REST API requests
+//!
+//! When authentication is required, every request must carry an `Authorization: Bearer` header
+//! with either one of the configured API tokens or a Cylinder JSON web token. API tokens are
+//! meant for operators and grant access to everything. A JSON web token identifies the Pike
+//! agent whose key signed it, and is only accepted if that agent is active in the service the
+//! request is for; the agent may then read what belongs to its organization.
+//!
+//! When the REST API maps client certificates onto scopes, a client's certificate identifies it
+//! on its own, in place of a bearer token, and clients whose certificates are not mapped are
+//! forbidden; see the `tls` module.
+
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context as TaskContext, Poll};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use actix_web::{
+    dev::{self, Service, ServiceRequest, ServiceResponse, Transform},
+    error::{BlockingError, Error as ActixError},
+    http::header,
+    web, FromRequest, HttpMessage, HttpRequest,
+};
+use cylinder::{jwt::JsonWebTokenParser, secp256k1::Secp256k1Context, Context};
+use futures::future::{self, LocalBoxFuture};
+use grid_sdk::pike::PikeStore;
+
+use crate::config::GridConfig;
+#[cfg(feature = "tls")]
+use crate::rest_api::tls::ClientScopes;
+use crate::rest_api::{
+    error::{RestApiResponseError, RestApiServerError},
+    QueryServiceId,
+};
+
+/// The routes that are open to every client, so that probes need no credentials; they reveal
+/// nothing about the data gridd holds
+const HEALTH_ROUTES: &str = "/health/";
+
+/// Who a request was made by
+#[derive(Clone, Debug, PartialEq)]
+pub enum Principal {
+    /// Authentication is not required, so the client is unknown
+    Anonymous,
+    /// A client holding one of the API tokens
+    Operator,
+    /// The Pike agent whose key signed the request's token
+    Agent { public_key: String, org_id: String },
+    /// A client whose certificate is limited to what belongs to an organization
+    #[cfg(feature = "tls")]
+    Organization { org_id: String },
+    /// A client whose certificate is limited to a single service
+    #[cfg(feature = "tls")]
+    Service { service_id: String },
+}
+
+#[cfg_attr(not(feature = "stable"), allow(dead_code))]
+impl Principal {
+    /// Returns whether the principal may read everything in the service, rather than only what
+    /// belongs to its organization
+    pub fn is_unrestricted(&self) -> bool {
+        self.org_id().is_none()
+    }
+
+    /// Returns whether the principal may read what belongs to the given organization
+    pub fn can_read_org(&self, org_id: &str) -> bool {
+        self.org_id()
+            .map(|own_org_id| own_org_id == org_id)
+            .unwrap_or(true)
+    }
+
+    /// Returns the organization the principal is restricted to, if any
+    pub fn org_id(&self) -> Option<&str> {
+        match self {
+            Principal::Agent { org_id, .. } => Some(org_id),
+            #[cfg(feature = "tls")]
+            Principal::Organization { org_id } => Some(org_id),
+            _ => None,
+        }
+    }
+
+    /// Returns the public key of the agent the principal is, if any
+    pub fn public_key(&self) -> Option<&str> {
+        match self {
+            Principal::Agent { public_key, .. } => Some(public_key),
+            _ => None,
+        }
+    }
+}
+
+impl FromRequest for Principal {
+    type Error = ActixError;
+    type Future = future::Ready<Result<Self, Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _: &mut dev::Payload) -> Self::Future {
+        future::ok(
+            req.extensions()
+                .get::<Principal>()
+                .cloned()
+                .unwrap_or(Principal::Anonymous),
+        )
+    }
+}
+
+/// Limits a route to operators, rejecting requests made by anyone else
+///
+/// Only when authentication is completely disabled, with neither an authenticator nor client
+/// scopes configured, is the route open to every client.
+pub struct OperatorOnly;
+
+impl FromRequest for OperatorOnly {
+    type Error = ActixError;
+    type Future = future::Ready<Result<Self, Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _: &mut dev::Payload) -> Self::Future {
+        match req.extensions().get::<Principal>() {
+            Some(Principal::Anonymous) | Some(Principal::Operator) => future::ok(OperatorOnly),
+            _ => future::err(
+                RestApiResponseError::Forbidden("Only operators may use this route".into()).into(),
+            ),
+        }
+    }
+}
+
+/// The longest a JSON web token may remain valid, in seconds; tokens must carry an `exp` claim
+/// no further in the future than this
+const MAX_TOKEN_LIFETIME: u64 = 24 * 60 * 60;
+
+/// Checks the credentials of requests, resolving them to principals
+pub struct Authenticator {
+    api_tokens: Vec<String>,
+    context: Secp256k1Context,
+    pike_store: Arc<dyn PikeStore>,
+}
+
+impl Authenticator {
+    pub fn new(api_tokens: Vec<String>, pike_store: Arc<dyn PikeStore>) -> Self {
+        Authenticator {
+            api_tokens,
+            context: Secp256k1Context::new(),
+            pike_store,
+        }
+    }
+
+    /// Returns the authenticator the REST API is configured with, or `None` if it does not
+    /// require authentication
+    pub fn from_config(
+        config: &GridConfig,
+        pike_store: Arc<dyn PikeStore>,
+    ) -> Result<Option<Self>, RestApiServerError> {
+        if !config.require_auth() {
+            return Ok(None);
+        }
+
+        let api_tokens = match config.api_token_file() {
+            Some(path) => read_api_tokens(path)?,
+            None => vec![],
+        };
+
+        Ok(Some(Authenticator::new(api_tokens, pike_store)))
+    }
+
+    /// Resolves the value of a request's `Authorization` header to the principal that made it
+    ///
+    /// This may look up the agent that signed a token, so it should not be called on the
+    /// server's event loop.
+    pub fn authenticate(
+        &self,
+        authorization: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Principal, RestApiResponseError> {
+        let token = authorization
+            .ok_or_else(|| RestApiResponseError::Unauthorized("Missing credentials".into()))?
+            .strip_prefix("Bearer ")
+            .ok_or_else(|| {
+                RestApiResponseError::Unauthorized("Credentials must be a bearer token".into())
+            })?
+            .trim();
+
+        if self
+            .api_tokens
+            .iter()
+            .any(|api_token| constant_time_eq(api_token.as_bytes(), token.as_bytes()))
+        {
+            return Ok(Principal::Operator);
+        }
+
+        let verifier = self.context.new_verifier();
+        let jwt = JsonWebTokenParser::new(&*verifier)
+            .parse(token)
+            .map_err(|_| RestApiResponseError::Unauthorized("Invalid token".into()))?;
+
+        let expiry = jwt
+            .claims()
+            .get("exp")
+            .ok_or_else(|| RestApiResponseError::Unauthorized("Token has no expiry".into()))?
+            .parse::<u64>()
+            .map_err(|_| RestApiResponseError::Unauthorized("Invalid token expiry".into()))?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or(0);
+        if expiry <= now {
+            return Err(RestApiResponseError::Unauthorized(
+                "Token has expired".into(),
+            ));
+        }
+        if expiry > now.saturating_add(MAX_TOKEN_LIFETIME) {
+            return Err(RestApiResponseError::Unauthorized(format!(
+                "Token must expire within {} seconds",
+                MAX_TOKEN_LIFETIME
+            )));
+        }
+
+        let public_key = jwt.issuer().as_hex();
+        match self.pike_store.fetch_agent(&public_key, None, service_id)? {
+            Some(agent) if agent.active => Ok(Principal::Agent {
+                public_key,
+                org_id: agent.org_id,
+            }),
+            _ => Err(RestApiResponseError::Forbidden(format!(
+                "{} is not an active agent",
+                public_key
+            ))),
+        }
+    }
+}
+
+/// Reads API tokens from a file, one per line, skipping blank lines and `#` comments
+fn read_api_tokens(path: &str) -> Result<Vec<String>, RestApiServerError> {
+    let contents = fs::read_to_string(path).map_err(|err| {
+        RestApiServerError::StartUpError(format!(
+            "Unable to read API tokens from {}: {}",
+            path, err
+        ))
+    })?;
+
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToOwned::to_owned)
+        .collect())
+}
+
+/// Compares secrets without revealing, through timing, how much of them matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Middleware that authenticates each request, storing its principal for handlers to extract
+///
+/// Requests pass through as anonymous when neither an authenticator nor client scopes are given;
+/// requests to the health routes pass through without a principal.
+#[derive(Clone)]
+pub struct Authenticate {
+    authenticator: Option<Arc<Authenticator>>,
+    #[cfg(feature = "tls")]
+    client_scopes: Option<ClientScopes>,
+}
+
+impl Authenticate {
+    pub fn new(authenticator: Option<Arc<Authenticator>>) -> Self {
+        Authenticate {
+            authenticator,
+            #[cfg(feature = "tls")]
+            client_scopes: None,
+        }
+    }
+
+    /// Identifies clients by their certificates in place of their bearer tokens
+    #[cfg(feature = "tls")]
+    pub fn with_client_scopes(mut self, client_scopes: Option<ClientScopes>) -> Self {
+        self.client_scopes = client_scopes;
+        self
+    }
+}
+
+impl<S, B> Transform<S> for Authenticate
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>
+        + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type InitError = ();
+    type Transform = AuthenticateMiddleware<S>;
+    type Future = future::Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        future::ok(AuthenticateMiddleware {
+            service: Rc::new(RefCell::new(service)),
+            authenticator: self.authenticator.clone(),
+            #[cfg(feature = "tls")]
+            client_scopes: self.client_scopes.clone(),
+        })
+    }
+}
+
+pub struct AuthenticateMiddleware<S> {
+    service: Rc<RefCell<S>>,
+    authenticator: Option<Arc<Authenticator>>,
+    #[cfg(feature = "tls")]
+    client_scopes: Option<ClientScopes>,
+}
+
+impl<S, B> Service for AuthenticateMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>
+        + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.borrow_mut().poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        if req.path().starts_with(HEALTH_ROUTES) {
+            return Box::pin(self.service.borrow_mut().call(req));
+        }
+
+        let service_id = web::Query::<QueryServiceId>::from_query(req.query_string())
+            .ok()
+            .and_then(|query| query.into_inner().service_id);
+
+        #[cfg(feature = "tls")]
+        {
+            if let Some(client_scopes) = &self.client_scopes {
+                return match client_scopes.principal(req.peer_addr(), service_id.as_deref()) {
+                    Ok(principal) => {
+                        req.extensions_mut().insert(principal);
+                        Box::pin(self.service.borrow_mut().call(req))
+                    }
+                    Err(err) => Box::pin(future::err(err.into())),
+                };
+            }
+        }
+
+        let authenticator = match &self.authenticator {
+            Some(authenticator) => authenticator.clone(),
+            None => {
+                req.extensions_mut().insert(Principal::Anonymous);
+                return Box::pin(self.service.borrow_mut().call(req));
+            }
+        };
+
+        let authorization = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned);
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let principal = web::block(move || {
+                authenticator.authenticate(authorization.as_deref(), service_id.as_deref())
+            })
+            .await
+            .map_err(|err| match err {
+                BlockingError::Error(err) => err,
+                BlockingError::Canceled => {
+                    RestApiResponseError::RequestHandlerError("Authentication was canceled".into())
+                }
+            })?;
+
+            req.extensions_mut().insert(principal);
+            let response = service.borrow_mut().call(req);
+            response.await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use cylinder::jwt::JsonWebTokenBuilder;
+    use grid_sdk::pike::{store::Agent, MemoryPikeStore};
+
+    const API_TOKEN: &str = "operator-token";
+    const ORG_ID: &str = "my_org";
+
+    fn agent(public_key: &str, active: bool) -> Agent {
+        Agent {
+            public_key: public_key.to_string(),
+            org_id: ORG_ID.to_string(),
+            active,
+            metadata: vec![],
+            roles: vec![],
+            start_commit_num: 0,
+            end_commit_num: i64::MAX,
+            service_id: None,
+        }
+    }
+
+    fn jwt(context: &Secp256k1Context, claims: HashMap<String, String>) -> (String, String) {
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = signer
+            .public_key()
+            .expect("Unable to get public key")
+            .as_hex();
+        let token = JsonWebTokenBuilder::new()
+            .with_claims(claims)
+            .build(&*signer)
+            .expect("Unable to build token");
+        (public_key, format!("Bearer {}", token))
+    }
+
+    /// Returns claims that expire the given number of seconds from now
+    fn expiring_in(secs: u64) -> HashMap<String, String> {
+        let expiry = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Unable to get time")
+            .as_secs()
+            + secs;
+        let mut claims = HashMap::new();
+        claims.insert("exp".to_string(), expiry.to_string());
+        claims
+    }
+
+    /// Verifies that API tokens authenticate operators, and that requests without a known
+    /// bearer token are unauthorized
+    #[test]
+    fn test_authenticate_api_token() {
+        let authenticator = Authenticator::new(
+            vec![API_TOKEN.to_string()],
+            Arc::new(MemoryPikeStore::new()),
+        );
+
+        assert_eq!(
+            authenticator
+                .authenticate(Some(&format!("Bearer {}", API_TOKEN)), None)
+                .expect("Unable to authenticate"),
+            Principal::Operator
+        );
+
+        for authorization in &[None, Some(API_TOKEN), Some("Bearer wrong-token")] {
+            match authenticator.authenticate(*authorization, None) {
+                Err(RestApiResponseError::Unauthorized(_)) => (),
+                res => panic!("Expected unauthorized, got {:?}", res),
+            }
+        }
+    }
+
+    /// Verifies that a JSON web token authenticates the active agent that signed it, and that
+    /// tokens from unknown or inactive agents, or that have expired, have no expiry or expire
+    /// too far in the future, are rejected
+    #[test]
+    fn test_authenticate_jwt() {
+        let context = Secp256k1Context::new();
+        let pike_store = MemoryPikeStore::new();
+
+        let (public_key, token) = jwt(&context, expiring_in(60 * 60));
+        pike_store
+            .add_agent(agent(&public_key, true))
+            .expect("Unable to add agent");
+        let (inactive_key, inactive_token) = jwt(&context, expiring_in(60 * 60));
+        pike_store
+            .add_agent(agent(&inactive_key, false))
+            .expect("Unable to add agent");
+        let (_, unknown_token) = jwt(&context, expiring_in(60 * 60));
+
+        let authenticator = Authenticator::new(vec![], Arc::new(pike_store));
+
+        assert_eq!(
+            authenticator
+                .authenticate(Some(&token), None)
+                .expect("Unable to authenticate"),
+            Principal::Agent {
+                public_key,
+                org_id: ORG_ID.to_string(),
+            }
+        );
+
+        for token in &[inactive_token, unknown_token] {
+            match authenticator.authenticate(Some(token), None) {
+                Err(RestApiResponseError::Forbidden(_)) => (),
+                res => panic!("Expected forbidden, got {:?}", res),
+            }
+        }
+
+        let mut expired = HashMap::new();
+        expired.insert("exp".to_string(), "1".to_string());
+        for claims in [
+            expired,
+            HashMap::new(),
+            expiring_in(MAX_TOKEN_LIFETIME + 60 * 60),
+        ] {
+            let (public_key, token) = jwt(&context, claims);
+            authenticator
+                .pike_store
+                .add_agent(agent(&public_key, true))
+                .expect("Unable to add agent");
+            match authenticator.authenticate(Some(&token), None) {
+                Err(RestApiResponseError::Unauthorized(_)) => (),
+                res => panic!("Expected unauthorized, got {:?}", res),
+            }
+        }
+    }
+
+    /// Verifies that agents may only read what belongs to their organization
+    #[test]
+    fn test_principal_access() {
+        let agent = Principal::Agent {
+            public_key: "key".to_string(),
+            org_id: ORG_ID.to_string(),
+        };
+
+        assert!(agent.can_read_org(ORG_ID));
+        assert!(!agent.can_read_org("other_org"));
+        assert!(!agent.is_unrestricted());
+
+        for principal in &[Principal::Anonymous, Principal::Operator] {
+            assert!(principal.can_read_org("other_org"));
+            assert!(principal.is_unrestricted());
+        }
+    }
+
+    /// Verifies that operator-only routes admit operators, and anonymous clients when
+    /// authentication is disabled, but reject agents and requests without a principal
+    #[test]
+    fn test_operator_only() {
+        let operator_only = |principal: Option<Principal>| {
+            let req = actix_web::test::TestRequest::default().to_http_request();
+            if let Some(principal) = principal {
+                req.extensions_mut().insert(principal);
+            }
+            OperatorOnly::from_request(&req, &mut dev::Payload::None)
+                .into_inner()
+                .is_ok()
+        };
+
+        assert!(operator_only(Some(Principal::Operator)));
+        assert!(operator_only(Some(Principal::Anonymous)));
+        assert!(!operator_only(None));
+        assert!(!operator_only(Some(Principal::Agent {
+            public_key: "key".to_string(),
+            org_id: ORG_ID.to_string(),
+        })));
+    }
+
+    /// Verifies that a client scoped to an organization reads like one of its agents, and one
+    /// scoped to a service reads everything
+    #[test]
+    #[cfg(feature = "tls")]
+    fn test_scoped_principal_access() {
+        let organization = Principal::Organization {
+            org_id: ORG_ID.to_string(),
+        };
+        assert!(organization.can_read_org(ORG_ID));
+        assert!(!organization.can_read_org("other_org"));
+        assert!(!organization.is_unrestricted());
+        assert_eq!(organization.public_key(), None);
+
+        let service = Principal::Service {
+            service_id: "01234-ABCDE::gsAA".to_string(),
+        };
+        assert!(service.can_read_org("other_org"));
+        assert!(service.is_unrestricted());
+    }
+}
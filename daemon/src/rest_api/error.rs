@@ -18,13 +18,18 @@ use actix::MailboxError;
 use actix_web::error::{PayloadError, UrlGenerationError};
 use actix_web::{
     error::{Error as ActixError, ResponseError},
+    http::header,
     HttpResponse,
 };
 use futures::future::{Future, TryFutureExt};
+#[cfg(feature = "webhooks")]
+use grid_sdk::webhooks::WebhookStoreError;
 use grid_sdk::{
-    commits::store::CommitStoreError, locations::store::LocationStoreError,
+    change_log::ChangeLogStoreError, commits::store::CommitStoreError,
+    dead_letters::DeadLetterStoreError, locations::store::LocationStoreError,
     pike::store::PikeStoreError, products::store::ProductStoreError,
-    schemas::store::SchemaStoreError, track_and_trace::store::TrackAndTraceStoreError,
+    purchase_order::store::PurchaseOrderStoreError, schemas::store::SchemaStoreError,
+    search::store::SearchStoreError, track_and_trace::store::TrackAndTraceStoreError,
 };
 use std::error::Error;
 
@@ -68,6 +73,8 @@ pub enum RestApiResponseError {
     RequestHandlerError(String),
     DatabaseError(String),
     NotFoundError(String),
+    Unauthorized(String),
+    Forbidden(String),
 }
 
 impl Error for RestApiResponseError {
@@ -79,6 +86,8 @@ impl Error for RestApiResponseError {
             RestApiResponseError::RequestHandlerError(_) => None,
             RestApiResponseError::DatabaseError(_) => None,
             RestApiResponseError::NotFoundError(_) => None,
+            RestApiResponseError::Unauthorized(_) => None,
+            RestApiResponseError::Forbidden(_) => None,
         }
     }
 }
@@ -98,6 +107,8 @@ impl fmt::Display for RestApiResponseError {
             }
             RestApiResponseError::NotFoundError(ref s) => write!(f, "Not Found Error: {}", s),
             RestApiResponseError::DatabaseError(ref s) => write!(f, "Database Error: {}", s),
+            RestApiResponseError::Unauthorized(ref s) => write!(f, "Unauthorized: {}", s),
+            RestApiResponseError::Forbidden(ref s) => write!(f, "Forbidden: {}", s),
         }
     }
 }
@@ -122,6 +133,15 @@ impl RestApiResponseError {
             RestApiResponseError::NotFoundError(ref message) => {
                 Box::new(HttpResponse::NotFound().json(message).into_future())
             }
+            RestApiResponseError::Unauthorized(ref message) => Box::new(
+                HttpResponse::Unauthorized()
+                    .header(header::WWW_AUTHENTICATE, "Bearer")
+                    .json(message)
+                    .into_future(),
+            ),
+            RestApiResponseError::Forbidden(ref message) => {
+                Box::new(HttpResponse::Forbidden().json(message).into_future())
+            }
             _ => Box::new(
                 HttpResponse::InternalServerError()
                     .json("Internal Server Error")
@@ -146,6 +166,10 @@ impl ResponseError for RestApiResponseError {
             RestApiResponseError::NotFoundError(ref message) => {
                 HttpResponse::NotFound().json(message)
             }
+            RestApiResponseError::Unauthorized(ref message) => HttpResponse::Unauthorized()
+                .header(header::WWW_AUTHENTICATE, "Bearer")
+                .json(message),
+            RestApiResponseError::Forbidden(ref message) => HttpResponse::Forbidden().json(message),
             _ => HttpResponse::InternalServerError().json("Internal Server Error"),
         }
     }
@@ -193,12 +217,29 @@ impl From<diesel::result::Error> for RestApiResponseError {
     }
 }
 
+impl From<ChangeLogStoreError> for RestApiResponseError {
+    fn from(err: ChangeLogStoreError) -> Self {
+        RestApiResponseError::DatabaseError(format!("{}", err))
+    }
+}
+
 impl From<CommitStoreError> for RestApiResponseError {
     fn from(err: CommitStoreError) -> Self {
         RestApiResponseError::DatabaseError(format!("{}", err))
     }
 }
 
+impl From<DeadLetterStoreError> for RestApiResponseError {
+    fn from(err: DeadLetterStoreError) -> Self {
+        match err {
+            DeadLetterStoreError::NotFoundError(id) => RestApiResponseError::NotFoundError(
+                format!("Could not find dead letter with id: {}", id),
+            ),
+            _ => RestApiResponseError::DatabaseError(format!("{}", err)),
+        }
+    }
+}
+
 impl From<LocationStoreError> for RestApiResponseError {
     fn from(err: LocationStoreError) -> Self {
         RestApiResponseError::DatabaseError(format!("{}", err))
@@ -217,14 +258,38 @@ impl From<ProductStoreError> for RestApiResponseError {
     }
 }
 
+impl From<PurchaseOrderStoreError> for RestApiResponseError {
+    fn from(err: PurchaseOrderStoreError) -> Self {
+        RestApiResponseError::DatabaseError(format!("{}", err))
+    }
+}
+
 impl From<SchemaStoreError> for RestApiResponseError {
     fn from(err: SchemaStoreError) -> Self {
         RestApiResponseError::DatabaseError(format!("{}", err))
     }
 }
 
+impl From<SearchStoreError> for RestApiResponseError {
+    fn from(err: SearchStoreError) -> Self {
+        RestApiResponseError::DatabaseError(format!("{}", err))
+    }
+}
+
 impl From<TrackAndTraceStoreError> for RestApiResponseError {
     fn from(err: TrackAndTraceStoreError) -> Self {
         RestApiResponseError::DatabaseError(format!("{}", err))
     }
 }
+
+#[cfg(feature = "webhooks")]
+impl From<WebhookStoreError> for RestApiResponseError {
+    fn from(err: WebhookStoreError) -> Self {
+        match err {
+            WebhookStoreError::NotFoundError(resource) => {
+                RestApiResponseError::NotFoundError(format!("Could not find webhook {}", resource))
+            }
+            _ => RestApiResponseError::DatabaseError(format!("{}", err)),
+        }
+    }
+}
@@ -12,27 +12,70 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod auth;
 pub mod error;
+#[cfg(feature = "metrics")]
+mod request_metrics;
 mod routes;
+#[cfg(feature = "tls")]
+mod tls;
 
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc};
 use std::thread;
 
 use crate::config::Endpoint;
+#[cfg_attr(not(feature = "stable"), allow(unused_imports))]
+pub use crate::rest_api::auth::Principal;
+pub use crate::rest_api::auth::{Authenticator, OperatorOnly};
 pub use crate::rest_api::error::RestApiServerError;
-
+#[cfg(feature = "tls")]
+pub use crate::rest_api::tls::TlsSettings;
+
+#[cfg(feature = "metrics")]
+use crate::rest_api::routes::fetch_metrics;
+#[cfg(feature = "workflow")]
+use crate::rest_api::routes::fetch_workflow_actions;
+#[cfg(feature = "search")]
+use crate::rest_api::routes::search;
+#[cfg(feature = "change-stream")]
+use crate::rest_api::routes::stream_changes;
+#[cfg(feature = "webhooks")]
+use crate::rest_api::routes::{
+    add_webhook_subscription, fetch_webhook_subscription, list_webhook_deliveries,
+    list_webhook_subscriptions, remove_webhook_subscription, retry_webhook_delivery,
+};
+#[cfg(feature = "event")]
+use crate::rest_api::routes::{
+    discard_dead_letter, fetch_indexing_status, list_dead_letters, replay_dead_letter,
+};
 #[cfg(feature = "pike")]
-use crate::rest_api::routes::{fetch_agent, fetch_organization, list_agents, list_organizations};
+use crate::rest_api::routes::{
+    fetch_agent, fetch_organization, fetch_organization_history, list_agents, list_organizations,
+};
 #[cfg(feature = "schema")]
 use crate::rest_api::routes::{fetch_grid_schema, list_grid_schemas};
 #[cfg(feature = "location")]
-use crate::rest_api::routes::{fetch_location, list_locations};
+use crate::rest_api::routes::{fetch_location, fetch_location_history, list_locations};
 #[cfg(feature = "product")]
-use crate::rest_api::routes::{fetch_product, list_products};
+use crate::rest_api::routes::{fetch_product, fetch_product_history, list_products};
+#[cfg(feature = "purchase-order")]
+use crate::rest_api::routes::{
+    fetch_purchase_order, fetch_purchase_order_actions, fetch_purchase_order_revision,
+    fetch_purchase_order_version, fetch_purchase_order_version_actions,
+    list_purchase_order_revisions, list_purchase_order_versions, list_purchase_orders,
+};
 #[cfg(feature = "track-and-trace")]
-use crate::rest_api::routes::{fetch_record, fetch_record_property, list_records};
+use crate::rest_api::routes::{
+    fetch_record, fetch_record_contents, fetch_record_epcis, fetch_record_property,
+    fetch_record_reporters, list_epcis_events, list_records,
+};
 
-use crate::rest_api::routes::{get_batch_statuses, submit_batches};
+use crate::rest_api::auth::Authenticate;
+#[cfg(feature = "metrics")]
+use crate::rest_api::request_metrics::RecordRequestMetrics;
+use crate::rest_api::routes::{
+    check_readiness, fetch_liveness, get_batch_statuses, submit_batches,
+};
 
 use crate::submitter::BatchSubmitter;
 use actix::{Addr, SyncArbiter};
@@ -43,13 +86,21 @@ use actix_web::{
 };
 use futures::executor::block_on;
 use futures::future;
+#[cfg(any(feature = "location", feature = "product"))]
+use grid_sdk::filter::ListFilter;
 #[cfg(feature = "integration")]
 use grid_sdk::rest_api::actix_web_3::{routes::submit, State as IntegrationState};
 use serde::{Deserialize, Serialize};
 
 pub use self::routes::DbExecutor;
+#[cfg(any(feature = "change-stream", feature = "webhooks"))]
+pub(crate) use self::routes::{
+    fetch_property_value_slice, fetch_record_slice, AgentSlice, GridSchemaSlice, LocationSlice,
+    OrganizationSlice, ProductSlice,
+};
 
-const SYNC_ARBITER_THREAD_COUNT: usize = 2;
+/// The number of threads that serve database queries for the REST API by default
+pub const DEFAULT_DB_EXECUTOR_THREADS: usize = 2;
 
 pub struct AppState {
     batch_submitter: Box<dyn BatchSubmitter + 'static>,
@@ -60,9 +111,10 @@ impl AppState {
     pub fn new(
         batch_submitter: Box<dyn BatchSubmitter + 'static>,
         db_executor: DbExecutor,
+        db_executor_threads: usize,
     ) -> Self {
         let database_connection =
-            SyncArbiter::start(SYNC_ARBITER_THREAD_COUNT, move || db_executor.clone());
+            SyncArbiter::start(db_executor_threads, move || db_executor.clone());
 
         AppState {
             batch_submitter,
@@ -86,13 +138,22 @@ pub struct QueryServiceId {
     pub wait: Option<u64>,
 }
 
+/// Selects the commit, by commit number or block ID, at which state is read. Reported values
+/// pruned once a property's page ring wraps are not returned, even as of an earlier commit.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryAsOf {
+    pub as_of: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct QueryPaging {
     pub offset: Option<u64>,
     pub limit: Option<u16>,
+    /// Selects a page by key instead of by offset; empty for the first page
+    pub cursor: Option<String>,
 }
 
-#[cfg(feature = "pike")]
+#[cfg(any(feature = "pike", feature = "event"))]
 impl QueryPaging {
     pub fn offset(&self) -> u64 {
         self.offset.unwrap_or(0)
@@ -140,6 +201,50 @@ impl FromRequest for AcceptServiceIdParam {
     }
 }
 
+/// The filters and sort order of a list request, taken from any number of `filter` query params
+/// and an optional `sort` query param
+#[cfg(any(feature = "location", feature = "product"))]
+pub struct QueryListFilter {
+    pub filter: ListFilter,
+    /// The `filter` and `sort` query params, for the paging links of the list to keep
+    pub params: Vec<(String, String)>,
+}
+
+#[cfg(any(feature = "location", feature = "product"))]
+impl FromRequest for QueryListFilter {
+    type Error = ActixError;
+    type Future = future::Ready<Result<Self, Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _: &mut dev::Payload) -> Self::Future {
+        let params = match web::Query::<Vec<(String, String)>>::from_query(req.query_string()) {
+            Ok(params) => params.into_inner(),
+            Err(_) => return future::err(ErrorBadRequest("Malformed query param")),
+        };
+
+        let mut list_filter = ListFilter::default();
+        let mut filter_params = vec![];
+
+        for (key, value) in params {
+            let result = match key.as_str() {
+                "filter" => value.parse().map(|filter| list_filter.filters.push(filter)),
+                "sort" => value.parse().map(|sort| list_filter.sort = sort),
+                _ => continue,
+            };
+
+            if let Err(err) = result {
+                return future::err(ErrorBadRequest(err.to_string()));
+            }
+            filter_params.push((key, value));
+        }
+
+        future::ok(QueryListFilter {
+            filter: list_filter,
+            params: filter_params,
+        })
+    }
+}
+
 pub struct RestApiShutdownHandle {
     server: dev::Server,
 }
@@ -153,8 +258,11 @@ impl RestApiShutdownHandle {
 pub fn run(
     bind_url: &str,
     db_executor: DbExecutor,
+    db_executor_threads: usize,
     batch_submitter: Box<dyn BatchSubmitter + 'static>,
     endpoint: Endpoint,
+    authenticator: Option<Authenticator>,
+    #[cfg(feature = "tls")] tls: Option<TlsSettings>,
     #[cfg(feature = "integration")] integration_state: IntegrationState,
 ) -> Result<
     (
@@ -164,18 +272,29 @@ pub fn run(
     RestApiServerError,
 > {
     let bind_url = bind_url.to_owned();
+    let authenticator = authenticator.map(Arc::new);
     let (tx, rx) = mpsc::channel();
 
     let join_handle = thread::Builder::new()
         .name("GridRestApi".into())
         .spawn(move || {
             let sys = actix::System::new("Grid-Rest-API");
-            let state = AppState::new(batch_submitter, db_executor);
+            let state = AppState::new(batch_submitter, db_executor, db_executor_threads);
+
+            #[cfg(feature = "tls")]
+            let client_scopes = tls.as_ref().and_then(TlsSettings::client_scopes);
+            #[cfg(feature = "tls")]
+            let on_connect_scopes = client_scopes.clone();
+
+            let server = HttpServer::new(move || {
+                let authenticate = Authenticate::new(authenticator.clone());
+                #[cfg(feature = "tls")]
+                let authenticate = authenticate.with_client_scopes(client_scopes.clone());
 
-            let addr = HttpServer::new(move || {
                 #[allow(clippy::let_and_return)]
                 #[allow(unused_mut)]
                 let mut app = App::new()
+                    .wrap(authenticate)
                     .data(state.clone())
                     .app_data(endpoint.clone())
                     .service(web::resource("/batches").route(web::post().to(submit_batches)))
@@ -183,7 +302,68 @@ pub fn run(
                         web::resource("/batch_statuses")
                             .name("batch_statuses")
                             .route(web::get().to(get_batch_statuses)),
+                    )
+                    .service(
+                        web::scope("/health")
+                            .service(web::resource("/live").route(web::get().to(fetch_liveness)))
+                            .service(web::resource("/ready").route(web::get().to(check_readiness))),
+                    );
+
+                #[cfg(feature = "event")]
+                {
+                    app = app.service(
+                        web::scope("/admin/dead_letters")
+                            .service(web::resource("").route(web::get().to(list_dead_letters)))
+                            .service(
+                                web::resource("/{id}").route(web::delete().to(discard_dead_letter)),
+                            )
+                            .service(
+                                web::resource("/{id}/replay")
+                                    .route(web::post().to(replay_dead_letter)),
+                            ),
+                    );
+                    app = app.service(
+                        web::resource("/admin/indexing")
+                            .route(web::get().to(fetch_indexing_status)),
                     );
+                }
+
+                #[cfg(feature = "change-stream")]
+                {
+                    app =
+                        app.service(web::resource("/changes").route(web::get().to(stream_changes)));
+                }
+
+                #[cfg(feature = "metrics")]
+                {
+                    app =
+                        app.service(web::resource("/metrics").route(web::get().to(fetch_metrics)));
+                }
+
+                #[cfg(feature = "webhooks")]
+                {
+                    app = app.service(
+                        web::scope("/admin/webhooks")
+                            .service(
+                                web::resource("")
+                                    .route(web::get().to(list_webhook_subscriptions))
+                                    .route(web::post().to(add_webhook_subscription)),
+                            )
+                            .service(
+                                web::resource("/deliveries/{id}/retry")
+                                    .route(web::post().to(retry_webhook_delivery)),
+                            )
+                            .service(
+                                web::resource("/{id}")
+                                    .route(web::get().to(fetch_webhook_subscription))
+                                    .route(web::delete().to(remove_webhook_subscription)),
+                            )
+                            .service(
+                                web::resource("/{id}/deliveries")
+                                    .route(web::get().to(list_webhook_deliveries)),
+                            ),
+                    );
+                }
 
                 #[cfg(feature = "pike")]
                 {
@@ -201,6 +381,10 @@ pub fn run(
                                 .service(web::resource("").route(web::get().to(list_organizations)))
                                 .service(
                                     web::resource("/{id}").route(web::get().to(fetch_organization)),
+                                )
+                                .service(
+                                    web::resource("/{id}/history")
+                                        .route(web::get().to(fetch_organization_history)),
                                 ),
                         );
                 }
@@ -210,7 +394,11 @@ pub fn run(
                     app = app.service(
                         web::scope("/location")
                             .service(web::resource("").route(web::get().to(list_locations)))
-                            .service(web::resource("/{id}").route(web::get().to(fetch_location))),
+                            .service(web::resource("/{id}").route(web::get().to(fetch_location)))
+                            .service(
+                                web::resource("/{id}/history")
+                                    .route(web::get().to(fetch_location_history)),
+                            ),
                     );
                 }
 
@@ -219,7 +407,59 @@ pub fn run(
                     app = app.service(
                         web::scope("/product")
                             .service(web::resource("").route(web::get().to(list_products)))
-                            .service(web::resource("/{id}").route(web::get().to(fetch_product))),
+                            .service(web::resource("/{id}").route(web::get().to(fetch_product)))
+                            .service(
+                                web::resource("/{id}/history")
+                                    .route(web::get().to(fetch_product_history)),
+                            ),
+                    );
+                }
+
+                #[cfg(feature = "purchase-order")]
+                {
+                    app = app.service(
+                        web::scope("/purchase_order")
+                            .service(web::resource("").route(web::get().to(list_purchase_orders)))
+                            .service(
+                                web::scope("/{uid}")
+                                    .service(
+                                        web::resource("")
+                                            .route(web::get().to(fetch_purchase_order)),
+                                    )
+                                    .service(
+                                        web::resource("/version")
+                                            .route(web::get().to(list_purchase_order_versions)),
+                                    )
+                                    .service(
+                                        web::resource("/version/{version_id}")
+                                            .route(web::get().to(fetch_purchase_order_version)),
+                                    )
+                                    .service(
+                                        web::resource("/version/{version_id}/revision")
+                                            .route(web::get().to(list_purchase_order_revisions)),
+                                    )
+                                    .service(
+                                        web::resource(
+                                            "/version/{version_id}/revision/{revision_id}",
+                                        )
+                                        .route(web::get().to(fetch_purchase_order_revision)),
+                                    )
+                                    .service(
+                                        web::resource("/actions")
+                                            .route(web::get().to(fetch_purchase_order_actions)),
+                                    )
+                                    .service(web::resource("/version/{version_id}/actions").route(
+                                        web::get().to(fetch_purchase_order_version_actions),
+                                    )),
+                            ),
+                    );
+                }
+
+                #[cfg(feature = "workflow")]
+                {
+                    app = app.service(
+                        web::resource("/workflow/{workflow}/{subworkflow}/{state}/actions")
+                            .route(web::get().to(fetch_workflow_actions)),
                     );
                 }
 
@@ -234,6 +474,11 @@ pub fn run(
                     );
                 }
 
+                #[cfg(feature = "search")]
+                {
+                    app = app.service(web::resource("/search").route(web::get().to(search)));
+                }
+
                 #[cfg(feature = "track-and-trace")]
                 {
                     app = app.service(
@@ -245,11 +490,30 @@ pub fn run(
                                     .service(
                                         web::resource("/property/{property_name}")
                                             .route(web::get().to(fetch_record_property)),
+                                    )
+                                    .service(
+                                        web::resource("/reporters")
+                                            .route(web::get().to(fetch_record_reporters)),
+                                    )
+                                    .service(
+                                        web::resource("/contents")
+                                            .route(web::get().to(fetch_record_contents)),
+                                    )
+                                    .service(
+                                        web::resource("/epcis")
+                                            .route(web::get().to(fetch_record_epcis)),
                                     ),
                             ),
                     );
                 }
 
+                #[cfg(feature = "track-and-trace")]
+                {
+                    app = app.service(
+                        web::resource("/epcis/events").route(web::get().to(list_epcis_events)),
+                    );
+                }
+
                 #[cfg(feature = "integration")]
                 {
                     app = app
@@ -257,12 +521,29 @@ pub fn run(
                         .service(web::scope("/integration").service(submit));
                 }
 
+                // Wrapped last, so that requests rejected by the other middleware are recorded
+                #[cfg(feature = "metrics")]
+                let app = app.wrap(RecordRequestMetrics);
+
                 app
-            })
-            .bind(bind_url)?
-            .disable_signals()
-            .system_exit()
-            .run();
+            });
+
+            #[cfg(feature = "tls")]
+            let server = match tls {
+                Some(tls) => {
+                    let server = match on_connect_scopes {
+                        Some(client_scopes) => server
+                            .on_connect(move |connection, _| client_scopes.on_connect(connection)),
+                        None => server,
+                    };
+                    server.bind_openssl(bind_url, tls.into_acceptor())?
+                }
+                None => server.bind(bind_url)?,
+            };
+            #[cfg(not(feature = "tls"))]
+            let server = server.bind(bind_url)?;
+
+            let addr = server.disable_signals().system_exit().run();
 
             tx.send(addr).map_err(|err| {
                 RestApiServerError::StartUpError(format!("Unable to send Server Addr: {}", err))
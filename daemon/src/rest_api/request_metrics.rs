@@ -0,0 +1,100 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Middleware that records the count and latency of REST API requests
+
+use std::task::{Context as TaskContext, Poll};
+use std::time::Instant;
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::Error as ActixError,
+};
+use futures::future::{self, LocalBoxFuture};
+
+use crate::metrics;
+
+/// The route recorded for requests that match no resource, so that arbitrary paths don't each
+/// get their own metrics
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Records each request by its method, the pattern of the route it matched, and its response
+/// status
+#[derive(Clone, Default)]
+pub struct RecordRequestMetrics;
+
+impl<S, B> Transform<S> for RecordRequestMetrics
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>
+        + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type InitError = ();
+    type Transform = RecordRequestMetricsMiddleware<S>;
+    type Future = future::Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        future::ok(RecordRequestMetricsMiddleware { service })
+    }
+}
+
+pub struct RecordRequestMetricsMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service for RecordRequestMetricsMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>
+        + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        let start = Instant::now();
+        let method = req.method().to_string();
+        let route = req
+            .match_pattern()
+            .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+        let response = self.service.call(req);
+
+        Box::pin(async move {
+            let response = response.await;
+            let status = match &response {
+                Ok(response) => response.status(),
+                Err(err) => err.as_response_error().status_code(),
+            };
+            metrics::record_request(
+                &method,
+                &route,
+                status.as_u16(),
+                start.elapsed().as_secs_f64(),
+            );
+            response
+        })
+    }
+}
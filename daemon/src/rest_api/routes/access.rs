@@ -0,0 +1,101 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::rest_api::{error::RestApiResponseError, routes::DbExecutor, Principal};
+
+impl DbExecutor {
+    /// Returns the IDs, out of those given, of the records the principal may read
+    ///
+    /// An agent may read a record if it, or another agent of its organization, is the record's
+    /// current owner or custodian.
+    pub(super) fn readable_records(
+        &self,
+        principal: &Principal,
+        record_ids: &[String],
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<String>, RestApiResponseError> {
+        if principal.is_unrestricted() || record_ids.is_empty() {
+            return Ok(record_ids.to_vec());
+        }
+
+        let associated_agents =
+            self.tnt_store
+                .list_associated_agents(record_ids, commit_height, service_id)?;
+        let mut agent_orgs: HashMap<String, Option<String>> = HashMap::new();
+        let mut readable = Vec::new();
+
+        for record_id in record_ids {
+            let current_agents = ["OWNER", "CUSTODIAN"].iter().filter_map(|role| {
+                associated_agents
+                    .iter()
+                    .filter(|agent| &agent.record_id == record_id && agent.role == *role)
+                    .max_by_key(|agent| agent.timestamp)
+            });
+
+            for agent in current_agents {
+                if principal.public_key() == Some(agent.agent_id.as_str()) {
+                    readable.push(record_id.clone());
+                    break;
+                }
+
+                let org_id = match agent_orgs.get(&agent.agent_id) {
+                    Some(org_id) => org_id.clone(),
+                    None => {
+                        let org_id = self
+                            .pike_store
+                            .fetch_agent(&agent.agent_id, commit_height, service_id)?
+                            .map(|agent| agent.org_id);
+                        agent_orgs.insert(agent.agent_id.clone(), org_id.clone());
+                        org_id
+                    }
+                };
+
+                if org_id.map(|org_id| principal.can_read_org(&org_id)) == Some(true) {
+                    readable.push(record_id.clone());
+                    break;
+                }
+            }
+        }
+
+        Ok(readable)
+    }
+
+    /// Fails as if the record did not exist if the principal may not read it
+    pub(super) fn check_record_access(
+        &self,
+        principal: &Principal,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<(), RestApiResponseError> {
+        if self
+            .readable_records(
+                principal,
+                &[record_id.to_string()],
+                commit_height,
+                service_id,
+            )?
+            .is_empty()
+        {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find record with id: {}",
+                record_id
+            )));
+        }
+        Ok(())
+    }
+}
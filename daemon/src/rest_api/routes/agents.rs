@@ -17,7 +17,7 @@ use std::{convert::TryFrom, str::FromStr};
 use crate::rest_api::{
     error::RestApiResponseError,
     routes::{paging::Paging, DbExecutor},
-    AcceptServiceIdParam, AppState, QueryPaging, QueryServiceId,
+    AcceptServiceIdParam, AppState, Principal, QueryAsOf, QueryPaging, QueryServiceId,
 };
 
 use actix::{Handler, Message, SyncContext};
@@ -70,9 +70,12 @@ impl TryFrom<Agent> for AgentSlice {
 }
 
 struct ListAgents {
+    as_of: Option<String>,
     service_id: Option<String>,
     offset: u64,
     limit: u16,
+    cursor: Option<String>,
+    principal: Principal,
 }
 
 impl Message for ListAgents {
@@ -87,9 +90,15 @@ impl Handler<ListAgents> for DbExecutor {
 
         let limit = i64::try_from(msg.limit).unwrap_or(10);
 
-        let agent_list = self
-            .pike_store
-            .list_agents(msg.service_id.as_deref(), offset, limit)?;
+        let page = self.page_query(msg.as_of.as_deref(), msg.cursor.as_deref(), offset, limit)?;
+        let commit_height = page.commit_height;
+
+        let agent_list = self.pike_store.list_agents(
+            commit_height,
+            msg.principal.org_id(),
+            msg.service_id.as_deref(),
+            &page.request,
+        )?;
 
         let data = agent_list
             .data
@@ -97,7 +106,7 @@ impl Handler<ListAgents> for DbExecutor {
             .map(AgentSlice::try_from)
             .collect::<Result<Vec<AgentSlice>, RestApiResponseError>>()?;
 
-        let paging = Paging::new("/agent", agent_list.paging, msg.service_id.as_deref());
+        let paging = page.paging("/agent", agent_list.paging, msg.service_id.as_deref());
 
         Ok(AgentListSlice { data, paging })
     }
@@ -107,15 +116,20 @@ pub async fn list_agents(
     state: web::Data<AppState>,
     query_service_id: web::Query<QueryServiceId>,
     query_paging: web::Query<QueryPaging>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     let paging = query_paging.into_inner();
     state
         .database_connection
         .send(ListAgents {
+            as_of: query_as_of.into_inner().as_of,
             service_id: query_service_id.into_inner().service_id,
             offset: paging.offset(),
             limit: paging.limit(),
+            cursor: paging.cursor,
+            principal,
         })
         .await?
         .map(|agents| HttpResponse::Ok().json(agents))
@@ -123,7 +137,9 @@ pub async fn list_agents(
 
 struct FetchAgent {
     public_key: String,
+    as_of: Option<String>,
     service_id: Option<String>,
+    principal: Principal,
 }
 
 impl Message for FetchAgent {
@@ -134,12 +150,15 @@ impl Handler<FetchAgent> for DbExecutor {
     type Result = Result<AgentSlice, RestApiResponseError>;
 
     fn handle(&mut self, msg: FetchAgent, _: &mut SyncContext<Self>) -> Self::Result {
-        match self
-            .pike_store
-            .fetch_agent(&msg.public_key, msg.service_id.as_deref())?
-        {
-            Some(agent) => AgentSlice::try_from(agent),
-            None => Err(RestApiResponseError::NotFoundError(format!(
+        let commit_height = self.commit_height(msg.as_of.as_deref())?;
+
+        match self.pike_store.fetch_agent(
+            &msg.public_key,
+            commit_height,
+            msg.service_id.as_deref(),
+        )? {
+            Some(agent) if msg.principal.can_read_org(&agent.org_id) => AgentSlice::try_from(agent),
+            _ => Err(RestApiResponseError::NotFoundError(format!(
                 "Could not find agent with public key: {}",
                 msg.public_key
             ))),
@@ -151,13 +170,17 @@ pub async fn fetch_agent(
     state: web::Data<AppState>,
     public_key: web::Path<String>,
     query: web::Query<QueryServiceId>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     state
         .database_connection
         .send(FetchAgent {
             public_key: public_key.into_inner(),
+            as_of: query_as_of.into_inner().as_of,
             service_id: query.into_inner().service_id,
+            principal,
         })
         .await?
         .map(|agent| HttpResponse::Ok().json(agent))
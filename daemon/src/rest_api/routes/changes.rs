@@ -0,0 +1,483 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::event::entity::EntityType;
+use crate::rest_api::{
+    error::RestApiResponseError, routes::DbExecutor, AcceptServiceIdParam, AppState, Principal,
+    QueryServiceId,
+};
+
+use actix::{Handler, Message, SyncContext};
+use actix_rt::time::{interval_at, Instant};
+use actix_web::{web, web::Bytes, HttpRequest, HttpResponse};
+use futures::channel::mpsc::Receiver;
+use futures::future;
+use futures::stream::{self, Stream, StreamExt};
+use grid_sdk::change_log::ChangeLogEntry;
+use serde::{Deserialize, Serialize};
+
+/// The number of commits read from the change log at a time while a client catches up
+const REPLAY_COMMIT_LIMIT: i64 = 100;
+
+/// The header an `EventSource` sends when it reconnects, holding the ID of the last event it
+/// received
+const LAST_EVENT_ID: &str = "Last-Event-ID";
+
+/// How often a comment is sent on the change stream, so that proxies and clients do not close a
+/// connection that has been idle while no changes were committed
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A server-sent event comment, which an `EventSource` ignores
+const HEARTBEAT: &[u8] = b": heartbeat\n\n";
+
+/// Selects the changes sent on the change stream
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryChangeStream {
+    /// A comma-separated list of entity types
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    /// The ID of the last commit the client has seen; changes after it are replayed first
+    pub since: Option<String>,
+}
+
+struct ChangeFilter {
+    entity_types: Vec<String>,
+    entity_id: Option<String>,
+    service_id: Option<String>,
+    /// The organization that must own the changed entities, when the client is restricted to one
+    owner: Option<String>,
+}
+
+impl ChangeFilter {
+    fn matches(&self, entry: &ChangeLogEntry) -> bool {
+        entry.service_id == self.service_id
+            && (self.entity_types.is_empty() || self.entity_types.contains(&entry.entity_type))
+            && self
+                .entity_id
+                .as_ref()
+                .map(|entity_id| entity_id == &entry.entity_id)
+                .unwrap_or(true)
+            && self
+                .owner
+                .as_ref()
+                .map(|owner| entry_owner(entry).as_ref() == Some(owner))
+                .unwrap_or(true)
+    }
+}
+
+/// Returns the organization that owns the entity a change is for, as recorded in its payload
+fn entry_owner(entry: &ChangeLogEntry) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(&entry.payload)
+        .ok()?
+        .get("owner")?
+        .as_str()
+        .map(String::from)
+}
+
+/// A batch of changes on its way to a client
+enum ChangeBatch {
+    /// Changes read from the change log, which may cover several commits
+    Replayed(Vec<ChangeLogEntry>),
+    /// The changes of a commit published while the client is connected
+    Live(Arc<Vec<ChangeLogEntry>>),
+}
+
+struct SubscribeToChanges {
+    since: Option<String>,
+}
+
+impl Message for SubscribeToChanges {
+    type Result = Result<(Receiver<Arc<Vec<ChangeLogEntry>>>, Option<i64>), RestApiResponseError>;
+}
+
+impl Handler<SubscribeToChanges> for DbExecutor {
+    type Result = Result<(Receiver<Arc<Vec<ChangeLogEntry>>>, Option<i64>), RestApiResponseError>;
+
+    fn handle(&mut self, msg: SubscribeToChanges, _: &mut SyncContext<Self>) -> Self::Result {
+        let since = match msg.since {
+            Some(commit_id) => Some(
+                self.commit_store
+                    .get_commit_by_commit_id(&commit_id)?
+                    .ok_or_else(|| {
+                        RestApiResponseError::NotFoundError(format!(
+                            "Could not find commit with id: {}",
+                            commit_id
+                        ))
+                    })?
+                    .commit_num,
+            ),
+            None => None,
+        };
+
+        // Subscribing before the change log is read ensures no commit is missed in between
+        Ok((self.change_broadcaster.subscribe(), since))
+    }
+}
+
+struct ListChanges {
+    after_commit_num: i64,
+    service_id: Option<String>,
+}
+
+impl Message for ListChanges {
+    type Result = Result<Vec<ChangeLogEntry>, RestApiResponseError>;
+}
+
+impl Handler<ListChanges> for DbExecutor {
+    type Result = Result<Vec<ChangeLogEntry>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListChanges, _: &mut SyncContext<Self>) -> Self::Result {
+        Ok(self.change_log_store.list_entries(
+            msg.after_commit_num,
+            msg.service_id.as_deref(),
+            REPLAY_COMMIT_LIMIT,
+        )?)
+    }
+}
+
+/// Streams entity changes as server-sent events
+///
+/// Each event is named after the kind of change, such as `product.created`, and carries the
+/// change as JSON. The last event of each commit carries the commit's ID, so a client that
+/// reconnects with `since` or the `Last-Event-ID` header set to it is first sent the changes of
+/// the commits that followed.
+pub async fn stream_changes(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<QueryChangeStream>,
+    query_service_id: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let query = query.into_inner();
+    let entity_types = query
+        .entity_type
+        .as_deref()
+        .map(|entity_types| {
+            entity_types
+                .split(',')
+                .map(|entity_type| {
+                    entity_type
+                        .parse::<EntityType>()
+                        .map(|entity_type| entity_type.to_string())
+                        .map_err(|err| RestApiResponseError::BadRequest(err.0))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let service_id = query_service_id.into_inner().service_id;
+    let filter = ChangeFilter {
+        entity_types,
+        entity_id: query.entity_id,
+        service_id: service_id.clone(),
+        owner: principal.org_id().map(String::from),
+    };
+
+    // An EventSource reconnects with the URL it was opened with, so the header takes precedence
+    let since = req
+        .headers()
+        .get(LAST_EVENT_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+        .or(query.since);
+
+    let (receiver, since) = state
+        .database_connection
+        .send(SubscribeToChanges { since })
+        .await??;
+
+    let database_connection = state.database_connection.clone();
+    let replayed = stream::unfold(since, move |after_commit_num| {
+        let database_connection = database_connection.clone();
+        let service_id = service_id.clone();
+        async move {
+            let after_commit_num = after_commit_num?;
+            let result = database_connection
+                .send(ListChanges {
+                    after_commit_num,
+                    service_id,
+                })
+                .await
+                .map_err(RestApiResponseError::from)
+                .and_then(|result| result);
+            match result {
+                Ok(entries) if entries.is_empty() => None,
+                Ok(entries) => {
+                    let last_commit_num = entries.last().map(|entry| entry.commit_num);
+                    Some((Ok(ChangeBatch::Replayed(entries)), last_commit_num))
+                }
+                Err(err) => Some((Err(err), None)),
+            }
+        }
+    });
+    let live = receiver.map(|changes| Ok(ChangeBatch::Live(changes)));
+    let heartbeat =
+        interval_at(Instant::now() + HEARTBEAT_INTERVAL, HEARTBEAT_INTERVAL).map(|_| ());
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .header("Cache-Control", "no-cache")
+        .streaming(Box::pin(with_heartbeat(
+            format_stream(replayed.chain(live), filter),
+            heartbeat,
+        ))))
+}
+
+/// Sends a heartbeat comment between the events each time `heartbeat` yields, ending when the
+/// events end
+fn with_heartbeat<S, H>(
+    events: S,
+    heartbeat: H,
+) -> impl Stream<Item = Result<Bytes, RestApiResponseError>>
+where
+    S: Stream<Item = Result<Bytes, RestApiResponseError>>,
+    H: Stream<Item = ()>,
+{
+    let events = events.map(Some).chain(stream::once(future::ready(None)));
+    let heartbeat = heartbeat.map(|_| Some(Ok(Bytes::from_static(HEARTBEAT))));
+
+    stream::select(events, heartbeat)
+        .take_while(|item| future::ready(item.is_some()))
+        .filter_map(future::ready)
+}
+
+/// Formats a stream of replayed batches followed by live ones as server-sent events. Live commits
+/// that were also replayed, having been published while the change log was read, are skipped.
+fn format_stream<S>(
+    batches: S,
+    filter: ChangeFilter,
+) -> impl Stream<Item = Result<Bytes, RestApiResponseError>>
+where
+    S: Stream<Item = Result<ChangeBatch, RestApiResponseError>>,
+{
+    batches
+        .scan(None, move |last_replayed, batch| {
+            let events = batch
+                .map(|batch| match batch {
+                    ChangeBatch::Replayed(entries) => {
+                        *last_replayed = entries
+                            .last()
+                            .map(|entry| (entry.commit_num, entry.commit_id.clone()));
+                        format_events(&entries, &filter)
+                    }
+                    ChangeBatch::Live(entries) => {
+                        let replayed = match (entries.first(), last_replayed.as_ref()) {
+                            (Some(entry), Some((commit_num, commit_id))) => {
+                                entry.commit_num < *commit_num
+                                    || (entry.commit_num == *commit_num
+                                        && &entry.commit_id == commit_id)
+                            }
+                            _ => false,
+                        };
+                        if replayed {
+                            String::new()
+                        } else {
+                            format_events(&entries, &filter)
+                        }
+                    }
+                })
+                .map_err(|err| {
+                    error!("Unable to stream changes: {}", err);
+                    err
+                });
+            future::ready(Some(events))
+        })
+        .filter(|events| future::ready(!matches!(events, Ok(events) if events.is_empty())))
+        .map(|events| events.map(Bytes::from))
+}
+
+/// Formats the entries that match the filter as server-sent events, giving the last event of each
+/// commit the commit's ID
+fn format_events(entries: &[ChangeLogEntry], filter: &ChangeFilter) -> String {
+    let entries = entries
+        .iter()
+        .filter(|entry| filter.matches(entry))
+        .collect::<Vec<_>>();
+
+    let mut events = String::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let last_of_commit = entries
+            .get(index + 1)
+            .map(|next| next.commit_id != entry.commit_id)
+            .unwrap_or(true);
+        if last_of_commit {
+            let _ = writeln!(events, "id: {}", entry.commit_id);
+        }
+        let _ = write!(
+            events,
+            "event: {}\ndata: {}\n\n",
+            entry.event_type, entry.payload
+        );
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::executor::block_on;
+
+    fn entry(commit_id: &str, entity_type: &str, entity_id: &str) -> ChangeLogEntry {
+        ChangeLogEntry {
+            commit_id: commit_id.to_string(),
+            commit_num: commit_id.trim_start_matches("commit-").parse().unwrap_or(0),
+            service_id: None,
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.to_string(),
+            event_type: format!("{}.updated", entity_type),
+            payload: format!("{{\"entity_id\":\"{}\"}}", entity_id),
+        }
+    }
+
+    #[test]
+    // Test that only the matching changes are formatted, and that the last event of each commit
+    // carries its ID
+    fn test_format_events() {
+        let entries = vec![
+            entry("commit-1", "product", "1"),
+            entry("commit-1", "product", "2"),
+            entry("commit-1", "location", "3"),
+            entry("commit-2", "product", "1"),
+        ];
+        let filter = ChangeFilter {
+            entity_types: vec!["product".to_string()],
+            entity_id: None,
+            service_id: None,
+            owner: None,
+        };
+
+        assert_eq!(
+            format_events(&entries, &filter),
+            "event: product.updated\ndata: {\"entity_id\":\"1\"}\n\n\
+             id: commit-1\nevent: product.updated\ndata: {\"entity_id\":\"2\"}\n\n\
+             id: commit-2\nevent: product.updated\ndata: {\"entity_id\":\"1\"}\n\n"
+        );
+
+        let filter = ChangeFilter {
+            entity_types: vec![],
+            entity_id: Some("3".to_string()),
+            service_id: None,
+            owner: None,
+        };
+        assert_eq!(
+            format_events(&entries, &filter),
+            "id: commit-1\nevent: location.updated\ndata: {\"entity_id\":\"3\"}\n\n"
+        );
+
+        let filter = ChangeFilter {
+            entity_types: vec![],
+            entity_id: None,
+            service_id: Some("service-1".to_string()),
+            owner: None,
+        };
+        assert_eq!(format_events(&entries, &filter), "");
+    }
+
+    #[test]
+    // Test that a client restricted to an organization only receives changes to its entities
+    fn test_format_events_for_owner() {
+        let mut owned = entry("commit-1", "product", "1");
+        owned.payload = "{\"entity_id\":\"1\",\"owner\":\"my_org\"}".to_string();
+        let mut other = entry("commit-1", "product", "2");
+        other.payload = "{\"entity_id\":\"2\",\"owner\":\"other_org\"}".to_string();
+        let filter = ChangeFilter {
+            entity_types: vec![],
+            entity_id: None,
+            service_id: None,
+            owner: Some("my_org".to_string()),
+        };
+
+        assert_eq!(
+            format_events(&[owned, other], &filter),
+            "id: commit-1\nevent: product.updated\ndata: {\"entity_id\":\"1\",\"owner\":\"my_org\"}\n\n"
+        );
+    }
+
+    #[test]
+    // Test that heartbeats are sent between the events, and that the stream ends with the events
+    fn test_with_heartbeat() {
+        let events = stream::iter(vec![
+            Ok(Bytes::from_static(b"id: commit-1\n\n")),
+            Ok(Bytes::from_static(b"id: commit-2\n\n")),
+        ]);
+
+        let sent = block_on(with_heartbeat(events, stream::repeat(())).collect::<Vec<_>>())
+            .into_iter()
+            .map(|sent| sent.expect("Stream failed"))
+            .collect::<Vec<_>>();
+        assert!(sent.iter().any(|sent| sent.as_ref() == HEARTBEAT));
+        assert_eq!(
+            sent.into_iter()
+                .filter(|sent| sent.as_ref() != HEARTBEAT)
+                .collect::<Vec<_>>(),
+            vec![
+                Bytes::from_static(b"id: commit-1\n\n"),
+                Bytes::from_static(b"id: commit-2\n\n"),
+            ]
+        );
+    }
+
+    #[test]
+    // Test that live commits that were already replayed are skipped, while later commits and
+    // commits from a fork are sent
+    fn test_format_stream() {
+        let filter = ChangeFilter {
+            entity_types: vec![],
+            entity_id: None,
+            service_id: None,
+            owner: None,
+        };
+        let batches = stream::iter(vec![
+            Ok(ChangeBatch::Replayed(vec![
+                entry("commit-1", "product", "1"),
+                entry("commit-2", "product", "2"),
+            ])),
+            Ok(ChangeBatch::Live(Arc::new(vec![entry(
+                "commit-1", "product", "1",
+            )]))),
+            Ok(ChangeBatch::Live(Arc::new(vec![entry(
+                "commit-2", "product", "2",
+            )]))),
+            Ok(ChangeBatch::Live(Arc::new(vec![ChangeLogEntry {
+                commit_id: "fork-2".to_string(),
+                ..entry("commit-2", "product", "4")
+            }]))),
+            Ok(ChangeBatch::Live(Arc::new(vec![entry(
+                "commit-3", "product", "3",
+            )]))),
+        ]);
+
+        let events = block_on(format_stream(batches, filter).collect::<Vec<_>>())
+            .into_iter()
+            .map(|events| String::from_utf8(events.expect("Stream failed").to_vec()))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Invalid events");
+        assert_eq!(
+            events,
+            vec![
+                "id: commit-1\nevent: product.updated\ndata: {\"entity_id\":\"1\"}\n\n\
+                 id: commit-2\nevent: product.updated\ndata: {\"entity_id\":\"2\"}\n\n",
+                "id: fork-2\nevent: product.updated\ndata: {\"entity_id\":\"4\"}\n\n",
+                "id: commit-3\nevent: product.updated\ndata: {\"entity_id\":\"3\"}\n\n",
+            ]
+        );
+    }
+}
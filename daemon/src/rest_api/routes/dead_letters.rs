@@ -0,0 +1,200 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use crate::event;
+use crate::rest_api::{
+    error::RestApiResponseError,
+    routes::{paging::Paging, DbExecutor},
+    AppState, OperatorOnly, QueryPaging,
+};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+use grid_sdk::dead_letters::DeadLetter;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLetterSlice {
+    pub id: i64,
+    pub handler: String,
+    pub commit_id: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_height: Option<u64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+    pub state_change_count: usize,
+    pub error: String,
+    pub attempts: i32,
+    pub created_at: i64,
+}
+
+impl From<DeadLetter> for DeadLetterSlice {
+    fn from(dead_letter: DeadLetter) -> Self {
+        Self {
+            id: dead_letter.id.unwrap_or_default(),
+            handler: dead_letter.handler,
+            commit_id: dead_letter.event.id,
+            commit_height: dead_letter.event.height,
+            service_id: dead_letter.event.service_id,
+            state_change_count: dead_letter.event.state_changes.len(),
+            error: dead_letter.error,
+            attempts: dead_letter.attempts,
+            created_at: dead_letter.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLetterListSlice {
+    pub data: Vec<DeadLetterSlice>,
+    pub paging: Paging,
+}
+
+struct ListDeadLetters {
+    offset: u64,
+    limit: u16,
+}
+
+impl Message for ListDeadLetters {
+    type Result = Result<DeadLetterListSlice, RestApiResponseError>;
+}
+
+impl Handler<ListDeadLetters> for DbExecutor {
+    type Result = Result<DeadLetterListSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListDeadLetters, _: &mut SyncContext<Self>) -> Self::Result {
+        let offset = i64::try_from(msg.offset).unwrap_or(i64::MAX);
+
+        let limit = i64::from(msg.limit);
+
+        let dead_letter_list = self.dead_letter_store.list_dead_letters(offset, limit)?;
+
+        let data = dead_letter_list
+            .data
+            .into_iter()
+            .map(DeadLetterSlice::from)
+            .collect();
+
+        let paging = Paging::new("/admin/dead_letters", dead_letter_list.paging, None);
+
+        Ok(DeadLetterListSlice { data, paging })
+    }
+}
+
+pub async fn list_dead_letters(
+    state: web::Data<AppState>,
+    query_paging: web::Query<QueryPaging>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let paging = query_paging.into_inner();
+    state
+        .database_connection
+        .send(ListDeadLetters {
+            offset: paging.offset(),
+            limit: paging.limit(),
+        })
+        .await?
+        .map(|dead_letters| HttpResponse::Ok().json(dead_letters))
+}
+
+struct ReplayDeadLetter {
+    id: i64,
+}
+
+impl Message for ReplayDeadLetter {
+    type Result = Result<(), RestApiResponseError>;
+}
+
+impl Handler<ReplayDeadLetter> for DbExecutor {
+    type Result = Result<(), RestApiResponseError>;
+
+    fn handle(&mut self, msg: ReplayDeadLetter, _: &mut SyncContext<Self>) -> Self::Result {
+        if self.dead_letter_store.fetch_dead_letter(msg.id)?.is_none() {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find dead letter with id: {}",
+                msg.id
+            )));
+        }
+
+        let event_handlers = self.event_handlers.lock().map_err(|_| {
+            RestApiResponseError::RequestHandlerError(
+                "Cannot access event handlers: mutex lock poisoned".to_string(),
+            )
+        })?;
+
+        event::replay_dead_letter(
+            &*self.dead_letter_store,
+            &*self.commit_store,
+            &self.indexing_status,
+            &event_handlers,
+            msg.id,
+        )
+        .map_err(|err| {
+            RestApiResponseError::DatabaseError(format!(
+                "Unable to replay dead letter {}: {}",
+                msg.id, err
+            ))
+        })
+    }
+}
+
+pub async fn replay_dead_letter(
+    state: web::Data<AppState>,
+    id: web::Path<i64>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(ReplayDeadLetter {
+            id: id.into_inner(),
+        })
+        .await?
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+struct DiscardDeadLetter {
+    id: i64,
+}
+
+impl Message for DiscardDeadLetter {
+    type Result = Result<(), RestApiResponseError>;
+}
+
+impl Handler<DiscardDeadLetter> for DbExecutor {
+    type Result = Result<(), RestApiResponseError>;
+
+    fn handle(&mut self, msg: DiscardDeadLetter, _: &mut SyncContext<Self>) -> Self::Result {
+        self.dead_letter_store.remove_dead_letter(msg.id)?;
+        warn!("Discarded dead letter {}", msg.id);
+        Ok(())
+    }
+}
+
+pub async fn discard_dead_letter(
+    state: web::Data<AppState>,
+    id: web::Path<i64>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(DiscardDeadLetter {
+            id: id.into_inner(),
+        })
+        .await?
+        .map(|_| HttpResponse::NoContent().finish())
+}
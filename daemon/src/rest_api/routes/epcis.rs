@@ -0,0 +1,708 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Renders track and trace records as GS1 EPCIS 2.0 JSON-LD event documents.
+//!
+//! A record's history is mapped to EPCIS events as follows:
+//!
+//! * creation of the record is an `ObjectEvent` with the `ADD` action
+//! * every owner or custodian after the first is a `TransactionEvent` moving the record
+//!   between owning or possessing parties
+//! * every reported property value is an `ObjectEvent` with the `OBSERVE` action carrying the
+//!   value as sensor data
+//! * finalization of the record is an `ObjectEvent` with the `DELETE` action
+//!
+//! Records linked to a product are identified by a GS1 Digital Link URI built from the
+//! product's GTIN, and records linked to a location use the location's GLN as the business
+//! location.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use crate::rest_api::{
+    error::RestApiResponseError,
+    routes::{
+        paging::list_link, parse_property_slice, DbExecutor, PropertySlice, StructPropertyValue,
+        Value,
+    },
+    AcceptServiceIdParam, AppState, Principal, QueryAsOf, QueryPaging, QueryServiceId,
+};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use grid_sdk::paging::{Cursor, PageDirection};
+use grid_sdk::track_and_trace::store::{AssociatedAgent, Record, RecordFilter, TrackAndTraceStore};
+use serde::{Deserialize, Serialize};
+
+const EPCIS_CONTEXT: &str = "https://ref.gs1.org/standards/epcis/2.0.0/epcis-context.jsonld";
+const EPCIS_SCHEMA_VERSION: &str = "2.0";
+const GRID_CONTEXT_PREFIX: &str = "grid";
+const GRID_CONTEXT: &str = "https://grid.hyperledger.org/epcis/";
+const GS1_DIGITAL_LINK: &str = "https://id.gs1.org";
+const GRID_URN: &str = "urn:grid";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum EpcisContext {
+    Uri(String),
+    Namespaces(BTreeMap<String, String>),
+}
+
+fn epcis_context() -> Vec<EpcisContext> {
+    let mut namespaces = BTreeMap::new();
+    namespaces.insert(GRID_CONTEXT_PREFIX.to_string(), GRID_CONTEXT.to_string());
+    vec![
+        EpcisContext::Uri(EPCIS_CONTEXT.to_string()),
+        EpcisContext::Namespaces(namespaces),
+    ]
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisDocument {
+    #[serde(rename = "@context")]
+    pub context: Vec<EpcisContext>,
+    #[serde(rename = "type")]
+    pub document_type: String,
+    pub schema_version: String,
+    pub creation_date: String,
+    pub epcis_body: EpcisBody,
+}
+
+impl EpcisDocument {
+    pub fn new(events: Vec<EpcisEvent>) -> Self {
+        Self {
+            context: epcis_context(),
+            document_type: "EPCISDocument".to_string(),
+            schema_version: EPCIS_SCHEMA_VERSION.to_string(),
+            creation_date: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            epcis_body: EpcisBody { event_list: events },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisBody {
+    pub event_list: Vec<EpcisEvent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisQueryDocument {
+    #[serde(rename = "@context")]
+    pub context: Vec<EpcisContext>,
+    #[serde(rename = "type")]
+    pub document_type: String,
+    pub schema_version: String,
+    pub creation_date: String,
+    pub epcis_body: EpcisQueryBody,
+}
+
+impl EpcisQueryDocument {
+    pub fn new(events: Vec<EpcisEvent>) -> Self {
+        Self {
+            context: epcis_context(),
+            document_type: "EPCISQueryDocument".to_string(),
+            schema_version: EPCIS_SCHEMA_VERSION.to_string(),
+            creation_date: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            epcis_body: EpcisQueryBody {
+                query_results: EpcisQueryResults {
+                    query_name: "SimpleEventQuery".to_string(),
+                    results_body: EpcisBody { event_list: events },
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisQueryBody {
+    pub query_results: EpcisQueryResults,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisQueryResults {
+    pub query_name: String,
+    pub results_body: EpcisBody,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub event_time: String,
+    pub event_time_zone_offset: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub epc_list: Vec<String>,
+    pub action: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub biz_step: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disposition: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub biz_location: Option<EpcisLocation>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub biz_transaction_list: Vec<EpcisBizTransaction>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub source_list: Vec<EpcisSource>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub destination_list: Vec<EpcisDestination>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sensor_element_list: Vec<EpcisSensorElement>,
+    #[serde(rename = "grid:recordId")]
+    pub record_id: String,
+    #[serde(skip)]
+    pub timestamp: u64,
+}
+
+impl EpcisEvent {
+    /// Creates an event for the record at the given time, or `None` if the time is out of the
+    /// range of dates that can be represented
+    fn new(event_type: &str, action: &str, record: &Record, timestamp: u64) -> Option<Self> {
+        Some(Self {
+            event_type: event_type.to_string(),
+            event_time: format_event_time(timestamp)?,
+            event_time_zone_offset: "+00:00".to_string(),
+            epc_list: vec![record_epc(record)],
+            action: action.to_string(),
+            biz_step: None,
+            disposition: None,
+            biz_location: record.location_id.as_deref().map(EpcisLocation::from_gln),
+            biz_transaction_list: vec![],
+            source_list: vec![],
+            destination_list: vec![],
+            sensor_element_list: vec![],
+            record_id: record.record_id.clone(),
+            timestamp,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EpcisLocation {
+    pub id: String,
+}
+
+impl EpcisLocation {
+    fn from_gln(gln: &str) -> Self {
+        Self {
+            id: format!("{}/414/{}", GS1_DIGITAL_LINK, gln),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisBizTransaction {
+    #[serde(rename = "type")]
+    pub transaction_type: String,
+    pub biz_transaction: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EpcisSource {
+    #[serde(rename = "type")]
+    pub source_type: String,
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EpcisDestination {
+    #[serde(rename = "type")]
+    pub destination_type: String,
+    pub destination: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisSensorElement {
+    pub sensor_metadata: EpcisSensorMetadata,
+    pub sensor_report: Vec<EpcisSensorReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisSensorMetadata {
+    pub time: String,
+    #[serde(rename = "deviceID")]
+    pub device_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpcisSensorReport {
+    #[serde(rename = "type")]
+    pub report_type: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub string_value: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boolean_value: Option<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri_value: Option<String>,
+}
+
+impl EpcisSensorReport {
+    fn new(report_type: String) -> Self {
+        Self {
+            report_type,
+            value: None,
+            string_value: None,
+            boolean_value: None,
+            uri_value: None,
+        }
+    }
+}
+
+fn format_event_time(timestamp: u64) -> Option<String> {
+    Utc.timestamp_opt(i64::try_from(timestamp).ok()?, 0)
+        .single()
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// Returns the EPC of a record: a GS1 Digital Link URI when the record is linked to a
+/// product, using the record ID as the serial number, or a Grid URN otherwise.
+fn record_epc(record: &Record) -> String {
+    match &record.product_id {
+        Some(gtin) => format!("{}/01/{}/21/{}", GS1_DIGITAL_LINK, gtin, record.record_id),
+        None => format!("{}:record:{}", GRID_URN, record.record_id),
+    }
+}
+
+fn agent_urn(public_key: &str) -> String {
+    format!("{}:agent:{}", GRID_URN, public_key)
+}
+
+/// Creates a transfer event for every agent that followed another agent in the given role.
+/// `updates` must be sorted by timestamp.
+fn transfer_events(
+    record: &Record,
+    updates: &[&AssociatedAgent],
+    party_type: &str,
+) -> Vec<EpcisEvent> {
+    updates
+        .windows(2)
+        .filter_map(|pair| {
+            let (previous, next) = (pair[0], pair[1]);
+            let timestamp = next.timestamp as u64;
+            let mut event = EpcisEvent::new("TransactionEvent", "ADD", record, timestamp)?;
+            event.biz_transaction_list = vec![EpcisBizTransaction {
+                transaction_type: format!("{}:{}_transfer", GRID_CONTEXT_PREFIX, party_type),
+                biz_transaction: format!(
+                    "{}:record:{}:{}:{}",
+                    GRID_URN, record.record_id, party_type, timestamp
+                ),
+            }];
+            event.source_list = vec![EpcisSource {
+                source_type: party_type.to_string(),
+                source: agent_urn(&previous.agent_id),
+            }];
+            event.destination_list = vec![EpcisDestination {
+                destination_type: party_type.to_string(),
+                destination: agent_urn(&next.agent_id),
+            }];
+            Some(event)
+        })
+        .collect()
+}
+
+fn sensor_reports(report_type: String, value: &Value) -> Vec<EpcisSensorReport> {
+    let mut report = EpcisSensorReport::new(report_type);
+    match value {
+        Value::String(string) => report.string_value = Some(string.clone()),
+        Value::Bytes(bytes) => report.string_value = Some(bytes.clone()),
+        Value::Bool(boolean) => report.boolean_value = Some(*boolean),
+        Value::Number(number) => report.value = Some(*number as f64),
+        Value::Enum(index) => report.value = Some(f64::from(*index)),
+        // Grid stores coordinates in millionths of a degree
+        Value::LatLong(lat_long) => {
+            report.uri_value = Some(format!(
+                "geo:{},{}",
+                lat_long.latitude as f64 / 1_000_000.0,
+                lat_long.longitude as f64 / 1_000_000.0
+            ))
+        }
+        Value::Struct(struct_values) => {
+            return struct_values
+                .iter()
+                .flat_map(|StructPropertyValue { name, value, .. }| {
+                    sensor_reports(format!("{}.{}", report.report_type, name), value)
+                })
+                .collect();
+        }
+    }
+    vec![report]
+}
+
+/// Builds the EPCIS events describing the history of a record, ordered by event time.
+///
+/// Changes timestamped outside the range of dates that can be represented, such as times given
+/// in milliseconds rather than seconds, are left out.
+///
+/// # Arguments
+///
+///  * `record` - The record the events are for
+///  * `associated_agents` - The owners and custodians of the record
+///  * `properties` - The record's properties, including their updates
+pub fn record_events(
+    record: &Record,
+    associated_agents: &[AssociatedAgent],
+    properties: &[PropertySlice],
+) -> Vec<EpcisEvent> {
+    let mut owners: Vec<&AssociatedAgent> = associated_agents
+        .iter()
+        .filter(|agent| agent.role == "OWNER")
+        .collect();
+    let mut custodians: Vec<&AssociatedAgent> = associated_agents
+        .iter()
+        .filter(|agent| agent.role == "CUSTODIAN")
+        .collect();
+    owners.sort_by_key(|agent| agent.timestamp);
+    custodians.sort_by_key(|agent| agent.timestamp);
+
+    let mut events = Vec::new();
+
+    if let Some(mut event) = owners
+        .first()
+        .and_then(|creator| EpcisEvent::new("ObjectEvent", "ADD", record, creator.timestamp as u64))
+    {
+        event.biz_step = Some("commissioning".to_string());
+        event.disposition = Some("active".to_string());
+        events.push(event);
+    }
+
+    events.append(&mut transfer_events(record, &owners, "owning_party"));
+    events.append(&mut transfer_events(
+        record,
+        &custodians,
+        "possessing_party",
+    ));
+
+    for property in properties {
+        for update in &property.updates {
+            let mut event =
+                match EpcisEvent::new("ObjectEvent", "OBSERVE", record, update.timestamp) {
+                    Some(event) => event,
+                    None => continue,
+                };
+            event.sensor_element_list = vec![EpcisSensorElement {
+                sensor_metadata: EpcisSensorMetadata {
+                    time: event.event_time.clone(),
+                    device_id: agent_urn(&update.reporter.public_key),
+                },
+                sensor_report: sensor_reports(
+                    format!("{}:{}", GRID_CONTEXT_PREFIX, property.name),
+                    &update.value,
+                ),
+            }];
+            events.push(event);
+        }
+    }
+
+    // The sort is stable, so creation stays ahead of events sharing its timestamp
+    events.sort_by_key(|event| event.timestamp);
+
+    if record.final_ {
+        // Finalization is not timestamped on chain, so it is placed at the time of the last
+        // recorded change to the record
+        let timestamp = events.last().map(|event| event.timestamp).unwrap_or(0);
+        if let Some(mut event) = EpcisEvent::new("ObjectEvent", "DELETE", record, timestamp) {
+            event.biz_step = Some("decommissioning".to_string());
+            event.disposition = Some("inactive".to_string());
+            events.push(event);
+        }
+    }
+
+    events
+}
+
+fn list_events_for_records(
+    store: &Arc<dyn TrackAndTraceStore>,
+    records: &[Record],
+    commit_height: Option<i64>,
+    service_id: Option<&str>,
+) -> Result<Vec<EpcisEvent>, RestApiResponseError> {
+    let record_ids: Vec<String> = records
+        .iter()
+        .map(|record| record.record_id.to_string())
+        .collect();
+
+    let associated_agents = store.list_associated_agents(&record_ids, commit_height, service_id)?;
+
+    let properties = store
+        .list_properties_with_data_type(&record_ids, commit_height, service_id)?
+        .iter()
+        .map(|(property, data_type)| {
+            parse_property_slice(store, property, data_type, commit_height, service_id)
+        })
+        .collect::<Result<Vec<PropertySlice>, _>>()?;
+
+    Ok(records
+        .iter()
+        .flat_map(|record| {
+            let agents: Vec<AssociatedAgent> = associated_agents
+                .iter()
+                .filter(|agent| agent.record_id.eq(&record.record_id))
+                .cloned()
+                .collect();
+            let record_properties: Vec<PropertySlice> = properties
+                .iter()
+                .filter(|property| property.record_id.eq(&record.record_id))
+                .cloned()
+                .collect();
+
+            record_events(record, &agents, &record_properties)
+        })
+        .collect())
+}
+
+struct FetchRecordEpcis {
+    record_id: String,
+    as_of: Option<String>,
+    service_id: Option<String>,
+    principal: Principal,
+}
+
+impl Message for FetchRecordEpcis {
+    type Result = Result<EpcisDocument, RestApiResponseError>;
+}
+
+impl Handler<FetchRecordEpcis> for DbExecutor {
+    type Result = Result<EpcisDocument, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchRecordEpcis, _: &mut SyncContext<Self>) -> Self::Result {
+        let commit_height = self.commit_height(msg.as_of.as_deref())?;
+
+        let record = self
+            .tnt_store
+            .fetch_record(&msg.record_id, commit_height, msg.service_id.as_deref())?
+            .ok_or_else(|| {
+                RestApiResponseError::NotFoundError(format!(
+                    "Could not find record with id: {}",
+                    msg.record_id
+                ))
+            })?;
+        self.check_record_access(
+            &msg.principal,
+            &msg.record_id,
+            commit_height,
+            msg.service_id.as_deref(),
+        )?;
+
+        let events = list_events_for_records(
+            &self.tnt_store,
+            &[record],
+            commit_height,
+            msg.service_id.as_deref(),
+        )?;
+
+        Ok(EpcisDocument::new(events))
+    }
+}
+
+pub async fn fetch_record_epcis(
+    state: web::Data<AppState>,
+    record_id: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchRecordEpcis {
+            record_id: record_id.into_inner(),
+            as_of: query_as_of.into_inner().as_of,
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|document| {
+            HttpResponse::Ok()
+                .content_type("application/ld+json")
+                .json(document)
+        })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryEpcisEvents {
+    /// Only include events at or after this time, in seconds since the epoch
+    pub from: Option<u64>,
+    /// Only include events before this time, in seconds since the epoch
+    pub to: Option<u64>,
+    /// Only include events for records of the product with this GTIN
+    pub gtin: Option<String>,
+}
+
+impl QueryEpcisEvents {
+    /// Returns the query params of this query, to carry over to the link of the next page
+    fn params(&self) -> Vec<(String, String)> {
+        let mut params = vec![];
+        if let Some(from) = self.from {
+            params.push(("from".to_string(), from.to_string()));
+        }
+        if let Some(to) = self.to {
+            params.push(("to".to_string(), to.to_string()));
+        }
+        if let Some(gtin) = &self.gtin {
+            params.push(("gtin".to_string(), gtin.to_string()));
+        }
+        params
+    }
+}
+
+/// A page of the events matching an event query, with the link to the next page, if any
+pub struct EpcisEventPage {
+    pub document: EpcisQueryDocument,
+    pub next: Option<String>,
+}
+
+struct ListEpcisEvents {
+    query: QueryEpcisEvents,
+    as_of: Option<String>,
+    service_id: Option<String>,
+    limit: u16,
+    cursor: Option<String>,
+    principal: Principal,
+}
+
+impl Message for ListEpcisEvents {
+    type Result = Result<EpcisEventPage, RestApiResponseError>;
+}
+
+impl Handler<ListEpcisEvents> for DbExecutor {
+    type Result = Result<EpcisEventPage, RestApiResponseError>;
+
+    /// Reads the events of a page of the records updated in the requested span of time. The
+    /// page is always read by key, so that each page of events is read at the same height.
+    fn handle(&mut self, msg: ListEpcisEvents, _: &mut SyncContext<Self>) -> Self::Result {
+        let service_id = msg.service_id.as_deref();
+        let limit = i64::from(msg.limit);
+        let from = msg
+            .query
+            .from
+            .map(|from| i64::try_from(from).unwrap_or(i64::MAX));
+        let to = msg.query.to.map(|to| i64::try_from(to).unwrap_or(i64::MAX));
+
+        let page = self.page_query(
+            msg.as_of.as_deref(),
+            Some(msg.cursor.as_deref().unwrap_or_default()),
+            0,
+            limit,
+        )?;
+        let commit_height = page.commit_height;
+
+        let filter = RecordFilter {
+            product_id: msg.query.gtin.as_deref(),
+            org_id: msg.principal.org_id(),
+            updated_from: from,
+            updated_to: to,
+        };
+        let record_list = self.tnt_store.list_records_matching(
+            &filter,
+            commit_height,
+            service_id,
+            &page.request,
+        )?;
+
+        let mut events = list_events_for_records(
+            &self.tnt_store,
+            &record_list.data,
+            commit_height,
+            service_id,
+        )?;
+        events.retain(|event| {
+            msg.query
+                .from
+                .map(|from| event.timestamp >= from)
+                .unwrap_or(true)
+                && msg.query.to.map(|to| event.timestamp < to).unwrap_or(true)
+        });
+        events.sort_by_key(|event| event.timestamp);
+
+        let next = match (record_list.paging.next_key, commit_height) {
+            (Some(key), Some(commit_height)) => {
+                let mut params = vec![
+                    (
+                        "cursor".to_string(),
+                        Cursor::new(commit_height, key, PageDirection::Next).encode(),
+                    ),
+                    ("limit".to_string(), limit.to_string()),
+                ];
+                params.extend(msg.query.params());
+                if let Some(service_id) = service_id {
+                    params.push(("service_id".to_string(), service_id.to_string()));
+                }
+                Some(list_link("/epcis/events", &params))
+            }
+            _ => None,
+        };
+
+        Ok(EpcisEventPage {
+            document: EpcisQueryDocument::new(events),
+            next,
+        })
+    }
+}
+
+pub async fn list_epcis_events(
+    state: web::Data<AppState>,
+    query_service_id: web::Query<QueryServiceId>,
+    query: web::Query<QueryEpcisEvents>,
+    query_paging: web::Query<QueryPaging>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let paging = query_paging.into_inner();
+    state
+        .database_connection
+        .send(ListEpcisEvents {
+            query: query.into_inner(),
+            as_of: query_as_of.into_inner().as_of,
+            service_id: query_service_id.into_inner().service_id,
+            limit: paging.limit(),
+            cursor: paging.cursor,
+            principal,
+        })
+        .await?
+        .map(|page| {
+            let mut response = HttpResponse::Ok();
+            if let Some(next) = page.next {
+                response.header("Link", format!("<{}>; rel=\"next\"", next));
+            }
+            response
+                .content_type("application/ld+json")
+                .json(page.document)
+        })
+}
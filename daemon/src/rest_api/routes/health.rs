@@ -0,0 +1,138 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "event")]
+use crate::rest_api::routes::IndexingStatusSlice;
+use crate::rest_api::{error::RestApiResponseError, routes::DbExecutor, AppState};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LivenessSlice {
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadinessSlice {
+    pub ready: bool,
+    pub database: bool,
+    /// Whether the ledger could be asked for its chain head
+    #[cfg(feature = "event")]
+    pub ledger: bool,
+    #[cfg(feature = "event")]
+    pub indexing: IndexingStatusSlice,
+    /// Number of commits the commit store trails the ledger by, if it is known
+    #[cfg(feature = "event")]
+    pub commit_lag: Option<u64>,
+    #[cfg(feature = "event")]
+    pub max_commit_lag: u64,
+}
+
+struct CheckReadiness {
+    #[cfg(feature = "event")]
+    ledger: bool,
+    #[cfg(feature = "event")]
+    chain_head_height: Option<u64>,
+}
+
+impl Message for CheckReadiness {
+    type Result = Result<ReadinessSlice, RestApiResponseError>;
+}
+
+impl Handler<CheckReadiness> for DbExecutor {
+    type Result = Result<ReadinessSlice, RestApiResponseError>;
+
+    #[cfg_attr(not(feature = "event"), allow(unused_variables))]
+    fn handle(&mut self, msg: CheckReadiness, _: &mut SyncContext<Self>) -> Self::Result {
+        let next_commit_num = match self.commit_store.get_next_commit_num() {
+            Ok(next_commit_num) => Some(next_commit_num),
+            Err(err) => {
+                warn!("Readiness check failed to query the database: {}", err);
+                None
+            }
+        };
+        let database = next_commit_num.is_some();
+
+        #[cfg(feature = "event")]
+        {
+            let indexing = IndexingStatusSlice::from(&self.indexing_status);
+            // A ledger that doesn't number its commits is followed by the backlog of commits its
+            // event connections have received but not yet stored
+            let commit_lag = match (msg.chain_head_height, next_commit_num) {
+                (Some(chain_head_height), Some(next_commit_num)) => {
+                    Some(chain_head_height.saturating_sub((next_commit_num - 1).max(0) as u64))
+                }
+                (None, _) if msg.ledger => self.indexing_status.backlog(),
+                _ => None,
+            };
+            let caught_up = commit_lag
+                .map(|lag| lag <= self.max_commit_lag)
+                .unwrap_or(true);
+
+            Ok(ReadinessSlice {
+                ready: database && msg.ledger && indexing.live && caught_up,
+                database,
+                ledger: msg.ledger,
+                indexing,
+                commit_lag,
+                max_commit_lag: self.max_commit_lag,
+            })
+        }
+
+        #[cfg(not(feature = "event"))]
+        Ok(ReadinessSlice {
+            ready: database,
+            database,
+        })
+    }
+}
+
+/// Reports that the REST API is serving requests; used as a liveness probe
+pub async fn fetch_liveness() -> HttpResponse {
+    HttpResponse::Ok().json(LivenessSlice {
+        status: "ok".to_string(),
+    })
+}
+
+/// Reports whether gridd can serve current data; used as a readiness probe
+pub async fn check_readiness(
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, RestApiResponseError> {
+    #[cfg(feature = "event")]
+    let msg = match state.batch_submitter.chain_head_height().await {
+        Ok(chain_head_height) => CheckReadiness {
+            ledger: true,
+            chain_head_height,
+        },
+        Err(err) => {
+            warn!("Readiness check failed to query the ledger: {}", err);
+            CheckReadiness {
+                ledger: false,
+                chain_head_height: None,
+            }
+        }
+    };
+    #[cfg(not(feature = "event"))]
+    let msg = CheckReadiness {};
+
+    state.database_connection.send(msg).await?.map(|readiness| {
+        if readiness.ready {
+            HttpResponse::Ok().json(readiness)
+        } else {
+            HttpResponse::ServiceUnavailable().json(readiness)
+        }
+    })
+}
@@ -0,0 +1,236 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builds the change history of an entity from the versions kept in the database.
+//!
+//! Every version is returned with the commit that introduced it and the fields that changed
+//! since the previous version. Fields are compared on the JSON form of the entity, so nested
+//! fields are named by their path, and list entries with a `name` or `key` are matched by it.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::rest_api::{error::RestApiResponseError, routes::DbExecutor};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldChangeSlice {
+    pub field: String,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionSlice<T> {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_id: Option<String>,
+    pub commit_num: i64,
+    pub value: T,
+    pub changes: Vec<FieldChangeSlice>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistorySlice<T> {
+    pub data: Vec<VersionSlice<T>>,
+}
+
+impl DbExecutor {
+    /// Builds the history of an entity from its versions, oldest first, each paired with the
+    /// number of the commit that introduced it
+    pub(super) fn history<T: Serialize>(
+        &self,
+        versions: Vec<(i64, T)>,
+    ) -> Result<HistorySlice<T>, RestApiResponseError> {
+        let mut data = Vec::with_capacity(versions.len());
+        let mut previous = None;
+
+        for (commit_num, value) in versions {
+            let current = serde_json::to_value(&value).map_err(|err| {
+                RestApiResponseError::RequestHandlerError(format!(
+                    "Unable to serialize version: {}",
+                    err
+                ))
+            })?;
+
+            let mut changes = vec![];
+            diff_values("", previous.as_ref(), Some(&current), &mut changes);
+
+            let commit_id = self
+                .commit_store
+                .get_commit_by_commit_num(commit_num)?
+                .map(|commit| commit.commit_id);
+
+            data.push(VersionSlice {
+                commit_id,
+                commit_num,
+                value,
+                changes,
+            });
+            previous = Some(current);
+        }
+
+        Ok(HistorySlice { data })
+    }
+}
+
+fn join(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+/// Returns the entries of a list keyed by their `name` or `key` field, or `None` if the entries
+/// cannot be told apart that way
+fn keyed_entries(entries: &[Value]) -> Option<Map<String, Value>> {
+    let mut keyed = Map::new();
+    for entry in entries {
+        let key = entry
+            .get("name")
+            .or_else(|| entry.get("key"))
+            .and_then(Value::as_str)?;
+        if keyed.insert(key.to_string(), entry.clone()).is_some() {
+            return None;
+        }
+    }
+    Some(keyed)
+}
+
+fn diff_objects(
+    path: &str,
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    changes: &mut Vec<FieldChangeSlice>,
+) {
+    for (field, new_value) in new {
+        diff_values(&join(path, field), old.get(field), Some(new_value), changes);
+    }
+    for (field, old_value) in old {
+        if !new.contains_key(field) {
+            diff_values(&join(path, field), Some(old_value), None, changes);
+        }
+    }
+}
+
+fn diff_values(
+    path: &str,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    changes: &mut Vec<FieldChangeSlice>,
+) {
+    match (old, new) {
+        (Some(old), Some(new)) if old == new => (),
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            diff_objects(path, old, new, changes)
+        }
+        (Some(Value::Array(old)), Some(Value::Array(new))) => {
+            match (keyed_entries(old), keyed_entries(new)) {
+                (Some(old), Some(new)) => diff_objects(path, &old, &new, changes),
+                _ => changes.push(FieldChangeSlice {
+                    field: path.to_string(),
+                    old_value: Some(Value::Array(old.clone())),
+                    new_value: Some(Value::Array(new.clone())),
+                }),
+            }
+        }
+        (None, Some(Value::Object(new))) if path.is_empty() => {
+            diff_objects(path, &Map::new(), new, changes)
+        }
+        _ => changes.push(FieldChangeSlice {
+            field: path.to_string(),
+            old_value: old.cloned(),
+            new_value: new.cloned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn diff(old: Option<Value>, new: Value) -> Vec<FieldChangeSlice> {
+        let mut changes = vec![];
+        diff_values("", old.as_ref(), Some(&new), &mut changes);
+        changes
+    }
+
+    fn change(field: &str, old_value: Option<Value>, new_value: Option<Value>) -> FieldChangeSlice {
+        FieldChangeSlice {
+            field: field.to_string(),
+            old_value,
+            new_value,
+        }
+    }
+
+    #[test]
+    // Test that every field of the first version is reported as added
+    fn test_diff_first_version() {
+        assert_eq!(
+            diff(None, json!({"name": "org", "address": "here"})),
+            vec![
+                change("address", None, Some(json!("here"))),
+                change("name", None, Some(json!("org"))),
+            ]
+        );
+    }
+
+    #[test]
+    // Test that only changed fields are reported, with nested fields named by their path
+    fn test_diff_changed_fields() {
+        assert_eq!(
+            diff(
+                Some(json!({"owner": "a", "lat_long": {"latitude": 1, "longitude": 2}})),
+                json!({"owner": "b", "lat_long": {"latitude": 1, "longitude": 3}}),
+            ),
+            vec![
+                change("lat_long.longitude", Some(json!(2)), Some(json!(3))),
+                change("owner", Some(json!("a")), Some(json!("b"))),
+            ]
+        );
+    }
+
+    #[test]
+    // Test that list entries are matched by name, and whole lists are compared otherwise
+    fn test_diff_lists() {
+        assert_eq!(
+            diff(
+                Some(json!({
+                    "properties": [{"name": "weight", "value": 1}, {"name": "color", "value": 2}],
+                    "reporters": ["a"],
+                })),
+                json!({
+                    "properties": [{"name": "weight", "value": 5}, {"name": "size", "value": 3}],
+                    "reporters": ["a", "b"],
+                }),
+            ),
+            vec![
+                change(
+                    "properties.size",
+                    None,
+                    Some(json!({"name": "size", "value": 3}))
+                ),
+                change("properties.weight.value", Some(json!(1)), Some(json!(5))),
+                change(
+                    "properties.color",
+                    Some(json!({"name": "color", "value": 2})),
+                    None
+                ),
+                change("reporters", Some(json!(["a"])), Some(json!(["a", "b"]))),
+            ]
+        );
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::event::IndexingStatus;
+use crate::rest_api::{error::RestApiResponseError, routes::DbExecutor, AppState, OperatorOnly};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventConnectionSlice {
+    pub name: String,
+    pub live: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexingStatusSlice {
+    pub live: bool,
+    pub connections: Vec<EventConnectionSlice>,
+}
+
+impl From<&IndexingStatus> for IndexingStatusSlice {
+    fn from(indexing_status: &IndexingStatus) -> Self {
+        Self {
+            live: indexing_status.is_live(),
+            connections: indexing_status
+                .connections()
+                .into_iter()
+                .map(|(name, live)| EventConnectionSlice { name, live })
+                .collect(),
+        }
+    }
+}
+
+struct FetchIndexingStatus;
+
+impl Message for FetchIndexingStatus {
+    type Result = Result<IndexingStatusSlice, RestApiResponseError>;
+}
+
+impl Handler<FetchIndexingStatus> for DbExecutor {
+    type Result = Result<IndexingStatusSlice, RestApiResponseError>;
+
+    fn handle(&mut self, _: FetchIndexingStatus, _: &mut SyncContext<Self>) -> Self::Result {
+        Ok(IndexingStatusSlice::from(&self.indexing_status))
+    }
+}
+
+pub async fn fetch_indexing_status(
+    state: web::Data<AppState>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchIndexingStatus)
+        .await?
+        .map(|indexing_status| HttpResponse::Ok().json(indexing_status))
+}
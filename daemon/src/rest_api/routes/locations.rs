@@ -16,12 +16,18 @@ use std::convert::TryFrom;
 
 use crate::rest_api::{
     error::RestApiResponseError,
-    routes::{paging::Paging, DbExecutor},
-    AcceptServiceIdParam, AppState, QueryPaging, QueryServiceId,
+    routes::{
+        history::HistorySlice,
+        paging::{list_link, Paging},
+        DbExecutor,
+    },
+    AcceptServiceIdParam, AppState, Principal, QueryAsOf, QueryListFilter, QueryPaging,
+    QueryServiceId,
 };
 
 use actix::{Handler, Message, SyncContext};
 use actix_web::{web, HttpResponse};
+use grid_sdk::filter::{Filter, ListFilter, Sort, StringMatch};
 use grid_sdk::locations::store::{LatLongValue, Location, LocationAttribute};
 use serde::{Deserialize, Serialize};
 
@@ -112,9 +118,14 @@ impl From<LatLongValue> for LatLongSlice {
 }
 
 struct ListLocations {
+    as_of: Option<String>,
+    filter: ListFilter,
+    link: String,
     service_id: Option<String>,
     offset: u64,
     limit: u16,
+    cursor: Option<String>,
+    principal: Principal,
 }
 
 impl Message for ListLocations {
@@ -124,14 +135,33 @@ impl Message for ListLocations {
 impl Handler<ListLocations> for DbExecutor {
     type Result = Result<LocationListSlice, RestApiResponseError>;
 
-    fn handle(&mut self, msg: ListLocations, _: &mut SyncContext<Self>) -> Self::Result {
+    fn handle(&mut self, mut msg: ListLocations, _: &mut SyncContext<Self>) -> Self::Result {
+        // Pages read by cursor are keyed, and so ordered, by ID
+        if msg.cursor.is_some() && msg.filter.sort != Sort::default() {
+            return Err(RestApiResponseError::BadRequest(
+                "A list read by cursor cannot be sorted".into(),
+            ));
+        }
+
+        if let Some(org_id) = msg.principal.org_id() {
+            msg.filter
+                .filters
+                .push(Filter::Owner(StringMatch::Equals(org_id.to_string())));
+        }
+
         let offset = i64::try_from(msg.offset).unwrap_or(i64::MAX);
 
         let limit = i64::try_from(msg.limit).unwrap_or(10);
 
-        let location_list =
-            self.location_store
-                .list_locations(msg.service_id.as_deref(), offset, limit)?;
+        let page = self.page_query(msg.as_of.as_deref(), msg.cursor.as_deref(), offset, limit)?;
+        let commit_height = page.commit_height;
+
+        let location_list = self.location_store.list_locations(
+            commit_height,
+            &msg.filter,
+            msg.service_id.as_deref(),
+            &page.request,
+        )?;
 
         let data = location_list
             .data
@@ -139,7 +169,7 @@ impl Handler<ListLocations> for DbExecutor {
             .map(LocationSlice::from)
             .collect();
 
-        let paging = Paging::new("/location", location_list.paging, msg.service_id.as_deref());
+        let paging = page.paging(&msg.link, location_list.paging, msg.service_id.as_deref());
 
         Ok(LocationListSlice { data, paging })
     }
@@ -149,15 +179,23 @@ pub async fn list_locations(
     state: web::Data<AppState>,
     query_service_id: web::Query<QueryServiceId>,
     query_paging: web::Query<QueryPaging>,
+    query_as_of: web::Query<QueryAsOf>,
+    query_list_filter: QueryListFilter,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     let paging = query_paging.into_inner();
     state
         .database_connection
         .send(ListLocations {
+            as_of: query_as_of.into_inner().as_of,
+            link: list_link("/location", &query_list_filter.params),
+            filter: query_list_filter.filter,
             service_id: query_service_id.into_inner().service_id,
             offset: paging.offset(),
             limit: paging.limit(),
+            cursor: paging.cursor,
+            principal,
         })
         .await?
         .map(|locations| HttpResponse::Ok().json(locations))
@@ -165,7 +203,9 @@ pub async fn list_locations(
 
 struct FetchLocation {
     location_id: String,
+    as_of: Option<String>,
     service_id: Option<String>,
+    principal: Principal,
 }
 
 impl Message for FetchLocation {
@@ -176,12 +216,17 @@ impl Handler<FetchLocation> for DbExecutor {
     type Result = Result<LocationSlice, RestApiResponseError>;
 
     fn handle(&mut self, msg: FetchLocation, _: &mut SyncContext<Self>) -> Self::Result {
-        match self
-            .location_store
-            .fetch_location(&msg.location_id, msg.service_id.as_deref())?
-        {
-            Some(location) => Ok(LocationSlice::from(location)),
-            None => Err(RestApiResponseError::NotFoundError(format!(
+        let commit_height = self.commit_height(msg.as_of.as_deref())?;
+
+        match self.location_store.fetch_location(
+            &msg.location_id,
+            commit_height,
+            msg.service_id.as_deref(),
+        )? {
+            Some(location) if msg.principal.can_read_org(&location.owner) => {
+                Ok(LocationSlice::from(location))
+            }
+            _ => Err(RestApiResponseError::NotFoundError(format!(
                 "Could not find location with id: {}",
                 msg.location_id
             ))),
@@ -193,14 +238,73 @@ pub async fn fetch_location(
     state: web::Data<AppState>,
     location_id: web::Path<String>,
     query: web::Query<QueryServiceId>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     state
         .database_connection
         .send(FetchLocation {
             location_id: location_id.into_inner(),
+            as_of: query_as_of.into_inner().as_of,
             service_id: query.into_inner().service_id,
+            principal,
         })
         .await?
         .map(|location| HttpResponse::Ok().json(location))
 }
+
+struct FetchLocationHistory {
+    location_id: String,
+    service_id: Option<String>,
+    principal: Principal,
+}
+
+impl Message for FetchLocationHistory {
+    type Result = Result<HistorySlice<LocationSlice>, RestApiResponseError>;
+}
+
+impl Handler<FetchLocationHistory> for DbExecutor {
+    type Result = Result<HistorySlice<LocationSlice>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchLocationHistory, _: &mut SyncContext<Self>) -> Self::Result {
+        // Only the versions owned by an organization the principal may read are shown
+        let versions = self
+            .location_store
+            .list_location_history(&msg.location_id, msg.service_id.as_deref())?
+            .into_iter()
+            .filter(|location| msg.principal.can_read_org(&location.owner))
+            .collect::<Vec<_>>();
+        if versions.is_empty() {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find location with id: {}",
+                msg.location_id
+            )));
+        }
+
+        self.history(
+            versions
+                .into_iter()
+                .map(|location| (location.start_commit_num, LocationSlice::from(location)))
+                .collect(),
+        )
+    }
+}
+
+pub async fn fetch_location_history(
+    state: web::Data<AppState>,
+    location_id: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchLocationHistory {
+            location_id: location_id.into_inner(),
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|history| HttpResponse::Ok().json(history))
+}
@@ -0,0 +1,49 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::metrics;
+use crate::rest_api::{error::RestApiResponseError, routes::DbExecutor, AppState, OperatorOnly};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+
+const TEXT_FORMAT: &str = "text/plain; version=0.0.4";
+
+struct FetchMetrics;
+
+impl Message for FetchMetrics {
+    type Result = Result<String, RestApiResponseError>;
+}
+
+impl Handler<FetchMetrics> for DbExecutor {
+    type Result = Result<String, RestApiResponseError>;
+
+    fn handle(&mut self, _: FetchMetrics, _: &mut SyncContext<Self>) -> Self::Result {
+        self.pool_metrics.record();
+        metrics::record_persisted_commit(self.commit_store.get_next_commit_num()? - 1);
+
+        metrics::render().map_err(RestApiResponseError::RequestHandlerError)
+    }
+}
+
+pub async fn fetch_metrics(
+    state: web::Data<AppState>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchMetrics)
+        .await?
+        .map(|metrics| HttpResponse::Ok().content_type(TEXT_FORMAT).body(metrics))
+}
@@ -13,56 +13,143 @@
 // limitations under the License.
 
 use std::sync::Arc;
-
+#[cfg(feature = "event")]
+use std::sync::Mutex;
+
+#[cfg(feature = "change-stream")]
+use grid_sdk::change_log::{ChangeLogStore, DieselChangeLogStore};
+#[cfg(feature = "event")]
+use grid_sdk::dead_letters::{DeadLetterStore, DieselDeadLetterStore};
+#[cfg(feature = "search")]
+use grid_sdk::search::{DieselSearchStore, SearchStore};
+#[cfg(feature = "webhooks")]
+use grid_sdk::webhooks::{DieselWebhookStore, WebhookStore};
 use grid_sdk::{
+    commits::{CommitStore, DieselCommitStore},
     locations::{DieselLocationStore, LocationStore},
     pike::{DieselPikeStore, PikeStore},
     products::{DieselProductStore, ProductStore},
+    purchase_order::{DieselPurchaseOrderStore, PurchaseOrderStore},
     schemas::{DieselSchemaStore, SchemaStore},
     track_and_trace::{DieselTrackAndTraceStore, TrackAndTraceStore},
 };
 
+#[cfg(feature = "track-and-trace")]
+mod access;
 #[cfg(feature = "pike")]
 mod agents;
 mod batches;
+#[cfg(feature = "change-stream")]
+mod changes;
+#[cfg(feature = "event")]
+mod dead_letters;
+#[cfg(feature = "track-and-trace")]
+mod epcis;
+mod health;
+#[cfg(feature = "pike")]
+mod history;
+#[cfg(feature = "event")]
+mod indexing;
 #[cfg(feature = "location")]
 mod locations;
+#[cfg(feature = "metrics")]
+mod metrics;
 #[cfg(feature = "pike")]
 mod organizations;
-#[cfg(feature = "pike")]
+#[cfg(any(feature = "pike", feature = "event"))]
 mod paging;
 #[cfg(feature = "product")]
 mod products;
+#[cfg(feature = "purchase-order")]
+mod purchase_orders;
 #[cfg(feature = "track-and-trace")]
 mod records;
 #[cfg(feature = "schema")]
 mod schemas;
+#[cfg(feature = "search")]
+mod search;
+#[cfg(feature = "webhooks")]
+mod webhooks;
+#[cfg(feature = "workflow")]
+mod workflows;
 
 #[cfg(feature = "pike")]
 pub use agents::*;
 pub use batches::*;
+#[cfg(feature = "change-stream")]
+pub use changes::*;
+#[cfg(feature = "event")]
+pub use dead_letters::*;
+#[cfg(feature = "track-and-trace")]
+pub use epcis::*;
+pub use health::*;
+#[cfg(feature = "event")]
+pub use indexing::*;
 #[cfg(feature = "location")]
 pub use locations::*;
+#[cfg(feature = "metrics")]
+pub use metrics::*;
 #[cfg(feature = "pike")]
 pub use organizations::*;
 #[cfg(feature = "product")]
 pub use products::*;
+#[cfg(feature = "purchase-order")]
+pub use purchase_orders::*;
 #[cfg(feature = "track-and-trace")]
 pub use records::*;
 #[cfg(feature = "schema")]
 pub use schemas::*;
-
+#[cfg(feature = "search")]
+pub use search::*;
+#[cfg(feature = "webhooks")]
+pub use webhooks::*;
+#[cfg(feature = "workflow")]
+pub use workflows::*;
+
+#[cfg(feature = "change-stream")]
+use crate::changes::ChangeBroadcaster;
 use crate::database::ConnectionPool;
+#[cfg(feature = "event")]
+use crate::event::{
+    db_handler::DatabaseEventHandler, EventHandler, IndexingStatus, DEFAULT_MAX_COMMIT_LAG,
+};
+#[cfg(feature = "metrics")]
+use crate::metrics::PoolMetrics;
+use crate::rest_api::error::RestApiResponseError;
+#[cfg(feature = "pike")]
+use grid_sdk::paging::{Cursor, PageDirection, PageRequest};
+#[cfg(feature = "pike")]
+use paging::PageQuery;
 
 use actix::{Actor, SyncContext};
 
 #[derive(Clone)]
 pub struct DbExecutor {
+    #[cfg(feature = "change-stream")]
+    change_broadcaster: ChangeBroadcaster,
+    #[cfg(feature = "change-stream")]
+    change_log_store: Arc<dyn ChangeLogStore>,
+    commit_store: Arc<dyn CommitStore>,
+    #[cfg(feature = "event")]
+    dead_letter_store: Arc<dyn DeadLetterStore>,
+    #[cfg(feature = "event")]
+    event_handlers: Arc<Mutex<Vec<Box<dyn EventHandler>>>>,
+    #[cfg(feature = "event")]
+    indexing_status: IndexingStatus,
     location_store: Arc<dyn LocationStore>,
+    #[cfg(feature = "event")]
+    max_commit_lag: u64,
+    #[cfg(feature = "metrics")]
+    pool_metrics: PoolMetrics,
     pike_store: Arc<dyn PikeStore>,
     product_store: Arc<dyn ProductStore>,
+    purchase_order_store: Arc<dyn PurchaseOrderStore>,
     schema_store: Arc<dyn SchemaStore>,
+    #[cfg(feature = "search")]
+    search_store: Arc<dyn SearchStore>,
     tnt_store: Arc<dyn TrackAndTraceStore>,
+    #[cfg(feature = "webhooks")]
+    webhook_store: Arc<dyn WebhookStore>,
 }
 
 impl Actor for DbExecutor {
@@ -71,52 +158,234 @@ impl Actor for DbExecutor {
 
 impl DbExecutor {
     pub fn from_pg_pool(connection_pool: ConnectionPool<diesel::pg::PgConnection>) -> DbExecutor {
+        #[cfg(feature = "change-stream")]
+        let change_log_store = Arc::new(DieselChangeLogStore::new(connection_pool.pool.clone()));
+        let commit_store = Arc::new(DieselCommitStore::new(connection_pool.pool.clone()));
+        #[cfg(feature = "event")]
+        let dead_letter_store = Arc::new(DieselDeadLetterStore::new(connection_pool.pool.clone()));
+        #[cfg(feature = "event")]
+        let event_handlers: Vec<Box<dyn EventHandler>> =
+            event_handlers![DatabaseEventHandler::from_pg_pool(connection_pool.clone())];
         let location_store = Arc::new(DieselLocationStore::new(connection_pool.pool.clone()));
+        #[cfg(feature = "metrics")]
+        let pool_metrics = PoolMetrics::new(connection_pool.pool.clone());
         let pike_store = Arc::new(DieselPikeStore::new(connection_pool.pool.clone()));
         let product_store = Arc::new(DieselProductStore::new(connection_pool.pool.clone()));
+        let purchase_order_store =
+            Arc::new(DieselPurchaseOrderStore::new(connection_pool.pool.clone()));
         let schema_store = Arc::new(DieselSchemaStore::new(connection_pool.pool.clone()));
-        let tnt_store = Arc::new(DieselTrackAndTraceStore::new(connection_pool.pool));
+        #[cfg(feature = "search")]
+        let search_store = Arc::new(DieselSearchStore::new(connection_pool.pool.clone()));
+        let tnt_store = Arc::new(DieselTrackAndTraceStore::new(connection_pool.pool.clone()));
+        #[cfg(feature = "webhooks")]
+        let webhook_store = Arc::new(DieselWebhookStore::new(connection_pool.pool));
 
         Self {
+            #[cfg(feature = "change-stream")]
+            change_broadcaster: ChangeBroadcaster::new(),
+            #[cfg(feature = "change-stream")]
+            change_log_store,
+            commit_store,
+            #[cfg(feature = "event")]
+            dead_letter_store,
+            #[cfg(feature = "event")]
+            event_handlers: Arc::new(Mutex::new(event_handlers)),
+            #[cfg(feature = "event")]
+            indexing_status: IndexingStatus::new(),
             location_store,
+            #[cfg(feature = "event")]
+            max_commit_lag: DEFAULT_MAX_COMMIT_LAG,
+            #[cfg(feature = "metrics")]
+            pool_metrics,
             pike_store,
             product_store,
+            purchase_order_store,
             schema_store,
+            #[cfg(feature = "search")]
+            search_store,
             tnt_store,
+            #[cfg(feature = "webhooks")]
+            webhook_store,
         }
     }
 
     pub fn from_sqlite_pool(
         connection_pool: ConnectionPool<diesel::sqlite::SqliteConnection>,
     ) -> DbExecutor {
+        #[cfg(feature = "change-stream")]
+        let change_log_store = Arc::new(DieselChangeLogStore::new(connection_pool.pool.clone()));
+        let commit_store = Arc::new(DieselCommitStore::new(connection_pool.pool.clone()));
+        #[cfg(feature = "event")]
+        let dead_letter_store = Arc::new(DieselDeadLetterStore::new(connection_pool.pool.clone()));
+        #[cfg(feature = "event")]
+        let event_handlers: Vec<Box<dyn EventHandler>> =
+            event_handlers![DatabaseEventHandler::from_sqlite_pool(
+                connection_pool.clone()
+            )];
         let location_store = Arc::new(DieselLocationStore::new(connection_pool.pool.clone()));
+        #[cfg(feature = "metrics")]
+        let pool_metrics = PoolMetrics::new(connection_pool.pool.clone());
         let pike_store = Arc::new(DieselPikeStore::new(connection_pool.pool.clone()));
         let product_store = Arc::new(DieselProductStore::new(connection_pool.pool.clone()));
+        let purchase_order_store =
+            Arc::new(DieselPurchaseOrderStore::new(connection_pool.pool.clone()));
         let schema_store = Arc::new(DieselSchemaStore::new(connection_pool.pool.clone()));
-        let tnt_store = Arc::new(DieselTrackAndTraceStore::new(connection_pool.pool));
+        #[cfg(feature = "search")]
+        let search_store = Arc::new(DieselSearchStore::new(connection_pool.pool.clone()));
+        let tnt_store = Arc::new(DieselTrackAndTraceStore::new(connection_pool.pool.clone()));
+        #[cfg(feature = "webhooks")]
+        let webhook_store = Arc::new(DieselWebhookStore::new(connection_pool.pool));
 
         Self {
+            #[cfg(feature = "change-stream")]
+            change_broadcaster: ChangeBroadcaster::new(),
+            #[cfg(feature = "change-stream")]
+            change_log_store,
+            commit_store,
+            #[cfg(feature = "event")]
+            dead_letter_store,
+            #[cfg(feature = "event")]
+            event_handlers: Arc::new(Mutex::new(event_handlers)),
+            #[cfg(feature = "event")]
+            indexing_status: IndexingStatus::new(),
             location_store,
+            #[cfg(feature = "event")]
+            max_commit_lag: DEFAULT_MAX_COMMIT_LAG,
+            #[cfg(feature = "metrics")]
+            pool_metrics,
             pike_store,
             product_store,
+            purchase_order_store,
             schema_store,
+            #[cfg(feature = "search")]
+            search_store,
             tnt_store,
+            #[cfg(feature = "webhooks")]
+            webhook_store,
+        }
+    }
+
+    /// Streams the changes published by the given broadcaster instead of those of an unused one
+    #[cfg(feature = "change-stream")]
+    pub fn with_change_broadcaster(mut self, change_broadcaster: ChangeBroadcaster) -> Self {
+        self.change_broadcaster = change_broadcaster;
+        self
+    }
+
+    /// Reports the indexing status of the given event processors instead of an empty one
+    #[cfg(feature = "event")]
+    pub fn with_indexing_status(mut self, indexing_status: IndexingStatus) -> Self {
+        self.indexing_status = indexing_status;
+        self
+    }
+
+    /// Reports that gridd is not ready once the commit store trails the ledger by more than the
+    /// given number of commits
+    #[cfg(feature = "event")]
+    pub fn with_max_commit_lag(mut self, max_commit_lag: u64) -> Self {
+        self.max_commit_lag = max_commit_lag;
+        self
+    }
+
+    /// Resolves the `as_of` query parameter to the commit height at which to read state
+    ///
+    /// The parameter is either a commit number or the ID of a commit (a block ID on Sawtooth).
+    /// Returns `None` when no parameter was given, meaning the current state should be read.
+    fn commit_height(&self, as_of: Option<&str>) -> Result<Option<i64>, RestApiResponseError> {
+        let as_of = match as_of {
+            Some(as_of) => as_of,
+            None => return Ok(None),
+        };
+
+        if let Ok(commit_num) = as_of.parse::<i64>() {
+            if commit_num < 0 {
+                return Err(RestApiResponseError::BadRequest(format!(
+                    "Invalid commit number: {}",
+                    commit_num
+                )));
+            }
+            return Ok(Some(commit_num));
+        }
+
+        match self.commit_store.get_commit_by_commit_id(as_of)? {
+            Some(commit) => Ok(Some(commit.commit_num)),
+            None => Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find commit with id: {}",
+                as_of
+            ))),
         }
     }
+
+    /// Resolves the paging query parameters to the page to read
+    ///
+    /// Without a cursor, the page is read by offset at the height given by `as_of`. A cursor
+    /// reads the page it selects at the height it was made at, while an empty cursor reads the
+    /// first page by key at the height given by `as_of`, or at the current height.
+    #[cfg(feature = "pike")]
+    fn page_query(
+        &self,
+        as_of: Option<&str>,
+        cursor: Option<&str>,
+        offset: i64,
+        limit: i64,
+    ) -> Result<PageQuery, RestApiResponseError> {
+        let cursor = match cursor {
+            Some(cursor) => cursor,
+            None => {
+                return Ok(PageQuery::new(
+                    self.commit_height(as_of)?,
+                    PageRequest::Offset { offset, limit },
+                    None,
+                ))
+            }
+        };
+
+        let (commit_height, key, direction) = if cursor.is_empty() {
+            let commit_height = match self.commit_height(as_of)? {
+                Some(commit_height) => commit_height,
+                None => self.commit_store.get_next_commit_num()? - 1,
+            };
+            (commit_height, None, PageDirection::Next)
+        } else {
+            let decoded = Cursor::decode(cursor)
+                .map_err(|err| RestApiResponseError::BadRequest(err.to_string()))?;
+            (decoded.commit_height, Some(decoded.key), decoded.direction)
+        };
+
+        Ok(PageQuery::new(
+            Some(commit_height),
+            PageRequest::Keyset {
+                key,
+                direction,
+                limit,
+            },
+            Some(cursor.to_string()),
+        ))
+    }
 }
 
 #[cfg(all(test, feature = "stable"))]
 mod test {
     use super::*;
+    use std::collections::HashMap;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
     use crate::config::Endpoint;
     use crate::database;
+    #[cfg(feature = "metrics")]
+    use crate::rest_api::request_metrics::RecordRequestMetrics;
     use crate::rest_api::{
+        auth::{Authenticate, Authenticator},
         error::RestApiResponseError,
-        routes::{AgentListSlice, AgentSlice, OrganizationListSlice, OrganizationSlice},
+        routes::{
+            history::{FieldChangeSlice, HistorySlice},
+            AgentListSlice, AgentSlice, OrganizationListSlice, OrganizationSlice,
+        },
         AppState,
     };
     use crate::sawtooth::batch_submitter::{
-        process_batch_status_response, process_validator_response, query_validator,
+        process_batch_status_response, process_validator_response, query_chain_head_height,
+        query_validator,
     };
     use crate::submitter::*;
 
@@ -125,6 +394,7 @@ mod test {
         test::{start, TestServer},
         web, App,
     };
+    use cylinder::{jwt::JsonWebTokenBuilder, secp256k1::Secp256k1Context, Context};
     use diesel::Connection;
     #[cfg(feature = "test-postgres")]
     use diesel::PgConnection;
@@ -135,23 +405,38 @@ mod test {
     use grid_sdk::migrations::{clear_postgres_database, run_postgres_migrations};
     #[cfg(not(feature = "test-postgres"))]
     use grid_sdk::migrations::{clear_sqlite_database, run_sqlite_migrations};
+    #[cfg(feature = "purchase-order")]
+    use grid_sdk::purchase_order::store::{
+        diesel::DieselPurchaseOrderStore, PurchaseOrder, PurchaseOrderVersion,
+        PurchaseOrderVersionRevision,
+    };
+    #[cfg(feature = "search")]
+    use grid_sdk::search::store::{diesel::DieselSearchStore, SearchEntry, SearchStore};
     #[cfg(feature = "track-and-trace")]
     use grid_sdk::track_and_trace::store::{
         diesel::DieselTrackAndTraceStore, AssociatedAgent, LatLongValue, Property, Proposal,
         Record, ReportedValue, Reporter,
     };
     use grid_sdk::{
+        commits::store::{diesel::DieselCommitStore, Commit},
         locations::store::{diesel::DieselLocationStore, Location, LocationAttribute},
-        pike::store::{diesel::DieselPikeStore, Agent, Organization},
+        pike::{
+            store::{diesel::DieselPikeStore, Agent, Organization},
+            MemoryPikeStore,
+        },
         products::store::{diesel::DieselProductStore, Product, PropertyValue},
         schemas::store::{diesel::DieselSchemaStore, PropertyDefinition, Schema},
     };
     use sawtooth_sdk::messages::batch::{Batch, BatchList};
+    use sawtooth_sdk::messages::block::{Block, BlockHeader};
     use sawtooth_sdk::messages::client_batch_submit::{
         ClientBatchStatus, ClientBatchStatusRequest, ClientBatchStatusResponse,
         ClientBatchStatusResponse_Status, ClientBatchStatus_Status, ClientBatchSubmitRequest,
         ClientBatchSubmitResponse, ClientBatchSubmitResponse_Status,
     };
+    use sawtooth_sdk::messages::client_block::{
+        ClientBlockListResponse, ClientBlockListResponse_Status,
+    };
     use sawtooth_sdk::messages::validator::{Message, Message_MessageType};
 
     use sawtooth_sdk::messaging::stream::{MessageFuture, MessageSender, SendError};
@@ -163,10 +448,17 @@ mod test {
     #[cfg(not(feature = "test-postgres"))]
     static DATABASE_URL: &str = "test_db";
 
+    static API_TOKEN: &str = "operator-token";
+
     static KEY1: &str = "111111111111111111111111111111111111111111111111111111111111111111";
     static KEY2: &str = "222222222222222222222222222222222222222222222222222222222222222222";
     static KEY3: &str = "333333333333333333333333333333333333333333333333333333333333333333";
 
+    #[cfg(feature = "track-and-trace")]
+    static TEST_GTIN: &str = "00012345678905";
+    #[cfg(feature = "track-and-trace")]
+    static TEST_GLN: &str = "0123456789012";
+
     static ORG_NAME_1: &str = "my_org";
     static ORG_NAME_2: &str = "other_org";
 
@@ -180,6 +472,9 @@ mod test {
 
     static TEST_SERVICE_ID: &str = "test_service";
 
+    /// The block number of the chain head the mock validator reports
+    const MOCK_CHAIN_HEAD_HEIGHT: u64 = 20;
+
     #[derive(Clone)]
     enum Backend {
         Splinter,
@@ -286,6 +581,13 @@ mod test {
             future::ready(process_batch_status_response(response_status)).boxed()
         }
 
+        fn chain_head_height(
+            &self,
+        ) -> Pin<Box<dyn Future<Output = Result<Option<u64>, RestApiResponseError>> + Send>>
+        {
+            future::ready(query_chain_head_height(&self.sender)).boxed()
+        }
+
         fn clone_box(&self) -> Box<dyn BatchSubmitter> {
             unimplemented!()
         }
@@ -301,33 +603,45 @@ mod test {
             let mut mock_validator_response = Message::new();
             mock_validator_response.set_message_type(destination);
             mock_validator_response.set_correlation_id(correlation_id.to_string());
-            match &self.response_type {
-                ResponseType::ClientBatchStatusResponseOK => {
-                    let request: ClientBatchStatusRequest =
-                        protobuf::Message::parse_from_bytes(contents).unwrap();
-                    if request.get_batch_ids().len() <= 1 {
-                        mock_validator_response.set_content(get_batch_statuses_response_one_id())
-                    } else {
-                        mock_validator_response
-                            .set_content(get_batch_statuses_response_multiple_ids())
+            if destination == Message_MessageType::CLIENT_BLOCK_LIST_REQUEST {
+                // The validator is only unreachable for the internal error responses
+                let status = match self.response_type {
+                    ResponseType::ClientBatchStatusResponseInternalError => {
+                        ClientBlockListResponse_Status::INTERNAL_ERROR
                     }
+                    _ => ClientBlockListResponse_Status::OK,
+                };
+                mock_validator_response.set_content(get_block_list_response(status));
+            } else {
+                match &self.response_type {
+                    ResponseType::ClientBatchStatusResponseOK => {
+                        let request: ClientBatchStatusRequest =
+                            protobuf::Message::parse_from_bytes(contents).unwrap();
+                        if request.get_batch_ids().len() <= 1 {
+                            mock_validator_response
+                                .set_content(get_batch_statuses_response_one_id())
+                        } else {
+                            mock_validator_response
+                                .set_content(get_batch_statuses_response_multiple_ids())
+                        }
+                    }
+                    ResponseType::ClientBatchStatusResponseInvalidId => mock_validator_response
+                        .set_content(get_batch_statuses_response_invalid_id()),
+                    ResponseType::ClientBatchStatusResponseInternalError => mock_validator_response
+                        .set_content(get_batch_statuses_response_validator_internal_error()),
+                    ResponseType::ClientBatchSubmitResponseOK => mock_validator_response
+                        .set_content(get_submit_batches_response(
+                            ClientBatchSubmitResponse_Status::OK,
+                        )),
+                    ResponseType::ClientBatchSubmitResponseInvalidBatch => mock_validator_response
+                        .set_content(get_submit_batches_response(
+                            ClientBatchSubmitResponse_Status::INVALID_BATCH,
+                        )),
+                    ResponseType::ClientBatchSubmitResponseInternalError => mock_validator_response
+                        .set_content(get_submit_batches_response(
+                            ClientBatchSubmitResponse_Status::INTERNAL_ERROR,
+                        )),
                 }
-                ResponseType::ClientBatchStatusResponseInvalidId => {
-                    mock_validator_response.set_content(get_batch_statuses_response_invalid_id())
-                }
-                ResponseType::ClientBatchStatusResponseInternalError => mock_validator_response
-                    .set_content(get_batch_statuses_response_validator_internal_error()),
-                ResponseType::ClientBatchSubmitResponseOK => mock_validator_response.set_content(
-                    get_submit_batches_response(ClientBatchSubmitResponse_Status::OK),
-                ),
-                ResponseType::ClientBatchSubmitResponseInvalidBatch => mock_validator_response
-                    .set_content(get_submit_batches_response(
-                        ClientBatchSubmitResponse_Status::INVALID_BATCH,
-                    )),
-                ResponseType::ClientBatchSubmitResponseInternalError => mock_validator_response
-                    .set_content(get_submit_batches_response(
-                        ClientBatchSubmitResponse_Status::INTERNAL_ERROR,
-                    )),
             }
 
             let mock_resut = Ok(mock_validator_response);
@@ -352,15 +666,25 @@ mod test {
 
     #[cfg(feature = "test-postgres")]
     fn get_connection_pool() -> ConnectionPool<diesel::pg::PgConnection> {
-        database::ConnectionPool::new(&DATABASE_URL).expect("Unable to unwrap connection pool")
+        database::ConnectionPool::with_max_size(&DATABASE_URL, database::DEFAULT_POOL_SIZE)
+            .expect("Unable to unwrap connection pool")
     }
 
     #[cfg(not(feature = "test-postgres"))]
     fn get_connection_pool() -> ConnectionPool<diesel::sqlite::SqliteConnection> {
-        database::ConnectionPool::new(&DATABASE_URL).expect("Unable to unwrap connection pool")
+        database::ConnectionPool::with_max_size(&DATABASE_URL, database::DEFAULT_POOL_SIZE)
+            .expect("Unable to unwrap connection pool")
     }
 
     fn create_test_server(backend: Backend, response_type: ResponseType) -> TestServer {
+        create_test_server_with_auth(backend, response_type, None)
+    }
+
+    fn create_test_server_with_auth(
+        backend: Backend,
+        response_type: ResponseType,
+        authenticator: Option<Arc<Authenticator>>,
+    ) -> TestServer {
         start(move || {
             let state = {
                 let mock_sender = MockMessageSender::new(response_type);
@@ -371,7 +695,11 @@ mod test {
                 let db_executor = DbExecutor::from_pg_pool(get_connection_pool());
                 #[cfg(not(feature = "test-postgres"))]
                 let db_executor = DbExecutor::from_sqlite_pool(get_connection_pool());
-                AppState::new(mock_batch_submitter, db_executor)
+                AppState::new(
+                    mock_batch_submitter,
+                    db_executor,
+                    crate::rest_api::DEFAULT_DB_EXECUTOR_THREADS,
+                )
             };
             let endpoint_backend = match backend {
                 Backend::Splinter => "splinter:",
@@ -380,6 +708,7 @@ mod test {
 
             #[allow(unused_mut)]
             let mut app = App::new()
+                .wrap(Authenticate::new(authenticator.clone()))
                 .data(state)
                 .app_data(Endpoint::from(
                     format!("{}tcp://localhost:9090", endpoint_backend).as_str(),
@@ -390,6 +719,11 @@ mod test {
                         .name("batch_statuses")
                         .route(web::get().to(get_batch_statuses)),
                 )
+                .service(
+                    web::scope("/health")
+                        .service(web::resource("/live").route(web::get().to(fetch_liveness)))
+                        .service(web::resource("/ready").route(web::get().to(check_readiness))),
+                )
                 .service(
                     web::scope("/agent")
                         .service(web::resource("").route(web::get().to(list_agents)))
@@ -398,17 +732,29 @@ mod test {
                 .service(
                     web::scope("/organization")
                         .service(web::resource("").route(web::get().to(list_organizations)))
-                        .service(web::resource("/{id}").route(web::get().to(fetch_organization))),
+                        .service(web::resource("/{id}").route(web::get().to(fetch_organization)))
+                        .service(
+                            web::resource("/{id}/history")
+                                .route(web::get().to(fetch_organization_history)),
+                        ),
                 )
                 .service(
                     web::scope("/product")
                         .service(web::resource("").route(web::get().to(list_products)))
-                        .service(web::resource("/{id}").route(web::get().to(fetch_product))),
+                        .service(web::resource("/{id}").route(web::get().to(fetch_product)))
+                        .service(
+                            web::resource("/{id}/history")
+                                .route(web::get().to(fetch_product_history)),
+                        ),
                 )
                 .service(
                     web::scope("/location")
                         .service(web::resource("").route(web::get().to(list_locations)))
-                        .service(web::resource("/{id}").route(web::get().to(fetch_location))),
+                        .service(web::resource("/{id}").route(web::get().to(fetch_location)))
+                        .service(
+                            web::resource("/{id}/history")
+                                .route(web::get().to(fetch_location_history)),
+                        ),
                 )
                 .service(
                     web::scope("/schema")
@@ -416,6 +762,11 @@ mod test {
                         .service(web::resource("/{name}").route(web::get().to(fetch_grid_schema))),
                 );
 
+            #[cfg(feature = "search")]
+            {
+                app = app.service(web::resource("/search").route(web::get().to(search)));
+            }
+
             #[cfg(feature = "track-and-trace")]
             {
                 app = app.service(
@@ -427,11 +778,84 @@ mod test {
                                 .service(
                                     web::resource("/property/{property_name}")
                                         .route(web::get().to(fetch_record_property)),
+                                )
+                                .service(
+                                    web::resource("/reporters")
+                                        .route(web::get().to(fetch_record_reporters)),
+                                )
+                                .service(
+                                    web::resource("/contents")
+                                        .route(web::get().to(fetch_record_contents)),
+                                )
+                                .service(
+                                    web::resource("/epcis")
+                                        .route(web::get().to(fetch_record_epcis)),
+                                ),
+                        ),
+                );
+            }
+
+            #[cfg(feature = "track-and-trace")]
+            {
+                app = app.service(
+                    web::resource("/epcis/events").route(web::get().to(list_epcis_events)),
+                );
+            }
+
+            #[cfg(feature = "purchase-order")]
+            {
+                app = app.service(
+                    web::scope("/purchase_order")
+                        .service(web::resource("").route(web::get().to(list_purchase_orders)))
+                        .service(
+                            web::scope("/{uid}")
+                                .service(
+                                    web::resource("").route(web::get().to(fetch_purchase_order)),
+                                )
+                                .service(
+                                    web::resource("/version")
+                                        .route(web::get().to(list_purchase_order_versions)),
+                                )
+                                .service(
+                                    web::resource("/version/{version_id}")
+                                        .route(web::get().to(fetch_purchase_order_version)),
+                                )
+                                .service(
+                                    web::resource("/version/{version_id}/revision")
+                                        .route(web::get().to(list_purchase_order_revisions)),
+                                )
+                                .service(
+                                    web::resource("/version/{version_id}/revision/{revision_id}")
+                                        .route(web::get().to(fetch_purchase_order_revision)),
+                                )
+                                .service(
+                                    web::resource("/actions")
+                                        .route(web::get().to(fetch_purchase_order_actions)),
+                                )
+                                .service(
+                                    web::resource("/version/{version_id}/actions")
+                                        .route(web::get().to(fetch_purchase_order_version_actions)),
                                 ),
                         ),
                 );
             }
 
+            #[cfg(feature = "workflow")]
+            {
+                app = app.service(
+                    web::resource("/workflow/{workflow}/{subworkflow}/{state}/actions")
+                        .route(web::get().to(fetch_workflow_actions)),
+                );
+            }
+
+            #[cfg(feature = "metrics")]
+            {
+                app = app.service(web::resource("/metrics").route(web::get().to(fetch_metrics)));
+            }
+
+            #[cfg(feature = "metrics")]
+            let app = app.wrap(RecordRequestMetrics);
+
             app
         })
     }
@@ -1355,403 +1779,2049 @@ mod test {
         assert_eq!(test_product.properties.len(), 2);
     }
 
-    /// Verifies a GET /location responds with an OK response with a
-    ///     list_locations request.
+    /// Verifies a GET /product with filter and sort params responds with an OK response
+    ///     and only the matching Products, in the requested order.
     ///
-    ///     The TestServer will receive a request with no parameters,
-    ///         then will respond with an Ok status and a list of Locations.
     #[actix_rt::test]
-    async fn test_list_locations() {
+    async fn test_list_products_filtered() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
 
         clear_database();
+        populate_product_table(get_filterable_products());
 
         let mut response = srv
-            .request(http::Method::GET, srv.url("location"))
-            .send()
-            .await
-            .unwrap();
-        assert!(response.status().is_success());
-        let empty_body: LocationListSlice =
-            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert!(empty_body.data.is_empty());
-
-        populate_location_table(get_location(None));
-
-        let mut response = srv
-            .request(http::Method::GET, srv.url("/location"))
+            .request(
+                http::Method::GET,
+                srv.url("/product?filter=owner:eq:acme001"),
+            )
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let body: LocationListSlice =
+        let body: ProductListSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
         assert_eq!(body.data.len(), 1);
+        assert_eq!(body.data[0].product_id, "041205707821".to_string());
 
-        let test_location = body.data.first().unwrap();
-        assert_eq!(
-            test_location.location_namespace,
-            "Grid Location".to_string()
-        );
-        assert_eq!(test_location.owner, "phillips001".to_string());
-        assert_eq!(test_location.properties.len(), 2);
-    }
-
-    /// Verifies a GET /product?service_id=test_service responds with an OK response with a
-    ///     list_products request.
-    ///
-    ///     The TestServer will receive a request with a service_id,
-    ///         then will respond with an Ok status and a list of Products.
-    #[actix_rt::test]
-    async fn test_list_products_with_service_id() {
-        run_migrations(&DATABASE_URL);
-        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
-        // Clears the product table in the test database
-        clear_database();
         let mut response = srv
             .request(
                 http::Method::GET,
-                srv.url(&format!("/product?service_id={}", TEST_SERVICE_ID)),
+                srv.url("/product?filter=property.Test%20Grid%20Product:range:10..&filter=namespace:prefix:Grid"),
             )
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let empty_body: ProductListSlice =
+        let body: ProductListSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert!(empty_body.data.is_empty());
+        assert_eq!(body.data.len(), 1);
+        assert_eq!(body.data[0].product_id, "041205707821".to_string());
 
-        populate_product_table(get_product(Some(TEST_SERVICE_ID.to_string())));
         let mut response = srv
-            .request(
-                http::Method::GET,
-                srv.url(&format!("/product?service_id={}", TEST_SERVICE_ID)),
-            )
+            .request(http::Method::GET, srv.url("/product?sort=-id"))
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
         let body: ProductListSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(body.data.len(), 1);
-
-        let test_product = body.data.first().unwrap();
-        assert_eq!(test_product.product_id, "041205707820".to_string());
-        assert_eq!(test_product.product_address, "test_address".to_string());
-        assert_eq!(test_product.product_namespace, "Grid Product".to_string());
-        assert_eq!(test_product.owner, "phillips001".to_string());
-        assert_eq!(test_product.properties.len(), 2);
-        assert_eq!(test_product.service_id, Some(TEST_SERVICE_ID.to_string()));
+        let product_ids: Vec<&str> = body
+            .data
+            .iter()
+            .map(|product| product.product_id.as_str())
+            .collect();
+        assert_eq!(product_ids, vec!["041205707821", "041205707820"]);
     }
 
-    /// Verifies a GET /location?service_id=test_service responds with an OK response with a
-    ///     list_locations request.
     ///
-    ///     The TestServer will receive a request with a service_id,
-    ///         then will respond with an Ok status and a list of Products.
+    /// Verifies the paging links of a filtered and sorted GET /product keep its filter and
+    ///     sort params, so that following the next link reads the next page of the same list.
+    ///
     #[actix_rt::test]
-    async fn test_list_locations_with_service_id() {
+    async fn test_list_products_filtered_paging() {
         run_migrations(&DATABASE_URL);
-        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
-        // Clears the location table in the test database
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
         clear_database();
+        let mut products = get_filterable_products();
+        for (product_id, owner) in &[
+            ("041205707822", "phillips001"),
+            ("041205707823", "acme001"),
+            ("041205707824", "acme001"),
+            ("041205707825", "acme001"),
+            ("041205707826", "phillips001"),
+        ] {
+            let mut product = products[1].clone();
+            product.product_id = product_id.to_string();
+            product.owner = owner.to_string();
+            product
+                .properties
+                .iter_mut()
+                .for_each(|property| property.product_id = product_id.to_string());
+            products.push(product);
+        }
+        populate_product_table(products);
+
         let mut response = srv
             .request(
                 http::Method::GET,
-                srv.url(&format!("/location?service_id={}", TEST_SERVICE_ID)),
+                srv.url("/product?filter=owner:eq:acme001&sort=-id&limit=1"),
             )
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let empty_body: LocationListSlice =
+        let body: serde_json::Value =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert!(empty_body.data.is_empty());
+        assert_eq!(body["data"][0]["product_id"], "041205707825");
+        assert_eq!(body["paging"]["total"], 4);
+        let next = body["paging"]["next"].as_str().unwrap().to_string();
 
-        populate_location_table(get_location(Some(TEST_SERVICE_ID.to_string())));
         let mut response = srv
-            .request(
-                http::Method::GET,
-                srv.url(&format!("/location?service_id={}", TEST_SERVICE_ID)),
-            )
+            .request(http::Method::GET, srv.url(&next))
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let body: LocationListSlice =
+        let body: serde_json::Value =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(body.data.len(), 1);
-
-        let test_location = body.data.first().unwrap();
-        assert_eq!(test_location.location_id, "0653114000000".to_string());
-        assert_eq!(
-            test_location.location_namespace,
-            "Grid Location".to_string()
-        );
-        assert_eq!(test_location.owner, "phillips001".to_string());
-        assert_eq!(test_location.properties.len(), 2);
-        assert_eq!(test_location.service_id, Some(TEST_SERVICE_ID.to_string()));
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"][0]["product_id"], "041205707824");
     }
 
     ///
-    /// Verifies a GET /product/{id} responds with an OK response
-    ///     and the Product with the specified id
+    /// Verifies a GET /product read by cursor with a sort param responds with a BadRequest
+    ///     response, as pages read by cursor are in ID order.
     ///
     #[actix_rt::test]
-    async fn test_fetch_product_ok() {
+    async fn test_list_products_sorted_by_cursor() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
         clear_database();
-        populate_product_table(get_product(None));
-        let mut response = srv
-            .request(
-                http::Method::GET,
-                srv.url(&format!("/product/{}", "041205707820".to_string())),
-            )
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/product?sort=-id&cursor="))
             .send()
             .await
             .unwrap();
-        assert!(response.status().is_success());
-        let test_product: ProductSlice =
-            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(test_product.product_id, "041205707820".to_string());
-        assert_eq!(test_product.product_address, "test_address".to_string());
-        assert_eq!(test_product.product_namespace, "Grid Product".to_string());
-        assert_eq!(test_product.owner, "phillips001".to_string());
-        assert_eq!(test_product.properties.len(), 2);
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
     }
 
     ///
-    /// Verifies a GET /location/{id} responds with an OK response
-    ///     and the Location with the specified id
+    /// Verifies a GET /product with a malformed filter param responds with a BadRequest
+    ///     response.
     ///
     #[actix_rt::test]
-    async fn test_fetch_location_ok() {
+    async fn test_list_products_invalid_filter() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
 
         clear_database();
 
-        populate_location_table(get_location(None));
-
-        let mut response = srv
-            .request(
-                http::Method::GET,
-                srv.url(&format!("/location/{}", "0653114000000".to_string())),
-            )
+        let response = srv
+            .request(http::Method::GET, srv.url("/product?filter=color:eq:red"))
             .send()
             .await
             .unwrap();
-        assert!(response.status().is_success());
-        let test_location: LocationSlice =
-            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(test_location.location_id, "0653114000000".to_string());
-        assert_eq!(
-            test_location.location_namespace,
-            "Grid Location".to_string()
-        );
-        assert_eq!(test_location.owner, "phillips001".to_string());
-        assert_eq!(test_location.properties.len(), 2);
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
     }
 
     ///
-    /// Verifies a GET /product/{id}?service_id=test_service responds with an OK response
-    ///     and the Product with the specified id and service id.
+    /// Verifies a GET /product with a cursor param responds with an OK response and pages
+    ///     through the Products by ID, with next and prev links that are not changed by a
+    ///     later commit.
     ///
     #[actix_rt::test]
-    async fn test_fetch_product_with_service_id_ok() {
+    async fn test_list_products_cursor() {
         run_migrations(&DATABASE_URL);
-        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
         clear_database();
-        populate_product_table(get_product(Some(TEST_SERVICE_ID.to_string())));
+        populate_commit_table(vec![Commit {
+            commit_id: "commit_0".to_string(),
+            commit_num: 0,
+            service_id: None,
+        }]);
+        populate_product_table(get_filterable_products());
+
         let mut response = srv
-            .request(
-                http::Method::GET,
-                srv.url(&format!(
-                    "/product/{}?service_id={}",
-                    "041205707820", TEST_SERVICE_ID
-                )),
-            )
+            .request(http::Method::GET, srv.url("/product?cursor=&limit=1"))
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let test_product: ProductSlice =
+        let body: serde_json::Value =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(test_product.product_id, "041205707820".to_string());
-        assert_eq!(test_product.product_address, "test_address".to_string());
-        assert_eq!(test_product.product_namespace, "Grid Product".to_string());
-        assert_eq!(test_product.owner, "phillips001".to_string());
-        assert_eq!(test_product.properties.len(), 2);
-        assert_eq!(test_product.service_id, Some(TEST_SERVICE_ID.to_string()));
+        assert_eq!(body["data"][0]["product_id"], "041205707820");
+        assert!(body["paging"]["prev"].is_null());
+        assert!(body["paging"]["total"].is_null());
+        let next = body["paging"]["next"].as_str().unwrap().to_string();
+
+        let mut product = get_product(None).remove(0);
+        product.product_id = "041205707819".to_string();
+        product.start_commit_num = 1;
+        product.properties.iter_mut().for_each(|property| {
+            property.product_id = "041205707819".to_string();
+            property.start_commit_num = 1;
+        });
+        populate_commit_table(vec![Commit {
+            commit_id: "commit_1".to_string(),
+            commit_num: 1,
+            service_id: None,
+        }]);
+        populate_product_table(vec![product]);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url(&next))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: serde_json::Value =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"][0]["product_id"], "041205707821");
+        assert!(body["paging"]["next"].is_null());
+        let prev = body["paging"]["prev"].as_str().unwrap().to_string();
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url(&prev))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: serde_json::Value =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"][0]["product_id"], "041205707820");
+        assert!(body["paging"]["prev"].is_null());
+        assert_eq!(body["paging"]["next"], next.as_str());
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/product?cursor=not-a-cursor"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
     }
 
     ///
-    /// Verifies a GET /location/{id}?service_id=test_service responds with an OK response
-    ///     and the Location with the specified id and service id.
+    /// Verifies a GET /search responds with an OK response and the indexed entities matching
+    ///     every word of the query, best matches first.
     ///
+    #[cfg(feature = "search")]
     #[actix_rt::test]
-    async fn test_fetch_location_with_service_id_ok() {
+    async fn test_search() {
         run_migrations(&DATABASE_URL);
-        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
 
         clear_database();
+        populate_search_index(get_search_entries());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/search?q=phillips001"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: SearchHitListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 2);
 
-        populate_location_table(get_location(Some(TEST_SERVICE_ID.to_string())));
         let mut response = srv
             .request(
                 http::Method::GET,
-                srv.url(&format!(
-                    "/location/{}?service_id={}",
-                    "0653114000000", TEST_SERVICE_ID
-                )),
+                srv.url("/search?q=my%20ware&type=location"),
             )
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let test_location: LocationSlice =
+        let body: SearchHitListSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(test_location.location_id, "0653114000000".to_string());
-        assert_eq!(
-            test_location.location_namespace,
-            "Grid Location".to_string()
-        );
-        assert_eq!(test_location.owner, "phillips001".to_string());
-        assert_eq!(test_location.properties.len(), 2);
-        assert_eq!(test_location.service_id, Some(TEST_SERVICE_ID.to_string()));
+        assert_eq!(body.data.len(), 1);
+        assert_eq!(body.data[0].entity_type, "location".to_string());
+        assert_eq!(body.data[0].entity_id, "0653114000000".to_string());
+
+        // The organization named for the query ranks above the product that mentions it
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/search?q=org"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: SearchHitListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        let hits: Vec<&str> = body
+            .data
+            .iter()
+            .map(|hit| hit.entity_type.as_str())
+            .collect();
+        assert_eq!(hits, vec!["organization", "product"]);
     }
 
     ///
-    /// Verifies a GET /product/{id} responds with a Not Found error
-    ///     when there is no Product with the specified id
+    /// Verifies a GET /search by an agent only finds the products and locations its
+    ///     organization owns, along with every organization.
     ///
+    #[cfg(feature = "search")]
     #[actix_rt::test]
-    async fn test_fetch_product_not_found() {
+    async fn test_search_scoped_to_agent_org() {
         run_migrations(&DATABASE_URL);
-        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        let (authenticator, token) = get_authenticator("phillips002");
+        let srv = create_test_server_with_auth(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+
         clear_database();
-        let response = srv
-            .request(http::Method::GET, srv.url("/product/not_in_database"))
+        populate_product_table(get_updated_product());
+        populate_location_table(get_location(None));
+        populate_search_index(get_search_entries());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/search?q=grid"))
+            .header("Authorization", token)
             .send()
             .await
             .unwrap();
-        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        assert!(response.status().is_success());
+        let body: serde_json::Value =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        let hits: Vec<&str> = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|hit| hit["entity_type"].as_str().unwrap())
+            .collect();
+        assert_eq!(hits, vec!["product"]);
+        assert_eq!(body["paging"]["total"], 1);
     }
 
     ///
-    /// Verifies a GET /location/{id} responds with a Not Found error
-    ///     when there is no Product with the specified id
+    /// Verifies a GET /search read by cursor pages through the hits in rank order, with paging
+    ///     links that keep the query.
     ///
+    #[cfg(feature = "search")]
     #[actix_rt::test]
-    async fn test_fetch_location_not_found() {
+    async fn test_search_by_cursor() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
         clear_database();
-        let response = srv
-            .request(http::Method::GET, srv.url("/location/not_in_database"))
+        populate_commit_table(vec![Commit {
+            commit_id: "commit_0".to_string(),
+            commit_num: 0,
+            service_id: None,
+        }]);
+        populate_search_index(get_search_entries());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/search?q=org&cursor=&limit=1"))
             .send()
             .await
             .unwrap();
-        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        assert!(response.status().is_success());
+        let body: serde_json::Value =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body["data"][0]["entity_type"], "organization");
+        let next = body["paging"]["next"].as_str().unwrap().to_string();
+        assert!(next.starts_with("/search?q=org&cursor="));
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url(&next))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: serde_json::Value =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"][0]["entity_type"], "product");
+        assert!(body["paging"]["next"].is_null());
+        assert!(body["paging"]["prev"].is_string());
     }
 
     ///
-    /// Verifies a GET /product/{id}?service_id=test_service responds with a Not Found error
-    ///     when there is no Product with the specified id and service id.
+    /// Verifies a GET /search with an unknown entity type responds with a BadRequest response.
     ///
+    #[cfg(feature = "search")]
     #[actix_rt::test]
-    async fn test_fetch_product_with_service_id_not_found() {
+    async fn test_search_invalid_type() {
         run_migrations(&DATABASE_URL);
-        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
-        clear_database();
-        populate_product_table(get_product(None));
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
         let response = srv
-            .request(
-                http::Method::GET,
-                srv.url(&format!(
-                    "/product/{}?service_id={}",
-                    "041205707820", TEST_SERVICE_ID
-                )),
-            )
+            .request(http::Method::GET, srv.url("/search?q=org&type=record"))
             .send()
             .await
             .unwrap();
-        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
     }
 
+    /// Verifies a GET /location responds with an OK response with a
+    ///     list_locations request.
     ///
-    /// Verifies a GET /location/{id}?service_id=test_service responds with a Not Found error
-    ///     when there is no Product with the specified id and service id.
+    ///     The TestServer will receive a request with no parameters,
+    ///         then will respond with an Ok status and a list of Locations.
+    #[actix_rt::test]
+    async fn test_list_locations() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("location"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let empty_body: LocationListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(empty_body.data.is_empty());
+
+        populate_location_table(get_location(None));
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/location"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: LocationListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+
+        let test_location = body.data.first().unwrap();
+        assert_eq!(
+            test_location.location_namespace,
+            "Grid Location".to_string()
+        );
+        assert_eq!(test_location.owner, "phillips001".to_string());
+        assert_eq!(test_location.properties.len(), 2);
+    }
+
+    /// Verifies a GET /product?service_id=test_service responds with an OK response with a
+    ///     list_products request.
     ///
+    ///     The TestServer will receive a request with a service_id,
+    ///         then will respond with an Ok status and a list of Products.
     #[actix_rt::test]
-    async fn test_fetch_location_with_service_id_not_found() {
+    async fn test_list_products_with_service_id() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+        // Clears the product table in the test database
         clear_database();
-        populate_location_table(get_location(None));
-        let response = srv
+        let mut response = srv
             .request(
                 http::Method::GET,
-                srv.url(&format!(
-                    "/location/{}?service_id={}",
-                    "0653114000000", TEST_SERVICE_ID
-                )),
+                srv.url(&format!("/product?service_id={}", TEST_SERVICE_ID)),
             )
             .send()
             .await
             .unwrap();
-        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        assert!(response.status().is_success());
+        let empty_body: ProductListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(empty_body.data.is_empty());
+
+        populate_product_table(get_product(Some(TEST_SERVICE_ID.to_string())));
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/product?service_id={}", TEST_SERVICE_ID)),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: ProductListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+
+        let test_product = body.data.first().unwrap();
+        assert_eq!(test_product.product_id, "041205707820".to_string());
+        assert_eq!(test_product.product_address, "test_address".to_string());
+        assert_eq!(test_product.product_namespace, "Grid Product".to_string());
+        assert_eq!(test_product.owner, "phillips001".to_string());
+        assert_eq!(test_product.properties.len(), 2);
+        assert_eq!(test_product.service_id, Some(TEST_SERVICE_ID.to_string()));
     }
 
+    /// Verifies a GET /location?service_id=test_service responds with an OK response with a
+    ///     list_locations request.
     ///
-    /// Verifies a GET /record responds with an Ok response
-    ///     with a list containing one record
-    ///
-    #[cfg(feature = "track-and-trace")]
+    ///     The TestServer will receive a request with a service_id,
+    ///         then will respond with an Ok status and a list of Products.
     #[actix_rt::test]
-    async fn test_list_records() {
+    async fn test_list_locations_with_service_id() {
         run_migrations(&DATABASE_URL);
-        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
-
+        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+        // Clears the location table in the test database
         clear_database();
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/location?service_id={}", TEST_SERVICE_ID)),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let empty_body: LocationListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(empty_body.data.is_empty());
 
-        populate_agent_table(get_agents_with_roles(None));
-        populate_associated_agent_table(get_associated_agents(None));
-        populate_proposal_table(get_proposal(None));
-        populate_grid_schema_table(get_grid_schema_for_record(None));
-        populate_record_table(get_record("TestRecord", None));
-        populate_tnt_property_table(
-            get_property_for_record(None),
-            get_reported_value_for_property_record(None),
-            get_reporter_for_property_record(None),
-        );
+        populate_location_table(get_location(Some(TEST_SERVICE_ID.to_string())));
         let mut response = srv
-            .request(http::Method::GET, srv.url(&format!("/record")))
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/location?service_id={}", TEST_SERVICE_ID)),
+            )
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let body: RecordListSlice =
+        let body: LocationListSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
         assert_eq!(body.data.len(), 1);
-        let test_record = body.data.first().unwrap();
-        assert_eq!(test_record.record_id, "TestRecord".to_string());
-        assert_eq!(test_record.schema, "TestGridSchema".to_string());
-        assert_eq!(test_record.owner, KEY1.to_string());
-        assert_eq!(test_record.custodian, KEY2.to_string());
-        assert_eq!(test_record.properties.len(), 2);
-        assert_eq!(test_record.properties[0].name, "TestProperty1");
-        assert_eq!(test_record.properties[0].record_id, "TestRecord");
-        assert_eq!(test_record.properties[0].data_type, "String");
-        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
-        assert_eq!(test_record.properties[0].updates.len(), 1);
+
+        let test_location = body.data.first().unwrap();
+        assert_eq!(test_location.location_id, "0653114000000".to_string());
         assert_eq!(
-            test_record.properties[0].updates[0].value,
-            Value::String("value_1".to_string())
+            test_location.location_namespace,
+            "Grid Location".to_string()
         );
-        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
+        assert_eq!(test_location.owner, "phillips001".to_string());
+        assert_eq!(test_location.properties.len(), 2);
+        assert_eq!(test_location.service_id, Some(TEST_SERVICE_ID.to_string()));
+    }
+
+    ///
+    /// Verifies a GET /product/{id} responds with an OK response
+    ///     and the Product with the specified id
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_product_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        populate_product_table(get_product(None));
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/product/{}", "041205707820".to_string())),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_product: ProductSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_product.product_id, "041205707820".to_string());
+        assert_eq!(test_product.product_address, "test_address".to_string());
+        assert_eq!(test_product.product_namespace, "Grid Product".to_string());
+        assert_eq!(test_product.owner, "phillips001".to_string());
+        assert_eq!(test_product.properties.len(), 2);
+    }
+
+    ///
+    /// Verifies that when authentication is required, a GET /product without a bearer token
+    ///     responds with an Unauthorized response, and one with an API token responds with
+    ///     an OK response and every Product.
+    ///
+    #[actix_rt::test]
+    async fn test_list_products_requires_auth() {
+        run_migrations(&DATABASE_URL);
+        let (authenticator, _) = get_authenticator("acme001");
+        let srv = create_test_server_with_auth(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+
+        clear_database();
+        populate_product_table(get_filterable_products());
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/product"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::UNAUTHORIZED);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product"))
+            .header("Authorization", format!("Bearer {}", API_TOKEN))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: ProductListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 2);
+    }
+
+    ///
+    /// Verifies that GET /health/live and GET /health/ready answer without credentials when
+    ///     authentication is enabled, and that readiness reports the database as reachable
+    ///     and the commits it trails the validator's chain head by.
+    ///
+    #[actix_rt::test]
+    async fn test_health_routes_skip_auth() {
+        run_migrations(&DATABASE_URL);
+        let (authenticator, _) = get_authenticator("acme001");
+        let srv = create_test_server_with_auth(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+
+        clear_database();
+        populate_commit_table(vec![Commit {
+            commit_id: "commit_15".to_string(),
+            commit_num: 15,
+            service_id: None,
+        }]);
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/batch_statuses?id=unknown"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::UNAUTHORIZED);
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/health/live"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/health/ready"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: ReadinessSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(body.ready);
+        assert!(body.database);
+        assert!(body.ledger);
+        assert_eq!(body.commit_lag, Some(5));
+    }
+
+    ///
+    /// Verifies that GET /health/ready responds with Service Unavailable while the commits
+    ///     trail the validator's chain head by more than the maximum lag, or while the
+    ///     validator cannot be reached.
+    ///
+    #[actix_rt::test]
+    async fn test_health_ready_lagging() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/health/ready"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        let body: ReadinessSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(body.database);
+        assert_eq!(body.commit_lag, Some(MOCK_CHAIN_HEAD_HEIGHT));
+
+        let srv = create_test_server(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseInternalError,
+        );
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/health/ready"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        let body: ReadinessSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(!body.ledger);
+    }
+
+    ///
+    /// Verifies that GET /metrics is limited to operators, and reports the requests made to
+    ///     each route and the utilization of the database connection pool.
+    ///
+    #[cfg(feature = "metrics")]
+    #[actix_rt::test]
+    async fn test_fetch_metrics() {
+        run_migrations(&DATABASE_URL);
+        let (authenticator, token) = get_authenticator("acme001");
+        let srv = create_test_server_with_auth(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/product/unknown"))
+            .header("Authorization", format!("Bearer {}", API_TOKEN))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/metrics"))
+            .header("Authorization", token)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/metrics"))
+            .header("Authorization", format!("Bearer {}", API_TOKEN))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body = String::from_utf8(response.body().await.unwrap().to_vec()).unwrap();
+        assert!(body.contains(
+            "grid_rest_api_requests_total{method=\"GET\",route=\"/product/{id}\",status=\"404\"}"
+        ));
+        assert!(body.contains(
+            "grid_rest_api_requests_total{method=\"GET\",route=\"/metrics\",status=\"403\"}"
+        ));
+        assert!(body.contains("grid_database_pool_max_connections 10"));
+    }
+
+    ///
+    /// Verifies that an agent authenticated with a JSON web token may only list and fetch
+    ///     the Products owned by its organization; other Products respond with a NotFound
+    ///     response.
+    ///
+    #[actix_rt::test]
+    async fn test_products_scoped_to_agent_org() {
+        run_migrations(&DATABASE_URL);
+        let (authenticator, token) = get_authenticator("acme001");
+        let srv = create_test_server_with_auth(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+
+        clear_database();
+        populate_product_table(get_filterable_products());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product"))
+            .header("Authorization", token.clone())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: ProductListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+        assert_eq!(body.data[0].owner, "acme001".to_string());
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/product/041205707821"))
+            .header("Authorization", token.clone())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/product/041205707820"))
+            .header("Authorization", token)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    ///
+    /// Verifies that an agent authenticated with a JSON web token may only list, fetch and read
+    ///     the history of its own Organization; other Organizations respond with a NotFound
+    ///     response.
+    ///
+    #[actix_rt::test]
+    async fn test_organizations_scoped_to_agent_org() {
+        run_migrations(&DATABASE_URL);
+        let (authenticator, token) = get_authenticator(KEY2);
+        let srv = create_test_server_with_auth(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+
+        clear_database();
+        populate_organization_table(get_organization(None));
+        populate_organization_table(get_updated_organization());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/organization"))
+            .header("Authorization", token.clone())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: OrganizationListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+        assert_eq!(body.data[0].org_id, KEY2.to_string());
+
+        for url in &[
+            format!("/organization/{}", KEY2),
+            format!("/organization/{}/history", KEY2),
+        ] {
+            let response = srv
+                .request(http::Method::GET, srv.url(url))
+                .header("Authorization", token.clone())
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+        }
+
+        for url in &[
+            format!("/organization/{}", KEY3),
+            format!("/organization/{}/history", KEY3),
+        ] {
+            let response = srv
+                .request(http::Method::GET, srv.url(url))
+                .header("Authorization", token.clone())
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        }
+    }
+
+    ///
+    /// Verifies that an agent authenticated with a JSON web token may only list and fetch the
+    ///     Agents of its own organization; other Agents respond with a NotFound response.
+    ///
+    #[actix_rt::test]
+    async fn test_agents_scoped_to_agent_org() {
+        run_migrations(&DATABASE_URL);
+        let (authenticator, token) = get_authenticator(KEY2);
+        let srv = create_test_server_with_auth(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+
+        clear_database();
+        let mut agents = get_agent(None);
+        agents.push(Agent {
+            public_key: KEY3.to_string(),
+            org_id: KEY3.to_string(),
+            ..get_agent(None).remove(0)
+        });
+        populate_agent_table(agents);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/agent"))
+            .header("Authorization", token.clone())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: AgentListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+        assert_eq!(body.data[0].public_key, KEY1.to_string());
+
+        let response = srv
+            .request(http::Method::GET, srv.url(&format!("/agent/{}", KEY1)))
+            .header("Authorization", token.clone())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let response = srv
+            .request(http::Method::GET, srv.url(&format!("/agent/{}", KEY3)))
+            .header("Authorization", token)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    ///
+    /// Verifies that an agent that is not active in the requested service responds with a
+    ///     Forbidden response.
+    ///
+    #[actix_rt::test]
+    async fn test_list_products_agent_wrong_service() {
+        run_migrations(&DATABASE_URL);
+        let (authenticator, token) = get_authenticator("acme001");
+        let srv = create_test_server_with_auth(
+            Backend::Splinter,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+
+        clear_database();
+
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/product?service_id={}", TEST_SERVICE_ID)),
+            )
+            .header("Authorization", token)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+    }
+
+    ///
+    /// Verifies a GET /location/{id} responds with an OK response
+    ///     and the Location with the specified id
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_location_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        populate_location_table(get_location(None));
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/location/{}", "0653114000000".to_string())),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_location: LocationSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_location.location_id, "0653114000000".to_string());
+        assert_eq!(
+            test_location.location_namespace,
+            "Grid Location".to_string()
+        );
+        assert_eq!(test_location.owner, "phillips001".to_string());
+        assert_eq!(test_location.properties.len(), 2);
+    }
+
+    ///
+    /// Verifies a GET /product/{id}?service_id=test_service responds with an OK response
+    ///     and the Product with the specified id and service id.
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_product_with_service_id_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        populate_product_table(get_product(Some(TEST_SERVICE_ID.to_string())));
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/product/{}?service_id={}",
+                    "041205707820", TEST_SERVICE_ID
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_product: ProductSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_product.product_id, "041205707820".to_string());
+        assert_eq!(test_product.product_address, "test_address".to_string());
+        assert_eq!(test_product.product_namespace, "Grid Product".to_string());
+        assert_eq!(test_product.owner, "phillips001".to_string());
+        assert_eq!(test_product.properties.len(), 2);
+        assert_eq!(test_product.service_id, Some(TEST_SERVICE_ID.to_string()));
+    }
+
+    ///
+    /// Verifies a GET /location/{id}?service_id=test_service responds with an OK response
+    ///     and the Location with the specified id and service id.
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_location_with_service_id_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        populate_location_table(get_location(Some(TEST_SERVICE_ID.to_string())));
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/location/{}?service_id={}",
+                    "0653114000000", TEST_SERVICE_ID
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_location: LocationSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_location.location_id, "0653114000000".to_string());
+        assert_eq!(
+            test_location.location_namespace,
+            "Grid Location".to_string()
+        );
+        assert_eq!(test_location.owner, "phillips001".to_string());
+        assert_eq!(test_location.properties.len(), 2);
+        assert_eq!(test_location.service_id, Some(TEST_SERVICE_ID.to_string()));
+    }
+
+    ///
+    /// Verifies a GET /product/{id}?as_of={commit} responds with an OK response
+    ///     and the Product as it was at the specified commit number or commit id
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_product_as_of_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        populate_product_table(get_updated_product());
+        populate_commit_table(vec![Commit {
+            commit_id: "commit_2".to_string(),
+            commit_num: 2,
+            service_id: None,
+        }]);
+
+        for as_of in &["1", "2", "commit_2"] {
+            let mut response = srv
+                .request(
+                    http::Method::GET,
+                    srv.url(&format!("/product/041205707820?as_of={}", as_of)),
+                )
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+            let test_product: ProductSlice =
+                serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+            assert_eq!(test_product.owner, "phillips001".to_string());
+            assert_eq!(test_product.properties.len(), 2);
+        }
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product/041205707820?as_of=3"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_product: ProductSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_product.owner, "phillips002".to_string());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: ProductListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+        assert_eq!(body.data[0].owner, "phillips002".to_string());
+
+        // The paging links of a list read at a commit keep reading at that commit
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product?as_of=1"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: serde_json::Value =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body["data"][0]["owner"], "phillips001");
+        assert_eq!(body["data"][0]["properties"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            body["paging"]["first"],
+            "/product?offset=0&limit=10&as_of=1"
+        );
+    }
+
+    ///
+    /// Verifies a GET /product/{id}?as_of={commit} responds with a Not Found error
+    ///     when the Product did not exist yet at that commit, or the commit id is unknown
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_product_as_of_not_found() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        populate_product_table(get_updated_product());
+
+        for as_of in &["0", "unknown_commit"] {
+            let response = srv
+                .request(
+                    http::Method::GET,
+                    srv.url(&format!("/product/041205707820?as_of={}", as_of)),
+                )
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        }
+    }
+
+    ///
+    /// Verifies a GET /product/{id}/history responds with an OK response
+    ///     and every version of the Product, with the fields changed by each
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_product_history_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        populate_product_table(get_updated_product());
+        populate_commit_table(vec![
+            Commit {
+                commit_id: "commit_1".to_string(),
+                commit_num: 1,
+                service_id: None,
+            },
+            Commit {
+                commit_id: "commit_3".to_string(),
+                commit_num: 3,
+                service_id: None,
+            },
+        ]);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product/041205707820/history"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let history: HistorySlice<ProductSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(history.data.len(), 2);
+
+        assert_eq!(history.data[0].commit_id, Some("commit_1".to_string()));
+        assert_eq!(history.data[0].commit_num, 1);
+        assert_eq!(history.data[0].value.owner, "phillips001".to_string());
+        assert!(history.data[0]
+            .changes
+            .iter()
+            .all(|change| change.old_value.is_none()));
+
+        assert_eq!(history.data[1].commit_id, Some("commit_3".to_string()));
+        assert_eq!(history.data[1].commit_num, 3);
+        assert_eq!(history.data[1].value.owner, "phillips002".to_string());
+        assert_eq!(
+            history.data[1].changes,
+            vec![FieldChangeSlice {
+                field: "owner".to_string(),
+                old_value: Some(serde_json::json!("phillips001")),
+                new_value: Some(serde_json::json!("phillips002")),
+            }]
+        );
+    }
+
+    ///
+    /// Verifies a GET /product/{id}/history by an agent only lists the versions of the Product
+    ///     owned by the agent's organization
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_product_history_scoped_to_agent_org() {
+        run_migrations(&DATABASE_URL);
+        let (authenticator, token) = get_authenticator("phillips001");
+        let srv = create_test_server_with_auth(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+        clear_database();
+        populate_product_table(get_updated_product());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product/041205707820/history"))
+            .header("Authorization", token)
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let history: HistorySlice<ProductSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(history.data.len(), 1);
+        assert_eq!(history.data[0].commit_num, 1);
+        assert_eq!(history.data[0].value.owner, "phillips001".to_string());
+    }
+
+    ///
+    /// Verifies a GET /location/{id}/history responds with a Not Found error
+    ///     when there is no Location with the specified id
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_location_history_not_found() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/location/not_in_database/history"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    ///
+    /// Verifies a GET /product/{id} responds with a Not Found error
+    ///     when there is no Product with the specified id
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_product_not_found() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        let response = srv
+            .request(http::Method::GET, srv.url("/product/not_in_database"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    ///
+    /// Verifies a GET /location/{id} responds with a Not Found error
+    ///     when there is no Product with the specified id
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_location_not_found() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        let response = srv
+            .request(http::Method::GET, srv.url("/location/not_in_database"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    ///
+    /// Verifies a GET /product/{id}?service_id=test_service responds with a Not Found error
+    ///     when there is no Product with the specified id and service id.
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_product_with_service_id_not_found() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        populate_product_table(get_product(None));
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/product/{}?service_id={}",
+                    "041205707820", TEST_SERVICE_ID
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    ///
+    /// Verifies a GET /location/{id}?service_id=test_service responds with a Not Found error
+    ///     when there is no Product with the specified id and service id.
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_location_with_service_id_not_found() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        populate_location_table(get_location(None));
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/location/{}?service_id={}",
+                    "0653114000000", TEST_SERVICE_ID
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    ///
+    /// Verifies a GET /record responds with an Ok response
+    ///     with a list containing one record
+    ///
+    #[cfg(feature = "track-and-trace")]
+    #[actix_rt::test]
+    async fn test_list_records() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        populate_agent_table(get_agents_with_roles(None));
+        populate_associated_agent_table(get_associated_agents(None));
+        populate_proposal_table(get_proposal(None));
+        populate_grid_schema_table(get_grid_schema_for_record(None));
+        populate_record_table(get_record("TestRecord", None));
+        populate_tnt_property_table(
+            get_property_for_record(None),
+            get_reported_value_for_property_record(None),
+            get_reporter_for_property_record(None),
+        );
+        let mut response = srv
+            .request(http::Method::GET, srv.url(&format!("/record")))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: RecordListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+        let test_record = body.data.first().unwrap();
+        assert_eq!(test_record.record_id, "TestRecord".to_string());
+        assert_eq!(test_record.schema, "TestGridSchema".to_string());
+        assert_eq!(test_record.owner, KEY1.to_string());
+        assert_eq!(test_record.custodian, KEY2.to_string());
+        assert_eq!(test_record.properties.len(), 2);
+        assert_eq!(test_record.properties[0].name, "TestProperty1");
+        assert_eq!(test_record.properties[0].record_id, "TestRecord");
+        assert_eq!(test_record.properties[0].data_type, "String");
+        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
+        assert_eq!(test_record.properties[0].updates.len(), 1);
+        assert_eq!(
+            test_record.properties[0].updates[0].value,
+            Value::String("value_1".to_string())
+        );
+        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
+        validate_reporter(&test_record.properties[0].updates[0].reporter, KEY1, None);
+        assert_eq!(
+            test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .value,
+            Value::String("value_1".to_string())
+        );
+        assert_eq!(
+            test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .timestamp,
+            5
+        );
+        validate_reporter(
+            &test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .reporter,
+            KEY1,
+            None,
+        );
+
+        assert_eq!(test_record.properties[1].name, "TestProperty2");
+        assert_eq!(test_record.properties[1].record_id, "TestRecord");
+        assert_eq!(test_record.properties[1].data_type, "Boolean");
+        assert_eq!(test_record.properties[1].reporters, vec![KEY2.to_string()]);
+        assert_eq!(test_record.properties[1].updates.len(), 1);
+        assert_eq!(
+            test_record.properties[1].updates[0].value,
+            Value::Bool(true)
+        );
+        assert_eq!(test_record.properties[1].updates[0].timestamp, 5);
+        validate_reporter(&test_record.properties[1].updates[0].reporter, KEY2, None);
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .value,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .timestamp,
+            5
+        );
+        validate_reporter(
+            &test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .reporter,
+            KEY2,
+            None,
+        );
+
+        assert_eq!(test_record.r#final, false);
+
+        assert_eq!(test_record.owner_updates[0].agent_id, KEY1.to_string());
+        assert_eq!(test_record.owner_updates[0].timestamp, 1);
+
+        assert_eq!(test_record.custodian_updates[0].agent_id, KEY2.to_string());
+        assert_eq!(test_record.custodian_updates[0].timestamp, 1);
+
+        assert_eq!(test_record.proposals[0].timestamp, 1);
+        assert_eq!(test_record.proposals[0].role, "OWNER");
+        assert_eq!(
+            test_record.proposals[0].properties,
+            vec!["location".to_string()]
+        );
+        assert_eq!(test_record.proposals[0].status, "OPEN");
+        assert_eq!(test_record.proposals[0].terms, "Proposal Terms".to_string());
+    }
+
+    ///
+    /// Verifies a GET /record by an agent only lists, and counts, the Records currently held
+    ///     by agents of its organization
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_list_records_scoped_to_agent_org() {
+        run_migrations(&DATABASE_URL);
+        let (authenticator, token) = get_authenticator(KEY3);
+        let srv = create_test_server_with_auth(
+            Backend::Sawtooth,
+            ResponseType::ClientBatchStatusResponseOK,
+            Some(authenticator),
+        );
+
+        clear_database();
+
+        populate_agent_table(get_agents_with_roles(None));
+        populate_grid_schema_table(get_grid_schema_for_record(None));
+        let mut records = get_record("TestRecord", None);
+        records.extend(
+            get_record("OtherRecord", None)
+                .into_iter()
+                .map(|record| Record {
+                    owners: vec!["other_agent".to_string()],
+                    custodians: vec![KEY2.to_string(), "other_agent".to_string()],
+                    ..record
+                }),
+        );
+        populate_record_table(records);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/record"))
+            .header("Authorization", token)
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: serde_json::Value =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"][0]["record_id"], "TestRecord");
+        assert_eq!(body["paging"]["total"], 1);
+    }
+
+    ///
+    /// Verifies a GET /record?service_id=test_service responds with an Ok response
+    ///     with a list containing one record with the correct service id.
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_list_records_with_service_id() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        populate_agent_table(get_agents_with_roles(Some(TEST_SERVICE_ID.to_string())));
+        populate_associated_agent_table(get_associated_agents(Some(TEST_SERVICE_ID.to_string())));
+        populate_proposal_table(get_proposal(Some(TEST_SERVICE_ID.to_string())));
+        populate_grid_schema_table(get_grid_schema_for_record(None));
+        populate_record_table(get_record("TestRecord", Some(TEST_SERVICE_ID.to_string())));
+        populate_tnt_property_table(
+            get_property_for_record(Some(TEST_SERVICE_ID.to_string())),
+            get_reported_value_for_property_record(Some(TEST_SERVICE_ID.to_string())),
+            get_reporter_for_property_record(Some(TEST_SERVICE_ID.to_string())),
+        );
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/record?service_id={}", TEST_SERVICE_ID)),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: RecordListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+        let test_record = body.data.first().unwrap();
+        assert_eq!(test_record.record_id, "TestRecord".to_string());
+        assert_eq!(test_record.schema, "TestGridSchema".to_string());
+        assert_eq!(test_record.owner, KEY1.to_string());
+        assert_eq!(test_record.custodian, KEY2.to_string());
+        assert_eq!(test_record.service_id, Some(TEST_SERVICE_ID.to_string()));
+        assert_eq!(test_record.properties.len(), 2);
+        assert_eq!(test_record.properties[0].name, "TestProperty1");
+        assert_eq!(test_record.properties[0].record_id, "TestRecord");
+        assert_eq!(test_record.properties[0].data_type, "String");
+        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
+        assert_eq!(test_record.properties[0].updates.len(), 1);
+        assert_eq!(
+            test_record.properties[0].updates[0].value,
+            Value::String("value_1".to_string())
+        );
+        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
+        assert_eq!(
+            test_record.properties[0].service_id,
+            Some(TEST_SERVICE_ID.to_string())
+        );
+        validate_reporter(
+            &test_record.properties[0].updates[0].reporter,
+            KEY1,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+        assert_eq!(
+            test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .value,
+            Value::String("value_1".to_string())
+        );
+        assert_eq!(
+            test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .timestamp,
+            5
+        );
+        assert_eq!(
+            test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .service_id,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+        validate_reporter(
+            &test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .reporter,
+            KEY1,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+
+        assert_eq!(test_record.properties[1].name, "TestProperty2");
+        assert_eq!(test_record.properties[1].record_id, "TestRecord");
+        assert_eq!(test_record.properties[1].data_type, "Boolean");
+        assert_eq!(test_record.properties[1].reporters, vec![KEY2.to_string()]);
+        assert_eq!(test_record.properties[1].updates.len(), 1);
+        assert_eq!(
+            test_record.properties[1].updates[0].value,
+            Value::Bool(true)
+        );
+        assert_eq!(test_record.properties[1].updates[0].timestamp, 5);
+        assert_eq!(
+            test_record.properties[1].updates[0].service_id,
+            Some(TEST_SERVICE_ID.to_string())
+        );
+        validate_reporter(
+            &test_record.properties[1].updates[0].reporter,
+            KEY2,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .value,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .timestamp,
+            5
+        );
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .service_id,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+        validate_reporter(
+            &test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .reporter,
+            KEY2,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+
+        assert_eq!(test_record.r#final, false);
+
+        assert_eq!(test_record.owner_updates[0].agent_id, KEY1.to_string());
+        assert_eq!(test_record.owner_updates[0].timestamp, 1);
+
+        assert_eq!(test_record.custodian_updates[0].agent_id, KEY2.to_string());
+        assert_eq!(test_record.custodian_updates[0].timestamp, 1);
+
+        assert_eq!(test_record.proposals[0].timestamp, 1);
+        assert_eq!(test_record.proposals[0].role, "OWNER");
+        assert_eq!(
+            test_record.proposals[0].properties,
+            vec!["location".to_string()]
+        );
+        assert_eq!(test_record.proposals[0].status, "OPEN");
+        assert_eq!(test_record.proposals[0].terms, "Proposal Terms".to_string());
+        assert_eq!(
+            test_record.proposals[0].service_id,
+            Some(TEST_SERVICE_ID.to_string())
+        );
+    }
+
+    ///
+    /// Verifies a GET /record responds with an Ok response
+    /// with a list containing one record, when there's two records for the same
+    /// record_id. The rest-api should return a list with a single record with the
+    /// record that contains the most recent information for that record
+    /// (end_commit_num == MAX_COMMIT_NUM)
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_list_records_updated() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+        // Adds two instances of record with the same org_id to the test database
+        populate_record_table(get_updated_record());
+
+        // Making another request to the database
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/record"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: RecordListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+        let test_record = body.data.first().unwrap();
+        assert_eq!(test_record.record_id, "TestRecord".to_string());
+        assert_eq!(test_record.schema, "TestGridSchema".to_string());
+        assert_eq!(test_record.r#final, true);
+    }
+
+    ///
+    /// Verifies a GET /record responds with an Ok response
+    /// with a list containing two records, when there's two records with differing
+    /// record_ids, one of which has been updated.
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_list_records_multiple() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+        // Adds two instances of record with the same org_id to the test database
+        populate_record_table(get_multiple_records());
+        populate_tnt_property_table(
+            get_property_for_record(None),
+            get_reported_value_for_property_record(None),
+            get_reporter_for_property_record(None),
+        );
+
+        // Making another request to the database
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/record"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: RecordListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(body.data.len(), 2);
+        let record_1 = &body.data[0];
+        assert_eq!(record_1.properties.len(), 2);
+
+        let record_2 = &body.data[1];
+        assert!(record_2.properties.is_empty());
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id} responds with an OK response
+    ///     and the Record with the specified record ID.
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_fetch_record_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+
+        populate_agent_table(get_agents_with_roles(None));
+        populate_associated_agent_table(get_associated_agents(None));
+        populate_proposal_table(get_proposal(None));
+        populate_record_table(get_record("TestRecord", None));
+        populate_grid_schema_table(get_grid_schema_for_record(None));
+        populate_tnt_property_table(
+            get_property_for_record(None),
+            get_reported_value_for_property_record(None),
+            get_reporter_for_property_record(None),
+        );
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/record/{}", "TestRecord".to_string())),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_record: RecordSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_record.record_id, "TestRecord".to_string());
+        assert_eq!(test_record.schema, "TestGridSchema".to_string());
+        assert_eq!(test_record.owner, KEY1.to_string());
+        assert_eq!(test_record.custodian, KEY2.to_string());
+
+        assert_eq!(test_record.properties.len(), 2);
+        assert_eq!(test_record.properties[0].name, "TestProperty1");
+        assert_eq!(test_record.properties[0].record_id, "TestRecord");
+        assert_eq!(test_record.properties[0].data_type, "String");
+        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
+        assert_eq!(test_record.properties[0].updates.len(), 1);
+        assert_eq!(
+            test_record.properties[0].updates[0].value,
+            Value::String("value_1".to_string())
+        );
+        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
+        validate_reporter(&test_record.properties[0].updates[0].reporter, KEY1, None);
+        assert_eq!(
+            test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .value,
+            Value::String("value_1".to_string())
+        );
+        assert_eq!(
+            test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .timestamp,
+            5
+        );
+        validate_reporter(
+            &test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .reporter,
+            KEY1,
+            None,
+        );
+
+        assert_eq!(test_record.properties[1].name, "TestProperty2");
+        assert_eq!(test_record.properties[1].record_id, "TestRecord");
+        assert_eq!(test_record.properties[1].data_type, "Boolean");
+        assert_eq!(test_record.properties[1].reporters, vec![KEY2.to_string()]);
+        assert_eq!(test_record.properties[1].updates.len(), 1);
+        assert_eq!(
+            test_record.properties[1].updates[0].value,
+            Value::Bool(true)
+        );
+        assert_eq!(test_record.properties[1].updates[0].timestamp, 5);
+        validate_reporter(&test_record.properties[1].updates[0].reporter, KEY2, None);
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .value,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .timestamp,
+            5
+        );
+        validate_reporter(
+            &test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .reporter,
+            KEY2,
+            None,
+        );
+
+        assert_eq!(test_record.r#final, false);
+
+        assert_eq!(test_record.owner_updates[0].agent_id, KEY1.to_string());
+        assert_eq!(test_record.owner_updates[0].timestamp, 1);
+
+        assert_eq!(test_record.custodian_updates[0].agent_id, KEY2.to_string());
+        assert_eq!(test_record.custodian_updates[0].timestamp, 1);
+
+        assert_eq!(test_record.proposals[0].timestamp, 1);
+        assert_eq!(test_record.proposals[0].role, "OWNER");
+        assert_eq!(
+            test_record.proposals[0].properties,
+            vec!["location".to_string()]
+        );
+        assert_eq!(test_record.proposals[0].status, "OPEN");
+        assert_eq!(test_record.proposals[0].terms, "Proposal Terms".to_string());
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id}?service_id=test_service responds with an OK response
+    ///     and the Record with the specified record ID.
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_fetch_record_with_service_id_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        populate_agent_table(get_agents_with_roles(Some(TEST_SERVICE_ID.to_string())));
+        populate_associated_agent_table(get_associated_agents(Some(TEST_SERVICE_ID.to_string())));
+        populate_proposal_table(get_proposal(Some(TEST_SERVICE_ID.to_string())));
+        populate_record_table(get_record("TestRecord", Some(TEST_SERVICE_ID.to_string())));
+        populate_grid_schema_table(get_grid_schema_for_record(None));
+        populate_tnt_property_table(
+            get_property_for_record(Some(TEST_SERVICE_ID.to_string())),
+            get_reported_value_for_property_record(Some(TEST_SERVICE_ID.to_string())),
+            get_reporter_for_property_record(Some(TEST_SERVICE_ID.to_string())),
+        );
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/record/{}?service_id={}",
+                    "TestRecord", TEST_SERVICE_ID
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_record: RecordSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_record.record_id, "TestRecord".to_string());
+        assert_eq!(test_record.schema, "TestGridSchema".to_string());
+        assert_eq!(test_record.owner, KEY1.to_string());
+        assert_eq!(test_record.custodian, KEY2.to_string());
+        assert_eq!(test_record.service_id, Some(TEST_SERVICE_ID.to_string()));
+
+        assert_eq!(test_record.properties.len(), 2);
+        assert_eq!(test_record.properties[0].name, "TestProperty1");
+        assert_eq!(test_record.properties[0].record_id, "TestRecord");
+        assert_eq!(test_record.properties[0].data_type, "String");
+        assert_eq!(
+            test_record.properties[0].service_id,
+            Some(TEST_SERVICE_ID.to_string())
+        );
+        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
+        assert_eq!(test_record.properties[0].updates.len(), 1);
+        assert_eq!(
+            test_record.properties[0].updates[0].value,
+            Value::String("value_1".to_string())
+        );
+        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
+        validate_reporter(
+            &test_record.properties[0].updates[0].reporter,
+            KEY1,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+        assert_eq!(
+            test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .value,
+            Value::String("value_1".to_string())
+        );
+        assert_eq!(
+            test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .timestamp,
+            5
+        );
+        validate_reporter(
+            &test_record.properties[0]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .reporter,
+            KEY1,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+
+        assert_eq!(test_record.properties[1].name, "TestProperty2");
+        assert_eq!(test_record.properties[1].record_id, "TestRecord");
+        assert_eq!(test_record.properties[1].data_type, "Boolean");
+        assert_eq!(test_record.properties[1].reporters, vec![KEY2.to_string()]);
+        assert_eq!(test_record.properties[1].updates.len(), 1);
+        assert_eq!(
+            test_record.properties[1].updates[0].value,
+            Value::Bool(true)
+        );
+        assert_eq!(test_record.properties[1].updates[0].timestamp, 5);
+        validate_reporter(
+            &test_record.properties[1].updates[0].reporter,
+            KEY2,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .value,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .timestamp,
+            5
+        );
+        assert_eq!(
+            test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .service_id,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+        validate_reporter(
+            &test_record.properties[1]
+                .value
+                .clone()
+                .expect("Property value not returned")
+                .reporter,
+            KEY2,
+            Some(TEST_SERVICE_ID.to_string()),
+        );
+
+        assert_eq!(test_record.r#final, false);
+
+        assert_eq!(test_record.owner_updates[0].agent_id, KEY1.to_string());
+        assert_eq!(test_record.owner_updates[0].timestamp, 1);
+
+        assert_eq!(test_record.custodian_updates[0].agent_id, KEY2.to_string());
+        assert_eq!(test_record.custodian_updates[0].timestamp, 1);
+
+        assert_eq!(test_record.proposals[0].timestamp, 1);
+        assert_eq!(test_record.proposals[0].role, "OWNER");
+        assert_eq!(
+            test_record.proposals[0].properties,
+            vec!["location".to_string()]
+        );
+        assert_eq!(test_record.proposals[0].status, "OPEN");
+        assert_eq!(test_record.proposals[0].terms, "Proposal Terms".to_string());
+        assert_eq!(
+            test_record.proposals[0].service_id,
+            Some(TEST_SERVICE_ID.to_string())
+        );
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id} responds with an OK response
+    ///     and the Record with the specified record ID after the Record's
+    ///     owners, custodians, and proposals have been updated.
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_fetch_record_updated_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        populate_grid_schema_table(get_grid_schema_for_record(None));
+        populate_record_table(get_updated_record());
+        populate_tnt_property_table(
+            get_property_for_record(None),
+            get_reported_value_for_property_record(None),
+            get_reporter_for_property_record(None),
+        );
+        populate_associated_agent_table(get_associated_agents_updated());
+        populate_proposal_table(get_updated_proposal());
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/record/{}", "TestRecord".to_string())),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_record: RecordSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(test_record.record_id, "TestRecord".to_string());
+        assert_eq!(test_record.schema, "TestGridSchema".to_string());
+        assert_eq!(test_record.owner, KEY2.to_string());
+        assert_eq!(test_record.custodian, KEY1.to_string());
+
+        assert_eq!(test_record.properties.len(), 2);
+        assert_eq!(test_record.properties[0].name, "TestProperty1");
+        assert_eq!(test_record.properties[0].record_id, "TestRecord");
+        assert_eq!(test_record.properties[0].data_type, "String");
+        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
+        assert_eq!(test_record.properties[0].updates.len(), 1);
+        assert_eq!(
+            test_record.properties[0].updates[0].value,
+            Value::String("value_1".to_string())
+        );
+        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
         validate_reporter(&test_record.properties[0].updates[0].reporter, KEY1, None);
         assert_eq!(
             test_record.properties[0]
@@ -1816,7 +3886,7 @@ mod test {
             None,
         );
 
-        assert_eq!(test_record.r#final, false);
+        assert_eq!(test_record.r#final, true);
 
         assert_eq!(test_record.owner_updates[0].agent_id, KEY1.to_string());
         assert_eq!(test_record.owner_updates[0].timestamp, 1);
@@ -1824,689 +3894,903 @@ mod test {
         assert_eq!(test_record.custodian_updates[0].agent_id, KEY2.to_string());
         assert_eq!(test_record.custodian_updates[0].timestamp, 1);
 
-        assert_eq!(test_record.proposals[0].timestamp, 1);
-        assert_eq!(test_record.proposals[0].role, "OWNER");
+        assert_eq!(test_record.owner_updates[1].agent_id, KEY2.to_string());
+        assert_eq!(test_record.owner_updates[1].timestamp, 2);
+
+        assert_eq!(test_record.custodian_updates[1].agent_id, KEY1.to_string());
+        assert_eq!(test_record.custodian_updates[1].timestamp, 2);
+
+        assert_eq!(test_record.proposals[0].status, "CANCELED");
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id} responds with a Not Found error
+    ///     when there is no Record with the specified record_id.
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_fetch_record_not_found() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        let response = srv
+            .request(http::Method::GET, srv.url("/record/not_in_database"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id}?service_id=test_service responds with a Not Found error
+    ///     when there is no Record with the specified record_id.
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_fetch_record_with_service_id_not_found() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/record/not_in_database?service_id={}",
+                    TEST_SERVICE_ID
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id}/property/{property_name} responds with an OK response
+    ///     and the infomation on the Property requested
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_fetch_record_property_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_database();
+
+        populate_grid_schema_table(get_grid_schema_for_struct_record(None));
+        populate_record_table(get_record("record_01", None));
+        populate_tnt_property_table(
+            get_property(None),
+            get_reported_value(None),
+            get_reporter(None),
+        );
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record/record_01/property/TestProperty"),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+
+        let property_info: PropertySlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(property_info.data_type, "Struct".to_string());
+        assert_eq!(property_info.name, "TestProperty".to_string());
+        assert_eq!(property_info.record_id, "record_01".to_string());
+
         assert_eq!(
-            test_record.proposals[0].properties,
-            vec!["location".to_string()]
+            property_info.reporters,
+            vec![KEY1.to_string(), KEY2.to_string()]
         );
-        assert_eq!(test_record.proposals[0].status, "OPEN");
-        assert_eq!(test_record.proposals[0].terms, "Proposal Terms".to_string());
+
+        validate_current_value(
+            &property_info
+                .value
+                .clone()
+                .expect("Property value not returned"),
+            None,
+        );
+
+        assert_eq!(property_info.updates.len(), 2);
+
+        let first_update = &property_info.updates[0];
+
+        validate_reporter(&first_update.reporter, KEY2, None);
+
+        assert_eq!(first_update.timestamp, 3);
+
+        match &first_update.value {
+            Value::Struct(root) => {
+                assert_eq!(root.len(), 1);
+                match &root[0].value {
+                    Value::Struct(struct_values) => {
+                        assert_eq!(struct_values.len(), 5);
+                        validate_struct_value(&struct_values[0], "value_1", false);
+                        validate_location_value(
+                            &struct_values[1],
+                            LatLong {
+                                latitude: 1,
+                                longitude: 1,
+                            },
+                        );
+                        validate_number_value(&struct_values[2], 1);
+                        validate_enum_value(&struct_values[3], 1);
+                        validate_bytes_value(&struct_values[4], &vec![0x01, 0x02, 0x03, 0x04]);
+                    }
+                    _ => panic!("Expected enum type Struct found: {:?}", first_update.value),
+                }
+            }
+            _ => panic!("Expected enum type Struct found: {:?}", first_update.value),
+        }
+
+        let second_update = &property_info.updates[1];
+        validate_current_value(second_update, None);
     }
 
     ///
-    /// Verifies a GET /record?service_id=test_service responds with an Ok response
-    ///     with a list containing one record with the correct service id.
+    /// Verifies a GET /record/{record_id}/property/{property_name}?service_id=test_service responds
+    ///     with an OK response and the infomation on the Property requested.
     ///
     #[actix_rt::test]
     #[cfg(feature = "track-and-trace")]
-    async fn test_list_records_with_service_id() {
+    async fn test_fetch_record_property_with_service_id_ok() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
 
         clear_database();
 
-        populate_agent_table(get_agents_with_roles(Some(TEST_SERVICE_ID.to_string())));
-        populate_associated_agent_table(get_associated_agents(Some(TEST_SERVICE_ID.to_string())));
-        populate_proposal_table(get_proposal(Some(TEST_SERVICE_ID.to_string())));
-        populate_grid_schema_table(get_grid_schema_for_record(None));
-        populate_record_table(get_record("TestRecord", Some(TEST_SERVICE_ID.to_string())));
+        populate_grid_schema_table(get_grid_schema_for_struct_record(Some(
+            TEST_SERVICE_ID.to_string(),
+        )));
+        populate_record_table(get_record("record_01", Some(TEST_SERVICE_ID.to_string())));
         populate_tnt_property_table(
-            get_property_for_record(Some(TEST_SERVICE_ID.to_string())),
-            get_reported_value_for_property_record(Some(TEST_SERVICE_ID.to_string())),
-            get_reporter_for_property_record(Some(TEST_SERVICE_ID.to_string())),
+            get_property(Some(TEST_SERVICE_ID.to_string())),
+            get_reported_value(Some(TEST_SERVICE_ID.to_string())),
+            get_reporter(Some(TEST_SERVICE_ID.to_string())),
         );
+
         let mut response = srv
             .request(
                 http::Method::GET,
-                srv.url(&format!("/record?service_id={}", TEST_SERVICE_ID)),
+                srv.url(&format!(
+                    "/record/record_01/property/TestProperty?service_id={}",
+                    TEST_SERVICE_ID
+                )),
             )
             .send()
             .await
             .unwrap();
+
         assert!(response.status().is_success());
-        let body: RecordListSlice =
+
+        let property_info: PropertySlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(body.data.len(), 1);
-        let test_record = body.data.first().unwrap();
-        assert_eq!(test_record.record_id, "TestRecord".to_string());
-        assert_eq!(test_record.schema, "TestGridSchema".to_string());
-        assert_eq!(test_record.owner, KEY1.to_string());
-        assert_eq!(test_record.custodian, KEY2.to_string());
-        assert_eq!(test_record.service_id, Some(TEST_SERVICE_ID.to_string()));
-        assert_eq!(test_record.properties.len(), 2);
-        assert_eq!(test_record.properties[0].name, "TestProperty1");
-        assert_eq!(test_record.properties[0].record_id, "TestRecord");
-        assert_eq!(test_record.properties[0].data_type, "String");
-        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
-        assert_eq!(test_record.properties[0].updates.len(), 1);
-        assert_eq!(
-            test_record.properties[0].updates[0].value,
-            Value::String("value_1".to_string())
-        );
-        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
-        assert_eq!(
-            test_record.properties[0].service_id,
-            Some(TEST_SERVICE_ID.to_string())
-        );
-        validate_reporter(
-            &test_record.properties[0].updates[0].reporter,
-            KEY1,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
-        assert_eq!(
-            test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .value,
-            Value::String("value_1".to_string())
-        );
-        assert_eq!(
-            test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .timestamp,
-            5
-        );
-        assert_eq!(
-            test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .service_id,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
-        validate_reporter(
-            &test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .reporter,
-            KEY1,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
 
-        assert_eq!(test_record.properties[1].name, "TestProperty2");
-        assert_eq!(test_record.properties[1].record_id, "TestRecord");
-        assert_eq!(test_record.properties[1].data_type, "Boolean");
-        assert_eq!(test_record.properties[1].reporters, vec![KEY2.to_string()]);
-        assert_eq!(test_record.properties[1].updates.len(), 1);
-        assert_eq!(
-            test_record.properties[1].updates[0].value,
-            Value::Bool(true)
-        );
-        assert_eq!(test_record.properties[1].updates[0].timestamp, 5);
-        assert_eq!(
-            test_record.properties[1].updates[0].service_id,
-            Some(TEST_SERVICE_ID.to_string())
-        );
-        validate_reporter(
-            &test_record.properties[1].updates[0].reporter,
-            KEY2,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
-        assert_eq!(
-            test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .value,
-            Value::Bool(true)
-        );
+        assert_eq!(property_info.data_type, "Struct".to_string());
+        assert_eq!(property_info.name, "TestProperty".to_string());
+        assert_eq!(property_info.record_id, "record_01".to_string());
+        assert_eq!(property_info.service_id, Some(TEST_SERVICE_ID.to_string()));
+
         assert_eq!(
-            test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .timestamp,
-            5
+            property_info.reporters,
+            vec![KEY1.to_string(), KEY2.to_string()]
         );
-        assert_eq!(
-            test_record.properties[1]
+
+        validate_current_value(
+            &property_info
                 .value
                 .clone()
-                .expect("Property value not returned")
-                .service_id,
+                .expect("Property value not returned"),
             Some(TEST_SERVICE_ID.to_string()),
         );
+
+        assert_eq!(property_info.updates.len(), 2);
+
+        let first_update = &property_info.updates[0];
+
         validate_reporter(
-            &test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .reporter,
+            &first_update.reporter,
             KEY2,
             Some(TEST_SERVICE_ID.to_string()),
         );
 
-        assert_eq!(test_record.r#final, false);
+        assert_eq!(first_update.timestamp, 3);
 
-        assert_eq!(test_record.owner_updates[0].agent_id, KEY1.to_string());
-        assert_eq!(test_record.owner_updates[0].timestamp, 1);
+        match &first_update.value {
+            Value::Struct(root) => {
+                assert_eq!(root.len(), 1);
+                match &root[0].value {
+                    Value::Struct(struct_values) => {
+                        assert_eq!(struct_values.len(), 5);
+                        validate_struct_value(&struct_values[0], "value_1", false);
+                        validate_location_value(
+                            &struct_values[1],
+                            LatLong {
+                                latitude: 1,
+                                longitude: 1,
+                            },
+                        );
+                        validate_number_value(&struct_values[2], 1);
+                        validate_enum_value(&struct_values[3], 1);
+                        validate_bytes_value(&struct_values[4], &vec![0x01, 0x02, 0x03, 0x04]);
+                    }
+                    _ => panic!("Expected enum type Struct found: {:?}", first_update.value),
+                }
+            }
+            _ => panic!("Expected enum type Struct found: {:?}", first_update.value),
+        }
 
-        assert_eq!(test_record.custodian_updates[0].agent_id, KEY2.to_string());
-        assert_eq!(test_record.custodian_updates[0].timestamp, 1);
+        let second_update = &property_info.updates[1];
+        validate_current_value(second_update, Some(TEST_SERVICE_ID.to_string()));
+    }
 
-        assert_eq!(test_record.proposals[0].timestamp, 1);
-        assert_eq!(test_record.proposals[0].role, "OWNER");
-        assert_eq!(
-            test_record.proposals[0].properties,
-            vec!["location".to_string()]
-        );
-        assert_eq!(test_record.proposals[0].status, "OPEN");
-        assert_eq!(test_record.proposals[0].terms, "Proposal Terms".to_string());
+    ///
+    /// Verifies a GET /record/{record_id}/reporters responds with an OK response
+    ///     and the authorization history of each reporter, per property
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_fetch_record_reporters_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+
+        populate_record_table(get_record("TestRecord", None));
+        populate_reporter_table(get_reporter_for_property_record(None));
+        populate_reporter_table(vec![get_reporter_for_property_1(2, false)]);
+        populate_reporter_table(vec![get_reporter_for_property_1(3, false)]);
+        populate_reporter_table(vec![get_reporter_for_property_1(4, true)]);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/record/TestRecord/reporters"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: RecordReportersSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(body.record_id, "TestRecord".to_string());
+        assert_eq!(body.properties.len(), 2);
+
+        let property_1 = &body.properties[0];
+        assert_eq!(property_1.name, "TestProperty1".to_string());
+        assert_eq!(property_1.reporters.len(), 1);
+        let reporter = &property_1.reporters[0];
+        assert_eq!(reporter.public_key, KEY1.to_string());
+        assert_eq!(reporter.reporter_index, 0);
+        assert!(reporter.authorized);
         assert_eq!(
-            test_record.proposals[0].service_id,
-            Some(TEST_SERVICE_ID.to_string())
+            reporter.history,
+            vec![
+                ReporterAuthorizationSlice {
+                    authorized: true,
+                    start_commit_num: 0,
+                    end_commit_num: Some(2),
+                },
+                ReporterAuthorizationSlice {
+                    authorized: false,
+                    start_commit_num: 2,
+                    end_commit_num: Some(4),
+                },
+                ReporterAuthorizationSlice {
+                    authorized: true,
+                    start_commit_num: 4,
+                    end_commit_num: None,
+                },
+            ]
         );
+
+        let property_2 = &body.properties[1];
+        assert_eq!(property_2.name, "TestProperty2".to_string());
+        assert_eq!(property_2.reporters[0].public_key, KEY2.to_string());
+        assert_eq!(property_2.reporters[0].history.len(), 1);
     }
 
     ///
-    /// Verifies a GET /record responds with an Ok response
-    /// with a list containing one record, when there's two records for the same
-    /// record_id. The rest-api should return a list with a single record with the
-    /// record that contains the most recent information for that record
-    /// (end_commit_num == MAX_COMMIT_NUM)
+    /// Verifies a GET /record/{record_id}/reporters responds with a Not Found error
+    ///     when there is no Record with the specified record_id.
     ///
     #[actix_rt::test]
     #[cfg(feature = "track-and-trace")]
-    async fn test_list_records_updated() {
+    async fn test_fetch_record_reporters_not_found() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record/not_in_database/reporters"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
 
+    ///
+    /// Verifies a GET /record/{record_id}/contents responds with an OK response
+    ///     and the records aggregated into the record, each with its own contents
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_fetch_record_contents_ok() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
         clear_database();
-        // Adds two instances of record with the same org_id to the test database
-        populate_record_table(get_updated_record());
 
-        // Making another request to the database
+        let contained = |record_id: &str, parent: &str| Record {
+            parent: Some(parent.to_string()),
+            ..get_record(record_id, None).remove(0)
+        };
+        populate_record_table(get_record("Pallet", None));
+        populate_record_table(vec![
+            contained("Case", "Pallet"),
+            contained("Item", "Case"),
+            contained("OtherItem", "OtherCase"),
+        ]);
+
         let mut response = srv
-            .request(http::Method::GET, srv.url("/record"))
+            .request(http::Method::GET, srv.url("/record/Pallet/contents"))
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let body: RecordListSlice =
+        let body: RecordContentsSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(body.data.len(), 1);
-        let test_record = body.data.first().unwrap();
-        assert_eq!(test_record.record_id, "TestRecord".to_string());
-        assert_eq!(test_record.schema, "TestGridSchema".to_string());
-        assert_eq!(test_record.r#final, true);
+
+        assert_eq!(body.record_id, "Pallet".to_string());
+        assert_eq!(body.contents.len(), 1);
+        let case = &body.contents[0];
+        assert_eq!(case.record_id, "Case".to_string());
+        assert_eq!(case.custodian, KEY2.to_string());
+        assert_eq!(case.contents.len(), 1);
+        assert_eq!(case.contents[0].record_id, "Item".to_string());
+        assert!(case.contents[0].contents.is_empty());
     }
 
     ///
-    /// Verifies a GET /record responds with an Ok response
-    /// with a list containing two records, when there's two records with differing
-    /// record_ids, one of which has been updated.
+    /// Verifies a GET /record/{record_id}/contents responds with a Not Found error
+    ///     when there is no Record with the specified record_id.
     ///
     #[actix_rt::test]
     #[cfg(feature = "track-and-trace")]
-    async fn test_list_records_multiple() {
+    async fn test_fetch_record_contents_not_found() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
-
         clear_database();
-        // Adds two instances of record with the same org_id to the test database
-        populate_record_table(get_multiple_records());
-        populate_tnt_property_table(
-            get_property_for_record(None),
-            get_reported_value_for_property_record(None),
-            get_reporter_for_property_record(None),
-        );
-
-        // Making another request to the database
-        let mut response = srv
-            .request(http::Method::GET, srv.url("/record"))
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record/not_in_database/contents"),
+            )
             .send()
             .await
             .unwrap();
-        assert!(response.status().is_success());
-        let body: RecordListSlice =
-            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-
-        assert_eq!(body.data.len(), 2);
-        let record_1 = &body.data[0];
-        assert_eq!(record_1.properties.len(), 2);
-
-        let record_2 = &body.data[1];
-        assert!(record_2.properties.is_empty());
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
     }
 
     ///
-    /// Verifies a GET /record/{record_id} responds with an OK response
-    ///     and the Record with the specified record ID.
+    /// Verifies a GET /record/{record_id}/epcis responds with an OK response
+    ///     and an EPCIS document describing the history of the record
     ///
     #[actix_rt::test]
     #[cfg(feature = "track-and-trace")]
-    async fn test_fetch_record_ok() {
+    async fn test_fetch_record_epcis_ok() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
         clear_database();
 
-        populate_agent_table(get_agents_with_roles(None));
-        populate_associated_agent_table(get_associated_agents(None));
-        populate_proposal_table(get_proposal(None));
-        populate_record_table(get_record("TestRecord", None));
         populate_grid_schema_table(get_grid_schema_for_record(None));
+        populate_record_table(get_record_with_product_and_location());
+        populate_associated_agent_table(get_associated_agents_updated());
         populate_tnt_property_table(
             get_property_for_record(None),
             get_reported_value_for_property_record(None),
             get_reporter_for_property_record(None),
         );
+
         let mut response = srv
-            .request(
-                http::Method::GET,
-                srv.url(&format!("/record/{}", "TestRecord".to_string())),
-            )
+            .request(http::Method::GET, srv.url("/record/TestRecord/epcis"))
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let test_record: RecordSlice =
+        let document: EpcisDocument =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(test_record.record_id, "TestRecord".to_string());
-        assert_eq!(test_record.schema, "TestGridSchema".to_string());
-        assert_eq!(test_record.owner, KEY1.to_string());
-        assert_eq!(test_record.custodian, KEY2.to_string());
 
-        assert_eq!(test_record.properties.len(), 2);
-        assert_eq!(test_record.properties[0].name, "TestProperty1");
-        assert_eq!(test_record.properties[0].record_id, "TestRecord");
-        assert_eq!(test_record.properties[0].data_type, "String");
-        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
-        assert_eq!(test_record.properties[0].updates.len(), 1);
-        assert_eq!(
-            test_record.properties[0].updates[0].value,
-            Value::String("value_1".to_string())
-        );
-        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
-        validate_reporter(&test_record.properties[0].updates[0].reporter, KEY1, None);
-        assert_eq!(
-            test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .value,
-            Value::String("value_1".to_string())
-        );
-        assert_eq!(
-            test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .timestamp,
-            5
-        );
-        validate_reporter(
-            &test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .reporter,
-            KEY1,
-            None,
-        );
+        assert_eq!(document.document_type, "EPCISDocument".to_string());
+        assert_eq!(document.schema_version, "2.0".to_string());
 
-        assert_eq!(test_record.properties[1].name, "TestProperty2");
-        assert_eq!(test_record.properties[1].record_id, "TestRecord");
-        assert_eq!(test_record.properties[1].data_type, "Boolean");
-        assert_eq!(test_record.properties[1].reporters, vec![KEY2.to_string()]);
-        assert_eq!(test_record.properties[1].updates.len(), 1);
+        let events = document.epcis_body.event_list;
+        assert_eq!(events.len(), 5);
+
+        let epc = format!("https://id.gs1.org/01/{}/21/TestRecord", TEST_GTIN);
+        let location = Some(EpcisLocation {
+            id: format!("https://id.gs1.org/414/{}", TEST_GLN),
+        });
+        for event in &events {
+            assert_eq!(event.epc_list, vec![epc.clone()]);
+            assert_eq!(event.biz_location, location);
+            assert_eq!(event.record_id, "TestRecord".to_string());
+        }
+
+        assert_eq!(events[0].event_type, "ObjectEvent".to_string());
+        assert_eq!(events[0].action, "ADD".to_string());
+        assert_eq!(events[0].event_time, "1970-01-01T00:00:01Z".to_string());
+
+        let owner_transfer = &events[1];
+        assert_eq!(owner_transfer.event_type, "TransactionEvent".to_string());
         assert_eq!(
-            test_record.properties[1].updates[0].value,
-            Value::Bool(true)
+            owner_transfer.event_time,
+            "1970-01-01T00:00:02Z".to_string()
         );
-        assert_eq!(test_record.properties[1].updates[0].timestamp, 5);
-        validate_reporter(&test_record.properties[1].updates[0].reporter, KEY2, None);
         assert_eq!(
-            test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .value,
-            Value::Bool(true)
+            owner_transfer.source_list,
+            vec![EpcisSource {
+                source_type: "owning_party".to_string(),
+                source: format!("urn:grid:agent:{}", KEY1),
+            }]
         );
         assert_eq!(
-            test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .timestamp,
-            5
-        );
-        validate_reporter(
-            &test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .reporter,
-            KEY2,
-            None,
+            owner_transfer.destination_list,
+            vec![EpcisDestination {
+                destination_type: "owning_party".to_string(),
+                destination: format!("urn:grid:agent:{}", KEY2),
+            }]
         );
 
-        assert_eq!(test_record.r#final, false);
-
-        assert_eq!(test_record.owner_updates[0].agent_id, KEY1.to_string());
-        assert_eq!(test_record.owner_updates[0].timestamp, 1);
-
-        assert_eq!(test_record.custodian_updates[0].agent_id, KEY2.to_string());
-        assert_eq!(test_record.custodian_updates[0].timestamp, 1);
-
-        assert_eq!(test_record.proposals[0].timestamp, 1);
-        assert_eq!(test_record.proposals[0].role, "OWNER");
+        let custody_transfer = &events[2];
+        assert_eq!(custody_transfer.event_type, "TransactionEvent".to_string());
         assert_eq!(
-            test_record.proposals[0].properties,
-            vec!["location".to_string()]
+            custody_transfer.source_list[0].source_type,
+            "possessing_party".to_string()
         );
-        assert_eq!(test_record.proposals[0].status, "OPEN");
-        assert_eq!(test_record.proposals[0].terms, "Proposal Terms".to_string());
+
+        let observations: Vec<&EpcisEvent> = events[3..]
+            .iter()
+            .filter(|event| event.action == "OBSERVE")
+            .collect();
+        assert_eq!(observations.len(), 2);
+        let reports: Vec<EpcisSensorReport> = observations
+            .iter()
+            .flat_map(|event| event.sensor_element_list[0].sensor_report.clone())
+            .collect();
+        assert!(reports
+            .iter()
+            .any(|report| report.report_type == "grid:TestProperty1"
+                && report.string_value == Some("value_1".to_string())));
+        assert!(reports
+            .iter()
+            .any(|report| report.report_type == "grid:TestProperty2"
+                && report.boolean_value == Some(true)));
     }
 
     ///
-    /// Verifies a GET /record/{record_id}?service_id=test_service responds with an OK response
-    ///     and the Record with the specified record ID.
+    /// Verifies a GET /record/{record_id}/epcis for a finalized record ends with a DELETE event
     ///
     #[actix_rt::test]
     #[cfg(feature = "track-and-trace")]
-    async fn test_fetch_record_with_service_id_ok() {
+    async fn test_fetch_record_epcis_finalized() {
         run_migrations(&DATABASE_URL);
-        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
-
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
         clear_database();
 
-        populate_agent_table(get_agents_with_roles(Some(TEST_SERVICE_ID.to_string())));
-        populate_associated_agent_table(get_associated_agents(Some(TEST_SERVICE_ID.to_string())));
-        populate_proposal_table(get_proposal(Some(TEST_SERVICE_ID.to_string())));
-        populate_record_table(get_record("TestRecord", Some(TEST_SERVICE_ID.to_string())));
-        populate_grid_schema_table(get_grid_schema_for_record(None));
-        populate_tnt_property_table(
-            get_property_for_record(Some(TEST_SERVICE_ID.to_string())),
-            get_reported_value_for_property_record(Some(TEST_SERVICE_ID.to_string())),
-            get_reporter_for_property_record(Some(TEST_SERVICE_ID.to_string())),
-        );
+        let mut records = get_record("TestRecord", None);
+        records[0].final_ = true;
+        populate_record_table(records);
+        populate_associated_agent_table(get_associated_agents(None));
 
         let mut response = srv
-            .request(
-                http::Method::GET,
-                srv.url(&format!(
-                    "/record/{}?service_id={}",
-                    "TestRecord", TEST_SERVICE_ID
-                )),
-            )
+            .request(http::Method::GET, srv.url("/record/TestRecord/epcis"))
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let test_record: RecordSlice =
+        let document: EpcisDocument =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(test_record.record_id, "TestRecord".to_string());
-        assert_eq!(test_record.schema, "TestGridSchema".to_string());
-        assert_eq!(test_record.owner, KEY1.to_string());
-        assert_eq!(test_record.custodian, KEY2.to_string());
-        assert_eq!(test_record.service_id, Some(TEST_SERVICE_ID.to_string()));
-
-        assert_eq!(test_record.properties.len(), 2);
-        assert_eq!(test_record.properties[0].name, "TestProperty1");
-        assert_eq!(test_record.properties[0].record_id, "TestRecord");
-        assert_eq!(test_record.properties[0].data_type, "String");
-        assert_eq!(
-            test_record.properties[0].service_id,
-            Some(TEST_SERVICE_ID.to_string())
-        );
-        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
-        assert_eq!(test_record.properties[0].updates.len(), 1);
-        assert_eq!(
-            test_record.properties[0].updates[0].value,
-            Value::String("value_1".to_string())
-        );
-        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
-        validate_reporter(
-            &test_record.properties[0].updates[0].reporter,
-            KEY1,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
-        assert_eq!(
-            test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .value,
-            Value::String("value_1".to_string())
-        );
-        assert_eq!(
-            test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .timestamp,
-            5
-        );
-        validate_reporter(
-            &test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .reporter,
-            KEY1,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
-
-        assert_eq!(test_record.properties[1].name, "TestProperty2");
-        assert_eq!(test_record.properties[1].record_id, "TestRecord");
-        assert_eq!(test_record.properties[1].data_type, "Boolean");
-        assert_eq!(test_record.properties[1].reporters, vec![KEY2.to_string()]);
-        assert_eq!(test_record.properties[1].updates.len(), 1);
-        assert_eq!(
-            test_record.properties[1].updates[0].value,
-            Value::Bool(true)
-        );
-        assert_eq!(test_record.properties[1].updates[0].timestamp, 5);
-        validate_reporter(
-            &test_record.properties[1].updates[0].reporter,
-            KEY2,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
-        assert_eq!(
-            test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .value,
-            Value::Bool(true)
-        );
-        assert_eq!(
-            test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .timestamp,
-            5
-        );
-        assert_eq!(
-            test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .service_id,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
-        validate_reporter(
-            &test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .reporter,
-            KEY2,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
 
-        assert_eq!(test_record.r#final, false);
-
-        assert_eq!(test_record.owner_updates[0].agent_id, KEY1.to_string());
-        assert_eq!(test_record.owner_updates[0].timestamp, 1);
-
-        assert_eq!(test_record.custodian_updates[0].agent_id, KEY2.to_string());
-        assert_eq!(test_record.custodian_updates[0].timestamp, 1);
-
-        assert_eq!(test_record.proposals[0].timestamp, 1);
-        assert_eq!(test_record.proposals[0].role, "OWNER");
-        assert_eq!(
-            test_record.proposals[0].properties,
-            vec!["location".to_string()]
-        );
-        assert_eq!(test_record.proposals[0].status, "OPEN");
-        assert_eq!(test_record.proposals[0].terms, "Proposal Terms".to_string());
+        let events = document.epcis_body.event_list;
+        assert_eq!(events.len(), 2);
         assert_eq!(
-            test_record.proposals[0].service_id,
-            Some(TEST_SERVICE_ID.to_string())
+            events[0].epc_list,
+            vec!["urn:grid:record:TestRecord".to_string()]
         );
+        assert_eq!(events[1].action, "DELETE".to_string());
+        assert_eq!(events[1].disposition, Some("inactive".to_string()));
+        assert_eq!(events[1].event_time, events[0].event_time);
     }
 
     ///
-    /// Verifies a GET /record/{record_id} responds with an OK response
-    ///     and the Record with the specified record ID after the Record's
-    ///     owners, custodians, and proposals have been updated.
+    /// Verifies a GET /record/{record_id}/epcis responds with a Not Found error
+    ///     when there is no Record with the specified record_id.
     ///
     #[actix_rt::test]
     #[cfg(feature = "track-and-trace")]
-    async fn test_fetch_record_updated_ok() {
+    async fn test_fetch_record_epcis_not_found() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        let response = srv
+            .request(http::Method::GET, srv.url("/record/not_in_database/epcis"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
 
+    ///
+    /// Verifies a GET /epcis/events responds with an OK response and only the events
+    ///     matching the requested GTIN and time range
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_list_epcis_events() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
         clear_database();
+        populate_commit_table(
+            (0..2)
+                .map(|commit_num| Commit {
+                    commit_id: format!("commit_{}", commit_num),
+                    commit_num,
+                    service_id: None,
+                })
+                .collect(),
+        );
 
         populate_grid_schema_table(get_grid_schema_for_record(None));
-        populate_record_table(get_updated_record());
+        populate_record_table(get_record_with_product_and_location());
+        populate_associated_agent_table(get_associated_agents_updated());
         populate_tnt_property_table(
             get_property_for_record(None),
             get_reported_value_for_property_record(None),
             get_reporter_for_property_record(None),
         );
-        populate_associated_agent_table(get_associated_agents_updated());
-        populate_proposal_table(get_updated_proposal());
+
         let mut response = srv
             .request(
                 http::Method::GET,
-                srv.url(&format!("/record/{}", "TestRecord".to_string())),
+                srv.url(&format!("/epcis/events?gtin={}&from=2&to=5", TEST_GTIN)),
             )
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let test_record: RecordSlice =
+        let document: EpcisQueryDocument =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
 
-        assert_eq!(test_record.record_id, "TestRecord".to_string());
-        assert_eq!(test_record.schema, "TestGridSchema".to_string());
-        assert_eq!(test_record.owner, KEY2.to_string());
-        assert_eq!(test_record.custodian, KEY1.to_string());
+        assert_eq!(document.document_type, "EPCISQueryDocument".to_string());
+        let events = document.epcis_body.query_results.results_body.event_list;
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|event| event.event_type == "TransactionEvent"));
 
-        assert_eq!(test_record.properties.len(), 2);
-        assert_eq!(test_record.properties[0].name, "TestProperty1");
-        assert_eq!(test_record.properties[0].record_id, "TestRecord");
-        assert_eq!(test_record.properties[0].data_type, "String");
-        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
-        assert_eq!(test_record.properties[0].updates.len(), 1);
-        assert_eq!(
-            test_record.properties[0].updates[0].value,
-            Value::String("value_1".to_string())
-        );
-        assert_eq!(test_record.properties[0].updates[0].timestamp, 5);
-        validate_reporter(&test_record.properties[0].updates[0].reporter, KEY1, None);
-        assert_eq!(
-            test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .value,
-            Value::String("value_1".to_string())
-        );
-        assert_eq!(
-            test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .timestamp,
-            5
-        );
-        validate_reporter(
-            &test_record.properties[0]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .reporter,
-            KEY1,
-            None,
-        );
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/epcis/events?gtin=00000000000000"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let document: EpcisQueryDocument =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(document
+            .epcis_body
+            .query_results
+            .results_body
+            .event_list
+            .is_empty());
 
-        assert_eq!(test_record.properties[1].name, "TestProperty2");
-        assert_eq!(test_record.properties[1].record_id, "TestRecord");
-        assert_eq!(test_record.properties[1].data_type, "Boolean");
-        assert_eq!(test_record.properties[1].reporters, vec![KEY2.to_string()]);
-        assert_eq!(test_record.properties[1].updates.len(), 1);
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/epcis/events?from=5"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let document: EpcisQueryDocument =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
         assert_eq!(
-            test_record.properties[1].updates[0].value,
-            Value::Bool(true)
+            document
+                .epcis_body
+                .query_results
+                .results_body
+                .event_list
+                .len(),
+            2
         );
-        assert_eq!(test_record.properties[1].updates[0].timestamp, 5);
-        validate_reporter(&test_record.properties[1].updates[0].reporter, KEY2, None);
-        assert_eq!(
-            test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .value,
-            Value::Bool(true)
+    }
+
+    ///
+    /// Verifies a GET /epcis/events reads the events of a page of records at a time, linking to
+    ///     the next page with the query params of the request, and leaves out events timestamped
+    ///     out of the range of dates that can be represented
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_list_epcis_events_paging() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        populate_commit_table(vec![Commit {
+            commit_id: "commit_0".to_string(),
+            commit_num: 0,
+            service_id: None,
+        }]);
+
+        let mut records = get_record("TestRecord", None);
+        records.append(&mut get_record("TestRecord2", None));
+        populate_record_table(records);
+        populate_associated_agent_table(
+            [
+                ("TestRecord", 1),
+                ("TestRecord2", 2),
+                ("TestRecord2", i64::MAX),
+            ]
+            .iter()
+            .map(|(record_id, timestamp)| AssociatedAgent {
+                id: None,
+                start_commit_num: 0,
+                end_commit_num: i64::MAX,
+                agent_id: format!("{}{}", KEY1, timestamp),
+                timestamp: *timestamp,
+                record_id: record_id.to_string(),
+                role: "OWNER".to_string(),
+                service_id: None,
+            })
+            .collect(),
         );
-        assert_eq!(
-            test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .timestamp,
-            5
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/epcis/events?limit=1&from=1"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let next = response
+            .headers()
+            .get("Link")
+            .expect("The first page should link to the next page")
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(next.starts_with("</epcis/events?cursor="));
+        assert!(next.ends_with("&limit=1&from=1>; rel=\"next\""));
+        let document: EpcisQueryDocument =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        let events = document.epcis_body.query_results.results_body.event_list;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].record_id, "TestRecord".to_string());
+
+        let next = next.trim_start_matches('<').split('>').next().unwrap();
+        let mut response = srv
+            .request(http::Method::GET, srv.url(next))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert!(response.headers().get("Link").is_none());
+        let document: EpcisQueryDocument =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        let events = document.epcis_body.query_results.results_body.event_list;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].record_id, "TestRecord2".to_string());
+        assert_eq!(events[0].event_time, "1970-01-01T00:00:02Z".to_string());
+    }
+
+    ///
+    /// Verifies the link to the next page of a GET /epcis/events percent-encodes the query
+    ///     params of the request, so that a GTIN with reserved characters reads the same records
+    ///
+    #[actix_rt::test]
+    #[cfg(feature = "track-and-trace")]
+    async fn test_list_epcis_events_paging_encodes_params() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
+        populate_commit_table(vec![Commit {
+            commit_id: "commit_0".to_string(),
+            commit_num: 0,
+            service_id: None,
+        }]);
+
+        let gtin = "gtin a&b=c";
+        let mut records = get_record("TestRecord", None);
+        records.append(&mut get_record("TestRecord2", None));
+        records.append(&mut get_record("OtherRecord", None));
+        populate_record_table(
+            records
+                .into_iter()
+                .map(|record| Record {
+                    product_id: if record.record_id.starts_with("Test") {
+                        Some(gtin.to_string())
+                    } else {
+                        Some("gtin a".to_string())
+                    },
+                    ..record
+                })
+                .collect(),
         );
-        validate_reporter(
-            &test_record.properties[1]
-                .value
-                .clone()
-                .expect("Property value not returned")
-                .reporter,
-            KEY2,
-            None,
+        populate_associated_agent_table(
+            ["TestRecord", "TestRecord2", "OtherRecord"]
+                .iter()
+                .map(|record_id| AssociatedAgent {
+                    id: None,
+                    start_commit_num: 0,
+                    end_commit_num: i64::MAX,
+                    agent_id: KEY1.to_string(),
+                    timestamp: 1,
+                    record_id: record_id.to_string(),
+                    role: "OWNER".to_string(),
+                    service_id: None,
+                })
+                .collect(),
         );
 
-        assert_eq!(test_record.r#final, true);
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/epcis/events?limit=1&gtin=gtin%20a%26b%3Dc"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let next = response
+            .headers()
+            .get("Link")
+            .expect("The first page should link to the next page")
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(next.ends_with("&limit=1&gtin=gtin+a%26b%3Dc>; rel=\"next\""));
+        let document: EpcisQueryDocument =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        let events = document.epcis_body.query_results.results_body.event_list;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].record_id, "TestRecord".to_string());
 
-        assert_eq!(test_record.owner_updates[0].agent_id, KEY1.to_string());
-        assert_eq!(test_record.owner_updates[0].timestamp, 1);
+        let next = next.trim_start_matches('<').split('>').next().unwrap();
+        let mut response = srv
+            .request(http::Method::GET, srv.url(next))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert!(response.headers().get("Link").is_none());
+        let document: EpcisQueryDocument =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        let events = document.epcis_body.query_results.results_body.event_list;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].record_id, "TestRecord2".to_string());
+    }
 
-        assert_eq!(test_record.custodian_updates[0].agent_id, KEY2.to_string());
-        assert_eq!(test_record.custodian_updates[0].timestamp, 1);
+    ///
+    /// Verifies a GET /purchase_order responds with an OK response with the list of purchase
+    ///     orders, and that a GET /purchase_order/{uid} responds with the purchase order
+    ///
+    #[cfg(feature = "purchase-order")]
+    #[actix_rt::test]
+    async fn test_list_and_fetch_purchase_orders() {
+        run_migrations(&DATABASE_URL);
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_database();
 
-        assert_eq!(test_record.owner_updates[1].agent_id, KEY2.to_string());
-        assert_eq!(test_record.owner_updates[1].timestamp, 2);
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/purchase_order"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let empty_body: PurchaseOrderListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(empty_body.data.is_empty());
 
-        assert_eq!(test_record.custodian_updates[1].agent_id, KEY1.to_string());
-        assert_eq!(test_record.custodian_updates[1].timestamp, 2);
+        populate_purchase_order_table(get_purchase_order(None));
 
-        assert_eq!(test_record.proposals[0].status, "CANCELED");
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/purchase_order"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: PurchaseOrderListSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.data.len(), 1);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/purchase_order/PO-00000001"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let purchase_order: PurchaseOrderSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(purchase_order.workflow_status, "issued".to_string());
+        assert_eq!(purchase_order.buyer_org_id, KEY2.to_string());
+        assert_eq!(purchase_order.versions.len(), 1);
+        assert_eq!(purchase_order.versions[0].revisions.len(), 2);
     }
 
     ///
-    /// Verifies a GET /record/{record_id} responds with a Not Found error
-    ///     when there is no Record with the specified record_id.
+    /// Verifies the version and revision routes of a purchase order respond with the requested
+    ///     version or revision
     ///
+    #[cfg(feature = "purchase-order")]
     #[actix_rt::test]
-    #[cfg(feature = "track-and-trace")]
-    async fn test_fetch_record_not_found() {
+    async fn test_fetch_purchase_order_versions_and_revisions() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
         clear_database();
+        populate_purchase_order_table(get_purchase_order(None));
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/purchase_order/PO-00000001/version"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let versions: Vec<PurchaseOrderVersionSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(versions.len(), 1);
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/purchase_order/PO-00000001/version/1"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let version: PurchaseOrderVersionSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(version.current_revision_id, 2);
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/purchase_order/PO-00000001/version/1/revision"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let revisions: Vec<PurchaseOrderRevisionSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(revisions.len(), 2);
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/purchase_order/PO-00000001/version/1/revision/2"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let revision: PurchaseOrderRevisionSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(revision.submitter, KEY1.to_string());
+
         let response = srv
-            .request(http::Method::GET, srv.url("/record/not_in_database"))
+            .request(
+                http::Method::GET,
+                srv.url("/purchase_order/PO-00000001/version/2"),
+            )
             .send()
             .await
             .unwrap();
@@ -2514,22 +4798,21 @@ mod test {
     }
 
     ///
-    /// Verifies a GET /record/{record_id}?service_id=test_service responds with a Not Found error
-    ///     when there is no Record with the specified record_id.
+    /// Verifies a GET /purchase_order/{uid}?service_id=test_service responds with a Not Found
+    ///     error when the purchase order only exists without a service id
     ///
+    #[cfg(feature = "purchase-order")]
     #[actix_rt::test]
-    #[cfg(feature = "track-and-trace")]
-    async fn test_fetch_record_with_service_id_not_found() {
+    async fn test_fetch_purchase_order_with_service_id_not_found() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
-
         clear_database();
-
+        populate_purchase_order_table(get_purchase_order(None));
         let response = srv
             .request(
                 http::Method::GET,
                 srv.url(&format!(
-                    "/record/not_in_database?service_id={}",
+                    "/purchase_order/PO-00000001?service_id={}",
                     TEST_SERVICE_ID
                 )),
             )
@@ -2540,187 +4823,125 @@ mod test {
     }
 
     ///
-    /// Verifies a GET /record/{record_id}/property/{property_name} responds with an OK response
-    ///     and the infomation on the Property requested
+    /// Verifies a GET /purchase_order/{uid}/actions responds with the transitions and
+    ///     permissions available to the agent, limited to the roles that apply to the agent's
+    ///     organization, and that the version actions follow the version's state
     ///
+    #[cfg(feature = "purchase-order")]
     #[actix_rt::test]
-    #[cfg(feature = "track-and-trace")]
-    async fn test_fetch_record_property_ok() {
+    async fn test_fetch_purchase_order_actions() {
         run_migrations(&DATABASE_URL);
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
-
         clear_database();
-
-        populate_grid_schema_table(get_grid_schema_for_struct_record(None));
-        populate_record_table(get_record("record_01", None));
-        populate_tnt_property_table(
-            get_property(None),
-            get_reported_value(None),
-            get_reporter(None),
-        );
+        populate_purchase_order_table(get_purchase_order(None));
+        populate_agent_table(get_purchase_order_agents());
 
         let mut response = srv
             .request(
                 http::Method::GET,
-                srv.url("/record/record_01/property/TestProperty"),
+                srv.url(&format!(
+                    "/purchase_order/PO-00000001/actions?agent={}",
+                    KEY1
+                )),
             )
             .send()
             .await
             .unwrap();
-
         assert!(response.status().is_success());
-
-        let property_info: PropertySlice =
+        let actions: WorkflowActionsSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(actions.subworkflow, "po".to_string());
+        assert_eq!(actions.state, "issued".to_string());
+        assert_eq!(actions.roles, vec!["po.buyer".to_string()]);
+        assert_eq!(actions.transitions, vec!["closed".to_string()]);
+        assert!(actions
+            .permissions
+            .contains(&"po.create-version".to_string()));
 
-        assert_eq!(property_info.data_type, "Struct".to_string());
-        assert_eq!(property_info.name, "TestProperty".to_string());
-        assert_eq!(property_info.record_id, "record_01".to_string());
-
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/purchase_order/PO-00000001/version/1/actions?agent={}",
+                    KEY3
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let actions: WorkflowActionsSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(actions.subworkflow, "version".to_string());
+        assert_eq!(actions.state, "proposed".to_string());
         assert_eq!(
-            property_info.reporters,
-            vec![KEY1.to_string(), KEY2.to_string()]
-        );
-
-        validate_current_value(
-            &property_info
-                .value
-                .clone()
-                .expect("Property value not returned"),
-            None,
+            actions.transitions,
+            vec!["accepted".to_string(), "rejected".to_string()]
         );
 
-        assert_eq!(property_info.updates.len(), 2);
-
-        let first_update = &property_info.updates[0];
-
-        validate_reporter(&first_update.reporter, KEY2, None);
-
-        assert_eq!(first_update.timestamp, 3);
-
-        match &first_update.value {
-            Value::Struct(root) => {
-                assert_eq!(root.len(), 1);
-                match &root[0].value {
-                    Value::Struct(struct_values) => {
-                        assert_eq!(struct_values.len(), 5);
-                        validate_struct_value(&struct_values[0], "value_1", false);
-                        validate_location_value(
-                            &struct_values[1],
-                            LatLong {
-                                latitude: 1,
-                                longitude: 1,
-                            },
-                        );
-                        validate_number_value(&struct_values[2], 1);
-                        validate_enum_value(&struct_values[3], 1);
-                        validate_bytes_value(&struct_values[4], &vec![0x01, 0x02, 0x03, 0x04]);
-                    }
-                    _ => panic!("Expected enum type Struct found: {:?}", first_update.value),
-                }
-            }
-            _ => panic!("Expected enum type Struct found: {:?}", first_update.value),
-        }
-
-        let second_update = &property_info.updates[1];
-        validate_current_value(second_update, None);
+        // The seller's agent holds the buyer role, which does not apply to the seller
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/purchase_order/PO-00000001/actions?agent={}",
+                    KEY2
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let actions: WorkflowActionsSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(actions.roles.is_empty());
+        assert!(actions.transitions.is_empty());
+        assert!(actions.permissions.is_empty());
     }
 
     ///
-    /// Verifies a GET /record/{record_id}/property/{property_name}?service_id=test_service responds
-    ///     with an OK response and the infomation on the Property requested.
+    /// Verifies a GET /workflow/{workflow}/{subworkflow}/{state}/actions responds without the
+    ///     actions of roles that only apply to the organizations on a purchase order, and with
+    ///     Not Found for an unknown state
     ///
+    #[cfg(feature = "purchase-order")]
     #[actix_rt::test]
-    #[cfg(feature = "track-and-trace")]
-    async fn test_fetch_record_property_with_service_id_ok() {
+    async fn test_fetch_workflow_actions() {
         run_migrations(&DATABASE_URL);
-        let srv = create_test_server(Backend::Splinter, ResponseType::ClientBatchStatusResponseOK);
-
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
         clear_database();
-
-        populate_grid_schema_table(get_grid_schema_for_struct_record(Some(
-            TEST_SERVICE_ID.to_string(),
-        )));
-        populate_record_table(get_record("record_01", Some(TEST_SERVICE_ID.to_string())));
-        populate_tnt_property_table(
-            get_property(Some(TEST_SERVICE_ID.to_string())),
-            get_reported_value(Some(TEST_SERVICE_ID.to_string())),
-            get_reporter(Some(TEST_SERVICE_ID.to_string())),
-        );
+        populate_agent_table(get_purchase_order_agents());
 
         let mut response = srv
             .request(
                 http::Method::GET,
                 srv.url(&format!(
-                    "/record/record_01/property/TestProperty?service_id={}",
-                    TEST_SERVICE_ID
+                    "/workflow/purchase_order/version/rejected/actions?agent={}",
+                    KEY1
                 )),
             )
             .send()
             .await
             .unwrap();
-
         assert!(response.status().is_success());
-
-        let property_info: PropertySlice =
+        let actions: WorkflowActionsSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(actions.roles.is_empty());
+        assert!(actions.transitions.is_empty());
+        assert!(actions.permissions.is_empty());
 
-        assert_eq!(property_info.data_type, "Struct".to_string());
-        assert_eq!(property_info.name, "TestProperty".to_string());
-        assert_eq!(property_info.record_id, "record_01".to_string());
-        assert_eq!(property_info.service_id, Some(TEST_SERVICE_ID.to_string()));
-
-        assert_eq!(
-            property_info.reporters,
-            vec![KEY1.to_string(), KEY2.to_string()]
-        );
-
-        validate_current_value(
-            &property_info
-                .value
-                .clone()
-                .expect("Property value not returned"),
-            Some(TEST_SERVICE_ID.to_string()),
-        );
-
-        assert_eq!(property_info.updates.len(), 2);
-
-        let first_update = &property_info.updates[0];
-
-        validate_reporter(
-            &first_update.reporter,
-            KEY2,
-            Some(TEST_SERVICE_ID.to_string()),
-        );
-
-        assert_eq!(first_update.timestamp, 3);
-
-        match &first_update.value {
-            Value::Struct(root) => {
-                assert_eq!(root.len(), 1);
-                match &root[0].value {
-                    Value::Struct(struct_values) => {
-                        assert_eq!(struct_values.len(), 5);
-                        validate_struct_value(&struct_values[0], "value_1", false);
-                        validate_location_value(
-                            &struct_values[1],
-                            LatLong {
-                                latitude: 1,
-                                longitude: 1,
-                            },
-                        );
-                        validate_number_value(&struct_values[2], 1);
-                        validate_enum_value(&struct_values[3], 1);
-                        validate_bytes_value(&struct_values[4], &vec![0x01, 0x02, 0x03, 0x04]);
-                    }
-                    _ => panic!("Expected enum type Struct found: {:?}", first_update.value),
-                }
-            }
-            _ => panic!("Expected enum type Struct found: {:?}", first_update.value),
-        }
-
-        let second_update = &property_info.updates[1];
-        validate_current_value(second_update, Some(TEST_SERVICE_ID.to_string()));
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/workflow/purchase_order/version/shipped/actions?agent={}",
+                    KEY1
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
     }
 
     #[cfg(feature = "track-and-trace")]
@@ -2940,6 +5161,23 @@ mod test {
             .expect("Failed to write batch statuses to bytes")
     }
 
+    fn get_block_list_response(status: ClientBlockListResponse_Status) -> Vec<u8> {
+        let mut header = BlockHeader::new();
+        header.set_block_num(MOCK_CHAIN_HEAD_HEIGHT);
+        let mut block = Block::new();
+        block.set_header(
+            protobuf::Message::write_to_bytes(&header).expect("Failed to write block header"),
+        );
+
+        let mut block_list_response = ClientBlockListResponse::new();
+        block_list_response.set_status(status);
+        if status == ClientBlockListResponse_Status::OK {
+            block_list_response.set_blocks(protobuf::RepeatedField::from_vec(vec![block]));
+        }
+        protobuf::Message::write_to_bytes(&block_list_response)
+            .expect("Failed to write block list to bytes")
+    }
+
     fn get_batch_list() -> Vec<u8> {
         let mut batch_list = BatchList::new();
         let mut batch = Batch::new();
@@ -2949,13 +5187,17 @@ mod test {
             .expect("Failed to write batch statuses to bytes")
     }
 
+    /// Agent metadata that reads back as an empty JSON list; an empty value can't be read back
+    /// from SQLite by the pinned diesel version
+    const EMPTY_AGENT_METADATA: &[u8] = b"[]";
+
     fn get_agent(service_id: Option<String>) -> Vec<Agent> {
         vec![Agent {
             public_key: KEY1.to_string(),
             org_id: KEY2.to_string(),
             active: true,
             roles: vec![],
-            metadata: vec![],
+            metadata: EMPTY_AGENT_METADATA.to_vec(),
             start_commit_num: 0,
             end_commit_num: i64::MAX,
             service_id,
@@ -2970,7 +5212,7 @@ mod test {
                 org_id: KEY3.to_string(),
                 active: true,
                 roles: vec!["OWNER".to_string()],
-                metadata: vec![],
+                metadata: EMPTY_AGENT_METADATA.to_vec(),
                 start_commit_num: 0,
                 end_commit_num: i64::MAX,
                 service_id: service_id.clone(),
@@ -2980,7 +5222,7 @@ mod test {
                 org_id: KEY3.to_string(),
                 active: true,
                 roles: vec!["CUSTODIAN".to_string()],
-                metadata: vec![],
+                metadata: EMPTY_AGENT_METADATA.to_vec(),
                 start_commit_num: 0,
                 end_commit_num: i64::MAX,
                 service_id,
@@ -3064,6 +5306,111 @@ mod test {
         }]
     }
 
+    /// Returns an authenticator that accepts `API_TOKEN`, along with the bearer token of an
+    /// active agent in the given organization.
+    fn get_authenticator(org_id: &str) -> (Arc<Authenticator>, String) {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let expiry = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Unable to get time")
+            .as_secs()
+            + 60 * 60;
+        let mut claims = HashMap::new();
+        claims.insert("exp".to_string(), expiry.to_string());
+        let token = JsonWebTokenBuilder::new()
+            .with_claims(claims)
+            .build(&*signer)
+            .expect("Unable to build token");
+
+        let pike_store = MemoryPikeStore::new();
+        pike_store
+            .add_agent(Agent {
+                public_key: signer
+                    .public_key()
+                    .expect("Unable to get public key")
+                    .as_hex(),
+                org_id: org_id.to_string(),
+                active: true,
+                metadata: vec![],
+                roles: vec![],
+                start_commit_num: 0,
+                end_commit_num: i64::MAX,
+                service_id: None,
+            })
+            .expect("Unable to add agent");
+
+        (
+            Arc::new(Authenticator::new(
+                vec![API_TOKEN.to_string()],
+                Arc::new(pike_store),
+            )),
+            format!("Bearer {}", token),
+        )
+    }
+
+    fn get_filterable_products() -> Vec<Product> {
+        let product = get_product(None).remove(0);
+
+        let mut other_product = product.clone();
+        other_product.product_id = "041205707821".to_string();
+        other_product.owner = "acme001".to_string();
+        other_product.properties.iter_mut().for_each(|property| {
+            property.product_id = "041205707821".to_string();
+            property.number_value = Some(20);
+        });
+
+        vec![product, other_product]
+    }
+
+    #[cfg(feature = "search")]
+    fn get_search_entries() -> Vec<SearchEntry> {
+        let mut product = SearchEntry::from(&get_product(None)[0]);
+        product.content.push_str(" Org Supplied");
+
+        vec![
+            product,
+            SearchEntry::from(&get_location(None)[0]),
+            SearchEntry::from(&get_organization(None)[0]),
+        ]
+    }
+
+    #[cfg(feature = "search")]
+    fn populate_search_index(entries: Vec<SearchEntry>) {
+        let pool = get_connection_pool();
+        let store = DieselSearchStore::new(pool.pool);
+        entries
+            .into_iter()
+            .for_each(|entry| store.index_entry(entry).unwrap());
+    }
+
+    fn get_updated_product() -> Vec<Product> {
+        let mut product = get_product(None).remove(0);
+        product.start_commit_num = 1;
+        product
+            .properties
+            .iter_mut()
+            .for_each(|property| property.start_commit_num = 1);
+
+        let mut updated_product = product.clone();
+        updated_product.owner = "phillips002".to_string();
+        updated_product.start_commit_num = 3;
+        updated_product
+            .properties
+            .iter_mut()
+            .for_each(|property| property.start_commit_num = 3);
+
+        vec![product, updated_product]
+    }
+
+    fn populate_commit_table(commits: Vec<Commit>) {
+        let pool = get_connection_pool();
+        let store = DieselCommitStore::new(pool.pool);
+        commits
+            .into_iter()
+            .for_each(|commit| store.add_commit(commit).unwrap());
+    }
+
     fn populate_location_table(locations: Vec<Location>) {
         let pool = get_connection_pool();
         let store = DieselLocationStore::new(pool.pool);
@@ -3257,10 +5604,21 @@ mod test {
             final_: false,
             owners: vec![KEY1.to_string()],
             custodians: vec![KEY2.to_string()],
+            parent: None,
+            product_id: None,
+            location_id: None,
             service_id,
         }]
     }
 
+    #[cfg(feature = "track-and-trace")]
+    fn get_record_with_product_and_location() -> Vec<Record> {
+        let mut records = get_record("TestRecord", None);
+        records[0].product_id = Some(TEST_GTIN.to_string());
+        records[0].location_id = Some(TEST_GLN.to_string());
+        records
+    }
+
     #[cfg(feature = "track-and-trace")]
     fn get_updated_record() -> Vec<Record> {
         vec![
@@ -3273,6 +5631,9 @@ mod test {
                 final_: false,
                 owners: vec![KEY1.to_string()],
                 custodians: vec![KEY2.to_string()],
+                parent: None,
+                product_id: None,
+                location_id: None,
                 service_id: None,
             },
             Record {
@@ -3284,6 +5645,9 @@ mod test {
                 final_: true,
                 owners: vec![KEY2.to_string(), KEY1.to_string()],
                 custodians: vec![KEY1.to_string(), KEY2.to_string()],
+                parent: None,
+                product_id: None,
+                location_id: None,
                 service_id: None,
             },
         ]
@@ -3301,6 +5665,9 @@ mod test {
                 final_: false,
                 owners: vec![KEY1.to_string()],
                 custodians: vec![KEY2.to_string()],
+                parent: None,
+                product_id: None,
+                location_id: None,
                 service_id: None,
             },
             Record {
@@ -3312,6 +5679,9 @@ mod test {
                 final_: true,
                 owners: vec![KEY2.to_string(), KEY1.to_string()],
                 custodians: vec![KEY1.to_string(), KEY2.to_string()],
+                parent: None,
+                product_id: None,
+                location_id: None,
                 service_id: None,
             },
             Record {
@@ -3323,6 +5693,9 @@ mod test {
                 final_: false,
                 owners: vec![KEY1.to_string()],
                 custodians: vec![KEY2.to_string()],
+                parent: None,
+                product_id: None,
+                location_id: None,
                 service_id: None,
             },
         ]
@@ -3400,6 +5773,21 @@ mod test {
         ]
     }
 
+    #[cfg(feature = "track-and-trace")]
+    fn get_reporter_for_property_1(start_commit_num: i64, authorized: bool) -> Reporter {
+        Reporter {
+            id: None,
+            start_commit_num,
+            end_commit_num: i64::MAX,
+            property_name: "TestProperty1".to_string(),
+            record_id: "TestRecord".to_string(),
+            public_key: KEY1.to_string(),
+            authorized,
+            reporter_index: 0,
+            service_id: None,
+        }
+    }
+
     #[cfg(feature = "track-and-trace")]
     fn get_reported_value_for_property_record(service_id: Option<String>) -> Vec<ReportedValue> {
         vec![
@@ -3484,6 +5872,13 @@ mod test {
         ]
     }
 
+    #[cfg(feature = "track-and-trace")]
+    fn populate_reporter_table(reporters: Vec<Reporter>) {
+        let pool = get_connection_pool();
+        let store = DieselTrackAndTraceStore::new(pool.pool);
+        store.add_reporters(reporters).unwrap();
+    }
+
     #[cfg(feature = "track-and-trace")]
     fn populate_tnt_property_table(
         properties: Vec<Property>,
@@ -3665,6 +6060,71 @@ mod test {
         store.add_records(records).unwrap();
     }
 
+    #[cfg(feature = "purchase-order")]
+    fn populate_purchase_order_table(purchase_orders: Vec<PurchaseOrder>) {
+        let pool = get_connection_pool();
+        let store = DieselPurchaseOrderStore::new(pool.pool);
+        purchase_orders
+            .into_iter()
+            .for_each(|purchase_order| store.add_purchase_order(purchase_order).unwrap());
+    }
+
+    #[cfg(feature = "purchase-order")]
+    fn get_purchase_order_agents() -> Vec<Agent> {
+        let agent = |public_key: &str, org_id: &str, role: &str| Agent {
+            public_key: public_key.to_string(),
+            org_id: org_id.to_string(),
+            active: true,
+            roles: vec![role.to_string()],
+            metadata: EMPTY_AGENT_METADATA.to_vec(),
+            start_commit_num: 0,
+            end_commit_num: i64::MAX,
+            service_id: None,
+        };
+
+        vec![
+            agent(KEY1, KEY2, "po.buyer"),
+            agent(KEY2, KEY3, "po.buyer"),
+            agent(KEY3, KEY3, "po.seller"),
+        ]
+    }
+
+    #[cfg(feature = "purchase-order")]
+    fn get_purchase_order(service_id: Option<String>) -> Vec<PurchaseOrder> {
+        vec![PurchaseOrder {
+            purchase_order_uid: "PO-00000001".to_string(),
+            workflow_status: "issued".to_string(),
+            buyer_org_id: KEY2.to_string(),
+            seller_org_id: KEY3.to_string(),
+            is_closed: false,
+            accepted_version_id: None,
+            versions: vec![PurchaseOrderVersion {
+                version_id: "1".to_string(),
+                is_draft: false,
+                current_revision_id: 2,
+                workflow_status: "proposed".to_string(),
+                revisions: vec![
+                    PurchaseOrderVersionRevision {
+                        revision_id: 1,
+                        order_xml_v3_4: "<order/>".to_string(),
+                        submitter: KEY2.to_string(),
+                        created_at: 1,
+                    },
+                    PurchaseOrderVersionRevision {
+                        revision_id: 2,
+                        order_xml_v3_4: "<order revised=\"true\"/>".to_string(),
+                        submitter: KEY1.to_string(),
+                        created_at: 2,
+                    },
+                ],
+            }],
+            created_at: 1,
+            start_commit_num: 0,
+            end_commit_num: i64::MAX,
+            service_id,
+        }]
+    }
+
     #[cfg(not(feature = "test-postgres"))]
     fn run_migrations(database_url: &str) {
         let connection = SqliteConnection::establish(database_url)
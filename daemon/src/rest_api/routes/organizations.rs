@@ -16,8 +16,8 @@ use std::convert::TryFrom;
 
 use crate::rest_api::{
     error::RestApiResponseError,
-    routes::{paging::Paging, DbExecutor},
-    AcceptServiceIdParam, AppState, QueryPaging, QueryServiceId,
+    routes::{history::HistorySlice, paging::Paging, DbExecutor},
+    AcceptServiceIdParam, AppState, Principal, QueryAsOf, QueryPaging, QueryServiceId,
 };
 
 use actix::{Handler, Message, SyncContext};
@@ -82,9 +82,12 @@ impl TryFrom<&OrganizationMetadata> for OrganizationMetadataSlice {
 }
 
 struct ListOrganizations {
+    as_of: Option<String>,
     service_id: Option<String>,
     offset: u64,
     limit: u16,
+    cursor: Option<String>,
+    principal: Principal,
 }
 
 impl Message for ListOrganizations {
@@ -99,9 +102,15 @@ impl Handler<ListOrganizations> for DbExecutor {
 
         let limit = i64::try_from(msg.limit).unwrap_or(10);
 
-        let orgs_list =
-            self.pike_store
-                .list_organizations(msg.service_id.as_deref(), offset, limit)?;
+        let page = self.page_query(msg.as_of.as_deref(), msg.cursor.as_deref(), offset, limit)?;
+        let commit_height = page.commit_height;
+
+        let orgs_list = self.pike_store.list_organizations(
+            commit_height,
+            msg.principal.org_id(),
+            msg.service_id.as_deref(),
+            &page.request,
+        )?;
 
         let data = orgs_list
             .data
@@ -109,7 +118,7 @@ impl Handler<ListOrganizations> for DbExecutor {
             .map(OrganizationSlice::try_from)
             .collect::<Result<Vec<OrganizationSlice>, RestApiResponseError>>()?;
 
-        let paging = Paging::new("/organization", orgs_list.paging, msg.service_id.as_deref());
+        let paging = page.paging("/organization", orgs_list.paging, msg.service_id.as_deref());
 
         Ok(OrganizationListSlice { data, paging })
     }
@@ -119,15 +128,20 @@ pub async fn list_organizations(
     state: web::Data<AppState>,
     query_service_id: web::Query<QueryServiceId>,
     query_paging: web::Query<QueryPaging>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     let paging = query_paging.into_inner();
     state
         .database_connection
         .send(ListOrganizations {
+            as_of: query_as_of.into_inner().as_of,
             service_id: query_service_id.into_inner().service_id,
             offset: paging.offset(),
             limit: paging.limit(),
+            cursor: paging.cursor,
+            principal,
         })
         .await?
         .map(|organizations| HttpResponse::Ok().json(organizations))
@@ -135,7 +149,9 @@ pub async fn list_organizations(
 
 struct FetchOrganization {
     organization_id: String,
+    as_of: Option<String>,
     service_id: Option<String>,
+    principal: Principal,
 }
 
 impl Message for FetchOrganization {
@@ -146,12 +162,17 @@ impl Handler<FetchOrganization> for DbExecutor {
     type Result = Result<OrganizationSlice, RestApiResponseError>;
 
     fn handle(&mut self, msg: FetchOrganization, _: &mut SyncContext<Self>) -> Self::Result {
-        match self
-            .pike_store
-            .fetch_organization(&msg.organization_id, msg.service_id.as_deref())?
-        {
-            Some(organization) => OrganizationSlice::try_from(organization),
-            None => Err(RestApiResponseError::NotFoundError(format!(
+        let commit_height = self.commit_height(msg.as_of.as_deref())?;
+
+        match self.pike_store.fetch_organization(
+            &msg.organization_id,
+            commit_height,
+            msg.service_id.as_deref(),
+        )? {
+            Some(organization) if msg.principal.can_read_org(&organization.org_id) => {
+                OrganizationSlice::try_from(organization)
+            }
+            _ => Err(RestApiResponseError::NotFoundError(format!(
                 "Could not find organization with id: {}",
                 msg.organization_id
             ))),
@@ -163,14 +184,79 @@ pub async fn fetch_organization(
     state: web::Data<AppState>,
     organization_id: web::Path<String>,
     query: web::Query<QueryServiceId>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     state
         .database_connection
         .send(FetchOrganization {
             organization_id: organization_id.into_inner(),
+            as_of: query_as_of.into_inner().as_of,
             service_id: query.into_inner().service_id,
+            principal,
         })
         .await?
         .map(|organization| HttpResponse::Ok().json(organization))
 }
+
+struct FetchOrganizationHistory {
+    organization_id: String,
+    service_id: Option<String>,
+    principal: Principal,
+}
+
+impl Message for FetchOrganizationHistory {
+    type Result = Result<HistorySlice<OrganizationSlice>, RestApiResponseError>;
+}
+
+impl Handler<FetchOrganizationHistory> for DbExecutor {
+    type Result = Result<HistorySlice<OrganizationSlice>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchOrganizationHistory, _: &mut SyncContext<Self>) -> Self::Result {
+        // Only the organization the principal may read is shown
+        let versions = self
+            .pike_store
+            .list_organization_history(&msg.organization_id, msg.service_id.as_deref())?
+            .into_iter()
+            .filter(|organization| msg.principal.can_read_org(&organization.org_id))
+            .collect::<Vec<_>>();
+
+        if versions.is_empty() {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find organization with id: {}",
+                msg.organization_id
+            )));
+        }
+
+        let versions = versions
+            .into_iter()
+            .map(|organization| {
+                Ok((
+                    organization.start_commit_num,
+                    OrganizationSlice::try_from(organization)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, RestApiResponseError>>()?;
+
+        self.history(versions)
+    }
+}
+
+pub async fn fetch_organization_history(
+    state: web::Data<AppState>,
+    organization_id: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchOrganizationHistory {
+            organization_id: organization_id.into_inner(),
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|history| HttpResponse::Ok().json(history))
+}
@@ -13,101 +13,215 @@
 // limitations under the License.
 
 use grid_sdk::paging;
+#[cfg(feature = "pike")]
+use grid_sdk::paging::{Cursor, PageDirection, PageRequest};
+use url::form_urlencoded;
 
+/// The paging links of a page read from a list
+///
+/// Pages read by offset link to the first, previous, next and last pages by offset. Pages read
+/// by cursor link to the first page and to the pages before and after them by cursor instead,
+/// and have no offset, total or last page.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Paging {
     current: String,
-    offset: i64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<i64>,
     limit: i64,
-    total: i64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<i64>,
     first: String,
-    prev: String,
+    prev: Option<String>,
     next: Option<String>,
-    last: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last: Option<String>,
 }
 
 impl Paging {
+    /// Returns the paging links of a page read by offset
+    ///
+    /// The base link may carry query params of its own, such as the filters of the list, which
+    /// every link keeps.
     pub fn new(base_link: &str, paging: paging::Paging, service_id: Option<&str>) -> Self {
-        let current = if let Some(service_id) = service_id {
-            format!(
-                "{}?offset={}&limit={}&service_id={}",
-                base_link, paging.offset, paging.limit, service_id
-            )
-        } else {
-            format!(
-                "{}?offset={}&limit={}",
-                base_link, paging.offset, paging.limit
-            )
-        };
-        let first = if let Some(service_id) = service_id {
+        Self::by_offset(base_link, paging, None, service_id)
+    }
+
+    /// Returns the paging links of a page read by offset at a commit height, if one was asked
+    /// for, which every link keeps
+    fn by_offset(
+        base_link: &str,
+        paging: paging::Paging,
+        as_of: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Self {
+        let limit = paging.limit;
+        let as_of_param = as_of
+            .map(|as_of| format!("&as_of={}", as_of))
+            .unwrap_or_default();
+        let service_id_param = service_id
+            .map(|service_id| format!("&service_id={}", encode_param(service_id)))
+            .unwrap_or_default();
+        let link = |offset: i64| {
             format!(
-                "{}?offset=0&limit={}&service_id={}",
-                base_link, paging.limit, service_id
+                "{}{}offset={}&limit={}{}{}",
+                base_link,
+                param_separator(base_link),
+                offset,
+                limit,
+                as_of_param,
+                service_id_param
             )
-        } else {
-            format!("{}?offset=0&limit={}", base_link, paging.limit)
         };
-        let previous_offset = if paging.offset > paging.limit {
-            paging.offset - paging.limit
+
+        let previous_offset = if paging.offset > limit {
+            paging.offset - limit
         } else {
             0
         };
-        let prev = if let Some(service_id) = service_id {
-            format!(
-                "{}?offset={}&limit={}&service_id={}",
-                base_link, previous_offset, paging.limit, service_id
-            )
-        } else {
-            format!(
-                "{}?offset={}&limit={}",
-                base_link, previous_offset, paging.limit
-            )
-        };
-
         let last_offset = if paging.total > 0 {
-            ((paging.total - 1) / paging.limit) * paging.limit
+            ((paging.total - 1) / limit) * limit
         } else {
             0
         };
-        let last = if let Some(service_id) = service_id {
-            format!(
-                "{}?offset={}&limit={}&service_id={}",
-                base_link, last_offset, paging.limit, service_id
-            )
-        } else {
-            format!(
-                "{}?offset={}&limit={}",
-                base_link, last_offset, paging.limit
-            )
-        };
-
-        let next_offset = if paging.offset + paging.limit > last_offset {
+        let next_offset = if paging.offset + limit > last_offset {
             last_offset
         } else {
-            paging.offset + paging.limit
+            paging.offset + limit
         };
 
-        let next = if let Some(service_id) = service_id {
-            format!(
-                "{}?offset={}&limit={}&service_id={}",
-                base_link, next_offset, paging.limit, service_id
-            )
-        } else {
+        let last = link(last_offset);
+        let next = link(next_offset);
+
+        Paging {
+            current: link(paging.offset),
+            offset: Some(paging.offset),
+            limit,
+            total: Some(paging.total),
+            first: link(0),
+            prev: Some(link(previous_offset)),
+            next: if next == last { None } else { Some(next) },
+            last: Some(last),
+        }
+    }
+
+    #[cfg(feature = "pike")]
+    fn from_cursor(
+        base_link: &str,
+        paging: paging::Paging,
+        commit_height: i64,
+        cursor: &str,
+        service_id: Option<&str>,
+    ) -> Self {
+        let limit = paging.limit;
+        let service_id_param = service_id
+            .map(|service_id| format!("&service_id={}", encode_param(service_id)))
+            .unwrap_or_default();
+        let separator = param_separator(base_link);
+        let link = |cursor: &str| {
             format!(
-                "{}?offset={}&limit={}",
-                base_link, next_offset, paging.limit
+                "{}{}cursor={}&limit={}{}",
+                base_link, separator, cursor, limit, service_id_param
             )
         };
+        let cursor_link = |key: String, direction: PageDirection| {
+            link(&Cursor::new(commit_height, key, direction).encode())
+        };
+
+        let first = format!(
+            "{}{}cursor=&limit={}&as_of={}{}",
+            base_link, separator, limit, commit_height, service_id_param
+        );
 
         Paging {
-            current,
-            offset: paging.offset,
-            limit: paging.limit,
-            total: paging.total,
+            current: if cursor.is_empty() {
+                first.clone()
+            } else {
+                link(cursor)
+            },
+            offset: None,
+            limit,
+            total: None,
             first,
-            prev,
-            next: if next == last { None } else { Some(next) },
-            last,
+            prev: paging
+                .prev_key
+                .map(|key| cursor_link(key, PageDirection::Previous)),
+            next: paging
+                .next_key
+                .map(|key| cursor_link(key, PageDirection::Next)),
+            last: None,
+        }
+    }
+}
+
+/// Returns the link to a list with the query params that select what it lists, such as its
+/// filters and sort order, for its paging links to keep
+#[cfg(any(
+    feature = "location",
+    feature = "product",
+    feature = "search",
+    feature = "track-and-trace"
+))]
+pub fn list_link(path: &str, params: &[(String, String)]) -> String {
+    if params.is_empty() {
+        return path.to_string();
+    }
+
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    format!("{}?{}", path, query)
+}
+
+/// Percent-encodes the value of a query param for a link
+fn encode_param(value: &str) -> String {
+    form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Returns the separator to add a query param to a link with
+fn param_separator(link: &str) -> char {
+    if link.contains('?') {
+        '&'
+    } else {
+        '?'
+    }
+}
+
+/// The page a list route was asked for
+///
+/// Pages asked for by cursor are read at the commit height the cursor was made at, so that the
+/// pages of a list read by cursor are not changed by commits made in between them.
+#[cfg(feature = "pike")]
+pub struct PageQuery {
+    pub commit_height: Option<i64>,
+    pub request: PageRequest,
+    cursor: Option<String>,
+}
+
+#[cfg(feature = "pike")]
+impl PageQuery {
+    pub fn new(commit_height: Option<i64>, request: PageRequest, cursor: Option<String>) -> Self {
+        PageQuery {
+            commit_height,
+            request,
+            cursor,
+        }
+    }
+
+    /// Returns the paging links of the page read for this query
+    pub fn paging(
+        &self,
+        base_link: &str,
+        paging: paging::Paging,
+        service_id: Option<&str>,
+    ) -> Paging {
+        match (&self.cursor, self.commit_height) {
+            (Some(cursor), Some(commit_height)) => {
+                Paging::from_cursor(base_link, paging, commit_height, cursor, service_id)
+            }
+            _ => Paging::by_offset(base_link, paging, self.commit_height, service_id),
         }
     }
 }
@@ -19,12 +19,18 @@ use std::convert::TryFrom;
 
 use crate::rest_api::{
     error::RestApiResponseError,
-    routes::{paging::Paging, DbExecutor},
-    AcceptServiceIdParam, AppState, QueryPaging, QueryServiceId,
+    routes::{
+        history::HistorySlice,
+        paging::{list_link, Paging},
+        DbExecutor,
+    },
+    AcceptServiceIdParam, AppState, Principal, QueryAsOf, QueryListFilter, QueryPaging,
+    QueryServiceId,
 };
 
 use actix::{Handler, Message, SyncContext};
 use actix_web::{web, HttpResponse};
+use grid_sdk::filter::{Filter, ListFilter, Sort, StringMatch};
 use grid_sdk::products::store::{LatLongValue, Product, PropertyValue};
 use serde::{Deserialize, Serialize};
 
@@ -116,9 +122,14 @@ impl From<LatLongValue> for LatLongSlice {
 }
 
 struct ListProducts {
+    as_of: Option<String>,
+    filter: ListFilter,
+    link: String,
     service_id: Option<String>,
     offset: u64,
     limit: u16,
+    cursor: Option<String>,
+    principal: Principal,
 }
 
 impl Message for ListProducts {
@@ -128,14 +139,33 @@ impl Message for ListProducts {
 impl Handler<ListProducts> for DbExecutor {
     type Result = Result<ProductListSlice, RestApiResponseError>;
 
-    fn handle(&mut self, msg: ListProducts, _: &mut SyncContext<Self>) -> Self::Result {
+    fn handle(&mut self, mut msg: ListProducts, _: &mut SyncContext<Self>) -> Self::Result {
+        // Pages read by cursor are keyed, and so ordered, by ID
+        if msg.cursor.is_some() && msg.filter.sort != Sort::default() {
+            return Err(RestApiResponseError::BadRequest(
+                "A list read by cursor cannot be sorted".into(),
+            ));
+        }
+
+        if let Some(org_id) = msg.principal.org_id() {
+            msg.filter
+                .filters
+                .push(Filter::Owner(StringMatch::Equals(org_id.to_string())));
+        }
+
         let offset = i64::try_from(msg.offset).unwrap_or(i64::MAX);
 
         let limit = i64::try_from(msg.limit).unwrap_or(10);
 
-        let product_list =
-            self.product_store
-                .list_products(msg.service_id.as_deref(), offset, limit)?;
+        let page = self.page_query(msg.as_of.as_deref(), msg.cursor.as_deref(), offset, limit)?;
+        let commit_height = page.commit_height;
+
+        let product_list = self.product_store.list_products(
+            commit_height,
+            &msg.filter,
+            msg.service_id.as_deref(),
+            &page.request,
+        )?;
 
         let data = product_list
             .data
@@ -143,7 +173,7 @@ impl Handler<ListProducts> for DbExecutor {
             .map(ProductSlice::from)
             .collect();
 
-        let paging = Paging::new("/product", product_list.paging, msg.service_id.as_deref());
+        let paging = page.paging(&msg.link, product_list.paging, msg.service_id.as_deref());
 
         Ok(ProductListSlice { data, paging })
     }
@@ -153,15 +183,23 @@ pub async fn list_products(
     state: web::Data<AppState>,
     query_service_id: web::Query<QueryServiceId>,
     query_paging: web::Query<QueryPaging>,
+    query_as_of: web::Query<QueryAsOf>,
+    query_list_filter: QueryListFilter,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     let paging = query_paging.into_inner();
     state
         .database_connection
         .send(ListProducts {
+            as_of: query_as_of.into_inner().as_of,
+            link: list_link("/product", &query_list_filter.params),
+            filter: query_list_filter.filter,
             service_id: query_service_id.into_inner().service_id,
             offset: paging.offset.unwrap_or(0),
             limit: paging.limit.unwrap_or(10),
+            cursor: paging.cursor,
+            principal,
         })
         .await?
         .map(|products| HttpResponse::Ok().json(products))
@@ -169,7 +207,9 @@ pub async fn list_products(
 
 struct FetchProduct {
     product_id: String,
+    as_of: Option<String>,
     service_id: Option<String>,
+    principal: Principal,
 }
 
 impl Message for FetchProduct {
@@ -180,12 +220,17 @@ impl Handler<FetchProduct> for DbExecutor {
     type Result = Result<ProductSlice, RestApiResponseError>;
 
     fn handle(&mut self, msg: FetchProduct, _: &mut SyncContext<Self>) -> Self::Result {
-        match self
-            .product_store
-            .fetch_product(&msg.product_id, msg.service_id.as_deref())?
-        {
-            Some(product) => Ok(ProductSlice::from(product)),
-            None => Err(RestApiResponseError::NotFoundError(format!(
+        let commit_height = self.commit_height(msg.as_of.as_deref())?;
+
+        match self.product_store.fetch_product(
+            &msg.product_id,
+            commit_height,
+            msg.service_id.as_deref(),
+        )? {
+            Some(product) if msg.principal.can_read_org(&product.owner) => {
+                Ok(ProductSlice::from(product))
+            }
+            _ => Err(RestApiResponseError::NotFoundError(format!(
                 "Could not find product with id: {}",
                 msg.product_id
             ))),
@@ -197,14 +242,73 @@ pub async fn fetch_product(
     state: web::Data<AppState>,
     product_id: web::Path<String>,
     query: web::Query<QueryServiceId>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     state
         .database_connection
         .send(FetchProduct {
             product_id: product_id.into_inner(),
+            as_of: query_as_of.into_inner().as_of,
             service_id: query.into_inner().service_id,
+            principal,
         })
         .await?
         .map(|product| HttpResponse::Ok().json(product))
 }
+
+struct FetchProductHistory {
+    product_id: String,
+    service_id: Option<String>,
+    principal: Principal,
+}
+
+impl Message for FetchProductHistory {
+    type Result = Result<HistorySlice<ProductSlice>, RestApiResponseError>;
+}
+
+impl Handler<FetchProductHistory> for DbExecutor {
+    type Result = Result<HistorySlice<ProductSlice>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchProductHistory, _: &mut SyncContext<Self>) -> Self::Result {
+        // Only the versions owned by an organization the principal may read are shown
+        let versions = self
+            .product_store
+            .list_product_history(&msg.product_id, msg.service_id.as_deref())?
+            .into_iter()
+            .filter(|product| msg.principal.can_read_org(&product.owner))
+            .collect::<Vec<_>>();
+        if versions.is_empty() {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find product with id: {}",
+                msg.product_id
+            )));
+        }
+
+        self.history(
+            versions
+                .into_iter()
+                .map(|product| (product.start_commit_num, ProductSlice::from(product)))
+                .collect(),
+        )
+    }
+}
+
+pub async fn fetch_product_history(
+    state: web::Data<AppState>,
+    product_id: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchProductHistory {
+            product_id: product_id.into_inner(),
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|history| HttpResponse::Ok().json(history))
+}
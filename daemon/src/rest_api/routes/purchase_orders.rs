@@ -0,0 +1,488 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use crate::rest_api::{
+    error::RestApiResponseError,
+    routes::{
+        paging::Paging,
+        workflows::{active_roles, allowed_actions, QueryAgent, WorkflowActionsSlice},
+        DbExecutor,
+    },
+    AcceptServiceIdParam, AppState, Principal, QueryPaging, QueryServiceId,
+};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+use grid_sdk::purchase_order::{
+    store::{PurchaseOrder, PurchaseOrderVersion, PurchaseOrderVersionRevision},
+    workflow::{
+        purchase_order_aliases, PO_SUBWORKFLOW, PURCHASE_ORDER_WORKFLOW, VERSION_SUBWORKFLOW,
+    },
+};
+use grid_sdk::workflow::AllowedActions;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PurchaseOrderSlice {
+    pub purchase_order_uid: String,
+    pub workflow_status: String,
+    pub buyer_org_id: String,
+    pub seller_org_id: String,
+    pub is_closed: bool,
+    pub accepted_version_id: Option<String>,
+    pub versions: Vec<PurchaseOrderVersionSlice>,
+    pub created_at: i64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+impl From<PurchaseOrder> for PurchaseOrderSlice {
+    fn from(purchase_order: PurchaseOrder) -> Self {
+        Self {
+            purchase_order_uid: purchase_order.purchase_order_uid,
+            workflow_status: purchase_order.workflow_status,
+            buyer_org_id: purchase_order.buyer_org_id,
+            seller_org_id: purchase_order.seller_org_id,
+            is_closed: purchase_order.is_closed,
+            accepted_version_id: purchase_order.accepted_version_id,
+            versions: purchase_order
+                .versions
+                .into_iter()
+                .map(PurchaseOrderVersionSlice::from)
+                .collect(),
+            created_at: purchase_order.created_at,
+            service_id: purchase_order.service_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PurchaseOrderListSlice {
+    pub data: Vec<PurchaseOrderSlice>,
+    pub paging: Paging,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PurchaseOrderVersionSlice {
+    pub version_id: String,
+    pub workflow_status: String,
+    pub is_draft: bool,
+    pub current_revision_id: i64,
+    pub revisions: Vec<PurchaseOrderRevisionSlice>,
+}
+
+impl From<PurchaseOrderVersion> for PurchaseOrderVersionSlice {
+    fn from(version: PurchaseOrderVersion) -> Self {
+        Self {
+            version_id: version.version_id,
+            workflow_status: version.workflow_status,
+            is_draft: version.is_draft,
+            current_revision_id: version.current_revision_id,
+            revisions: version
+                .revisions
+                .into_iter()
+                .map(PurchaseOrderRevisionSlice::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PurchaseOrderRevisionSlice {
+    pub revision_id: i64,
+    pub order_xml_v3_4: String,
+    pub submitter: String,
+    pub created_at: i64,
+}
+
+impl From<PurchaseOrderVersionRevision> for PurchaseOrderRevisionSlice {
+    fn from(revision: PurchaseOrderVersionRevision) -> Self {
+        Self {
+            revision_id: revision.revision_id,
+            order_xml_v3_4: revision.order_xml_v3_4,
+            submitter: revision.submitter,
+            created_at: revision.created_at,
+        }
+    }
+}
+
+struct ListPurchaseOrders {
+    service_id: Option<String>,
+    offset: u64,
+    limit: u16,
+    cursor: Option<String>,
+    principal: Principal,
+}
+
+impl Message for ListPurchaseOrders {
+    type Result = Result<PurchaseOrderListSlice, RestApiResponseError>;
+}
+
+impl Handler<ListPurchaseOrders> for DbExecutor {
+    type Result = Result<PurchaseOrderListSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListPurchaseOrders, _: &mut SyncContext<Self>) -> Self::Result {
+        let offset = i64::try_from(msg.offset).unwrap_or(i64::MAX);
+
+        let limit = i64::try_from(msg.limit).unwrap_or(10);
+
+        let page = self.page_query(None, msg.cursor.as_deref(), offset, limit)?;
+
+        let purchase_order_list = self.purchase_order_store.list_purchase_orders(
+            msg.principal.org_id(),
+            msg.service_id.as_deref(),
+            &page.request,
+        )?;
+
+        let data = purchase_order_list
+            .data
+            .into_iter()
+            .map(PurchaseOrderSlice::from)
+            .collect();
+
+        let paging = page.paging(
+            "/purchase_order",
+            purchase_order_list.paging,
+            msg.service_id.as_deref(),
+        );
+
+        Ok(PurchaseOrderListSlice { data, paging })
+    }
+}
+
+pub async fn list_purchase_orders(
+    state: web::Data<AppState>,
+    query_service_id: web::Query<QueryServiceId>,
+    query_paging: web::Query<QueryPaging>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let paging = query_paging.into_inner();
+    state
+        .database_connection
+        .send(ListPurchaseOrders {
+            service_id: query_service_id.into_inner().service_id,
+            offset: paging.offset(),
+            limit: paging.limit(),
+            cursor: paging.cursor,
+            principal,
+        })
+        .await?
+        .map(|purchase_orders| HttpResponse::Ok().json(purchase_orders))
+}
+
+struct FetchPurchaseOrder {
+    uid: String,
+    service_id: Option<String>,
+    principal: Principal,
+}
+
+impl Message for FetchPurchaseOrder {
+    type Result = Result<PurchaseOrderSlice, RestApiResponseError>;
+}
+
+impl Handler<FetchPurchaseOrder> for DbExecutor {
+    type Result = Result<PurchaseOrderSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchPurchaseOrder, _: &mut SyncContext<Self>) -> Self::Result {
+        match self
+            .purchase_order_store
+            .fetch_purchase_order(&msg.uid, msg.service_id.as_deref())?
+        {
+            Some(purchase_order)
+                if can_read_purchase_order(
+                    &msg.principal,
+                    &purchase_order.buyer_org_id,
+                    &purchase_order.seller_org_id,
+                ) =>
+            {
+                Ok(PurchaseOrderSlice::from(purchase_order))
+            }
+            _ => Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find purchase order with uid: {}",
+                msg.uid
+            ))),
+        }
+    }
+}
+
+pub async fn fetch_purchase_order(
+    state: web::Data<AppState>,
+    uid: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchPurchaseOrder {
+            uid: uid.into_inner(),
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|purchase_order| HttpResponse::Ok().json(purchase_order))
+}
+
+pub async fn list_purchase_order_versions(
+    state: web::Data<AppState>,
+    uid: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchPurchaseOrder {
+            uid: uid.into_inner(),
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|purchase_order| HttpResponse::Ok().json(purchase_order.versions))
+}
+
+pub async fn fetch_purchase_order_version(
+    state: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+    query: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let (uid, version_id) = params.into_inner();
+    let purchase_order = state
+        .database_connection
+        .send(FetchPurchaseOrder {
+            uid,
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await??;
+
+    find_version(purchase_order, &version_id).map(|version| HttpResponse::Ok().json(version))
+}
+
+pub async fn list_purchase_order_revisions(
+    state: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+    query: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let (uid, version_id) = params.into_inner();
+    let purchase_order = state
+        .database_connection
+        .send(FetchPurchaseOrder {
+            uid,
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await??;
+
+    find_version(purchase_order, &version_id)
+        .map(|version| HttpResponse::Ok().json(version.revisions))
+}
+
+pub async fn fetch_purchase_order_revision(
+    state: web::Data<AppState>,
+    params: web::Path<(String, String, i64)>,
+    query: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let (uid, version_id, revision_id) = params.into_inner();
+    let purchase_order = state
+        .database_connection
+        .send(FetchPurchaseOrder {
+            uid,
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await??;
+
+    let version = find_version(purchase_order, &version_id)?;
+    match version
+        .revisions
+        .into_iter()
+        .find(|revision| revision.revision_id == revision_id)
+    {
+        Some(revision) => Ok(HttpResponse::Ok().json(revision)),
+        None => Err(RestApiResponseError::NotFoundError(format!(
+            "Could not find revision {} of version {}",
+            revision_id, version_id
+        ))),
+    }
+}
+
+/// Returns whether the principal may read a purchase order: the order's buyer and seller may
+fn can_read_purchase_order(principal: &Principal, buyer_org_id: &str, seller_org_id: &str) -> bool {
+    principal.can_read_org(buyer_org_id) || principal.can_read_org(seller_org_id)
+}
+
+fn find_version(
+    purchase_order: PurchaseOrderSlice,
+    version_id: &str,
+) -> Result<PurchaseOrderVersionSlice, RestApiResponseError> {
+    let uid = purchase_order.purchase_order_uid;
+    purchase_order
+        .versions
+        .into_iter()
+        .find(|version| version.version_id == version_id)
+        .ok_or_else(|| {
+            RestApiResponseError::NotFoundError(format!(
+                "Could not find version {} of purchase order {}",
+                version_id, uid
+            ))
+        })
+}
+
+struct FetchPurchaseOrderActions {
+    uid: String,
+    version_id: Option<String>,
+    agent: String,
+    service_id: Option<String>,
+    principal: Principal,
+}
+
+impl Message for FetchPurchaseOrderActions {
+    type Result = Result<WorkflowActionsSlice, RestApiResponseError>;
+}
+
+impl Handler<FetchPurchaseOrderActions> for DbExecutor {
+    type Result = Result<WorkflowActionsSlice, RestApiResponseError>;
+
+    fn handle(
+        &mut self,
+        msg: FetchPurchaseOrderActions,
+        _: &mut SyncContext<Self>,
+    ) -> Self::Result {
+        let purchase_order = self
+            .purchase_order_store
+            .fetch_purchase_order(&msg.uid, msg.service_id.as_deref())?
+            .filter(|purchase_order| {
+                can_read_purchase_order(
+                    &msg.principal,
+                    &purchase_order.buyer_org_id,
+                    &purchase_order.seller_org_id,
+                )
+            })
+            .ok_or_else(|| {
+                RestApiResponseError::NotFoundError(format!(
+                    "Could not find purchase order with uid: {}",
+                    msg.uid
+                ))
+            })?;
+
+        let agent = self
+            .pike_store
+            .fetch_agent(&msg.agent, None, msg.service_id.as_deref())?
+            .ok_or_else(|| {
+                RestApiResponseError::NotFoundError(format!(
+                    "Could not find agent with public key: {}",
+                    msg.agent
+                ))
+            })?;
+
+        let roles = purchase_order_aliases(
+            &agent.org_id,
+            &active_roles(&agent),
+            &purchase_order.buyer_org_id,
+            &purchase_order.seller_org_id,
+        );
+
+        let (subworkflow, workflow_state) = match &msg.version_id {
+            Some(version_id) => {
+                let version = purchase_order
+                    .versions
+                    .iter()
+                    .find(|version| &version.version_id == version_id)
+                    .ok_or_else(|| {
+                        RestApiResponseError::NotFoundError(format!(
+                            "Could not find version {} of purchase order {}",
+                            version_id, msg.uid
+                        ))
+                    })?;
+                (VERSION_SUBWORKFLOW, version.workflow_status.clone())
+            }
+            None => (PO_SUBWORKFLOW, purchase_order.workflow_status.clone()),
+        };
+
+        // A closed purchase order accepts no further changes
+        let actions = if purchase_order.is_closed {
+            AllowedActions::default()
+        } else {
+            allowed_actions(
+                PURCHASE_ORDER_WORKFLOW,
+                subworkflow,
+                &workflow_state,
+                &roles,
+            )?
+        };
+
+        Ok(WorkflowActionsSlice::new(
+            PURCHASE_ORDER_WORKFLOW,
+            subworkflow,
+            &workflow_state,
+            &msg.agent,
+            roles,
+            actions,
+        ))
+    }
+}
+
+pub async fn fetch_purchase_order_actions(
+    state: web::Data<AppState>,
+    uid: web::Path<String>,
+    query_agent: web::Query<QueryAgent>,
+    query_service_id: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchPurchaseOrderActions {
+            uid: uid.into_inner(),
+            version_id: None,
+            agent: query_agent.into_inner().agent,
+            service_id: query_service_id.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|actions| HttpResponse::Ok().json(actions))
+}
+
+pub async fn fetch_purchase_order_version_actions(
+    state: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+    query_agent: web::Query<QueryAgent>,
+    query_service_id: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let (uid, version_id) = params.into_inner();
+    state
+        .database_connection
+        .send(FetchPurchaseOrderActions {
+            uid,
+            version_id: Some(version_id),
+            agent: query_agent.into_inner().agent,
+            service_id: query_service_id.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|actions| HttpResponse::Ok().json(actions))
+}
@@ -18,14 +18,14 @@ use std::sync::Arc;
 use crate::rest_api::{
     error::RestApiResponseError,
     routes::{paging::Paging, DbExecutor},
-    AcceptServiceIdParam, AppState, QueryPaging, QueryServiceId,
+    AcceptServiceIdParam, AppState, Principal, QueryAsOf, QueryPaging, QueryServiceId,
 };
 
 use actix::{Handler, Message, SyncContext};
 use actix_web::{web, HttpResponse};
 use grid_sdk::track_and_trace::store::{
-    AssociatedAgent, LatLongValue, Property, Proposal, Record,
-    ReportedValueReporterToAgentMetadata, TrackAndTraceStore,
+    AssociatedAgent, LatLongValue, Property, Proposal, Record, RecordFilter,
+    ReportedValueReporterToAgentMetadata, Reporter, TrackAndTraceStore,
 };
 use serde::{Deserialize, Serialize};
 
@@ -143,9 +143,12 @@ pub struct RecordListSlice {
 }
 
 struct ListRecords {
+    as_of: Option<String>,
     service_id: Option<String>,
     offset: u64,
     limit: u16,
+    cursor: Option<String>,
+    principal: Principal,
 }
 
 impl Message for ListRecords {
@@ -160,32 +163,44 @@ impl Handler<ListRecords> for DbExecutor {
 
         let limit = i64::try_from(msg.limit).unwrap_or(10);
 
-        let record_list = self
-            .tnt_store
-            .list_records(msg.service_id.as_deref(), offset, limit)?;
+        let page = self.page_query(msg.as_of.as_deref(), msg.cursor.as_deref(), offset, limit)?;
+        let commit_height = page.commit_height;
 
-        let record_ids: Vec<String> = record_list
+        let filter = RecordFilter {
+            org_id: msg.principal.org_id(),
+            ..RecordFilter::default()
+        };
+        let record_list = self.tnt_store.list_records_matching(
+            &filter,
+            commit_height,
+            msg.service_id.as_deref(),
+            &page.request,
+        )?;
+        let record_ids = record_list
             .data
             .iter()
             .map(|record| record.record_id.to_string())
-            .collect();
-
-        let proposals = self
-            .tnt_store
-            .list_proposals(&record_ids, msg.service_id.as_deref())?;
-        let associated_agents = self
-            .tnt_store
-            .list_associated_agents(&record_ids, msg.service_id.as_deref())?;
+            .collect::<Vec<_>>();
+
+        let proposals =
+            self.tnt_store
+                .list_proposals(&record_ids, commit_height, msg.service_id.as_deref())?;
+        let associated_agents = self.tnt_store.list_associated_agents(
+            &record_ids,
+            commit_height,
+            msg.service_id.as_deref(),
+        )?;
 
         let properties = self
             .tnt_store
-            .list_properties_with_data_type(&record_ids, msg.service_id.as_deref())?
+            .list_properties_with_data_type(&record_ids, commit_height, msg.service_id.as_deref())?
             .iter()
             .map(|(property, data_type)| {
                 parse_property_slice(
                     &self.tnt_store,
                     property,
                     data_type,
+                    commit_height,
                     msg.service_id.as_deref(),
                 )
             })
@@ -216,7 +231,7 @@ impl Handler<ListRecords> for DbExecutor {
             })
             .collect();
 
-        let paging = Paging::new("/record", record_list.paging, msg.service_id.as_deref());
+        let paging = page.paging("/record", record_list.paging, msg.service_id.as_deref());
 
         Ok(RecordListSlice { data, paging })
     }
@@ -226,15 +241,20 @@ pub async fn list_records(
     state: web::Data<AppState>,
     query_service_id: web::Query<QueryServiceId>,
     query_paging: web::Query<QueryPaging>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     let paging = query_paging.into_inner();
     state
         .database_connection
         .send(ListRecords {
+            as_of: query_as_of.into_inner().as_of,
             service_id: query_service_id.into_inner().service_id,
             offset: paging.offset(),
             limit: paging.limit(),
+            cursor: paging.cursor,
+            principal,
         })
         .await?
         .map(|records| HttpResponse::Ok().json(records))
@@ -242,7 +262,9 @@ pub async fn list_records(
 
 struct FetchRecord {
     record_id: String,
+    as_of: Option<String>,
     service_id: Option<String>,
+    principal: Principal,
 }
 
 impl Message for FetchRecord {
@@ -253,47 +275,26 @@ impl Handler<FetchRecord> for DbExecutor {
     type Result = Result<RecordSlice, RestApiResponseError>;
 
     fn handle(&mut self, msg: FetchRecord, _: &mut SyncContext<Self>) -> Self::Result {
-        let record = match self
-            .tnt_store
-            .fetch_record(&msg.record_id, msg.service_id.as_deref())?
-        {
-            Some(record) => record,
-            None => {
-                return Err(RestApiResponseError::NotFoundError(format!(
-                    "Could not find record with id: {}",
-                    msg.record_id
-                )));
-            }
-        };
-
-        let proposals = self
-            .tnt_store
-            .list_proposals(&[msg.record_id.clone()], msg.service_id.as_deref())?;
-
-        let properties = self
-            .tnt_store
-            .list_properties_with_data_type(&[msg.record_id.clone()], msg.service_id.as_deref())?
-            .iter()
-            .map(|(property, data_type)| {
-                parse_property_slice(
-                    &self.tnt_store,
-                    property,
-                    data_type,
-                    msg.service_id.as_deref(),
-                )
-            })
-            .collect::<Result<Vec<PropertySlice>, _>>()?;
-
-        let associated_agents = self
-            .tnt_store
-            .list_associated_agents(&[msg.record_id], msg.service_id.as_deref())?;
+        let commit_height = self.commit_height(msg.as_of.as_deref())?;
+        self.check_record_access(
+            &msg.principal,
+            &msg.record_id,
+            commit_height,
+            msg.service_id.as_deref(),
+        )?;
 
-        Ok(RecordSlice::from_models(
-            record,
-            proposals,
-            associated_agents,
-            properties,
-        ))
+        fetch_record_slice(
+            &self.tnt_store,
+            &msg.record_id,
+            commit_height,
+            msg.service_id.as_deref(),
+        )?
+        .ok_or_else(|| {
+            RestApiResponseError::NotFoundError(format!(
+                "Could not find record with id: {}",
+                msg.record_id
+            ))
+        })
     }
 }
 
@@ -301,13 +302,17 @@ pub async fn fetch_record(
     state: web::Data<AppState>,
     record_id: web::Path<String>,
     query: web::Query<QueryServiceId>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     state
         .database_connection
         .send(FetchRecord {
             record_id: record_id.into_inner(),
+            as_of: query_as_of.into_inner().as_of,
             service_id: query.into_inner().service_id,
+            principal,
         })
         .await?
         .map(|record| HttpResponse::Ok().json(record))
@@ -391,6 +396,110 @@ pub struct ReporterSlice {
     pub service_id: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReporterAuthorizationSlice {
+    pub authorized: bool,
+    pub start_commit_num: i64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_commit_num: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReporterHistorySlice {
+    pub public_key: String,
+    pub reporter_index: i32,
+    pub authorized: bool,
+    pub history: Vec<ReporterAuthorizationSlice>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PropertyReportersSlice {
+    pub name: String,
+    pub reporters: Vec<ReporterHistorySlice>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecordReportersSlice {
+    pub record_id: String,
+    pub properties: Vec<PropertyReportersSlice>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+impl RecordReportersSlice {
+    /// Builds the slice from reporter rows ordered by property name, public key and start
+    /// commit number. Consecutive rows with the same authorization are merged into a single
+    /// entry, since every update to a property rewrites all of its reporters.
+    pub fn from_models(
+        record_id: &str,
+        reporters: &[Reporter],
+        service_id: Option<String>,
+    ) -> Self {
+        let mut properties: Vec<PropertyReportersSlice> = Vec::new();
+
+        for reporter in reporters {
+            let end_commit_num = if reporter.end_commit_num == i64::MAX {
+                None
+            } else {
+                Some(reporter.end_commit_num)
+            };
+
+            if properties
+                .last()
+                .map(|property| property.name != reporter.property_name)
+                .unwrap_or(true)
+            {
+                properties.push(PropertyReportersSlice {
+                    name: reporter.property_name.clone(),
+                    reporters: Vec::new(),
+                });
+            }
+            let property_reporters = &mut properties
+                .last_mut()
+                .expect("property was just pushed")
+                .reporters;
+
+            if property_reporters
+                .last()
+                .map(|last| last.public_key != reporter.public_key)
+                .unwrap_or(true)
+            {
+                property_reporters.push(ReporterHistorySlice {
+                    public_key: reporter.public_key.clone(),
+                    reporter_index: reporter.reporter_index,
+                    authorized: reporter.authorized,
+                    history: Vec::new(),
+                });
+            }
+            let reporter_slice = property_reporters
+                .last_mut()
+                .expect("reporter was just pushed");
+
+            reporter_slice.reporter_index = reporter.reporter_index;
+            reporter_slice.authorized = reporter.authorized;
+
+            match reporter_slice.history.last_mut() {
+                Some(period) if period.authorized == reporter.authorized => {
+                    period.end_commit_num = end_commit_num;
+                }
+                _ => reporter_slice.history.push(ReporterAuthorizationSlice {
+                    authorized: reporter.authorized,
+                    start_commit_num: reporter.start_commit_num,
+                    end_commit_num,
+                }),
+            }
+        }
+
+        Self {
+            record_id: record_id.to_string(),
+            properties,
+            service_id,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct StructPropertyValue {
     pub name: String,
@@ -515,7 +624,9 @@ fn parse_value(
 struct FetchRecordProperty {
     record_id: String,
     property_name: String,
+    as_of: Option<String>,
     service_id: Option<String>,
+    principal: Principal,
 }
 
 impl Message for FetchRecordProperty {
@@ -526,6 +637,8 @@ pub async fn fetch_record_property(
     state: web::Data<AppState>,
     params: web::Path<(String, String)>,
     query: web::Query<QueryServiceId>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     let (record_id, property_name) = params.into_inner();
@@ -534,21 +647,201 @@ pub async fn fetch_record_property(
         .send(FetchRecordProperty {
             record_id,
             property_name,
+            as_of: query_as_of.into_inner().as_of,
             service_id: query.into_inner().service_id,
+            principal,
         })
         .await?
         .map(|record| HttpResponse::Ok().json(record))
 }
 
+struct FetchRecordReporters {
+    record_id: String,
+    service_id: Option<String>,
+    principal: Principal,
+}
+
+impl Message for FetchRecordReporters {
+    type Result = Result<RecordReportersSlice, RestApiResponseError>;
+}
+
+impl Handler<FetchRecordReporters> for DbExecutor {
+    type Result = Result<RecordReportersSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchRecordReporters, _: &mut SyncContext<Self>) -> Self::Result {
+        if self
+            .tnt_store
+            .fetch_record(&msg.record_id, None, msg.service_id.as_deref())?
+            .is_none()
+        {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find record with id: {}",
+                msg.record_id
+            )));
+        }
+        self.check_record_access(
+            &msg.principal,
+            &msg.record_id,
+            None,
+            msg.service_id.as_deref(),
+        )?;
+
+        let reporters = self
+            .tnt_store
+            .list_reporter_history(&msg.record_id, msg.service_id.as_deref())?;
+
+        Ok(RecordReportersSlice::from_models(
+            &msg.record_id,
+            &reporters,
+            msg.service_id,
+        ))
+    }
+}
+
+pub async fn fetch_record_reporters(
+    state: web::Data<AppState>,
+    record_id: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchRecordReporters {
+            record_id: record_id.into_inner(),
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|reporters| HttpResponse::Ok().json(reporters))
+}
+
+/// A record along with the records aggregated into it, each with their own contents
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordContentsSlice {
+    pub record_id: String,
+    pub schema: String,
+    pub owner: String,
+    pub custodian: String,
+    pub r#final: bool,
+    pub contents: Vec<RecordContentsSlice>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+impl RecordContentsSlice {
+    pub fn from_model(record: Record, contents: Vec<RecordContentsSlice>) -> Self {
+        Self {
+            record_id: record.record_id,
+            schema: record.schema,
+            owner: record.owners.last().cloned().unwrap_or_default(),
+            custodian: record.custodians.last().cloned().unwrap_or_default(),
+            r#final: record.final_,
+            contents,
+            service_id: record.service_id,
+        }
+    }
+}
+
+struct FetchRecordContents {
+    record_id: String,
+    as_of: Option<String>,
+    service_id: Option<String>,
+    principal: Principal,
+}
+
+impl Message for FetchRecordContents {
+    type Result = Result<RecordContentsSlice, RestApiResponseError>;
+}
+
+impl Handler<FetchRecordContents> for DbExecutor {
+    type Result = Result<RecordContentsSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchRecordContents, _: &mut SyncContext<Self>) -> Self::Result {
+        let commit_height = self.commit_height(msg.as_of.as_deref())?;
+        // Aggregated records follow the custody of the record that contains them, so whoever
+        // may read the record may read its contents
+        self.check_record_access(
+            &msg.principal,
+            &msg.record_id,
+            commit_height,
+            msg.service_id.as_deref(),
+        )?;
+
+        let record = self
+            .tnt_store
+            .fetch_record(&msg.record_id, commit_height, msg.service_id.as_deref())?
+            .ok_or_else(|| {
+                RestApiResponseError::NotFoundError(format!(
+                    "Could not find record with id: {}",
+                    msg.record_id
+                ))
+            })?;
+
+        record_contents_slice(
+            &self.tnt_store,
+            record,
+            commit_height,
+            msg.service_id.as_deref(),
+        )
+    }
+}
+
+pub async fn fetch_record_contents(
+    state: web::Data<AppState>,
+    record_id: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    query_as_of: web::Query<QueryAsOf>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchRecordContents {
+            record_id: record_id.into_inner(),
+            as_of: query_as_of.into_inner().as_of,
+            service_id: query.into_inner().service_id,
+            principal,
+        })
+        .await?
+        .map(|contents| HttpResponse::Ok().json(contents))
+}
+
+/// Builds the tree of records aggregated, directly or through nested aggregation, into a record
+fn record_contents_slice(
+    store: &Arc<dyn TrackAndTraceStore>,
+    record: Record,
+    commit_height: Option<i64>,
+    service_id: Option<&str>,
+) -> Result<RecordContentsSlice, RestApiResponseError> {
+    let contents = store
+        .list_record_contents(&record.record_id, commit_height, service_id)?
+        .into_iter()
+        .map(|content| record_contents_slice(store, content, commit_height, service_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(RecordContentsSlice::from_model(record, contents))
+}
+
 impl Handler<FetchRecordProperty> for DbExecutor {
     type Result = Result<PropertySlice, RestApiResponseError>;
 
     fn handle(&mut self, msg: FetchRecordProperty, _: &mut SyncContext<Self>) -> Self::Result {
+        let commit_height = self.commit_height(msg.as_of.as_deref())?;
+        self.check_record_access(
+            &msg.principal,
+            &msg.record_id,
+            commit_height,
+            msg.service_id.as_deref(),
+        )?;
+
         let (property, data_type) = self
             .tnt_store
             .fetch_property_with_data_type(
                 &msg.record_id,
                 &msg.property_name,
+                commit_height,
                 msg.service_id.as_deref(),
             )?
             .ok_or_else(|| {
@@ -562,30 +855,87 @@ impl Handler<FetchRecordProperty> for DbExecutor {
             &self.tnt_store,
             &property,
             &data_type,
+            commit_height,
             msg.service_id.as_deref(),
         )
     }
 }
 
-fn parse_property_slice(
+/// Fetches a record with its properties, proposals and owner and custodian history as of the
+/// given commit height
+pub fn fetch_record_slice(
+    store: &Arc<dyn TrackAndTraceStore>,
+    record_id: &str,
+    commit_height: Option<i64>,
+    service_id: Option<&str>,
+) -> Result<Option<RecordSlice>, RestApiResponseError> {
+    let record = match store.fetch_record(record_id, commit_height, service_id)? {
+        Some(record) => record,
+        None => return Ok(None),
+    };
+
+    let record_ids = [record_id.to_string()];
+
+    let proposals = store.list_proposals(&record_ids, commit_height, service_id)?;
+
+    let properties = store
+        .list_properties_with_data_type(&record_ids, commit_height, service_id)?
+        .iter()
+        .map(|(property, data_type)| {
+            parse_property_slice(store, property, data_type, commit_height, service_id)
+        })
+        .collect::<Result<Vec<PropertySlice>, _>>()?;
+
+    let associated_agents = store.list_associated_agents(&record_ids, commit_height, service_id)?;
+
+    Ok(Some(RecordSlice::from_models(
+        record,
+        proposals,
+        associated_agents,
+        properties,
+    )))
+}
+
+/// Fetches the most recently reported value of a property as of the given commit height
+pub fn fetch_property_value_slice(
+    store: &Arc<dyn TrackAndTraceStore>,
+    record_id: &str,
+    property_name: &str,
+    commit_height: Option<i64>,
+    service_id: Option<&str>,
+) -> Result<Option<PropertyValueSlice>, RestApiResponseError> {
+    store
+        .fetch_reported_value_reporter_to_agent_metadata(
+            record_id,
+            property_name,
+            commit_height,
+            service_id,
+        )?
+        .map(|value| parse_reported_values(&value, service_id))
+        .transpose()
+}
+
+pub(super) fn parse_property_slice(
     store: &Arc<dyn TrackAndTraceStore>,
     property: &Property,
     data_type: &Option<String>,
+    commit_height: Option<i64>,
     service_id: Option<&str>,
 ) -> Result<PropertySlice, RestApiResponseError> {
-    let reporters = store.list_reporters(&property.record_id, &property.name, service_id)?;
-
-    let reported_value = store.fetch_reported_value_reporter_to_agent_metadata(
+    let reporters = store.list_reporters(
         &property.record_id,
         &property.name,
-        None,
+        commit_height,
         service_id,
     )?;
 
-    let property_value_slice = match reported_value {
-        Some(value) => Some(parse_reported_values(&value, service_id)?),
-        None => None,
-    };
+    let property_value_slice = fetch_property_value_slice(
+        store,
+        &property.record_id,
+        &property.name,
+        commit_height,
+        service_id,
+    )?;
 
     let active_reporters = reporters
         .iter()
@@ -602,6 +952,7 @@ fn parse_property_slice(
         .list_reported_value_reporter_to_agent_metadata(
             &property.record_id,
             &property.name,
+            commit_height,
             service_id,
         )?
         .iter()
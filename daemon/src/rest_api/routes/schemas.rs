@@ -17,7 +17,7 @@ use std::convert::TryFrom;
 use crate::rest_api::{
     error::RestApiResponseError,
     routes::{paging::Paging, DbExecutor},
-    AcceptServiceIdParam, AppState, QueryPaging, QueryServiceId,
+    AcceptServiceIdParam, AppState, QueryAsOf, QueryPaging, QueryServiceId,
 };
 
 use actix::{Handler, Message, SyncContext};
@@ -94,9 +94,11 @@ impl From<PropertyDefinition> for GridPropertyDefinitionSlice {
 }
 
 struct ListGridSchemas {
+    as_of: Option<String>,
     service_id: Option<String>,
     offset: u64,
     limit: u16,
+    cursor: Option<String>,
 }
 
 impl Message for ListGridSchemas {
@@ -111,9 +113,14 @@ impl Handler<ListGridSchemas> for DbExecutor {
 
         let limit = i64::try_from(msg.limit).unwrap_or(10);
 
-        let schema_list =
-            self.schema_store
-                .list_schemas(msg.service_id.as_deref(), offset, limit)?;
+        let page = self.page_query(msg.as_of.as_deref(), msg.cursor.as_deref(), offset, limit)?;
+        let commit_height = page.commit_height;
+
+        let schema_list = self.schema_store.list_schemas(
+            commit_height,
+            msg.service_id.as_deref(),
+            &page.request,
+        )?;
 
         let data = schema_list
             .data
@@ -121,7 +128,7 @@ impl Handler<ListGridSchemas> for DbExecutor {
             .map(GridSchemaSlice::from)
             .collect();
 
-        let paging = Paging::new("/schema", schema_list.paging, msg.service_id.as_deref());
+        let paging = page.paging("/schema", schema_list.paging, msg.service_id.as_deref());
 
         Ok(GridSchemaListSlice { data, paging })
     }
@@ -131,15 +138,18 @@ pub async fn list_grid_schemas(
     state: web::Data<AppState>,
     query_service_id: web::Query<QueryServiceId>,
     query_paging: web::Query<QueryPaging>,
+    query_as_of: web::Query<QueryAsOf>,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     let paging = query_paging.into_inner();
     state
         .database_connection
         .send(ListGridSchemas {
+            as_of: query_as_of.into_inner().as_of,
             service_id: query_service_id.into_inner().service_id,
             offset: paging.offset(),
             limit: paging.limit(),
+            cursor: paging.cursor,
         })
         .await?
         .map(|schemas| HttpResponse::Ok().json(schemas))
@@ -147,6 +157,7 @@ pub async fn list_grid_schemas(
 
 struct FetchGridSchema {
     name: String,
+    as_of: Option<String>,
     service_id: Option<String>,
 }
 
@@ -158,9 +169,11 @@ impl Handler<FetchGridSchema> for DbExecutor {
     type Result = Result<GridSchemaSlice, RestApiResponseError>;
 
     fn handle(&mut self, msg: FetchGridSchema, _: &mut SyncContext<Self>) -> Self::Result {
+        let commit_height = self.commit_height(msg.as_of.as_deref())?;
+
         match self
             .schema_store
-            .fetch_schema(&msg.name, msg.service_id.as_deref())?
+            .fetch_schema(&msg.name, commit_height, msg.service_id.as_deref())?
         {
             Some(schema) => Ok(GridSchemaSlice::from(schema)),
             None => Err(RestApiResponseError::NotFoundError(format!(
@@ -175,12 +188,14 @@ pub async fn fetch_grid_schema(
     state: web::Data<AppState>,
     schema_name: web::Path<String>,
     query: web::Query<QueryServiceId>,
+    query_as_of: web::Query<QueryAsOf>,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
     state
         .database_connection
         .send(FetchGridSchema {
             name: schema_name.into_inner(),
+            as_of: query_as_of.into_inner().as_of,
             service_id: query.into_inner().service_id,
         })
         .await?
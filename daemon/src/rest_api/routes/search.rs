@@ -0,0 +1,149 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use crate::rest_api::{
+    error::RestApiResponseError,
+    routes::{
+        paging::{list_link, Paging},
+        DbExecutor,
+    },
+    AcceptServiceIdParam, AppState, Principal, QueryPaging, QueryServiceId,
+};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+use grid_sdk::search::store::{SearchEntityType, SearchHit};
+use serde::{Deserialize, Serialize};
+
+/// The words to search for, and optionally the kind of entity to search
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuerySearch {
+    pub q: String,
+    #[serde(rename = "type")]
+    pub entity_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchHitSlice {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub title: String,
+    pub rank: f64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+impl From<SearchHit> for SearchHitSlice {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            entity_type: hit.entity_type.as_str().to_string(),
+            entity_id: hit.entity_id,
+            title: hit.title,
+            rank: hit.rank,
+            service_id: hit.service_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchHitListSlice {
+    pub data: Vec<SearchHitSlice>,
+    pub paging: Paging,
+}
+
+struct Search {
+    query: String,
+    entity_type: Option<SearchEntityType>,
+    service_id: Option<String>,
+    offset: u64,
+    limit: u16,
+    cursor: Option<String>,
+    principal: Principal,
+}
+
+impl Message for Search {
+    type Result = Result<SearchHitListSlice, RestApiResponseError>;
+}
+
+impl Handler<Search> for DbExecutor {
+    type Result = Result<SearchHitListSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: Search, _: &mut SyncContext<Self>) -> Self::Result {
+        let offset = i64::try_from(msg.offset).unwrap_or(i64::MAX);
+
+        let limit = i64::try_from(msg.limit).unwrap_or(10);
+
+        // The search index only holds current state, so a cursor's commit height is not read
+        let page = self.page_query(None, msg.cursor.as_deref(), offset, limit)?;
+
+        let hit_list = self.search_store.search(
+            &msg.query,
+            msg.entity_type,
+            msg.principal.org_id(),
+            msg.service_id.as_deref(),
+            &page.request,
+        )?;
+
+        let data = hit_list
+            .data
+            .into_iter()
+            .map(SearchHitSlice::from)
+            .collect();
+
+        let mut params = vec![("q".to_string(), msg.query)];
+        if let Some(entity_type) = msg.entity_type {
+            params.push(("type".to_string(), entity_type.as_str().to_string()));
+        }
+        let paging = page.paging(
+            &list_link("/search", &params),
+            hit_list.paging,
+            msg.service_id.as_deref(),
+        );
+
+        Ok(SearchHitListSlice { data, paging })
+    }
+}
+
+pub async fn search(
+    state: web::Data<AppState>,
+    query_search: web::Query<QuerySearch>,
+    query_service_id: web::Query<QueryServiceId>,
+    query_paging: web::Query<QueryPaging>,
+    principal: Principal,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let query_search = query_search.into_inner();
+    let entity_type = query_search
+        .entity_type
+        .map(|entity_type| entity_type.parse())
+        .transpose()
+        .map_err(|err| RestApiResponseError::BadRequest(format!("{}", err)))?;
+    let paging = query_paging.into_inner();
+    state
+        .database_connection
+        .send(Search {
+            query: query_search.q,
+            entity_type,
+            service_id: query_service_id.into_inner().service_id,
+            offset: paging.offset(),
+            limit: paging.limit(),
+            cursor: paging.cursor,
+            principal,
+        })
+        .await?
+        .map(|hits| HttpResponse::Ok().json(hits))
+}
@@ -0,0 +1,413 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::event::entity::EntityType;
+use crate::rest_api::{
+    error::RestApiResponseError,
+    routes::{paging::Paging, DbExecutor},
+    AppState, OperatorOnly, QueryPaging,
+};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+use grid_sdk::webhooks::{DeliveryStatus, WebhookDelivery, WebhookSubscription};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use url::Url;
+
+/// A webhook subscription as returned by the REST API. The secret is never returned.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookSubscriptionSlice {
+    pub id: i64,
+    pub url: String,
+    pub entity_types: Vec<String>,
+    pub owner_orgs: Vec<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+    pub created_at: i64,
+}
+
+impl From<WebhookSubscription> for WebhookSubscriptionSlice {
+    fn from(subscription: WebhookSubscription) -> Self {
+        Self {
+            id: subscription.id.unwrap_or_default(),
+            url: subscription.url,
+            entity_types: subscription.entity_types,
+            owner_orgs: subscription.owner_orgs,
+            service_id: subscription.service_id,
+            created_at: subscription.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookSubscriptionListSlice {
+    pub data: Vec<WebhookSubscriptionSlice>,
+    pub paging: Paging,
+}
+
+/// The body of a request to create a webhook subscription
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewWebhookSubscriptionSlice {
+    pub url: String,
+    pub secret: String,
+    #[serde(default)]
+    pub entity_types: Vec<String>,
+    #[serde(default)]
+    pub owner_orgs: Vec<String>,
+    #[serde(default)]
+    pub service_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookDeliverySlice {
+    pub id: i64,
+    pub subscription_id: i64,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: JsonValue,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: i64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: i64,
+}
+
+impl From<WebhookDelivery> for WebhookDeliverySlice {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id.unwrap_or_default(),
+            subscription_id: delivery.subscription_id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            payload: serde_json::from_str(&delivery.payload)
+                .unwrap_or(JsonValue::String(delivery.payload)),
+            status: delivery.status.as_str().to_string(),
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            last_error: delivery.last_error,
+            created_at: delivery.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookDeliveryListSlice {
+    pub data: Vec<WebhookDeliverySlice>,
+    pub paging: Paging,
+}
+
+struct AddWebhookSubscription {
+    subscription: NewWebhookSubscriptionSlice,
+}
+
+impl Message for AddWebhookSubscription {
+    type Result = Result<WebhookSubscriptionSlice, RestApiResponseError>;
+}
+
+impl Handler<AddWebhookSubscription> for DbExecutor {
+    type Result = Result<WebhookSubscriptionSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: AddWebhookSubscription, _: &mut SyncContext<Self>) -> Self::Result {
+        let new = msg.subscription;
+
+        let url = Url::parse(&new.url)
+            .map_err(|err| RestApiResponseError::BadRequest(format!("Invalid url: {}", err)))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(RestApiResponseError::BadRequest(format!(
+                "Invalid url: {}; expected an http or https url",
+                new.url
+            )));
+        }
+        if new.secret.is_empty() {
+            return Err(RestApiResponseError::BadRequest(
+                "A secret is required to sign deliveries".to_string(),
+            ));
+        }
+        for entity_type in &new.entity_types {
+            entity_type
+                .parse::<EntityType>()
+                .map_err(|err| RestApiResponseError::BadRequest(err.0))?;
+        }
+
+        let subscription = WebhookSubscription::new(
+            &new.url,
+            &new.secret,
+            new.entity_types,
+            new.owner_orgs,
+            new.service_id,
+        );
+        let id = self.webhook_store.add_subscription(subscription.clone())?;
+        info!("Added webhook subscription {} for {}", id, subscription.url);
+
+        Ok(WebhookSubscriptionSlice::from(WebhookSubscription {
+            id: Some(id),
+            ..subscription
+        }))
+    }
+}
+
+pub async fn add_webhook_subscription(
+    state: web::Data<AppState>,
+    subscription: web::Json<NewWebhookSubscriptionSlice>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(AddWebhookSubscription {
+            subscription: subscription.into_inner(),
+        })
+        .await?
+        .map(|subscription| HttpResponse::Created().json(subscription))
+}
+
+struct ListWebhookSubscriptions {
+    offset: u64,
+    limit: u16,
+}
+
+impl Message for ListWebhookSubscriptions {
+    type Result = Result<WebhookSubscriptionListSlice, RestApiResponseError>;
+}
+
+impl Handler<ListWebhookSubscriptions> for DbExecutor {
+    type Result = Result<WebhookSubscriptionListSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListWebhookSubscriptions, _: &mut SyncContext<Self>) -> Self::Result {
+        let offset = i64::try_from(msg.offset).unwrap_or(i64::MAX);
+
+        let limit = i64::from(msg.limit);
+
+        let subscription_list = self.webhook_store.list_subscriptions(offset, limit)?;
+
+        let data = subscription_list
+            .data
+            .into_iter()
+            .map(WebhookSubscriptionSlice::from)
+            .collect();
+
+        let paging = Paging::new("/admin/webhooks", subscription_list.paging, None);
+
+        Ok(WebhookSubscriptionListSlice { data, paging })
+    }
+}
+
+pub async fn list_webhook_subscriptions(
+    state: web::Data<AppState>,
+    query_paging: web::Query<QueryPaging>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let paging = query_paging.into_inner();
+    state
+        .database_connection
+        .send(ListWebhookSubscriptions {
+            offset: paging.offset(),
+            limit: paging.limit(),
+        })
+        .await?
+        .map(|subscriptions| HttpResponse::Ok().json(subscriptions))
+}
+
+struct FetchWebhookSubscription {
+    id: i64,
+}
+
+impl Message for FetchWebhookSubscription {
+    type Result = Result<WebhookSubscriptionSlice, RestApiResponseError>;
+}
+
+impl Handler<FetchWebhookSubscription> for DbExecutor {
+    type Result = Result<WebhookSubscriptionSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchWebhookSubscription, _: &mut SyncContext<Self>) -> Self::Result {
+        self.webhook_store
+            .fetch_subscription(msg.id)?
+            .map(WebhookSubscriptionSlice::from)
+            .ok_or_else(|| {
+                RestApiResponseError::NotFoundError(format!(
+                    "Could not find webhook subscription with id: {}",
+                    msg.id
+                ))
+            })
+    }
+}
+
+pub async fn fetch_webhook_subscription(
+    state: web::Data<AppState>,
+    id: web::Path<i64>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchWebhookSubscription {
+            id: id.into_inner(),
+        })
+        .await?
+        .map(|subscription| HttpResponse::Ok().json(subscription))
+}
+
+struct RemoveWebhookSubscription {
+    id: i64,
+}
+
+impl Message for RemoveWebhookSubscription {
+    type Result = Result<(), RestApiResponseError>;
+}
+
+impl Handler<RemoveWebhookSubscription> for DbExecutor {
+    type Result = Result<(), RestApiResponseError>;
+
+    fn handle(
+        &mut self,
+        msg: RemoveWebhookSubscription,
+        _: &mut SyncContext<Self>,
+    ) -> Self::Result {
+        self.webhook_store.remove_subscription(msg.id)?;
+        info!("Removed webhook subscription {}", msg.id);
+        Ok(())
+    }
+}
+
+pub async fn remove_webhook_subscription(
+    state: web::Data<AppState>,
+    id: web::Path<i64>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(RemoveWebhookSubscription {
+            id: id.into_inner(),
+        })
+        .await?
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+struct ListWebhookDeliveries {
+    subscription_id: i64,
+    offset: u64,
+    limit: u16,
+}
+
+impl Message for ListWebhookDeliveries {
+    type Result = Result<WebhookDeliveryListSlice, RestApiResponseError>;
+}
+
+impl Handler<ListWebhookDeliveries> for DbExecutor {
+    type Result = Result<WebhookDeliveryListSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListWebhookDeliveries, _: &mut SyncContext<Self>) -> Self::Result {
+        if self
+            .webhook_store
+            .fetch_subscription(msg.subscription_id)?
+            .is_none()
+        {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find webhook subscription with id: {}",
+                msg.subscription_id
+            )));
+        }
+
+        let offset = i64::try_from(msg.offset).unwrap_or(i64::MAX);
+
+        let limit = i64::from(msg.limit);
+
+        let delivery_list =
+            self.webhook_store
+                .list_deliveries(msg.subscription_id, offset, limit)?;
+
+        let data = delivery_list
+            .data
+            .into_iter()
+            .map(WebhookDeliverySlice::from)
+            .collect();
+
+        let paging = Paging::new(
+            &format!("/admin/webhooks/{}/deliveries", msg.subscription_id),
+            delivery_list.paging,
+            None,
+        );
+
+        Ok(WebhookDeliveryListSlice { data, paging })
+    }
+}
+
+pub async fn list_webhook_deliveries(
+    state: web::Data<AppState>,
+    subscription_id: web::Path<i64>,
+    query_paging: web::Query<QueryPaging>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let paging = query_paging.into_inner();
+    state
+        .database_connection
+        .send(ListWebhookDeliveries {
+            subscription_id: subscription_id.into_inner(),
+            offset: paging.offset(),
+            limit: paging.limit(),
+        })
+        .await?
+        .map(|deliveries| HttpResponse::Ok().json(deliveries))
+}
+
+struct RetryWebhookDelivery {
+    id: i64,
+}
+
+impl Message for RetryWebhookDelivery {
+    type Result = Result<(), RestApiResponseError>;
+}
+
+impl Handler<RetryWebhookDelivery> for DbExecutor {
+    type Result = Result<(), RestApiResponseError>;
+
+    fn handle(&mut self, msg: RetryWebhookDelivery, _: &mut SyncContext<Self>) -> Self::Result {
+        let mut delivery = self.webhook_store.fetch_delivery(msg.id)?.ok_or_else(|| {
+            RestApiResponseError::NotFoundError(format!(
+                "Could not find webhook delivery with id: {}",
+                msg.id
+            ))
+        })?;
+
+        // A retried delivery starts over with a full set of attempts
+        delivery.status = DeliveryStatus::Pending;
+        delivery.attempts = 0;
+        delivery.next_attempt_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0);
+        self.webhook_store.update_delivery(&delivery)?;
+        info!("Retrying webhook delivery {}", msg.id);
+        Ok(())
+    }
+}
+
+pub async fn retry_webhook_delivery(
+    state: web::Data<AppState>,
+    id: web::Path<i64>,
+    _: OperatorOnly,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(RetryWebhookDelivery {
+            id: id.into_inner(),
+        })
+        .await?
+        .map(|_| HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,157 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::rest_api::{
+    error::RestApiResponseError, routes::DbExecutor, AcceptServiceIdParam, AppState, QueryServiceId,
+};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+use grid_sdk::pike::store::Agent;
+use grid_sdk::workflow::{get_workflow, org_scoped_aliases, AllowedActions};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct QueryAgent {
+    pub agent: String,
+}
+
+/// The actions an agent may take on an entity in its current workflow state
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkflowActionsSlice {
+    pub workflow: String,
+    pub subworkflow: String,
+    pub state: String,
+    pub agent: String,
+    pub roles: Vec<String>,
+    pub transitions: Vec<String>,
+    pub permissions: Vec<String>,
+}
+
+impl WorkflowActionsSlice {
+    pub fn new(
+        workflow: &str,
+        subworkflow: &str,
+        state: &str,
+        agent: &str,
+        roles: Vec<String>,
+        actions: AllowedActions,
+    ) -> Self {
+        Self {
+            workflow: workflow.to_string(),
+            subworkflow: subworkflow.to_string(),
+            state: state.to_string(),
+            agent: agent.to_string(),
+            roles,
+            transitions: actions.transitions().to_vec(),
+            permissions: actions.permissions().to_vec(),
+        }
+    }
+}
+
+/// Returns the roles of an agent, or none if the agent is not active
+pub fn active_roles(agent: &Agent) -> Vec<String> {
+    if agent.active {
+        agent.roles.clone()
+    } else {
+        vec![]
+    }
+}
+
+/// Returns the actions allowed in a state of a registered workflow
+pub fn allowed_actions(
+    workflow: &str,
+    subworkflow: &str,
+    state: &str,
+    roles: &[String],
+) -> Result<AllowedActions, RestApiResponseError> {
+    get_workflow(workflow)
+        .ok_or_else(|| {
+            RestApiResponseError::NotFoundError(format!("Could not find workflow: {}", workflow))
+        })?
+        .allowed_actions(subworkflow, state, roles)
+        .ok_or_else(|| {
+            RestApiResponseError::NotFoundError(format!(
+                "Could not find state {} of subworkflow {} in workflow {}",
+                state, subworkflow, workflow
+            ))
+        })
+}
+
+struct FetchWorkflowActions {
+    workflow: String,
+    subworkflow: String,
+    state: String,
+    agent: String,
+    service_id: Option<String>,
+}
+
+impl Message for FetchWorkflowActions {
+    type Result = Result<WorkflowActionsSlice, RestApiResponseError>;
+}
+
+impl Handler<FetchWorkflowActions> for DbExecutor {
+    type Result = Result<WorkflowActionsSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchWorkflowActions, _: &mut SyncContext<Self>) -> Self::Result {
+        let agent = self
+            .pike_store
+            .fetch_agent(&msg.agent, None, msg.service_id.as_deref())?
+            .ok_or_else(|| {
+                RestApiResponseError::NotFoundError(format!(
+                    "Could not find agent with public key: {}",
+                    msg.agent
+                ))
+            })?;
+
+        // Without an entity there is no telling whether an organization's roles apply, so only
+        // the roles that apply regardless of organization are granted
+        let org_scoped = org_scoped_aliases(&msg.workflow);
+        let roles = active_roles(&agent)
+            .into_iter()
+            .filter(|role| !org_scoped.contains(&role.as_str()))
+            .collect::<Vec<_>>();
+        let actions = allowed_actions(&msg.workflow, &msg.subworkflow, &msg.state, &roles)?;
+
+        Ok(WorkflowActionsSlice::new(
+            &msg.workflow,
+            &msg.subworkflow,
+            &msg.state,
+            &msg.agent,
+            roles,
+            actions,
+        ))
+    }
+}
+
+pub async fn fetch_workflow_actions(
+    state: web::Data<AppState>,
+    params: web::Path<(String, String, String)>,
+    query_agent: web::Query<QueryAgent>,
+    query_service_id: web::Query<QueryServiceId>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let (workflow, subworkflow, workflow_state) = params.into_inner();
+    state
+        .database_connection
+        .send(FetchWorkflowActions {
+            workflow,
+            subworkflow,
+            state: workflow_state,
+            agent: query_agent.into_inner().agent,
+            service_id: query_service_id.into_inner().service_id,
+        })
+        .await?
+        .map(|actions| HttpResponse::Ok().json(actions))
+}
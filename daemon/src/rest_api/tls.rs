@@ -0,0 +1,702 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TLS termination for the REST API
+//!
+//! Given a certificate and key, the REST API serves HTTPS. Given a bundle of CAs as well, it
+//! requires every client to present a certificate signed by one of them. The common name of a
+//! client certificate may be mapped onto a scope, either an organization or a service, which
+//! then identifies the client in place of a bearer token; once any are mapped, clients whose
+//! certificates are not mapped are refused.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use actix_tls::openssl::SslStream;
+use actix_web::rt::net::TcpStream;
+use openssl::ex_data::Index;
+use openssl::nid::Nid;
+use openssl::ssl::{
+    ClientHelloResponse, Ssl, SslAcceptor, SslAcceptorBuilder, SslFiletype, SslMethod,
+    SslVerifyMode,
+};
+use openssl::x509::{X509Name, X509Ref};
+
+use crate::config::GridConfig;
+use crate::rest_api::{
+    auth::Principal,
+    error::{RestApiResponseError, RestApiServerError},
+};
+
+/// What a client with a mapped certificate may read
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientScope {
+    /// What belongs to the organization, as if the client were one of its agents
+    Organization(String),
+    /// Everything in the service, and nothing outside of it
+    Service(String),
+}
+
+impl ClientScope {
+    fn principal(&self) -> Principal {
+        match self {
+            ClientScope::Organization(org_id) => Principal::Organization {
+                org_id: org_id.clone(),
+            },
+            ClientScope::Service(service_id) => Principal::Service {
+                service_id: service_id.clone(),
+            },
+        }
+    }
+}
+
+impl FromStr for ClientScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("org", org_id)) if !org_id.is_empty() => {
+                Ok(ClientScope::Organization(org_id.to_string()))
+            }
+            Some(("service", service_id)) if !service_id.is_empty() => {
+                Ok(ClientScope::Service(service_id.to_string()))
+            }
+            _ => Err(format!(
+                "{} is not a valid scope; expected org:<org_id> or service:<service_id>",
+                s
+            )),
+        }
+    }
+}
+
+/// The TLS settings the REST API is served with
+pub struct TlsSettings {
+    acceptor: SslAcceptorBuilder,
+    client_scopes: Option<ClientScopes>,
+}
+
+impl TlsSettings {
+    /// Loads the certificate and key, and the client CAs if any, that the REST API is served
+    /// with
+    ///
+    /// Each client scope is given as `<common name>=<scope>`.
+    pub fn new(
+        cert_file: &str,
+        key_file: &str,
+        client_ca_file: Option<&str>,
+        client_scopes: &[String],
+    ) -> Result<Self, RestApiServerError> {
+        let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls_server())
+            .map_err(|err| tls_error("Unable to create TLS acceptor", err))?;
+        acceptor
+            .set_certificate_chain_file(cert_file)
+            .map_err(|err| tls_error(&format!("Unable to load certificate {}", cert_file), err))?;
+        acceptor
+            .set_private_key_file(key_file, SslFiletype::PEM)
+            .map_err(|err| tls_error(&format!("Unable to load private key {}", key_file), err))?;
+        acceptor
+            .check_private_key()
+            .map_err(|err| tls_error("Private key does not match certificate", err))?;
+
+        let client_scopes = match client_ca_file {
+            Some(client_ca_file) => {
+                acceptor.set_ca_file(client_ca_file).map_err(|err| {
+                    tls_error(
+                        &format!("Unable to load client CAs {}", client_ca_file),
+                        err,
+                    )
+                })?;
+                acceptor.set_client_ca_list(
+                    X509Name::load_client_ca_file(client_ca_file).map_err(|err| {
+                        tls_error(
+                            &format!("Unable to load client CAs {}", client_ca_file),
+                            err,
+                        )
+                    })?,
+                );
+                // Certificates are required during the handshake, before any route is matched,
+                // so the health routes require one as well
+                acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+
+                if client_scopes.is_empty() {
+                    None
+                } else {
+                    let client_scopes = ClientScopes::new(parse_client_scopes(client_scopes)?)?;
+                    client_scopes.track_connections(&mut acceptor);
+                    Some(client_scopes)
+                }
+            }
+            None if client_scopes.is_empty() => None,
+            None => {
+                return Err(RestApiServerError::StartUpError(
+                    "Client scopes require client CAs to verify certificates with".into(),
+                ))
+            }
+        };
+
+        Ok(TlsSettings {
+            acceptor,
+            client_scopes,
+        })
+    }
+
+    /// Returns the TLS settings the REST API is configured with, or `None` if it serves plain
+    /// HTTP
+    pub fn from_config(config: &GridConfig) -> Result<Option<Self>, RestApiServerError> {
+        match (config.tls_cert_file(), config.tls_key_file()) {
+            (Some(cert_file), Some(key_file)) => TlsSettings::new(
+                cert_file,
+                key_file,
+                config.tls_client_ca_file(),
+                config.tls_client_scopes(),
+            )
+            .map(Some),
+            (None, None) => Ok(None),
+            _ => Err(RestApiServerError::StartUpError(
+                "A TLS certificate and key must be given together".into(),
+            )),
+        }
+    }
+
+    /// Returns the scopes of client certificates, if any are mapped
+    pub fn client_scopes(&self) -> Option<ClientScopes> {
+        self.client_scopes.clone()
+    }
+
+    pub fn into_acceptor(self) -> SslAcceptorBuilder {
+        self.acceptor
+    }
+}
+
+fn tls_error(context: &str, err: openssl::error::ErrorStack) -> RestApiServerError {
+    RestApiServerError::StartUpError(format!("{}: {}", context, err))
+}
+
+fn parse_client_scopes(
+    client_scopes: &[String],
+) -> Result<HashMap<String, ClientScope>, RestApiServerError> {
+    client_scopes
+        .iter()
+        .map(|client_scope| {
+            let (common_name, scope) = client_scope.split_once('=').ok_or_else(|| {
+                RestApiServerError::StartUpError(format!(
+                    "{} is not a valid client scope; expected <common name>=<scope>",
+                    client_scope
+                ))
+            })?;
+            let scope = scope.parse().map_err(RestApiServerError::StartUpError)?;
+            Ok((common_name.to_string(), scope))
+        })
+        .collect()
+}
+
+/// The common names of the certificates of open connections, by peer address, along with the
+/// ID of the connection
+type OpenConnections = Arc<Mutex<HashMap<SocketAddr, (u64, String)>>>;
+
+/// The scopes of clients, by the common names of their certificates
+///
+/// Each connection's TLS session holds an `OpenConnection`, which is dropped with the session
+/// when the connection closes. actix-web only hands connection data to the first request on a
+/// connection, so while it is open, the connection's common name is also listed by its peer
+/// address, which no other open connection shares.
+#[derive(Clone)]
+pub struct ClientScopes {
+    scopes: Arc<HashMap<String, ClientScope>>,
+    connections: OpenConnections,
+    next_connection_id: Arc<AtomicU64>,
+    index: Index<Ssl, OpenConnection>,
+}
+
+impl ClientScopes {
+    fn new(scopes: HashMap<String, ClientScope>) -> Result<Self, RestApiServerError> {
+        Ok(ClientScopes {
+            scopes: Arc::new(scopes),
+            connections: Arc::new(Mutex::new(HashMap::new())),
+            next_connection_id: Arc::new(AtomicU64::new(0)),
+            index: Ssl::new_ex_index()
+                .map_err(|err| tls_error("Unable to index client connections", err))?,
+        })
+    }
+
+    /// Gives the TLS session of each new connection an `OpenConnection` to list its certificate
+    /// with
+    fn track_connections(&self, acceptor: &mut SslAcceptorBuilder) {
+        let client_scopes = self.clone();
+        acceptor.set_client_hello_callback(move |ssl, _| {
+            ssl.set_ex_data(client_scopes.index, client_scopes.open_connection());
+            Ok(ClientHelloResponse::SUCCESS)
+        });
+    }
+
+    fn open_connection(&self) -> OpenConnection {
+        OpenConnection {
+            id: self.next_connection_id.fetch_add(1, Ordering::Relaxed),
+            connections: self.connections.clone(),
+            peer_addr: Mutex::new(None),
+        }
+    }
+
+    /// Lists the certificate presented on a new connection until the connection closes; to be
+    /// passed to `HttpServer::on_connect`
+    pub fn on_connect(&self, connection: &dyn Any) {
+        let stream = match connection.downcast_ref::<SslStream<TcpStream>>() {
+            Some(stream) => stream,
+            None => return,
+        };
+        let peer_addr = match stream.get_ref().peer_addr() {
+            Ok(peer_addr) => peer_addr,
+            Err(err) => {
+                error!("Unable to get peer address of connection: {}", err);
+                return;
+            }
+        };
+        let common_name = match stream
+            .ssl()
+            .peer_certificate()
+            .and_then(|cert| common_name(&cert))
+        {
+            Some(common_name) => common_name,
+            None => return,
+        };
+
+        match stream.ssl().ex_data(self.index) {
+            Some(connection) => connection.list(peer_addr, common_name),
+            None => error!("Connection from {} has no TLS session to track", peer_addr),
+        }
+    }
+
+    /// Returns the principal of a request, from the scope the certificate of the connection it
+    /// came in on is mapped to
+    ///
+    /// Requests over connections whose certificates are not mapped, and requests outside of a
+    /// service scope, are forbidden.
+    pub fn principal(
+        &self,
+        peer_addr: Option<SocketAddr>,
+        service_id: Option<&str>,
+    ) -> Result<Principal, RestApiResponseError> {
+        let common_name = peer_addr
+            .and_then(|peer_addr| {
+                self.connections.lock().ok().and_then(|connections| {
+                    connections
+                        .get(&peer_addr)
+                        .map(|(_, common_name)| common_name.clone())
+                })
+            })
+            .ok_or_else(|| {
+                RestApiResponseError::Unauthorized(
+                    "No verified client certificate for this connection".into(),
+                )
+            })?;
+
+        match self.scopes.get(&common_name) {
+            Some(ClientScope::Service(scope_service_id))
+                if service_id != Some(scope_service_id.as_str()) =>
+            {
+                Err(RestApiResponseError::Forbidden(format!(
+                    "Client certificate is limited to service {}",
+                    scope_service_id
+                )))
+            }
+            Some(scope) => Ok(scope.principal()),
+            None => Err(RestApiResponseError::Forbidden(format!(
+                "Client certificate {} is not mapped to a scope",
+                common_name
+            ))),
+        }
+    }
+}
+
+/// A connection whose certificate's common name is listed by its peer address until it closes
+struct OpenConnection {
+    id: u64,
+    connections: OpenConnections,
+    peer_addr: Mutex<Option<SocketAddr>>,
+}
+
+impl OpenConnection {
+    fn list(&self, peer_addr: SocketAddr, common_name: String) {
+        match (self.peer_addr.lock(), self.connections.lock()) {
+            (Ok(mut own_peer_addr), Ok(mut connections)) => {
+                *own_peer_addr = Some(peer_addr);
+                connections.insert(peer_addr, (self.id, common_name));
+            }
+            _ => error!("Client connections lock was poisoned"),
+        }
+    }
+}
+
+impl Drop for OpenConnection {
+    fn drop(&mut self) {
+        let peer_addr = match self.peer_addr.get_mut() {
+            Ok(Some(peer_addr)) => *peer_addr,
+            _ => return,
+        };
+
+        // A new connection from the same address may already have replaced the entry
+        if let Ok(mut connections) = self.connections.lock() {
+            if connections.get(&peer_addr).map(|(id, _)| *id) == Some(self.id) {
+                connections.remove(&peer_addr);
+            }
+        }
+    }
+}
+
+fn common_name(cert: &X509Ref) -> Option<String> {
+    cert.subject_name()
+        .entries_by_nid(Nid::COMMONNAME)
+        .next()
+        .and_then(|entry| entry.data().to_string().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use actix_web::{
+        client::{Client, Connector},
+        web, App, HttpServer,
+    };
+    use openssl::asn1::Asn1Time;
+    use openssl::bn::{BigNum, MsbOption};
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::{PKey, Private};
+    use openssl::ssl::SslConnector;
+    use openssl::x509::extension::{BasicConstraints, ExtendedKeyUsage, SubjectAlternativeName};
+    use openssl::x509::{X509NameBuilder, X509};
+
+    use crate::rest_api::auth::Authenticate;
+
+    const SERVICE_ID: &str = "01234-ABCDE::gsAA";
+
+    /// Verifies that client scopes parse from `org:` and `service:` prefixes only
+    #[test]
+    fn test_client_scope_from_str() {
+        assert_eq!(
+            "org:acme001".parse(),
+            Ok(ClientScope::Organization("acme001".into()))
+        );
+        assert_eq!(
+            format!("service:{}", SERVICE_ID).parse(),
+            Ok(ClientScope::Service(SERVICE_ID.into()))
+        );
+        for scope in &["acme001", "org:", "agent:acme001"] {
+            assert!(scope.parse::<ClientScope>().is_err());
+        }
+    }
+
+    /// Verifies that a connection's certificate maps onto its scope only while the connection
+    /// is open, and that closing a connection leaves the entry of a newer one from the same
+    /// address in place
+    #[test]
+    fn test_open_connections() {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 1));
+        let mut scopes = HashMap::new();
+        scopes.insert(
+            "acme-gateway".to_string(),
+            ClientScope::Organization("acme001".into()),
+        );
+        let client_scopes = ClientScopes::new(scopes).expect("Unable to create client scopes");
+        let organization = Principal::Organization {
+            org_id: "acme001".into(),
+        };
+
+        let first = client_scopes.open_connection();
+        first.list(addr, "acme-gateway".into());
+        assert_eq!(
+            client_scopes
+                .principal(Some(addr), None)
+                .expect("Unable to get principal"),
+            organization
+        );
+        drop(first);
+        assert!(matches!(
+            client_scopes.principal(Some(addr), None),
+            Err(RestApiResponseError::Unauthorized(_))
+        ));
+
+        let second = client_scopes.open_connection();
+        let third = client_scopes.open_connection();
+        second.list(addr, "unmapped".into());
+        assert!(matches!(
+            client_scopes.principal(Some(addr), None),
+            Err(RestApiResponseError::Forbidden(_))
+        ));
+        third.list(addr, "acme-gateway".into());
+        drop(second);
+        assert_eq!(
+            client_scopes
+                .principal(Some(addr), None)
+                .expect("Unable to get principal"),
+            organization
+        );
+        drop(third);
+        assert!(client_scopes.connections.lock().unwrap().is_empty());
+    }
+
+    /// Verifies that a certificate that does not match its key is rejected, as are client
+    /// scopes without client CAs to verify certificates with
+    #[test]
+    fn test_tls_settings_invalid() {
+        let dir = TempDir::new();
+        let ca = Identity::ca(&dir, "Test CA");
+        let server = Identity::issued_by(&dir, &ca, "localhost");
+        let other = Identity::issued_by(&dir, &ca, "other");
+
+        assert!(TlsSettings::new(&server.cert_file(), &server.key_file(), None, &[]).is_ok());
+        assert!(TlsSettings::new(&server.cert_file(), &other.key_file(), None, &[]).is_err());
+        assert!(TlsSettings::new(
+            &server.cert_file(),
+            &server.key_file(),
+            None,
+            &["other=org:acme001".to_string()]
+        )
+        .is_err());
+        assert!(TlsSettings::new(
+            &server.cert_file(),
+            &server.key_file(),
+            Some(&ca.cert_file()),
+            &["other".to_string()]
+        )
+        .is_err());
+    }
+
+    /// Verifies that, with client CAs, the REST API refuses clients without a certificate, maps
+    /// the certificates of other clients onto their scopes across requests on the same
+    /// connection, and forbids clients with unmapped certificates
+    #[actix_rt::test]
+    async fn test_mutual_tls() {
+        let dir = TempDir::new();
+        let ca = Identity::ca(&dir, "Test CA");
+        let server = Identity::issued_by(&dir, &ca, "localhost");
+        let tls = TlsSettings::new(
+            &server.cert_file(),
+            &server.key_file(),
+            Some(&ca.cert_file()),
+            &[
+                "acme-gateway=org:acme001".to_string(),
+                format!("auditor=service:{}", SERVICE_ID),
+            ],
+        )
+        .expect("Unable to load TLS settings");
+
+        let client_scopes = tls.client_scopes();
+        let on_connect_scopes = client_scopes.clone().expect("Client scopes not mapped");
+        let http_server = HttpServer::new(move || {
+            App::new()
+                .wrap(Authenticate::new(None).with_client_scopes(client_scopes.clone()))
+                .route(
+                    "/principal",
+                    web::get().to(|principal: Principal| async move { format!("{:?}", principal) }),
+                )
+        })
+        .on_connect(move |connection, _| on_connect_scopes.on_connect(connection))
+        .bind_openssl("127.0.0.1:0", tls.into_acceptor())
+        .expect("Unable to bind server");
+        let port = http_server.addrs()[0].port();
+        let srv = http_server.run();
+        let url = |path: &str| format!("https://localhost:{}{}", port, path);
+
+        let gateway = client(&ca, Some(&Identity::issued_by(&dir, &ca, "acme-gateway")));
+        for _ in 0..2 {
+            let mut response = gateway.get(url("/principal")).send().await.unwrap();
+            assert!(response.status().is_success());
+            assert_eq!(
+                &*response.body().await.unwrap(),
+                br#"Organization { org_id: "acme001" }"#
+            );
+        }
+
+        let auditor = client(&ca, Some(&Identity::issued_by(&dir, &ca, "auditor")));
+        let response = auditor.get(url("/principal")).send().await.unwrap();
+        assert_eq!(response.status(), 403);
+        let mut response = auditor
+            .get(url(&format!("/principal?service_id={}", SERVICE_ID)))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(
+            &*response.body().await.unwrap(),
+            format!(r#"Service {{ service_id: "{}" }}"#, SERVICE_ID).as_bytes()
+        );
+
+        let unmapped = client(&ca, Some(&Identity::issued_by(&dir, &ca, "unmapped")));
+        let response = unmapped.get(url("/principal")).send().await.unwrap();
+        assert_eq!(response.status(), 403);
+
+        let anonymous = client(&ca, None);
+        assert!(anonymous.get(url("/principal")).send().await.is_err());
+
+        let rogue_ca = Identity::ca(&dir, "Rogue CA");
+        let rogue = client(
+            &ca,
+            Some(&Identity::issued_by(&dir, &rogue_ca, "acme-gateway")),
+        );
+        assert!(rogue.get(url("/principal")).send().await.is_err());
+
+        srv.stop(true).await;
+    }
+
+    /// Returns a client that trusts the CA and presents the identity's certificate, if any
+    fn client(ca: &Identity, identity: Option<&Identity>) -> Client {
+        let mut connector =
+            SslConnector::builder(SslMethod::tls()).expect("Unable to create connector");
+        connector
+            .set_ca_file(ca.cert_file())
+            .expect("Unable to trust CA");
+        if let Some(identity) = identity {
+            connector
+                .set_certificate(&identity.cert)
+                .expect("Unable to set certificate");
+            connector
+                .set_private_key(&identity.key)
+                .expect("Unable to set private key");
+        }
+
+        Client::builder()
+            .connector(Connector::new().ssl(connector.build()).finish())
+            .finish()
+    }
+
+    /// A generated key and self-signed, or CA-issued, certificate, written to PEM files
+    struct Identity {
+        cert: X509,
+        key: PKey<Private>,
+        path: PathBuf,
+    }
+
+    impl Identity {
+        fn ca(dir: &TempDir, common_name: &str) -> Self {
+            Identity::new(dir, common_name, None)
+        }
+
+        fn issued_by(dir: &TempDir, issuer: &Identity, common_name: &str) -> Self {
+            Identity::new(dir, common_name, Some(issuer))
+        }
+
+        fn new(dir: &TempDir, common_name: &str, issuer: Option<&Identity>) -> Self {
+            let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+            let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+            let mut name = X509NameBuilder::new().unwrap();
+            name.append_entry_by_nid(Nid::COMMONNAME, common_name)
+                .unwrap();
+            let name = name.build();
+
+            let mut serial = BigNum::new().unwrap();
+            serial.rand(64, MsbOption::MAYBE_ZERO, false).unwrap();
+
+            let mut builder = X509::builder().unwrap();
+            builder.set_version(2).unwrap();
+            builder
+                .set_serial_number(&serial.to_asn1_integer().unwrap())
+                .unwrap();
+            builder.set_subject_name(&name).unwrap();
+            builder
+                .set_issuer_name(
+                    issuer
+                        .map(|issuer| issuer.cert.subject_name())
+                        .unwrap_or(&name),
+                )
+                .unwrap();
+            builder.set_pubkey(&key).unwrap();
+            builder
+                .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+                .unwrap();
+            builder
+                .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+                .unwrap();
+
+            if issuer.is_none() {
+                builder
+                    .append_extension(BasicConstraints::new().critical().ca().build().unwrap())
+                    .unwrap();
+            } else {
+                builder
+                    .append_extension(
+                        ExtendedKeyUsage::new()
+                            .server_auth()
+                            .client_auth()
+                            .build()
+                            .unwrap(),
+                    )
+                    .unwrap();
+                let san = SubjectAlternativeName::new()
+                    .dns(common_name)
+                    .build(&builder.x509v3_context(issuer.map(|issuer| &*issuer.cert), None))
+                    .unwrap();
+                builder.append_extension(san).unwrap();
+            }
+
+            let signing_key = issuer.map(|issuer| &issuer.key).unwrap_or(&key);
+            builder.sign(signing_key, MessageDigest::sha256()).unwrap();
+            let cert = builder.build();
+
+            let path = dir.path().join(common_name.replace(' ', "_"));
+            fs::write(path.with_extension("crt"), cert.to_pem().unwrap()).unwrap();
+            fs::write(
+                path.with_extension("key"),
+                key.private_key_to_pem_pkcs8().unwrap(),
+            )
+            .unwrap();
+
+            Identity { cert, key, path }
+        }
+
+        fn cert_file(&self) -> String {
+            self.path
+                .with_extension("crt")
+                .to_string_lossy()
+                .into_owned()
+        }
+
+        fn key_file(&self) -> String {
+            self.path
+                .with_extension("key")
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+
+    /// A directory for generated certificates that is removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("gridd-tls-{}", uuid::Uuid::new_v4()));
+            fs::create_dir_all(&path).expect("Unable to create temp dir");
+            TempDir(path)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+}
@@ -17,15 +17,22 @@ use std::time::Duration;
 
 use futures::prelude::*;
 use sawtooth_sdk::messages::batch::Batch;
+use sawtooth_sdk::messages::block::BlockHeader;
 use sawtooth_sdk::messages::client_batch_submit::{
     ClientBatchStatusRequest, ClientBatchStatusResponse, ClientBatchStatusResponse_Status,
     ClientBatchSubmitRequest, ClientBatchSubmitResponse, ClientBatchSubmitResponse_Status,
 };
+use sawtooth_sdk::messages::client_block::{
+    ClientBlockListRequest, ClientBlockListResponse, ClientBlockListResponse_Status,
+};
+use sawtooth_sdk::messages::client_list_control::ClientPagingControls;
 use sawtooth_sdk::messages::validator::Message_MessageType;
 use sawtooth_sdk::messaging::stream::MessageSender;
 use sawtooth_sdk::messaging::zmq_stream::ZmqMessageSender;
 use uuid::Uuid;
 
+#[cfg(feature = "metrics")]
+use crate::metrics;
 use crate::rest_api::error::RestApiResponseError;
 use crate::submitter::{
     BatchStatus, BatchStatusLink, BatchStatuses, BatchSubmitter, SubmitBatches, DEFAULT_TIME_OUT,
@@ -51,8 +58,8 @@ macro_rules! try_fut {
     };
 }
 
-impl BatchSubmitter for SawtoothBatchSubmitter {
-    fn submit_batches(
+impl SawtoothBatchSubmitter {
+    fn submit(
         &self,
         msg: SubmitBatches,
     ) -> Pin<Box<dyn Future<Output = Result<BatchStatusLink, RestApiResponseError>> + Send>> {
@@ -87,6 +94,22 @@ impl BatchSubmitter for SawtoothBatchSubmitter {
         )
         .boxed()
     }
+}
+
+impl BatchSubmitter for SawtoothBatchSubmitter {
+    fn submit_batches(
+        &self,
+        msg: SubmitBatches,
+    ) -> Pin<Box<dyn Future<Output = Result<BatchStatusLink, RestApiResponseError>> + Send>> {
+        #[cfg(feature = "metrics")]
+        {
+            metrics::record_submission("sawtooth", || self.submit(msg))
+        }
+        #[cfg(not(feature = "metrics"))]
+        {
+            self.submit(msg)
+        }
+    }
 
     fn batch_status(
         &self,
@@ -113,11 +136,55 @@ impl BatchSubmitter for SawtoothBatchSubmitter {
         future::ready(process_batch_status_response(response_status)).boxed()
     }
 
+    fn chain_head_height(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<u64>, RestApiResponseError>> + Send>> {
+        future::ready(query_chain_head_height(&self.sender)).boxed()
+    }
+
     fn clone_box(&self) -> Box<dyn BatchSubmitter> {
         Box::new(self.clone())
     }
 }
 
+/// Asks the validator for its chain head, returning the head's block number, or `None` if the
+/// chain has no blocks yet
+pub fn query_chain_head_height<MS: MessageSender>(
+    sender: &MS,
+) -> Result<Option<u64>, RestApiResponseError> {
+    let mut paging = ClientPagingControls::new();
+    paging.set_limit(1);
+    let mut block_list_request = ClientBlockListRequest::new();
+    block_list_request.set_paging(paging);
+
+    let response: ClientBlockListResponse = query_validator(
+        sender,
+        Message_MessageType::CLIENT_BLOCK_LIST_REQUEST,
+        &block_list_request,
+    )?;
+
+    match response.get_status() {
+        ClientBlockListResponse_Status::OK => response
+            .get_blocks()
+            .first()
+            .map(|block| {
+                protobuf::Message::parse_from_bytes(block.get_header())
+                    .map(|header: BlockHeader| header.get_block_num())
+                    .map_err(|err| {
+                        RestApiResponseError::RequestHandlerError(format!(
+                            "Failed to parse block header from bytes. {}",
+                            err
+                        ))
+                    })
+            })
+            .transpose(),
+        ClientBlockListResponse_Status::NO_RESOURCE => Ok(None),
+        status => Err(RestApiResponseError::SawtoothValidatorResponseError(
+            format!("Validator responded with error {:?}", status),
+        )),
+    }
+}
+
 pub fn query_validator<T: protobuf::Message, C: protobuf::Message, MS: MessageSender>(
     sender: &MS,
     message_type: Message_MessageType,
@@ -16,20 +16,43 @@
  */
 
 use sawtooth_sdk::messaging::{
-    stream::{MessageConnection, MessageReceiver},
+    stream::{MessageConnection, MessageReceiver, MessageSender},
     zmq_stream::{ZmqMessageConnection, ZmqMessageSender},
 };
 
 pub struct SawtoothConnection {
+    validator_address: String,
     sender: ZmqMessageSender,
     receiver: MessageReceiver,
+    reopened: bool,
 }
 
 impl SawtoothConnection {
     pub fn new(validator_address: &str) -> SawtoothConnection {
         let zmq_connection = ZmqMessageConnection::new(&validator_address);
         let (sender, receiver) = zmq_connection.create();
-        SawtoothConnection { sender, receiver }
+        SawtoothConnection {
+            validator_address: validator_address.to_string(),
+            sender,
+            receiver,
+            reopened: false,
+        }
+    }
+
+    /// Replaces the sender and receiver with a new connection to the same validator
+    pub fn reopen(&mut self) {
+        self.sender.close();
+
+        let zmq_connection = ZmqMessageConnection::new(&self.validator_address);
+        let (sender, receiver) = zmq_connection.create();
+        self.sender = sender;
+        self.receiver = receiver;
+        self.reopened = true;
+    }
+
+    /// Whether the connection has been reopened since it was created
+    pub fn is_reopened(&self) -> bool {
+        self.reopened
     }
 
     pub fn get_sender(&self) -> ZmqMessageSender {
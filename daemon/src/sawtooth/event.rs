@@ -56,6 +56,7 @@ const BLOCK_ID_ATTR: &str = "block_id";
 const BLOCK_NUM_ATTR: &str = "block_num";
 
 const SHUTDOWN_TIMEOUT: u64 = 2;
+const SUBSCRIBE_TIMEOUT: u64 = 10;
 
 impl EventConnection for SawtoothConnection {
     type Unsubscriber = SawtoothEventUnsubscriber;
@@ -84,9 +85,17 @@ impl EventConnection for SawtoothConnection {
             })?,
         )?;
 
+        // A reopened connection may be waiting on a validator that is still down, so the
+        // request times out to let the event processor retry it
+        let response_message = if self.is_reopened() {
+            future.get_timeout(Duration::from_secs(SUBSCRIBE_TIMEOUT))?
+        } else {
+            future.get()?
+        };
+
         let response: ClientEventsSubscribeResponse = content_of_type(
             Message_MessageType::CLIENT_EVENTS_SUBSCRIBE_RESPONSE,
-            future.get()?,
+            response_message,
         )?;
 
         if response.get_status() != ClientEventsSubscribeResponse_Status::OK {
@@ -138,6 +147,12 @@ impl EventConnection for SawtoothConnection {
         }
     }
 
+    fn reconnect(&mut self) -> Result<(), EventIoError> {
+        self.reopen();
+
+        Ok(())
+    }
+
     fn close(self) -> Result<(), EventIoError> {
         self.get_sender().close();
 
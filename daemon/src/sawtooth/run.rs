@@ -18,16 +18,27 @@
 
 use std::process;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
+use grid_sdk::dead_letters::DieselDeadLetterStore;
 #[cfg(feature = "integration")]
 use grid_sdk::rest_api::actix_web_3::State as IntegrationState;
+#[cfg(any(feature = "change-stream", feature = "webhooks"))]
+use grid_sdk::store::StoreFactory;
 use grid_sdk::store::{create_store_factory, ConnectionUri};
 
+#[cfg(feature = "change-stream")]
+use crate::changes::{ChangeBroadcaster, ChangeLogEventHandler};
 use crate::config::GridConfig;
-use crate::database::{ConnectionPool, DatabaseError};
+use crate::database::ConnectionPool;
 use crate::error::DaemonError;
-use crate::event::{db_handler::DatabaseEventHandler, EventProcessor};
+use crate::event::{
+    db_handler::DatabaseEventHandler, EventHandler, EventProcessor, FailurePolicy, IndexingStatus,
+    ResumeFrom,
+};
 use crate::rest_api;
+#[cfg(feature = "webhooks")]
+use crate::webhooks::{WebhookDeliverer, WebhookEventHandler};
 
 use super::{batch_submitter::SawtoothBatchSubmitter, connection::SawtoothConnection};
 
@@ -44,57 +55,86 @@ pub fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
     let batch_submitter = Box::new(SawtoothBatchSubmitter::new(
         sawtooth_connection.get_sender(),
     ));
+    let indexing_status = IndexingStatus::new();
+    #[cfg(feature = "change-stream")]
+    let change_broadcaster = ChangeBroadcaster::new();
     let (db_executor, evt_processor) = {
-        let commit_store = store_factory.get_grid_commit_store();
-        let current_commit =
-            commit_store
-                .get_current_commit_id()
-                .map_err(|err| DatabaseError::ConnectionError {
-                    context: "Could not get current commit ID".to_string(),
-                    source: Box::new(err),
-                })?;
+        let resume_from = ResumeFrom::CommitStore(store_factory.get_grid_commit_store());
 
         match connection_uri {
             ConnectionUri::Postgres(_) => {
                 let connection_pool: ConnectionPool<diesel::pg::PgConnection> =
-                    ConnectionPool::new(config.database_url())?;
+                    ConnectionPool::with_max_size(
+                        config.database_url(),
+                        config.database_pool_size(),
+                    )?;
                 let evt_processor = EventProcessor::start(
                     sawtooth_connection,
-                    current_commit.as_deref(),
-                    event_handlers![DatabaseEventHandler::from_pg_pool(connection_pool.clone())],
+                    resume_from,
+                    event_handlers(
+                        DatabaseEventHandler::from_pg_pool(connection_pool.clone()),
+                        #[cfg(any(feature = "change-stream", feature = "webhooks"))]
+                        &*store_factory,
+                        #[cfg(feature = "change-stream")]
+                        change_broadcaster.clone(),
+                    ),
+                    FailurePolicy::new(config.event_retries(), config.event_failure_mode())
+                        .with_dead_letter_store(Arc::new(DieselDeadLetterStore::new(
+                            connection_pool.pool.clone(),
+                        ))),
+                    indexing_status.clone(),
                 )
                 .map_err(|err| DaemonError::EventProcessorError(Box::new(err)))?;
 
                 (
-                    rest_api::DbExecutor::from_pg_pool(connection_pool),
+                    rest_api::DbExecutor::from_pg_pool(connection_pool)
+                        .with_indexing_status(indexing_status)
+                        .with_max_commit_lag(config.max_commit_lag()),
                     evt_processor,
                 )
             }
             ConnectionUri::Sqlite(_) => {
                 let connection_pool: ConnectionPool<diesel::sqlite::SqliteConnection> =
-                    ConnectionPool::new(config.database_url())?;
+                    ConnectionPool::with_max_size(
+                        config.database_url(),
+                        config.database_pool_size(),
+                    )?;
                 let evt_processor = EventProcessor::start(
                     sawtooth_connection,
-                    current_commit.as_deref(),
-                    event_handlers![DatabaseEventHandler::from_sqlite_pool(
-                        connection_pool.clone()
-                    )],
+                    resume_from,
+                    event_handlers(
+                        DatabaseEventHandler::from_sqlite_pool(connection_pool.clone()),
+                        #[cfg(any(feature = "change-stream", feature = "webhooks"))]
+                        &*store_factory,
+                        #[cfg(feature = "change-stream")]
+                        change_broadcaster.clone(),
+                    ),
+                    FailurePolicy::new(config.event_retries(), config.event_failure_mode())
+                        .with_dead_letter_store(Arc::new(DieselDeadLetterStore::new(
+                            connection_pool.pool.clone(),
+                        ))),
+                    indexing_status.clone(),
                 )
                 .map_err(|err| DaemonError::EventProcessorError(Box::new(err)))?;
 
                 (
-                    rest_api::DbExecutor::from_sqlite_pool(connection_pool),
+                    rest_api::DbExecutor::from_sqlite_pool(connection_pool)
+                        .with_indexing_status(indexing_status)
+                        .with_max_commit_lag(config.max_commit_lag()),
                     evt_processor,
                 )
             }
         }
     };
 
+    #[cfg(feature = "change-stream")]
+    let db_executor = db_executor.with_change_broadcaster(change_broadcaster.clone());
+
     #[cfg(feature = "integration")]
     let integration_state = match connection_uri {
         ConnectionUri::Postgres(_) => {
             let connection_pool: ConnectionPool<diesel::pg::PgConnection> =
-                ConnectionPool::new(config.database_url())?;
+                ConnectionPool::with_max_size(config.database_url(), config.database_pool_size())?;
             IntegrationState::with_pg_pool(
                 &config.key_file_name().to_string(),
                 connection_pool.pool,
@@ -102,7 +142,7 @@ pub fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
         }
         ConnectionUri::Sqlite(_) => {
             let connection_pool: ConnectionPool<diesel::sqlite::SqliteConnection> =
-                ConnectionPool::new(config.database_url())?;
+                ConnectionPool::with_max_size(config.database_url(), config.database_pool_size())?;
             IntegrationState::with_sqlite_pool(
                 &config.key_file_name().to_string(),
                 connection_pool.pool,
@@ -110,11 +150,20 @@ pub fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
         }
     };
 
+    let authenticator =
+        rest_api::Authenticator::from_config(&config, store_factory.get_grid_pike_store().into())?;
+    #[cfg(feature = "tls")]
+    let tls = rest_api::TlsSettings::from_config(&config)?;
+
     let (rest_api_shutdown_handle, rest_api_join_handle) = rest_api::run(
         config.rest_api_endpoint(),
         db_executor,
+        config.db_executor_threads(),
         batch_submitter,
         config.endpoint().clone(),
+        authenticator,
+        #[cfg(feature = "tls")]
+        tls,
         #[cfg(feature = "integration")]
         integration_state,
     )?;
@@ -122,6 +171,10 @@ pub fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
     let (event_processor_shutdown_handle, event_processor_join_handle) =
         evt_processor.take_shutdown_controls();
 
+    #[cfg(feature = "webhooks")]
+    let (webhook_deliverer_shutdown_handle, webhook_deliverer_join_handle) =
+        WebhookDeliverer::start(store_factory.get_webhook_store().into())?.take_shutdown_controls();
+
     let ctrlc_triggered = AtomicBool::new(false);
     ctrlc::set_handler(move || {
         if ctrlc_triggered.load(Ordering::SeqCst) {
@@ -131,11 +184,18 @@ pub fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
 
         ctrlc_triggered.store(true, Ordering::SeqCst);
 
+        // Change streams would otherwise hold up the REST API's graceful shutdown
+        #[cfg(feature = "change-stream")]
+        change_broadcaster.close();
+
         rest_api_shutdown_handle.shutdown();
 
         if let Err(err) = event_processor_shutdown_handle.shutdown() {
             error!("Unable to gracefully shutdown Event Processor: {}", err);
         }
+
+        #[cfg(feature = "webhooks")]
+        webhook_deliverer_shutdown_handle.shutdown();
     })
     .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
 
@@ -153,5 +213,33 @@ pub fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
         })
         .and_then(|res| res.map_err(DaemonError::from))?;
 
+    #[cfg(feature = "webhooks")]
+    webhook_deliverer_join_handle.join().map_err(|_| {
+        DaemonError::ShutdownError("Unable to cleanly join the webhook deliverer".into())
+    })?;
+
     Ok(())
 }
+
+/// Returns the handlers run for each commit: the database handler, followed by the handlers that
+/// read the changes it indexed
+fn event_handlers<H: EventHandler + 'static>(
+    db_handler: H,
+    #[cfg(any(feature = "change-stream", feature = "webhooks"))] store_factory: &dyn StoreFactory,
+    #[cfg(feature = "change-stream")] change_broadcaster: ChangeBroadcaster,
+) -> Vec<Box<dyn EventHandler>> {
+    #[allow(unused_mut)]
+    let mut handlers: Vec<Box<dyn EventHandler>> = vec![Box::new(db_handler)];
+    #[cfg(feature = "change-stream")]
+    handlers.push(Box::new(ChangeLogEventHandler::new(
+        store_factory,
+        store_factory.get_change_log_store().into(),
+        change_broadcaster,
+    )));
+    #[cfg(feature = "webhooks")]
+    handlers.push(Box::new(WebhookEventHandler::new(
+        store_factory,
+        store_factory.get_webhook_store().into(),
+    )));
+    handlers
+}
@@ -26,10 +26,12 @@ use splinter::{
     events::{Igniter, ParseBytes, ParseError, WebSocketClient, WebSocketError, WsResponse},
 };
 
-use crate::event::{EventHandler, EventProcessor};
+use crate::event::{
+    EventHandler, EventProcessor, EventProcessorError, FailurePolicy, IndexingStatus, ResumeFrom,
+};
 use crate::splinter::{
     app_auth_handler::{error::AppAuthHandlerError, node::get_node_id, sabre::setup_grid},
-    event::ScabbardEventConnectionFactory,
+    event::{ScabbardEventConnection, ScabbardEventConnectionFactory},
 };
 
 /// default value if the client should attempt to reconnet if ws connection is lost
@@ -58,7 +60,9 @@ impl ParseBytes<AdminEvent> for AdminEvent {
 pub fn run(
     splinterd_url: String,
     event_connection_factory: ScabbardEventConnectionFactory,
-    handler: Box<dyn EventHandler + Sync>,
+    handlers: Vec<Box<dyn EventHandler + Sync>>,
+    failure_policy: FailurePolicy,
+    indexing_status: IndexingStatus,
     igniter: Igniter,
     scabbard_admin_key: String,
 ) -> Result<(), AppAuthHandlerError> {
@@ -66,11 +70,27 @@ pub fn run(
 
     let node_id = get_node_id(splinterd_url.clone())?;
 
+    // Commits from every circuit share the commit store, so each service's processor resumes
+    // from the last event it received rather than from the store's current commit
+    let start_event_processor = move |event_connection: ScabbardEventConnection| {
+        EventProcessor::start(
+            event_connection,
+            ResumeFrom::LastEvent(None),
+            handlers
+                .iter()
+                .map(|handler| handler.cloned_box())
+                .collect(),
+            failure_policy.clone(),
+            indexing_status.clone(),
+        )
+        .map(|_| ())
+    };
+
     let mut ws = WebSocketClient::new(&registration_route, move |_ctx, event| {
         if let Err(err) = process_admin_event(
             event,
             &event_connection_factory,
-            handler.cloned_box(),
+            &start_event_processor,
             &node_id,
             &scabbard_admin_key,
             &splinterd_url,
@@ -107,7 +127,7 @@ pub fn run(
 fn process_admin_event(
     event: AdminEvent,
     event_connection_factory: &ScabbardEventConnectionFactory,
-    handler: Box<dyn EventHandler>,
+    start_event_processor: &dyn Fn(ScabbardEventConnection) -> Result<(), EventProcessorError>,
     node_id: &str,
     scabbard_admin_key: &str,
     splinterd_url: &str,
@@ -154,7 +174,7 @@ fn process_admin_event(
             let event_connection = event_connection_factory
                 .create_connection(&msg_proposal.circuit_id, &service.service_id)?;
 
-            EventProcessor::start(event_connection, None, vec![handler])
+            start_event_processor(event_connection)
                 .map_err(|err| AppAuthHandlerError::EventProcessorError(err.0))?;
 
             setup_grid(
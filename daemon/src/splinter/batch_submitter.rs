@@ -22,6 +22,8 @@ use futures::prelude::*;
 use protobuf::Message;
 use sawtooth_sdk::messages::batch::Batch;
 
+#[cfg(feature = "metrics")]
+use crate::metrics;
 use crate::rest_api::error::RestApiResponseError;
 use crate::submitter::{
     BatchStatus, BatchStatusLink, BatchStatuses, BatchSubmitter, InvalidTransaction, SubmitBatches,
@@ -49,8 +51,8 @@ impl SplinterBatchSubmitter {
     }
 }
 
-impl BatchSubmitter for SplinterBatchSubmitter {
-    fn submit_batches(
+impl SplinterBatchSubmitter {
+    fn submit(
         &self,
         msg: SubmitBatches,
     ) -> Pin<Box<dyn Future<Output = Result<BatchStatusLink, RestApiResponseError>> + Send>> {
@@ -98,6 +100,22 @@ impl BatchSubmitter for SplinterBatchSubmitter {
             })
             .boxed()
     }
+}
+
+impl BatchSubmitter for SplinterBatchSubmitter {
+    fn submit_batches(
+        &self,
+        msg: SubmitBatches,
+    ) -> Pin<Box<dyn Future<Output = Result<BatchStatusLink, RestApiResponseError>> + Send>> {
+        #[cfg(feature = "metrics")]
+        {
+            metrics::record_submission("splinter", || self.submit(msg))
+        }
+        #[cfg(not(feature = "metrics"))]
+        {
+            self.submit(msg)
+        }
+    }
 
     fn batch_status(
         &self,
@@ -149,6 +167,14 @@ impl BatchSubmitter for SplinterBatchSubmitter {
             .boxed()
     }
 
+    fn chain_head_height(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<u64>, RestApiResponseError>> + Send>> {
+        // Scabbard does not number its commits; the lag of its event connections is counted
+        // by the connections instead
+        future::ok(None).boxed()
+    }
+
     fn clone_box(&self) -> Box<dyn BatchSubmitter> {
         Box::new(self.clone())
     }
@@ -16,7 +16,9 @@
  */
 
 use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
 
 use scabbard::service::{StateChange as ScabbardStateChange, StateChangeEvent};
 use splinter::events::{Igniter, WebSocketClient, WebSocketError, WsResponse};
@@ -72,7 +74,8 @@ impl ScabbardEventConnectionFactory {
 }
 
 enum ConnectionState {
-    Connected(Receiver<ConnectionCommand>),
+    /// Receives the events of the web socket, counting those that have not been read yet
+    Connected(Receiver<ConnectionCommand>, Arc<AtomicU64>),
     Disconnected,
 }
 
@@ -113,6 +116,8 @@ impl EventConnection for ScabbardEventConnection {
         last_commit_id: Option<&str>,
     ) -> Result<Self::Unsubscriber, EventIoError> {
         let (sender, receiver) = sync_channel(128);
+        let backlog = Arc::new(AtomicU64::new(0));
+        let ws_backlog = backlog.clone();
 
         let source = self.name.clone();
         let unsubscribe_sender = sender.clone();
@@ -122,9 +127,12 @@ impl EventConnection for ScabbardEventConnection {
             self.connection_url.clone()
         };
         let mut state_delta_ws = WebSocketClient::new(&url, move |_, event: StateChangeEvent| {
+            // Counted before it is sent, so that it is never read before it is counted
+            ws_backlog.fetch_add(1, Ordering::SeqCst);
             match sender.try_send(ConnectionCommand::Message(event)) {
                 Ok(_) => (),
                 Err(TrySendError::Full(ConnectionCommand::Message(event))) => {
+                    ws_backlog.fetch_sub(1, Ordering::SeqCst);
                     error!(
                         "dropping commit event {} from {} due to back pressure",
                         event.id, source
@@ -134,7 +142,10 @@ impl EventConnection for ScabbardEventConnection {
                     // This shouldn't happen, since we never send this type
                     unreachable!()
                 }
-                Err(TrySendError::Disconnected(_)) => return WsResponse::Close,
+                Err(TrySendError::Disconnected(_)) => {
+                    ws_backlog.fetch_sub(1, Ordering::SeqCst);
+                    return WsResponse::Close;
+                }
             }
             WsResponse::Empty
         });
@@ -157,7 +168,7 @@ impl EventConnection for ScabbardEventConnection {
         })?;
 
         let mut connection_state = self.connection_state.borrow_mut();
-        *connection_state = ConnectionState::Connected(receiver);
+        *connection_state = ConnectionState::Connected(receiver, backlog);
 
         Ok(ScabbardEventUnsubscriber {
             name: self.name.clone(),
@@ -168,22 +179,25 @@ impl EventConnection for ScabbardEventConnection {
     fn recv(&self) -> Result<CommitEvent, EventIoError> {
         let mut connection_state = self.connection_state.borrow_mut();
         match *connection_state {
-            ConnectionState::Connected(ref receiver) => match receiver.recv() {
-                Ok(ConnectionCommand::Message(scabbard_evt)) => Ok(CommitEvent {
-                    service_id: Some(self.name.clone()),
-                    id: scabbard_evt.id,
-                    height: None,
-                    state_changes: scabbard_evt
-                        .state_changes
-                        .into_iter()
-                        .map(|state_change| match state_change {
-                            ScabbardStateChange::Set { key, value } => {
-                                StateChange::Set { key, value }
-                            }
-                            ScabbardStateChange::Delete { key } => StateChange::Delete { key },
-                        })
-                        .collect(),
-                }),
+            ConnectionState::Connected(ref receiver, ref backlog) => match receiver.recv() {
+                Ok(ConnectionCommand::Message(scabbard_evt)) => {
+                    backlog.fetch_sub(1, Ordering::SeqCst);
+                    Ok(CommitEvent {
+                        service_id: Some(self.name.clone()),
+                        id: scabbard_evt.id,
+                        height: None,
+                        state_changes: scabbard_evt
+                            .state_changes
+                            .into_iter()
+                            .map(|state_change| match state_change {
+                                ScabbardStateChange::Set { key, value } => {
+                                    StateChange::Set { key, value }
+                                }
+                                ScabbardStateChange::Delete { key } => StateChange::Delete { key },
+                            })
+                            .collect(),
+                    })
+                }
                 Ok(ConnectionCommand::Shutdown) => {
                     debug!("Disconnecting event connection to {}", self.name);
 
@@ -206,6 +220,21 @@ impl EventConnection for ScabbardEventConnection {
         }
     }
 
+    fn reconnect(&mut self) -> Result<(), EventIoError> {
+        // Each subscription starts its own web socket, so there is nothing to reopen until the
+        // connection is subscribed again
+        *self.connection_state.borrow_mut() = ConnectionState::Disconnected;
+
+        Ok(())
+    }
+
+    fn backlog(&self) -> Option<u64> {
+        match *self.connection_state.borrow() {
+            ConnectionState::Connected(_, ref backlog) => Some(backlog.load(Ordering::SeqCst)),
+            ConnectionState::Disconnected => None,
+        }
+    }
+
     fn close(self) -> Result<(), EventIoError> {
         Ok(())
     }
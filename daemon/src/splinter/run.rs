@@ -18,17 +18,25 @@
 
 use std::process;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
+use grid_sdk::dead_letters::DieselDeadLetterStore;
 #[cfg(feature = "integration")]
 use grid_sdk::rest_api::actix_web_3::State as IntegrationState;
-use grid_sdk::store::ConnectionUri;
+#[cfg(any(feature = "change-stream", feature = "webhooks"))]
+use grid_sdk::store::StoreFactory;
+use grid_sdk::store::{create_store_factory, ConnectionUri};
 use splinter::events::Reactor;
 
+#[cfg(feature = "change-stream")]
+use crate::changes::{ChangeBroadcaster, ChangeLogEventHandler};
 use crate::config::GridConfig;
 use crate::database::ConnectionPool;
 use crate::error::DaemonError;
-use crate::event::{db_handler::DatabaseEventHandler, EventHandler};
+use crate::event::{db_handler::DatabaseEventHandler, EventHandler, FailurePolicy, IndexingStatus};
 use crate::rest_api;
+#[cfg(feature = "webhooks")]
+use crate::webhooks::{WebhookDeliverer, WebhookEventHandler};
 
 use super::{
     app_auth_handler, batch_submitter::SplinterBatchSubmitter,
@@ -44,7 +52,24 @@ pub fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
     let scabbard_event_connection_factory =
         ScabbardEventConnectionFactory::new(&config.endpoint().url(), reactor.igniter());
 
-    let (db_executor, db_handler): (rest_api::DbExecutor, Box<dyn EventHandler + Sync + 'static>) = {
+    let store_factory = create_store_factory(
+        &config
+            .database_url()
+            .parse()
+            .map_err(|err| DaemonError::StartUpError(Box::new(err)))?,
+    )
+    .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
+
+    let failure_policy = FailurePolicy::new(config.event_retries(), config.event_failure_mode());
+    let indexing_status = IndexingStatus::new();
+    #[cfg(feature = "change-stream")]
+    let change_broadcaster = ChangeBroadcaster::new();
+
+    let (db_executor, db_handler, failure_policy): (
+        rest_api::DbExecutor,
+        Box<dyn EventHandler + Sync + 'static>,
+        FailurePolicy,
+    ) = {
         let connection_uri = config
             .database_url()
             .parse()
@@ -52,23 +77,44 @@ pub fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
         match connection_uri {
             ConnectionUri::Postgres(_) => {
                 let connection_pool: ConnectionPool<diesel::pg::PgConnection> =
-                    ConnectionPool::new(config.database_url())?;
+                    ConnectionPool::with_max_size(
+                        config.database_url(),
+                        config.database_pool_size(),
+                    )?;
                 (
-                    rest_api::DbExecutor::from_pg_pool(connection_pool.clone()),
-                    Box::new(DatabaseEventHandler::from_pg_pool(connection_pool)),
+                    rest_api::DbExecutor::from_pg_pool(connection_pool.clone())
+                        .with_indexing_status(indexing_status.clone())
+                        .with_max_commit_lag(config.max_commit_lag()),
+                    Box::new(DatabaseEventHandler::from_pg_pool(connection_pool.clone())),
+                    failure_policy.with_dead_letter_store(Arc::new(DieselDeadLetterStore::new(
+                        connection_pool.pool,
+                    ))),
                 )
             }
             ConnectionUri::Sqlite(_) => {
                 let connection_pool: ConnectionPool<diesel::sqlite::SqliteConnection> =
-                    ConnectionPool::new(config.database_url())?;
+                    ConnectionPool::with_max_size(
+                        config.database_url(),
+                        config.database_pool_size(),
+                    )?;
                 (
-                    rest_api::DbExecutor::from_sqlite_pool(connection_pool.clone()),
-                    Box::new(DatabaseEventHandler::from_sqlite_pool(connection_pool)),
+                    rest_api::DbExecutor::from_sqlite_pool(connection_pool.clone())
+                        .with_indexing_status(indexing_status.clone())
+                        .with_max_commit_lag(config.max_commit_lag()),
+                    Box::new(DatabaseEventHandler::from_sqlite_pool(
+                        connection_pool.clone(),
+                    )),
+                    failure_policy.with_dead_letter_store(Arc::new(DieselDeadLetterStore::new(
+                        connection_pool.pool,
+                    ))),
                 )
             }
         }
     };
 
+    #[cfg(feature = "change-stream")]
+    let db_executor = db_executor.with_change_broadcaster(change_broadcaster.clone());
+
     #[cfg(feature = "integration")]
     let connection_uri = config
         .database_url()
@@ -79,7 +125,7 @@ pub fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
     let integration_state = match connection_uri {
         ConnectionUri::Postgres(_) => {
             let connection_pool: ConnectionPool<diesel::pg::PgConnection> =
-                ConnectionPool::new(config.database_url())?;
+                ConnectionPool::with_max_size(config.database_url(), config.database_pool_size())?;
             IntegrationState::with_pg_pool(
                 &config.key_file_name().to_string(),
                 connection_pool.pool,
@@ -87,7 +133,7 @@ pub fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
         }
         ConnectionUri::Sqlite(_) => {
             let connection_pool: ConnectionPool<diesel::sqlite::SqliteConnection> =
-                ConnectionPool::new(config.database_url())?;
+                ConnectionPool::with_max_size(config.database_url(), config.database_pool_size())?;
             IntegrationState::with_sqlite_pool(
                 &config.key_file_name().to_string(),
                 connection_pool.pool,
@@ -98,22 +144,43 @@ pub fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
     app_auth_handler::run(
         config.endpoint().url(),
         scabbard_event_connection_factory,
-        db_handler,
+        event_handlers(
+            db_handler,
+            #[cfg(any(feature = "change-stream", feature = "webhooks"))]
+            &*store_factory,
+            #[cfg(feature = "change-stream")]
+            change_broadcaster.clone(),
+        ),
+        failure_policy,
+        indexing_status,
         reactor.igniter(),
         scabbard_admin_key,
     )?;
 
     let batch_submitter = Box::new(SplinterBatchSubmitter::new(config.endpoint().url()));
 
+    let authenticator =
+        rest_api::Authenticator::from_config(&config, store_factory.get_grid_pike_store().into())?;
+    #[cfg(feature = "tls")]
+    let tls = rest_api::TlsSettings::from_config(&config)?;
+
     let (rest_api_shutdown_handle, rest_api_join_handle) = rest_api::run(
         config.rest_api_endpoint(),
         db_executor,
+        config.db_executor_threads(),
         batch_submitter,
         config.endpoint().clone(),
+        authenticator,
+        #[cfg(feature = "tls")]
+        tls,
         #[cfg(feature = "integration")]
         integration_state,
     )?;
 
+    #[cfg(feature = "webhooks")]
+    let (webhook_deliverer_shutdown_handle, webhook_deliverer_join_handle) =
+        WebhookDeliverer::start(store_factory.get_webhook_store().into())?.take_shutdown_controls();
+
     let reactor_shutdown_signaler = reactor.shutdown_signaler();
 
     let ctrlc_triggered = AtomicBool::new(false);
@@ -132,7 +199,14 @@ pub fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
             );
         }
 
+        // Change streams would otherwise hold up the REST API's graceful shutdown
+        #[cfg(feature = "change-stream")]
+        change_broadcaster.close();
+
         rest_api_shutdown_handle.shutdown();
+
+        #[cfg(feature = "webhooks")]
+        webhook_deliverer_shutdown_handle.shutdown();
     })
     .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
 
@@ -147,5 +221,33 @@ pub fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
         error!("Unable to shutdown splinter event reactor: {}", err);
     }
 
+    #[cfg(feature = "webhooks")]
+    webhook_deliverer_join_handle.join().map_err(|_| {
+        DaemonError::ShutdownError("Unable to cleanly join the webhook deliverer".into())
+    })?;
+
     Ok(())
 }
+
+/// Returns the handlers run for each commit of every service: the database handler, followed by
+/// the handlers that read the changes it indexed
+fn event_handlers(
+    db_handler: Box<dyn EventHandler + Sync>,
+    #[cfg(any(feature = "change-stream", feature = "webhooks"))] store_factory: &dyn StoreFactory,
+    #[cfg(feature = "change-stream")] change_broadcaster: ChangeBroadcaster,
+) -> Vec<Box<dyn EventHandler + Sync>> {
+    #[allow(unused_mut)]
+    let mut handlers = vec![db_handler];
+    #[cfg(feature = "change-stream")]
+    handlers.push(Box::new(ChangeLogEventHandler::new(
+        store_factory,
+        store_factory.get_change_log_store().into(),
+        change_broadcaster,
+    )));
+    #[cfg(feature = "webhooks")]
+    handlers.push(Box::new(WebhookEventHandler::new(
+        store_factory,
+        store_factory.get_webhook_store().into(),
+    )));
+    handlers
+}
@@ -34,6 +34,12 @@ pub trait BatchSubmitter: Send + 'static {
         batch_statuses: BatchStatuses,
     ) -> Pin<Box<dyn Future<Output = Result<Vec<BatchStatus>, RestApiResponseError>> + Send>>;
 
+    /// Returns the height of the ledger's chain head, or `None` if the ledger has no blocks yet
+    /// or does not number its commits
+    fn chain_head_height(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<u64>, RestApiResponseError>> + Send>>;
+
     fn clone_box(&self) -> Box<dyn BatchSubmitter>;
 }
 
@@ -0,0 +1,525 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use grid_sdk::error::InternalError;
+use grid_sdk::webhooks::{DeliveryStatus, WebhookDelivery, WebhookStore, WebhookStoreError};
+use reqwest::blocking::Client;
+
+use crate::error::DaemonError;
+
+use super::signature::sign;
+
+/// The number of attempts after which a delivery is marked as failed
+const MAX_DELIVERY_ATTEMPTS: i32 = 10;
+
+/// The number of due deliveries attempted between polls of the store
+const DELIVERY_BATCH_SIZE: i64 = 50;
+
+/// The number of one subscription's deliveries attempted in a batch, so that a subscriber with a
+/// backlog cannot take up the whole batch
+const MAX_SUBSCRIPTION_BATCH_SIZE: usize = 10;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const INITIAL_RETRY_BACKOFF_SECS: i64 = 10;
+const MAX_RETRY_BACKOFF_SECS: i64 = 60 * 60;
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct WebhookDelivererShutdownHandle {
+    shutdown: Arc<AtomicBool>,
+}
+
+impl WebhookDelivererShutdownHandle {
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Posts queued webhook deliveries on a separate thread
+pub struct WebhookDeliverer {
+    join_handle: thread::JoinHandle<()>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl WebhookDeliverer {
+    /// Starts delivering the deliveries in `webhook_store` as they fall due
+    pub fn start(webhook_store: Arc<dyn WebhookStore>) -> Result<Self, DaemonError> {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let thread_shutdown = shutdown.clone();
+
+        let join_handle = thread::Builder::new()
+            .name("WebhookDeliverer".into())
+            .spawn(move || {
+                while !thread_shutdown.load(Ordering::SeqCst) {
+                    match deliver_due(&*webhook_store, &client, now()) {
+                        // A full batch may mean more deliveries are due, so poll again at once
+                        Ok(attempted) if attempted as i64 == DELIVERY_BATCH_SIZE => continue,
+                        Ok(_) => (),
+                        Err(err) => error!("Unable to deliver webhooks: {}", err),
+                    }
+                    sleep_unless_shutdown(POLL_INTERVAL, &thread_shutdown);
+                }
+            })
+            .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
+
+        Ok(WebhookDeliverer {
+            join_handle,
+            shutdown,
+        })
+    }
+
+    pub fn take_shutdown_controls(
+        self,
+    ) -> (WebhookDelivererShutdownHandle, thread::JoinHandle<()>) {
+        (
+            WebhookDelivererShutdownHandle {
+                shutdown: self.shutdown,
+            },
+            self.join_handle,
+        )
+    }
+}
+
+/// Attempts each delivery that is due, returning the number of deliveries attempted
+///
+/// Each subscription's deliveries are posted in order on a thread of their own, so a slow or
+/// unreachable subscriber holds up only its own deliveries. At most
+/// `MAX_SUBSCRIPTION_BATCH_SIZE` of a subscription's deliveries are attempted at a time; the
+/// rest are left for the next batch.
+///
+/// A delivery succeeds when the subscriber responds with a 2xx status. A failed delivery is
+/// retried with exponential backoff until it has been attempted `MAX_DELIVERY_ATTEMPTS` times.
+///
+/// # Arguments
+///
+///  * `webhook_store` - The store the deliveries are queued in
+///  * `client` - The client used to post the deliveries
+///  * `now` - Seconds since the Unix epoch
+pub fn deliver_due(
+    webhook_store: &dyn WebhookStore,
+    client: &Client,
+    now: i64,
+) -> Result<usize, WebhookStoreError> {
+    let mut by_subscription: BTreeMap<i64, Vec<WebhookDelivery>> = BTreeMap::new();
+    for delivery in webhook_store.list_due_deliveries(now, DELIVERY_BATCH_SIZE)? {
+        let queued = by_subscription.entry(delivery.subscription_id).or_default();
+        if queued.len() < MAX_SUBSCRIPTION_BATCH_SIZE {
+            queued.push(delivery);
+        }
+    }
+    let attempted = by_subscription.values().map(Vec::len).sum();
+
+    thread::scope(|scope| {
+        let handles = by_subscription
+            .into_iter()
+            .map(|(subscription_id, deliveries)| {
+                // Store errors are not `Send`, so only their message leaves the thread
+                scope.spawn(move || {
+                    deliver_to_subscription(webhook_store, client, subscription_id, deliveries, now)
+                        .map_err(|err| err.to_string())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles.into_iter().try_for_each(|handle| {
+            handle
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+    })
+    .map_err(|msg| WebhookStoreError::InternalError(InternalError::with_message(msg)))?;
+
+    Ok(attempted)
+}
+
+/// Attempts the given deliveries to one subscription in turn
+fn deliver_to_subscription(
+    webhook_store: &dyn WebhookStore,
+    client: &Client,
+    subscription_id: i64,
+    deliveries: Vec<WebhookDelivery>,
+    now: i64,
+) -> Result<(), WebhookStoreError> {
+    let subscription = webhook_store.fetch_subscription(subscription_id)?;
+
+    for mut delivery in deliveries {
+        let result = match &subscription {
+            Some(subscription) => post(
+                client,
+                &subscription.url,
+                &subscription.secret,
+                &delivery,
+                now,
+            ),
+            None => Err("Subscription no longer exists".to_string()),
+        };
+
+        delivery.attempts += 1;
+        match result {
+            Ok(()) => {
+                delivery.status = DeliveryStatus::Delivered;
+                delivery.last_error = None;
+            }
+            Err(err) => {
+                warn!(
+                    "Webhook delivery {} of event {} failed on attempt {}: {}",
+                    delivery.id.unwrap_or_default(),
+                    delivery.event_id,
+                    delivery.attempts,
+                    err
+                );
+                if delivery.attempts >= MAX_DELIVERY_ATTEMPTS {
+                    delivery.status = DeliveryStatus::Failed;
+                } else {
+                    delivery.next_attempt_at = now + retry_backoff(delivery.attempts);
+                }
+                delivery.last_error = Some(err);
+            }
+        }
+
+        webhook_store.update_delivery(&delivery)?;
+    }
+
+    Ok(())
+}
+
+fn post(
+    client: &Client,
+    url: &str,
+    secret: &str,
+    delivery: &WebhookDelivery,
+    now: i64,
+) -> Result<(), String> {
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .header("X-Grid-Event", &delivery.event_type)
+        .header("X-Grid-Delivery", &delivery.event_id)
+        .header("X-Grid-Timestamp", now.to_string())
+        .header("X-Grid-Signature", sign(secret, now, &delivery.payload))
+        .body(delivery.payload.clone())
+        .send()
+        .map_err(|err| err.to_string())?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("Subscriber responded with {}", response.status()))
+    }
+}
+
+/// Returns the number of seconds to wait before retrying a delivery that has failed `attempts`
+/// times
+fn retry_backoff(attempts: i32) -> i64 {
+    2i64.checked_pow((attempts - 1).max(0) as u32)
+        .and_then(|factor| factor.checked_mul(INITIAL_RETRY_BACKOFF_SECS))
+        .map(|backoff| backoff.min(MAX_RETRY_BACKOFF_SECS))
+        .unwrap_or(MAX_RETRY_BACKOFF_SECS)
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn sleep_unless_shutdown(duration: Duration, shutdown: &AtomicBool) {
+    let deadline = Instant::now() + duration;
+    while !shutdown.load(Ordering::SeqCst) {
+        let now = Instant::now();
+        if now >= deadline {
+            return;
+        }
+        thread::sleep((deadline - now).min(SHUTDOWN_CHECK_INTERVAL));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{channel, Receiver};
+
+    use grid_sdk::webhooks::{MemoryWebhookStore, WebhookSubscription};
+
+    /// A request received by the stand-in subscriber
+    struct Request {
+        headers: Vec<(String, String)>,
+        body: String,
+    }
+
+    impl Request {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// Starts a local HTTP server that answers requests with the given statuses in turn,
+    /// returning its URL and the requests it receives
+    fn stand_in(statuses: Vec<u16>) -> (String, Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind listener");
+        let url = format!(
+            "http://{}/hook",
+            listener.local_addr().expect("Failed to get address")
+        );
+        let (sender, receiver) = channel();
+
+        thread::spawn(move || {
+            for status in statuses {
+                let (stream, _) = match listener.accept() {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                };
+                let mut reader = BufReader::new(stream);
+
+                let mut line = String::new();
+                reader.read_line(&mut line).expect("Failed to read request");
+                let mut headers = vec![];
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).expect("Failed to read header");
+                    let header = line.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    if let Some(index) = header.find(':') {
+                        headers.push((
+                            header[..index].to_string(),
+                            header[index + 1..].trim().to_string(),
+                        ));
+                    }
+                }
+                let length = headers
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                    .and_then(|(_, value)| value.parse().ok())
+                    .unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).expect("Failed to read body");
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Stand-in\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .expect("Failed to write response");
+
+                let _ = sender.send(Request {
+                    headers,
+                    body: String::from_utf8(body).expect("Body is not UTF-8"),
+                });
+            }
+        });
+
+        (url, receiver)
+    }
+
+    fn queue(store: &MemoryWebhookStore, url: &str) -> i64 {
+        let subscription = store
+            .add_subscription(WebhookSubscription::new(
+                url,
+                "secret",
+                vec![],
+                vec![],
+                None,
+            ))
+            .expect("Failed to add subscription");
+        let mut delivery = WebhookDelivery::new(
+            subscription,
+            "commit-1-0",
+            "product.created",
+            r#"{"id":"commit-1-0"}"#,
+        );
+        delivery.next_attempt_at = 0;
+        store
+            .add_deliveries(vec![delivery])
+            .expect("Failed to add delivery");
+        store
+            .list_deliveries(subscription, 0, 1)
+            .expect("Failed to list deliveries")
+            .data[0]
+            .id
+            .expect("Delivery has no id")
+    }
+
+    fn fetch(store: &MemoryWebhookStore, id: i64) -> WebhookDelivery {
+        store
+            .fetch_delivery(id)
+            .expect("Failed to fetch delivery")
+            .expect("Delivery not found")
+    }
+
+    #[test]
+    // Test that a delivery is posted with its signature, and that a delivery that is rejected
+    // is retried once its backoff has passed
+    fn test_signed_delivery_is_retried() {
+        let store = MemoryWebhookStore::new();
+        let (url, requests) = stand_in(vec![500, 200]);
+        let id = queue(&store, &url);
+        let client = Client::new();
+
+        assert_eq!(
+            deliver_due(&store, &client, 1000).expect("Failed to deliver"),
+            1
+        );
+        let request = requests.recv().expect("No request received");
+        assert_eq!(request.body, r#"{"id":"commit-1-0"}"#);
+        assert_eq!(request.header("X-Grid-Event"), Some("product.created"));
+        assert_eq!(request.header("X-Grid-Delivery"), Some("commit-1-0"));
+        assert_eq!(request.header("X-Grid-Timestamp"), Some("1000"));
+        assert_eq!(
+            request.header("X-Grid-Signature"),
+            Some(sign("secret", 1000, &request.body).as_str())
+        );
+
+        let delivery = fetch(&store, id);
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.next_attempt_at, 1000 + INITIAL_RETRY_BACKOFF_SECS);
+        assert!(delivery.last_error.is_some());
+
+        // Nothing is due until the backoff has passed
+        assert_eq!(
+            deliver_due(&store, &client, 1001).expect("Failed to deliver"),
+            0
+        );
+
+        assert_eq!(
+            deliver_due(&store, &client, delivery.next_attempt_at).expect("Failed to deliver"),
+            1
+        );
+        let request = requests.recv().expect("No request received");
+        assert_eq!(request.header("X-Grid-Delivery"), Some("commit-1-0"));
+
+        let delivery = fetch(&store, id);
+        assert_eq!(delivery.status, DeliveryStatus::Delivered);
+        assert_eq!(delivery.attempts, 2);
+        assert_eq!(delivery.last_error, None);
+    }
+
+    #[test]
+    // Test that a delivery is marked as failed once it has been attempted the maximum number of
+    // times
+    fn test_delivery_fails_after_max_attempts() {
+        let store = MemoryWebhookStore::new();
+        let (url, _requests) = stand_in(vec![503; MAX_DELIVERY_ATTEMPTS as usize]);
+        let id = queue(&store, &url);
+        let client = Client::new();
+
+        let mut now = 0;
+        for _ in 0..MAX_DELIVERY_ATTEMPTS {
+            assert_eq!(
+                deliver_due(&store, &client, now).expect("Failed to deliver"),
+                1
+            );
+            now = fetch(&store, id).next_attempt_at;
+        }
+
+        let delivery = fetch(&store, id);
+        assert_eq!(delivery.status, DeliveryStatus::Failed);
+        assert_eq!(delivery.attempts, MAX_DELIVERY_ATTEMPTS);
+        assert_eq!(
+            deliver_due(&store, &client, i64::MAX).expect("Failed to deliver"),
+            0
+        );
+    }
+
+    #[test]
+    // Test that a batch attempts only a share of a subscription's backlog, so that the
+    // deliveries of other subscriptions are attempted alongside it
+    fn test_subscription_share_of_batch() {
+        let store = MemoryWebhookStore::new();
+        let backlog = MAX_SUBSCRIPTION_BATCH_SIZE + 5;
+        let (busy_url, busy_requests) = stand_in(vec![200; backlog]);
+        let busy = store
+            .add_subscription(WebhookSubscription::new(
+                &busy_url,
+                "secret",
+                vec![],
+                vec![],
+                None,
+            ))
+            .expect("Failed to add subscription");
+        store
+            .add_deliveries(
+                (0..backlog)
+                    .map(|i| {
+                        let event_id = format!("commit-{}-0", i);
+                        let mut delivery = WebhookDelivery::new(
+                            busy,
+                            &event_id,
+                            "product.created",
+                            &format!(r#"{{"id":"{}"}}"#, event_id),
+                        );
+                        delivery.next_attempt_at = 0;
+                        delivery
+                    })
+                    .collect(),
+            )
+            .expect("Failed to add deliveries");
+        let (url, requests) = stand_in(vec![200]);
+        let id = queue(&store, &url);
+        let client = Client::new();
+
+        assert_eq!(
+            deliver_due(&store, &client, 1000).expect("Failed to deliver"),
+            MAX_SUBSCRIPTION_BATCH_SIZE + 1
+        );
+        requests.recv().expect("No request received");
+        assert_eq!(fetch(&store, id).status, DeliveryStatus::Delivered);
+
+        // The busy subscription's deliveries are posted in the order they were queued
+        for i in 0..MAX_SUBSCRIPTION_BATCH_SIZE {
+            let request = busy_requests.recv().expect("No request received");
+            assert_eq!(
+                request.header("X-Grid-Delivery"),
+                Some(format!("commit-{}-0", i).as_str())
+            );
+        }
+
+        // The rest of the backlog is left for the next batch
+        assert_eq!(
+            deliver_due(&store, &client, 1000).expect("Failed to deliver"),
+            5
+        );
+    }
+
+    #[test]
+    // Test that the backoff doubles with each attempt up to its maximum
+    fn test_retry_backoff() {
+        assert_eq!(retry_backoff(1), INITIAL_RETRY_BACKOFF_SECS);
+        assert_eq!(retry_backoff(2), 2 * INITIAL_RETRY_BACKOFF_SECS);
+        assert_eq!(retry_backoff(3), 4 * INITIAL_RETRY_BACKOFF_SECS);
+        assert_eq!(retry_backoff(100), MAX_RETRY_BACKOFF_SECS);
+    }
+}
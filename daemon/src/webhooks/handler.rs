@@ -0,0 +1,292 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use grid_sdk::store::StoreFactory;
+use grid_sdk::webhooks::{WebhookDelivery, WebhookStore, WebhookSubscription};
+
+use crate::event::entity::{EntityChange, EntityChangeReader};
+use crate::event::{CommitEvent, EventError, EventHandler};
+
+/// The number of subscriptions fetched from the store at a time
+const SUBSCRIPTION_PAGE_SIZE: i64 = 100;
+
+/// The body of a webhook request
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    /// Identifies the event; a subscriber receives each event at most once per subscription,
+    /// though the same event may be retried
+    id: &'a str,
+    #[serde(flatten)]
+    change: &'a EntityChange,
+}
+
+/// Queues a delivery of each entity change in a commit for every subscription that matches it
+///
+/// The handler reads the changes from the stores, so it must come after the handler that indexes
+/// the commit.
+#[derive(Clone)]
+pub struct WebhookEventHandler {
+    reader: EntityChangeReader,
+    webhook_store: Arc<dyn WebhookStore>,
+}
+
+impl WebhookEventHandler {
+    pub fn new(store_factory: &dyn StoreFactory, webhook_store: Arc<dyn WebhookStore>) -> Self {
+        WebhookEventHandler {
+            reader: EntityChangeReader::new(store_factory),
+            webhook_store,
+        }
+    }
+
+    fn subscriptions(&self) -> Result<Vec<WebhookSubscription>, EventError> {
+        let mut subscriptions = vec![];
+        loop {
+            let page = self
+                .webhook_store
+                .list_subscriptions(subscriptions.len() as i64, SUBSCRIPTION_PAGE_SIZE)?;
+            let total = page.paging.total;
+            let received = page.data.len();
+            subscriptions.extend(page.data);
+            if received == 0 || subscriptions.len() as i64 >= total {
+                return Ok(subscriptions);
+            }
+        }
+    }
+}
+
+impl EventHandler for WebhookEventHandler {
+    fn name(&self) -> &str {
+        "webhooks"
+    }
+
+    fn handle_event(&self, event: &CommitEvent) -> Result<(), EventError> {
+        let subscriptions = self.subscriptions()?;
+        if subscriptions.is_empty() {
+            return Ok(());
+        }
+
+        let mut deliveries = vec![];
+        for (index, change) in self.reader.read_changes(event)?.iter().enumerate() {
+            let event_id = format!("{}-{}", change.commit_id, index);
+            let payload = serde_json::to_string(&WebhookPayload {
+                id: &event_id,
+                change,
+            })
+            .map_err(|err| EventError(format!("Unable to serialize webhook payload: {}", err)))?;
+
+            deliveries.extend(
+                subscriptions
+                    .iter()
+                    .filter(|subscription| {
+                        subscription.matches(
+                            change.entity_type.as_str(),
+                            change.owner.as_deref(),
+                            change.service_id.as_deref(),
+                        )
+                    })
+                    .filter_map(|subscription| subscription.id)
+                    .map(|id| WebhookDelivery::new(id, &event_id, &change.event_type, &payload)),
+            );
+        }
+
+        if !deliveries.is_empty() {
+            debug!(
+                "Queuing {} webhook deliveries for {}",
+                deliveries.len(),
+                event
+            );
+            self.webhook_store.add_deliveries(deliveries)?;
+        }
+
+        Ok(())
+    }
+
+    fn cloned_box(&self) -> Box<dyn EventHandler> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use grid_sdk::commits::store::Commit;
+    use grid_sdk::products::addressing::compute_gs1_product_address;
+    use grid_sdk::products::store::Product;
+    use grid_sdk::protocol::product::state::{
+        ProductBuilder, ProductListBuilder, ProductNamespace,
+    };
+    use grid_sdk::protos::IntoBytes;
+    use grid_sdk::store::memory::MemoryStoreFactory;
+    use grid_sdk::webhooks::MemoryWebhookStore;
+    use serde_json::Value as JsonValue;
+
+    use crate::event::StateChange;
+
+    const GTIN: &str = "762111906130";
+
+    #[test]
+    // Test that a product that is set for the first time is reported as created, and then as
+    // updated, and that changes are only queued for the subscriptions they match
+    fn test_product_changes_are_queued() {
+        let factory = MemoryStoreFactory::new();
+        let webhook_store = MemoryWebhookStore::new();
+        let all = webhook_store
+            .add_subscription(WebhookSubscription::new(
+                "http://localhost/all",
+                "secret",
+                vec![],
+                vec![],
+                None,
+            ))
+            .expect("Failed to add subscription");
+        let other_org = webhook_store
+            .add_subscription(WebhookSubscription::new(
+                "http://localhost/other",
+                "secret",
+                vec!["product".to_string()],
+                vec!["other-org".to_string()],
+                None,
+            ))
+            .expect("Failed to add subscription");
+        let handler = WebhookEventHandler::new(&factory, Arc::new(webhook_store.clone()));
+
+        let created = index_product(&factory, "commit-1", 1, "org-1");
+        handler
+            .handle_event(&created)
+            .expect("Failed to handle event");
+        let updated = index_product(&factory, "commit-2", 2, "org-2");
+        handler
+            .handle_event(&updated)
+            .expect("Failed to handle event");
+        // Handling an event again does not queue it twice
+        handler
+            .handle_event(&updated)
+            .expect("Failed to handle event");
+
+        let deliveries = webhook_store
+            .list_deliveries(all, 0, 10)
+            .expect("Failed to list deliveries")
+            .data;
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].event_id, "commit-1-0");
+        assert_eq!(deliveries[0].event_type, "product.created");
+        assert_eq!(deliveries[1].event_id, "commit-2-0");
+        assert_eq!(deliveries[1].event_type, "product.updated");
+
+        let payload: JsonValue =
+            serde_json::from_str(&deliveries[1].payload).expect("Invalid payload");
+        assert_eq!(payload["id"], "commit-2-0");
+        assert_eq!(payload["event_type"], "product.updated");
+        assert_eq!(payload["entity_type"], "product");
+        assert_eq!(payload["entity_id"], GTIN);
+        assert_eq!(payload["owner"], "org-2");
+        assert_eq!(payload["commit_id"], "commit-2");
+        assert_eq!(payload["commit_num"], 2);
+        assert_eq!(payload["data"]["product_id"], GTIN);
+        assert_eq!(payload["data"]["owner"], "org-2");
+
+        assert_eq!(
+            webhook_store
+                .list_deliveries(other_org, 0, 10)
+                .expect("Failed to list deliveries")
+                .paging
+                .total,
+            0
+        );
+    }
+
+    #[test]
+    // Test that an event is not handled until its commit has been indexed, so that it is retried
+    fn test_unindexed_commit_fails() {
+        let factory = MemoryStoreFactory::new();
+        let webhook_store = MemoryWebhookStore::new();
+        webhook_store
+            .add_subscription(WebhookSubscription::new(
+                "http://localhost",
+                "secret",
+                vec![],
+                vec![],
+                None,
+            ))
+            .expect("Failed to add subscription");
+        let handler = WebhookEventHandler::new(&factory, Arc::new(webhook_store));
+
+        let event = CommitEvent {
+            service_id: None,
+            id: "commit-1".to_string(),
+            height: Some(1),
+            state_changes: vec![],
+        };
+        assert!(handler.handle_event(&event).is_err());
+    }
+
+    /// Stores a product and its commit as the database handler would, returning the event
+    fn index_product(
+        factory: &MemoryStoreFactory,
+        commit_id: &str,
+        commit_num: i64,
+        owner: &str,
+    ) -> CommitEvent {
+        let address = compute_gs1_product_address(GTIN);
+
+        factory
+            .get_grid_commit_store()
+            .add_commit(Commit {
+                commit_id: commit_id.to_string(),
+                commit_num,
+                service_id: None,
+            })
+            .expect("Failed to add commit");
+        factory
+            .get_grid_product_store()
+            .add_product(Product {
+                product_id: GTIN.to_string(),
+                product_address: address.clone(),
+                product_namespace: "GS1".to_string(),
+                owner: owner.to_string(),
+                start_commit_num: commit_num,
+                end_commit_num: i64::MAX,
+                service_id: None,
+                properties: vec![],
+            })
+            .expect("Failed to add product");
+
+        let product = ProductBuilder::new()
+            .with_product_id(GTIN.to_string())
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_owner(owner.to_string())
+            .with_properties(vec![])
+            .build()
+            .expect("Failed to build product");
+        let value = ProductListBuilder::new()
+            .with_products(vec![product])
+            .build()
+            .expect("Failed to build product list")
+            .into_bytes()
+            .expect("Failed to serialize product list");
+
+        CommitEvent {
+            service_id: None,
+            id: commit_id.to_string(),
+            height: Some(commit_num as u64),
+            state_changes: vec![StateChange::Set {
+                key: address,
+                value,
+            }],
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Outbound webhooks.
+//!
+//! `WebhookEventHandler` turns each indexed commit into entity-level events and queues one
+//! delivery per matching subscription. `WebhookDeliverer` posts the queued deliveries on its own
+//! thread, signing each request with the subscription's secret and retrying failed deliveries
+//! with exponential backoff. Deliveries are kept in a `WebhookStore`, so they survive restarts.
+//!
+//! Each request carries the following headers:
+//!
+//! * `X-Grid-Event` - the event type, such as `product.created`
+//! * `X-Grid-Delivery` - the event identifier, which stays the same across retries
+//! * `X-Grid-Timestamp` - seconds since the Unix epoch at which the request was signed
+//! * `X-Grid-Signature` - `sha256=` followed by the hex encoded HMAC-SHA256 of the timestamp, a
+//!   period and the body, keyed with the subscription's secret
+
+mod delivery;
+mod handler;
+mod signature;
+
+pub use delivery::WebhookDeliverer;
+pub use handler::WebhookEventHandler;
@@ -0,0 +1,72 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Signs a request body, returning the value of the `X-Grid-Signature` header
+///
+/// # Arguments
+///
+///  * `secret` - The secret of the subscription the request is sent to
+///  * `timestamp` - The value of the `X-Grid-Timestamp` header
+///  * `body` - The body of the request
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    format!(
+        "sha256={}",
+        hmac_sha256_hex(
+            secret.as_bytes(),
+            format!("{}.{}", timestamp, body).as_bytes()
+        )
+    )
+}
+
+fn hmac_sha256_hex(key: &[u8], message: &[u8]) -> String {
+    // HMAC accepts keys of any length, so creating it cannot fail
+    let mut mac = Hmac::<Sha256>::new_varkey(key).expect("HMAC rejected key");
+    mac.input(message);
+    mac.result()
+        .code()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test the HMAC against test case 2 of RFC 4231
+    fn hmac_sha256() {
+        assert_eq!(
+            hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    // Test that the signature covers both the timestamp and the body
+    fn signature_covers_timestamp_and_body() {
+        let signature = sign("secret", 1600000000, "{}");
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(
+            signature,
+            format!("sha256={}", hmac_sha256_hex(b"secret", b"1600000000.{}"))
+        );
+        assert_ne!(signature, sign("secret", 1600000001, "{}"));
+        assert_ne!(signature, sign("secret", 1600000000, "[]"));
+        assert_ne!(signature, sign("other", 1600000000, "{}"));
+    }
+}
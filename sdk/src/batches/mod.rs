@@ -16,4 +16,6 @@ pub mod store;
 
 #[cfg(feature = "diesel")]
 pub use store::diesel::DieselBatchStore;
+#[cfg(feature = "memory")]
+pub use store::memory::MemoryBatchStore;
 pub use store::BatchStore;
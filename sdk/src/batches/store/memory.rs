@@ -0,0 +1,96 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::{ConstraintViolationError, ConstraintViolationType, InternalError};
+use crate::paging::Paging;
+
+use super::{Batch, BatchList, BatchStatus, BatchStore, BatchStoreError};
+
+/// Implementation of `BatchStore` that keeps batches in memory. Useful for tests and when
+/// persistence isn't necessary.
+#[derive(Clone, Default)]
+pub struct MemoryBatchStore {
+    inner: Arc<Mutex<Vec<Batch>>>,
+}
+
+impl MemoryBatchStore {
+    pub fn new() -> Self {
+        MemoryBatchStore::default()
+    }
+
+    fn batches(&self) -> Result<MutexGuard<'_, Vec<Batch>>, BatchStoreError> {
+        self.inner.lock().map_err(|_| {
+            BatchStoreError::InternalError(InternalError::with_message(
+                "Cannot access batches: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+fn batch_page<'a, I>(batches: I, offset: i64, limit: i64) -> BatchList
+where
+    I: Iterator<Item = &'a Batch> + Clone,
+{
+    let total = batches.clone().count() as i64;
+    let data = batches
+        .skip(offset.max(0) as usize)
+        .take(limit.max(0) as usize)
+        .cloned()
+        .collect();
+    BatchList::new(data, Paging::new(offset, limit, total))
+}
+
+impl BatchStore for MemoryBatchStore {
+    fn add_batch(&self, batch: Batch) -> Result<(), BatchStoreError> {
+        let mut batches = self.batches()?;
+        if batches.iter().any(|existing| existing.id == batch.id) {
+            return Err(BatchStoreError::ConstraintViolationError(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::Unique),
+            ));
+        }
+        batches.push(batch);
+        Ok(())
+    }
+
+    fn fetch_batch(&self, id: &str) -> Result<Option<Batch>, BatchStoreError> {
+        Ok(self.batches()?.iter().find(|batch| batch.id == id).cloned())
+    }
+
+    fn list_batches(&self, offset: i64, limit: i64) -> Result<BatchList, BatchStoreError> {
+        Ok(batch_page(self.batches()?.iter(), offset, limit))
+    }
+
+    fn list_batches_with_status(
+        &self,
+        status: BatchStatus,
+        offset: i64,
+        limit: i64,
+    ) -> Result<BatchList, BatchStoreError> {
+        let batches = self.batches()?;
+        Ok(batch_page(
+            batches.iter().filter(|batch| batch.status == status),
+            offset,
+            limit,
+        ))
+    }
+
+    fn update_status(&self, id: &str, status: BatchStatus) -> Result<(), BatchStoreError> {
+        if let Some(batch) = self.batches()?.iter_mut().find(|batch| batch.id == id) {
+            batch.status = status;
+        }
+        Ok(())
+    }
+}
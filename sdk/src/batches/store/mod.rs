@@ -15,6 +15,8 @@
 #[cfg(feature = "diesel")]
 pub mod diesel;
 mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
 
 use std::fmt;
 
@@ -61,8 +63,8 @@ impl Batch {
 
 #[derive(Clone, Debug)]
 pub struct BatchList {
-    data: Vec<Batch>,
-    paging: Paging,
+    pub data: Vec<Batch>,
+    pub paging: Paging,
 }
 
 impl BatchList {
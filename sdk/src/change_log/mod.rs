@@ -0,0 +1,26 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A log of the entity changes made by each commit.
+//!
+//! Entries are recorded as commits are indexed, so that consumers of a live stream of changes
+//! can catch up on the commits they missed while disconnected.
+
+pub mod store;
+
+#[cfg(feature = "diesel")]
+pub use store::diesel::DieselChangeLogStore;
+#[cfg(feature = "memory")]
+pub use store::memory::MemoryChangeLogStore;
+pub use store::{ChangeLogEntry, ChangeLogStore, ChangeLogStoreError};
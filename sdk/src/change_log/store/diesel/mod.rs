@@ -0,0 +1,135 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod models;
+mod operations;
+pub(in crate) mod schema;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use super::{ChangeLogEntry, ChangeLogStore, ChangeLogStoreError};
+
+use models::{ChangeLogEntryModel, NewChangeLogEntryModel};
+use operations::add_entries::AddEntriesOperation as _;
+use operations::list_entries::ListEntriesOperation as _;
+use operations::ChangeLogStoreOperations;
+
+/// Manages the change log in the database
+#[derive(Clone)]
+pub struct DieselChangeLogStore<C: diesel::Connection + 'static> {
+    connection_pool: Pool<ConnectionManager<C>>,
+}
+
+impl<C: diesel::Connection> DieselChangeLogStore<C> {
+    /// Creates a new DieselChangeLogStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselChangeLogStore { connection_pool }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ChangeLogStore for DieselChangeLogStore<diesel::pg::PgConnection> {
+    fn add_entries(
+        &self,
+        commit_num: i64,
+        service_id: Option<&str>,
+        entries: Vec<ChangeLogEntry>,
+    ) -> Result<(), ChangeLogStoreError> {
+        ChangeLogStoreOperations::new(&*self.connection_pool.get()?).add_entries(
+            commit_num,
+            service_id,
+            entries
+                .into_iter()
+                .map(NewChangeLogEntryModel::from)
+                .collect(),
+        )
+    }
+
+    fn list_entries(
+        &self,
+        after_commit_num: i64,
+        service_id: Option<&str>,
+        commit_limit: i64,
+    ) -> Result<Vec<ChangeLogEntry>, ChangeLogStoreError> {
+        Ok(ChangeLogStoreOperations::new(&*self.connection_pool.get()?)
+            .list_entries(after_commit_num, service_id, commit_limit)?
+            .into_iter()
+            .map(ChangeLogEntry::from)
+            .collect())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ChangeLogStore for DieselChangeLogStore<diesel::sqlite::SqliteConnection> {
+    fn add_entries(
+        &self,
+        commit_num: i64,
+        service_id: Option<&str>,
+        entries: Vec<ChangeLogEntry>,
+    ) -> Result<(), ChangeLogStoreError> {
+        ChangeLogStoreOperations::new(&*self.connection_pool.get()?).add_entries(
+            commit_num,
+            service_id,
+            entries
+                .into_iter()
+                .map(NewChangeLogEntryModel::from)
+                .collect(),
+        )
+    }
+
+    fn list_entries(
+        &self,
+        after_commit_num: i64,
+        service_id: Option<&str>,
+        commit_limit: i64,
+    ) -> Result<Vec<ChangeLogEntry>, ChangeLogStoreError> {
+        Ok(ChangeLogStoreOperations::new(&*self.connection_pool.get()?)
+            .list_entries(after_commit_num, service_id, commit_limit)?
+            .into_iter()
+            .map(ChangeLogEntry::from)
+            .collect())
+    }
+}
+
+impl From<ChangeLogEntry> for NewChangeLogEntryModel {
+    fn from(entry: ChangeLogEntry) -> Self {
+        NewChangeLogEntryModel {
+            commit_id: entry.commit_id,
+            commit_num: entry.commit_num,
+            service_id: entry.service_id,
+            entity_type: entry.entity_type,
+            entity_id: entry.entity_id,
+            event_type: entry.event_type,
+            payload: entry.payload,
+        }
+    }
+}
+
+impl From<ChangeLogEntryModel> for ChangeLogEntry {
+    fn from(model: ChangeLogEntryModel) -> Self {
+        ChangeLogEntry {
+            commit_id: model.commit_id,
+            commit_num: model.commit_num,
+            service_id: model.service_id,
+            entity_type: model.entity_type,
+            entity_id: model.entity_id,
+            event_type: model.event_type,
+            payload: model.payload,
+        }
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::ChangeLogStoreOperations;
+use crate::change_log::store::diesel::{models::NewChangeLogEntryModel, schema::change_log};
+use crate::change_log::store::ChangeLogStoreError;
+
+use diesel::{dsl::delete, prelude::*};
+
+pub(in crate::change_log::store::diesel) trait AddEntriesOperation {
+    fn add_entries(
+        &self,
+        commit_num: i64,
+        service_id: Option<&str>,
+        models: Vec<NewChangeLogEntryModel>,
+    ) -> Result<(), ChangeLogStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AddEntriesOperation for ChangeLogStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_entries(
+        &self,
+        commit_num: i64,
+        service_id: Option<&str>,
+        models: Vec<NewChangeLogEntryModel>,
+    ) -> Result<(), ChangeLogStoreError> {
+        self.conn.transaction::<_, ChangeLogStoreError, _>(|| {
+            let replaced = change_log::table.filter(change_log::commit_num.ge(commit_num));
+            if let Some(service_id) = service_id {
+                delete(replaced.filter(change_log::service_id.eq(service_id)))
+                    .execute(self.conn)?;
+            } else {
+                delete(replaced.filter(change_log::service_id.is_null())).execute(self.conn)?;
+            }
+
+            if !models.is_empty() {
+                diesel::insert_into(change_log::table)
+                    .values(&models)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AddEntriesOperation for ChangeLogStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn add_entries(
+        &self,
+        commit_num: i64,
+        service_id: Option<&str>,
+        models: Vec<NewChangeLogEntryModel>,
+    ) -> Result<(), ChangeLogStoreError> {
+        self.conn.transaction::<_, ChangeLogStoreError, _>(|| {
+            let replaced = change_log::table.filter(change_log::commit_num.ge(commit_num));
+            if let Some(service_id) = service_id {
+                delete(replaced.filter(change_log::service_id.eq(service_id)))
+                    .execute(self.conn)?;
+            } else {
+                delete(replaced.filter(change_log::service_id.is_null())).execute(self.conn)?;
+            }
+
+            for model in &models {
+                diesel::insert_into(change_log::table)
+                    .values(model)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
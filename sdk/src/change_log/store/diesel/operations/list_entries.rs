@@ -0,0 +1,98 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::ChangeLogStoreOperations;
+use crate::change_log::store::diesel::{models::ChangeLogEntryModel, schema::change_log};
+use crate::change_log::store::ChangeLogStoreError;
+
+use diesel::prelude::*;
+
+pub(in crate::change_log::store::diesel) trait ListEntriesOperation {
+    fn list_entries(
+        &self,
+        after_commit_num: i64,
+        service_id: Option<&str>,
+        commit_limit: i64,
+    ) -> Result<Vec<ChangeLogEntryModel>, ChangeLogStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ListEntriesOperation for ChangeLogStoreOperations<'a, diesel::pg::PgConnection> {
+    fn list_entries(
+        &self,
+        after_commit_num: i64,
+        service_id: Option<&str>,
+        commit_limit: i64,
+    ) -> Result<Vec<ChangeLogEntryModel>, ChangeLogStoreError> {
+        let mut commit_query = change_log::table
+            .into_boxed()
+            .select(change_log::commit_num)
+            .distinct()
+            .filter(change_log::commit_num.gt(after_commit_num));
+        let mut query = change_log::table.into_boxed();
+
+        if let Some(service_id) = service_id {
+            commit_query = commit_query.filter(change_log::service_id.eq(service_id));
+            query = query.filter(change_log::service_id.eq(service_id));
+        } else {
+            commit_query = commit_query.filter(change_log::service_id.is_null());
+            query = query.filter(change_log::service_id.is_null());
+        }
+
+        let commit_nums = commit_query
+            .order(change_log::commit_num.asc())
+            .limit(commit_limit)
+            .load::<i64>(self.conn)?;
+
+        Ok(query
+            .filter(change_log::commit_num.eq_any(commit_nums))
+            .order((change_log::commit_num.asc(), change_log::id.asc()))
+            .load::<ChangeLogEntryModel>(self.conn)?)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ListEntriesOperation for ChangeLogStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn list_entries(
+        &self,
+        after_commit_num: i64,
+        service_id: Option<&str>,
+        commit_limit: i64,
+    ) -> Result<Vec<ChangeLogEntryModel>, ChangeLogStoreError> {
+        let mut commit_query = change_log::table
+            .into_boxed()
+            .select(change_log::commit_num)
+            .distinct()
+            .filter(change_log::commit_num.gt(after_commit_num));
+        let mut query = change_log::table.into_boxed();
+
+        if let Some(service_id) = service_id {
+            commit_query = commit_query.filter(change_log::service_id.eq(service_id));
+            query = query.filter(change_log::service_id.eq(service_id));
+        } else {
+            commit_query = commit_query.filter(change_log::service_id.is_null());
+            query = query.filter(change_log::service_id.is_null());
+        }
+
+        let commit_nums = commit_query
+            .order(change_log::commit_num.asc())
+            .limit(commit_limit)
+            .load::<i64>(self.conn)?;
+
+        Ok(query
+            .filter(change_log::commit_num.eq_any(commit_nums))
+            .order((change_log::commit_num.asc(), change_log::id.asc()))
+            .load::<ChangeLogEntryModel>(self.conn)?)
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "diesel")]
+use crate::error::ConstraintViolationType;
+use crate::error::{ConstraintViolationError, InternalError, ResourceTemporarilyUnavailableError};
+
+/// Represents ChangeLogStore errors
+#[derive(Debug)]
+pub enum ChangeLogStoreError {
+    InternalError(InternalError),
+    ConstraintViolationError(ConstraintViolationError),
+    ResourceTemporarilyUnavailableError(ResourceTemporarilyUnavailableError),
+}
+
+impl Error for ChangeLogStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ChangeLogStoreError::InternalError(err) => Some(err),
+            ChangeLogStoreError::ConstraintViolationError(err) => Some(err),
+            ChangeLogStoreError::ResourceTemporarilyUnavailableError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for ChangeLogStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChangeLogStoreError::InternalError(err) => err.fmt(f),
+            ChangeLogStoreError::ConstraintViolationError(err) => err.fmt(f),
+            ChangeLogStoreError::ResourceTemporarilyUnavailableError(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for ChangeLogStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        match err {
+            diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            ) => ChangeLogStoreError::ConstraintViolationError(
+                ConstraintViolationError::from_source_with_violation_type(
+                    ConstraintViolationType::Unique,
+                    Box::new(err),
+                ),
+            ),
+            _ => ChangeLogStoreError::InternalError(InternalError::from_source(Box::new(err))),
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for ChangeLogStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> ChangeLogStoreError {
+        ChangeLogStoreError::ResourceTemporarilyUnavailableError(
+            ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+        )
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::InternalError;
+
+use super::{ChangeLogEntry, ChangeLogStore, ChangeLogStoreError};
+
+/// Implementation of `ChangeLogStore` that keeps entries in memory. Useful for tests and when
+/// persistence isn't necessary.
+#[derive(Clone, Default)]
+pub struct MemoryChangeLogStore {
+    entries: Arc<Mutex<Vec<ChangeLogEntry>>>,
+}
+
+impl MemoryChangeLogStore {
+    pub fn new() -> Self {
+        MemoryChangeLogStore::default()
+    }
+
+    fn entries(&self) -> Result<MutexGuard<'_, Vec<ChangeLogEntry>>, ChangeLogStoreError> {
+        self.entries.lock().map_err(|_| {
+            ChangeLogStoreError::InternalError(InternalError::with_message(
+                "Cannot access change log: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+impl ChangeLogStore for MemoryChangeLogStore {
+    fn add_entries(
+        &self,
+        commit_num: i64,
+        service_id: Option<&str>,
+        entries: Vec<ChangeLogEntry>,
+    ) -> Result<(), ChangeLogStoreError> {
+        let mut log = self.entries()?;
+        log.retain(|entry| {
+            entry.service_id.as_deref() != service_id || entry.commit_num < commit_num
+        });
+        log.extend(entries);
+        Ok(())
+    }
+
+    fn list_entries(
+        &self,
+        after_commit_num: i64,
+        service_id: Option<&str>,
+        commit_limit: i64,
+    ) -> Result<Vec<ChangeLogEntry>, ChangeLogStoreError> {
+        let log = self.entries()?;
+        let mut entries = log
+            .iter()
+            .filter(|entry| {
+                entry.service_id.as_deref() == service_id && entry.commit_num > after_commit_num
+            })
+            .collect::<Vec<_>>();
+        // Entries are recorded in order, so a stable sort keeps a commit's entries in order
+        entries.sort_by_key(|entry| entry.commit_num);
+
+        let mut commits = 0;
+        let mut last_commit_num = None;
+        Ok(entries
+            .into_iter()
+            .take_while(|entry| {
+                if last_commit_num != Some(entry.commit_num) {
+                    last_commit_num = Some(entry.commit_num);
+                    commits += 1;
+                }
+                commits <= commit_limit
+            })
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
+
+pub use error::ChangeLogStoreError;
+
+/// A change made to an entity by a commit
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeLogEntry {
+    pub commit_id: String,
+    pub commit_num: i64,
+    pub service_id: Option<String>,
+    /// The type of the entity, such as `product`
+    pub entity_type: String,
+    pub entity_id: String,
+    /// The type of the change, such as `product.created`
+    pub event_type: String,
+    /// The change, serialized as JSON
+    pub payload: String,
+}
+
+pub trait ChangeLogStore: Send + Sync {
+    /// Records the entries for a commit, in order. Any entries already recorded for the service at
+    /// or after the commit's number are replaced, as they belong to a commit that is being
+    /// handled again or to a fork that has been abandoned.
+    ///
+    /// # Arguments
+    ///
+    ///  * `commit_num` - The number of the commit
+    ///  * `service_id` - The service the commit belongs to, if any
+    ///  * `entries` - The entries for the commit, which may be empty
+    fn add_entries(
+        &self,
+        commit_num: i64,
+        service_id: Option<&str>,
+        entries: Vec<ChangeLogEntry>,
+    ) -> Result<(), ChangeLogStoreError>;
+
+    /// Lists the entries for the commits of a service that come after the given commit, in the
+    /// order they were recorded. Whole commits are returned: the entries of at most
+    /// `commit_limit` commits are listed, and a commit is never split across calls.
+    ///
+    /// # Arguments
+    ///
+    ///  * `after_commit_num` - The number of the last commit that is not to be listed
+    ///  * `service_id` - The service the commits belong to, if any
+    ///  * `commit_limit` - The maximum number of commits to list entries for
+    fn list_entries(
+        &self,
+        after_commit_num: i64,
+        service_id: Option<&str>,
+        commit_limit: i64,
+    ) -> Result<Vec<ChangeLogEntry>, ChangeLogStoreError>;
+}
+
+impl<CS> ChangeLogStore for Box<CS>
+where
+    CS: ChangeLogStore + ?Sized,
+{
+    fn add_entries(
+        &self,
+        commit_num: i64,
+        service_id: Option<&str>,
+        entries: Vec<ChangeLogEntry>,
+    ) -> Result<(), ChangeLogStoreError> {
+        (**self).add_entries(commit_num, service_id, entries)
+    }
+
+    fn list_entries(
+        &self,
+        after_commit_num: i64,
+        service_id: Option<&str>,
+        commit_limit: i64,
+    ) -> Result<Vec<ChangeLogEntry>, ChangeLogStoreError> {
+        (**self).list_entries(after_commit_num, service_id, commit_limit)
+    }
+}
+
+#[cfg(all(test, any(feature = "memory", feature = "sqlite")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "memory")]
+    #[test]
+    // Test that the memory store lists whole commits in order and replaces abandoned commits
+    fn test_memory_change_log_store() {
+        check_change_log_store(&memory::MemoryChangeLogStore::new());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    // Test that the SQLite store lists whole commits in order and replaces abandoned commits
+    fn test_sqlite_change_log_store() {
+        use ::diesel::r2d2::{ConnectionManager, Pool};
+
+        let connection_manager =
+            ConnectionManager::<::diesel::sqlite::SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+        crate::migrations::run_sqlite_migrations(&*pool.get().expect("Failed to get connection"))
+            .expect("Failed to run migrations");
+
+        check_change_log_store(&diesel::DieselChangeLogStore::new(pool));
+    }
+
+    fn entry(commit_num: i64, service_id: Option<&str>, entity_id: &str) -> ChangeLogEntry {
+        ChangeLogEntry {
+            commit_id: format!("commit-{}", commit_num),
+            commit_num,
+            service_id: service_id.map(String::from),
+            entity_type: "product".to_string(),
+            entity_id: entity_id.to_string(),
+            event_type: "product.updated".to_string(),
+            payload: "{}".to_string(),
+        }
+    }
+
+    fn entity_ids(entries: &[ChangeLogEntry]) -> Vec<(i64, &str)> {
+        entries
+            .iter()
+            .map(|entry| (entry.commit_num, entry.entity_id.as_str()))
+            .collect()
+    }
+
+    fn check_change_log_store(store: &dyn ChangeLogStore) {
+        store
+            .add_entries(1, None, vec![entry(1, None, "b"), entry(1, None, "a")])
+            .expect("Failed to add entries");
+        store
+            .add_entries(2, None, vec![])
+            .expect("Failed to add entries");
+        store
+            .add_entries(3, None, vec![entry(3, None, "c")])
+            .expect("Failed to add entries");
+        store
+            .add_entries(1, Some("service-1"), vec![entry(1, Some("service-1"), "d")])
+            .expect("Failed to add entries");
+
+        let entries = store
+            .list_entries(0, None, 10)
+            .expect("Failed to list entries");
+        assert_eq!(entity_ids(&entries), vec![(1, "b"), (1, "a"), (3, "c")]);
+
+        // Commits without entries are not counted against the limit
+        let entries = store
+            .list_entries(0, None, 1)
+            .expect("Failed to list entries");
+        assert_eq!(entity_ids(&entries), vec![(1, "b"), (1, "a")]);
+        let entries = store
+            .list_entries(1, None, 1)
+            .expect("Failed to list entries");
+        assert_eq!(entity_ids(&entries), vec![(3, "c")]);
+
+        let entries = store
+            .list_entries(0, Some("service-1"), 10)
+            .expect("Failed to list entries");
+        assert_eq!(entity_ids(&entries), vec![(1, "d")]);
+
+        // A fork at commit 3 replaces it without touching other services
+        store
+            .add_entries(3, None, vec![entry(3, None, "e")])
+            .expect("Failed to add entries");
+        let entries = store
+            .list_entries(1, None, 10)
+            .expect("Failed to list entries");
+        assert_eq!(entity_ids(&entries), vec![(3, "e")]);
+
+        // A fork at commit 1 abandons everything after it
+        store
+            .add_entries(1, None, vec![])
+            .expect("Failed to add entries");
+        assert!(store
+            .list_entries(0, None, 10)
+            .expect("Failed to list entries")
+            .is_empty());
+        assert_eq!(
+            store
+                .list_entries(0, Some("service-1"), 10)
+                .expect("Failed to list entries")
+                .len(),
+            1
+        );
+    }
+}
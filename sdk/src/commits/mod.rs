@@ -16,6 +16,88 @@ pub mod store;
 
 pub const MAX_COMMIT_NUM: i64 = i64::MAX;
 
+/// Returns the commit height at which to read state: the given height, or the height of the
+/// current state if none is given
+///
+/// A row is part of the state at height `h` if its `start_commit_num <= h` and its
+/// `end_commit_num > h`. Rows that are still current have an `end_commit_num` of
+/// `MAX_COMMIT_NUM`, so they are exactly the rows selected at height `MAX_COMMIT_NUM - 1`.
+pub fn commit_height_or_current(commit_height: Option<i64>) -> i64 {
+    commit_height.unwrap_or(MAX_COMMIT_NUM - 1)
+}
+
+/// A row of state that is started and ended by commits, for stores that keep their state in
+/// memory
+#[cfg(feature = "memory")]
+pub(crate) trait Versioned {
+    fn start_commit_num(&self) -> i64;
+
+    fn end_commit_num(&self) -> i64;
+
+    fn set_end_commit_num(&mut self, end_commit_num: i64);
+
+    /// Returns whether the row is part of the state at the given height
+    fn is_current_at(&self, height: i64) -> bool {
+        self.start_commit_num() <= height && self.end_commit_num() > height
+    }
+
+    /// Returns whether the row is part of the current state
+    fn is_current(&self) -> bool {
+        self.end_commit_num() == MAX_COMMIT_NUM
+    }
+}
+
+/// Implements `Versioned` for types with `start_commit_num` and `end_commit_num` fields
+#[cfg(feature = "memory")]
+macro_rules! impl_versioned {
+    ($($row:ty),* $(,)?) => {
+        $(
+            impl crate::commits::Versioned for $row {
+                fn start_commit_num(&self) -> i64 {
+                    self.start_commit_num
+                }
+
+                fn end_commit_num(&self) -> i64 {
+                    self.end_commit_num
+                }
+
+                fn set_end_commit_num(&mut self, end_commit_num: i64) {
+                    self.end_commit_num = end_commit_num;
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "memory")]
+pub(crate) use impl_versioned;
+
+/// Ends the current rows that match, at the given commit
+#[cfg(feature = "memory")]
+pub(crate) fn end_current_rows<T, F>(rows: &mut [T], end_commit_num: i64, matches: F)
+where
+    T: Versioned,
+    F: Fn(&T) -> bool,
+{
+    for row in rows.iter_mut() {
+        if row.is_current() && matches(row) {
+            row.set_end_commit_num(end_commit_num);
+        }
+    }
+}
+
+/// Rolls rows back to the state before the given commit, as when a fork is resolved: rows
+/// started at or after the commit are removed, and rows ended at or after it are current again
+#[cfg(feature = "memory")]
+pub(crate) fn resolve_fork_rows<T: Versioned>(rows: &mut Vec<T>, commit_num: i64) {
+    rows.retain(|row| row.start_commit_num() < commit_num);
+    for row in rows.iter_mut() {
+        if row.end_commit_num() >= commit_num {
+            row.set_end_commit_num(MAX_COMMIT_NUM);
+        }
+    }
+}
+
 #[cfg(feature = "diesel")]
 pub use store::diesel::DieselCommitStore;
 pub use store::memory::MemoryCommitStore;
@@ -26,6 +26,7 @@ use crate::error::{
 };
 use operations::add_commit::CommitStoreAddCommitOperation as _;
 use operations::create_db_commit_from_commit_event::CommitStoreCreateDbCommitFromCommitEventOperation as _;
+use operations::get_commit_by_commit_id::CommitStoreGetCommitByCommitIdOperation as _;
 use operations::get_commit_by_commit_num::CommitStoreGetCommitByCommitNumOperation as _;
 use operations::get_current_commit_id::CommitStoreGetCurrentCommitIdOperation as _;
 use operations::get_next_commit_num::CommitStoreGetNextCommitNumOperation as _;
@@ -81,6 +82,15 @@ impl CommitStore for DieselCommitStore<diesel::pg::PgConnection> {
         .get_commit_by_commit_num(commit_num)
     }
 
+    fn get_commit_by_commit_id(&self, commit_id: &str) -> Result<Option<Commit>, CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            CommitStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .get_commit_by_commit_id(commit_id)
+    }
+
     fn get_current_commit_id(&self) -> Result<Option<String>, CommitStoreError> {
         CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             CommitStoreError::ResourceTemporarilyUnavailableError(
@@ -144,6 +154,15 @@ impl CommitStore for DieselCommitStore<diesel::sqlite::SqliteConnection> {
         .get_commit_by_commit_num(commit_num)
     }
 
+    fn get_commit_by_commit_id(&self, commit_id: &str) -> Result<Option<Commit>, CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            CommitStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .get_commit_by_commit_id(commit_id)
+    }
+
     fn get_current_commit_id(&self) -> Result<Option<String>, CommitStoreError> {
         CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             CommitStoreError::ResourceTemporarilyUnavailableError(
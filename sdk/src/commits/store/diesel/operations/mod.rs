@@ -14,6 +14,7 @@
 
 pub(super) mod add_commit;
 pub(super) mod create_db_commit_from_commit_event;
+pub(super) mod get_commit_by_commit_id;
 pub(super) mod get_commit_by_commit_num;
 pub(super) mod get_current_commit_id;
 pub(super) mod get_next_commit_num;
@@ -96,6 +96,15 @@ impl CommitStore for MemoryCommitStore {
         }
     }
 
+    fn get_commit_by_commit_id(&self, commit_id: &str) -> Result<Option<Commit>, CommitStoreError> {
+        let inner_commit = self.inner_commit.lock().map_err(|_| {
+            CommitStoreError::InternalError(InternalError::with_message(
+                "Cannot access commits: mutex lock poisoned".to_string(),
+            ))
+        })?;
+        Ok(inner_commit.get(commit_id).cloned())
+    }
+
     fn get_current_commit_id(&self) -> Result<Option<String>, CommitStoreError> {
         let inner_commit = self.inner_commit.lock().map_err(|_| {
             CommitStoreError::InternalError(InternalError::with_message(
@@ -71,6 +71,13 @@ pub trait CommitStore: Send + Sync {
     fn get_commit_by_commit_num(&self, commit_num: i64)
         -> Result<Option<Commit>, CommitStoreError>;
 
+    /// Gets a commit from the underlying storage by its ID
+    ///
+    /// # Arguments
+    ///
+    ///  * `commit_id` - The ID of the commit to be fetched, such as a block ID
+    fn get_commit_by_commit_id(&self, commit_id: &str) -> Result<Option<Commit>, CommitStoreError>;
+
     /// Gets the current commit ID from the underlying storage
     fn get_current_commit_id(&self) -> Result<Option<String>, CommitStoreError>;
 
@@ -110,6 +117,10 @@ where
         (**self).get_commit_by_commit_num(commit_num)
     }
 
+    fn get_commit_by_commit_id(&self, commit_id: &str) -> Result<Option<Commit>, CommitStoreError> {
+        (**self).get_commit_by_commit_id(commit_id)
+    }
+
     fn get_current_commit_id(&self) -> Result<Option<String>, CommitStoreError> {
         (**self).get_current_commit_id()
     }
@@ -0,0 +1,201 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod models;
+mod operations;
+pub(in crate) mod schema;
+
+use std::convert::TryFrom;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use super::{DeadLetter, DeadLetterList, DeadLetterStore, DeadLetterStoreError};
+use crate::commits::store::{CommitEvent, StateChange};
+use crate::error::InternalError;
+use crate::hex;
+use crate::paging::Paging;
+
+use models::{DeadLetterModel, NewDeadLetterModel, StateChangeModel};
+use operations::add_dead_letter::AddDeadLetterOperation as _;
+use operations::fetch_dead_letter::FetchDeadLetterOperation as _;
+use operations::list_dead_letters::ListDeadLettersOperation as _;
+use operations::record_attempt::RecordAttemptOperation as _;
+use operations::remove_dead_letter::RemoveDeadLetterOperation as _;
+use operations::DeadLetterStoreOperations;
+
+/// Manages dead letters in the database
+#[derive(Clone)]
+pub struct DieselDeadLetterStore<C: diesel::Connection + 'static> {
+    connection_pool: Pool<ConnectionManager<C>>,
+}
+
+impl<C: diesel::Connection> DieselDeadLetterStore<C> {
+    /// Creates a new DieselDeadLetterStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselDeadLetterStore { connection_pool }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl DeadLetterStore for DieselDeadLetterStore<diesel::pg::PgConnection> {
+    fn add_dead_letter(&self, dead_letter: DeadLetter) -> Result<i64, DeadLetterStoreError> {
+        let model = NewDeadLetterModel::try_from(dead_letter)?;
+        DeadLetterStoreOperations::new(&*self.connection_pool.get()?).add_dead_letter(model)
+    }
+
+    fn fetch_dead_letter(&self, id: i64) -> Result<Option<DeadLetter>, DeadLetterStoreError> {
+        DeadLetterStoreOperations::new(&*self.connection_pool.get()?)
+            .fetch_dead_letter(id)?
+            .map(DeadLetter::try_from)
+            .transpose()
+    }
+
+    fn list_dead_letters(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<DeadLetterList, DeadLetterStoreError> {
+        let (models, total) = DeadLetterStoreOperations::new(&*self.connection_pool.get()?)
+            .list_dead_letters(offset, limit)?;
+        let data = models
+            .into_iter()
+            .map(DeadLetter::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(DeadLetterList::new(data, Paging::new(offset, limit, total)))
+    }
+
+    fn record_attempt(&self, id: i64, error: &str) -> Result<(), DeadLetterStoreError> {
+        DeadLetterStoreOperations::new(&*self.connection_pool.get()?).record_attempt(id, error)
+    }
+
+    fn remove_dead_letter(&self, id: i64) -> Result<(), DeadLetterStoreError> {
+        DeadLetterStoreOperations::new(&*self.connection_pool.get()?).remove_dead_letter(id)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl DeadLetterStore for DieselDeadLetterStore<diesel::sqlite::SqliteConnection> {
+    fn add_dead_letter(&self, dead_letter: DeadLetter) -> Result<i64, DeadLetterStoreError> {
+        let model = NewDeadLetterModel::try_from(dead_letter)?;
+        DeadLetterStoreOperations::new(&*self.connection_pool.get()?).add_dead_letter(model)
+    }
+
+    fn fetch_dead_letter(&self, id: i64) -> Result<Option<DeadLetter>, DeadLetterStoreError> {
+        DeadLetterStoreOperations::new(&*self.connection_pool.get()?)
+            .fetch_dead_letter(id)?
+            .map(DeadLetter::try_from)
+            .transpose()
+    }
+
+    fn list_dead_letters(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<DeadLetterList, DeadLetterStoreError> {
+        let (models, total) = DeadLetterStoreOperations::new(&*self.connection_pool.get()?)
+            .list_dead_letters(offset, limit)?;
+        let data = models
+            .into_iter()
+            .map(DeadLetter::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(DeadLetterList::new(data, Paging::new(offset, limit, total)))
+    }
+
+    fn record_attempt(&self, id: i64, error: &str) -> Result<(), DeadLetterStoreError> {
+        DeadLetterStoreOperations::new(&*self.connection_pool.get()?).record_attempt(id, error)
+    }
+
+    fn remove_dead_letter(&self, id: i64) -> Result<(), DeadLetterStoreError> {
+        DeadLetterStoreOperations::new(&*self.connection_pool.get()?).remove_dead_letter(id)
+    }
+}
+
+impl TryFrom<DeadLetter> for NewDeadLetterModel {
+    type Error = DeadLetterStoreError;
+
+    fn try_from(dead_letter: DeadLetter) -> Result<Self, Self::Error> {
+        let state_changes = dead_letter
+            .event
+            .state_changes
+            .iter()
+            .map(|change| match change {
+                StateChange::Set { key, value } => StateChangeModel {
+                    key: key.clone(),
+                    value: Some(hex::to_hex(value)),
+                },
+                StateChange::Delete { key } => StateChangeModel {
+                    key: key.clone(),
+                    value: None,
+                },
+            })
+            .collect::<Vec<_>>();
+
+        Ok(NewDeadLetterModel {
+            handler: dead_letter.handler,
+            commit_id: dead_letter.event.id,
+            commit_height: dead_letter.event.height.map(|height| height as i64),
+            service_id: dead_letter.event.service_id,
+            state_changes: serde_json::to_string(&state_changes).map_err(|err| {
+                DeadLetterStoreError::InternalError(InternalError::from_source(Box::new(err)))
+            })?,
+            error: dead_letter.error,
+            attempts: dead_letter.attempts,
+            created_at: dead_letter.created_at,
+        })
+    }
+}
+
+impl TryFrom<DeadLetterModel> for DeadLetter {
+    type Error = DeadLetterStoreError;
+
+    fn try_from(model: DeadLetterModel) -> Result<Self, Self::Error> {
+        let state_changes = serde_json::from_str::<Vec<StateChangeModel>>(&model.state_changes)
+            .map_err(|err| {
+                DeadLetterStoreError::InternalError(InternalError::from_source(Box::new(err)))
+            })?
+            .into_iter()
+            .map(|change| match change.value {
+                Some(value) => Ok(StateChange::Set {
+                    key: change.key,
+                    value: hex::parse_hex(&value).map_err(|err| {
+                        DeadLetterStoreError::InternalError(InternalError::from_source(Box::new(
+                            err,
+                        )))
+                    })?,
+                }),
+                None => Ok(StateChange::Delete { key: change.key }),
+            })
+            .collect::<Result<_, DeadLetterStoreError>>()?;
+
+        Ok(DeadLetter {
+            id: Some(model.id),
+            handler: model.handler,
+            event: CommitEvent {
+                service_id: model.service_id,
+                id: model.commit_id,
+                height: model.commit_height.map(|height| height as u64),
+                state_changes,
+            },
+            error: model.error,
+            attempts: model.attempts,
+            created_at: model.created_at,
+        })
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::schema::dead_letter;
+
+#[derive(Insertable, PartialEq, Debug)]
+#[table_name = "dead_letter"]
+pub struct NewDeadLetterModel {
+    pub handler: String,
+    pub commit_id: String,
+    pub commit_height: Option<i64>,
+    pub service_id: Option<String>,
+    pub state_changes: String,
+    pub error: String,
+    pub attempts: i32,
+    pub created_at: i64,
+}
+
+#[derive(Queryable, PartialEq, Debug)]
+pub struct DeadLetterModel {
+    pub id: i64,
+    pub handler: String,
+    pub commit_id: String,
+    pub commit_height: Option<i64>,
+    pub service_id: Option<String>,
+    pub state_changes: String,
+    pub error: String,
+    pub attempts: i32,
+    pub created_at: i64,
+}
+
+/// A state change as stored in the `state_changes` column. Deletes have no value; set values
+/// are hex encoded.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct StateChangeModel {
+    pub key: String,
+    pub value: Option<String>,
+}
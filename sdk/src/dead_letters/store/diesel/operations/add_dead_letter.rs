@@ -0,0 +1,53 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::DeadLetterStoreOperations;
+use crate::dead_letters::store::diesel::{models::NewDeadLetterModel, schema::dead_letter};
+use crate::dead_letters::store::DeadLetterStoreError;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+pub(in crate::dead_letters::store::diesel) trait AddDeadLetterOperation {
+    fn add_dead_letter(&self, dead_letter: NewDeadLetterModel)
+        -> Result<i64, DeadLetterStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AddDeadLetterOperation for DeadLetterStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_dead_letter(&self, model: NewDeadLetterModel) -> Result<i64, DeadLetterStoreError> {
+        Ok(insert_into(dead_letter::table)
+            .values(&model)
+            .returning(dead_letter::id)
+            .get_result(self.conn)?)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AddDeadLetterOperation
+    for DeadLetterStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_dead_letter(&self, model: NewDeadLetterModel) -> Result<i64, DeadLetterStoreError> {
+        self.conn
+            .immediate_transaction::<_, DeadLetterStoreError, _>(|| {
+                insert_into(dead_letter::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+
+                Ok(dead_letter::table
+                    .select(dead_letter::id)
+                    .order(dead_letter::id.desc())
+                    .first(self.conn)?)
+            })
+    }
+}
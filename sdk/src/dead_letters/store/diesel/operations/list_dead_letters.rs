@@ -0,0 +1,67 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::DeadLetterStoreOperations;
+use crate::dead_letters::store::diesel::{models::DeadLetterModel, schema::dead_letter};
+use crate::dead_letters::store::DeadLetterStoreError;
+
+use diesel::prelude::*;
+
+pub(in crate::dead_letters::store::diesel) trait ListDeadLettersOperation {
+    fn list_dead_letters(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<DeadLetterModel>, i64), DeadLetterStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ListDeadLettersOperation for DeadLetterStoreOperations<'a, diesel::pg::PgConnection> {
+    fn list_dead_letters(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<DeadLetterModel>, i64), DeadLetterStoreError> {
+        let models = dead_letter::table
+            .order(dead_letter::id.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<DeadLetterModel>(self.conn)?;
+
+        let total = dead_letter::table.count().get_result(self.conn)?;
+
+        Ok((models, total))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ListDeadLettersOperation
+    for DeadLetterStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_dead_letters(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<DeadLetterModel>, i64), DeadLetterStoreError> {
+        let models = dead_letter::table
+            .order(dead_letter::id.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<DeadLetterModel>(self.conn)?;
+
+        let total = dead_letter::table.count().get_result(self.conn)?;
+
+        Ok((models, total))
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::DeadLetterStoreOperations;
+use crate::dead_letters::store::diesel::schema::dead_letter;
+use crate::dead_letters::store::DeadLetterStoreError;
+
+use diesel::{dsl::update, prelude::*};
+
+pub(in crate::dead_letters::store::diesel) trait RecordAttemptOperation {
+    fn record_attempt(&self, id: i64, error: &str) -> Result<(), DeadLetterStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RecordAttemptOperation for DeadLetterStoreOperations<'a, diesel::pg::PgConnection> {
+    fn record_attempt(&self, id: i64, error: &str) -> Result<(), DeadLetterStoreError> {
+        let updated = update(dead_letter::table.filter(dead_letter::id.eq(id)))
+            .set((
+                dead_letter::attempts.eq(dead_letter::attempts + 1),
+                dead_letter::error.eq(error),
+            ))
+            .execute(self.conn)?;
+
+        if updated == 0 {
+            return Err(DeadLetterStoreError::NotFoundError(id.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RecordAttemptOperation
+    for DeadLetterStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn record_attempt(&self, id: i64, error: &str) -> Result<(), DeadLetterStoreError> {
+        let updated = update(dead_letter::table.filter(dead_letter::id.eq(id)))
+            .set((
+                dead_letter::attempts.eq(dead_letter::attempts + 1),
+                dead_letter::error.eq(error),
+            ))
+            .execute(self.conn)?;
+
+        if updated == 0 {
+            return Err(DeadLetterStoreError::NotFoundError(id.to_string()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::DeadLetterStoreOperations;
+use crate::dead_letters::store::diesel::schema::dead_letter;
+use crate::dead_letters::store::DeadLetterStoreError;
+
+use diesel::{dsl::delete, prelude::*};
+
+pub(in crate::dead_letters::store::diesel) trait RemoveDeadLetterOperation {
+    fn remove_dead_letter(&self, id: i64) -> Result<(), DeadLetterStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RemoveDeadLetterOperation for DeadLetterStoreOperations<'a, diesel::pg::PgConnection> {
+    fn remove_dead_letter(&self, id: i64) -> Result<(), DeadLetterStoreError> {
+        let deleted =
+            delete(dead_letter::table.filter(dead_letter::id.eq(id))).execute(self.conn)?;
+
+        if deleted == 0 {
+            return Err(DeadLetterStoreError::NotFoundError(id.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RemoveDeadLetterOperation
+    for DeadLetterStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_dead_letter(&self, id: i64) -> Result<(), DeadLetterStoreError> {
+        let deleted =
+            delete(dead_letter::table.filter(dead_letter::id.eq(id))).execute(self.conn)?;
+
+        if deleted == 0 {
+            return Err(DeadLetterStoreError::NotFoundError(id.to_string()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::InternalError;
+use crate::paging::Paging;
+
+use super::{DeadLetter, DeadLetterList, DeadLetterStore, DeadLetterStoreError};
+
+#[derive(Default)]
+struct Inner {
+    next_id: i64,
+    dead_letters: Vec<DeadLetter>,
+}
+
+/// Implementation of `DeadLetterStore` that keeps dead letters in memory. Useful for tests and
+/// when persistence isn't necessary.
+#[derive(Clone, Default)]
+pub struct MemoryDeadLetterStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl MemoryDeadLetterStore {
+    pub fn new() -> Self {
+        MemoryDeadLetterStore::default()
+    }
+
+    fn inner(&self) -> Result<MutexGuard<'_, Inner>, DeadLetterStoreError> {
+        self.inner.lock().map_err(|_| {
+            DeadLetterStoreError::InternalError(InternalError::with_message(
+                "Cannot access dead letters: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+impl DeadLetterStore for MemoryDeadLetterStore {
+    fn add_dead_letter(&self, mut dead_letter: DeadLetter) -> Result<i64, DeadLetterStoreError> {
+        let mut inner = self.inner()?;
+        inner.next_id += 1;
+        let id = inner.next_id;
+        dead_letter.id = Some(id);
+        inner.dead_letters.push(dead_letter);
+        Ok(id)
+    }
+
+    fn fetch_dead_letter(&self, id: i64) -> Result<Option<DeadLetter>, DeadLetterStoreError> {
+        Ok(self
+            .inner()?
+            .dead_letters
+            .iter()
+            .find(|dead_letter| dead_letter.id == Some(id))
+            .cloned())
+    }
+
+    fn list_dead_letters(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<DeadLetterList, DeadLetterStoreError> {
+        let inner = self.inner()?;
+        let data = inner
+            .dead_letters
+            .iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect();
+        let total = inner.dead_letters.len() as i64;
+        Ok(DeadLetterList::new(data, Paging::new(offset, limit, total)))
+    }
+
+    fn record_attempt(&self, id: i64, error: &str) -> Result<(), DeadLetterStoreError> {
+        let mut inner = self.inner()?;
+        let dead_letter = inner
+            .dead_letters
+            .iter_mut()
+            .find(|dead_letter| dead_letter.id == Some(id))
+            .ok_or_else(|| DeadLetterStoreError::NotFoundError(id.to_string()))?;
+        dead_letter.attempts += 1;
+        dead_letter.error = error.to_string();
+        Ok(())
+    }
+
+    fn remove_dead_letter(&self, id: i64) -> Result<(), DeadLetterStoreError> {
+        let mut inner = self.inner()?;
+        let len = inner.dead_letters.len();
+        inner
+            .dead_letters
+            .retain(|dead_letter| dead_letter.id != Some(id));
+        if inner.dead_letters.len() == len {
+            return Err(DeadLetterStoreError::NotFoundError(id.to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::commits::store::CommitEvent;
+use crate::paging::Paging;
+
+pub use error::DeadLetterStoreError;
+
+/// A commit event that an event handler could not process
+#[derive(Clone)]
+pub struct DeadLetter {
+    /// The identifier assigned by the store; `None` until the dead letter has been added
+    pub id: Option<i64>,
+    /// The name of the event handler that failed
+    pub handler: String,
+    /// The event that could not be handled
+    pub event: CommitEvent,
+    /// The most recent error returned by the handler
+    pub error: String,
+    /// The number of times the handler has attempted the event
+    pub attempts: i32,
+    /// Seconds since the Unix epoch at which the event was dead-lettered
+    pub created_at: i64,
+}
+
+impl DeadLetter {
+    pub fn new(handler: &str, event: CommitEvent, error: &str, attempts: i32) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0);
+
+        DeadLetter {
+            id: None,
+            handler: handler.to_string(),
+            event,
+            error: error.to_string(),
+            attempts,
+            created_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DeadLetterList {
+    pub data: Vec<DeadLetter>,
+    pub paging: Paging,
+}
+
+impl DeadLetterList {
+    fn new(data: Vec<DeadLetter>, paging: Paging) -> Self {
+        Self { data, paging }
+    }
+}
+
+pub trait DeadLetterStore: Send + Sync {
+    /// Adds a dead letter to the store, returning the identifier assigned to it
+    ///
+    /// # Arguments
+    ///
+    ///  * `dead_letter` - The dead letter to be added
+    fn add_dead_letter(&self, dead_letter: DeadLetter) -> Result<i64, DeadLetterStoreError>;
+
+    /// Fetches a dead letter from the store
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The identifier of the dead letter
+    fn fetch_dead_letter(&self, id: i64) -> Result<Option<DeadLetter>, DeadLetterStoreError>;
+
+    /// Lists dead letters in the order they were added
+    ///
+    /// # Arguments
+    ///
+    ///  * `offset` - The index of the first dead letter to return
+    ///  * `limit` - The maximum number of dead letters to return
+    fn list_dead_letters(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<DeadLetterList, DeadLetterStoreError>;
+
+    /// Records a further failed attempt at handling a dead letter
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The identifier of the dead letter
+    ///  * `error` - The error returned by the handler
+    fn record_attempt(&self, id: i64, error: &str) -> Result<(), DeadLetterStoreError>;
+
+    /// Removes a dead letter from the store
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The identifier of the dead letter
+    fn remove_dead_letter(&self, id: i64) -> Result<(), DeadLetterStoreError>;
+}
+
+impl<DS> DeadLetterStore for Box<DS>
+where
+    DS: DeadLetterStore + ?Sized,
+{
+    fn add_dead_letter(&self, dead_letter: DeadLetter) -> Result<i64, DeadLetterStoreError> {
+        (**self).add_dead_letter(dead_letter)
+    }
+
+    fn fetch_dead_letter(&self, id: i64) -> Result<Option<DeadLetter>, DeadLetterStoreError> {
+        (**self).fetch_dead_letter(id)
+    }
+
+    fn list_dead_letters(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<DeadLetterList, DeadLetterStoreError> {
+        (**self).list_dead_letters(offset, limit)
+    }
+
+    fn record_attempt(&self, id: i64, error: &str) -> Result<(), DeadLetterStoreError> {
+        (**self).record_attempt(id, error)
+    }
+
+    fn remove_dead_letter(&self, id: i64) -> Result<(), DeadLetterStoreError> {
+        (**self).remove_dead_letter(id)
+    }
+}
+
+#[cfg(all(test, any(feature = "memory", feature = "sqlite")))]
+mod tests {
+    use super::*;
+
+    use crate::commits::store::StateChange;
+
+    #[cfg(feature = "memory")]
+    #[test]
+    // Test that the memory store adds, updates and removes dead letters
+    fn test_memory_dead_letter_store() {
+        check_dead_letter_store(&memory::MemoryDeadLetterStore::new());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    // Test that the SQLite store adds, updates and removes dead letters, keeping their events
+    // intact
+    fn test_sqlite_dead_letter_store() {
+        use ::diesel::r2d2::{ConnectionManager, Pool};
+
+        let connection_manager =
+            ConnectionManager::<::diesel::sqlite::SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+        crate::migrations::run_sqlite_migrations(&*pool.get().expect("Failed to get connection"))
+            .expect("Failed to run migrations");
+
+        check_dead_letter_store(&diesel::DieselDeadLetterStore::new(pool));
+    }
+
+    fn check_dead_letter_store(store: &dyn DeadLetterStore) {
+        let first = store
+            .add_dead_letter(DeadLetter::new("database", event("commit-1"), "failed", 3))
+            .expect("Failed to add dead letter");
+        let second = store
+            .add_dead_letter(DeadLetter::new("database", event("commit-2"), "failed", 3))
+            .expect("Failed to add dead letter");
+        assert_ne!(first, second);
+
+        let dead_letter = store
+            .fetch_dead_letter(first)
+            .expect("Failed to fetch dead letter")
+            .expect("Dead letter not found");
+        assert_eq!(dead_letter.id, Some(first));
+        assert_eq!(dead_letter.handler, "database");
+        assert_eq!(dead_letter.attempts, 3);
+        assert_eq!(dead_letter.event.id, "commit-1");
+        assert_eq!(dead_letter.event.height, Some(7));
+        assert_eq!(dead_letter.event.service_id.as_deref(), Some("service-1"));
+        assert!(dead_letter.event.state_changes == event("commit-1").state_changes);
+
+        store
+            .record_attempt(first, "failed again")
+            .expect("Failed to record attempt");
+        let dead_letter = store
+            .fetch_dead_letter(first)
+            .expect("Failed to fetch dead letter")
+            .expect("Dead letter not found");
+        assert_eq!(dead_letter.attempts, 4);
+        assert_eq!(dead_letter.error, "failed again");
+
+        let list = store
+            .list_dead_letters(0, 1)
+            .expect("Failed to list dead letters");
+        assert_eq!(list.paging.total, 2);
+        assert_eq!(list.data.len(), 1);
+        assert_eq!(list.data[0].id, Some(first));
+
+        store
+            .remove_dead_letter(first)
+            .expect("Failed to remove dead letter");
+        assert!(store
+            .fetch_dead_letter(first)
+            .expect("Failed to fetch dead letter")
+            .is_none());
+        assert!(matches!(
+            store.remove_dead_letter(first),
+            Err(DeadLetterStoreError::NotFoundError(_))
+        ));
+        assert!(matches!(
+            store.record_attempt(first, "failed"),
+            Err(DeadLetterStoreError::NotFoundError(_))
+        ));
+    }
+
+    fn event(id: &str) -> CommitEvent {
+        CommitEvent {
+            service_id: Some("service-1".to_string()),
+            id: id.to_string(),
+            height: Some(7),
+            state_changes: vec![
+                StateChange::Set {
+                    key: "621dee01".to_string(),
+                    value: vec![1, 2, 3],
+                },
+                StateChange::Delete {
+                    key: "621dee02".to_string(),
+                },
+            ],
+        }
+    }
+}
@@ -0,0 +1,335 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Filters and sort orders for listing entities that carry property values, such as products
+//! and locations.
+//!
+//! Filters and sort orders can be parsed from strings, for use in query parameters:
+//!
+//! * `owner:eq:<value>` and `owner:prefix:<value>` match the owner
+//! * `namespace:eq:<value>` and `namespace:prefix:<value>` match the namespace
+//! * `property.<name>:eq:<value>` matches a property with the given value
+//! * `property.<name>:prefix:<value>` matches a string property starting with the value
+//! * `property.<name>:range:<min>..<max>` matches a number property within the inclusive
+//!   range, where either bound may be left out
+//! * `property.<name>:exists` matches entities that have the property
+//!
+//! Sort orders are `id`, `owner` or `namespace`, descending when prefixed with `-`.
+
+use std::str::FromStr;
+
+/// Matches a string field of an entity
+#[derive(Clone, Debug, PartialEq)]
+pub enum StringMatch {
+    Equals(String),
+    Prefix(String),
+}
+
+/// Matches the value of a property of an entity
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyMatch {
+    /// The property has this value. The value is compared against string properties as is,
+    /// and against number, enum and boolean properties if it parses as one.
+    Equals(String),
+    /// The property is a string starting with this prefix
+    Prefix(String),
+    /// The property is a number within this inclusive range
+    Range { min: Option<i64>, max: Option<i64> },
+    /// The entity has the property, whatever its value
+    Exists,
+}
+
+/// A condition an entity must meet to be listed
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    Owner(StringMatch),
+    Namespace(StringMatch),
+    Property { name: String, value: PropertyMatch },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortField {
+    Id,
+    Owner,
+    Namespace,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sort {
+    pub field: SortField,
+    pub order: SortOrder,
+}
+
+impl Default for Sort {
+    fn default() -> Self {
+        Sort {
+            field: SortField::Id,
+            order: SortOrder::Ascending,
+        }
+    }
+}
+
+/// The filters and sort order to list entities with
+///
+/// Entities must meet every filter to be listed. The default sort order is ascending by ID.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ListFilter {
+    pub filters: Vec<Filter>,
+    pub sort: Sort,
+}
+
+impl ListFilter {
+    pub fn new(filters: Vec<Filter>, sort: Sort) -> Self {
+        ListFilter { filters, sort }
+    }
+}
+
+/// Escapes the `LIKE` wildcards in a prefix, using `\` as the escape character
+#[cfg(all(feature = "diesel", any(feature = "product", feature = "location")))]
+pub(crate) fn like_prefix(prefix: &str) -> String {
+    let mut pattern = prefix
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    pattern.push('%');
+    pattern
+}
+
+#[cfg(feature = "memory")]
+impl StringMatch {
+    /// Returns whether the value is matched
+    pub(crate) fn matches(&self, value: &str) -> bool {
+        match self {
+            StringMatch::Equals(expected) => value == expected,
+            StringMatch::Prefix(prefix) => value.starts_with(prefix.as_str()),
+        }
+    }
+}
+
+#[cfg(feature = "memory")]
+impl PropertyMatch {
+    /// Returns whether a property with the given values is matched
+    pub(crate) fn matches(
+        &self,
+        string_value: Option<&str>,
+        number_value: Option<i64>,
+        enum_value: Option<i32>,
+        boolean_value: Option<bool>,
+    ) -> bool {
+        match self {
+            PropertyMatch::Equals(value) => {
+                string_value == Some(value.as_str())
+                    || (number_value.is_some() && value.parse().ok() == number_value)
+                    || (enum_value.is_some() && value.parse().ok() == enum_value)
+                    || (boolean_value.is_some() && value.parse().ok() == boolean_value)
+            }
+            PropertyMatch::Prefix(prefix) => match string_value {
+                Some(value) => value.starts_with(prefix.as_str()),
+                None => false,
+            },
+            PropertyMatch::Range { min, max } => match (number_value, min, max) {
+                (_, None, None) => true,
+                (Some(number), min, max) => {
+                    min.iter().all(|min| number >= *min) && max.iter().all(|max| number <= *max)
+                }
+                (None, _, _) => false,
+            },
+            PropertyMatch::Exists => true,
+        }
+    }
+}
+
+fn parse_string_match(op: &str, value: Option<&str>) -> Result<StringMatch, ParseFilterError> {
+    match (op, value) {
+        ("eq", Some(value)) => Ok(StringMatch::Equals(value.to_string())),
+        ("prefix", Some(value)) => Ok(StringMatch::Prefix(value.to_string())),
+        _ => Err(ParseFilterError(format!(
+            "owner and namespace filters take eq or prefix with a value, not {}",
+            op
+        ))),
+    }
+}
+
+fn parse_bound(bound: &str) -> Result<Option<i64>, ParseFilterError> {
+    if bound.is_empty() {
+        return Ok(None);
+    }
+    bound
+        .parse()
+        .map(Some)
+        .map_err(|_| ParseFilterError(format!("range bound is not a number: {}", bound)))
+}
+
+impl FromStr for Filter {
+    type Err = ParseFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, ':');
+        let field = parts.next().unwrap_or("");
+        let op = parts
+            .next()
+            .ok_or_else(|| ParseFilterError(format!("missing operator: {}", s)))?;
+        let value = parts.next();
+
+        if field == "owner" {
+            return Ok(Filter::Owner(parse_string_match(op, value)?));
+        }
+        if field == "namespace" {
+            return Ok(Filter::Namespace(parse_string_match(op, value)?));
+        }
+
+        let name = match field.strip_prefix("property.") {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => return Err(ParseFilterError(format!("unknown field: {}", field))),
+        };
+
+        let value = match (op, value) {
+            ("eq", Some(value)) => PropertyMatch::Equals(value.to_string()),
+            ("prefix", Some(value)) => PropertyMatch::Prefix(value.to_string()),
+            ("range", Some(range)) => {
+                let mut bounds = range.splitn(2, "..");
+                let min = parse_bound(bounds.next().unwrap_or(""))?;
+                let max = bounds
+                    .next()
+                    .ok_or_else(|| ParseFilterError(format!("range must be min..max: {}", range)))
+                    .and_then(parse_bound)?;
+                PropertyMatch::Range { min, max }
+            }
+            ("exists", None) => PropertyMatch::Exists,
+            _ => return Err(ParseFilterError(format!("invalid operator: {}", s))),
+        };
+
+        Ok(Filter::Property { name, value })
+    }
+}
+
+impl FromStr for Sort {
+    type Err = ParseFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (order, field) = match s.strip_prefix('-') {
+            Some(field) => (SortOrder::Descending, field),
+            None => (SortOrder::Ascending, s),
+        };
+
+        let field = match field {
+            "id" => SortField::Id,
+            "owner" => SortField::Owner,
+            "namespace" => SortField::Namespace,
+            _ => return Err(ParseFilterError(format!("unknown sort field: {}", field))),
+        };
+
+        Ok(Sort { field, order })
+    }
+}
+
+/// Errors raised by trying to parse a `Filter` or `Sort`
+#[derive(Debug)]
+pub struct ParseFilterError(pub String);
+
+impl std::error::Error for ParseFilterError {}
+
+impl std::fmt::Display for ParseFilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Unable to parse filter: {}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test that owner, namespace and property filters are parsed
+    fn test_parse_filter() {
+        assert_eq!(
+            "owner:eq:acme".parse::<Filter>().unwrap(),
+            Filter::Owner(StringMatch::Equals("acme".to_string()))
+        );
+        assert_eq!(
+            "namespace:prefix:GS1".parse::<Filter>().unwrap(),
+            Filter::Namespace(StringMatch::Prefix("GS1".to_string()))
+        );
+        assert_eq!(
+            "property.brand_name:eq:Acme: Deluxe"
+                .parse::<Filter>()
+                .unwrap(),
+            Filter::Property {
+                name: "brand_name".to_string(),
+                value: PropertyMatch::Equals("Acme: Deluxe".to_string()),
+            }
+        );
+        assert_eq!(
+            "property.weight:range:10..".parse::<Filter>().unwrap(),
+            Filter::Property {
+                name: "weight".to_string(),
+                value: PropertyMatch::Range {
+                    min: Some(10),
+                    max: None
+                },
+            }
+        );
+        assert_eq!(
+            "property.weight:exists".parse::<Filter>().unwrap(),
+            Filter::Property {
+                name: "weight".to_string(),
+                value: PropertyMatch::Exists,
+            }
+        );
+    }
+
+    #[test]
+    // Test that malformed filters are rejected
+    fn test_parse_filter_invalid() {
+        for filter in &[
+            "owner",
+            "owner:exists",
+            "color:eq:red",
+            "property.:eq:red",
+            "property.weight:range:10",
+            "property.weight:range:a..b",
+            "property.weight:exists:yes",
+            "property.weight:like:red",
+        ] {
+            assert!(filter.parse::<Filter>().is_err(), "{} was parsed", filter);
+        }
+    }
+
+    #[test]
+    // Test that sort orders are parsed
+    fn test_parse_sort() {
+        assert_eq!(
+            "-owner".parse::<Sort>().unwrap(),
+            Sort {
+                field: SortField::Owner,
+                order: SortOrder::Descending,
+            }
+        );
+        assert_eq!("id".parse::<Sort>().unwrap(), Sort::default());
+        assert!("brand_name".parse::<Sort>().is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "diesel", any(feature = "product", feature = "location")))]
+    // Test that LIKE wildcards in prefixes are escaped
+    fn test_like_prefix() {
+        assert_eq!(like_prefix("50%_off\\"), "50\\%\\_off\\\\%");
+    }
+}
@@ -14,7 +14,6 @@
 
 use std::error::Error;
 use std::fmt;
-#[cfg(feature = "batch-store")]
 use std::fmt::Write;
 
 use serde::de;
@@ -25,7 +24,6 @@ use serde::Deserializer;
 /// # Arguments
 ///
 ///  * `bytes`: the byte array to convert
-#[cfg(feature = "batch-store")]
 pub fn to_hex(bytes: &[u8]) -> String {
     let mut buf = String::new();
     for b in bytes {
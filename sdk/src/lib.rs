@@ -33,8 +33,13 @@ extern crate log;
 
 #[cfg(feature = "batch-store")]
 pub mod batches;
+#[cfg(feature = "change-log")]
+pub mod change_log;
 pub mod commits;
+#[cfg(feature = "dead-letter")]
+pub mod dead_letters;
 pub mod error;
+pub mod filter;
 mod hex;
 #[cfg(feature = "location")]
 pub mod locations;
@@ -48,11 +53,17 @@ pub mod pike;
 pub mod products;
 pub mod protocol;
 pub mod protos;
+#[cfg(feature = "purchase-order")]
+pub mod purchase_order;
 pub mod rest_api;
 #[cfg(feature = "schema")]
 pub mod schemas;
+#[cfg(feature = "search")]
+pub mod search;
 pub mod store;
 #[cfg(feature = "track-and-trace")]
 pub mod track_and_trace;
+#[cfg(feature = "webhooks")]
+pub mod webhooks;
 #[cfg(feature = "workflow")]
 pub mod workflow;
@@ -17,4 +17,6 @@ pub mod store;
 
 #[cfg(feature = "diesel")]
 pub use store::diesel::DieselLocationStore;
+#[cfg(feature = "memory")]
+pub use store::memory::MemoryLocationStore;
 pub use store::LocationStore;
@@ -26,10 +26,13 @@ use super::{
 };
 use crate::commits::MAX_COMMIT_NUM;
 use crate::error::ResourceTemporarilyUnavailableError;
+use crate::filter::ListFilter;
+use crate::paging::PageRequest;
 
 use operations::add_location::LocationStoreAddLocationOperation as _;
 use operations::delete_location::LocationStoreDeleteLocationOperation as _;
 use operations::fetch_location::LocationStoreFetchLocationOperation as _;
+use operations::list_location_history::LocationStoreListLocationHistoryOperation as _;
 use operations::list_locations::LocationStoreListLocationsOperation as _;
 use operations::update_location::LocationStoreUpdateLocationOperation as _;
 use operations::LocationStoreOperations;
@@ -67,6 +70,7 @@ impl LocationStore for DieselLocationStore<diesel::pg::PgConnection> {
     fn fetch_location(
         &self,
         location_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Location>, LocationStoreError> {
         LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -74,21 +78,35 @@ impl LocationStore for DieselLocationStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_location(location_id, service_id)
+        .fetch_location(location_id, commit_height, service_id)
     }
 
     fn list_locations(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<LocationList, LocationStoreError> {
         LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             LocationStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_locations(service_id, offset, limit)
+        .list_locations(commit_height, filter, service_id, page)
+    }
+
+    fn list_location_history(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            LocationStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_location_history(location_id, service_id)
     }
 
     fn update_location(&self, location: Location) -> Result<(), LocationStoreError> {
@@ -132,6 +150,7 @@ impl LocationStore for DieselLocationStore<diesel::sqlite::SqliteConnection> {
     fn fetch_location(
         &self,
         location_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Location>, LocationStoreError> {
         LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -139,21 +158,35 @@ impl LocationStore for DieselLocationStore<diesel::sqlite::SqliteConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_location(location_id, service_id)
+        .fetch_location(location_id, commit_height, service_id)
     }
 
     fn list_locations(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<LocationList, LocationStoreError> {
         LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             LocationStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_locations(service_id, offset, limit)
+        .list_locations(commit_height, filter, service_id, page)
+    }
+
+    fn list_location_history(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            LocationStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_location_history(location_id, service_id)
     }
 
     fn update_location(&self, location: Location) -> Result<(), LocationStoreError> {
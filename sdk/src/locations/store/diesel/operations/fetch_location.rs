@@ -18,7 +18,7 @@ use crate::locations::store::diesel::{
     LocationStoreError,
 };
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::locations::store::diesel::models::{LocationAttributeModel, LocationModel};
 use crate::locations::store::{Location, LocationAttribute};
@@ -28,15 +28,18 @@ pub(in crate::locations::store::diesel) trait LocationStoreFetchLocationOperatio
     fn fetch_location(
         &self,
         location_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Location>, LocationStoreError>;
     fn get_root_attributes(
         conn: &C,
+        height: i64,
         location_id: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Vec<LocationAttributeModel>>;
     fn get_attributes(
         conn: &C,
+        height: i64,
         attributes: Vec<LocationAttributeModel>,
     ) -> Result<Vec<LocationAttribute>, LocationStoreError>;
 }
@@ -48,8 +51,10 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::pg::PgConnection>
     fn fetch_location(
         &self,
         location_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Location>, LocationStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn
             .build_transaction()
             .read_write()
@@ -58,9 +63,11 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::pg::PgConnection>
                     .into_boxed()
                     .select(location::all_columns)
                     .filter(
-                        location::location_id
-                            .eq(&location_id)
-                            .and(location::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        location::location_id.eq(&location_id).and(
+                            location::start_commit_num
+                                .le(height)
+                                .and(location::end_commit_num.gt(height)),
+                        ),
                     );
 
                 if let Some(service_id) = service_id {
@@ -77,9 +84,10 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::pg::PgConnection>
                         LocationStoreError::InternalError(InternalError::from_source(Box::new(err)))
                     })?;
 
-                let roots = Self::get_root_attributes(&*self.conn, &location_id, service_id)?;
+                let roots =
+                    Self::get_root_attributes(&*self.conn, height, &location_id, service_id)?;
 
-                let attrs = Self::get_attributes(&*self.conn, roots)?;
+                let attrs = Self::get_attributes(&*self.conn, height, roots)?;
 
                 Ok(loc.map(|loc| Location::from((loc, attrs))))
             })
@@ -87,6 +95,7 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::pg::PgConnection>
 
     fn get_root_attributes(
         conn: &PgConnection,
+        height: i64,
         location_id: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Vec<LocationAttributeModel>> {
@@ -97,7 +106,11 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::pg::PgConnection>
                 location_attribute::location_id
                     .eq(location_id)
                     .and(location_attribute::parent_property_name.is_null())
-                    .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        location_attribute::start_commit_num
+                            .le(height)
+                            .and(location_attribute::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
@@ -111,6 +124,7 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::pg::PgConnection>
 
     fn get_attributes(
         conn: &PgConnection,
+        height: i64,
         attributes: Vec<LocationAttributeModel>,
     ) -> Result<Vec<LocationAttribute>, LocationStoreError> {
         let mut attrs = Vec::new();
@@ -122,7 +136,11 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::pg::PgConnection>
                 .filter(
                     location_attribute::parent_property_name
                         .eq(&attr.parent_property_name)
-                        .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        .and(
+                            location_attribute::start_commit_num
+                                .le(height)
+                                .and(location_attribute::end_commit_num.gt(height)),
+                        ),
                 );
 
             if let Some(ref service_id) = attr.service_id {
@@ -138,7 +156,7 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::pg::PgConnection>
             } else {
                 attrs.push(LocationAttribute::from((
                     attr,
-                    Self::get_attributes(&conn, children)?,
+                    Self::get_attributes(&conn, height, children)?,
                 )));
             }
         }
@@ -154,17 +172,21 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::sqlite::SqliteConnection>
     fn fetch_location(
         &self,
         location_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Location>, LocationStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn
             .immediate_transaction::<_, LocationStoreError, _>(|| {
                 let mut query = location::table
                     .into_boxed()
                     .select(location::all_columns)
                     .filter(
-                        location::location_id
-                            .eq(&location_id)
-                            .and(location::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        location::location_id.eq(&location_id).and(
+                            location::start_commit_num
+                                .le(height)
+                                .and(location::end_commit_num.gt(height)),
+                        ),
                     );
 
                 if let Some(service_id) = service_id {
@@ -181,9 +203,10 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::sqlite::SqliteConnection>
                         LocationStoreError::InternalError(InternalError::from_source(Box::new(err)))
                     })?;
 
-                let roots = Self::get_root_attributes(&*self.conn, &location_id, service_id)?;
+                let roots =
+                    Self::get_root_attributes(&*self.conn, height, &location_id, service_id)?;
 
-                let attrs = Self::get_attributes(&*self.conn, roots)?;
+                let attrs = Self::get_attributes(&*self.conn, height, roots)?;
 
                 Ok(loc.map(|loc| Location::from((loc, attrs))))
             })
@@ -191,6 +214,7 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::sqlite::SqliteConnection>
 
     fn get_root_attributes(
         conn: &SqliteConnection,
+        height: i64,
         location_id: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Vec<LocationAttributeModel>> {
@@ -201,7 +225,11 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::sqlite::SqliteConnection>
                 location_attribute::location_id
                     .eq(location_id)
                     .and(location_attribute::parent_property_name.is_null())
-                    .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        location_attribute::start_commit_num
+                            .le(height)
+                            .and(location_attribute::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
@@ -215,6 +243,7 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::sqlite::SqliteConnection>
 
     fn get_attributes(
         conn: &SqliteConnection,
+        height: i64,
         attributes: Vec<LocationAttributeModel>,
     ) -> Result<Vec<LocationAttribute>, LocationStoreError> {
         let mut attrs = Vec::new();
@@ -226,7 +255,11 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::sqlite::SqliteConnection>
                 .filter(
                     location_attribute::parent_property_name
                         .eq(&attr.parent_property_name)
-                        .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        .and(
+                            location_attribute::start_commit_num
+                                .le(height)
+                                .and(location_attribute::end_commit_num.gt(height)),
+                        ),
                 );
 
             if let Some(ref service_id) = attr.service_id {
@@ -242,7 +275,7 @@ impl<'a> LocationStoreFetchLocationOperation<diesel::sqlite::SqliteConnection>
             } else {
                 attrs.push(LocationAttribute::from((
                     attr,
-                    Self::get_attributes(&conn, children)?,
+                    Self::get_attributes(&conn, height, children)?,
                 )));
             }
         }
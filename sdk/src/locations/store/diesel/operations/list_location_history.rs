@@ -0,0 +1,107 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::fetch_location::LocationStoreFetchLocationOperation;
+use super::LocationStoreOperations;
+use crate::locations::store::diesel::{schema::location, LocationStoreError};
+
+use crate::error::InternalError;
+use crate::locations::store::Location;
+use diesel::prelude::*;
+
+pub(in crate::locations::store::diesel) trait LocationStoreListLocationHistoryOperation {
+    fn list_location_history(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> LocationStoreListLocationHistoryOperation
+    for LocationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_location_history(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        let mut query = location::table
+            .into_boxed()
+            .select(location::start_commit_num)
+            .filter(location::location_id.eq(location_id))
+            .order(location::start_commit_num.asc());
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location::service_id.eq(service_id));
+        } else {
+            query = query.filter(location::service_id.is_null());
+        }
+
+        let start_commit_nums = query.load::<i64>(self.conn).map_err(|err| {
+            LocationStoreError::InternalError(InternalError::from_source(Box::new(err)))
+        })?;
+
+        let mut versions = Vec::new();
+
+        for start_commit_num in start_commit_nums {
+            if let Some(location) =
+                self.fetch_location(location_id, Some(start_commit_num), service_id)?
+            {
+                versions.push(location);
+            }
+        }
+
+        Ok(versions)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> LocationStoreListLocationHistoryOperation
+    for LocationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_location_history(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        let mut query = location::table
+            .into_boxed()
+            .select(location::start_commit_num)
+            .filter(location::location_id.eq(location_id))
+            .order(location::start_commit_num.asc());
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location::service_id.eq(service_id));
+        } else {
+            query = query.filter(location::service_id.is_null());
+        }
+
+        let start_commit_nums = query.load::<i64>(self.conn).map_err(|err| {
+            LocationStoreError::InternalError(InternalError::from_source(Box::new(err)))
+        })?;
+
+        let mut versions = Vec::new();
+
+        for start_commit_num in start_commit_nums {
+            if let Some(location) =
+                self.fetch_location(location_id, Some(start_commit_num), service_id)?
+            {
+                versions.push(location);
+            }
+        }
+
+        Ok(versions)
+    }
+}
@@ -13,33 +13,37 @@
 // limitations under the License.
 
 use super::LocationStoreOperations;
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::locations::store::diesel::{
     schema::{location, location_attribute},
     LocationStoreError,
 };
 
 use crate::error::InternalError;
+use crate::filter::ListFilter;
 use crate::locations::store::diesel::models::{LocationAttributeModel, LocationModel};
 use crate::locations::store::{Location, LocationAttribute, LocationList};
-use crate::paging::Paging;
+use crate::paging::{paged_query, PageRequest, Paging};
 
-use diesel::prelude::*;
+use diesel::{prelude::*, sql_types::Bool};
 
 pub(in crate::locations::store::diesel) trait LocationStoreListLocationsOperation<C: Connection> {
     fn list_locations(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<LocationList, LocationStoreError>;
     fn get_root_attributes(
         conn: &C,
+        height: i64,
         location_id: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Vec<LocationAttributeModel>>;
     fn get_attributes(
         conn: &C,
+        height: i64,
         attributes: Vec<LocationAttributeModel>,
     ) -> Result<Vec<LocationAttribute>, LocationStoreError>;
 }
@@ -50,62 +54,66 @@ impl<'a> LocationStoreListLocationsOperation<diesel::pg::PgConnection>
 {
     fn list_locations(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<LocationList, LocationStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn
             .build_transaction()
             .read_write()
             .run::<_, LocationStoreError, _>(|| {
-                let mut query = location::table
-                    .into_boxed()
-                    .select(location::all_columns)
-                    .limit(limit)
-                    .offset(offset)
-                    .filter(location::end_commit_num.eq(MAX_COMMIT_NUM));
-
-                if let Some(service_id) = service_id {
-                    query = query.filter(location::service_id.eq(service_id));
-                } else {
-                    query = query.filter(location::service_id.is_null());
-                }
+                let query = paged_query(
+                    pg::filtered_locations(height, filter, service_id),
+                    location::location_id,
+                    page,
+                );
+
+                // Pages read by key are in ID order, so only pages read by offset are sorted
+                let query = match page {
+                    PageRequest::Offset { .. } => pg::sort_locations(query, filter),
+                    PageRequest::Keyset { .. } => query,
+                };
 
                 let locs = query.load::<LocationModel>(self.conn).map_err(|err| {
                     LocationStoreError::InternalError(InternalError::from_source(Box::new(err)))
                 })?;
 
+                let (locs, paging) = Paging::for_page(
+                    locs,
+                    page,
+                    || {
+                        pg::filtered_locations(height, filter, service_id)
+                            .count()
+                            .get_result(self.conn)
+                    },
+                    |loc| loc.location_id.clone(),
+                )?;
+
                 let mut locations = Vec::new();
 
                 for l in locs {
                     let loc: LocationModel = l;
-                    let roots =
-                        Self::get_root_attributes(&*self.conn, &loc.location_id, service_id)?;
+                    let roots = Self::get_root_attributes(
+                        &*self.conn,
+                        height,
+                        &loc.location_id,
+                        service_id,
+                    )?;
 
-                    let attrs = Self::get_attributes(&*self.conn, roots)?;
+                    let attrs = Self::get_attributes(&*self.conn, height, roots)?;
 
                     locations.push(Location::from((loc, attrs)));
                 }
 
-                let mut count_query = location::table.into_boxed().select(location::all_columns);
-
-                if let Some(service_id) = service_id {
-                    count_query = count_query.filter(location::service_id.eq(service_id));
-                } else {
-                    count_query = count_query.filter(location::service_id.is_null());
-                }
-
-                let total = count_query.count().get_result(self.conn)?;
-
-                Ok(LocationList::new(
-                    locations,
-                    Paging::new(offset, limit, total),
-                ))
+                Ok(LocationList::new(locations, paging))
             })
     }
 
     fn get_root_attributes(
         conn: &PgConnection,
+        height: i64,
         location_id: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Vec<LocationAttributeModel>> {
@@ -116,7 +124,11 @@ impl<'a> LocationStoreListLocationsOperation<diesel::pg::PgConnection>
                 location_attribute::location_id
                     .eq(location_id)
                     .and(location_attribute::parent_property_name.is_null())
-                    .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        location_attribute::start_commit_num
+                            .le(height)
+                            .and(location_attribute::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
@@ -130,6 +142,7 @@ impl<'a> LocationStoreListLocationsOperation<diesel::pg::PgConnection>
 
     fn get_attributes(
         conn: &PgConnection,
+        height: i64,
         attributes: Vec<LocationAttributeModel>,
     ) -> Result<Vec<LocationAttribute>, LocationStoreError> {
         let mut attrs = Vec::new();
@@ -141,7 +154,11 @@ impl<'a> LocationStoreListLocationsOperation<diesel::pg::PgConnection>
                 .filter(
                     location_attribute::parent_property_name
                         .eq(&attr.parent_property_name)
-                        .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        .and(
+                            location_attribute::start_commit_num
+                                .le(height)
+                                .and(location_attribute::end_commit_num.gt(height)),
+                        ),
                 );
 
             if let Some(ref service_id) = attr.service_id {
@@ -157,7 +174,7 @@ impl<'a> LocationStoreListLocationsOperation<diesel::pg::PgConnection>
             } else {
                 attrs.push(LocationAttribute::from((
                     attr,
-                    Self::get_attributes(&conn, children)?,
+                    Self::get_attributes(&conn, height, children)?,
                 )));
             }
         }
@@ -172,60 +189,64 @@ impl<'a> LocationStoreListLocationsOperation<diesel::sqlite::SqliteConnection>
 {
     fn list_locations(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<LocationList, LocationStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn
             .immediate_transaction::<_, LocationStoreError, _>(|| {
-                let mut query = location::table
-                    .into_boxed()
-                    .select(location::all_columns)
-                    .limit(limit)
-                    .offset(offset)
-                    .filter(location::end_commit_num.eq(MAX_COMMIT_NUM));
-
-                if let Some(service_id) = service_id {
-                    query = query.filter(location::service_id.eq(service_id));
-                } else {
-                    query = query.filter(location::service_id.is_null());
-                }
+                let query = paged_query(
+                    sqlite::filtered_locations(height, filter, service_id),
+                    location::location_id,
+                    page,
+                );
+
+                // Pages read by key are in ID order, so only pages read by offset are sorted
+                let query = match page {
+                    PageRequest::Offset { .. } => sqlite::sort_locations(query, filter),
+                    PageRequest::Keyset { .. } => query,
+                };
 
                 let locs = query.load::<LocationModel>(self.conn).map_err(|err| {
                     LocationStoreError::InternalError(InternalError::from_source(Box::new(err)))
                 })?;
 
+                let (locs, paging) = Paging::for_page(
+                    locs,
+                    page,
+                    || {
+                        sqlite::filtered_locations(height, filter, service_id)
+                            .count()
+                            .get_result(self.conn)
+                    },
+                    |loc| loc.location_id.clone(),
+                )?;
+
                 let mut locations = Vec::new();
 
                 for l in locs {
                     let loc: LocationModel = l;
-                    let roots =
-                        Self::get_root_attributes(&*self.conn, &loc.location_id, service_id)?;
+                    let roots = Self::get_root_attributes(
+                        &*self.conn,
+                        height,
+                        &loc.location_id,
+                        service_id,
+                    )?;
 
-                    let attrs = Self::get_attributes(&*self.conn, roots)?;
+                    let attrs = Self::get_attributes(&*self.conn, height, roots)?;
 
                     locations.push(Location::from((loc, attrs)));
                 }
 
-                let mut count_query = location::table.into_boxed().select(location::all_columns);
-
-                if let Some(service_id) = service_id {
-                    count_query = count_query.filter(location::service_id.eq(service_id));
-                } else {
-                    count_query = count_query.filter(location::service_id.is_null());
-                }
-
-                let total = count_query.count().get_result(self.conn)?;
-
-                Ok(LocationList::new(
-                    locations,
-                    Paging::new(offset, limit, total),
-                ))
+                Ok(LocationList::new(locations, paging))
             })
     }
 
     fn get_root_attributes(
         conn: &SqliteConnection,
+        height: i64,
         location_id: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Vec<LocationAttributeModel>> {
@@ -236,7 +257,11 @@ impl<'a> LocationStoreListLocationsOperation<diesel::sqlite::SqliteConnection>
                 location_attribute::location_id
                     .eq(location_id)
                     .and(location_attribute::parent_property_name.is_null())
-                    .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        location_attribute::start_commit_num
+                            .le(height)
+                            .and(location_attribute::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
@@ -250,6 +275,7 @@ impl<'a> LocationStoreListLocationsOperation<diesel::sqlite::SqliteConnection>
 
     fn get_attributes(
         conn: &SqliteConnection,
+        height: i64,
         attributes: Vec<LocationAttributeModel>,
     ) -> Result<Vec<LocationAttribute>, LocationStoreError> {
         let mut attrs = Vec::new();
@@ -261,7 +287,11 @@ impl<'a> LocationStoreListLocationsOperation<diesel::sqlite::SqliteConnection>
                 .filter(
                     location_attribute::parent_property_name
                         .eq(&attr.parent_property_name)
-                        .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        .and(
+                            location_attribute::start_commit_num
+                                .le(height)
+                                .and(location_attribute::end_commit_num.gt(height)),
+                        ),
                 );
 
             if let Some(ref service_id) = attr.service_id {
@@ -277,7 +307,7 @@ impl<'a> LocationStoreListLocationsOperation<diesel::sqlite::SqliteConnection>
             } else {
                 attrs.push(LocationAttribute::from((
                     attr,
-                    Self::get_attributes(&conn, children)?,
+                    Self::get_attributes(&conn, height, children)?,
                 )));
             }
         }
@@ -285,3 +315,279 @@ impl<'a> LocationStoreListLocationsOperation<diesel::sqlite::SqliteConnection>
         Ok(attrs)
     }
 }
+
+#[cfg(feature = "postgres")]
+mod pg {
+    use super::*;
+
+    use crate::filter::{like_prefix, Filter, PropertyMatch, SortField, SortOrder, StringMatch};
+
+    /// Selects the locations current at the given height that meet every filter
+    pub fn filtered_locations<'a>(
+        height: i64,
+        filter: &'a ListFilter,
+        service_id: Option<&'a str>,
+    ) -> location::BoxedQuery<'a, diesel::pg::Pg> {
+        let mut query = location::table.into_boxed().filter(
+            location::start_commit_num
+                .le(height)
+                .and(location::end_commit_num.gt(height)),
+        );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location::service_id.eq(service_id));
+        } else {
+            query = query.filter(location::service_id.is_null());
+        }
+
+        for condition in &filter.filters {
+            query = match condition {
+                Filter::Owner(StringMatch::Equals(owner)) => {
+                    query.filter(location::owner.eq(owner))
+                }
+                Filter::Owner(StringMatch::Prefix(prefix)) => {
+                    query.filter(location::owner.like(like_prefix(prefix)).escape('\\'))
+                }
+                Filter::Namespace(StringMatch::Equals(namespace)) => {
+                    query.filter(location::location_namespace.eq(namespace))
+                }
+                Filter::Namespace(StringMatch::Prefix(prefix)) => query.filter(
+                    location::location_namespace
+                        .like(like_prefix(prefix))
+                        .escape('\\'),
+                ),
+                Filter::Property { name, value } => query.filter(
+                    location::location_id
+                        .eq_any(locations_with_property(name, value, height, service_id)),
+                ),
+            };
+        }
+
+        query
+    }
+
+    /// Orders the locations by the sort field, then by ID
+    pub fn sort_locations<'a>(
+        query: location::BoxedQuery<'a, diesel::pg::Pg>,
+        filter: &ListFilter,
+    ) -> location::BoxedQuery<'a, diesel::pg::Pg> {
+        let query = match (filter.sort.field, filter.sort.order) {
+            (SortField::Id, SortOrder::Ascending) => query.order(location::location_id.asc()),
+            (SortField::Id, SortOrder::Descending) => query.order(location::location_id.desc()),
+            (SortField::Owner, SortOrder::Ascending) => query.order(location::owner.asc()),
+            (SortField::Owner, SortOrder::Descending) => query.order(location::owner.desc()),
+            (SortField::Namespace, SortOrder::Ascending) => {
+                query.order(location::location_namespace.asc())
+            }
+            (SortField::Namespace, SortOrder::Descending) => {
+                query.order(location::location_namespace.desc())
+            }
+        };
+
+        query.then_order_by(location::location_id.asc())
+    }
+
+    /// Selects the IDs of the locations with a root attribute matching the value
+    fn locations_with_property<'a>(
+        name: &'a str,
+        value: &'a PropertyMatch,
+        height: i64,
+        service_id: Option<&'a str>,
+    ) -> location_attribute::BoxedQuery<'a, diesel::pg::Pg, diesel::sql_types::Varchar> {
+        let mut query = location_attribute::table
+            .into_boxed()
+            .select(location_attribute::location_id)
+            .filter(
+                location_attribute::property_name
+                    .eq(name)
+                    .and(location_attribute::parent_property_name.is_null())
+                    .and(
+                        location_attribute::start_commit_num
+                            .le(height)
+                            .and(location_attribute::end_commit_num.gt(height)),
+                    ),
+            );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location_attribute::service_id.eq(service_id));
+        } else {
+            query = query.filter(location_attribute::service_id.is_null());
+        }
+
+        match value {
+            PropertyMatch::Equals(value) => {
+                let mut condition: Box<
+                    dyn BoxableExpression<location_attribute::table, diesel::pg::Pg, SqlType = Bool>
+                        + 'a,
+                > = Box::new(location_attribute::string_value.eq(value));
+                if let Ok(number) = value.parse::<i64>() {
+                    condition = Box::new(condition.or(location_attribute::number_value.eq(number)));
+                }
+                if let Ok(enum_value) = value.parse::<i32>() {
+                    condition =
+                        Box::new(condition.or(location_attribute::enum_value.eq(enum_value)));
+                }
+                if let Ok(boolean) = value.parse::<bool>() {
+                    condition =
+                        Box::new(condition.or(location_attribute::boolean_value.eq(boolean)));
+                }
+                query.filter(condition)
+            }
+            PropertyMatch::Prefix(prefix) => query.filter(
+                location_attribute::string_value
+                    .like(like_prefix(prefix))
+                    .escape('\\'),
+            ),
+            PropertyMatch::Range { min, max } => {
+                if let Some(min) = min {
+                    query = query.filter(location_attribute::number_value.ge(*min));
+                }
+                if let Some(max) = max {
+                    query = query.filter(location_attribute::number_value.le(*max));
+                }
+                query
+            }
+            PropertyMatch::Exists => query,
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::*;
+
+    use crate::filter::{like_prefix, Filter, PropertyMatch, SortField, SortOrder, StringMatch};
+
+    /// Selects the locations current at the given height that meet every filter
+    pub fn filtered_locations<'a>(
+        height: i64,
+        filter: &'a ListFilter,
+        service_id: Option<&'a str>,
+    ) -> location::BoxedQuery<'a, diesel::sqlite::Sqlite> {
+        let mut query = location::table.into_boxed().filter(
+            location::start_commit_num
+                .le(height)
+                .and(location::end_commit_num.gt(height)),
+        );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location::service_id.eq(service_id));
+        } else {
+            query = query.filter(location::service_id.is_null());
+        }
+
+        for condition in &filter.filters {
+            query = match condition {
+                Filter::Owner(StringMatch::Equals(owner)) => {
+                    query.filter(location::owner.eq(owner))
+                }
+                Filter::Owner(StringMatch::Prefix(prefix)) => {
+                    query.filter(location::owner.like(like_prefix(prefix)).escape('\\'))
+                }
+                Filter::Namespace(StringMatch::Equals(namespace)) => {
+                    query.filter(location::location_namespace.eq(namespace))
+                }
+                Filter::Namespace(StringMatch::Prefix(prefix)) => query.filter(
+                    location::location_namespace
+                        .like(like_prefix(prefix))
+                        .escape('\\'),
+                ),
+                Filter::Property { name, value } => query.filter(
+                    location::location_id
+                        .eq_any(locations_with_property(name, value, height, service_id)),
+                ),
+            };
+        }
+
+        query
+    }
+
+    /// Orders the locations by the sort field, then by ID
+    pub fn sort_locations<'a>(
+        query: location::BoxedQuery<'a, diesel::sqlite::Sqlite>,
+        filter: &ListFilter,
+    ) -> location::BoxedQuery<'a, diesel::sqlite::Sqlite> {
+        let query = match (filter.sort.field, filter.sort.order) {
+            (SortField::Id, SortOrder::Ascending) => query.order(location::location_id.asc()),
+            (SortField::Id, SortOrder::Descending) => query.order(location::location_id.desc()),
+            (SortField::Owner, SortOrder::Ascending) => query.order(location::owner.asc()),
+            (SortField::Owner, SortOrder::Descending) => query.order(location::owner.desc()),
+            (SortField::Namespace, SortOrder::Ascending) => {
+                query.order(location::location_namespace.asc())
+            }
+            (SortField::Namespace, SortOrder::Descending) => {
+                query.order(location::location_namespace.desc())
+            }
+        };
+
+        query.then_order_by(location::location_id.asc())
+    }
+
+    /// Selects the IDs of the locations with a root attribute matching the value
+    fn locations_with_property<'a>(
+        name: &'a str,
+        value: &'a PropertyMatch,
+        height: i64,
+        service_id: Option<&'a str>,
+    ) -> location_attribute::BoxedQuery<'a, diesel::sqlite::Sqlite, diesel::sql_types::Varchar>
+    {
+        let mut query = location_attribute::table
+            .into_boxed()
+            .select(location_attribute::location_id)
+            .filter(
+                location_attribute::property_name
+                    .eq(name)
+                    .and(location_attribute::parent_property_name.is_null())
+                    .and(
+                        location_attribute::start_commit_num
+                            .le(height)
+                            .and(location_attribute::end_commit_num.gt(height)),
+                    ),
+            );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location_attribute::service_id.eq(service_id));
+        } else {
+            query = query.filter(location_attribute::service_id.is_null());
+        }
+
+        match value {
+            PropertyMatch::Equals(value) => {
+                let mut condition: Box<
+                    dyn BoxableExpression<
+                            location_attribute::table,
+                            diesel::sqlite::Sqlite,
+                            SqlType = Bool,
+                        > + 'a,
+                > = Box::new(location_attribute::string_value.eq(value));
+                if let Ok(number) = value.parse::<i64>() {
+                    condition = Box::new(condition.or(location_attribute::number_value.eq(number)));
+                }
+                if let Ok(enum_value) = value.parse::<i32>() {
+                    condition =
+                        Box::new(condition.or(location_attribute::enum_value.eq(enum_value)));
+                }
+                if let Ok(boolean) = value.parse::<bool>() {
+                    condition =
+                        Box::new(condition.or(location_attribute::boolean_value.eq(boolean)));
+                }
+                query.filter(condition)
+            }
+            PropertyMatch::Prefix(prefix) => query.filter(
+                location_attribute::string_value
+                    .like(like_prefix(prefix))
+                    .escape('\\'),
+            ),
+            PropertyMatch::Range { min, max } => {
+                if let Some(min) = min {
+                    query = query.filter(location_attribute::number_value.ge(*min));
+                }
+                if let Some(max) = max {
+                    query = query.filter(location_attribute::number_value.le(*max));
+                }
+                query
+            }
+            PropertyMatch::Exists => query,
+        }
+    }
+}
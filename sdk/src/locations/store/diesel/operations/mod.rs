@@ -15,6 +15,7 @@
 pub(super) mod add_location;
 pub(super) mod delete_location;
 pub(super) mod fetch_location;
+pub(super) mod list_location_history;
 pub(super) mod list_locations;
 pub(super) mod update_location;
 
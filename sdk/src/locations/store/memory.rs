@@ -0,0 +1,347 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::commits::{
+    commit_height_or_current, end_current_rows, impl_versioned, resolve_fork_rows, Versioned,
+    MAX_COMMIT_NUM,
+};
+use crate::error::InternalError;
+use crate::filter::{Filter, ListFilter, SortField, SortOrder};
+use crate::paging::{page_items, PageRequest};
+
+use super::{Location, LocationAttribute, LocationList, LocationStore, LocationStoreError};
+
+impl_versioned!(Location);
+
+/// A location attribute without its struct values, with the name of the attribute it is a
+/// struct value of, if any
+#[derive(Clone)]
+struct AttributeRow {
+    parent_property_name: Option<String>,
+    attribute: LocationAttribute,
+}
+
+impl Versioned for AttributeRow {
+    fn start_commit_num(&self) -> i64 {
+        self.attribute.start_commit_num
+    }
+
+    fn end_commit_num(&self) -> i64 {
+        self.attribute.end_commit_num
+    }
+
+    fn set_end_commit_num(&mut self, end_commit_num: i64) {
+        self.attribute.end_commit_num = end_commit_num;
+    }
+}
+
+fn attribute_rows(
+    attributes: &[LocationAttribute],
+    parent_property_name: Option<&str>,
+    rows: &mut Vec<AttributeRow>,
+) {
+    for attribute in attributes {
+        rows.push(AttributeRow {
+            parent_property_name: parent_property_name.map(String::from),
+            attribute: LocationAttribute {
+                struct_values: None,
+                end_commit_num: MAX_COMMIT_NUM,
+                ..attribute.clone()
+            },
+        });
+        if let Some(struct_values) = &attribute.struct_values {
+            attribute_rows(struct_values, Some(&attribute.property_name), rows);
+        }
+    }
+}
+
+#[derive(Default)]
+struct LocationState {
+    locations: Vec<Location>,
+    attributes: Vec<AttributeRow>,
+}
+
+impl LocationState {
+    fn attributes_at<'a>(
+        &'a self,
+        location_id: &'a str,
+        height: i64,
+        service_id: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a AttributeRow> + 'a {
+        self.attributes.iter().filter(move |row| {
+            row.attribute.location_id == location_id
+                && row.attribute.service_id.as_deref() == service_id
+                && row.is_current_at(height)
+        })
+    }
+
+    fn struct_values(
+        &self,
+        location_id: &str,
+        parent_property_name: Option<&str>,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> Vec<LocationAttribute> {
+        self.attributes_at(location_id, height, service_id)
+            .filter(|row| row.parent_property_name.as_deref() == parent_property_name)
+            .map(|row| {
+                let children = self.struct_values(
+                    location_id,
+                    Some(&row.attribute.property_name),
+                    height,
+                    service_id,
+                );
+                LocationAttribute {
+                    struct_values: Some(children).filter(|children| !children.is_empty()),
+                    ..row.attribute.clone()
+                }
+            })
+            .collect()
+    }
+
+    fn location_at(&self, location: &Location, height: i64, service_id: Option<&str>) -> Location {
+        Location {
+            attributes: self.struct_values(&location.location_id, None, height, service_id),
+            ..location.clone()
+        }
+    }
+
+    fn fetch_location(
+        &self,
+        location_id: &str,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> Option<Location> {
+        self.locations
+            .iter()
+            .find(|location| {
+                location.location_id == location_id
+                    && location.service_id.as_deref() == service_id
+                    && location.is_current_at(height)
+            })
+            .map(|location| self.location_at(location, height, service_id))
+    }
+
+    fn meets_filter(
+        &self,
+        location: &Location,
+        filter: &Filter,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> bool {
+        match filter {
+            Filter::Owner(owner) => owner.matches(&location.owner),
+            Filter::Namespace(namespace) => namespace.matches(&location.location_namespace),
+            Filter::Property { name, value } => self
+                .attributes_at(&location.location_id, height, service_id)
+                .filter(|row| {
+                    row.parent_property_name.is_none() && &row.attribute.property_name == name
+                })
+                .any(|row| {
+                    value.matches(
+                        row.attribute.string_value.as_deref(),
+                        row.attribute.number_value,
+                        row.attribute.enum_value,
+                        row.attribute.boolean_value,
+                    )
+                }),
+        }
+    }
+
+    fn insert_location(&mut self, location: Location) {
+        attribute_rows(&location.attributes, None, &mut self.attributes);
+        self.locations.push(Location {
+            attributes: Vec::new(),
+            end_commit_num: MAX_COMMIT_NUM,
+            ..location
+        });
+    }
+}
+
+/// Implementation of `LocationStore` that keeps locations in memory, versioned by commit like
+/// the database-backed store. Useful for tests and when persistence isn't necessary.
+#[derive(Clone, Default)]
+pub struct MemoryLocationStore {
+    inner: Arc<Mutex<LocationState>>,
+}
+
+impl MemoryLocationStore {
+    pub fn new() -> Self {
+        MemoryLocationStore::default()
+    }
+
+    /// Rolls the locations back to their state before the given commit
+    ///
+    /// # Arguments
+    ///
+    ///  * `commit_num` - The number of the first commit that is no longer on the chain
+    pub fn resolve_fork(&self, commit_num: i64) -> Result<(), LocationStoreError> {
+        let mut state = self.state()?;
+        resolve_fork_rows(&mut state.locations, commit_num);
+        resolve_fork_rows(&mut state.attributes, commit_num);
+        Ok(())
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, LocationState>, LocationStoreError> {
+        self.inner.lock().map_err(|_| {
+            LocationStoreError::InternalError(InternalError::with_message(
+                "Cannot access locations: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+impl LocationStore for MemoryLocationStore {
+    fn add_location(&self, location: Location) -> Result<(), LocationStoreError> {
+        let mut state = self.state()?;
+
+        let current_commit_num = location.start_commit_num;
+        end_current_rows(&mut state.locations, current_commit_num, |current| {
+            current.location_id == location.location_id && current.service_id == location.service_id
+        });
+
+        let mut rows = Vec::new();
+        attribute_rows(&location.attributes, None, &mut rows);
+        end_current_rows(&mut state.attributes, current_commit_num, |current| {
+            rows.iter().any(|row| {
+                current.attribute.location_id == row.attribute.location_id
+                    && current.attribute.property_name == row.attribute.property_name
+                    && current.attribute.service_id == row.attribute.service_id
+            })
+        });
+
+        state.insert_location(location);
+        Ok(())
+    }
+
+    fn fetch_location(
+        &self,
+        location_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Option<Location>, LocationStoreError> {
+        let height = commit_height_or_current(commit_height);
+        Ok(self
+            .state()?
+            .fetch_location(location_id, height, service_id))
+    }
+
+    fn list_locations(
+        &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<LocationList, LocationStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let mut locations = state
+            .locations
+            .iter()
+            .filter(|location| {
+                location.service_id.as_deref() == service_id
+                    && location.is_current_at(height)
+                    && filter.filters.iter().all(|condition| {
+                        state.meets_filter(location, condition, height, service_id)
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        // Pages read by offset are in the sort order of the filter, while `page_items` reads
+        // pages by key in ID order
+        let sort_key = |location: &Location| match filter.sort.field {
+            SortField::Id => location.location_id.clone(),
+            SortField::Owner => location.owner.clone(),
+            SortField::Namespace => location.location_namespace.clone(),
+        };
+        locations.sort_by(|a, b| {
+            let order = sort_key(a).cmp(&sort_key(b));
+            let order = match filter.sort.order {
+                SortOrder::Ascending => order,
+                SortOrder::Descending => order.reverse(),
+            };
+            order.then_with(|| a.location_id.cmp(&b.location_id))
+        });
+
+        let (locations, paging) =
+            page_items(locations, page, |location| location.location_id.clone());
+
+        Ok(LocationList::new(
+            locations
+                .into_iter()
+                .map(|location| state.location_at(location, height, service_id))
+                .collect(),
+            paging,
+        ))
+    }
+
+    fn list_location_history(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        let state = self.state()?;
+
+        let mut start_commit_nums = state
+            .locations
+            .iter()
+            .filter(|location| {
+                location.location_id == location_id && location.service_id.as_deref() == service_id
+            })
+            .map(|location| location.start_commit_num)
+            .collect::<Vec<_>>();
+        start_commit_nums.sort_unstable();
+
+        Ok(start_commit_nums
+            .into_iter()
+            .filter_map(|start_commit_num| {
+                state.fetch_location(location_id, start_commit_num, service_id)
+            })
+            .collect())
+    }
+
+    fn update_location(&self, location: Location) -> Result<(), LocationStoreError> {
+        let mut state = self.state()?;
+
+        let current_commit_num = location.start_commit_num;
+        end_current_rows(&mut state.locations, current_commit_num, |current| {
+            current.location_id == location.location_id && current.service_id == location.service_id
+        });
+        end_current_rows(&mut state.attributes, current_commit_num, |row| {
+            row.attribute.location_id == location.location_id
+                && row.attribute.service_id == location.service_id
+        });
+
+        state.insert_location(location);
+        Ok(())
+    }
+
+    fn delete_location(
+        &self,
+        address: &str,
+        current_commit_num: i64,
+    ) -> Result<(), LocationStoreError> {
+        let mut state = self.state()?;
+        end_current_rows(&mut state.locations, current_commit_num, |location| {
+            location.location_address == address
+        });
+        end_current_rows(&mut state.attributes, current_commit_num, |row| {
+            row.attribute.location_address == address
+        });
+        Ok(())
+    }
+}
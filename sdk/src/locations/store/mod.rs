@@ -15,8 +15,11 @@
 #[cfg(feature = "diesel")]
 pub mod diesel;
 mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
 
-use crate::paging::Paging;
+use crate::filter::ListFilter;
+use crate::paging::{PageRequest, Paging};
 
 pub use error::LocationStoreError;
 
@@ -85,10 +88,13 @@ pub trait LocationStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `location_id` - The ID of the location to be fetched
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - optional - The service ID to fetch the location from
     fn fetch_location(
         &self,
         location_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Location>, LocationStoreError>;
 
@@ -96,16 +102,32 @@ pub trait LocationStore: Send + Sync {
     ///
     /// # Arguments
     ///
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
+    ///  * `filter` - The filters the locations must meet, and the order to list them in when
+    ///    paging by offset; pages read by key are in location ID order
     ///  * `service_id` - optional - The service ID to get the locations for
-    ///  * `offset` - The index of the first in storage to retrieve
-    ///  * `limit` - The number of items to retrieve from the offset
+    ///  * `page` - The page to retrieve, by offset or by key
     fn list_locations(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<LocationList, LocationStoreError>;
 
+    /// Gets every version of a location from the underlying storage, oldest first
+    ///
+    /// # Arguments
+    ///
+    ///  * `location_id` - The ID of the location to get the history of
+    ///  * `service_id` - optional - The service ID to get the location history for
+    fn list_location_history(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError>;
+
     /// Updates a location in the underlying storage
     ///
     /// # Arguments
@@ -137,18 +159,28 @@ where
     fn fetch_location(
         &self,
         location_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Location>, LocationStoreError> {
-        (**self).fetch_location(location_id, service_id)
+        (**self).fetch_location(location_id, commit_height, service_id)
     }
 
     fn list_locations(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<LocationList, LocationStoreError> {
-        (**self).list_locations(service_id, offset, limit)
+        (**self).list_locations(commit_height, filter, service_id, page)
+    }
+
+    fn list_location_history(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        (**self).list_location_history(location_id, service_id)
     }
 
     fn update_location(&self, location: Location) -> Result<(), LocationStoreError> {
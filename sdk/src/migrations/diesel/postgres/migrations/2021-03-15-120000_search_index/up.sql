@@ -0,0 +1,72 @@
+-- Copyright 2021 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE search_index (
+    id BIGSERIAL PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    address TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    service_id TEXT,
+    document TSVECTOR NOT NULL
+);
+
+CREATE INDEX search_index_document_idx ON search_index USING GIN (document);
+CREATE INDEX search_index_entity_idx ON search_index (entity_type, entity_id);
+CREATE INDEX search_index_address_idx ON search_index (address);
+
+-- Index the current products, locations and organizations. Organization metadata is indexed
+-- as organizations are next updated.
+INSERT INTO search_index (entity_type, entity_id, address, title, content, service_id, document)
+SELECT 'product', product_id, product_address, product_id, content, service_id,
+    setweight(to_tsvector('simple', product_id), 'A')
+    || setweight(to_tsvector('simple', content), 'B')
+FROM (
+    SELECT p.product_id, p.product_address, p.service_id,
+        concat_ws(' ', p.product_namespace, p.owner, (
+            SELECT string_agg(v.string_value, ' ')
+            FROM product_property_value v
+            WHERE v.product_id = p.product_id
+            AND v.service_id IS NOT DISTINCT FROM p.service_id
+            AND v.end_commit_num = 9223372036854775807
+        )) AS content
+    FROM product p
+    WHERE p.end_commit_num = 9223372036854775807
+) AS current_product;
+
+INSERT INTO search_index (entity_type, entity_id, address, title, content, service_id, document)
+SELECT 'location', location_id, location_address, location_id, content, service_id,
+    setweight(to_tsvector('simple', location_id), 'A')
+    || setweight(to_tsvector('simple', content), 'B')
+FROM (
+    SELECT l.location_id, l.location_address, l.service_id,
+        concat_ws(' ', l.location_namespace, l.owner, (
+            SELECT string_agg(a.string_value, ' ')
+            FROM location_attribute a
+            WHERE a.location_id = l.location_id
+            AND a.service_id IS NOT DISTINCT FROM l.service_id
+            AND a.end_commit_num = 9223372036854775807
+        )) AS content
+    FROM location l
+    WHERE l.end_commit_num = 9223372036854775807
+) AS current_location;
+
+INSERT INTO search_index (entity_type, entity_id, address, title, content, service_id, document)
+SELECT 'organization', org_id, address, name, org_id, service_id,
+    setweight(to_tsvector('simple', name), 'A')
+    || setweight(to_tsvector('simple', org_id), 'B')
+FROM pike_organization
+WHERE end_commit_num = 9223372036854775807;
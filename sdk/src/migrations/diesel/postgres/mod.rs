@@ -14,7 +14,11 @@
 
 //! Defines methods and utilities to interact with user tables in the database.
 
+#[cfg(feature = "change-log")]
+use crate::change_log::store::diesel::schema::change_log;
 use crate::commits::store::diesel::schema::{chain_record::dsl::*, commits::dsl::*};
+#[cfg(feature = "dead-letter")]
+use crate::dead_letters::store::diesel::schema::dead_letter;
 #[cfg(feature = "location")]
 use crate::locations::store::diesel::schema::{location::dsl::*, location_attribute::dsl::*};
 #[cfg(all(feature = "pike", feature = "location"))]
@@ -27,6 +31,10 @@ use crate::pike::store::diesel::schema::{
 };
 #[cfg(feature = "product")]
 use crate::products::store::diesel::schema::{product::dsl::*, product_property_value::dsl::*};
+#[cfg(feature = "purchase-order")]
+use crate::purchase_order::store::diesel::schema::{
+    purchase_order::dsl::*, purchase_order_version::dsl::*, purchase_order_version_revision::dsl::*,
+};
 #[cfg(feature = "schema")]
 use crate::schemas::store::diesel::schema::{
     grid_property_definition::dsl::grid_property_definition, grid_schema::dsl::*,
@@ -36,6 +44,8 @@ use crate::track_and_trace::store::diesel::schema::{
     associated_agent::dsl::*, property::dsl::*, proposal::dsl::*, record::dsl::*,
     reported_value::dsl::*, reporter::dsl::*,
 };
+#[cfg(feature = "webhooks")]
+use crate::webhooks::store::diesel::schema::{webhook_delivery, webhook_subscription};
 
 use diesel::RunQueryDsl;
 #[cfg(feature = "postgres")]
@@ -94,6 +104,12 @@ pub fn clear_database(conn: &PgConnection) -> Result<(), MigrationsError> {
             diesel::delete(product).execute(conn)?;
             diesel::delete(product_property_value).execute(conn)?;
         }
+        #[cfg(feature = "purchase-order")]
+        {
+            diesel::delete(purchase_order_version_revision).execute(conn)?;
+            diesel::delete(purchase_order_version).execute(conn)?;
+            diesel::delete(purchase_order).execute(conn)?;
+        }
         #[cfg(feature = "schema")]
         {
             diesel::delete(grid_property_definition).execute(conn)?;
@@ -108,6 +124,23 @@ pub fn clear_database(conn: &PgConnection) -> Result<(), MigrationsError> {
             diesel::delete(reported_value).execute(conn)?;
             diesel::delete(reporter).execute(conn)?;
         }
+        #[cfg(feature = "search")]
+        {
+            diesel::sql_query("DELETE FROM search_index").execute(conn)?;
+        }
+        #[cfg(feature = "change-log")]
+        {
+            diesel::delete(change_log::table).execute(conn)?;
+        }
+        #[cfg(feature = "dead-letter")]
+        {
+            diesel::delete(dead_letter::table).execute(conn)?;
+        }
+        #[cfg(feature = "webhooks")]
+        {
+            diesel::delete(webhook_delivery::table).execute(conn)?;
+            diesel::delete(webhook_subscription::table).execute(conn)?;
+        }
         diesel::delete(chain_record).execute(conn)?;
         diesel::delete(commits).execute(conn)?;
 
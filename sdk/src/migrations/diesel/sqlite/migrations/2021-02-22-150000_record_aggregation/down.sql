@@ -0,0 +1,35 @@
+-- Copyright 2021 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE record_without_parent (
+    id INTEGER PRIMARY KEY,
+    record_id TEXT NOT NULL,
+    schema TEXT NOT NULL,
+    final BOOL NOT NULL,
+    owners TEXT NOT NULL,
+    custodians TEXT NOT NULL,
+    service_id TEXT,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL
+);
+
+INSERT INTO record_without_parent
+    SELECT id, record_id, schema, final, owners, custodians, service_id, start_commit_num,
+           end_commit_num
+    FROM record;
+
+DROP TABLE record;
+
+ALTER TABLE record_without_parent RENAME TO record;
@@ -0,0 +1,56 @@
+-- Copyright 2021 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE VIRTUAL TABLE search_index USING fts5(
+    entity_type UNINDEXED,
+    entity_id UNINDEXED,
+    address UNINDEXED,
+    title,
+    content,
+    service_id UNINDEXED
+);
+
+-- Index the current products, locations and organizations. Organization metadata is indexed
+-- as organizations are next updated.
+INSERT INTO search_index (entity_type, entity_id, address, title, content, service_id)
+SELECT 'product', p.product_id, p.product_address, p.product_id,
+    p.product_namespace || ' ' || p.owner || ' ' || IFNULL((
+        SELECT group_concat(v.string_value, ' ')
+        FROM product_property_value v
+        WHERE v.product_id = p.product_id
+        AND v.service_id IS p.service_id
+        AND v.end_commit_num = 9223372036854775807
+    ), ''),
+    p.service_id
+FROM product p
+WHERE p.end_commit_num = 9223372036854775807;
+
+INSERT INTO search_index (entity_type, entity_id, address, title, content, service_id)
+SELECT 'location', l.location_id, l.location_address, l.location_id,
+    l.location_namespace || ' ' || l.owner || ' ' || IFNULL((
+        SELECT group_concat(a.string_value, ' ')
+        FROM location_attribute a
+        WHERE a.location_id = l.location_id
+        AND a.service_id IS l.service_id
+        AND a.end_commit_num = 9223372036854775807
+    ), ''),
+    l.service_id
+FROM location l
+WHERE l.end_commit_num = 9223372036854775807;
+
+INSERT INTO search_index (entity_type, entity_id, address, title, content, service_id)
+SELECT 'organization', org_id, address, name, org_id, service_id
+FROM pike_organization
+WHERE end_commit_num = 9223372036854775807;
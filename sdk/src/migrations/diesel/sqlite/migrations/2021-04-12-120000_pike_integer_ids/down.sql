@@ -0,0 +1,255 @@
+-- Copyright 2021 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- The views read from pike_agent, so they are recreated along with it
+DROP VIEW reported_value_reporter_to_agent_metadata;
+DROP VIEW reporter_to_agent_metadata;
+
+CREATE TABLE pike_agent_new (
+    id BIGSERIAL PRIMARY KEY,
+    public_key VARCHAR(70) NOT NULL,
+    org_id VARCHAR(256) NOT NULL,
+    active BOOLEAN NOT NULL,
+    metadata BYTEA NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_agent_new (id, public_key, org_id, active, metadata, start_commit_num, end_commit_num, service_id)
+    SELECT id, public_key, org_id, active, metadata, start_commit_num, end_commit_num, service_id FROM pike_agent;
+DROP TABLE pike_agent;
+ALTER TABLE pike_agent_new RENAME TO pike_agent;
+
+CREATE TABLE pike_organization_new (
+    id BIGSERIAL PRIMARY KEY,
+    org_id VARCHAR(256) NOT NULL,
+    name VARCHAR(256) NOT NULL,
+    address VARCHAR(256) NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_organization_new (id, org_id, name, address, start_commit_num, end_commit_num, service_id)
+    SELECT id, org_id, name, address, start_commit_num, end_commit_num, service_id FROM pike_organization;
+DROP TABLE pike_organization;
+ALTER TABLE pike_organization_new RENAME TO pike_organization;
+
+CREATE TABLE pike_agent_role_assoc_new (
+    id BIGSERIAL PRIMARY KEY,
+    agent_public_key VARCHAR(70) NOT NULL,
+    org_id VARCHAR(256) NOT NULL,
+    role_name VARCHAR(256) NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_agent_role_assoc_new (id, agent_public_key, org_id, role_name, start_commit_num, end_commit_num, service_id)
+    SELECT id, agent_public_key, org_id, role_name, start_commit_num, end_commit_num, service_id FROM pike_agent_role_assoc;
+DROP TABLE pike_agent_role_assoc;
+ALTER TABLE pike_agent_role_assoc_new RENAME TO pike_agent_role_assoc;
+
+CREATE TABLE pike_role_new (
+    id BIGSERIAL PRIMARY KEY,
+    public_key VARCHAR(70) NOT NULL,
+    role_name TEXT NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_role_new (id, public_key, role_name, start_commit_num, end_commit_num, service_id)
+    SELECT id, public_key, role_name, start_commit_num, end_commit_num, service_id FROM pike_role;
+DROP TABLE pike_role;
+ALTER TABLE pike_role_new RENAME TO pike_role;
+
+CREATE TABLE pike_organization_metadata_new (
+    id BIGSERIAL PRIMARY KEY,
+    org_id VARCHAR(256) NOT NULL,
+    key VARCHAR NOT NULL,
+    value BYTEA NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_organization_metadata_new (id, org_id, key, value, start_commit_num, end_commit_num, service_id)
+    SELECT id, org_id, key, value, start_commit_num, end_commit_num, service_id FROM pike_organization_metadata;
+DROP TABLE pike_organization_metadata;
+ALTER TABLE pike_organization_metadata_new RENAME TO pike_organization_metadata;
+
+CREATE TABLE pike_organization_alternate_id_new (
+    id BIGSERIAL PRIMARY KEY,
+    org_id VARCHAR(256) NOT NULL,
+    alternate_id_type VARCHAR NOT NULL,
+    alternate_id VARCHAR NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_organization_alternate_id_new (id, org_id, alternate_id_type, alternate_id, start_commit_num, end_commit_num, service_id)
+    SELECT id, org_id, alternate_id_type, alternate_id, start_commit_num, end_commit_num, service_id FROM pike_organization_alternate_id;
+DROP TABLE pike_organization_alternate_id;
+ALTER TABLE pike_organization_alternate_id_new RENAME TO pike_organization_alternate_id;
+
+CREATE TABLE pike_organization_location_assoc_new (
+    id BIGSERIAL PRIMARY KEY,
+    org_id VARCHAR(256) NOT NULL,
+    location_id VARCHAR(256) NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_organization_location_assoc_new (id, org_id, location_id, start_commit_num, end_commit_num, service_id)
+    SELECT id, org_id, location_id, start_commit_num, end_commit_num, service_id FROM pike_organization_location_assoc;
+DROP TABLE pike_organization_location_assoc;
+ALTER TABLE pike_organization_location_assoc_new RENAME TO pike_organization_location_assoc;
+
+CREATE TABLE pike_inherit_from_new (
+    id BIGSERIAL PRIMARY KEY,
+    role_name VARCHAR(256) NOT NULL,
+    org_id VARCHAR(256) NOT NULL,
+    inherit_from_org_id VARCHAR(256) NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_inherit_from_new (id, role_name, org_id, inherit_from_org_id, start_commit_num, end_commit_num, service_id)
+    SELECT id, role_name, org_id, inherit_from_org_id, start_commit_num, end_commit_num, service_id FROM pike_inherit_from;
+DROP TABLE pike_inherit_from;
+ALTER TABLE pike_inherit_from_new RENAME TO pike_inherit_from;
+
+CREATE TABLE pike_permissions_new (
+    id BIGSERIAL PRIMARY KEY,
+    role_name VARCHAR(256) NOT NULL,
+    org_id VARCHAR(256) NOT NULL,
+    name VARCHAR(256) NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_permissions_new (id, role_name, org_id, name, start_commit_num, end_commit_num, service_id)
+    SELECT id, role_name, org_id, name, start_commit_num, end_commit_num, service_id FROM pike_permissions;
+DROP TABLE pike_permissions;
+ALTER TABLE pike_permissions_new RENAME TO pike_permissions;
+
+CREATE TABLE pike_allowed_orgs_new (
+    id BIGSERIAL PRIMARY KEY,
+    role_name VARCHAR(256) NOT NULL,
+    org_id VARCHAR(256) NOT NULL,
+    allowed_org_id VARCHAR(256) NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+INSERT INTO pike_allowed_orgs_new (id, role_name, org_id, allowed_org_id, start_commit_num, end_commit_num, service_id)
+    SELECT id, role_name, org_id, allowed_org_id, start_commit_num, end_commit_num, service_id FROM pike_allowed_orgs;
+DROP TABLE pike_allowed_orgs;
+ALTER TABLE pike_allowed_orgs_new RENAME TO pike_allowed_orgs;
+
+CREATE VIEW reporter_to_agent_metadata
+AS
+  SELECT id,
+         property_name,
+         record_id,
+         public_key,
+         authorized,
+         reporter_index,
+         metadata,
+         service_id,
+         reporter_end_commit_num
+  FROM   (SELECT Row_number()
+                   OVER (
+                     partition BY id
+                     ORDER BY agent_end_commit_num) AS RowNum,
+                 *
+          FROM   (SELECT reporter.id,
+                         reporter.property_name,
+                         reporter.record_id,
+                         reporter.reporter_index,
+                         reporter.authorized,
+                         reporter.public_key,
+                         reporter.end_commit_num AS "reporter_end_commit_num",
+                         pike_agent.end_commit_num    AS "agent_end_commit_num",
+                         pike_agent.metadata,
+                         pike_agent.service_id
+                  FROM   reporter
+                         LEFT JOIN pike_agent
+                                ON reporter.public_key = pike_agent.public_key
+                                   AND reporter.end_commit_num <=
+                                       pike_agent.end_commit_num) AS
+                 join_tables) X
+  WHERE  rownum = 1;
+
+CREATE VIEW reported_value_reporter_to_agent_metadata
+AS
+  SELECT id,
+         property_name,
+         record_id,
+         reporter_index,
+         timestamp,
+         data_type,
+         bytes_value,
+         boolean_value,
+         number_value,
+         string_value,
+         enum_value,
+         parent_name,
+         latitude_value,
+         longitude_value,
+         public_key,
+         authorized,
+         metadata,
+         reported_value_start_commit_num,
+         reported_value_end_commit_num,
+         reporter_end_commit_num,
+         service_id
+  FROM   (SELECT Row_number()
+                   OVER (
+                     partition BY id
+                     ORDER BY reporter_end_commit_num) AS RowNum,
+                 *
+          FROM   (SELECT reported_value.id,
+                         reported_value.property_name,
+                         reported_value.record_id,
+                         reported_value.reporter_index,
+                         reported_value.timestamp,
+                         reported_value.data_type,
+                         reported_value.bytes_value,
+                         reported_value.boolean_value,
+                         reported_value.number_value,
+                         reported_value.string_value,
+                         reported_value.enum_value,
+                         reported_value.parent_name,
+                         reported_value.latitude_value,
+                         reported_value.longitude_value,
+                         reported_value.start_commit_num AS
+                         "reported_value_start_commit_num",
+                         reported_value.end_commit_num AS
+                         "reported_value_end_commit_num",
+                         reporter_to_agent_metadata.reporter_end_commit_num,
+                         reporter_to_agent_metadata.public_key,
+                         reporter_to_agent_metadata.authorized,
+                         reporter_to_agent_metadata.metadata,
+                         reported_value.service_id
+                  FROM   reported_value
+                         LEFT JOIN reporter_to_agent_metadata
+                                ON reported_value.record_id =
+                                   reporter_to_agent_metadata.record_id
+                                   AND reported_value.property_name =
+                                       reporter_to_agent_metadata.property_name
+                                   AND reported_value.reporter_index =
+                                       reporter_to_agent_metadata.reporter_index
+                                   AND reported_value.end_commit_num <=
+  reporter_to_agent_metadata.reporter_end_commit_num) AS
+  join_tables) X
+  WHERE  rownum = 1;
@@ -12,11 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "diesel")]
+use diesel::{
+    dsl::{Asc, Desc, Gt, Lt},
+    expression::AsExpression,
+    query_dsl::methods::{FilterDsl, LimitDsl, OffsetDsl, OrderDsl},
+    ExpressionMethods,
+};
+
+use crate::hex::{parse_hex, to_hex};
+
+/// The paging of a page read from a list
+///
+/// Pages selected by offset carry the total length of the list. Pages selected by key carry
+/// the keys to request the pages before and after them with instead, and a total of zero.
 #[derive(Clone, Debug, Serialize, PartialEq)]
 pub struct Paging {
     pub offset: i64,
     pub limit: i64,
     pub total: i64,
+    pub prev_key: Option<String>,
+    pub next_key: Option<String>,
 }
 
 impl Paging {
@@ -25,6 +41,337 @@ impl Paging {
             offset,
             limit,
             total,
+            prev_key: None,
+            next_key: None,
+        }
+    }
+
+    /// Pages a list read in key order for a keyset page request
+    ///
+    /// The list must be read with the key bound and order of the request, and with one more
+    /// item than the limit, to tell whether there are more items past the page.
+    ///
+    /// # Arguments
+    ///
+    ///  * `items` - The items read for the page
+    ///  * `key` - The key the request continues from, if any
+    ///  * `direction` - The direction the request reads in
+    ///  * `limit` - The number of items in a page
+    ///  * `item_key` - Returns the key of an item
+    pub fn keyset<T, F>(
+        mut items: Vec<T>,
+        key: Option<&str>,
+        direction: PageDirection,
+        limit: i64,
+        item_key: F,
+    ) -> (Vec<T>, Self)
+    where
+        F: Fn(&T) -> String,
+    {
+        let has_more = items.len() as i64 > limit;
+        items.truncate(limit.max(0) as usize);
+        if direction == PageDirection::Previous {
+            items.reverse();
+        }
+
+        let (has_prev, has_next) = match direction {
+            PageDirection::Next => (key.is_some(), has_more),
+            PageDirection::Previous => (has_more, key.is_some()),
+        };
+
+        let paging = Paging {
+            offset: 0,
+            limit,
+            total: 0,
+            prev_key: items.first().filter(|_| has_prev).map(&item_key),
+            next_key: items.last().filter(|_| has_next).map(&item_key),
+        };
+
+        (items, paging)
+    }
+
+    /// Pages the items read for a page request, counting the list for offset pages
+    ///
+    /// # Arguments
+    ///
+    ///  * `items` - The items read for the page, by a query paged with `paged_query`
+    ///  * `page` - The page request the items were read for
+    ///  * `count` - Counts the whole list
+    ///  * `item_key` - Returns the key of an item
+    pub fn for_page<T, E, C, F>(
+        items: Vec<T>,
+        page: &PageRequest,
+        count: C,
+        item_key: F,
+    ) -> Result<(Vec<T>, Self), E>
+    where
+        C: FnOnce() -> Result<i64, E>,
+        F: Fn(&T) -> String,
+    {
+        match page {
+            PageRequest::Offset { offset, limit } => {
+                let total = count()?;
+                Ok((items, Paging::new(*offset, *limit, total)))
+            }
+            PageRequest::Keyset {
+                key,
+                direction,
+                limit,
+            } => Ok(Paging::keyset(
+                items,
+                key.as_deref(),
+                *direction,
+                *limit,
+                item_key,
+            )),
+        }
+    }
+}
+
+/// Pages a query for a page request
+///
+/// Offset pages are read at their offset. Keyset pages are read past their key in the order of
+/// the key column, with one more item than the limit, for `Paging::keyset`.
+///
+/// # Arguments
+///
+///  * `query` - The query to page
+///  * `key_column` - The column of the unique key of the items listed
+///  * `page` - The page to read
+#[cfg(feature = "diesel")]
+pub(crate) fn paged_query<'a, Q, K>(query: Q, key_column: K, page: &'a PageRequest) -> Q
+where
+    K: ExpressionMethods + Copy,
+    &'a String: AsExpression<K::SqlType>,
+    Q: FilterDsl<Gt<K, &'a String>, Output = Q>
+        + FilterDsl<Lt<K, &'a String>, Output = Q>
+        + OrderDsl<Asc<K>, Output = Q>
+        + OrderDsl<Desc<K>, Output = Q>
+        + LimitDsl<Output = Q>
+        + OffsetDsl<Output = Q>,
+{
+    match page {
+        PageRequest::Offset { offset, limit } => {
+            OffsetDsl::offset(LimitDsl::limit(query, *limit), *offset)
+        }
+        PageRequest::Keyset {
+            key,
+            direction: PageDirection::Next,
+            limit,
+        } => {
+            let query = match key {
+                Some(key) => FilterDsl::filter(query, key_column.gt(key)),
+                None => query,
+            };
+            LimitDsl::limit(OrderDsl::order(query, key_column.asc()), limit + 1)
+        }
+        PageRequest::Keyset {
+            key,
+            direction: PageDirection::Previous,
+            limit,
+        } => {
+            let query = match key {
+                Some(key) => FilterDsl::filter(query, key_column.lt(key)),
+                None => query,
+            };
+            LimitDsl::limit(OrderDsl::order(query, key_column.desc()), limit + 1)
+        }
+    }
+}
+
+/// Pages a list of items held in memory for a page request, the way `paged_query` and
+/// `Paging::for_page` page a query
+///
+/// Offset pages are read from the items in the order given. Keyset pages are read past their key
+/// in the order of the key.
+///
+/// # Arguments
+///
+///  * `items` - Every item that may be listed
+///  * `page` - The page to read
+///  * `item_key` - Gets the unique key of an item
+#[cfg(feature = "memory")]
+pub(crate) fn page_items<T, F>(
+    mut items: Vec<T>,
+    page: &PageRequest,
+    item_key: F,
+) -> (Vec<T>, Paging)
+where
+    F: Fn(&T) -> String,
+{
+    match page {
+        PageRequest::Offset { offset, limit } => {
+            let total = items.len() as i64;
+            let items = items
+                .into_iter()
+                .skip((*offset).max(0) as usize)
+                .take((*limit).max(0) as usize)
+                .collect();
+            (items, Paging::new(*offset, *limit, total))
+        }
+        PageRequest::Keyset {
+            key,
+            direction,
+            limit,
+        } => {
+            items.sort_by_key(|item| item_key(item));
+            if let Some(key) = key {
+                items.retain(|item| match direction {
+                    PageDirection::Next => item_key(item).as_str() > key.as_str(),
+                    PageDirection::Previous => item_key(item).as_str() < key.as_str(),
+                });
+            }
+            if *direction == PageDirection::Previous {
+                items.reverse();
+            }
+            items.truncate(((*limit).max(0) + 1) as usize);
+            Paging::keyset(items, key.as_deref(), *direction, *limit, item_key)
+        }
+    }
+}
+
+/// The direction a keyset page is read in from its key
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PageDirection {
+    /// The items after the key, in ascending key order
+    Next,
+    /// The items before the key, returned in ascending key order
+    Previous,
+}
+
+/// Selects a page of a list
+#[derive(Clone, Debug, PartialEq)]
+pub enum PageRequest {
+    /// The page at an offset from the start of the list, counting the whole list
+    Offset { offset: i64, limit: i64 },
+    /// The page next to a key in key order, or the first page if there is no key, without
+    /// counting the list
+    Keyset {
+        key: Option<String>,
+        direction: PageDirection,
+        limit: i64,
+    },
+}
+
+impl PageRequest {
+    pub fn limit(&self) -> i64 {
+        match self {
+            PageRequest::Offset { limit, .. } | PageRequest::Keyset { limit, .. } => *limit,
+        }
+    }
+}
+
+/// An opaque position in a list, pinned to the commit height the list is read at
+///
+/// A cursor selects the page next to the key of an item, as the list was at the commit height,
+/// so that paging through the list is not disturbed by later commits.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cursor {
+    pub commit_height: i64,
+    pub key: String,
+    pub direction: PageDirection,
+}
+
+impl Cursor {
+    pub fn new(commit_height: i64, key: String, direction: PageDirection) -> Self {
+        Cursor {
+            commit_height,
+            key,
+            direction,
         }
     }
+
+    /// Encodes the cursor as an opaque string
+    pub fn encode(&self) -> String {
+        let direction = match self.direction {
+            PageDirection::Next => 'n',
+            PageDirection::Previous => 'p',
+        };
+        to_hex(format!("{}:{}:{}", direction, self.commit_height, self.key).as_bytes())
+    }
+
+    /// Decodes a cursor encoded by `encode`
+    pub fn decode(cursor: &str) -> Result<Self, ParseCursorError> {
+        let invalid = || ParseCursorError(cursor.to_string());
+
+        let bytes = parse_hex(cursor).map_err(|_| invalid())?;
+        let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let mut parts = decoded.splitn(3, ':');
+
+        let direction = match parts.next() {
+            Some("n") => PageDirection::Next,
+            Some("p") => PageDirection::Previous,
+            _ => return Err(invalid()),
+        };
+        let commit_height = parts
+            .next()
+            .and_then(|height| height.parse::<i64>().ok())
+            .filter(|height| *height >= 0)
+            .ok_or_else(invalid)?;
+        let key = parts.next().ok_or_else(invalid)?.to_string();
+
+        Ok(Cursor {
+            commit_height,
+            key,
+            direction,
+        })
+    }
+}
+
+/// Errors raised by trying to decode an invalid `Cursor`
+#[derive(Debug)]
+pub struct ParseCursorError(pub String);
+
+impl std::error::Error for ParseCursorError {}
+
+impl std::fmt::Display for ParseCursorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Invalid cursor: {}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test that cursors survive encoding, including keys with separators in them
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::new(42, "org:01".to_string(), PageDirection::Previous);
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+
+        assert!(Cursor::decode("not a cursor").is_err());
+        assert!(Cursor::decode(&to_hex(b"x:1:key")).is_err());
+    }
+
+    #[test]
+    // Test that keyset pages find their neighboring pages from the extra item read
+    fn test_keyset_paging() {
+        let key = |item: &&str| item.to_string();
+
+        // The first page, with more items after it
+        let (items, paging) =
+            Paging::keyset(vec!["a", "b", "c"], None, PageDirection::Next, 2, key);
+        assert_eq!(items, vec!["a", "b"]);
+        assert_eq!(paging.prev_key, None);
+        assert_eq!(paging.next_key, Some("b".to_string()));
+
+        // The last page, after "b"
+        let (items, paging) = Paging::keyset(vec!["c"], Some("b"), PageDirection::Next, 2, key);
+        assert_eq!(items, vec!["c"]);
+        assert_eq!(paging.prev_key, Some("c".to_string()));
+        assert_eq!(paging.next_key, None);
+
+        // The page before "d", read in descending order
+        let (items, paging) = Paging::keyset(
+            vec!["c", "b", "a"],
+            Some("d"),
+            PageDirection::Previous,
+            2,
+            key,
+        );
+        assert_eq!(items, vec!["b", "c"]);
+        assert_eq!(paging.prev_key, Some("b".to_string()));
+        assert_eq!(paging.next_key, Some("c".to_string()));
+    }
 }
@@ -17,4 +17,6 @@ pub mod store;
 
 #[cfg(feature = "diesel")]
 pub use store::diesel::DieselPikeStore;
+#[cfg(feature = "memory")]
+pub use store::memory::MemoryPikeStore;
 pub use store::PikeStore;
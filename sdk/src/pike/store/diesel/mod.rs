@@ -23,12 +23,14 @@ use super::{
     PikeStoreError, Role,
 };
 use crate::error::ResourceTemporarilyUnavailableError;
+use crate::paging::PageRequest;
 use models::{make_org_metadata_models, make_role_models};
 use operations::add_agent::PikeStoreAddAgentOperation as _;
 use operations::add_organization::PikeStoreAddOrganizationOperation as _;
 use operations::fetch_agent::PikeStoreFetchAgentOperation as _;
 use operations::fetch_organization::PikeStoreFetchOrganizationOperation as _;
 use operations::list_agents::PikeStoreListAgentsOperation as _;
+use operations::list_organization_history::PikeStoreListOrganizationHistoryOperation as _;
 use operations::list_organizations::PikeStoreListOrganizationsOperation as _;
 use operations::update_agent::PikeStoreUpdateAgentOperation as _;
 use operations::PikeStoreOperations;
@@ -63,21 +65,23 @@ impl PikeStore for DieselPikeStore<diesel::pg::PgConnection> {
 
     fn list_agents(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<AgentList, PikeStoreError> {
         PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             PikeStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_agents(service_id, offset, limit)
+        .list_agents(commit_height, org_id, service_id, page)
     }
 
     fn fetch_agent(
         &self,
         pub_key: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Agent>, PikeStoreError> {
         PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -85,7 +89,7 @@ impl PikeStore for DieselPikeStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_agent(pub_key, service_id)
+        .fetch_agent(pub_key, commit_height, service_id)
     }
 
     fn update_agent(&self, agent: Agent) -> Result<(), PikeStoreError> {
@@ -108,21 +112,23 @@ impl PikeStore for DieselPikeStore<diesel::pg::PgConnection> {
 
     fn list_organizations(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<OrganizationList, PikeStoreError> {
         PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             PikeStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_organizations(service_id, offset, limit)
+        .list_organizations(commit_height, org_id, service_id, page)
     }
 
     fn fetch_organization(
         &self,
         org_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Organization>, PikeStoreError> {
         PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -130,7 +136,20 @@ impl PikeStore for DieselPikeStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_organization(org_id, service_id)
+        .fetch_organization(org_id, commit_height, service_id)
+    }
+
+    fn list_organization_history(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Organization>, PikeStoreError> {
+        PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            PikeStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_organization_history(org_id, service_id)
     }
 }
 
@@ -147,21 +166,23 @@ impl PikeStore for DieselPikeStore<diesel::sqlite::SqliteConnection> {
 
     fn list_agents(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<AgentList, PikeStoreError> {
         PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             PikeStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_agents(service_id, offset, limit)
+        .list_agents(commit_height, org_id, service_id, page)
     }
 
     fn fetch_agent(
         &self,
         pub_key: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Agent>, PikeStoreError> {
         PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -169,7 +190,7 @@ impl PikeStore for DieselPikeStore<diesel::sqlite::SqliteConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_agent(pub_key, service_id)
+        .fetch_agent(pub_key, commit_height, service_id)
     }
 
     fn update_agent(&self, agent: Agent) -> Result<(), PikeStoreError> {
@@ -192,21 +213,23 @@ impl PikeStore for DieselPikeStore<diesel::sqlite::SqliteConnection> {
 
     fn list_organizations(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<OrganizationList, PikeStoreError> {
         PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             PikeStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_organizations(service_id, offset, limit)
+        .list_organizations(commit_height, org_id, service_id, page)
     }
 
     fn fetch_organization(
         &self,
         org_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Organization>, PikeStoreError> {
         PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -214,6 +237,19 @@ impl PikeStore for DieselPikeStore<diesel::sqlite::SqliteConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_organization(org_id, service_id)
+        .fetch_organization(org_id, commit_height, service_id)
+    }
+
+    fn list_organization_history(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Organization>, PikeStoreError> {
+        PikeStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            PikeStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_organization_history(org_id, service_id)
     }
 }
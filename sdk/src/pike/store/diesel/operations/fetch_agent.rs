@@ -18,7 +18,7 @@ use crate::pike::store::diesel::{
     Agent, PikeStoreError,
 };
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::pike::store::diesel::models::{AgentModel, RoleModel};
 use diesel::{prelude::*, result::Error::NotFound};
@@ -27,6 +27,7 @@ pub(in crate::pike::store::diesel) trait PikeStoreFetchAgentOperation {
     fn fetch_agent(
         &self,
         pub_key: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Agent>, PikeStoreError>;
 }
@@ -36,16 +37,20 @@ impl<'a> PikeStoreFetchAgentOperation for PikeStoreOperations<'a, diesel::pg::Pg
     fn fetch_agent(
         &self,
         pub_key: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Agent>, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn.transaction::<_, PikeStoreError, _>(|| {
             let mut query = pike_agent::table
                 .into_boxed()
                 .select(pike_agent::all_columns)
                 .filter(
-                    pike_agent::public_key
-                        .eq(&pub_key)
-                        .and(pike_agent::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    pike_agent::public_key.eq(&pub_key).and(
+                        pike_agent::start_commit_num
+                            .le(height)
+                            .and(pike_agent::end_commit_num.gt(height)),
+                    ),
                 );
 
             if let Some(service_id) = service_id {
@@ -66,9 +71,11 @@ impl<'a> PikeStoreFetchAgentOperation for PikeStoreOperations<'a, diesel::pg::Pg
                 .into_boxed()
                 .select(pike_role::all_columns)
                 .filter(
-                    pike_role::public_key
-                        .eq(&pub_key)
-                        .and(pike_role::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    pike_role::public_key.eq(&pub_key).and(
+                        pike_role::start_commit_num
+                            .le(height)
+                            .and(pike_role::end_commit_num.gt(height)),
+                    ),
                 );
 
             if let Some(service_id) = service_id {
@@ -93,16 +100,20 @@ impl<'a> PikeStoreFetchAgentOperation
     fn fetch_agent(
         &self,
         pub_key: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Agent>, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn.transaction::<_, PikeStoreError, _>(|| {
             let mut query = pike_agent::table
                 .into_boxed()
                 .select(pike_agent::all_columns)
                 .filter(
-                    pike_agent::public_key
-                        .eq(&pub_key)
-                        .and(pike_agent::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    pike_agent::public_key.eq(&pub_key).and(
+                        pike_agent::start_commit_num
+                            .le(height)
+                            .and(pike_agent::end_commit_num.gt(height)),
+                    ),
                 );
 
             if let Some(service_id) = service_id {
@@ -123,9 +134,11 @@ impl<'a> PikeStoreFetchAgentOperation
                 .into_boxed()
                 .select(pike_role::all_columns)
                 .filter(
-                    pike_role::public_key
-                        .eq(&pub_key)
-                        .and(pike_role::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    pike_role::public_key.eq(&pub_key).and(
+                        pike_role::start_commit_num
+                            .le(height)
+                            .and(pike_role::end_commit_num.gt(height)),
+                    ),
                 );
 
             if let Some(service_id) = service_id {
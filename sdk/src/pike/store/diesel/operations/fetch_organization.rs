@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::PikeStoreOperations;
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::pike::store::diesel::models::{OrganizationMetadataModel, OrganizationModel};
 use crate::pike::store::diesel::{
@@ -27,6 +27,7 @@ pub(in crate::pike::store::diesel) trait PikeStoreFetchOrganizationOperation {
     fn fetch_organization(
         &self,
         org_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Organization>, PikeStoreError>;
 }
@@ -36,16 +37,20 @@ impl<'a> PikeStoreFetchOrganizationOperation for PikeStoreOperations<'a, diesel:
     fn fetch_organization(
         &self,
         org_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Organization>, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn.transaction::<_, PikeStoreError, _>(|| {
             let mut query = pike_organization::table
                 .into_boxed()
                 .select(pike_organization::all_columns)
                 .filter(
-                    pike_organization::org_id
-                        .eq(&org_id)
-                        .and(pike_organization::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    pike_organization::org_id.eq(&org_id).and(
+                        pike_organization::start_commit_num
+                            .le(height)
+                            .and(pike_organization::end_commit_num.gt(height)),
+                    ),
                 );
 
             if let Some(service_id) = service_id {
@@ -66,9 +71,11 @@ impl<'a> PikeStoreFetchOrganizationOperation for PikeStoreOperations<'a, diesel:
                 .into_boxed()
                 .select(pike_organization_metadata::all_columns)
                 .filter(
-                    pike_organization_metadata::org_id
-                        .eq(&org_id)
-                        .and(pike_organization_metadata::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    pike_organization_metadata::org_id.eq(&org_id).and(
+                        pike_organization_metadata::start_commit_num
+                            .le(height)
+                            .and(pike_organization_metadata::end_commit_num.gt(height)),
+                    ),
                 );
 
             if let Some(service_id) = service_id {
@@ -102,16 +109,20 @@ impl<'a> PikeStoreFetchOrganizationOperation
     fn fetch_organization(
         &self,
         org_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Organization>, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn.transaction::<_, PikeStoreError, _>(|| {
             let mut query = pike_organization::table
                 .into_boxed()
                 .select(pike_organization::all_columns)
                 .filter(
-                    pike_organization::org_id
-                        .eq(&org_id)
-                        .and(pike_organization::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    pike_organization::org_id.eq(&org_id).and(
+                        pike_organization::start_commit_num
+                            .le(height)
+                            .and(pike_organization::end_commit_num.gt(height)),
+                    ),
                 );
 
             if let Some(service_id) = service_id {
@@ -132,9 +143,11 @@ impl<'a> PikeStoreFetchOrganizationOperation
                 .into_boxed()
                 .select(pike_organization_metadata::all_columns)
                 .filter(
-                    pike_organization_metadata::org_id
-                        .eq(&org_id)
-                        .and(pike_organization_metadata::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    pike_organization_metadata::org_id.eq(&org_id).and(
+                        pike_organization_metadata::start_commit_num
+                            .le(height)
+                            .and(pike_organization_metadata::end_commit_num.gt(height)),
+                    ),
                 );
 
             if let Some(service_id) = service_id {
@@ -13,13 +13,13 @@
 // limitations under the License.
 
 use super::PikeStoreOperations;
-use crate::paging::Paging;
+use crate::paging::{paged_query, PageRequest, Paging};
 use crate::pike::store::diesel::{
     schema::{pike_agent, pike_role},
     Agent, AgentList, PikeStoreError,
 };
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::pike::store::diesel::models::{AgentModel, RoleModel};
 use diesel::prelude::*;
@@ -27,9 +27,10 @@ use diesel::prelude::*;
 pub(in crate::pike::store::diesel) trait PikeStoreListAgentsOperation {
     fn list_agents(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<AgentList, PikeStoreError>;
 }
 
@@ -37,17 +38,25 @@ pub(in crate::pike::store::diesel) trait PikeStoreListAgentsOperation {
 impl<'a> PikeStoreListAgentsOperation for PikeStoreOperations<'a, diesel::pg::PgConnection> {
     fn list_agents(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<AgentList, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn.transaction::<_, PikeStoreError, _>(|| {
             let mut query = pike_agent::table
                 .into_boxed()
                 .select(pike_agent::all_columns)
-                .offset(offset)
-                .limit(limit)
-                .filter(pike_agent::end_commit_num.eq(MAX_COMMIT_NUM));
+                .filter(
+                    pike_agent::start_commit_num
+                        .le(height)
+                        .and(pike_agent::end_commit_num.gt(height)),
+                );
+
+            if let Some(org_id) = org_id {
+                query = query.filter(pike_agent::org_id.eq(org_id));
+            }
 
             if let Some(service_id) = service_id {
                 query = query.filter(pike_agent::service_id.eq(service_id));
@@ -55,6 +64,8 @@ impl<'a> PikeStoreListAgentsOperation for PikeStoreOperations<'a, diesel::pg::Pg
                 query = query.filter(pike_agent::service_id.is_null());
             }
 
+            let query = paged_query(query, pike_agent::public_key, page);
+
             let agent_models = query.load::<AgentModel>(self.conn).map_err(|err| {
                 PikeStoreError::InternalError(InternalError::from_source(Box::new(err)))
             })?;
@@ -63,13 +74,22 @@ impl<'a> PikeStoreListAgentsOperation for PikeStoreOperations<'a, diesel::pg::Pg
                 .into_boxed()
                 .select(pike_agent::all_columns);
 
+            if let Some(org_id) = org_id {
+                count_query = count_query.filter(pike_agent::org_id.eq(org_id));
+            }
+
             if let Some(service_id) = service_id {
                 count_query = count_query.filter(pike_agent::service_id.eq(service_id));
             } else {
                 count_query = count_query.filter(pike_agent::service_id.is_null());
             }
 
-            let total = count_query.count().get_result(self.conn)?;
+            let (agent_models, paging) = Paging::for_page(
+                agent_models,
+                page,
+                || count_query.count().get_result(self.conn),
+                |model| model.public_key.clone(),
+            )?;
 
             let mut agents = Vec::new();
 
@@ -78,9 +98,11 @@ impl<'a> PikeStoreListAgentsOperation for PikeStoreOperations<'a, diesel::pg::Pg
                     .into_boxed()
                     .select(pike_role::all_columns)
                     .filter(
-                        pike_role::public_key
-                            .eq(&a.public_key)
-                            .and(pike_role::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        pike_role::public_key.eq(&a.public_key).and(
+                            pike_role::start_commit_num
+                                .le(height)
+                                .and(pike_role::end_commit_num.gt(height)),
+                        ),
                     );
 
                 if let Some(service_id) = service_id {
@@ -96,7 +118,7 @@ impl<'a> PikeStoreListAgentsOperation for PikeStoreOperations<'a, diesel::pg::Pg
                 agents.push(Agent::from((a, roles)));
             }
 
-            Ok(AgentList::new(agents, Paging::new(offset, limit, total)))
+            Ok(AgentList::new(agents, paging))
         })
     }
 }
@@ -107,17 +129,25 @@ impl<'a> PikeStoreListAgentsOperation
 {
     fn list_agents(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<AgentList, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn.transaction::<_, PikeStoreError, _>(|| {
             let mut query = pike_agent::table
                 .into_boxed()
                 .select(pike_agent::all_columns)
-                .offset(offset)
-                .limit(limit)
-                .filter(pike_agent::end_commit_num.eq(MAX_COMMIT_NUM));
+                .filter(
+                    pike_agent::start_commit_num
+                        .le(height)
+                        .and(pike_agent::end_commit_num.gt(height)),
+                );
+
+            if let Some(org_id) = org_id {
+                query = query.filter(pike_agent::org_id.eq(org_id));
+            }
 
             if let Some(service_id) = service_id {
                 query = query.filter(pike_agent::service_id.eq(service_id));
@@ -125,6 +155,8 @@ impl<'a> PikeStoreListAgentsOperation
                 query = query.filter(pike_agent::service_id.is_null());
             }
 
+            let query = paged_query(query, pike_agent::public_key, page);
+
             let agent_models = query.load::<AgentModel>(self.conn).map_err(|err| {
                 PikeStoreError::InternalError(InternalError::from_source(Box::new(err)))
             })?;
@@ -133,13 +165,22 @@ impl<'a> PikeStoreListAgentsOperation
                 .into_boxed()
                 .select(pike_agent::all_columns);
 
+            if let Some(org_id) = org_id {
+                count_query = count_query.filter(pike_agent::org_id.eq(org_id));
+            }
+
             if let Some(service_id) = service_id {
                 count_query = count_query.filter(pike_agent::service_id.eq(service_id));
             } else {
                 count_query = count_query.filter(pike_agent::service_id.is_null());
             }
 
-            let total = count_query.count().get_result(self.conn)?;
+            let (agent_models, paging) = Paging::for_page(
+                agent_models,
+                page,
+                || count_query.count().get_result(self.conn),
+                |model| model.public_key.clone(),
+            )?;
 
             let mut agents = Vec::new();
 
@@ -148,9 +189,11 @@ impl<'a> PikeStoreListAgentsOperation
                     .into_boxed()
                     .select(pike_role::all_columns)
                     .filter(
-                        pike_role::public_key
-                            .eq(&a.public_key)
-                            .and(pike_role::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        pike_role::public_key.eq(&a.public_key).and(
+                            pike_role::start_commit_num
+                                .le(height)
+                                .and(pike_role::end_commit_num.gt(height)),
+                        ),
                     );
 
                 if let Some(service_id) = service_id {
@@ -166,7 +209,7 @@ impl<'a> PikeStoreListAgentsOperation
                 agents.push(Agent::from((a, roles)));
             }
 
-            Ok(AgentList::new(agents, Paging::new(offset, limit, total)))
+            Ok(AgentList::new(agents, paging))
         })
     }
 }
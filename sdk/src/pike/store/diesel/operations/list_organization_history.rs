@@ -0,0 +1,107 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::fetch_organization::PikeStoreFetchOrganizationOperation;
+use super::PikeStoreOperations;
+use crate::pike::store::diesel::{schema::pike_organization, PikeStoreError};
+
+use crate::error::InternalError;
+use crate::pike::store::Organization;
+use diesel::prelude::*;
+
+pub(in crate::pike::store::diesel) trait PikeStoreListOrganizationHistoryOperation {
+    fn list_organization_history(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Organization>, PikeStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> PikeStoreListOrganizationHistoryOperation
+    for PikeStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_organization_history(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Organization>, PikeStoreError> {
+        let mut query = pike_organization::table
+            .into_boxed()
+            .select(pike_organization::start_commit_num)
+            .filter(pike_organization::org_id.eq(org_id))
+            .order(pike_organization::start_commit_num.asc());
+
+        if let Some(service_id) = service_id {
+            query = query.filter(pike_organization::service_id.eq(service_id));
+        } else {
+            query = query.filter(pike_organization::service_id.is_null());
+        }
+
+        let start_commit_nums = query.load::<i64>(self.conn).map_err(|err| {
+            PikeStoreError::InternalError(InternalError::from_source(Box::new(err)))
+        })?;
+
+        let mut versions = Vec::new();
+
+        for start_commit_num in start_commit_nums {
+            if let Some(org) =
+                self.fetch_organization(org_id, Some(start_commit_num), service_id)?
+            {
+                versions.push(org);
+            }
+        }
+
+        Ok(versions)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> PikeStoreListOrganizationHistoryOperation
+    for PikeStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_organization_history(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Organization>, PikeStoreError> {
+        let mut query = pike_organization::table
+            .into_boxed()
+            .select(pike_organization::start_commit_num)
+            .filter(pike_organization::org_id.eq(org_id))
+            .order(pike_organization::start_commit_num.asc());
+
+        if let Some(service_id) = service_id {
+            query = query.filter(pike_organization::service_id.eq(service_id));
+        } else {
+            query = query.filter(pike_organization::service_id.is_null());
+        }
+
+        let start_commit_nums = query.load::<i64>(self.conn).map_err(|err| {
+            PikeStoreError::InternalError(InternalError::from_source(Box::new(err)))
+        })?;
+
+        let mut versions = Vec::new();
+
+        for start_commit_num in start_commit_nums {
+            if let Some(org) =
+                self.fetch_organization(org_id, Some(start_commit_num), service_id)?
+            {
+                versions.push(org);
+            }
+        }
+
+        Ok(versions)
+    }
+}
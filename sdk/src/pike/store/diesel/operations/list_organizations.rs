@@ -13,9 +13,9 @@
 // limitations under the License.
 
 use super::PikeStoreOperations;
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
-use crate::paging::Paging;
+use crate::paging::{paged_query, PageRequest, Paging};
 use crate::pike::store::diesel::models::{OrganizationMetadataModel, OrganizationModel};
 use crate::pike::store::diesel::{
     schema::{pike_organization, pike_organization_metadata},
@@ -28,9 +28,10 @@ use diesel::prelude::*;
 pub(in crate::pike::store::diesel) trait PikeStoreListOrganizationsOperation {
     fn list_organizations(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<OrganizationList, PikeStoreError>;
 }
 
@@ -38,15 +39,25 @@ pub(in crate::pike::store::diesel) trait PikeStoreListOrganizationsOperation {
 impl<'a> PikeStoreListOrganizationsOperation for PikeStoreOperations<'a, diesel::pg::PgConnection> {
     fn list_organizations(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<OrganizationList, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn.transaction::<_, PikeStoreError, _>(|| {
             let mut query = pike_organization::table
                 .into_boxed()
                 .select(pike_organization::all_columns)
-                .filter(pike_organization::end_commit_num.eq(MAX_COMMIT_NUM));
+                .filter(
+                    pike_organization::start_commit_num
+                        .le(height)
+                        .and(pike_organization::end_commit_num.gt(height)),
+                );
+
+            if let Some(org_id) = org_id {
+                query = query.filter(pike_organization::org_id.eq(org_id));
+            }
 
             if let Some(service_id) = service_id {
                 query = query.filter(pike_organization::service_id.eq(service_id));
@@ -54,6 +65,8 @@ impl<'a> PikeStoreListOrganizationsOperation for PikeStoreOperations<'a, diesel:
                 query = query.filter(pike_organization::service_id.is_null());
             }
 
+            let query = paged_query(query, pike_organization::org_id, page);
+
             let org_models = query.load::<OrganizationModel>(self.conn).map_err(|err| {
                 PikeStoreError::InternalError(InternalError::from_source(Box::new(err)))
             })?;
@@ -62,13 +75,22 @@ impl<'a> PikeStoreListOrganizationsOperation for PikeStoreOperations<'a, diesel:
                 .into_boxed()
                 .select(pike_organization::all_columns);
 
+            if let Some(org_id) = org_id {
+                count_query = count_query.filter(pike_organization::org_id.eq(org_id));
+            }
+
             if let Some(service_id) = service_id {
                 count_query = count_query.filter(pike_organization::service_id.eq(service_id));
             } else {
                 count_query = count_query.filter(pike_organization::service_id.is_null());
             }
 
-            let total = count_query.count().get_result(self.conn)?;
+            let (org_models, paging) = Paging::for_page(
+                org_models,
+                page,
+                || count_query.count().get_result(self.conn),
+                |model| model.org_id.clone(),
+            )?;
 
             let mut orgs = Vec::new();
 
@@ -77,9 +99,11 @@ impl<'a> PikeStoreListOrganizationsOperation for PikeStoreOperations<'a, diesel:
                     .into_boxed()
                     .select(pike_organization_metadata::all_columns)
                     .filter(
-                        pike_organization_metadata::org_id
-                            .eq(&org.org_id)
-                            .and(pike_organization_metadata::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        pike_organization_metadata::org_id.eq(&org.org_id).and(
+                            pike_organization_metadata::start_commit_num
+                                .le(height)
+                                .and(pike_organization_metadata::end_commit_num.gt(height)),
+                        ),
                     );
 
                 if let Some(service_id) = service_id {
@@ -103,10 +127,7 @@ impl<'a> PikeStoreListOrganizationsOperation for PikeStoreOperations<'a, diesel:
                 orgs.push(Organization::from((org, metadata)));
             }
 
-            Ok(OrganizationList::new(
-                orgs,
-                Paging::new(offset, limit, total),
-            ))
+            Ok(OrganizationList::new(orgs, paging))
         })
     }
 }
@@ -117,15 +138,25 @@ impl<'a> PikeStoreListOrganizationsOperation
 {
     fn list_organizations(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<OrganizationList, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
         self.conn.transaction::<_, PikeStoreError, _>(|| {
             let mut query = pike_organization::table
                 .into_boxed()
                 .select(pike_organization::all_columns)
-                .filter(pike_organization::end_commit_num.eq(MAX_COMMIT_NUM));
+                .filter(
+                    pike_organization::start_commit_num
+                        .le(height)
+                        .and(pike_organization::end_commit_num.gt(height)),
+                );
+
+            if let Some(org_id) = org_id {
+                query = query.filter(pike_organization::org_id.eq(org_id));
+            }
 
             if let Some(service_id) = service_id {
                 query = query.filter(pike_organization::service_id.eq(service_id));
@@ -133,6 +164,8 @@ impl<'a> PikeStoreListOrganizationsOperation
                 query = query.filter(pike_organization::service_id.is_null());
             }
 
+            let query = paged_query(query, pike_organization::org_id, page);
+
             let org_models = query.load::<OrganizationModel>(self.conn).map_err(|err| {
                 PikeStoreError::InternalError(InternalError::from_source(Box::new(err)))
             })?;
@@ -141,13 +174,22 @@ impl<'a> PikeStoreListOrganizationsOperation
                 .into_boxed()
                 .select(pike_organization::all_columns);
 
+            if let Some(org_id) = org_id {
+                count_query = count_query.filter(pike_organization::org_id.eq(org_id));
+            }
+
             if let Some(service_id) = service_id {
                 count_query = count_query.filter(pike_organization::service_id.eq(service_id));
             } else {
                 count_query = count_query.filter(pike_organization::service_id.is_null());
             }
 
-            let total = count_query.count().get_result(self.conn)?;
+            let (org_models, paging) = Paging::for_page(
+                org_models,
+                page,
+                || count_query.count().get_result(self.conn),
+                |model| model.org_id.clone(),
+            )?;
 
             let mut orgs = Vec::new();
 
@@ -156,9 +198,11 @@ impl<'a> PikeStoreListOrganizationsOperation
                     .into_boxed()
                     .select(pike_organization_metadata::all_columns)
                     .filter(
-                        pike_organization_metadata::org_id
-                            .eq(&org.org_id)
-                            .and(pike_organization_metadata::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        pike_organization_metadata::org_id.eq(&org.org_id).and(
+                            pike_organization_metadata::start_commit_num
+                                .le(height)
+                                .and(pike_organization_metadata::end_commit_num.gt(height)),
+                        ),
                     );
 
                 if let Some(service_id) = service_id {
@@ -182,10 +226,7 @@ impl<'a> PikeStoreListOrganizationsOperation
                 orgs.push(Organization::from((org, metadata)));
             }
 
-            Ok(OrganizationList::new(
-                orgs,
-                Paging::new(offset, limit, total),
-            ))
+            Ok(OrganizationList::new(orgs, paging))
         })
     }
 }
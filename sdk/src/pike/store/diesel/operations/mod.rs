@@ -17,6 +17,7 @@ pub(super) mod add_organization;
 pub(super) mod fetch_agent;
 pub(super) mod fetch_organization;
 pub(super) mod list_agents;
+pub(super) mod list_organization_history;
 pub(super) mod list_organizations;
 pub(super) mod update_agent;
 
@@ -0,0 +1,377 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::commits::{
+    commit_height_or_current, end_current_rows, impl_versioned, resolve_fork_rows, Versioned,
+};
+use crate::error::InternalError;
+use crate::paging::{page_items, PageRequest};
+
+use super::{
+    Agent, AgentList, Organization, OrganizationList, OrganizationMetadata, PikeStore,
+    PikeStoreError, Role,
+};
+
+impl_versioned!(Agent, Role, Organization);
+
+/// An organization's metadata entry, with the ID of the organization it belongs to
+#[derive(Clone)]
+struct MetadataRow {
+    org_id: String,
+    metadata: OrganizationMetadata,
+}
+
+impl Versioned for MetadataRow {
+    fn start_commit_num(&self) -> i64 {
+        self.metadata.start_commit_num
+    }
+
+    fn end_commit_num(&self) -> i64 {
+        self.metadata.end_commit_num
+    }
+
+    fn set_end_commit_num(&mut self, end_commit_num: i64) {
+        self.metadata.end_commit_num = end_commit_num;
+    }
+}
+
+#[derive(Default)]
+struct PikeState {
+    agents: Vec<Agent>,
+    roles: Vec<Role>,
+    organizations: Vec<Organization>,
+    metadata: Vec<MetadataRow>,
+}
+
+impl PikeState {
+    fn agent_at(&self, agent: &Agent, height: i64, service_id: Option<&str>) -> Agent {
+        let roles = self
+            .roles
+            .iter()
+            .filter(|role| {
+                role.public_key == agent.public_key
+                    && role.service_id.as_deref() == service_id
+                    && role.is_current_at(height)
+            })
+            .map(|role| role.role_name.clone())
+            .collect();
+
+        Agent {
+            roles,
+            ..agent.clone()
+        }
+    }
+
+    fn organization_at(
+        &self,
+        org: &Organization,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> Organization {
+        let metadata = self
+            .metadata
+            .iter()
+            .filter(|row| {
+                row.org_id == org.org_id
+                    && row.metadata.service_id.as_deref() == service_id
+                    && row.is_current_at(height)
+            })
+            .map(|row| row.metadata.clone())
+            .collect();
+
+        Organization {
+            metadata,
+            ..org.clone()
+        }
+    }
+
+    fn fetch_organization(
+        &self,
+        org_id: &str,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> Option<Organization> {
+        self.organizations
+            .iter()
+            .find(|org| {
+                org.org_id == org_id
+                    && org.service_id.as_deref() == service_id
+                    && org.is_current_at(height)
+            })
+            .map(|org| self.organization_at(org, height, service_id))
+    }
+
+    fn insert_agent(&mut self, agent: Agent) {
+        for role_name in &agent.roles {
+            self.roles.push(Role {
+                public_key: agent.public_key.clone(),
+                role_name: role_name.clone(),
+                start_commit_num: agent.start_commit_num,
+                end_commit_num: agent.end_commit_num,
+                service_id: agent.service_id.clone(),
+            });
+        }
+        self.agents.push(Agent {
+            roles: Vec::new(),
+            ..agent
+        });
+    }
+}
+
+/// Implementation of `PikeStore` that keeps agents and organizations in memory, versioned by
+/// commit like the database-backed store. Useful for tests and when persistence isn't necessary.
+#[derive(Clone, Default)]
+pub struct MemoryPikeStore {
+    inner: Arc<Mutex<PikeState>>,
+}
+
+impl MemoryPikeStore {
+    pub fn new() -> Self {
+        MemoryPikeStore::default()
+    }
+
+    /// Rolls the agents and organizations back to their state before the given commit
+    ///
+    /// # Arguments
+    ///
+    ///  * `commit_num` - The number of the first commit that is no longer on the chain
+    pub fn resolve_fork(&self, commit_num: i64) -> Result<(), PikeStoreError> {
+        let mut state = self.state()?;
+        resolve_fork_rows(&mut state.agents, commit_num);
+        resolve_fork_rows(&mut state.roles, commit_num);
+        resolve_fork_rows(&mut state.organizations, commit_num);
+        resolve_fork_rows(&mut state.metadata, commit_num);
+        Ok(())
+    }
+
+    /// Returns the metadata of the first version of an agent that was still current when a
+    /// reporter's version ended, as the reporter metadata view of the database-backed stores does
+    #[cfg(feature = "track-and-trace")]
+    pub(crate) fn reporter_metadata(
+        &self,
+        public_key: &str,
+        reporter_end_commit_num: i64,
+    ) -> Result<Option<Vec<u8>>, PikeStoreError> {
+        let state = self.state()?;
+        Ok(state
+            .agents
+            .iter()
+            .filter(|agent| {
+                agent.public_key == public_key && agent.end_commit_num >= reporter_end_commit_num
+            })
+            .min_by_key(|agent| agent.end_commit_num)
+            .map(|agent| agent.metadata.clone()))
+    }
+
+    /// Returns the organization of the agent with a public key at a commit height, as the
+    /// record holder filter of the database-backed stores reads it
+    #[cfg(feature = "track-and-trace")]
+    pub(crate) fn agent_org_id(
+        &self,
+        public_key: &str,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> Result<Option<String>, PikeStoreError> {
+        let state = self.state()?;
+        Ok(state
+            .agents
+            .iter()
+            .find(|agent| {
+                agent.public_key == public_key
+                    && agent.service_id.as_deref() == service_id
+                    && agent.is_current_at(height)
+            })
+            .map(|agent| agent.org_id.clone()))
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, PikeState>, PikeStoreError> {
+        self.inner.lock().map_err(|_| {
+            PikeStoreError::InternalError(InternalError::with_message(
+                "Cannot access pike state: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+impl PikeStore for MemoryPikeStore {
+    fn add_agent(&self, agent: Agent) -> Result<(), PikeStoreError> {
+        let mut state = self.state()?;
+
+        let start_commit_num = agent.start_commit_num;
+        end_current_rows(&mut state.agents, start_commit_num, |current| {
+            current.public_key == agent.public_key && current.service_id == agent.service_id
+        });
+        end_current_rows(&mut state.roles, start_commit_num, |role| {
+            role.public_key == agent.public_key
+                && role.service_id == agent.service_id
+                && agent.roles.contains(&role.role_name)
+        });
+
+        state.insert_agent(agent);
+        Ok(())
+    }
+
+    fn list_agents(
+        &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<AgentList, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let agents = state
+            .agents
+            .iter()
+            .filter(|agent| {
+                org_id.map(|org_id| agent.org_id == org_id).unwrap_or(true)
+                    && agent.service_id.as_deref() == service_id
+                    && agent.is_current_at(height)
+            })
+            .collect();
+        let (agents, paging) = page_items(agents, page, |agent| agent.public_key.clone());
+
+        Ok(AgentList::new(
+            agents
+                .into_iter()
+                .map(|agent| state.agent_at(agent, height, service_id))
+                .collect(),
+            paging,
+        ))
+    }
+
+    fn fetch_agent(
+        &self,
+        pub_key: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Option<Agent>, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        Ok(state
+            .agents
+            .iter()
+            .find(|agent| {
+                agent.public_key == pub_key
+                    && agent.service_id.as_deref() == service_id
+                    && agent.is_current_at(height)
+            })
+            .map(|agent| state.agent_at(agent, height, service_id)))
+    }
+
+    fn update_agent(&self, agent: Agent) -> Result<(), PikeStoreError> {
+        let mut state = self.state()?;
+
+        let start_commit_num = agent.start_commit_num;
+        end_current_rows(&mut state.agents, start_commit_num, |current| {
+            current.public_key == agent.public_key && current.service_id == agent.service_id
+        });
+        end_current_rows(&mut state.roles, start_commit_num, |role| {
+            role.public_key == agent.public_key && role.service_id == agent.service_id
+        });
+
+        state.insert_agent(agent);
+        Ok(())
+    }
+
+    fn add_organization(&self, org: Organization) -> Result<(), PikeStoreError> {
+        let mut state = self.state()?;
+
+        let start_commit_num = org.start_commit_num;
+        end_current_rows(&mut state.organizations, start_commit_num, |current| {
+            current.org_id == org.org_id && current.service_id == org.service_id
+        });
+        end_current_rows(&mut state.metadata, start_commit_num, |row| {
+            row.org_id == org.org_id && row.metadata.service_id == org.service_id
+        });
+
+        for data in &org.metadata {
+            state.metadata.push(MetadataRow {
+                org_id: org.org_id.clone(),
+                metadata: data.clone(),
+            });
+        }
+        state.organizations.push(Organization {
+            metadata: Vec::new(),
+            ..org
+        });
+        Ok(())
+    }
+
+    fn list_organizations(
+        &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<OrganizationList, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let orgs = state
+            .organizations
+            .iter()
+            .filter(|org| {
+                org_id.map(|org_id| org.org_id == org_id).unwrap_or(true)
+                    && org.service_id.as_deref() == service_id
+                    && org.is_current_at(height)
+            })
+            .collect();
+        let (orgs, paging) = page_items(orgs, page, |org| org.org_id.clone());
+
+        Ok(OrganizationList::new(
+            orgs.into_iter()
+                .map(|org| state.organization_at(org, height, service_id))
+                .collect(),
+            paging,
+        ))
+    }
+
+    fn fetch_organization(
+        &self,
+        org_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Option<Organization>, PikeStoreError> {
+        let height = commit_height_or_current(commit_height);
+        Ok(self.state()?.fetch_organization(org_id, height, service_id))
+    }
+
+    fn list_organization_history(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Organization>, PikeStoreError> {
+        let state = self.state()?;
+
+        let mut start_commit_nums = state
+            .organizations
+            .iter()
+            .filter(|org| org.org_id == org_id && org.service_id.as_deref() == service_id)
+            .map(|org| org.start_commit_num)
+            .collect::<Vec<_>>();
+        start_commit_nums.sort_unstable();
+
+        Ok(start_commit_nums
+            .into_iter()
+            .filter_map(|start_commit_num| {
+                state.fetch_organization(org_id, start_commit_num, service_id)
+            })
+            .collect())
+    }
+}
@@ -15,8 +15,10 @@
 #[cfg(feature = "diesel")]
 pub mod diesel;
 mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
 
-use crate::paging::Paging;
+use crate::paging::{PageRequest, Paging};
 
 pub use error::PikeStoreError;
 
@@ -104,14 +106,18 @@ pub trait PikeStore: Send + Sync {
     ///
     /// # Arguments
     ///
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
+    ///  * `org_id` - The organization to list the agents of, or every organization if not
+    ///    provided
     ///  * `service_id` - The service id to list agents for
-    ///  * `offset` - The index of the first in storage to retrieve
-    ///  * `limit` - The number of items to retrieve from the offset
+    ///  * `page` - The page to retrieve, by offset or by key
     fn list_agents(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<AgentList, PikeStoreError>;
 
     /// Fetches an agent from the underlying storage
@@ -119,10 +125,13 @@ pub trait PikeStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `pub_key` - This public key of the agent to fetch
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service id of the agent to fetch
     fn fetch_agent(
         &self,
         pub_key: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Agent>, PikeStoreError>;
 
@@ -144,14 +153,17 @@ pub trait PikeStore: Send + Sync {
     ///
     /// # Arguments
     ///
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
+    ///  * `org_id` - The only organization to list, or every organization if not provided
     ///  * `service_id` - The service ID to list organizations for
-    ///  * `offset` - The index of the first in storage to retrieve
-    ///  * `limit` - The number of items to retrieve from the offset
+    ///  * `page` - The page to retrieve, by offset or by key
     fn list_organizations(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<OrganizationList, PikeStoreError>;
 
     /// Fetches an organization from the underlying storage
@@ -159,12 +171,27 @@ pub trait PikeStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `org_id` - This organization ID to fetch
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service ID of the organization to fetch
     fn fetch_organization(
         &self,
         org_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Organization>, PikeStoreError>;
+
+    /// Lists every version of an organization from the underlying storage, oldest first
+    ///
+    /// # Arguments
+    ///
+    ///  * `org_id` - The ID of the organization to list the history of
+    ///  * `service_id` - The service ID of the organization
+    fn list_organization_history(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Organization>, PikeStoreError>;
 }
 
 impl<PS> PikeStore for Box<PS>
@@ -177,19 +204,21 @@ where
 
     fn list_agents(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<AgentList, PikeStoreError> {
-        (**self).list_agents(service_id, offset, limit)
+        (**self).list_agents(commit_height, org_id, service_id, page)
     }
 
     fn fetch_agent(
         &self,
         pub_key: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Agent>, PikeStoreError> {
-        (**self).fetch_agent(pub_key, service_id)
+        (**self).fetch_agent(pub_key, commit_height, service_id)
     }
 
     fn update_agent(&self, agent: Agent) -> Result<(), PikeStoreError> {
@@ -202,18 +231,28 @@ where
 
     fn list_organizations(
         &self,
+        commit_height: Option<i64>,
+        org_id: Option<&str>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<OrganizationList, PikeStoreError> {
-        (**self).list_organizations(service_id, offset, limit)
+        (**self).list_organizations(commit_height, org_id, service_id, page)
     }
 
     fn fetch_organization(
         &self,
         org_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Organization>, PikeStoreError> {
-        (**self).fetch_organization(org_id, service_id)
+        (**self).fetch_organization(org_id, commit_height, service_id)
+    }
+
+    fn list_organization_history(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Organization>, PikeStoreError> {
+        (**self).list_organization_history(org_id, service_id)
     }
 }
@@ -19,4 +19,6 @@ pub const MAX_COMMIT_NUM: i64 = i64::MAX;
 
 #[cfg(feature = "diesel")]
 pub use store::diesel::DieselProductStore;
+#[cfg(feature = "memory")]
+pub use store::memory::MemoryProductStore;
 pub use store::ProductStore;
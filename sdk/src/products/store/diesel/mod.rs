@@ -17,13 +17,16 @@ mod operations;
 pub(in crate) mod schema;
 
 use crate::error::ResourceTemporarilyUnavailableError;
+use crate::filter::ListFilter;
+use crate::paging::PageRequest;
 use crate::products::MAX_COMMIT_NUM;
 
 use models::{NewProduct, NewProductPropertyValue, Product as ModelProduct, ProductPropertyValue};
 use operations::{
     add_product::AddProductOperation, delete_product::DeleteProductOperation,
-    fetch_product::FetchProductOperation, list_products::ListProductsOperation,
-    update_product::UpdateProductOperation, ProductStoreOperations,
+    fetch_product::FetchProductOperation, list_product_history::ListProductHistoryOperation,
+    list_products::ListProductsOperation, update_product::UpdateProductOperation,
+    ProductStoreOperations,
 };
 
 use diesel::r2d2::{ConnectionManager, Pool};
@@ -55,6 +58,7 @@ impl ProductStore for DieselProductStore<diesel::pg::PgConnection> {
     fn fetch_product(
         &self,
         product_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError> {
         ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -62,21 +66,35 @@ impl ProductStore for DieselProductStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_product(product_id, service_id)
+        .fetch_product(product_id, commit_height, service_id)
     }
 
     fn list_products(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<ProductList, ProductStoreError> {
         ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             ProductStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_products(service_id, offset, limit)
+        .list_products(commit_height, filter, service_id, page)
+    }
+
+    fn list_product_history(
+        &self,
+        product_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError> {
+        ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            ProductStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_product_history(product_id, service_id)
     }
 
     fn update_product(
@@ -121,6 +139,7 @@ impl ProductStore for DieselProductStore<diesel::sqlite::SqliteConnection> {
     fn fetch_product(
         &self,
         product_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError> {
         ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -128,21 +147,35 @@ impl ProductStore for DieselProductStore<diesel::sqlite::SqliteConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_product(product_id, service_id)
+        .fetch_product(product_id, commit_height, service_id)
     }
 
     fn list_products(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<ProductList, ProductStoreError> {
         ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             ProductStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_products(service_id, offset, limit)
+        .list_products(commit_height, filter, service_id, page)
+    }
+
+    fn list_product_history(
+        &self,
+        product_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError> {
+        ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            ProductStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_product_history(product_id, service_id)
     }
 
     fn update_product(
@@ -14,16 +14,14 @@
 
 use super::ProductStoreOperations;
 
-use crate::products::{
-    store::{
-        diesel::{
-            models::{Product as ModelProduct, ProductPropertyValue},
-            schema::{product, product_property_value},
-        },
-        error::ProductStoreError,
-        Product, PropertyValue,
+use crate::commits::commit_height_or_current;
+use crate::products::store::{
+    diesel::{
+        models::{Product as ModelProduct, ProductPropertyValue},
+        schema::{product, product_property_value},
     },
-    MAX_COMMIT_NUM,
+    error::ProductStoreError,
+    Product, PropertyValue,
 };
 use diesel::{prelude::*, result::Error::NotFound};
 
@@ -31,6 +29,7 @@ pub(in crate::products) trait FetchProductOperation {
     fn fetch_product(
         &self,
         product_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError>;
 }
@@ -40,18 +39,21 @@ impl<'a> FetchProductOperation for ProductStoreOperations<'a, diesel::pg::PgConn
     fn fetch_product(
         &self,
         product_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError> {
-        let product = if let Some(product) = pg::fetch_product(&*self.conn, product_id, service_id)?
+        let height = commit_height_or_current(commit_height);
+        let product = if let Some(product) =
+            pg::fetch_product(&*self.conn, product_id, height, service_id)?
         {
             product
         } else {
             return Ok(None);
         };
 
-        let root_values = pg::get_root_values(&*self.conn, product_id)?;
+        let root_values = pg::get_root_values(&*self.conn, product_id, height)?;
 
-        let values = pg::get_property_values(&*self.conn, root_values)?;
+        let values = pg::get_property_values(&*self.conn, root_values, height)?;
 
         Ok(Some(Product::from((product, values))))
     }
@@ -62,18 +64,21 @@ impl<'a> FetchProductOperation for ProductStoreOperations<'a, diesel::sqlite::Sq
     fn fetch_product(
         &self,
         product_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError> {
-        let product =
-            if let Some(product) = sqlite::fetch_product(&*self.conn, product_id, service_id)? {
-                product
-            } else {
-                return Ok(None);
-            };
+        let height = commit_height_or_current(commit_height);
+        let product = if let Some(product) =
+            sqlite::fetch_product(&*self.conn, product_id, height, service_id)?
+        {
+            product
+        } else {
+            return Ok(None);
+        };
 
-        let root_values = sqlite::get_root_values(&*self.conn, product_id)?;
+        let root_values = sqlite::get_root_values(&*self.conn, product_id, height)?;
 
-        let values = sqlite::get_property_values(&*self.conn, root_values)?;
+        let values = sqlite::get_property_values(&*self.conn, root_values, height)?;
 
         Ok(Some(Product::from((product, values))))
     }
@@ -86,15 +91,18 @@ mod pg {
     pub fn fetch_product(
         conn: &PgConnection,
         product_id: &str,
+        height: i64,
         service_id: Option<&str>,
     ) -> QueryResult<Option<ModelProduct>> {
         let mut query = product::table
             .into_boxed()
             .select(product::all_columns)
             .filter(
-                product::product_id
-                    .eq(product_id)
-                    .and(product::end_commit_num.eq(MAX_COMMIT_NUM)),
+                product::product_id.eq(product_id).and(
+                    product::start_commit_num
+                        .le(height)
+                        .and(product::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
@@ -112,6 +120,7 @@ mod pg {
     pub fn get_root_values(
         conn: &PgConnection,
         product_id: &str,
+        height: i64,
     ) -> QueryResult<Vec<ProductPropertyValue>> {
         product_property_value::table
             .select(product_property_value::all_columns)
@@ -119,7 +128,11 @@ mod pg {
                 product_property_value::product_id
                     .eq(product_id)
                     .and(product_property_value::parent_property.is_null())
-                    .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        product_property_value::start_commit_num
+                            .le(height)
+                            .and(product_property_value::end_commit_num.gt(height)),
+                    ),
             )
             .load::<ProductPropertyValue>(conn)
     }
@@ -127,13 +140,24 @@ mod pg {
     pub fn get_property_values(
         conn: &PgConnection,
         root_values: Vec<ProductPropertyValue>,
+        height: i64,
     ) -> Result<Vec<PropertyValue>, ProductStoreError> {
         let mut definitions = Vec::new();
 
         for root_value in root_values {
+            // Struct values are stored with their parent's product ID and property name
+            let parent_property = format!("{}:{}", root_value.product_id, root_value.property_name);
             let children = product_property_value::table
                 .select(product_property_value::all_columns)
-                .filter(product_property_value::parent_property.eq(&root_value.parent_property))
+                .filter(
+                    product_property_value::parent_property
+                        .eq(&parent_property)
+                        .and(
+                            product_property_value::start_commit_num
+                                .le(height)
+                                .and(product_property_value::end_commit_num.gt(height)),
+                        ),
+                )
                 .load(conn)?;
 
             if children.is_empty() {
@@ -141,7 +165,7 @@ mod pg {
             } else {
                 definitions.push(PropertyValue::from((
                     root_value,
-                    get_property_values(conn, children)?,
+                    get_property_values(conn, children, height)?,
                 )));
             }
         }
@@ -157,15 +181,18 @@ mod sqlite {
     pub fn fetch_product(
         conn: &SqliteConnection,
         product_id: &str,
+        height: i64,
         service_id: Option<&str>,
     ) -> QueryResult<Option<ModelProduct>> {
         let mut query = product::table
             .into_boxed()
             .select(product::all_columns)
             .filter(
-                product::product_id
-                    .eq(product_id)
-                    .and(product::end_commit_num.eq(MAX_COMMIT_NUM)),
+                product::product_id.eq(product_id).and(
+                    product::start_commit_num
+                        .le(height)
+                        .and(product::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
@@ -183,6 +210,7 @@ mod sqlite {
     pub fn get_root_values(
         conn: &SqliteConnection,
         product_id: &str,
+        height: i64,
     ) -> QueryResult<Vec<ProductPropertyValue>> {
         product_property_value::table
             .select(product_property_value::all_columns)
@@ -190,7 +218,11 @@ mod sqlite {
                 product_property_value::product_id
                     .eq(product_id)
                     .and(product_property_value::parent_property.is_null())
-                    .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        product_property_value::start_commit_num
+                            .le(height)
+                            .and(product_property_value::end_commit_num.gt(height)),
+                    ),
             )
             .load::<ProductPropertyValue>(conn)
     }
@@ -198,13 +230,24 @@ mod sqlite {
     pub fn get_property_values(
         conn: &SqliteConnection,
         root_values: Vec<ProductPropertyValue>,
+        height: i64,
     ) -> Result<Vec<PropertyValue>, ProductStoreError> {
         let mut definitions = Vec::new();
 
         for root_value in root_values {
+            // Struct values are stored with their parent's product ID and property name
+            let parent_property = format!("{}:{}", root_value.product_id, root_value.property_name);
             let children = product_property_value::table
                 .select(product_property_value::all_columns)
-                .filter(product_property_value::parent_property.eq(&root_value.parent_property))
+                .filter(
+                    product_property_value::parent_property
+                        .eq(&parent_property)
+                        .and(
+                            product_property_value::start_commit_num
+                                .le(height)
+                                .and(product_property_value::end_commit_num.gt(height)),
+                        ),
+                )
                 .load(conn)?;
 
             if children.is_empty() {
@@ -212,7 +255,7 @@ mod sqlite {
             } else {
                 definitions.push(PropertyValue::from((
                     root_value,
-                    get_property_values(conn, children)?,
+                    get_property_values(conn, children, height)?,
                 )));
             }
         }
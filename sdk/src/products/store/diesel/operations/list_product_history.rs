@@ -0,0 +1,95 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::fetch_product::FetchProductOperation;
+use super::ProductStoreOperations;
+
+use crate::products::store::{diesel::schema::product, error::ProductStoreError, Product};
+use diesel::prelude::*;
+
+pub(in crate::products) trait ListProductHistoryOperation {
+    fn list_product_history(
+        &self,
+        product_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ListProductHistoryOperation for ProductStoreOperations<'a, diesel::pg::PgConnection> {
+    fn list_product_history(
+        &self,
+        product_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError> {
+        let mut query = product::table
+            .into_boxed()
+            .select(product::start_commit_num)
+            .filter(product::product_id.eq(product_id))
+            .order(product::start_commit_num.asc());
+
+        if let Some(service_id) = service_id {
+            query = query.filter(product::service_id.eq(service_id));
+        } else {
+            query = query.filter(product::service_id.is_null());
+        }
+
+        let mut versions = Vec::new();
+
+        for start_commit_num in query.load::<i64>(self.conn)? {
+            if let Some(product) =
+                self.fetch_product(product_id, Some(start_commit_num), service_id)?
+            {
+                versions.push(product);
+            }
+        }
+
+        Ok(versions)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ListProductHistoryOperation
+    for ProductStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_product_history(
+        &self,
+        product_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError> {
+        let mut query = product::table
+            .into_boxed()
+            .select(product::start_commit_num)
+            .filter(product::product_id.eq(product_id))
+            .order(product::start_commit_num.asc());
+
+        if let Some(service_id) = service_id {
+            query = query.filter(product::service_id.eq(service_id));
+        } else {
+            query = query.filter(product::service_id.is_null());
+        }
+
+        let mut versions = Vec::new();
+
+        for start_commit_num in query.load::<i64>(self.conn)? {
+            if let Some(product) =
+                self.fetch_product(product_id, Some(start_commit_num), service_id)?
+            {
+                versions.push(product);
+            }
+        }
+
+        Ok(versions)
+    }
+}
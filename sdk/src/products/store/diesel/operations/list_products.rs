@@ -14,28 +14,28 @@
 
 use super::ProductStoreOperations;
 
+use crate::commits::commit_height_or_current;
 use crate::{
-    paging::Paging,
-    products::{
-        store::{
-            diesel::{
-                models::{Product as ModelProduct, ProductPropertyValue},
-                schema::{product, product_property_value},
-            },
-            error::ProductStoreError,
-            Product, ProductList, PropertyValue,
+    filter::{like_prefix, Filter, ListFilter, PropertyMatch, SortField, SortOrder, StringMatch},
+    paging::{paged_query, PageRequest, Paging},
+    products::store::{
+        diesel::{
+            models::{Product as ModelProduct, ProductPropertyValue},
+            schema::{product, product_property_value},
         },
-        MAX_COMMIT_NUM,
+        error::ProductStoreError,
+        Product, ProductList, PropertyValue,
     },
 };
-use diesel::prelude::*;
+use diesel::{prelude::*, sql_types::Bool};
 
 pub(in crate::products) trait ListProductsOperation {
     fn list_products(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<ProductList, ProductStoreError>;
 }
 
@@ -43,36 +43,36 @@ pub(in crate::products) trait ListProductsOperation {
 impl<'a> ListProductsOperation for ProductStoreOperations<'a, diesel::pg::PgConnection> {
     fn list_products(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<ProductList, ProductStoreError> {
-        let db_products = pg::list_products(&*self.conn, service_id, offset, limit)?;
+        let height = commit_height_or_current(commit_height);
+        let db_products = pg::list_products(&*self.conn, height, filter, service_id, page)?;
+
+        let (db_products, paging) = Paging::for_page(
+            db_products,
+            page,
+            || {
+                pg::filtered_products(height, filter, service_id)
+                    .count()
+                    .get_result(self.conn)
+            },
+            |product| product.product_id.clone(),
+        )?;
 
         let mut products = Vec::new();
 
         for product in db_products {
-            let root_values = pg::get_root_values(&*self.conn, &product.product_id)?;
+            let root_values = pg::get_root_values(&*self.conn, &product.product_id, height)?;
 
-            let values = pg::get_property_values(&*self.conn, root_values)?;
+            let values = pg::get_property_values(&*self.conn, root_values, height)?;
 
             products.push(Product::from((product, values)));
         }
 
-        let mut count_query = product::table.into_boxed().select(product::all_columns);
-
-        if let Some(service_id) = service_id {
-            count_query = count_query.filter(product::service_id.eq(service_id));
-        } else {
-            count_query = count_query.filter(product::service_id.is_null());
-        }
-
-        let total = count_query.count().get_result(self.conn)?;
-
-        Ok(ProductList::new(
-            products,
-            Paging::new(offset, limit, total),
-        ))
+        Ok(ProductList::new(products, paging))
     }
 }
 
@@ -80,36 +80,36 @@ impl<'a> ListProductsOperation for ProductStoreOperations<'a, diesel::pg::PgConn
 impl<'a> ListProductsOperation for ProductStoreOperations<'a, diesel::sqlite::SqliteConnection> {
     fn list_products(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<ProductList, ProductStoreError> {
-        let db_products = sqlite::list_products(&*self.conn, service_id, offset, limit)?;
+        let height = commit_height_or_current(commit_height);
+        let db_products = sqlite::list_products(&*self.conn, height, filter, service_id, page)?;
+
+        let (db_products, paging) = Paging::for_page(
+            db_products,
+            page,
+            || {
+                sqlite::filtered_products(height, filter, service_id)
+                    .count()
+                    .get_result(self.conn)
+            },
+            |product| product.product_id.clone(),
+        )?;
 
         let mut products = Vec::new();
 
         for product in db_products {
-            let root_values = sqlite::get_root_values(&*self.conn, &product.product_id)?;
+            let root_values = sqlite::get_root_values(&*self.conn, &product.product_id, height)?;
 
-            let values = sqlite::get_property_values(&*self.conn, root_values)?;
+            let values = sqlite::get_property_values(&*self.conn, root_values, height)?;
 
             products.push(Product::from((product, values)));
         }
 
-        let mut count_query = product::table.into_boxed().select(product::all_columns);
-
-        if let Some(service_id) = service_id {
-            count_query = count_query.filter(product::service_id.eq(service_id));
-        } else {
-            count_query = count_query.filter(product::service_id.is_null());
-        }
-
-        let total = count_query.count().get_result(self.conn)?;
-
-        Ok(ProductList::new(
-            products,
-            Paging::new(offset, limit, total),
-        ))
+        Ok(ProductList::new(products, paging))
     }
 }
 
@@ -119,28 +119,154 @@ mod pg {
 
     pub fn list_products(
         conn: &PgConnection,
+        height: i64,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> QueryResult<Vec<ModelProduct>> {
-        let mut query = product::table
-            .into_boxed()
-            .select(product::all_columns)
-            .limit(limit)
-            .offset(offset)
-            .filter(product::end_commit_num.eq(MAX_COMMIT_NUM));
+        let query = paged_query(
+            filtered_products(height, filter, service_id),
+            product::product_id,
+            page,
+        );
+
+        // Pages read by key are in ID order, so only pages read by offset are sorted
+        if let PageRequest::Keyset { .. } = page {
+            return query.load::<ModelProduct>(conn);
+        }
+
+        let query = match (filter.sort.field, filter.sort.order) {
+            (SortField::Id, SortOrder::Ascending) => query.order(product::product_id.asc()),
+            (SortField::Id, SortOrder::Descending) => query.order(product::product_id.desc()),
+            (SortField::Owner, SortOrder::Ascending) => query.order(product::owner.asc()),
+            (SortField::Owner, SortOrder::Descending) => query.order(product::owner.desc()),
+            (SortField::Namespace, SortOrder::Ascending) => {
+                query.order(product::product_namespace.asc())
+            }
+            (SortField::Namespace, SortOrder::Descending) => {
+                query.order(product::product_namespace.desc())
+            }
+        };
+
+        query
+            .then_order_by(product::product_id.asc())
+            .load::<ModelProduct>(conn)
+    }
+
+    /// Selects the products current at the given height that meet every filter
+    pub fn filtered_products<'a>(
+        height: i64,
+        filter: &'a ListFilter,
+        service_id: Option<&'a str>,
+    ) -> product::BoxedQuery<'a, diesel::pg::Pg> {
+        let mut query = product::table.into_boxed().filter(
+            product::start_commit_num
+                .le(height)
+                .and(product::end_commit_num.gt(height)),
+        );
 
         if let Some(service_id) = service_id {
             query = query.filter(product::service_id.eq(service_id));
         } else {
             query = query.filter(product::service_id.is_null());
         }
-        query.load::<ModelProduct>(conn)
+
+        for condition in &filter.filters {
+            query = match condition {
+                Filter::Owner(StringMatch::Equals(owner)) => query.filter(product::owner.eq(owner)),
+                Filter::Owner(StringMatch::Prefix(prefix)) => {
+                    query.filter(product::owner.like(like_prefix(prefix)).escape('\\'))
+                }
+                Filter::Namespace(StringMatch::Equals(namespace)) => {
+                    query.filter(product::product_namespace.eq(namespace))
+                }
+                Filter::Namespace(StringMatch::Prefix(prefix)) => query.filter(
+                    product::product_namespace
+                        .like(like_prefix(prefix))
+                        .escape('\\'),
+                ),
+                Filter::Property { name, value } => query.filter(
+                    product::product_id
+                        .eq_any(products_with_property(name, value, height, service_id)),
+                ),
+            };
+        }
+
+        query
+    }
+
+    /// Selects the IDs of the products with a root property matching the value
+    fn products_with_property<'a>(
+        name: &'a str,
+        value: &'a PropertyMatch,
+        height: i64,
+        service_id: Option<&'a str>,
+    ) -> product_property_value::BoxedQuery<'a, diesel::pg::Pg, diesel::sql_types::Varchar> {
+        let mut query = product_property_value::table
+            .into_boxed()
+            .select(product_property_value::product_id)
+            .filter(
+                product_property_value::property_name
+                    .eq(name)
+                    .and(product_property_value::parent_property.is_null())
+                    .and(
+                        product_property_value::start_commit_num
+                            .le(height)
+                            .and(product_property_value::end_commit_num.gt(height)),
+                    ),
+            );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(product_property_value::service_id.eq(service_id));
+        } else {
+            query = query.filter(product_property_value::service_id.is_null());
+        }
+
+        match value {
+            PropertyMatch::Equals(value) => {
+                let mut condition: Box<
+                    dyn BoxableExpression<
+                            product_property_value::table,
+                            diesel::pg::Pg,
+                            SqlType = Bool,
+                        > + 'a,
+                > = Box::new(product_property_value::string_value.eq(value));
+                if let Ok(number) = value.parse::<i64>() {
+                    condition =
+                        Box::new(condition.or(product_property_value::number_value.eq(number)));
+                }
+                if let Ok(enum_value) = value.parse::<i32>() {
+                    condition =
+                        Box::new(condition.or(product_property_value::enum_value.eq(enum_value)));
+                }
+                if let Ok(boolean) = value.parse::<bool>() {
+                    condition =
+                        Box::new(condition.or(product_property_value::boolean_value.eq(boolean)));
+                }
+                query.filter(condition)
+            }
+            PropertyMatch::Prefix(prefix) => query.filter(
+                product_property_value::string_value
+                    .like(like_prefix(prefix))
+                    .escape('\\'),
+            ),
+            PropertyMatch::Range { min, max } => {
+                if let Some(min) = min {
+                    query = query.filter(product_property_value::number_value.ge(*min));
+                }
+                if let Some(max) = max {
+                    query = query.filter(product_property_value::number_value.le(*max));
+                }
+                query
+            }
+            PropertyMatch::Exists => query,
+        }
     }
 
     pub fn get_root_values(
         conn: &PgConnection,
         product_id: &str,
+        height: i64,
     ) -> QueryResult<Vec<ProductPropertyValue>> {
         product_property_value::table
             .select(product_property_value::all_columns)
@@ -148,7 +274,11 @@ mod pg {
                 product_property_value::product_id
                     .eq(product_id)
                     .and(product_property_value::parent_property.is_null())
-                    .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        product_property_value::start_commit_num
+                            .le(height)
+                            .and(product_property_value::end_commit_num.gt(height)),
+                    ),
             )
             .load::<ProductPropertyValue>(conn)
     }
@@ -156,13 +286,24 @@ mod pg {
     pub fn get_property_values(
         conn: &PgConnection,
         root_values: Vec<ProductPropertyValue>,
+        height: i64,
     ) -> Result<Vec<PropertyValue>, ProductStoreError> {
         let mut definitions = Vec::new();
 
         for root_value in root_values {
+            // Struct values are stored with their parent's product ID and property name
+            let parent_property = format!("{}:{}", root_value.product_id, root_value.property_name);
             let children = product_property_value::table
                 .select(product_property_value::all_columns)
-                .filter(product_property_value::parent_property.eq(&root_value.parent_property))
+                .filter(
+                    product_property_value::parent_property
+                        .eq(&parent_property)
+                        .and(
+                            product_property_value::start_commit_num
+                                .le(height)
+                                .and(product_property_value::end_commit_num.gt(height)),
+                        ),
+                )
                 .load(conn)?;
 
             if children.is_empty() {
@@ -170,7 +311,7 @@ mod pg {
             } else {
                 definitions.push(PropertyValue::from((
                     root_value,
-                    get_property_values(conn, children)?,
+                    get_property_values(conn, children, height)?,
                 )));
             }
         }
@@ -185,28 +326,155 @@ mod sqlite {
 
     pub fn list_products(
         conn: &SqliteConnection,
+        height: i64,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> QueryResult<Vec<ModelProduct>> {
-        let mut query = product::table
-            .into_boxed()
-            .select(product::all_columns)
-            .limit(limit)
-            .offset(offset)
-            .filter(product::end_commit_num.eq(MAX_COMMIT_NUM));
+        let query = paged_query(
+            filtered_products(height, filter, service_id),
+            product::product_id,
+            page,
+        );
+
+        // Pages read by key are in ID order, so only pages read by offset are sorted
+        if let PageRequest::Keyset { .. } = page {
+            return query.load::<ModelProduct>(conn);
+        }
+
+        let query = match (filter.sort.field, filter.sort.order) {
+            (SortField::Id, SortOrder::Ascending) => query.order(product::product_id.asc()),
+            (SortField::Id, SortOrder::Descending) => query.order(product::product_id.desc()),
+            (SortField::Owner, SortOrder::Ascending) => query.order(product::owner.asc()),
+            (SortField::Owner, SortOrder::Descending) => query.order(product::owner.desc()),
+            (SortField::Namespace, SortOrder::Ascending) => {
+                query.order(product::product_namespace.asc())
+            }
+            (SortField::Namespace, SortOrder::Descending) => {
+                query.order(product::product_namespace.desc())
+            }
+        };
+
+        query
+            .then_order_by(product::product_id.asc())
+            .load::<ModelProduct>(conn)
+    }
+
+    /// Selects the products current at the given height that meet every filter
+    pub fn filtered_products<'a>(
+        height: i64,
+        filter: &'a ListFilter,
+        service_id: Option<&'a str>,
+    ) -> product::BoxedQuery<'a, diesel::sqlite::Sqlite> {
+        let mut query = product::table.into_boxed().filter(
+            product::start_commit_num
+                .le(height)
+                .and(product::end_commit_num.gt(height)),
+        );
 
         if let Some(service_id) = service_id {
             query = query.filter(product::service_id.eq(service_id));
         } else {
             query = query.filter(product::service_id.is_null());
         }
-        query.load::<ModelProduct>(conn)
+
+        for condition in &filter.filters {
+            query = match condition {
+                Filter::Owner(StringMatch::Equals(owner)) => query.filter(product::owner.eq(owner)),
+                Filter::Owner(StringMatch::Prefix(prefix)) => {
+                    query.filter(product::owner.like(like_prefix(prefix)).escape('\\'))
+                }
+                Filter::Namespace(StringMatch::Equals(namespace)) => {
+                    query.filter(product::product_namespace.eq(namespace))
+                }
+                Filter::Namespace(StringMatch::Prefix(prefix)) => query.filter(
+                    product::product_namespace
+                        .like(like_prefix(prefix))
+                        .escape('\\'),
+                ),
+                Filter::Property { name, value } => query.filter(
+                    product::product_id
+                        .eq_any(products_with_property(name, value, height, service_id)),
+                ),
+            };
+        }
+
+        query
+    }
+
+    /// Selects the IDs of the products with a root property matching the value
+    fn products_with_property<'a>(
+        name: &'a str,
+        value: &'a PropertyMatch,
+        height: i64,
+        service_id: Option<&'a str>,
+    ) -> product_property_value::BoxedQuery<'a, diesel::sqlite::Sqlite, diesel::sql_types::Varchar>
+    {
+        let mut query = product_property_value::table
+            .into_boxed()
+            .select(product_property_value::product_id)
+            .filter(
+                product_property_value::property_name
+                    .eq(name)
+                    .and(product_property_value::parent_property.is_null())
+                    .and(
+                        product_property_value::start_commit_num
+                            .le(height)
+                            .and(product_property_value::end_commit_num.gt(height)),
+                    ),
+            );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(product_property_value::service_id.eq(service_id));
+        } else {
+            query = query.filter(product_property_value::service_id.is_null());
+        }
+
+        match value {
+            PropertyMatch::Equals(value) => {
+                let mut condition: Box<
+                    dyn BoxableExpression<
+                            product_property_value::table,
+                            diesel::sqlite::Sqlite,
+                            SqlType = Bool,
+                        > + 'a,
+                > = Box::new(product_property_value::string_value.eq(value));
+                if let Ok(number) = value.parse::<i64>() {
+                    condition =
+                        Box::new(condition.or(product_property_value::number_value.eq(number)));
+                }
+                if let Ok(enum_value) = value.parse::<i32>() {
+                    condition =
+                        Box::new(condition.or(product_property_value::enum_value.eq(enum_value)));
+                }
+                if let Ok(boolean) = value.parse::<bool>() {
+                    condition =
+                        Box::new(condition.or(product_property_value::boolean_value.eq(boolean)));
+                }
+                query.filter(condition)
+            }
+            PropertyMatch::Prefix(prefix) => query.filter(
+                product_property_value::string_value
+                    .like(like_prefix(prefix))
+                    .escape('\\'),
+            ),
+            PropertyMatch::Range { min, max } => {
+                if let Some(min) = min {
+                    query = query.filter(product_property_value::number_value.ge(*min));
+                }
+                if let Some(max) = max {
+                    query = query.filter(product_property_value::number_value.le(*max));
+                }
+                query
+            }
+            PropertyMatch::Exists => query,
+        }
     }
 
     pub fn get_root_values(
         conn: &SqliteConnection,
         product_id: &str,
+        height: i64,
     ) -> QueryResult<Vec<ProductPropertyValue>> {
         product_property_value::table
             .select(product_property_value::all_columns)
@@ -214,7 +482,11 @@ mod sqlite {
                 product_property_value::product_id
                     .eq(product_id)
                     .and(product_property_value::parent_property.is_null())
-                    .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        product_property_value::start_commit_num
+                            .le(height)
+                            .and(product_property_value::end_commit_num.gt(height)),
+                    ),
             )
             .load::<ProductPropertyValue>(conn)
     }
@@ -222,13 +494,24 @@ mod sqlite {
     pub fn get_property_values(
         conn: &SqliteConnection,
         root_values: Vec<ProductPropertyValue>,
+        height: i64,
     ) -> Result<Vec<PropertyValue>, ProductStoreError> {
         let mut definitions = Vec::new();
 
         for root_value in root_values {
+            // Struct values are stored with their parent's product ID and property name
+            let parent_property = format!("{}:{}", root_value.product_id, root_value.property_name);
             let children = product_property_value::table
                 .select(product_property_value::all_columns)
-                .filter(product_property_value::parent_property.eq(&root_value.parent_property))
+                .filter(
+                    product_property_value::parent_property
+                        .eq(&parent_property)
+                        .and(
+                            product_property_value::start_commit_num
+                                .le(height)
+                                .and(product_property_value::end_commit_num.gt(height)),
+                        ),
+                )
                 .load(conn)?;
 
             if children.is_empty() {
@@ -236,7 +519,7 @@ mod sqlite {
             } else {
                 definitions.push(PropertyValue::from((
                     root_value,
-                    get_property_values(conn, children)?,
+                    get_property_values(conn, children, height)?,
                 )));
             }
         }
@@ -0,0 +1,319 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::commits::{
+    commit_height_or_current, end_current_rows, impl_versioned, resolve_fork_rows, Versioned,
+    MAX_COMMIT_NUM,
+};
+use crate::error::InternalError;
+use crate::filter::{Filter, ListFilter, SortField, SortOrder};
+use crate::paging::{page_items, PageRequest};
+
+use super::{Product, ProductList, ProductStore, ProductStoreError, PropertyValue};
+
+impl_versioned!(Product);
+
+/// A property value without its struct values, with the name of the property it is a struct
+/// value of, if any
+#[derive(Clone)]
+struct ValueRow {
+    parent_property: Option<String>,
+    value: PropertyValue,
+}
+
+impl Versioned for ValueRow {
+    fn start_commit_num(&self) -> i64 {
+        self.value.start_commit_num
+    }
+
+    fn end_commit_num(&self) -> i64 {
+        self.value.end_commit_num
+    }
+
+    fn set_end_commit_num(&mut self, end_commit_num: i64) {
+        self.value.end_commit_num = end_commit_num;
+    }
+}
+
+fn value_rows(values: &[PropertyValue], parent_property: Option<&str>, rows: &mut Vec<ValueRow>) {
+    for value in values {
+        rows.push(ValueRow {
+            parent_property: parent_property.map(String::from),
+            value: PropertyValue {
+                struct_values: Vec::new(),
+                end_commit_num: MAX_COMMIT_NUM,
+                ..value.clone()
+            },
+        });
+        value_rows(&value.struct_values, Some(&value.property_name), rows);
+    }
+}
+
+#[derive(Default)]
+struct ProductState {
+    products: Vec<Product>,
+    values: Vec<ValueRow>,
+}
+
+impl ProductState {
+    fn values_at<'a>(
+        &'a self,
+        product_id: &'a str,
+        height: i64,
+        service_id: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a ValueRow> + 'a {
+        self.values.iter().filter(move |row| {
+            row.value.product_id == product_id
+                && row.value.service_id.as_deref() == service_id
+                && row.is_current_at(height)
+        })
+    }
+
+    fn struct_values(
+        &self,
+        product_id: &str,
+        parent_property: Option<&str>,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> Vec<PropertyValue> {
+        self.values_at(product_id, height, service_id)
+            .filter(|row| row.parent_property.as_deref() == parent_property)
+            .map(|row| PropertyValue {
+                struct_values: self.struct_values(
+                    product_id,
+                    Some(&row.value.property_name),
+                    height,
+                    service_id,
+                ),
+                ..row.value.clone()
+            })
+            .collect()
+    }
+
+    fn product_at(&self, product: &Product, height: i64, service_id: Option<&str>) -> Product {
+        Product {
+            properties: self.struct_values(&product.product_id, None, height, service_id),
+            ..product.clone()
+        }
+    }
+
+    fn fetch_product(
+        &self,
+        product_id: &str,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> Option<Product> {
+        self.products
+            .iter()
+            .find(|product| {
+                product.product_id == product_id
+                    && product.service_id.as_deref() == service_id
+                    && product.is_current_at(height)
+            })
+            .map(|product| self.product_at(product, height, service_id))
+    }
+
+    fn meets_filter(
+        &self,
+        product: &Product,
+        filter: &Filter,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> bool {
+        match filter {
+            Filter::Owner(owner) => owner.matches(&product.owner),
+            Filter::Namespace(namespace) => namespace.matches(&product.product_namespace),
+            Filter::Property { name, value } => self
+                .values_at(&product.product_id, height, service_id)
+                .filter(|row| row.parent_property.is_none() && &row.value.property_name == name)
+                .any(|row| {
+                    value.matches(
+                        row.value.string_value.as_deref(),
+                        row.value.number_value,
+                        row.value.enum_value,
+                        row.value.boolean_value,
+                    )
+                }),
+        }
+    }
+}
+
+/// Implementation of `ProductStore` that keeps products in memory, versioned by commit like the
+/// database-backed store. Useful for tests and when persistence isn't necessary.
+#[derive(Clone, Default)]
+pub struct MemoryProductStore {
+    inner: Arc<Mutex<ProductState>>,
+}
+
+impl MemoryProductStore {
+    pub fn new() -> Self {
+        MemoryProductStore::default()
+    }
+
+    /// Rolls the products back to their state before the given commit
+    ///
+    /// # Arguments
+    ///
+    ///  * `commit_num` - The number of the first commit that is no longer on the chain
+    pub fn resolve_fork(&self, commit_num: i64) -> Result<(), ProductStoreError> {
+        let mut state = self.state()?;
+        resolve_fork_rows(&mut state.products, commit_num);
+        resolve_fork_rows(&mut state.values, commit_num);
+        Ok(())
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, ProductState>, ProductStoreError> {
+        self.inner.lock().map_err(|_| {
+            ProductStoreError::InternalError(InternalError::with_message(
+                "Cannot access products: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+impl ProductStore for MemoryProductStore {
+    fn add_product(&self, product: Product) -> Result<(), ProductStoreError> {
+        let mut state = self.state()?;
+
+        let start_commit_num = product.start_commit_num;
+        end_current_rows(&mut state.products, start_commit_num, |current| {
+            current.product_id == product.product_id && current.service_id == product.service_id
+        });
+        end_current_rows(&mut state.values, start_commit_num, |row| {
+            row.value.product_id == product.product_id && row.value.service_id == product.service_id
+        });
+
+        value_rows(&product.properties, None, &mut state.values);
+        state.products.push(Product {
+            properties: Vec::new(),
+            ..product
+        });
+        Ok(())
+    }
+
+    fn fetch_product(
+        &self,
+        product_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Option<Product>, ProductStoreError> {
+        let height = commit_height_or_current(commit_height);
+        Ok(self.state()?.fetch_product(product_id, height, service_id))
+    }
+
+    fn list_products(
+        &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<ProductList, ProductStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let mut products = state
+            .products
+            .iter()
+            .filter(|product| {
+                product.service_id.as_deref() == service_id
+                    && product.is_current_at(height)
+                    && filter
+                        .filters
+                        .iter()
+                        .all(|condition| state.meets_filter(product, condition, height, service_id))
+            })
+            .collect::<Vec<_>>();
+
+        // Pages read by offset are in the sort order of the filter, while `page_items` reads
+        // pages by key in ID order
+        let sort_key = |product: &Product| match filter.sort.field {
+            SortField::Id => product.product_id.clone(),
+            SortField::Owner => product.owner.clone(),
+            SortField::Namespace => product.product_namespace.clone(),
+        };
+        products.sort_by(|a, b| {
+            let order = sort_key(a).cmp(&sort_key(b));
+            let order = match filter.sort.order {
+                SortOrder::Ascending => order,
+                SortOrder::Descending => order.reverse(),
+            };
+            order.then_with(|| a.product_id.cmp(&b.product_id))
+        });
+
+        let (products, paging) = page_items(products, page, |product| product.product_id.clone());
+
+        Ok(ProductList::new(
+            products
+                .into_iter()
+                .map(|product| state.product_at(product, height, service_id))
+                .collect(),
+            paging,
+        ))
+    }
+
+    fn list_product_history(
+        &self,
+        product_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError> {
+        let state = self.state()?;
+
+        let mut start_commit_nums = state
+            .products
+            .iter()
+            .filter(|product| {
+                product.product_id == product_id && product.service_id.as_deref() == service_id
+            })
+            .map(|product| product.start_commit_num)
+            .collect::<Vec<_>>();
+        start_commit_nums.sort_unstable();
+
+        Ok(start_commit_nums
+            .into_iter()
+            .filter_map(|start_commit_num| {
+                state.fetch_product(product_id, start_commit_num, service_id)
+            })
+            .collect())
+    }
+
+    fn update_product(
+        &self,
+        product_id: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), ProductStoreError> {
+        let mut state = self.state()?;
+        end_current_rows(&mut state.values, current_commit_num, |row| {
+            row.value.product_id == product_id && row.value.service_id.as_deref() == service_id
+        });
+        Ok(())
+    }
+
+    fn delete_product(
+        &self,
+        address: &str,
+        current_commit_num: i64,
+    ) -> Result<(), ProductStoreError> {
+        let mut state = self.state()?;
+        end_current_rows(&mut state.products, current_commit_num, |product| {
+            product.product_address == address
+        });
+        end_current_rows(&mut state.values, current_commit_num, |row| {
+            row.value.product_address == address
+        });
+        Ok(())
+    }
+}
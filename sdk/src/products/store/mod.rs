@@ -15,8 +15,11 @@
 #[cfg(feature = "diesel")]
 pub mod diesel;
 pub mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
 
-use crate::paging::Paging;
+use crate::filter::ListFilter;
+use crate::paging::{PageRequest, Paging};
 
 pub use error::ProductStoreError;
 
@@ -74,16 +77,24 @@ pub trait ProductStore: Send + Sync {
     fn fetch_product(
         &self,
         product_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError>;
 
     fn list_products(
         &self,
+        commit_height: Option<i64>,
+        filter: &ListFilter,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<ProductList, ProductStoreError>;
 
+    fn list_product_history(
+        &self,
+        product_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError>;
+
     fn update_product(
         &self,
         product_id: &str,
@@ -16,5 +16,6 @@ pub mod errors;
 pub mod location;
 pub mod pike;
 pub mod product;
+pub mod purchase_order;
 pub mod schema;
 pub mod track_and_trace;
@@ -0,0 +1,938 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use protobuf::Message;
+
+use super::errors::BuilderError;
+
+use crate::protos::{purchase_order_payload, purchase_order_payload::PurchaseOrderPayload_Action};
+use crate::protos::{
+    FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    CreatePo(CreatePurchaseOrderPayload),
+    UpdatePo(UpdatePurchaseOrderPayload),
+    CreateVersion(CreateVersionPayload),
+    UpdateVersion(UpdateVersionPayload),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseOrderPayload {
+    action: Action,
+    timestamp: u64,
+}
+
+impl PurchaseOrderPayload {
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
+    }
+}
+
+impl FromProto<purchase_order_payload::PurchaseOrderPayload> for PurchaseOrderPayload {
+    fn from_proto(
+        payload: purchase_order_payload::PurchaseOrderPayload,
+    ) -> Result<Self, ProtoConversionError> {
+        let action = match payload.get_action() {
+            PurchaseOrderPayload_Action::CREATE_PO => Action::CreatePo(
+                CreatePurchaseOrderPayload::from_proto(payload.get_create_po_payload().clone())?,
+            ),
+            PurchaseOrderPayload_Action::UPDATE_PO => Action::UpdatePo(
+                UpdatePurchaseOrderPayload::from_proto(payload.get_update_po_payload().clone())?,
+            ),
+            PurchaseOrderPayload_Action::CREATE_VERSION => Action::CreateVersion(
+                CreateVersionPayload::from_proto(payload.get_create_version_payload().clone())?,
+            ),
+            PurchaseOrderPayload_Action::UPDATE_VERSION => Action::UpdateVersion(
+                UpdateVersionPayload::from_proto(payload.get_update_version_payload().clone())?,
+            ),
+            PurchaseOrderPayload_Action::UNSET_ACTION => {
+                return Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert PurchaseOrderPayload_Action with type unset".to_string(),
+                ));
+            }
+        };
+
+        Ok(PurchaseOrderPayload {
+            action,
+            timestamp: payload.get_timestamp(),
+        })
+    }
+}
+
+impl FromNative<PurchaseOrderPayload> for purchase_order_payload::PurchaseOrderPayload {
+    fn from_native(native: PurchaseOrderPayload) -> Result<Self, ProtoConversionError> {
+        let mut proto = purchase_order_payload::PurchaseOrderPayload::new();
+
+        proto.set_timestamp(*native.timestamp());
+
+        match native.action() {
+            Action::CreatePo(payload) => {
+                proto.set_action(PurchaseOrderPayload_Action::CREATE_PO);
+                proto.set_create_po_payload(payload.clone().into_proto()?);
+            }
+            Action::UpdatePo(payload) => {
+                proto.set_action(PurchaseOrderPayload_Action::UPDATE_PO);
+                proto.set_update_po_payload(payload.clone().into_proto()?);
+            }
+            Action::CreateVersion(payload) => {
+                proto.set_action(PurchaseOrderPayload_Action::CREATE_VERSION);
+                proto.set_create_version_payload(payload.clone().into_proto()?);
+            }
+            Action::UpdateVersion(payload) => {
+                proto.set_action(PurchaseOrderPayload_Action::UPDATE_VERSION);
+                proto.set_update_version_payload(payload.clone().into_proto()?);
+            }
+        }
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<PurchaseOrderPayload> for PurchaseOrderPayload {
+    fn from_bytes(bytes: &[u8]) -> Result<PurchaseOrderPayload, ProtoConversionError> {
+        let proto: purchase_order_payload::PurchaseOrderPayload = Message::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get PurchaseOrderPayload from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for PurchaseOrderPayload {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from PurchaseOrderPayload".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<purchase_order_payload::PurchaseOrderPayload> for PurchaseOrderPayload {}
+impl IntoNative<PurchaseOrderPayload> for purchase_order_payload::PurchaseOrderPayload {}
+
+#[derive(Default, Clone)]
+pub struct PurchaseOrderPayloadBuilder {
+    action: Option<Action>,
+    timestamp: Option<u64>,
+}
+
+impl PurchaseOrderPayloadBuilder {
+    pub fn new() -> Self {
+        PurchaseOrderPayloadBuilder::default()
+    }
+
+    pub fn with_action(mut self, action: Action) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn with_timestamp(mut self, value: u64) -> Self {
+        self.timestamp = Some(value);
+        self
+    }
+
+    pub fn build(self) -> Result<PurchaseOrderPayload, BuilderError> {
+        let action = self
+            .action
+            .ok_or_else(|| BuilderError::MissingField("'action' field is required".into()))?;
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| BuilderError::MissingField("'timestamp' field is required".into()))?;
+        Ok(PurchaseOrderPayload { action, timestamp })
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CreatePurchaseOrderPayload {
+    uid: String,
+    created_at: u64,
+    buyer_org_id: String,
+    seller_org_id: String,
+    workflow_status: String,
+}
+
+impl CreatePurchaseOrderPayload {
+    pub fn uid(&self) -> &str {
+        &self.uid
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn buyer_org_id(&self) -> &str {
+        &self.buyer_org_id
+    }
+
+    pub fn seller_org_id(&self) -> &str {
+        &self.seller_org_id
+    }
+
+    pub fn workflow_status(&self) -> &str {
+        &self.workflow_status
+    }
+}
+
+impl FromProto<purchase_order_payload::CreatePurchaseOrderPayload> for CreatePurchaseOrderPayload {
+    fn from_proto(
+        proto: purchase_order_payload::CreatePurchaseOrderPayload,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(CreatePurchaseOrderPayload {
+            uid: proto.get_uid().to_string(),
+            created_at: proto.get_created_at(),
+            buyer_org_id: proto.get_buyer_org_id().to_string(),
+            seller_org_id: proto.get_seller_org_id().to_string(),
+            workflow_status: proto.get_workflow_status().to_string(),
+        })
+    }
+}
+
+impl FromNative<CreatePurchaseOrderPayload> for purchase_order_payload::CreatePurchaseOrderPayload {
+    fn from_native(native: CreatePurchaseOrderPayload) -> Result<Self, ProtoConversionError> {
+        let mut proto = purchase_order_payload::CreatePurchaseOrderPayload::new();
+        proto.set_uid(native.uid().to_string());
+        proto.set_created_at(native.created_at());
+        proto.set_buyer_org_id(native.buyer_org_id().to_string());
+        proto.set_seller_org_id(native.seller_org_id().to_string());
+        proto.set_workflow_status(native.workflow_status().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<CreatePurchaseOrderPayload> for CreatePurchaseOrderPayload {
+    fn from_bytes(bytes: &[u8]) -> Result<CreatePurchaseOrderPayload, ProtoConversionError> {
+        let proto: purchase_order_payload::CreatePurchaseOrderPayload =
+            Message::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get CreatePurchaseOrderPayload from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for CreatePurchaseOrderPayload {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from CreatePurchaseOrderPayload".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<purchase_order_payload::CreatePurchaseOrderPayload> for CreatePurchaseOrderPayload {}
+impl IntoNative<CreatePurchaseOrderPayload> for purchase_order_payload::CreatePurchaseOrderPayload {}
+
+#[derive(Default, Clone)]
+pub struct CreatePurchaseOrderPayloadBuilder {
+    uid: Option<String>,
+    created_at: Option<u64>,
+    buyer_org_id: Option<String>,
+    seller_org_id: Option<String>,
+    workflow_status: Option<String>,
+}
+
+impl CreatePurchaseOrderPayloadBuilder {
+    pub fn new() -> Self {
+        CreatePurchaseOrderPayloadBuilder::default()
+    }
+
+    pub fn with_uid(mut self, uid: String) -> Self {
+        self.uid = Some(uid);
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: u64) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    pub fn with_buyer_org_id(mut self, buyer_org_id: String) -> Self {
+        self.buyer_org_id = Some(buyer_org_id);
+        self
+    }
+
+    pub fn with_seller_org_id(mut self, seller_org_id: String) -> Self {
+        self.seller_org_id = Some(seller_org_id);
+        self
+    }
+
+    pub fn with_workflow_status(mut self, workflow_status: String) -> Self {
+        self.workflow_status = Some(workflow_status);
+        self
+    }
+
+    pub fn build(self) -> Result<CreatePurchaseOrderPayload, BuilderError> {
+        let uid = self
+            .uid
+            .ok_or_else(|| BuilderError::MissingField("'uid' field is required".to_string()))?;
+
+        let created_at = self.created_at.ok_or_else(|| {
+            BuilderError::MissingField("'created_at' field is required".to_string())
+        })?;
+
+        let buyer_org_id = self.buyer_org_id.ok_or_else(|| {
+            BuilderError::MissingField("'buyer_org_id' field is required".to_string())
+        })?;
+
+        let seller_org_id = self.seller_org_id.ok_or_else(|| {
+            BuilderError::MissingField("'seller_org_id' field is required".to_string())
+        })?;
+
+        let workflow_status = self.workflow_status.ok_or_else(|| {
+            BuilderError::MissingField("'workflow_status' field is required".to_string())
+        })?;
+
+        Ok(CreatePurchaseOrderPayload {
+            uid,
+            created_at,
+            buyer_org_id,
+            seller_org_id,
+            workflow_status,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UpdatePurchaseOrderPayload {
+    uid: String,
+    workflow_status: String,
+    is_closed: bool,
+    accepted_version_number: Option<String>,
+}
+
+impl UpdatePurchaseOrderPayload {
+    pub fn uid(&self) -> &str {
+        &self.uid
+    }
+
+    pub fn workflow_status(&self) -> &str {
+        &self.workflow_status
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.is_closed
+    }
+
+    pub fn accepted_version_number(&self) -> Option<&str> {
+        self.accepted_version_number.as_deref()
+    }
+}
+
+impl FromProto<purchase_order_payload::UpdatePurchaseOrderPayload> for UpdatePurchaseOrderPayload {
+    fn from_proto(
+        proto: purchase_order_payload::UpdatePurchaseOrderPayload,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(UpdatePurchaseOrderPayload {
+            uid: proto.get_uid().to_string(),
+            workflow_status: proto.get_workflow_status().to_string(),
+            is_closed: proto.get_is_closed(),
+            accepted_version_number: match proto.get_accepted_version_number() {
+                "" => None,
+                version => Some(version.to_string()),
+            },
+        })
+    }
+}
+
+impl FromNative<UpdatePurchaseOrderPayload> for purchase_order_payload::UpdatePurchaseOrderPayload {
+    fn from_native(native: UpdatePurchaseOrderPayload) -> Result<Self, ProtoConversionError> {
+        let mut proto = purchase_order_payload::UpdatePurchaseOrderPayload::new();
+        proto.set_uid(native.uid().to_string());
+        proto.set_workflow_status(native.workflow_status().to_string());
+        proto.set_is_closed(native.is_closed());
+        if let Some(accepted_version_number) = native.accepted_version_number() {
+            proto.set_accepted_version_number(accepted_version_number.to_string());
+        }
+        Ok(proto)
+    }
+}
+
+impl FromBytes<UpdatePurchaseOrderPayload> for UpdatePurchaseOrderPayload {
+    fn from_bytes(bytes: &[u8]) -> Result<UpdatePurchaseOrderPayload, ProtoConversionError> {
+        let proto: purchase_order_payload::UpdatePurchaseOrderPayload =
+            Message::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get UpdatePurchaseOrderPayload from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for UpdatePurchaseOrderPayload {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from UpdatePurchaseOrderPayload".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<purchase_order_payload::UpdatePurchaseOrderPayload> for UpdatePurchaseOrderPayload {}
+impl IntoNative<UpdatePurchaseOrderPayload> for purchase_order_payload::UpdatePurchaseOrderPayload {}
+
+#[derive(Default, Clone)]
+pub struct UpdatePurchaseOrderPayloadBuilder {
+    uid: Option<String>,
+    workflow_status: Option<String>,
+    is_closed: Option<bool>,
+    accepted_version_number: Option<String>,
+}
+
+impl UpdatePurchaseOrderPayloadBuilder {
+    pub fn new() -> Self {
+        UpdatePurchaseOrderPayloadBuilder::default()
+    }
+
+    pub fn with_uid(mut self, uid: String) -> Self {
+        self.uid = Some(uid);
+        self
+    }
+
+    pub fn with_workflow_status(mut self, workflow_status: String) -> Self {
+        self.workflow_status = Some(workflow_status);
+        self
+    }
+
+    pub fn with_is_closed(mut self, is_closed: bool) -> Self {
+        self.is_closed = Some(is_closed);
+        self
+    }
+
+    pub fn with_accepted_version_number(mut self, accepted_version_number: String) -> Self {
+        self.accepted_version_number = Some(accepted_version_number);
+        self
+    }
+
+    pub fn build(self) -> Result<UpdatePurchaseOrderPayload, BuilderError> {
+        let uid = self
+            .uid
+            .ok_or_else(|| BuilderError::MissingField("'uid' field is required".to_string()))?;
+
+        let workflow_status = self.workflow_status.ok_or_else(|| {
+            BuilderError::MissingField("'workflow_status' field is required".to_string())
+        })?;
+
+        Ok(UpdatePurchaseOrderPayload {
+            uid,
+            workflow_status,
+            is_closed: self.is_closed.unwrap_or(false),
+            accepted_version_number: self.accepted_version_number,
+        })
+    }
+}
+
+/// A revision submitted as part of a version payload
+///
+/// The submitter is not part of the payload; it is taken from the transaction signer.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PayloadRevision {
+    revision_id: u64,
+    created_at: u64,
+    order_xml_v3_4: String,
+}
+
+impl PayloadRevision {
+    pub fn revision_id(&self) -> u64 {
+        self.revision_id
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn order_xml_v3_4(&self) -> &str {
+        &self.order_xml_v3_4
+    }
+}
+
+impl FromProto<purchase_order_payload::PayloadRevision> for PayloadRevision {
+    fn from_proto(
+        proto: purchase_order_payload::PayloadRevision,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(PayloadRevision {
+            revision_id: proto.get_revision_id(),
+            created_at: proto.get_created_at(),
+            order_xml_v3_4: proto.get_order_xml_v3_4().to_string(),
+        })
+    }
+}
+
+impl FromNative<PayloadRevision> for purchase_order_payload::PayloadRevision {
+    fn from_native(native: PayloadRevision) -> Result<Self, ProtoConversionError> {
+        let mut proto = purchase_order_payload::PayloadRevision::new();
+        proto.set_revision_id(native.revision_id());
+        proto.set_created_at(native.created_at());
+        proto.set_order_xml_v3_4(native.order_xml_v3_4().to_string());
+        Ok(proto)
+    }
+}
+
+impl IntoProto<purchase_order_payload::PayloadRevision> for PayloadRevision {}
+impl IntoNative<PayloadRevision> for purchase_order_payload::PayloadRevision {}
+
+#[derive(Default, Clone)]
+pub struct PayloadRevisionBuilder {
+    revision_id: Option<u64>,
+    created_at: Option<u64>,
+    order_xml_v3_4: Option<String>,
+}
+
+impl PayloadRevisionBuilder {
+    pub fn new() -> Self {
+        PayloadRevisionBuilder::default()
+    }
+
+    pub fn with_revision_id(mut self, revision_id: u64) -> Self {
+        self.revision_id = Some(revision_id);
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: u64) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    pub fn with_order_xml_v3_4(mut self, order_xml_v3_4: String) -> Self {
+        self.order_xml_v3_4 = Some(order_xml_v3_4);
+        self
+    }
+
+    pub fn build(self) -> Result<PayloadRevision, BuilderError> {
+        let revision_id = self.revision_id.ok_or_else(|| {
+            BuilderError::MissingField("'revision_id' field is required".to_string())
+        })?;
+
+        let created_at = self.created_at.ok_or_else(|| {
+            BuilderError::MissingField("'created_at' field is required".to_string())
+        })?;
+
+        let order_xml_v3_4 = self.order_xml_v3_4.ok_or_else(|| {
+            BuilderError::MissingField("'order_xml_v3_4' field is required".to_string())
+        })?;
+
+        Ok(PayloadRevision {
+            revision_id,
+            created_at,
+            order_xml_v3_4,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CreateVersionPayload {
+    version_id: String,
+    po_uid: String,
+    is_draft: bool,
+    workflow_status: String,
+    revision: PayloadRevision,
+}
+
+impl CreateVersionPayload {
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    pub fn po_uid(&self) -> &str {
+        &self.po_uid
+    }
+
+    pub fn is_draft(&self) -> bool {
+        self.is_draft
+    }
+
+    pub fn workflow_status(&self) -> &str {
+        &self.workflow_status
+    }
+
+    pub fn revision(&self) -> &PayloadRevision {
+        &self.revision
+    }
+}
+
+impl FromProto<purchase_order_payload::CreateVersionPayload> for CreateVersionPayload {
+    fn from_proto(
+        proto: purchase_order_payload::CreateVersionPayload,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(CreateVersionPayload {
+            version_id: proto.get_version_id().to_string(),
+            po_uid: proto.get_po_uid().to_string(),
+            is_draft: proto.get_is_draft(),
+            workflow_status: proto.get_workflow_status().to_string(),
+            revision: PayloadRevision::from_proto(proto.get_revision().clone())?,
+        })
+    }
+}
+
+impl FromNative<CreateVersionPayload> for purchase_order_payload::CreateVersionPayload {
+    fn from_native(native: CreateVersionPayload) -> Result<Self, ProtoConversionError> {
+        let mut proto = purchase_order_payload::CreateVersionPayload::new();
+        proto.set_version_id(native.version_id().to_string());
+        proto.set_po_uid(native.po_uid().to_string());
+        proto.set_is_draft(native.is_draft());
+        proto.set_workflow_status(native.workflow_status().to_string());
+        proto.set_revision(native.revision().clone().into_proto()?);
+        Ok(proto)
+    }
+}
+
+impl FromBytes<CreateVersionPayload> for CreateVersionPayload {
+    fn from_bytes(bytes: &[u8]) -> Result<CreateVersionPayload, ProtoConversionError> {
+        let proto: purchase_order_payload::CreateVersionPayload = Message::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get CreateVersionPayload from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for CreateVersionPayload {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from CreateVersionPayload".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<purchase_order_payload::CreateVersionPayload> for CreateVersionPayload {}
+impl IntoNative<CreateVersionPayload> for purchase_order_payload::CreateVersionPayload {}
+
+#[derive(Default, Clone)]
+pub struct CreateVersionPayloadBuilder {
+    version_id: Option<String>,
+    po_uid: Option<String>,
+    is_draft: Option<bool>,
+    workflow_status: Option<String>,
+    revision: Option<PayloadRevision>,
+}
+
+impl CreateVersionPayloadBuilder {
+    pub fn new() -> Self {
+        CreateVersionPayloadBuilder::default()
+    }
+
+    pub fn with_version_id(mut self, version_id: String) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+
+    pub fn with_po_uid(mut self, po_uid: String) -> Self {
+        self.po_uid = Some(po_uid);
+        self
+    }
+
+    pub fn with_is_draft(mut self, is_draft: bool) -> Self {
+        self.is_draft = Some(is_draft);
+        self
+    }
+
+    pub fn with_workflow_status(mut self, workflow_status: String) -> Self {
+        self.workflow_status = Some(workflow_status);
+        self
+    }
+
+    pub fn with_revision(mut self, revision: PayloadRevision) -> Self {
+        self.revision = Some(revision);
+        self
+    }
+
+    pub fn build(self) -> Result<CreateVersionPayload, BuilderError> {
+        let version_id = self.version_id.ok_or_else(|| {
+            BuilderError::MissingField("'version_id' field is required".to_string())
+        })?;
+
+        let po_uid = self
+            .po_uid
+            .ok_or_else(|| BuilderError::MissingField("'po_uid' field is required".to_string()))?;
+
+        let workflow_status = self.workflow_status.ok_or_else(|| {
+            BuilderError::MissingField("'workflow_status' field is required".to_string())
+        })?;
+
+        let revision = self.revision.ok_or_else(|| {
+            BuilderError::MissingField("'revision' field is required".to_string())
+        })?;
+
+        Ok(CreateVersionPayload {
+            version_id,
+            po_uid,
+            is_draft: self.is_draft.unwrap_or(false),
+            workflow_status,
+            revision,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UpdateVersionPayload {
+    version_id: String,
+    po_uid: String,
+    workflow_status: String,
+    is_draft: bool,
+    revision: Option<PayloadRevision>,
+}
+
+impl UpdateVersionPayload {
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    pub fn po_uid(&self) -> &str {
+        &self.po_uid
+    }
+
+    pub fn workflow_status(&self) -> &str {
+        &self.workflow_status
+    }
+
+    pub fn is_draft(&self) -> bool {
+        self.is_draft
+    }
+
+    pub fn revision(&self) -> Option<&PayloadRevision> {
+        self.revision.as_ref()
+    }
+}
+
+impl FromProto<purchase_order_payload::UpdateVersionPayload> for UpdateVersionPayload {
+    fn from_proto(
+        proto: purchase_order_payload::UpdateVersionPayload,
+    ) -> Result<Self, ProtoConversionError> {
+        let revision = if proto.has_revision() {
+            Some(PayloadRevision::from_proto(proto.get_revision().clone())?)
+        } else {
+            None
+        };
+
+        Ok(UpdateVersionPayload {
+            version_id: proto.get_version_id().to_string(),
+            po_uid: proto.get_po_uid().to_string(),
+            workflow_status: proto.get_workflow_status().to_string(),
+            is_draft: proto.get_is_draft(),
+            revision,
+        })
+    }
+}
+
+impl FromNative<UpdateVersionPayload> for purchase_order_payload::UpdateVersionPayload {
+    fn from_native(native: UpdateVersionPayload) -> Result<Self, ProtoConversionError> {
+        let mut proto = purchase_order_payload::UpdateVersionPayload::new();
+        proto.set_version_id(native.version_id().to_string());
+        proto.set_po_uid(native.po_uid().to_string());
+        proto.set_workflow_status(native.workflow_status().to_string());
+        proto.set_is_draft(native.is_draft());
+        if let Some(revision) = native.revision() {
+            proto.set_revision(revision.clone().into_proto()?);
+        }
+        Ok(proto)
+    }
+}
+
+impl FromBytes<UpdateVersionPayload> for UpdateVersionPayload {
+    fn from_bytes(bytes: &[u8]) -> Result<UpdateVersionPayload, ProtoConversionError> {
+        let proto: purchase_order_payload::UpdateVersionPayload = Message::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get UpdateVersionPayload from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for UpdateVersionPayload {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from UpdateVersionPayload".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<purchase_order_payload::UpdateVersionPayload> for UpdateVersionPayload {}
+impl IntoNative<UpdateVersionPayload> for purchase_order_payload::UpdateVersionPayload {}
+
+#[derive(Default, Clone)]
+pub struct UpdateVersionPayloadBuilder {
+    version_id: Option<String>,
+    po_uid: Option<String>,
+    workflow_status: Option<String>,
+    is_draft: Option<bool>,
+    revision: Option<PayloadRevision>,
+}
+
+impl UpdateVersionPayloadBuilder {
+    pub fn new() -> Self {
+        UpdateVersionPayloadBuilder::default()
+    }
+
+    pub fn with_version_id(mut self, version_id: String) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+
+    pub fn with_po_uid(mut self, po_uid: String) -> Self {
+        self.po_uid = Some(po_uid);
+        self
+    }
+
+    pub fn with_workflow_status(mut self, workflow_status: String) -> Self {
+        self.workflow_status = Some(workflow_status);
+        self
+    }
+
+    pub fn with_is_draft(mut self, is_draft: bool) -> Self {
+        self.is_draft = Some(is_draft);
+        self
+    }
+
+    pub fn with_revision(mut self, revision: PayloadRevision) -> Self {
+        self.revision = Some(revision);
+        self
+    }
+
+    pub fn build(self) -> Result<UpdateVersionPayload, BuilderError> {
+        let version_id = self.version_id.ok_or_else(|| {
+            BuilderError::MissingField("'version_id' field is required".to_string())
+        })?;
+
+        let po_uid = self
+            .po_uid
+            .ok_or_else(|| BuilderError::MissingField("'po_uid' field is required".to_string()))?;
+
+        let workflow_status = self.workflow_status.ok_or_else(|| {
+            BuilderError::MissingField("'workflow_status' field is required".to_string())
+        })?;
+
+        Ok(UpdateVersionPayload {
+            version_id,
+            po_uid,
+            workflow_status,
+            is_draft: self.is_draft.unwrap_or(false),
+            revision: self.revision,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test that a create purchase order payload can be converted to bytes and back
+    fn test_create_po_payload_into_bytes() {
+        let action = CreatePurchaseOrderPayloadBuilder::new()
+            .with_uid("PO-00000001".into())
+            .with_created_at(1)
+            .with_buyer_org_id("buyer".into())
+            .with_seller_org_id("seller".into())
+            .with_workflow_status("issued".into())
+            .build()
+            .unwrap();
+
+        let payload = PurchaseOrderPayloadBuilder::new()
+            .with_action(Action::CreatePo(action))
+            .with_timestamp(1)
+            .build()
+            .unwrap();
+
+        let bytes = payload.clone().into_bytes().unwrap();
+        assert_eq!(payload, PurchaseOrderPayload::from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    // Test that an update version payload keeps an optional revision across a bytes round trip
+    fn test_update_version_payload_into_bytes() {
+        let without_revision = UpdateVersionPayloadBuilder::new()
+            .with_version_id("1".into())
+            .with_po_uid("PO-00000001".into())
+            .with_workflow_status("accepted".into())
+            .build()
+            .unwrap();
+
+        let bytes = without_revision.clone().into_bytes().unwrap();
+        let from_bytes = UpdateVersionPayload::from_bytes(&bytes).unwrap();
+        assert_eq!(without_revision, from_bytes);
+        assert!(from_bytes.revision().is_none());
+
+        let revision = PayloadRevisionBuilder::new()
+            .with_revision_id(2)
+            .with_created_at(2)
+            .with_order_xml_v3_4("<order/>".into())
+            .build()
+            .unwrap();
+        let with_revision = UpdateVersionPayloadBuilder::new()
+            .with_version_id("1".into())
+            .with_po_uid("PO-00000001".into())
+            .with_workflow_status("proposed".into())
+            .with_is_draft(true)
+            .with_revision(revision.clone())
+            .build()
+            .unwrap();
+
+        let bytes = with_revision.clone().into_bytes().unwrap();
+        let from_bytes = UpdateVersionPayload::from_bytes(&bytes).unwrap();
+        assert_eq!(with_revision, from_bytes);
+        assert_eq!(from_bytes.revision(), Some(&revision));
+    }
+
+    #[test]
+    // Test that a create version payload requires a revision
+    fn test_create_version_payload_missing_revision() {
+        let result = CreateVersionPayloadBuilder::new()
+            .with_version_id("1".into())
+            .with_po_uid("PO-00000001".into())
+            .with_workflow_status("proposed".into())
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    // Test that a payload with an unset action cannot be converted
+    fn test_unset_action() {
+        let mut proto = purchase_order_payload::PurchaseOrderPayload::new();
+        proto.set_timestamp(1);
+        let bytes = proto.write_to_bytes().unwrap();
+
+        assert!(PurchaseOrderPayload::from_bytes(&bytes).is_err());
+    }
+}
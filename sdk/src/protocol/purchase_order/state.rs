@@ -0,0 +1,799 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use protobuf::Message;
+use protobuf::RepeatedField;
+
+use super::errors::BuilderError;
+
+use crate::protos;
+use crate::protos::{
+    FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
+};
+
+/// Native representation of a purchase order revision
+///
+/// A revision holds a single submitted copy of the order document. Revisions are never modified
+/// once they are added to a version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseOrderRevision {
+    revision_id: u64,
+    submitter: String,
+    created_at: u64,
+    order_xml_v3_4: String,
+}
+
+impl PurchaseOrderRevision {
+    pub fn revision_id(&self) -> u64 {
+        self.revision_id
+    }
+
+    pub fn submitter(&self) -> &str {
+        &self.submitter
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn order_xml_v3_4(&self) -> &str {
+        &self.order_xml_v3_4
+    }
+
+    pub fn into_builder(self) -> PurchaseOrderRevisionBuilder {
+        PurchaseOrderRevisionBuilder::new()
+            .with_revision_id(self.revision_id)
+            .with_submitter(self.submitter)
+            .with_created_at(self.created_at)
+            .with_order_xml_v3_4(self.order_xml_v3_4)
+    }
+}
+
+impl FromProto<protos::purchase_order_state::PurchaseOrderRevision> for PurchaseOrderRevision {
+    fn from_proto(
+        proto: protos::purchase_order_state::PurchaseOrderRevision,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(PurchaseOrderRevision {
+            revision_id: proto.get_revision_id(),
+            submitter: proto.get_submitter().to_string(),
+            created_at: proto.get_created_at(),
+            order_xml_v3_4: proto.get_order_xml_v3_4().to_string(),
+        })
+    }
+}
+
+impl FromNative<PurchaseOrderRevision> for protos::purchase_order_state::PurchaseOrderRevision {
+    fn from_native(native: PurchaseOrderRevision) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::purchase_order_state::PurchaseOrderRevision::new();
+        proto.set_revision_id(native.revision_id());
+        proto.set_submitter(native.submitter().to_string());
+        proto.set_created_at(native.created_at());
+        proto.set_order_xml_v3_4(native.order_xml_v3_4().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<PurchaseOrderRevision> for PurchaseOrderRevision {
+    fn from_bytes(bytes: &[u8]) -> Result<PurchaseOrderRevision, ProtoConversionError> {
+        let proto: protos::purchase_order_state::PurchaseOrderRevision =
+            Message::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get PurchaseOrderRevision from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for PurchaseOrderRevision {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from PurchaseOrderRevision".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::purchase_order_state::PurchaseOrderRevision> for PurchaseOrderRevision {}
+impl IntoNative<PurchaseOrderRevision> for protos::purchase_order_state::PurchaseOrderRevision {}
+
+#[derive(Default, Clone)]
+pub struct PurchaseOrderRevisionBuilder {
+    revision_id: Option<u64>,
+    submitter: Option<String>,
+    created_at: Option<u64>,
+    order_xml_v3_4: Option<String>,
+}
+
+impl PurchaseOrderRevisionBuilder {
+    pub fn new() -> Self {
+        PurchaseOrderRevisionBuilder::default()
+    }
+
+    pub fn with_revision_id(mut self, revision_id: u64) -> Self {
+        self.revision_id = Some(revision_id);
+        self
+    }
+
+    pub fn with_submitter(mut self, submitter: String) -> Self {
+        self.submitter = Some(submitter);
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: u64) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    pub fn with_order_xml_v3_4(mut self, order_xml_v3_4: String) -> Self {
+        self.order_xml_v3_4 = Some(order_xml_v3_4);
+        self
+    }
+
+    pub fn build(self) -> Result<PurchaseOrderRevision, BuilderError> {
+        let revision_id = self.revision_id.ok_or_else(|| {
+            BuilderError::MissingField("'revision_id' field is required".to_string())
+        })?;
+
+        let submitter = self.submitter.ok_or_else(|| {
+            BuilderError::MissingField("'submitter' field is required".to_string())
+        })?;
+
+        let created_at = self.created_at.ok_or_else(|| {
+            BuilderError::MissingField("'created_at' field is required".to_string())
+        })?;
+
+        let order_xml_v3_4 = self.order_xml_v3_4.ok_or_else(|| {
+            BuilderError::MissingField("'order_xml_v3_4' field is required".to_string())
+        })?;
+
+        Ok(PurchaseOrderRevision {
+            revision_id,
+            submitter,
+            created_at,
+            order_xml_v3_4,
+        })
+    }
+}
+
+/// Native representation of a purchase order version
+///
+/// Each version moves through the "version" subworkflow independently of the purchase order it
+/// belongs to, and keeps every revision submitted for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseOrderVersion {
+    version_id: String,
+    workflow_status: String,
+    is_draft: bool,
+    current_revision_id: u64,
+    revisions: Vec<PurchaseOrderRevision>,
+}
+
+impl PurchaseOrderVersion {
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    pub fn workflow_status(&self) -> &str {
+        &self.workflow_status
+    }
+
+    pub fn is_draft(&self) -> bool {
+        self.is_draft
+    }
+
+    pub fn current_revision_id(&self) -> u64 {
+        self.current_revision_id
+    }
+
+    pub fn revisions(&self) -> &[PurchaseOrderRevision] {
+        &self.revisions
+    }
+
+    pub fn into_builder(self) -> PurchaseOrderVersionBuilder {
+        PurchaseOrderVersionBuilder::new()
+            .with_version_id(self.version_id)
+            .with_workflow_status(self.workflow_status)
+            .with_is_draft(self.is_draft)
+            .with_current_revision_id(self.current_revision_id)
+            .with_revisions(self.revisions)
+    }
+}
+
+impl FromProto<protos::purchase_order_state::PurchaseOrderVersion> for PurchaseOrderVersion {
+    fn from_proto(
+        proto: protos::purchase_order_state::PurchaseOrderVersion,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(PurchaseOrderVersion {
+            version_id: proto.get_version_id().to_string(),
+            workflow_status: proto.get_workflow_status().to_string(),
+            is_draft: proto.get_is_draft(),
+            current_revision_id: proto.get_current_revision_id(),
+            revisions: proto
+                .get_revisions()
+                .to_vec()
+                .into_iter()
+                .map(PurchaseOrderRevision::from_proto)
+                .collect::<Result<Vec<PurchaseOrderRevision>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<PurchaseOrderVersion> for protos::purchase_order_state::PurchaseOrderVersion {
+    fn from_native(native: PurchaseOrderVersion) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::purchase_order_state::PurchaseOrderVersion::new();
+        proto.set_version_id(native.version_id().to_string());
+        proto.set_workflow_status(native.workflow_status().to_string());
+        proto.set_is_draft(native.is_draft());
+        proto.set_current_revision_id(native.current_revision_id());
+        proto.set_revisions(RepeatedField::from_vec(
+            native
+                .revisions()
+                .to_vec()
+                .into_iter()
+                .map(PurchaseOrderRevision::into_proto)
+                .collect::<Result<
+                    Vec<protos::purchase_order_state::PurchaseOrderRevision>,
+                    ProtoConversionError,
+                >>()?,
+        ));
+        Ok(proto)
+    }
+}
+
+impl FromBytes<PurchaseOrderVersion> for PurchaseOrderVersion {
+    fn from_bytes(bytes: &[u8]) -> Result<PurchaseOrderVersion, ProtoConversionError> {
+        let proto: protos::purchase_order_state::PurchaseOrderVersion =
+            Message::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get PurchaseOrderVersion from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for PurchaseOrderVersion {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from PurchaseOrderVersion".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::purchase_order_state::PurchaseOrderVersion> for PurchaseOrderVersion {}
+impl IntoNative<PurchaseOrderVersion> for protos::purchase_order_state::PurchaseOrderVersion {}
+
+#[derive(Default, Clone)]
+pub struct PurchaseOrderVersionBuilder {
+    version_id: Option<String>,
+    workflow_status: Option<String>,
+    is_draft: Option<bool>,
+    current_revision_id: Option<u64>,
+    revisions: Vec<PurchaseOrderRevision>,
+}
+
+impl PurchaseOrderVersionBuilder {
+    pub fn new() -> Self {
+        PurchaseOrderVersionBuilder::default()
+    }
+
+    pub fn with_version_id(mut self, version_id: String) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+
+    pub fn with_workflow_status(mut self, workflow_status: String) -> Self {
+        self.workflow_status = Some(workflow_status);
+        self
+    }
+
+    pub fn with_is_draft(mut self, is_draft: bool) -> Self {
+        self.is_draft = Some(is_draft);
+        self
+    }
+
+    pub fn with_current_revision_id(mut self, current_revision_id: u64) -> Self {
+        self.current_revision_id = Some(current_revision_id);
+        self
+    }
+
+    pub fn with_revisions(mut self, revisions: Vec<PurchaseOrderRevision>) -> Self {
+        self.revisions = revisions;
+        self
+    }
+
+    pub fn build(self) -> Result<PurchaseOrderVersion, BuilderError> {
+        let version_id = self.version_id.ok_or_else(|| {
+            BuilderError::MissingField("'version_id' field is required".to_string())
+        })?;
+
+        let workflow_status = self.workflow_status.ok_or_else(|| {
+            BuilderError::MissingField("'workflow_status' field is required".to_string())
+        })?;
+
+        let is_draft = self.is_draft.unwrap_or(false);
+
+        let current_revision_id = self.current_revision_id.ok_or_else(|| {
+            BuilderError::MissingField("'current_revision_id' field is required".to_string())
+        })?;
+
+        Ok(PurchaseOrderVersion {
+            version_id,
+            workflow_status,
+            is_draft,
+            current_revision_id,
+            revisions: self.revisions,
+        })
+    }
+}
+
+/// Native representation of a purchase order
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseOrder {
+    uid: String,
+    workflow_status: String,
+    versions: Vec<PurchaseOrderVersion>,
+    accepted_version_number: Option<String>,
+    created_at: u64,
+    is_closed: bool,
+    buyer_org_id: String,
+    seller_org_id: String,
+}
+
+impl PurchaseOrder {
+    pub fn uid(&self) -> &str {
+        &self.uid
+    }
+
+    pub fn workflow_status(&self) -> &str {
+        &self.workflow_status
+    }
+
+    pub fn versions(&self) -> &[PurchaseOrderVersion] {
+        &self.versions
+    }
+
+    pub fn accepted_version_number(&self) -> Option<&str> {
+        self.accepted_version_number.as_deref()
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.is_closed
+    }
+
+    pub fn buyer_org_id(&self) -> &str {
+        &self.buyer_org_id
+    }
+
+    pub fn seller_org_id(&self) -> &str {
+        &self.seller_org_id
+    }
+
+    pub fn into_builder(self) -> PurchaseOrderBuilder {
+        let builder = PurchaseOrderBuilder::new()
+            .with_uid(self.uid)
+            .with_workflow_status(self.workflow_status)
+            .with_versions(self.versions)
+            .with_created_at(self.created_at)
+            .with_is_closed(self.is_closed)
+            .with_buyer_org_id(self.buyer_org_id)
+            .with_seller_org_id(self.seller_org_id);
+
+        match self.accepted_version_number {
+            Some(accepted_version_number) => {
+                builder.with_accepted_version_number(accepted_version_number)
+            }
+            None => builder,
+        }
+    }
+}
+
+impl FromProto<protos::purchase_order_state::PurchaseOrder> for PurchaseOrder {
+    fn from_proto(
+        proto: protos::purchase_order_state::PurchaseOrder,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(PurchaseOrder {
+            uid: proto.get_uid().to_string(),
+            workflow_status: proto.get_workflow_status().to_string(),
+            versions: proto
+                .get_versions()
+                .to_vec()
+                .into_iter()
+                .map(PurchaseOrderVersion::from_proto)
+                .collect::<Result<Vec<PurchaseOrderVersion>, ProtoConversionError>>()?,
+            accepted_version_number: match proto.get_accepted_version_number() {
+                "" => None,
+                version => Some(version.to_string()),
+            },
+            created_at: proto.get_created_at(),
+            is_closed: proto.get_is_closed(),
+            buyer_org_id: proto.get_buyer_org_id().to_string(),
+            seller_org_id: proto.get_seller_org_id().to_string(),
+        })
+    }
+}
+
+impl FromNative<PurchaseOrder> for protos::purchase_order_state::PurchaseOrder {
+    fn from_native(native: PurchaseOrder) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::purchase_order_state::PurchaseOrder::new();
+        proto.set_uid(native.uid().to_string());
+        proto.set_workflow_status(native.workflow_status().to_string());
+        proto.set_versions(RepeatedField::from_vec(
+            native
+                .versions()
+                .to_vec()
+                .into_iter()
+                .map(PurchaseOrderVersion::into_proto)
+                .collect::<Result<
+                    Vec<protos::purchase_order_state::PurchaseOrderVersion>,
+                    ProtoConversionError,
+                >>()?,
+        ));
+        if let Some(accepted_version_number) = native.accepted_version_number() {
+            proto.set_accepted_version_number(accepted_version_number.to_string());
+        }
+        proto.set_created_at(native.created_at());
+        proto.set_is_closed(native.is_closed());
+        proto.set_buyer_org_id(native.buyer_org_id().to_string());
+        proto.set_seller_org_id(native.seller_org_id().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<PurchaseOrder> for PurchaseOrder {
+    fn from_bytes(bytes: &[u8]) -> Result<PurchaseOrder, ProtoConversionError> {
+        let proto: protos::purchase_order_state::PurchaseOrder = Message::parse_from_bytes(bytes)
+            .map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get PurchaseOrder from bytes".to_string(),
+            )
+        })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for PurchaseOrder {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from PurchaseOrder".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::purchase_order_state::PurchaseOrder> for PurchaseOrder {}
+impl IntoNative<PurchaseOrder> for protos::purchase_order_state::PurchaseOrder {}
+
+#[derive(Default, Clone)]
+pub struct PurchaseOrderBuilder {
+    uid: Option<String>,
+    workflow_status: Option<String>,
+    versions: Vec<PurchaseOrderVersion>,
+    accepted_version_number: Option<String>,
+    created_at: Option<u64>,
+    is_closed: Option<bool>,
+    buyer_org_id: Option<String>,
+    seller_org_id: Option<String>,
+}
+
+impl PurchaseOrderBuilder {
+    pub fn new() -> Self {
+        PurchaseOrderBuilder::default()
+    }
+
+    pub fn with_uid(mut self, uid: String) -> Self {
+        self.uid = Some(uid);
+        self
+    }
+
+    pub fn with_workflow_status(mut self, workflow_status: String) -> Self {
+        self.workflow_status = Some(workflow_status);
+        self
+    }
+
+    pub fn with_versions(mut self, versions: Vec<PurchaseOrderVersion>) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    pub fn with_accepted_version_number(mut self, accepted_version_number: String) -> Self {
+        self.accepted_version_number = Some(accepted_version_number);
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: u64) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    pub fn with_is_closed(mut self, is_closed: bool) -> Self {
+        self.is_closed = Some(is_closed);
+        self
+    }
+
+    pub fn with_buyer_org_id(mut self, buyer_org_id: String) -> Self {
+        self.buyer_org_id = Some(buyer_org_id);
+        self
+    }
+
+    pub fn with_seller_org_id(mut self, seller_org_id: String) -> Self {
+        self.seller_org_id = Some(seller_org_id);
+        self
+    }
+
+    pub fn build(self) -> Result<PurchaseOrder, BuilderError> {
+        let uid = self
+            .uid
+            .ok_or_else(|| BuilderError::MissingField("'uid' field is required".to_string()))?;
+
+        let workflow_status = self.workflow_status.ok_or_else(|| {
+            BuilderError::MissingField("'workflow_status' field is required".to_string())
+        })?;
+
+        let created_at = self.created_at.ok_or_else(|| {
+            BuilderError::MissingField("'created_at' field is required".to_string())
+        })?;
+
+        let is_closed = self.is_closed.unwrap_or(false);
+
+        let buyer_org_id = self.buyer_org_id.ok_or_else(|| {
+            BuilderError::MissingField("'buyer_org_id' field is required".to_string())
+        })?;
+
+        let seller_org_id = self.seller_org_id.ok_or_else(|| {
+            BuilderError::MissingField("'seller_org_id' field is required".to_string())
+        })?;
+
+        Ok(PurchaseOrder {
+            uid,
+            workflow_status,
+            versions: self.versions,
+            accepted_version_number: self.accepted_version_number,
+            created_at,
+            is_closed,
+            buyer_org_id,
+            seller_org_id,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseOrderList {
+    purchase_orders: Vec<PurchaseOrder>,
+}
+
+impl PurchaseOrderList {
+    pub fn purchase_orders(&self) -> &[PurchaseOrder] {
+        &self.purchase_orders
+    }
+
+    pub fn into_builder(self) -> PurchaseOrderListBuilder {
+        PurchaseOrderListBuilder::new().with_purchase_orders(self.purchase_orders)
+    }
+}
+
+impl FromProto<protos::purchase_order_state::PurchaseOrderList> for PurchaseOrderList {
+    fn from_proto(
+        proto: protos::purchase_order_state::PurchaseOrderList,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(PurchaseOrderList {
+            purchase_orders: proto
+                .get_entries()
+                .to_vec()
+                .into_iter()
+                .map(PurchaseOrder::from_proto)
+                .collect::<Result<Vec<PurchaseOrder>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<PurchaseOrderList> for protos::purchase_order_state::PurchaseOrderList {
+    fn from_native(native: PurchaseOrderList) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::purchase_order_state::PurchaseOrderList::new();
+        proto.set_entries(
+            RepeatedField::from_vec(
+                native
+                    .purchase_orders()
+                    .to_vec()
+                    .into_iter()
+                    .map(PurchaseOrder::into_proto)
+                    .collect::<Result<
+                        Vec<protos::purchase_order_state::PurchaseOrder>,
+                        ProtoConversionError,
+                    >>()?,
+            ),
+        );
+        Ok(proto)
+    }
+}
+
+impl FromBytes<PurchaseOrderList> for PurchaseOrderList {
+    fn from_bytes(bytes: &[u8]) -> Result<PurchaseOrderList, ProtoConversionError> {
+        let proto: protos::purchase_order_state::PurchaseOrderList =
+            Message::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get PurchaseOrderList from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for PurchaseOrderList {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from PurchaseOrderList".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::purchase_order_state::PurchaseOrderList> for PurchaseOrderList {}
+impl IntoNative<PurchaseOrderList> for protos::purchase_order_state::PurchaseOrderList {}
+
+#[derive(Default, Clone)]
+pub struct PurchaseOrderListBuilder {
+    purchase_orders: Vec<PurchaseOrder>,
+}
+
+impl PurchaseOrderListBuilder {
+    pub fn new() -> Self {
+        PurchaseOrderListBuilder::default()
+    }
+
+    pub fn with_purchase_orders(mut self, purchase_orders: Vec<PurchaseOrder>) -> Self {
+        self.purchase_orders = purchase_orders;
+        self
+    }
+
+    pub fn build(self) -> Result<PurchaseOrderList, BuilderError> {
+        if self.purchase_orders.is_empty() {
+            return Err(BuilderError::MissingField(
+                "'purchase_orders' field is required".to_string(),
+            ));
+        }
+
+        Ok(PurchaseOrderList {
+            purchase_orders: self.purchase_orders,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Debug;
+
+    #[test]
+    // Test that a purchase order with versions and revisions can be built correctly
+    fn test_purchase_order_builder() {
+        let purchase_order = build_purchase_order();
+
+        assert_eq!(purchase_order.uid(), "PO-00000001");
+        assert_eq!(purchase_order.workflow_status(), "issued");
+        assert_eq!(purchase_order.accepted_version_number(), None);
+        assert_eq!(purchase_order.created_at(), 1);
+        assert!(!purchase_order.is_closed());
+        assert_eq!(purchase_order.buyer_org_id(), "buyer");
+        assert_eq!(purchase_order.seller_org_id(), "seller");
+
+        let version = &purchase_order.versions()[0];
+        assert_eq!(version.version_id(), "1");
+        assert_eq!(version.workflow_status(), "proposed");
+        assert!(!version.is_draft());
+        assert_eq!(version.current_revision_id(), 1);
+        assert_eq!(version.revisions()[0].submitter(), "buyer_agent");
+        assert_eq!(version.revisions()[0].order_xml_v3_4(), "<order/>");
+    }
+
+    #[test]
+    // Test that a purchase order is missing a required field
+    fn test_purchase_order_builder_missing_field() {
+        let result = PurchaseOrderBuilder::new()
+            .with_uid("PO-00000001".into())
+            .with_workflow_status("issued".into())
+            .with_created_at(1)
+            .with_buyer_org_id("buyer".into())
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    // Test that a purchase order can be converted to a builder and back
+    fn test_purchase_order_into_builder() {
+        let original = build_purchase_order()
+            .into_builder()
+            .with_accepted_version_number("1".into())
+            .build()
+            .unwrap();
+
+        let rebuilt = original.clone().into_builder().build().unwrap();
+
+        assert_eq!(rebuilt.accepted_version_number(), Some("1"));
+        assert_eq!(original, rebuilt);
+    }
+
+    #[test]
+    // Test that a purchase order and purchase order list can be converted to bytes and back
+    fn test_purchase_order_into_bytes() {
+        let purchase_order = build_purchase_order();
+        test_from_bytes(purchase_order.clone(), PurchaseOrder::from_bytes);
+
+        let purchase_order_list = PurchaseOrderListBuilder::new()
+            .with_purchase_orders(vec![purchase_order])
+            .build()
+            .unwrap();
+        test_from_bytes(purchase_order_list, PurchaseOrderList::from_bytes);
+    }
+
+    fn build_purchase_order() -> PurchaseOrder {
+        let revision = PurchaseOrderRevisionBuilder::new()
+            .with_revision_id(1)
+            .with_submitter("buyer_agent".into())
+            .with_created_at(1)
+            .with_order_xml_v3_4("<order/>".into())
+            .build()
+            .expect("Failed to build test revision");
+
+        let version = PurchaseOrderVersionBuilder::new()
+            .with_version_id("1".into())
+            .with_workflow_status("proposed".into())
+            .with_is_draft(false)
+            .with_current_revision_id(1)
+            .with_revisions(vec![revision])
+            .build()
+            .expect("Failed to build test version");
+
+        PurchaseOrderBuilder::new()
+            .with_uid("PO-00000001".into())
+            .with_workflow_status("issued".into())
+            .with_versions(vec![version])
+            .with_created_at(1)
+            .with_is_closed(false)
+            .with_buyer_org_id("buyer".into())
+            .with_seller_org_id("seller".into())
+            .build()
+            .expect("Failed to build test purchase order")
+    }
+
+    fn test_from_bytes<T: FromBytes<T> + Clone + PartialEq + IntoBytes + Debug, F>(
+        under_test: T,
+        from_bytes: F,
+    ) where
+        F: Fn(&[u8]) -> Result<T, ProtoConversionError>,
+    {
+        let bytes = under_test.clone().into_bytes().unwrap();
+        let created_from_bytes = from_bytes(&bytes).unwrap();
+        assert_eq!(under_test, created_from_bytes);
+    }
+}
@@ -32,6 +32,8 @@ pub struct CreateRecordAction {
     record_id: String,
     schema: String,
     properties: Vec<PropertyValue>,
+    product_id: String,
+    location_id: String,
 }
 
 impl CreateRecordAction {
@@ -44,6 +46,12 @@ impl CreateRecordAction {
     pub fn properties(&self) -> &[PropertyValue] {
         &self.properties
     }
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+    pub fn location_id(&self) -> &str {
+        &self.location_id
+    }
 }
 
 #[derive(Default, Debug)]
@@ -51,6 +59,8 @@ pub struct CreateRecordActionBuilder {
     record_id: Option<String>,
     schema: Option<String>,
     properties: Option<Vec<PropertyValue>>,
+    product_id: Option<String>,
+    location_id: Option<String>,
 }
 
 impl CreateRecordActionBuilder {
@@ -69,6 +79,14 @@ impl CreateRecordActionBuilder {
         self.properties = Some(value);
         self
     }
+    pub fn with_product_id(mut self, value: String) -> Self {
+        self.product_id = Some(value);
+        self
+    }
+    pub fn with_location_id(mut self, value: String) -> Self {
+        self.location_id = Some(value);
+        self
+    }
     pub fn build(self) -> Result<CreateRecordAction, BuilderError> {
         let record_id = self
             .record_id
@@ -79,10 +97,14 @@ impl CreateRecordActionBuilder {
         let properties = self
             .properties
             .ok_or_else(|| BuilderError::MissingField("properties".into()))?;
+        let product_id = self.product_id.unwrap_or_default();
+        let location_id = self.location_id.unwrap_or_default();
         Ok(CreateRecordAction {
             record_id,
             schema,
             properties,
+            product_id,
+            location_id,
         })
     }
 }
@@ -100,6 +122,8 @@ impl FromProto<track_and_trace_payload::CreateRecordAction> for CreateRecordActi
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            product_id: proto.get_product_id().to_string(),
+            location_id: proto.get_location_id().to_string(),
         })
     }
 }
@@ -118,6 +142,8 @@ impl FromNative<CreateRecordAction> for track_and_trace_payload::CreateRecordAct
                 .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
                 )?,
         ));
+        proto.set_product_id(create_record_action.product_id().to_string());
+        proto.set_location_id(create_record_action.location_id().to_string());
 
         Ok(proto)
     }
@@ -744,6 +770,413 @@ impl IntoBytes for RevokeReporterAction {
 impl IntoProto<track_and_trace_payload::RevokeReporterAction> for RevokeReporterAction {}
 impl IntoNative<RevokeReporterAction> for track_and_trace_payload::RevokeReporterAction {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReauthorizeReporterAction {
+    record_id: String,
+    reporter_id: String,
+    properties: Vec<String>,
+}
+
+impl ReauthorizeReporterAction {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    pub fn reporter_id(&self) -> &str {
+        &self.reporter_id
+    }
+    pub fn properties(&self) -> &[String] {
+        &self.properties
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct ReauthorizeReporterActionBuilder {
+    record_id: Option<String>,
+    reporter_id: Option<String>,
+    properties: Option<Vec<String>>,
+}
+
+impl ReauthorizeReporterActionBuilder {
+    pub fn new() -> Self {
+        ReauthorizeReporterActionBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_reporter_id(mut self, value: String) -> Self {
+        self.reporter_id = Some(value);
+        self
+    }
+    pub fn with_properties(mut self, value: Vec<String>) -> Self {
+        self.properties = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<ReauthorizeReporterAction, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let reporter_id = self
+            .reporter_id
+            .ok_or_else(|| BuilderError::MissingField("reporter_id".into()))?;
+        let properties = self
+            .properties
+            .ok_or_else(|| BuilderError::MissingField("properties".into()))?;
+        Ok(ReauthorizeReporterAction {
+            record_id,
+            reporter_id,
+            properties,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::ReauthorizeReporterAction> for ReauthorizeReporterAction {
+    fn from_proto(
+        proto: track_and_trace_payload::ReauthorizeReporterAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ReauthorizeReporterAction {
+            record_id: proto.get_record_id().to_string(),
+            reporter_id: proto.get_reporter_id().to_string(),
+            properties: proto
+                .get_properties()
+                .to_vec()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        })
+    }
+}
+
+impl FromNative<ReauthorizeReporterAction> for track_and_trace_payload::ReauthorizeReporterAction {
+    fn from_native(native: ReauthorizeReporterAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::ReauthorizeReporterAction::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_reporter_id(native.reporter_id().to_string());
+        proto.set_properties(RepeatedField::from_vec(native.properties().to_vec()));
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ReauthorizeReporterAction> for ReauthorizeReporterAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ReauthorizeReporterAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::ReauthorizeReporterAction =
+            Message::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ReauthorizeReporterAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for ReauthorizeReporterAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get ReauthorizeReporterAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::ReauthorizeReporterAction> for ReauthorizeReporterAction {}
+impl IntoNative<ReauthorizeReporterAction> for track_and_trace_payload::ReauthorizeReporterAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateRecordsAction {
+    record_id: String,
+    child_record_ids: Vec<String>,
+}
+
+impl AggregateRecordsAction {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    pub fn child_record_ids(&self) -> &[String] {
+        &self.child_record_ids
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AggregateRecordsActionBuilder {
+    record_id: Option<String>,
+    child_record_ids: Option<Vec<String>>,
+}
+
+impl AggregateRecordsActionBuilder {
+    pub fn new() -> Self {
+        AggregateRecordsActionBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_child_record_ids(mut self, value: Vec<String>) -> Self {
+        self.child_record_ids = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<AggregateRecordsAction, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let child_record_ids = self
+            .child_record_ids
+            .ok_or_else(|| BuilderError::MissingField("child_record_ids".into()))?;
+        Ok(AggregateRecordsAction {
+            record_id,
+            child_record_ids,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::AggregateRecordsAction> for AggregateRecordsAction {
+    fn from_proto(
+        proto: track_and_trace_payload::AggregateRecordsAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(AggregateRecordsAction {
+            record_id: proto.get_record_id().to_string(),
+            child_record_ids: proto
+                .get_child_record_ids()
+                .to_vec()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        })
+    }
+}
+
+impl FromNative<AggregateRecordsAction> for track_and_trace_payload::AggregateRecordsAction {
+    fn from_native(native: AggregateRecordsAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::AggregateRecordsAction::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_child_record_ids(RepeatedField::from_vec(native.child_record_ids().to_vec()));
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<AggregateRecordsAction> for AggregateRecordsAction {
+    fn from_bytes(bytes: &[u8]) -> Result<AggregateRecordsAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::AggregateRecordsAction =
+            Message::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get AggregateRecordsAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for AggregateRecordsAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get AggregateRecordsAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::AggregateRecordsAction> for AggregateRecordsAction {}
+impl IntoNative<AggregateRecordsAction> for track_and_trace_payload::AggregateRecordsAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisaggregateRecordsAction {
+    record_id: String,
+    child_record_ids: Vec<String>,
+}
+
+impl DisaggregateRecordsAction {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    pub fn child_record_ids(&self) -> &[String] {
+        &self.child_record_ids
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct DisaggregateRecordsActionBuilder {
+    record_id: Option<String>,
+    child_record_ids: Option<Vec<String>>,
+}
+
+impl DisaggregateRecordsActionBuilder {
+    pub fn new() -> Self {
+        DisaggregateRecordsActionBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_child_record_ids(mut self, value: Vec<String>) -> Self {
+        self.child_record_ids = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<DisaggregateRecordsAction, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let child_record_ids = self
+            .child_record_ids
+            .ok_or_else(|| BuilderError::MissingField("child_record_ids".into()))?;
+        Ok(DisaggregateRecordsAction {
+            record_id,
+            child_record_ids,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::DisaggregateRecordsAction> for DisaggregateRecordsAction {
+    fn from_proto(
+        proto: track_and_trace_payload::DisaggregateRecordsAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(DisaggregateRecordsAction {
+            record_id: proto.get_record_id().to_string(),
+            child_record_ids: proto
+                .get_child_record_ids()
+                .to_vec()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        })
+    }
+}
+
+impl FromNative<DisaggregateRecordsAction> for track_and_trace_payload::DisaggregateRecordsAction {
+    fn from_native(native: DisaggregateRecordsAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::DisaggregateRecordsAction::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_child_record_ids(RepeatedField::from_vec(native.child_record_ids().to_vec()));
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<DisaggregateRecordsAction> for DisaggregateRecordsAction {
+    fn from_bytes(bytes: &[u8]) -> Result<DisaggregateRecordsAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::DisaggregateRecordsAction =
+            Message::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get DisaggregateRecordsAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for DisaggregateRecordsAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get DisaggregateRecordsAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::DisaggregateRecordsAction> for DisaggregateRecordsAction {}
+impl IntoNative<DisaggregateRecordsAction> for track_and_trace_payload::DisaggregateRecordsAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateRecordLocationAction {
+    record_id: String,
+    location_id: String,
+}
+
+impl UpdateRecordLocationAction {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    pub fn location_id(&self) -> &str {
+        &self.location_id
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct UpdateRecordLocationActionBuilder {
+    record_id: Option<String>,
+    location_id: Option<String>,
+}
+
+impl UpdateRecordLocationActionBuilder {
+    pub fn new() -> Self {
+        UpdateRecordLocationActionBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_location_id(mut self, value: String) -> Self {
+        self.location_id = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<UpdateRecordLocationAction, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let location_id = self
+            .location_id
+            .ok_or_else(|| BuilderError::MissingField("location_id".into()))?;
+        Ok(UpdateRecordLocationAction {
+            record_id,
+            location_id,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::UpdateRecordLocationAction> for UpdateRecordLocationAction {
+    fn from_proto(
+        proto: track_and_trace_payload::UpdateRecordLocationAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(UpdateRecordLocationAction {
+            record_id: proto.get_record_id().to_string(),
+            location_id: proto.get_location_id().to_string(),
+        })
+    }
+}
+
+impl FromNative<UpdateRecordLocationAction>
+    for track_and_trace_payload::UpdateRecordLocationAction
+{
+    fn from_native(native: UpdateRecordLocationAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::UpdateRecordLocationAction::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_location_id(native.location_id().to_string());
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<UpdateRecordLocationAction> for UpdateRecordLocationAction {
+    fn from_bytes(bytes: &[u8]) -> Result<UpdateRecordLocationAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::UpdateRecordLocationAction =
+            Message::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get UpdateRecordLocationAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for UpdateRecordLocationAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get UpdateRecordLocationAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::UpdateRecordLocationAction> for UpdateRecordLocationAction {}
+impl IntoNative<UpdateRecordLocationAction>
+    for track_and_trace_payload::UpdateRecordLocationAction
+{
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Action {
     CreateRecord(CreateRecordAction),
@@ -752,6 +1185,10 @@ pub enum Action {
     CreateProposal(CreateProposalAction),
     AnswerProposal(AnswerProposalAction),
     RevokeReporter(RevokeReporterAction),
+    AggregateRecords(AggregateRecordsAction),
+    DisaggregateRecords(DisaggregateRecordsAction),
+    UpdateRecordLocation(UpdateRecordLocationAction),
+    ReauthorizeReporter(ReauthorizeReporterAction),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -821,6 +1258,18 @@ impl FromProto<track_and_trace_payload::TrackAndTracePayload> for TrackAndTraceP
             TrackAndTracePayload_Action::REVOKE_REPORTER => Action::RevokeReporter(
                 RevokeReporterAction::from_proto(proto.get_revoke_reporter().clone())?,
             ),
+            TrackAndTracePayload_Action::AGGREGATE_RECORDS => Action::AggregateRecords(
+                AggregateRecordsAction::from_proto(proto.get_aggregate_records().clone())?,
+            ),
+            TrackAndTracePayload_Action::DISAGGREGATE_RECORDS => Action::DisaggregateRecords(
+                DisaggregateRecordsAction::from_proto(proto.get_disaggregate_records().clone())?,
+            ),
+            TrackAndTracePayload_Action::UPDATE_RECORD_LOCATION => Action::UpdateRecordLocation(
+                UpdateRecordLocationAction::from_proto(proto.get_update_record_location().clone())?,
+            ),
+            TrackAndTracePayload_Action::REAUTHORIZE_REPORTER => Action::ReauthorizeReporter(
+                ReauthorizeReporterAction::from_proto(proto.get_reauthorize_reporter().clone())?,
+            ),
             TrackAndTracePayload_Action::UNSET_ACTION => {
                 return Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert TrackAndTracePayload_Action with type unset.".to_string(),
@@ -866,6 +1315,22 @@ impl FromNative<TrackAndTracePayload> for track_and_trace_payload::TrackAndTrace
                 proto.set_action(TrackAndTracePayload_Action::REVOKE_REPORTER);
                 proto.set_revoke_reporter(payload.clone().into_proto()?);
             }
+            Action::AggregateRecords(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::AGGREGATE_RECORDS);
+                proto.set_aggregate_records(payload.clone().into_proto()?);
+            }
+            Action::DisaggregateRecords(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::DISAGGREGATE_RECORDS);
+                proto.set_disaggregate_records(payload.clone().into_proto()?);
+            }
+            Action::UpdateRecordLocation(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::UPDATE_RECORD_LOCATION);
+                proto.set_update_record_location(payload.clone().into_proto()?);
+            }
+            Action::ReauthorizeReporter(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::REAUTHORIZE_REPORTER);
+                proto.set_reauthorize_reporter(payload.clone().into_proto()?);
+            }
         }
 
         Ok(proto)
@@ -954,6 +1419,23 @@ mod tests {
         test_from_bytes(action, CreateRecordAction::from_bytes);
     }
 
+    #[test]
+    fn test_create_record_with_product_and_location() {
+        let action = CreateRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .with_schema("schema".into())
+            .with_properties(vec![])
+            .with_product_id("00012345600012".into())
+            .with_location_id("0123456789012".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.product_id(), "00012345600012");
+        assert_eq!(action.location_id(), "0123456789012");
+
+        test_from_bytes(action, CreateRecordAction::from_bytes);
+    }
+
     #[test]
     fn test_finalize_record_action_builder() {
         let action = FinalizeRecordActionBuilder::new()
@@ -1098,6 +1580,107 @@ mod tests {
         test_from_bytes(action, RevokeReporterAction::from_bytes);
     }
 
+    #[test]
+    fn test_reauthorize_reporter_action_builder() {
+        let action = ReauthorizeReporterActionBuilder::new()
+            .with_record_id("32".into())
+            .with_reporter_id("jim".into())
+            .with_properties(vec!["egg".into()])
+            .build()
+            .unwrap();
+
+        assert_eq!(action.record_id(), "32");
+        assert_eq!(action.reporter_id(), "jim");
+        assert!(action.properties().iter().any(|x| x == "egg"));
+    }
+
+    #[test]
+    fn test_reauthorize_reporter_action_bytes() {
+        let action = ReauthorizeReporterActionBuilder::new()
+            .with_record_id("32".into())
+            .with_reporter_id("jim".into())
+            .with_properties(vec!["egg".into()])
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, ReauthorizeReporterAction::from_bytes);
+    }
+
+    #[test]
+    fn test_aggregate_records_action_builder() {
+        let action = AggregateRecordsActionBuilder::new()
+            .with_record_id("pallet".into())
+            .with_child_record_ids(vec!["32".into(), "33".into()])
+            .build()
+            .unwrap();
+
+        assert_eq!(action.record_id(), "pallet");
+        assert_eq!(
+            action.child_record_ids(),
+            &["32".to_string(), "33".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_aggregate_records_action_bytes() {
+        let action = AggregateRecordsActionBuilder::new()
+            .with_record_id("pallet".into())
+            .with_child_record_ids(vec!["32".into(), "33".into()])
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, AggregateRecordsAction::from_bytes);
+    }
+
+    #[test]
+    fn test_disaggregate_records_action_builder() {
+        let action = DisaggregateRecordsActionBuilder::new()
+            .with_record_id("pallet".into())
+            .with_child_record_ids(vec!["32".into(), "33".into()])
+            .build()
+            .unwrap();
+
+        assert_eq!(action.record_id(), "pallet");
+        assert_eq!(
+            action.child_record_ids(),
+            &["32".to_string(), "33".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_disaggregate_records_action_bytes() {
+        let action = DisaggregateRecordsActionBuilder::new()
+            .with_record_id("pallet".into())
+            .with_child_record_ids(vec!["32".into(), "33".into()])
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, DisaggregateRecordsAction::from_bytes);
+    }
+
+    #[test]
+    fn test_update_record_location_action_builder() {
+        let action = UpdateRecordLocationActionBuilder::new()
+            .with_record_id("32".into())
+            .with_location_id("0123456789012".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.record_id(), "32");
+        assert_eq!(action.location_id(), "0123456789012");
+    }
+
+    #[test]
+    fn test_update_record_location_action_bytes() {
+        let action = UpdateRecordLocationActionBuilder::new()
+            .with_record_id("32".into())
+            .with_location_id("0123456789012".into())
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, UpdateRecordLocationAction::from_bytes);
+    }
+
     #[test]
     fn test_payload_builder() {
         let action = RevokeReporterActionBuilder::new()
@@ -1200,6 +1200,10 @@ pub struct Record {
     owners: Vec<AssociatedAgent>,
     custodians: Vec<AssociatedAgent>,
     field_final: bool,
+    parent: String,
+    children: Vec<String>,
+    product_id: String,
+    location_id: String,
 }
 
 impl Record {
@@ -1218,6 +1222,18 @@ impl Record {
     pub fn field_final(&self) -> &bool {
         &self.field_final
     }
+    pub fn parent(&self) -> &str {
+        &self.parent
+    }
+    pub fn children(&self) -> &[String] {
+        &self.children
+    }
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+    pub fn location_id(&self) -> &str {
+        &self.location_id
+    }
     pub fn into_builder(self) -> RecordBuilder {
         RecordBuilder::new()
             .with_record_id(self.record_id)
@@ -1225,6 +1241,10 @@ impl Record {
             .with_owners(self.owners)
             .with_custodians(self.custodians)
             .with_field_final(self.field_final)
+            .with_parent(self.parent)
+            .with_children(self.children)
+            .with_product_id(self.product_id)
+            .with_location_id(self.location_id)
     }
 }
 
@@ -1235,6 +1255,10 @@ pub struct RecordBuilder {
     owners: Option<Vec<AssociatedAgent>>,
     custodians: Option<Vec<AssociatedAgent>>,
     field_final: Option<bool>,
+    parent: Option<String>,
+    children: Option<Vec<String>>,
+    product_id: Option<String>,
+    location_id: Option<String>,
 }
 
 impl RecordBuilder {
@@ -1261,6 +1285,22 @@ impl RecordBuilder {
         self.field_final = Some(value);
         self
     }
+    pub fn with_parent(mut self, value: String) -> Self {
+        self.parent = Some(value);
+        self
+    }
+    pub fn with_children(mut self, value: Vec<String>) -> Self {
+        self.children = Some(value);
+        self
+    }
+    pub fn with_product_id(mut self, value: String) -> Self {
+        self.product_id = Some(value);
+        self
+    }
+    pub fn with_location_id(mut self, value: String) -> Self {
+        self.location_id = Some(value);
+        self
+    }
     pub fn build(self) -> Result<Record, BuilderError> {
         let record_id = self
             .record_id
@@ -1277,12 +1317,20 @@ impl RecordBuilder {
         let field_final = self
             .field_final
             .ok_or_else(|| BuilderError::MissingField("field_final".into()))?;
+        let parent = self.parent.unwrap_or_default();
+        let children = self.children.unwrap_or_default();
+        let product_id = self.product_id.unwrap_or_default();
+        let location_id = self.location_id.unwrap_or_default();
         Ok(Record {
             record_id,
             schema,
             owners,
             custodians,
             field_final,
+            parent,
+            children,
+            product_id,
+            location_id,
         })
     }
 }
@@ -1305,6 +1353,15 @@ impl FromProto<track_and_trace_state::Record> for Record {
                 .map(AssociatedAgent::from_proto)
                 .collect::<Result<Vec<AssociatedAgent>, ProtoConversionError>>()?,
             field_final: proto.get_field_final(),
+            parent: proto.get_parent().to_string(),
+            children: proto
+                .get_children()
+                .to_vec()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            product_id: proto.get_product_id().to_string(),
+            location_id: proto.get_location_id().to_string(),
         })
     }
 }
@@ -1341,6 +1398,10 @@ impl FromNative<Record> for track_and_trace_state::Record {
             ),
         );
         proto.set_field_final(*native.field_final());
+        proto.set_parent(native.parent().to_string());
+        proto.set_children(RepeatedField::from_vec(native.children().to_vec()));
+        proto.set_product_id(native.product_id().to_string());
+        proto.set_location_id(native.location_id().to_string());
 
         Ok(proto)
     }
@@ -2116,6 +2177,59 @@ mod tests {
         test_from_bytes(record, Record::from_bytes);
     }
 
+    #[test]
+    fn test_record_aggregated() {
+        let associated_agent = AssociatedAgentBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_timestamp(2132)
+            .build()
+            .unwrap();
+
+        let record = RecordBuilder::new()
+            .with_record_id("case1234".into())
+            .with_schema("case".into())
+            .with_owners(vec![associated_agent.clone()])
+            .with_custodians(vec![associated_agent.clone()])
+            .with_field_final(false)
+            .with_parent("pallet1234".into())
+            .with_children(vec!["egg1234".into(), "egg5678".into()])
+            .build()
+            .unwrap();
+
+        assert_eq!(record.parent(), "pallet1234");
+        assert_eq!(
+            record.children(),
+            &["egg1234".to_string(), "egg5678".to_string()]
+        );
+
+        test_from_bytes(record, Record::from_bytes);
+    }
+
+    #[test]
+    fn test_record_product_and_location() {
+        let associated_agent = AssociatedAgentBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_timestamp(2132)
+            .build()
+            .unwrap();
+
+        let record = RecordBuilder::new()
+            .with_record_id("egg1234".into())
+            .with_schema("egg".into())
+            .with_owners(vec![associated_agent.clone()])
+            .with_custodians(vec![associated_agent.clone()])
+            .with_field_final(false)
+            .with_product_id("00012345600012".into())
+            .with_location_id("0123456789012".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(record.product_id(), "00012345600012");
+        assert_eq!(record.location_id(), "0123456789012");
+
+        test_from_bytes(record, Record::from_bytes);
+    }
+
     #[test]
     fn test_record_list() {
         let associated_agent = AssociatedAgentBuilder::new()
@@ -0,0 +1,34 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crypto::digest::Digest;
+use crypto::sha2::Sha512;
+
+pub const GRID_NAMESPACE: &str = "621dee";
+pub const PO_PREFIX: &str = "06";
+pub const GRID_PURCHASE_ORDER_NAMESPACE: &str = "621dee06";
+
+fn hash(to_hash: &str, num: usize) -> String {
+    let mut sha = Sha512::new();
+    sha.input_str(to_hash);
+    let temp = sha.result_str();
+    let hash = temp.get(..num).unwrap_or("");
+    hash.to_string()
+}
+
+/// Computes the address of a purchase order based on its uid
+pub fn compute_purchase_order_address(uid: &str) -> String {
+    // 621dee (grid namespace) + 06 (purchase order namespace)
+    String::from(GRID_NAMESPACE) + PO_PREFIX + &hash(uid, 62)
+}
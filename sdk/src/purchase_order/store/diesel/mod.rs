@@ -0,0 +1,230 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod models;
+mod operations;
+pub(in crate) mod schema;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use super::diesel::models::{
+    NewPurchaseOrderModel, NewPurchaseOrderVersionModel, NewPurchaseOrderVersionRevisionModel,
+    PurchaseOrderModel, PurchaseOrderVersionModel, PurchaseOrderVersionRevisionModel,
+};
+use super::{
+    PurchaseOrder, PurchaseOrderList, PurchaseOrderStore, PurchaseOrderStoreError,
+    PurchaseOrderVersion, PurchaseOrderVersionRevision,
+};
+use crate::commits::MAX_COMMIT_NUM;
+use crate::error::ResourceTemporarilyUnavailableError;
+use crate::paging::PageRequest;
+
+use operations::add_purchase_order::PurchaseOrderStoreAddPurchaseOrderOperation as _;
+use operations::fetch_purchase_order::PurchaseOrderStoreFetchPurchaseOrderOperation as _;
+use operations::list_purchase_orders::PurchaseOrderStoreListPurchaseOrdersOperation as _;
+use operations::PurchaseOrderStoreOperations;
+
+#[derive(Clone)]
+pub struct DieselPurchaseOrderStore<C: diesel::Connection + 'static> {
+    connection_pool: Pool<ConnectionManager<C>>,
+}
+
+impl<C: diesel::Connection> DieselPurchaseOrderStore<C> {
+    /// Creates a new DieselPurchaseOrderStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselPurchaseOrderStore { connection_pool }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl PurchaseOrderStore for DieselPurchaseOrderStore<diesel::pg::PgConnection> {
+    fn add_purchase_order(&self, order: PurchaseOrder) -> Result<(), PurchaseOrderStoreError> {
+        let (order, versions, revisions) = make_purchase_order_models(order);
+        PurchaseOrderStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            PurchaseOrderStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .add_purchase_order(order, versions, revisions)
+    }
+
+    fn fetch_purchase_order(
+        &self,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<PurchaseOrder>, PurchaseOrderStoreError> {
+        PurchaseOrderStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            PurchaseOrderStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .fetch_purchase_order(purchase_order_uid, service_id)
+    }
+
+    fn list_purchase_orders(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<PurchaseOrderList, PurchaseOrderStoreError> {
+        PurchaseOrderStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            PurchaseOrderStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_purchase_orders(org_id, service_id, page)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl PurchaseOrderStore for DieselPurchaseOrderStore<diesel::sqlite::SqliteConnection> {
+    fn add_purchase_order(&self, order: PurchaseOrder) -> Result<(), PurchaseOrderStoreError> {
+        let (order, versions, revisions) = make_purchase_order_models(order);
+        PurchaseOrderStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            PurchaseOrderStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .add_purchase_order(order, versions, revisions)
+    }
+
+    fn fetch_purchase_order(
+        &self,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<PurchaseOrder>, PurchaseOrderStoreError> {
+        PurchaseOrderStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            PurchaseOrderStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .fetch_purchase_order(purchase_order_uid, service_id)
+    }
+
+    fn list_purchase_orders(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<PurchaseOrderList, PurchaseOrderStoreError> {
+        PurchaseOrderStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            PurchaseOrderStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_purchase_orders(org_id, service_id, page)
+    }
+}
+
+/// Splits a purchase order into the models for its own row and the rows of its versions and
+/// revisions, all starting at the purchase order's start commit
+pub fn make_purchase_order_models(
+    order: PurchaseOrder,
+) -> (
+    NewPurchaseOrderModel,
+    Vec<NewPurchaseOrderVersionModel>,
+    Vec<NewPurchaseOrderVersionRevisionModel>,
+) {
+    let mut versions = Vec::new();
+    let mut revisions = Vec::new();
+
+    for version in order.versions {
+        for revision in version.revisions {
+            revisions.push(NewPurchaseOrderVersionRevisionModel {
+                purchase_order_uid: order.purchase_order_uid.to_string(),
+                version_id: version.version_id.to_string(),
+                revision_id: revision.revision_id,
+                order_xml_v3_4: revision.order_xml_v3_4,
+                submitter: revision.submitter,
+                created_at: revision.created_at,
+                start_commit_num: order.start_commit_num,
+                end_commit_num: MAX_COMMIT_NUM,
+                service_id: order.service_id.clone(),
+            });
+        }
+
+        versions.push(NewPurchaseOrderVersionModel {
+            purchase_order_uid: order.purchase_order_uid.to_string(),
+            version_id: version.version_id,
+            is_draft: version.is_draft,
+            current_revision_id: version.current_revision_id,
+            workflow_status: version.workflow_status,
+            start_commit_num: order.start_commit_num,
+            end_commit_num: MAX_COMMIT_NUM,
+            service_id: order.service_id.clone(),
+        });
+    }
+
+    let order = NewPurchaseOrderModel {
+        purchase_order_uid: order.purchase_order_uid,
+        workflow_status: order.workflow_status,
+        buyer_org_id: order.buyer_org_id,
+        seller_org_id: order.seller_org_id,
+        is_closed: order.is_closed,
+        accepted_version_id: order.accepted_version_id,
+        created_at: order.created_at,
+        start_commit_num: order.start_commit_num,
+        end_commit_num: MAX_COMMIT_NUM,
+        service_id: order.service_id,
+    };
+
+    (order, versions, revisions)
+}
+
+impl From<PurchaseOrderVersionRevisionModel> for PurchaseOrderVersionRevision {
+    fn from(model: PurchaseOrderVersionRevisionModel) -> Self {
+        Self {
+            revision_id: model.revision_id,
+            order_xml_v3_4: model.order_xml_v3_4,
+            submitter: model.submitter,
+            created_at: model.created_at,
+        }
+    }
+}
+
+impl From<(PurchaseOrderVersionModel, Vec<PurchaseOrderVersionRevision>)> for PurchaseOrderVersion {
+    fn from(
+        (version, revisions): (PurchaseOrderVersionModel, Vec<PurchaseOrderVersionRevision>),
+    ) -> Self {
+        Self {
+            version_id: version.version_id,
+            is_draft: version.is_draft,
+            current_revision_id: version.current_revision_id,
+            workflow_status: version.workflow_status,
+            revisions,
+        }
+    }
+}
+
+impl From<(PurchaseOrderModel, Vec<PurchaseOrderVersion>)> for PurchaseOrder {
+    fn from((order, versions): (PurchaseOrderModel, Vec<PurchaseOrderVersion>)) -> Self {
+        Self {
+            purchase_order_uid: order.purchase_order_uid,
+            workflow_status: order.workflow_status,
+            buyer_org_id: order.buyer_org_id,
+            seller_org_id: order.seller_org_id,
+            is_closed: order.is_closed,
+            accepted_version_id: order.accepted_version_id,
+            versions,
+            created_at: order.created_at,
+            start_commit_num: order.start_commit_num,
+            end_commit_num: order.end_commit_num,
+            service_id: order.service_id,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::purchase_order::store::diesel::schema::{
+    purchase_order, purchase_order_version, purchase_order_version_revision,
+};
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "purchase_order"]
+pub struct NewPurchaseOrderModel {
+    pub purchase_order_uid: String,
+    pub workflow_status: String,
+    pub buyer_org_id: String,
+    pub seller_org_id: String,
+    pub is_closed: bool,
+    pub accepted_version_id: Option<String>,
+    pub created_at: i64,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "purchase_order"]
+pub struct PurchaseOrderModel {
+    pub id: i64,
+    pub purchase_order_uid: String,
+    pub workflow_status: String,
+    pub buyer_org_id: String,
+    pub seller_org_id: String,
+    pub is_closed: bool,
+    pub accepted_version_id: Option<String>,
+    pub created_at: i64,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "purchase_order_version"]
+pub struct NewPurchaseOrderVersionModel {
+    pub purchase_order_uid: String,
+    pub version_id: String,
+    pub is_draft: bool,
+    pub current_revision_id: i64,
+    pub workflow_status: String,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "purchase_order_version"]
+pub struct PurchaseOrderVersionModel {
+    pub id: i64,
+    pub purchase_order_uid: String,
+    pub version_id: String,
+    pub is_draft: bool,
+    pub current_revision_id: i64,
+    pub workflow_status: String,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "purchase_order_version_revision"]
+pub struct NewPurchaseOrderVersionRevisionModel {
+    pub purchase_order_uid: String,
+    pub version_id: String,
+    pub revision_id: i64,
+    pub order_xml_v3_4: String,
+    pub submitter: String,
+    pub created_at: i64,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "purchase_order_version_revision"]
+pub struct PurchaseOrderVersionRevisionModel {
+    pub id: i64,
+    pub purchase_order_uid: String,
+    pub version_id: String,
+    pub revision_id: i64,
+    pub order_xml_v3_4: String,
+    pub submitter: String,
+    pub created_at: i64,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+}
@@ -0,0 +1,225 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::PurchaseOrderStoreOperations;
+use crate::purchase_order::store::diesel::{
+    schema::{purchase_order, purchase_order_version, purchase_order_version_revision},
+    PurchaseOrderStoreError,
+};
+
+use crate::commits::MAX_COMMIT_NUM;
+use crate::purchase_order::store::diesel::models::{
+    NewPurchaseOrderModel, NewPurchaseOrderVersionModel, NewPurchaseOrderVersionRevisionModel,
+};
+use diesel::{
+    dsl::{insert_into, update},
+    prelude::*,
+};
+
+pub(in crate::purchase_order::store::diesel) trait PurchaseOrderStoreAddPurchaseOrderOperation {
+    fn add_purchase_order(
+        &self,
+        order: NewPurchaseOrderModel,
+        versions: Vec<NewPurchaseOrderVersionModel>,
+        revisions: Vec<NewPurchaseOrderVersionRevisionModel>,
+    ) -> Result<(), PurchaseOrderStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> PurchaseOrderStoreAddPurchaseOrderOperation
+    for PurchaseOrderStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_purchase_order(
+        &self,
+        order: NewPurchaseOrderModel,
+        versions: Vec<NewPurchaseOrderVersionModel>,
+        revisions: Vec<NewPurchaseOrderVersionRevisionModel>,
+    ) -> Result<(), PurchaseOrderStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, PurchaseOrderStoreError, _>(|| {
+                let mut order_query = purchase_order::table
+                    .select(purchase_order::id)
+                    .into_boxed()
+                    .filter(
+                        purchase_order::purchase_order_uid
+                            .eq(&order.purchase_order_uid)
+                            .and(purchase_order::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    );
+                let mut version_query = purchase_order_version::table
+                    .select(purchase_order_version::id)
+                    .into_boxed()
+                    .filter(
+                        purchase_order_version::purchase_order_uid
+                            .eq(&order.purchase_order_uid)
+                            .and(purchase_order_version::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    );
+                let mut revision_query = purchase_order_version_revision::table
+                    .select(purchase_order_version_revision::id)
+                    .into_boxed()
+                    .filter(
+                        purchase_order_version_revision::purchase_order_uid
+                            .eq(&order.purchase_order_uid)
+                            .and(
+                                purchase_order_version_revision::end_commit_num.eq(MAX_COMMIT_NUM),
+                            ),
+                    );
+
+                if let Some(service_id) = &order.service_id {
+                    order_query = order_query.filter(purchase_order::service_id.eq(service_id));
+                    version_query =
+                        version_query.filter(purchase_order_version::service_id.eq(service_id));
+                    revision_query = revision_query
+                        .filter(purchase_order_version_revision::service_id.eq(service_id));
+                } else {
+                    order_query = order_query.filter(purchase_order::service_id.is_null());
+                    version_query =
+                        version_query.filter(purchase_order_version::service_id.is_null());
+                    revision_query = revision_query
+                        .filter(purchase_order_version_revision::service_id.is_null());
+                }
+
+                // End the current rows of the purchase order, its versions and its revisions
+                // before inserting their replacements
+                let order_ids = order_query.load::<i64>(self.conn)?;
+                let version_ids = version_query.load::<i64>(self.conn)?;
+                let revision_ids = revision_query.load::<i64>(self.conn)?;
+
+                update(purchase_order::table)
+                    .filter(purchase_order::id.eq_any(order_ids))
+                    .set(purchase_order::end_commit_num.eq(order.start_commit_num))
+                    .execute(self.conn)?;
+                update(purchase_order_version::table)
+                    .filter(purchase_order_version::id.eq_any(version_ids))
+                    .set(purchase_order_version::end_commit_num.eq(order.start_commit_num))
+                    .execute(self.conn)?;
+                update(purchase_order_version_revision::table)
+                    .filter(purchase_order_version_revision::id.eq_any(revision_ids))
+                    .set(purchase_order_version_revision::end_commit_num.eq(order.start_commit_num))
+                    .execute(self.conn)?;
+
+                insert_into(purchase_order::table)
+                    .values(&order)
+                    .execute(self.conn)?;
+
+                for version in versions {
+                    insert_into(purchase_order_version::table)
+                        .values(&version)
+                        .execute(self.conn)?;
+                }
+
+                for revision in revisions {
+                    insert_into(purchase_order_version_revision::table)
+                        .values(&revision)
+                        .execute(self.conn)?;
+                }
+
+                Ok(())
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> PurchaseOrderStoreAddPurchaseOrderOperation
+    for PurchaseOrderStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_purchase_order(
+        &self,
+        order: NewPurchaseOrderModel,
+        versions: Vec<NewPurchaseOrderVersionModel>,
+        revisions: Vec<NewPurchaseOrderVersionRevisionModel>,
+    ) -> Result<(), PurchaseOrderStoreError> {
+        self.conn
+            .immediate_transaction::<_, PurchaseOrderStoreError, _>(|| {
+                let mut order_query = purchase_order::table
+                    .select(purchase_order::id)
+                    .into_boxed()
+                    .filter(
+                        purchase_order::purchase_order_uid
+                            .eq(&order.purchase_order_uid)
+                            .and(purchase_order::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    );
+                let mut version_query = purchase_order_version::table
+                    .select(purchase_order_version::id)
+                    .into_boxed()
+                    .filter(
+                        purchase_order_version::purchase_order_uid
+                            .eq(&order.purchase_order_uid)
+                            .and(purchase_order_version::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    );
+                let mut revision_query = purchase_order_version_revision::table
+                    .select(purchase_order_version_revision::id)
+                    .into_boxed()
+                    .filter(
+                        purchase_order_version_revision::purchase_order_uid
+                            .eq(&order.purchase_order_uid)
+                            .and(
+                                purchase_order_version_revision::end_commit_num.eq(MAX_COMMIT_NUM),
+                            ),
+                    );
+
+                if let Some(service_id) = &order.service_id {
+                    order_query = order_query.filter(purchase_order::service_id.eq(service_id));
+                    version_query =
+                        version_query.filter(purchase_order_version::service_id.eq(service_id));
+                    revision_query = revision_query
+                        .filter(purchase_order_version_revision::service_id.eq(service_id));
+                } else {
+                    order_query = order_query.filter(purchase_order::service_id.is_null());
+                    version_query =
+                        version_query.filter(purchase_order_version::service_id.is_null());
+                    revision_query = revision_query
+                        .filter(purchase_order_version_revision::service_id.is_null());
+                }
+
+                // End the current rows of the purchase order, its versions and its revisions
+                // before inserting their replacements
+                let order_ids = order_query.load::<i64>(self.conn)?;
+                let version_ids = version_query.load::<i64>(self.conn)?;
+                let revision_ids = revision_query.load::<i64>(self.conn)?;
+
+                update(purchase_order::table)
+                    .filter(purchase_order::id.eq_any(order_ids))
+                    .set(purchase_order::end_commit_num.eq(order.start_commit_num))
+                    .execute(self.conn)?;
+                update(purchase_order_version::table)
+                    .filter(purchase_order_version::id.eq_any(version_ids))
+                    .set(purchase_order_version::end_commit_num.eq(order.start_commit_num))
+                    .execute(self.conn)?;
+                update(purchase_order_version_revision::table)
+                    .filter(purchase_order_version_revision::id.eq_any(revision_ids))
+                    .set(purchase_order_version_revision::end_commit_num.eq(order.start_commit_num))
+                    .execute(self.conn)?;
+
+                insert_into(purchase_order::table)
+                    .values(&order)
+                    .execute(self.conn)?;
+
+                for version in versions {
+                    insert_into(purchase_order_version::table)
+                        .values(&version)
+                        .execute(self.conn)?;
+                }
+
+                for revision in revisions {
+                    insert_into(purchase_order_version_revision::table)
+                        .values(&revision)
+                        .execute(self.conn)?;
+                }
+
+                Ok(())
+            })
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::PurchaseOrderStoreOperations;
+use crate::commits::MAX_COMMIT_NUM;
+use crate::purchase_order::store::diesel::{
+    schema::{purchase_order, purchase_order_version, purchase_order_version_revision},
+    PurchaseOrderStoreError,
+};
+
+use crate::error::InternalError;
+use crate::purchase_order::store::diesel::models::{
+    PurchaseOrderModel, PurchaseOrderVersionModel, PurchaseOrderVersionRevisionModel,
+};
+use crate::purchase_order::store::{
+    PurchaseOrder, PurchaseOrderVersion, PurchaseOrderVersionRevision,
+};
+
+use diesel::prelude::*;
+
+pub(in crate::purchase_order::store::diesel) trait PurchaseOrderStoreFetchPurchaseOrderOperation<
+    C: Connection,
+>
+{
+    fn fetch_purchase_order(
+        &self,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<PurchaseOrder>, PurchaseOrderStoreError>;
+    fn get_versions(
+        conn: &C,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<PurchaseOrderVersion>, PurchaseOrderStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> PurchaseOrderStoreFetchPurchaseOrderOperation<diesel::pg::PgConnection>
+    for PurchaseOrderStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn fetch_purchase_order(
+        &self,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<PurchaseOrder>, PurchaseOrderStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, PurchaseOrderStoreError, _>(|| {
+                let mut query = purchase_order::table
+                    .into_boxed()
+                    .select(purchase_order::all_columns)
+                    .filter(
+                        purchase_order::purchase_order_uid
+                            .eq(purchase_order_uid)
+                            .and(purchase_order::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    );
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(purchase_order::service_id.eq(service_id));
+                } else {
+                    query = query.filter(purchase_order::service_id.is_null());
+                }
+
+                let order = query
+                    .first::<PurchaseOrderModel>(self.conn)
+                    .optional()
+                    .map_err(|err| {
+                        PurchaseOrderStoreError::InternalError(InternalError::from_source(
+                            Box::new(err),
+                        ))
+                    })?;
+
+                match order {
+                    Some(order) => {
+                        let versions =
+                            Self::get_versions(self.conn, &order.purchase_order_uid, service_id)?;
+                        Ok(Some(PurchaseOrder::from((order, versions))))
+                    }
+                    None => Ok(None),
+                }
+            })
+    }
+
+    fn get_versions(
+        conn: &diesel::pg::PgConnection,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<PurchaseOrderVersion>, PurchaseOrderStoreError> {
+        let mut version_query = purchase_order_version::table
+            .into_boxed()
+            .select(purchase_order_version::all_columns)
+            .filter(
+                purchase_order_version::purchase_order_uid
+                    .eq(purchase_order_uid)
+                    .and(purchase_order_version::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .order(purchase_order_version::version_id.asc());
+        let mut revision_query = purchase_order_version_revision::table
+            .into_boxed()
+            .select(purchase_order_version_revision::all_columns)
+            .filter(
+                purchase_order_version_revision::purchase_order_uid
+                    .eq(purchase_order_uid)
+                    .and(purchase_order_version_revision::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .order(purchase_order_version_revision::revision_id.asc());
+
+        if let Some(service_id) = service_id {
+            version_query = version_query.filter(purchase_order_version::service_id.eq(service_id));
+            revision_query =
+                revision_query.filter(purchase_order_version_revision::service_id.eq(service_id));
+        } else {
+            version_query = version_query.filter(purchase_order_version::service_id.is_null());
+            revision_query =
+                revision_query.filter(purchase_order_version_revision::service_id.is_null());
+        }
+
+        let versions = version_query.load::<PurchaseOrderVersionModel>(conn)?;
+        let mut revisions = revision_query.load::<PurchaseOrderVersionRevisionModel>(conn)?;
+
+        Ok(versions
+            .into_iter()
+            .map(|version| {
+                let (version_revisions, remaining): (Vec<_>, Vec<_>) = revisions
+                    .drain(..)
+                    .partition(|revision| revision.version_id == version.version_id);
+                revisions = remaining;
+
+                PurchaseOrderVersion::from((
+                    version,
+                    version_revisions
+                        .into_iter()
+                        .map(PurchaseOrderVersionRevision::from)
+                        .collect(),
+                ))
+            })
+            .collect())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> PurchaseOrderStoreFetchPurchaseOrderOperation<diesel::sqlite::SqliteConnection>
+    for PurchaseOrderStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn fetch_purchase_order(
+        &self,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<PurchaseOrder>, PurchaseOrderStoreError> {
+        self.conn
+            .immediate_transaction::<_, PurchaseOrderStoreError, _>(|| {
+                let mut query = purchase_order::table
+                    .into_boxed()
+                    .select(purchase_order::all_columns)
+                    .filter(
+                        purchase_order::purchase_order_uid
+                            .eq(purchase_order_uid)
+                            .and(purchase_order::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    );
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(purchase_order::service_id.eq(service_id));
+                } else {
+                    query = query.filter(purchase_order::service_id.is_null());
+                }
+
+                let order = query
+                    .first::<PurchaseOrderModel>(self.conn)
+                    .optional()
+                    .map_err(|err| {
+                        PurchaseOrderStoreError::InternalError(InternalError::from_source(
+                            Box::new(err),
+                        ))
+                    })?;
+
+                match order {
+                    Some(order) => {
+                        let versions =
+                            Self::get_versions(self.conn, &order.purchase_order_uid, service_id)?;
+                        Ok(Some(PurchaseOrder::from((order, versions))))
+                    }
+                    None => Ok(None),
+                }
+            })
+    }
+
+    fn get_versions(
+        conn: &diesel::sqlite::SqliteConnection,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<PurchaseOrderVersion>, PurchaseOrderStoreError> {
+        let mut version_query = purchase_order_version::table
+            .into_boxed()
+            .select(purchase_order_version::all_columns)
+            .filter(
+                purchase_order_version::purchase_order_uid
+                    .eq(purchase_order_uid)
+                    .and(purchase_order_version::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .order(purchase_order_version::version_id.asc());
+        let mut revision_query = purchase_order_version_revision::table
+            .into_boxed()
+            .select(purchase_order_version_revision::all_columns)
+            .filter(
+                purchase_order_version_revision::purchase_order_uid
+                    .eq(purchase_order_uid)
+                    .and(purchase_order_version_revision::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .order(purchase_order_version_revision::revision_id.asc());
+
+        if let Some(service_id) = service_id {
+            version_query = version_query.filter(purchase_order_version::service_id.eq(service_id));
+            revision_query =
+                revision_query.filter(purchase_order_version_revision::service_id.eq(service_id));
+        } else {
+            version_query = version_query.filter(purchase_order_version::service_id.is_null());
+            revision_query =
+                revision_query.filter(purchase_order_version_revision::service_id.is_null());
+        }
+
+        let versions = version_query.load::<PurchaseOrderVersionModel>(conn)?;
+        let mut revisions = revision_query.load::<PurchaseOrderVersionRevisionModel>(conn)?;
+
+        Ok(versions
+            .into_iter()
+            .map(|version| {
+                let (version_revisions, remaining): (Vec<_>, Vec<_>) = revisions
+                    .drain(..)
+                    .partition(|revision| revision.version_id == version.version_id);
+                revisions = remaining;
+
+                PurchaseOrderVersion::from((
+                    version,
+                    version_revisions
+                        .into_iter()
+                        .map(PurchaseOrderVersionRevision::from)
+                        .collect(),
+                ))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::fetch_purchase_order::PurchaseOrderStoreFetchPurchaseOrderOperation;
+use super::PurchaseOrderStoreOperations;
+use crate::commits::MAX_COMMIT_NUM;
+use crate::purchase_order::store::diesel::{schema::purchase_order, PurchaseOrderStoreError};
+
+use crate::error::InternalError;
+use crate::paging::{paged_query, PageRequest, Paging};
+use crate::purchase_order::store::diesel::models::PurchaseOrderModel;
+use crate::purchase_order::store::{PurchaseOrder, PurchaseOrderList};
+
+use diesel::prelude::*;
+
+pub(in crate::purchase_order::store::diesel) trait PurchaseOrderStoreListPurchaseOrdersOperation {
+    fn list_purchase_orders(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<PurchaseOrderList, PurchaseOrderStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> PurchaseOrderStoreListPurchaseOrdersOperation
+    for PurchaseOrderStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_purchase_orders(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<PurchaseOrderList, PurchaseOrderStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, PurchaseOrderStoreError, _>(|| {
+                let mut query = purchase_order::table
+                    .into_boxed()
+                    .select(purchase_order::all_columns)
+                    .filter(purchase_order::end_commit_num.eq(MAX_COMMIT_NUM))
+                    .order(purchase_order::purchase_order_uid.asc());
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(purchase_order::service_id.eq(service_id));
+                } else {
+                    query = query.filter(purchase_order::service_id.is_null());
+                }
+
+                if let Some(org_id) = org_id {
+                    query = query.filter(
+                        purchase_order::buyer_org_id
+                            .eq(org_id)
+                            .or(purchase_order::seller_org_id.eq(org_id)),
+                    );
+                }
+
+                let query = paged_query(query, purchase_order::purchase_order_uid, page);
+
+                let orders = query.load::<PurchaseOrderModel>(self.conn).map_err(|err| {
+                    PurchaseOrderStoreError::InternalError(InternalError::from_source(Box::new(
+                        err,
+                    )))
+                })?;
+
+                let mut count_query = purchase_order::table
+                    .into_boxed()
+                    .select(purchase_order::all_columns)
+                    .filter(purchase_order::end_commit_num.eq(MAX_COMMIT_NUM));
+
+                if let Some(service_id) = service_id {
+                    count_query = count_query.filter(purchase_order::service_id.eq(service_id));
+                } else {
+                    count_query = count_query.filter(purchase_order::service_id.is_null());
+                }
+
+                if let Some(org_id) = org_id {
+                    count_query = count_query.filter(
+                        purchase_order::buyer_org_id
+                            .eq(org_id)
+                            .or(purchase_order::seller_org_id.eq(org_id)),
+                    );
+                }
+
+                let (orders, paging) = Paging::for_page(
+                    orders,
+                    page,
+                    || count_query.count().get_result(self.conn),
+                    |model| model.purchase_order_uid.clone(),
+                )?;
+
+                let mut data = Vec::new();
+
+                for order in orders {
+                    let versions =
+                        Self::get_versions(self.conn, &order.purchase_order_uid, service_id)?;
+                    data.push(PurchaseOrder::from((order, versions)));
+                }
+
+                Ok(PurchaseOrderList::new(data, paging))
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> PurchaseOrderStoreListPurchaseOrdersOperation
+    for PurchaseOrderStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_purchase_orders(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<PurchaseOrderList, PurchaseOrderStoreError> {
+        self.conn
+            .immediate_transaction::<_, PurchaseOrderStoreError, _>(|| {
+                let mut query = purchase_order::table
+                    .into_boxed()
+                    .select(purchase_order::all_columns)
+                    .filter(purchase_order::end_commit_num.eq(MAX_COMMIT_NUM))
+                    .order(purchase_order::purchase_order_uid.asc());
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(purchase_order::service_id.eq(service_id));
+                } else {
+                    query = query.filter(purchase_order::service_id.is_null());
+                }
+
+                if let Some(org_id) = org_id {
+                    query = query.filter(
+                        purchase_order::buyer_org_id
+                            .eq(org_id)
+                            .or(purchase_order::seller_org_id.eq(org_id)),
+                    );
+                }
+
+                let query = paged_query(query, purchase_order::purchase_order_uid, page);
+
+                let orders = query.load::<PurchaseOrderModel>(self.conn).map_err(|err| {
+                    PurchaseOrderStoreError::InternalError(InternalError::from_source(Box::new(
+                        err,
+                    )))
+                })?;
+
+                let mut count_query = purchase_order::table
+                    .into_boxed()
+                    .select(purchase_order::all_columns)
+                    .filter(purchase_order::end_commit_num.eq(MAX_COMMIT_NUM));
+
+                if let Some(service_id) = service_id {
+                    count_query = count_query.filter(purchase_order::service_id.eq(service_id));
+                } else {
+                    count_query = count_query.filter(purchase_order::service_id.is_null());
+                }
+
+                if let Some(org_id) = org_id {
+                    count_query = count_query.filter(
+                        purchase_order::buyer_org_id
+                            .eq(org_id)
+                            .or(purchase_order::seller_org_id.eq(org_id)),
+                    );
+                }
+
+                let (orders, paging) = Paging::for_page(
+                    orders,
+                    page,
+                    || count_query.count().get_result(self.conn),
+                    |model| model.purchase_order_uid.clone(),
+                )?;
+
+                let mut data = Vec::new();
+
+                for order in orders {
+                    let versions =
+                        Self::get_versions(self.conn, &order.purchase_order_uid, service_id)?;
+                    data.push(PurchaseOrder::from((order, versions)));
+                }
+
+                Ok(PurchaseOrderList::new(data, paging))
+            })
+    }
+}
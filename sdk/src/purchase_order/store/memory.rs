@@ -0,0 +1,123 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::commits::{end_current_rows, impl_versioned, resolve_fork_rows, MAX_COMMIT_NUM};
+use crate::error::InternalError;
+use crate::paging::{page_items, PageRequest};
+
+use super::{PurchaseOrder, PurchaseOrderList, PurchaseOrderStore, PurchaseOrderStoreError};
+
+impl_versioned!(PurchaseOrder);
+
+/// Implementation of `PurchaseOrderStore` that keeps purchase orders in memory, versioned by
+/// commit like the database-backed store. Useful for tests and when persistence isn't necessary.
+#[derive(Clone, Default)]
+pub struct MemoryPurchaseOrderStore {
+    inner: Arc<Mutex<Vec<PurchaseOrder>>>,
+}
+
+impl MemoryPurchaseOrderStore {
+    pub fn new() -> Self {
+        MemoryPurchaseOrderStore::default()
+    }
+
+    /// Rolls the purchase orders back to their state before the given commit
+    ///
+    /// # Arguments
+    ///
+    ///  * `commit_num` - The number of the first commit that is no longer on the chain
+    pub fn resolve_fork(&self, commit_num: i64) -> Result<(), PurchaseOrderStoreError> {
+        resolve_fork_rows(&mut *self.orders()?, commit_num);
+        Ok(())
+    }
+
+    fn orders(&self) -> Result<MutexGuard<'_, Vec<PurchaseOrder>>, PurchaseOrderStoreError> {
+        self.inner.lock().map_err(|_| {
+            PurchaseOrderStoreError::InternalError(InternalError::with_message(
+                "Cannot access purchase orders: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+impl PurchaseOrderStore for MemoryPurchaseOrderStore {
+    fn add_purchase_order(&self, order: PurchaseOrder) -> Result<(), PurchaseOrderStoreError> {
+        let mut orders = self.orders()?;
+
+        end_current_rows(&mut orders, order.start_commit_num, |current| {
+            current.purchase_order_uid == order.purchase_order_uid
+                && current.service_id == order.service_id
+        });
+
+        let mut order = PurchaseOrder {
+            end_commit_num: MAX_COMMIT_NUM,
+            ..order
+        };
+        order
+            .versions
+            .sort_by(|a, b| a.version_id.cmp(&b.version_id));
+        for version in order.versions.iter_mut() {
+            version
+                .revisions
+                .sort_by_key(|revision| revision.revision_id);
+        }
+        orders.push(order);
+        Ok(())
+    }
+
+    fn fetch_purchase_order(
+        &self,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<PurchaseOrder>, PurchaseOrderStoreError> {
+        Ok(self
+            .orders()?
+            .iter()
+            .find(|order| {
+                order.purchase_order_uid == purchase_order_uid
+                    && order.service_id.as_deref() == service_id
+                    && order.end_commit_num == MAX_COMMIT_NUM
+            })
+            .cloned())
+    }
+
+    fn list_purchase_orders(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<PurchaseOrderList, PurchaseOrderStoreError> {
+        let orders = self.orders()?;
+
+        let mut current = orders
+            .iter()
+            .filter(|order| {
+                order.service_id.as_deref() == service_id
+                    && order.end_commit_num == MAX_COMMIT_NUM
+                    && org_id
+                        .map(|org_id| order.buyer_org_id == org_id || order.seller_org_id == org_id)
+                        .unwrap_or(true)
+            })
+            .collect::<Vec<_>>();
+        current.sort_by(|a, b| a.purchase_order_uid.cmp(&b.purchase_order_uid));
+        let (current, paging) = page_items(current, page, |order| order.purchase_order_uid.clone());
+
+        Ok(PurchaseOrderList::new(
+            current.into_iter().cloned().collect(),
+            paging,
+        ))
+    }
+}
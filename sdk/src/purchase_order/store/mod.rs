@@ -0,0 +1,131 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
+
+use crate::paging::{PageRequest, Paging};
+
+pub use error::PurchaseOrderStoreError;
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct PurchaseOrder {
+    pub purchase_order_uid: String,
+    pub workflow_status: String,
+    pub buyer_org_id: String,
+    pub seller_org_id: String,
+    pub is_closed: bool,
+    pub accepted_version_id: Option<String>,
+    pub versions: Vec<PurchaseOrderVersion>,
+    pub created_at: i64,
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+    pub service_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct PurchaseOrderVersion {
+    pub version_id: String,
+    pub is_draft: bool,
+    pub current_revision_id: i64,
+    pub workflow_status: String,
+    pub revisions: Vec<PurchaseOrderVersionRevision>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct PurchaseOrderVersionRevision {
+    pub revision_id: i64,
+    pub order_xml_v3_4: String,
+    pub submitter: String,
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct PurchaseOrderList {
+    pub data: Vec<PurchaseOrder>,
+    pub paging: Paging,
+}
+
+impl PurchaseOrderList {
+    pub fn new(data: Vec<PurchaseOrder>, paging: Paging) -> Self {
+        Self { data, paging }
+    }
+}
+
+pub trait PurchaseOrderStore: Send + Sync {
+    /// Adds a purchase order to the underlying storage, replacing the current state of the
+    /// purchase order and its versions if it already exists
+    ///
+    /// # Arguments
+    ///
+    ///  * `order` - The purchase order to be added
+    fn add_purchase_order(&self, order: PurchaseOrder) -> Result<(), PurchaseOrderStoreError>;
+
+    /// Fetches a purchase order, with its versions and revisions, from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `purchase_order_uid` - The uid of the purchase order to be fetched
+    ///  * `service_id` - optional - The service ID to fetch the purchase order from
+    fn fetch_purchase_order(
+        &self,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<PurchaseOrder>, PurchaseOrderStoreError>;
+
+    /// Gets purchase orders from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `org_id` - optional - The organization that must be the buyer or seller of the
+    ///    purchase orders
+    ///  * `service_id` - optional - The service ID to get the purchase orders for
+    ///  * `page` - The page to retrieve, by offset or by key
+    fn list_purchase_orders(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<PurchaseOrderList, PurchaseOrderStoreError>;
+}
+
+impl<PS> PurchaseOrderStore for Box<PS>
+where
+    PS: PurchaseOrderStore + ?Sized,
+{
+    fn add_purchase_order(&self, order: PurchaseOrder) -> Result<(), PurchaseOrderStoreError> {
+        (**self).add_purchase_order(order)
+    }
+
+    fn fetch_purchase_order(
+        &self,
+        purchase_order_uid: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<PurchaseOrder>, PurchaseOrderStoreError> {
+        (**self).fetch_purchase_order(purchase_order_uid, service_id)
+    }
+
+    fn list_purchase_orders(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<PurchaseOrderList, PurchaseOrderStoreError> {
+        (**self).list_purchase_orders(org_id, service_id, page)
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The workflow that governs purchase orders and their versions.
+//!
+//! A purchase order moves through the `po` subworkflow, and each of its versions moves through
+//! the `version` subworkflow. Pike agents are granted the `po.buyer` or `po.seller` role; the
+//! aliases below expand those roles into the permissions and transitions available in each
+//! state. A role only applies to an agent whose organization is the buyer or seller,
+//! respectively, on the purchase order.
+
+use std::convert::TryFrom;
+
+use crate::protocol::purchase_order::state::{PurchaseOrder, PurchaseOrderVersion};
+use crate::workflow::{
+    ConstraintFields, FieldValue, PermissionAlias, SubWorkflow, SubWorkflowBuilder, Workflow,
+    WorkflowStateBuilder,
+};
+
+pub const PURCHASE_ORDER_WORKFLOW: &str = "purchase_order";
+
+pub const PO_SUBWORKFLOW: &str = "po";
+pub const VERSION_SUBWORKFLOW: &str = "version";
+
+pub const BUYER_ALIAS: &str = "po.buyer";
+pub const SELLER_ALIAS: &str = "po.seller";
+
+pub const PERMISSION_CREATE_PO: &str = "po.create";
+pub const PERMISSION_UPDATE_PO: &str = "po.update";
+pub const PERMISSION_CREATE_VERSION: &str = "po.create-version";
+pub const PERMISSION_UPDATE_VERSION: &str = "po.update-version";
+
+/// Returns the purchase order workflow
+pub fn purchase_order_workflow() -> Workflow {
+    Workflow::new(vec![po_subworkflow(), version_subworkflow()])
+}
+
+/// Returns the roles of an agent that apply to a purchase order. The buyer role only applies to
+/// agents of the buyer organization, and the seller role only applies to agents of the seller
+/// organization.
+///
+/// # Arguments
+///
+/// * `agent_org_id` - The organization of the agent
+/// * `roles` - The Pike roles held by the agent
+/// * `buyer_org_id` - The buyer organization of the purchase order
+/// * `seller_org_id` - The seller organization of the purchase order
+pub fn purchase_order_aliases(
+    agent_org_id: &str,
+    roles: &[String],
+    buyer_org_id: &str,
+    seller_org_id: &str,
+) -> Vec<String> {
+    roles
+        .iter()
+        .filter(|role| {
+            (role.as_str() == BUYER_ALIAS && agent_org_id == buyer_org_id)
+                || (role.as_str() == SELLER_ALIAS && agent_org_id == seller_org_id)
+        })
+        .cloned()
+        .collect()
+}
+
+fn alias(name: &str, permissions: &[&str], transitions: &[&str]) -> PermissionAlias {
+    let mut alias = PermissionAlias::new(name);
+    for permission in permissions {
+        alias.add_permission(permission);
+    }
+    for transition in transitions {
+        alias.add_transition(transition);
+    }
+    alias
+}
+
+fn po_subworkflow() -> SubWorkflow {
+    let issued = WorkflowStateBuilder::new("issued")
+        .add_transition("confirmed")
+        .add_transition("closed")
+        .add_permission_alias(alias(
+            BUYER_ALIAS,
+            &[
+                PERMISSION_CREATE_PO,
+                PERMISSION_UPDATE_PO,
+                PERMISSION_CREATE_VERSION,
+                PERMISSION_UPDATE_VERSION,
+            ],
+            &["closed"],
+        ))
+        .add_permission_alias(alias(
+            SELLER_ALIAS,
+            &[PERMISSION_UPDATE_PO, PERMISSION_UPDATE_VERSION],
+            &["confirmed"],
+        ))
+        .build();
+
+    let confirmed = WorkflowStateBuilder::new("confirmed")
+        .add_transition("closed")
+        .add_permission_alias(alias(
+            BUYER_ALIAS,
+            &[
+                PERMISSION_UPDATE_PO,
+                PERMISSION_CREATE_VERSION,
+                PERMISSION_UPDATE_VERSION,
+            ],
+            &["closed"],
+        ))
+        .add_permission_alias(alias(
+            SELLER_ALIAS,
+            &[PERMISSION_UPDATE_PO, PERMISSION_UPDATE_VERSION],
+            &["closed"],
+        ))
+        .build();
+
+    let closed = WorkflowStateBuilder::new("closed").build();
+
+    SubWorkflowBuilder::new(PO_SUBWORKFLOW)
+        .add_state(issued)
+        .add_state(confirmed)
+        .add_state(closed)
+        .add_starting_state("issued")
+        .build()
+}
+
+fn version_subworkflow() -> SubWorkflow {
+    let proposed = WorkflowStateBuilder::new("proposed")
+        .add_transition("accepted")
+        .add_transition("rejected")
+        .add_transition("obsolete")
+        .add_permission_alias(alias(
+            BUYER_ALIAS,
+            &[PERMISSION_UPDATE_VERSION],
+            &["obsolete"],
+        ))
+        .add_permission_alias(alias(
+            SELLER_ALIAS,
+            &[PERMISSION_UPDATE_VERSION],
+            &["accepted", "rejected"],
+        ))
+        .build();
+
+    let rejected = WorkflowStateBuilder::new("rejected")
+        .add_transition("proposed")
+        .add_transition("obsolete")
+        .add_permission_alias(alias(
+            BUYER_ALIAS,
+            &[PERMISSION_UPDATE_VERSION],
+            &["proposed", "obsolete"],
+        ))
+        .build();
+
+    let accepted = WorkflowStateBuilder::new("accepted")
+        .add_constraint("is_draft = false")
+        .add_transition("obsolete")
+        .add_permission_alias(alias(
+            BUYER_ALIAS,
+            &[PERMISSION_UPDATE_VERSION],
+            &["obsolete"],
+        ))
+        .build();
+
+    let obsolete = WorkflowStateBuilder::new("obsolete").build();
+
+    SubWorkflowBuilder::new(VERSION_SUBWORKFLOW)
+        .add_state(proposed)
+        .add_state(rejected)
+        .add_state(accepted)
+        .add_state(obsolete)
+        .add_starting_state("proposed")
+        .build()
+}
+
+fn integer(value: u64) -> FieldValue {
+    FieldValue::Integer(i64::try_from(value).unwrap_or(i64::MAX))
+}
+
+/// The fields of a purchase order that constraints in the `po` subworkflow may refer to
+impl ConstraintFields for PurchaseOrder {
+    fn field(&self, name: &str) -> Option<FieldValue> {
+        match name {
+            "uid" => Some(FieldValue::String(self.uid().to_string())),
+            "workflow_status" => Some(FieldValue::String(self.workflow_status().to_string())),
+            "buyer_org_id" => Some(FieldValue::String(self.buyer_org_id().to_string())),
+            "seller_org_id" => Some(FieldValue::String(self.seller_org_id().to_string())),
+            "is_closed" => Some(FieldValue::Bool(self.is_closed())),
+            "accepted_version_id" => Some(self.accepted_version_number().into()),
+            "version_count" => Some(integer(self.versions().len() as u64)),
+            "created_at" => Some(integer(self.created_at())),
+            _ => None,
+        }
+    }
+}
+
+/// The fields of a purchase order version that constraints in the `version` subworkflow may
+/// refer to
+impl ConstraintFields for PurchaseOrderVersion {
+    fn field(&self, name: &str) -> Option<FieldValue> {
+        match name {
+            "version_id" => Some(FieldValue::String(self.version_id().to_string())),
+            "workflow_status" => Some(FieldValue::String(self.workflow_status().to_string())),
+            "is_draft" => Some(FieldValue::Bool(self.is_draft())),
+            "current_revision_id" => Some(integer(self.current_revision_id())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test that the buyer, but not the seller, may create a purchase order
+    fn test_issued_permissions() {
+        let workflow = purchase_order_workflow();
+        let po = workflow.subworkflow(PO_SUBWORKFLOW).unwrap();
+        assert_eq!(&["issued".to_string()], po.starting_states());
+
+        let issued = po.state("issued").unwrap();
+        assert!(issued
+            .expand_permissions(&[BUYER_ALIAS.to_string()])
+            .contains(&PERMISSION_CREATE_PO.to_string()));
+        assert!(!issued
+            .expand_permissions(&[SELLER_ALIAS.to_string()])
+            .contains(&PERMISSION_CREATE_PO.to_string()));
+    }
+
+    #[test]
+    // Test that only the seller may accept a proposed version
+    fn test_version_transitions() {
+        let workflow = purchase_order_workflow();
+        let proposed = workflow
+            .subworkflow(VERSION_SUBWORKFLOW)
+            .unwrap()
+            .state("proposed")
+            .unwrap();
+
+        assert!(proposed.can_transition("accepted".into(), vec![SELLER_ALIAS.to_string()]));
+        assert!(!proposed.can_transition("accepted".into(), vec![BUYER_ALIAS.to_string()]));
+        assert!(proposed.can_transition("obsolete".into(), vec![BUYER_ALIAS.to_string()]));
+    }
+
+    #[test]
+    // Test that a draft version does not satisfy the constraints of the accepted state
+    fn test_accepted_constraints() {
+        use crate::protocol::purchase_order::state::{
+            PurchaseOrderRevisionBuilder, PurchaseOrderVersionBuilder,
+        };
+
+        let accepted = purchase_order_workflow()
+            .subworkflow(VERSION_SUBWORKFLOW)
+            .unwrap()
+            .state("accepted")
+            .unwrap();
+
+        let version = |is_draft| {
+            PurchaseOrderVersionBuilder::new()
+                .with_version_id("1".to_string())
+                .with_workflow_status("accepted".to_string())
+                .with_is_draft(is_draft)
+                .with_current_revision_id(1)
+                .with_revisions(vec![PurchaseOrderRevisionBuilder::new()
+                    .with_revision_id(1)
+                    .with_submitter("buyer".to_string())
+                    .with_created_at(1)
+                    .with_order_xml_v3_4("<order/>".to_string())
+                    .build()
+                    .unwrap()])
+                .build()
+                .unwrap()
+        };
+
+        assert!(accepted.check_constraints(&version(false)).is_ok());
+        assert!(accepted.check_constraints(&version(true)).is_err());
+    }
+}
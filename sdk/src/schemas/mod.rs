@@ -19,4 +19,6 @@ pub const MAX_COMMIT_NUM: i64 = i64::MAX;
 
 #[cfg(feature = "diesel")]
 pub use store::diesel::DieselSchemaStore;
+#[cfg(feature = "memory")]
+pub use store::memory::MemorySchemaStore;
 pub use store::SchemaStore;
@@ -20,6 +20,7 @@ use crate::error::{
     ConstraintViolationError, ConstraintViolationType, InternalError,
     ResourceTemporarilyUnavailableError,
 };
+use crate::paging::PageRequest;
 
 use models::{GridPropertyDefinition, GridSchema, NewGridPropertyDefinition, NewGridSchema};
 use operations::{
@@ -60,6 +61,7 @@ impl SchemaStore for DieselSchemaStore<diesel::pg::PgConnection> {
     fn fetch_schema(
         &self,
         name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Schema>, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -67,25 +69,26 @@ impl SchemaStore for DieselSchemaStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_schema(name, service_id)
+        .fetch_schema(name, commit_height, service_id)
     }
 
     fn list_schemas(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<SchemaList, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             SchemaStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_schemas(service_id, offset, limit)
+        .list_schemas(commit_height, service_id, page)
     }
 
     fn list_property_definitions(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -93,12 +96,13 @@ impl SchemaStore for DieselSchemaStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_property_definitions(service_id)
+        .list_property_definitions(commit_height, service_id)
     }
 
     fn list_property_definitions_with_schema_name(
         &self,
         schema_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -106,13 +110,18 @@ impl SchemaStore for DieselSchemaStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_property_definitions_with_schema_name(schema_name, service_id)
+        .list_property_definitions_with_schema_name(
+            schema_name,
+            commit_height,
+            service_id,
+        )
     }
 
     fn get_property_definition_by_name(
         &self,
         schema_name: &str,
         definition_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<PropertyDefinition>, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -120,7 +129,12 @@ impl SchemaStore for DieselSchemaStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .get_property_definition_by_name(schema_name, definition_name, service_id)
+        .get_property_definition_by_name(
+            schema_name,
+            definition_name,
+            commit_height,
+            service_id,
+        )
     }
 }
 
@@ -138,6 +152,7 @@ impl SchemaStore for DieselSchemaStore<diesel::sqlite::SqliteConnection> {
     fn fetch_schema(
         &self,
         name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Schema>, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -145,25 +160,26 @@ impl SchemaStore for DieselSchemaStore<diesel::sqlite::SqliteConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_schema(name, service_id)
+        .fetch_schema(name, commit_height, service_id)
     }
 
     fn list_schemas(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<SchemaList, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             SchemaStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_schemas(service_id, offset, limit)
+        .list_schemas(commit_height, service_id, page)
     }
 
     fn list_property_definitions(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -171,12 +187,13 @@ impl SchemaStore for DieselSchemaStore<diesel::sqlite::SqliteConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_property_definitions(service_id)
+        .list_property_definitions(commit_height, service_id)
     }
 
     fn list_property_definitions_with_schema_name(
         &self,
         schema_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -184,13 +201,18 @@ impl SchemaStore for DieselSchemaStore<diesel::sqlite::SqliteConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_property_definitions_with_schema_name(schema_name, service_id)
+        .list_property_definitions_with_schema_name(
+            schema_name,
+            commit_height,
+            service_id,
+        )
     }
 
     fn get_property_definition_by_name(
         &self,
         schema_name: &str,
         definition_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<PropertyDefinition>, SchemaStoreError> {
         SchemaStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -198,7 +220,12 @@ impl SchemaStore for DieselSchemaStore<diesel::sqlite::SqliteConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .get_property_definition_by_name(schema_name, definition_name, service_id)
+        .get_property_definition_by_name(
+            schema_name,
+            definition_name,
+            commit_height,
+            service_id,
+        )
     }
 }
 
@@ -14,16 +14,14 @@
 
 use super::SchemaStoreOperations;
 
-use crate::schemas::{
-    store::{
-        diesel::{
-            models::{GridPropertyDefinition, GridSchema},
-            schema::{grid_property_definition, grid_schema},
-        },
-        error::SchemaStoreError,
-        PropertyDefinition, Schema,
+use crate::commits::commit_height_or_current;
+use crate::schemas::store::{
+    diesel::{
+        models::{GridPropertyDefinition, GridSchema},
+        schema::{grid_property_definition, grid_schema},
     },
-    MAX_COMMIT_NUM,
+    error::SchemaStoreError,
+    PropertyDefinition, Schema,
 };
 use diesel::{prelude::*, result::Error::NotFound};
 
@@ -31,6 +29,7 @@ pub(in crate::schemas) trait FetchSchemaOperation {
     fn fetch_schema(
         &self,
         name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Schema>, SchemaStoreError>;
 }
@@ -40,15 +39,18 @@ impl<'a> FetchSchemaOperation for SchemaStoreOperations<'a, diesel::pg::PgConnec
     fn fetch_schema(
         &self,
         name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Schema>, SchemaStoreError> {
-        let schema = if let Some(schema) = pg::fetch_grid_schema(&*self.conn, name, service_id)? {
-            schema
-        } else {
-            return Ok(None);
-        };
+        let height = commit_height_or_current(commit_height);
+        let schema =
+            if let Some(schema) = pg::fetch_grid_schema(&*self.conn, height, name, service_id)? {
+                schema
+            } else {
+                return Ok(None);
+            };
 
-        let roots = pg::get_root_definitions(&*self.conn, &schema.name)?;
+        let roots = pg::get_root_definitions(&*self.conn, height, &schema.name)?;
 
         let properties = pg::get_property_definitions_for_schema(&*self.conn, roots)?;
 
@@ -61,16 +63,19 @@ impl<'a> FetchSchemaOperation for SchemaStoreOperations<'a, diesel::sqlite::Sqli
     fn fetch_schema(
         &self,
         name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Schema>, SchemaStoreError> {
-        let schema = if let Some(schema) = sqlite::fetch_grid_schema(&*self.conn, name, service_id)?
+        let height = commit_height_or_current(commit_height);
+        let schema = if let Some(schema) =
+            sqlite::fetch_grid_schema(&*self.conn, height, name, service_id)?
         {
             schema
         } else {
             return Ok(None);
         };
 
-        let roots = sqlite::get_root_definitions(&*self.conn, &schema.name)?;
+        let roots = sqlite::get_root_definitions(&*self.conn, height, &schema.name)?;
 
         let properties = sqlite::get_property_definitions_for_schema(&*self.conn, roots)?;
 
@@ -84,6 +89,7 @@ mod pg {
 
     pub fn fetch_grid_schema(
         conn: &PgConnection,
+        height: i64,
         name: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Option<GridSchema>> {
@@ -91,9 +97,11 @@ mod pg {
             .into_boxed()
             .select(grid_schema::all_columns)
             .filter(
-                grid_schema::name
-                    .eq(name)
-                    .and(grid_schema::end_commit_num.eq(MAX_COMMIT_NUM)),
+                grid_schema::name.eq(name).and(
+                    grid_schema::start_commit_num
+                        .le(height)
+                        .and(grid_schema::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
@@ -110,6 +118,7 @@ mod pg {
 
     pub fn get_root_definitions(
         conn: &PgConnection,
+        height: i64,
         schema_name: &str,
     ) -> QueryResult<Vec<GridPropertyDefinition>> {
         grid_property_definition::table
@@ -118,7 +127,11 @@ mod pg {
                 grid_property_definition::schema_name
                     .eq(schema_name)
                     .and(grid_property_definition::parent_name.is_null())
-                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        grid_property_definition::start_commit_num
+                            .le(height)
+                            .and(grid_property_definition::end_commit_num.gt(height)),
+                    ),
             )
             .load::<GridPropertyDefinition>(conn)
     }
@@ -155,6 +168,7 @@ mod sqlite {
 
     pub fn fetch_grid_schema(
         conn: &SqliteConnection,
+        height: i64,
         name: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Option<GridSchema>> {
@@ -162,9 +176,11 @@ mod sqlite {
             .into_boxed()
             .select(grid_schema::all_columns)
             .filter(
-                grid_schema::name
-                    .eq(name)
-                    .and(grid_schema::end_commit_num.eq(MAX_COMMIT_NUM)),
+                grid_schema::name.eq(name).and(
+                    grid_schema::start_commit_num
+                        .le(height)
+                        .and(grid_schema::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
@@ -181,6 +197,7 @@ mod sqlite {
 
     pub fn get_root_definitions(
         conn: &SqliteConnection,
+        height: i64,
         schema_name: &str,
     ) -> QueryResult<Vec<GridPropertyDefinition>> {
         grid_property_definition::table
@@ -189,7 +206,11 @@ mod sqlite {
                 grid_property_definition::schema_name
                     .eq(schema_name)
                     .and(grid_property_definition::parent_name.is_null())
-                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        grid_property_definition::start_commit_num
+                            .le(height)
+                            .and(grid_property_definition::end_commit_num.gt(height)),
+                    ),
             )
             .load::<GridPropertyDefinition>(conn)
     }
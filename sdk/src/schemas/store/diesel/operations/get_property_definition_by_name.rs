@@ -14,14 +14,12 @@
 
 use super::SchemaStoreOperations;
 
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
-use crate::schemas::{
-    store::{
-        diesel::{models::GridPropertyDefinition, schema::grid_property_definition},
-        error::SchemaStoreError,
-        PropertyDefinition,
-    },
-    MAX_COMMIT_NUM,
+use crate::schemas::store::{
+    diesel::{models::GridPropertyDefinition, schema::grid_property_definition},
+    error::SchemaStoreError,
+    PropertyDefinition,
 };
 use diesel::{prelude::*, result::Error::NotFound};
 
@@ -30,6 +28,7 @@ pub(in crate::schemas) trait GetPropertyDefinitionByNameOperation {
         &self,
         schema_name: &str,
         definition_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<PropertyDefinition>, SchemaStoreError>;
 }
@@ -42,8 +41,10 @@ impl<'a> GetPropertyDefinitionByNameOperation
         &self,
         schema_name: &str,
         definition_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<PropertyDefinition>, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = grid_property_definition::table
             .into_boxed()
             .select(grid_property_definition::all_columns)
@@ -51,7 +52,11 @@ impl<'a> GetPropertyDefinitionByNameOperation
                 grid_property_definition::schema_name
                     .eq(&schema_name)
                     .and(grid_property_definition::name.eq(&definition_name))
-                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        grid_property_definition::start_commit_num
+                            .le(height)
+                            .and(grid_property_definition::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
@@ -81,8 +86,10 @@ impl<'a> GetPropertyDefinitionByNameOperation
         &self,
         schema_name: &str,
         definition_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<PropertyDefinition>, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = grid_property_definition::table
             .into_boxed()
             .select(grid_property_definition::all_columns)
@@ -90,7 +97,11 @@ impl<'a> GetPropertyDefinitionByNameOperation
                 grid_property_definition::schema_name
                     .eq(&schema_name)
                     .and(grid_property_definition::name.eq(&definition_name))
-                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        grid_property_definition::start_commit_num
+                            .le(height)
+                            .and(grid_property_definition::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
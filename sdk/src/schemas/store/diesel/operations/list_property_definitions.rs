@@ -14,20 +14,19 @@
 
 use super::SchemaStoreOperations;
 
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
-use crate::schemas::{
-    store::{
-        diesel::{models::GridPropertyDefinition, schema::grid_property_definition},
-        error::SchemaStoreError,
-        PropertyDefinition,
-    },
-    MAX_COMMIT_NUM,
+use crate::schemas::store::{
+    diesel::{models::GridPropertyDefinition, schema::grid_property_definition},
+    error::SchemaStoreError,
+    PropertyDefinition,
 };
 use diesel::prelude::*;
 
 pub(in crate::schemas) trait ListPropertyDefinitionsOperation {
     fn list_property_definitions(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError>;
 }
@@ -36,12 +35,18 @@ pub(in crate::schemas) trait ListPropertyDefinitionsOperation {
 impl<'a> ListPropertyDefinitionsOperation for SchemaStoreOperations<'a, diesel::pg::PgConnection> {
     fn list_property_definitions(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = grid_property_definition::table
             .into_boxed()
             .select(grid_property_definition::all_columns)
-            .filter(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM));
+            .filter(
+                grid_property_definition::start_commit_num
+                    .le(height)
+                    .and(grid_property_definition::end_commit_num.gt(height)),
+            );
 
         if let Some(service_id) = service_id {
             query = query.filter(grid_property_definition::service_id.eq(service_id));
@@ -74,12 +79,18 @@ impl<'a> ListPropertyDefinitionsOperation
 {
     fn list_property_definitions(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = grid_property_definition::table
             .into_boxed()
             .select(grid_property_definition::all_columns)
-            .filter(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM));
+            .filter(
+                grid_property_definition::start_commit_num
+                    .le(height)
+                    .and(grid_property_definition::end_commit_num.gt(height)),
+            );
 
         if let Some(service_id) = service_id {
             query = query.filter(grid_property_definition::service_id.eq(service_id));
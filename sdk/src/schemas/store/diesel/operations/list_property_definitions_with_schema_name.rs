@@ -14,14 +14,12 @@
 
 use super::SchemaStoreOperations;
 
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
-use crate::schemas::{
-    store::{
-        diesel::{models::GridPropertyDefinition, schema::grid_property_definition},
-        error::SchemaStoreError,
-        PropertyDefinition,
-    },
-    MAX_COMMIT_NUM,
+use crate::schemas::store::{
+    diesel::{models::GridPropertyDefinition, schema::grid_property_definition},
+    error::SchemaStoreError,
+    PropertyDefinition,
 };
 use diesel::prelude::*;
 
@@ -29,6 +27,7 @@ pub(in crate::schemas) trait ListPropertyDefinitionsWithSchemaNameOperation {
     fn list_property_definitions_with_schema_name(
         &self,
         schema_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError>;
 }
@@ -40,15 +39,19 @@ impl<'a> ListPropertyDefinitionsWithSchemaNameOperation
     fn list_property_definitions_with_schema_name(
         &self,
         schema_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = grid_property_definition::table
             .into_boxed()
             .select(grid_property_definition::all_columns)
             .filter(
-                grid_property_definition::schema_name
-                    .eq(&schema_name)
-                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM)),
+                grid_property_definition::schema_name.eq(&schema_name).and(
+                    grid_property_definition::start_commit_num
+                        .le(height)
+                        .and(grid_property_definition::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
@@ -84,15 +87,19 @@ impl<'a> ListPropertyDefinitionsWithSchemaNameOperation
     fn list_property_definitions_with_schema_name(
         &self,
         schema_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = grid_property_definition::table
             .into_boxed()
             .select(grid_property_definition::all_columns)
             .filter(
-                grid_property_definition::schema_name
-                    .eq(&schema_name)
-                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM)),
+                grid_property_definition::schema_name.eq(&schema_name).and(
+                    grid_property_definition::start_commit_num
+                        .le(height)
+                        .and(grid_property_definition::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
@@ -14,18 +14,16 @@
 
 use super::SchemaStoreOperations;
 
+use crate::commits::commit_height_or_current;
 use crate::{
-    paging::Paging,
-    schemas::{
-        store::{
-            diesel::{
-                models::{GridPropertyDefinition, GridSchema},
-                schema::{grid_property_definition, grid_schema},
-            },
-            error::SchemaStoreError,
-            PropertyDefinition, Schema, SchemaList,
+    paging::{paged_query, PageRequest, Paging},
+    schemas::store::{
+        diesel::{
+            models::{GridPropertyDefinition, GridSchema},
+            schema::{grid_property_definition, grid_schema},
         },
-        MAX_COMMIT_NUM,
+        error::SchemaStoreError,
+        PropertyDefinition, Schema, SchemaList,
     },
 };
 use diesel::prelude::*;
@@ -33,9 +31,9 @@ use diesel::prelude::*;
 pub(in crate::schemas) trait ListSchemasOperation {
     fn list_schemas(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<SchemaList, SchemaStoreError>;
 }
 
@@ -43,23 +41,24 @@ pub(in crate::schemas) trait ListSchemasOperation {
 impl<'a> ListSchemasOperation for SchemaStoreOperations<'a, diesel::pg::PgConnection> {
     fn list_schemas(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<SchemaList, SchemaStoreError> {
-        let (db_schemas, total) = pg::fetch_grid_schemas(&*self.conn, service_id, offset, limit)?;
+        let height = commit_height_or_current(commit_height);
+        let (db_schemas, paging) = pg::fetch_grid_schemas(&*self.conn, height, service_id, page)?;
 
         let mut schemas = Vec::new();
 
         for schema in db_schemas {
-            let roots = pg::get_root_definitions(&*self.conn, &schema.name)?;
+            let roots = pg::get_root_definitions(&*self.conn, height, &schema.name)?;
 
             let properties = pg::get_property_definitions_for_schema(&*self.conn, roots)?;
 
             schemas.push(Schema::from((schema, properties)));
         }
 
-        Ok(SchemaList::new(schemas, Paging::new(offset, limit, total)))
+        Ok(SchemaList::new(schemas, paging))
     }
 }
 
@@ -67,24 +66,25 @@ impl<'a> ListSchemasOperation for SchemaStoreOperations<'a, diesel::pg::PgConnec
 impl<'a> ListSchemasOperation for SchemaStoreOperations<'a, diesel::sqlite::SqliteConnection> {
     fn list_schemas(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<SchemaList, SchemaStoreError> {
-        let (db_schemas, total) =
-            sqlite::fetch_grid_schemas(&*self.conn, service_id, offset, limit)?;
+        let height = commit_height_or_current(commit_height);
+        let (db_schemas, paging) =
+            sqlite::fetch_grid_schemas(&*self.conn, height, service_id, page)?;
 
         let mut schemas = Vec::new();
 
         for schema in db_schemas {
-            let roots = sqlite::get_root_definitions(&*self.conn, &schema.name)?;
+            let roots = sqlite::get_root_definitions(&*self.conn, height, &schema.name)?;
 
             let properties = sqlite::get_property_definitions_for_schema(&*self.conn, roots)?;
 
             schemas.push(Schema::from((schema, properties)));
         }
 
-        Ok(SchemaList::new(schemas, Paging::new(offset, limit, total)))
+        Ok(SchemaList::new(schemas, paging))
     }
 }
 
@@ -94,16 +94,18 @@ mod pg {
 
     pub fn fetch_grid_schemas(
         conn: &PgConnection,
+        height: i64,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
-    ) -> QueryResult<(Vec<GridSchema>, i64)> {
+        page: &PageRequest,
+    ) -> QueryResult<(Vec<GridSchema>, Paging)> {
         let mut query = grid_schema::table
             .into_boxed()
             .select(grid_schema::all_columns)
-            .offset(offset)
-            .limit(limit)
-            .filter(grid_schema::end_commit_num.eq(MAX_COMMIT_NUM));
+            .filter(
+                grid_schema::start_commit_num
+                    .le(height)
+                    .and(grid_schema::end_commit_num.gt(height)),
+            );
 
         if let Some(service_id) = service_id {
             query = query.filter(grid_schema::service_id.eq(service_id));
@@ -111,9 +113,16 @@ mod pg {
             query = query.filter(grid_schema::service_id.is_null());
         }
 
+        let schemas = paged_query(query, grid_schema::name, page).load::<GridSchema>(conn)?;
+
         let mut count_query = grid_schema::table
             .into_boxed()
-            .select(grid_schema::all_columns);
+            .select(grid_schema::all_columns)
+            .filter(
+                grid_schema::start_commit_num
+                    .le(height)
+                    .and(grid_schema::end_commit_num.gt(height)),
+            );
 
         if let Some(service_id) = service_id {
             count_query = count_query.filter(grid_schema::service_id.eq(service_id));
@@ -121,13 +130,17 @@ mod pg {
             count_query = count_query.filter(grid_schema::service_id.is_null());
         }
 
-        let total = count_query.count().get_result(conn)?;
-
-        query.load(conn).map(|list| (list, total))
+        Paging::for_page(
+            schemas,
+            page,
+            || count_query.count().get_result(conn),
+            |schema| schema.name.clone(),
+        )
     }
 
     pub fn get_root_definitions(
         conn: &PgConnection,
+        height: i64,
         schema_name: &str,
     ) -> QueryResult<Vec<GridPropertyDefinition>> {
         grid_property_definition::table
@@ -136,7 +149,11 @@ mod pg {
                 grid_property_definition::schema_name
                     .eq(schema_name)
                     .and(grid_property_definition::parent_name.is_null())
-                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        grid_property_definition::start_commit_num
+                            .le(height)
+                            .and(grid_property_definition::end_commit_num.gt(height)),
+                    ),
             )
             .load::<GridPropertyDefinition>(conn)
     }
@@ -173,16 +190,18 @@ mod sqlite {
 
     pub fn fetch_grid_schemas(
         conn: &SqliteConnection,
+        height: i64,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
-    ) -> QueryResult<(Vec<GridSchema>, i64)> {
+        page: &PageRequest,
+    ) -> QueryResult<(Vec<GridSchema>, Paging)> {
         let mut query = grid_schema::table
             .into_boxed()
             .select(grid_schema::all_columns)
-            .offset(offset)
-            .limit(limit)
-            .filter(grid_schema::end_commit_num.eq(MAX_COMMIT_NUM));
+            .filter(
+                grid_schema::start_commit_num
+                    .le(height)
+                    .and(grid_schema::end_commit_num.gt(height)),
+            );
 
         if let Some(service_id) = service_id {
             query = query.filter(grid_schema::service_id.eq(service_id));
@@ -190,9 +209,16 @@ mod sqlite {
             query = query.filter(grid_schema::service_id.is_null());
         }
 
+        let schemas = paged_query(query, grid_schema::name, page).load::<GridSchema>(conn)?;
+
         let mut count_query = grid_schema::table
             .into_boxed()
-            .select(grid_schema::all_columns);
+            .select(grid_schema::all_columns)
+            .filter(
+                grid_schema::start_commit_num
+                    .le(height)
+                    .and(grid_schema::end_commit_num.gt(height)),
+            );
 
         if let Some(service_id) = service_id {
             count_query = count_query.filter(grid_schema::service_id.eq(service_id));
@@ -200,13 +226,17 @@ mod sqlite {
             count_query = count_query.filter(grid_schema::service_id.is_null());
         }
 
-        let total = count_query.count().get_result(conn)?;
-
-        query.load(conn).map(|list| (list, total))
+        Paging::for_page(
+            schemas,
+            page,
+            || count_query.count().get_result(conn),
+            |schema| schema.name.clone(),
+        )
     }
 
     pub fn get_root_definitions(
         conn: &SqliteConnection,
+        height: i64,
         schema_name: &str,
     ) -> QueryResult<Vec<GridPropertyDefinition>> {
         grid_property_definition::table
@@ -215,7 +245,11 @@ mod sqlite {
                 grid_property_definition::schema_name
                     .eq(schema_name)
                     .and(grid_property_definition::parent_name.is_null())
-                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        grid_property_definition::start_commit_num
+                            .le(height)
+                            .and(grid_property_definition::end_commit_num.gt(height)),
+                    ),
             )
             .load::<GridPropertyDefinition>(conn)
     }
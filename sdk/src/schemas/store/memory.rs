@@ -12,84 +12,283 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::sync::{Arc, Mutex, MutexGuard};
 
+use crate::commits::{
+    commit_height_or_current, end_current_rows, impl_versioned, resolve_fork_rows, Versioned,
+};
 use crate::error::InternalError;
-use crate::schemas::store::{Schema, SchemaStore, SchemaStoreError};
+use crate::paging::{page_items, PageRequest};
+
+use super::{PropertyDefinition, Schema, SchemaList, SchemaStore, SchemaStoreError};
+
+impl_versioned!(Schema);
+
+/// A property definition without its struct properties, with the name of the definition it is
+/// a struct property of, if any
+#[derive(Clone)]
+struct DefinitionRow {
+    parent_name: Option<String>,
+    definition: PropertyDefinition,
+}
+
+impl Versioned for DefinitionRow {
+    fn start_commit_num(&self) -> i64 {
+        self.definition.start_commit_num
+    }
+
+    fn end_commit_num(&self) -> i64 {
+        self.definition.end_commit_num
+    }
+
+    fn set_end_commit_num(&mut self, end_commit_num: i64) {
+        self.definition.end_commit_num = end_commit_num;
+    }
+}
+
+fn definition_rows(
+    definitions: &[PropertyDefinition],
+    parent_name: Option<&str>,
+    rows: &mut Vec<DefinitionRow>,
+) {
+    for definition in definitions {
+        rows.push(DefinitionRow {
+            parent_name: parent_name.map(String::from),
+            definition: PropertyDefinition {
+                struct_properties: Vec::new(),
+                ..definition.clone()
+            },
+        });
+        definition_rows(&definition.struct_properties, Some(&definition.name), rows);
+    }
+}
 
+#[derive(Default)]
+struct SchemaState {
+    schemas: Vec<Schema>,
+    definitions: Vec<DefinitionRow>,
+}
+
+impl SchemaState {
+    fn definitions_at<'a>(
+        &'a self,
+        height: i64,
+        service_id: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a DefinitionRow> + 'a {
+        self.definitions.iter().filter(move |row| {
+            row.definition.service_id.as_deref() == service_id && row.is_current_at(height)
+        })
+    }
+
+    fn struct_properties(
+        &self,
+        schema_name: &str,
+        parent_name: Option<&str>,
+        height: i64,
+        service_id: Option<&str>,
+    ) -> Vec<PropertyDefinition> {
+        self.definitions_at(height, service_id)
+            .filter(|row| {
+                row.definition.schema_name == schema_name
+                    && row.parent_name.as_deref() == parent_name
+            })
+            .map(|row| PropertyDefinition {
+                struct_properties: self.struct_properties(
+                    schema_name,
+                    Some(&row.definition.name),
+                    height,
+                    service_id,
+                ),
+                ..row.definition.clone()
+            })
+            .collect()
+    }
+
+    fn schema_at(&self, schema: &Schema, height: i64, service_id: Option<&str>) -> Schema {
+        Schema {
+            properties: self.struct_properties(&schema.name, None, height, service_id),
+            ..schema.clone()
+        }
+    }
+}
+
+/// Implementation of `SchemaStore` that keeps schemas in memory, versioned by commit like the
+/// database-backed store. Useful for tests and when persistence isn't necessary.
 #[derive(Clone, Default)]
 pub struct MemorySchemaStore {
-    inner: Arc<Mutex<HashMap<String, Schema>>>,
+    inner: Arc<Mutex<SchemaState>>,
 }
 
 impl MemorySchemaStore {
     pub fn new() -> Self {
-        MemorySchemaStore {
-            inner: Arc::new(Mutex::new(HashMap::new())),
-        }
+        MemorySchemaStore::default()
+    }
+
+    /// Rolls the schemas back to their state before the given commit
+    ///
+    /// # Arguments
+    ///
+    ///  * `commit_num` - The number of the first commit that is no longer on the chain
+    pub fn resolve_fork(&self, commit_num: i64) -> Result<(), SchemaStoreError> {
+        let mut state = self.state()?;
+        resolve_fork_rows(&mut state.schemas, commit_num);
+        resolve_fork_rows(&mut state.definitions, commit_num);
+        Ok(())
+    }
+
+    /// Returns the data type of a schema's property definition that ended with the given commit,
+    /// as a record property is joined with its definition by the database-backed stores
+    #[cfg(feature = "track-and-trace")]
+    pub(crate) fn property_data_type(
+        &self,
+        schema_name: &str,
+        property_name: &str,
+        end_commit_num: i64,
+    ) -> Result<Option<String>, SchemaStoreError> {
+        let state = self.state()?;
+        Ok(state
+            .definitions
+            .iter()
+            .find(|row| {
+                row.definition.schema_name == schema_name
+                    && row.definition.name == property_name
+                    && row.definition.end_commit_num == end_commit_num
+            })
+            .map(|row| row.definition.data_type.clone()))
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, SchemaState>, SchemaStoreError> {
+        self.inner.lock().map_err(|_| {
+            SchemaStoreError::InternalError(InternalError::with_message(
+                "Cannot access schemas: mutex lock poisoned".to_string(),
+            ))
+        })
     }
 }
 
 impl SchemaStore for MemorySchemaStore {
     fn add_schema(&self, schema: Schema) -> Result<(), SchemaStoreError> {
-        let mut inner = self
-            .inner
-            .lock()
-            .map_err(|_| SchemaStoreError::InternalError(
-                InternalError::with_message("Cannot access schemas: mutex lock poisoned".to_string()
-            ))?;
+        let mut state = self.state()?;
 
-        let key = if let Some(ref service_id) = schema.service_id {
-            format!("{}:{}", schema.name, service_id)
-        } else {
-            schema.name.clone()
-        };
+        end_current_rows(&mut state.schemas, schema.start_commit_num, |current| {
+            current.name == schema.name && current.service_id == schema.service_id
+        });
 
-        inner.insert(key, schema);
+        let mut rows = Vec::new();
+        definition_rows(&schema.properties, None, &mut rows);
+        for row in rows {
+            end_current_rows(
+                &mut state.definitions,
+                row.definition.start_commit_num,
+                |current| {
+                    current.definition.schema_name == row.definition.schema_name
+                        && current.definition.name == row.definition.name
+                        && current.definition.service_id == row.definition.service_id
+                },
+            );
+            state.definitions.push(row);
+        }
 
+        state.schemas.push(Schema {
+            properties: Vec::new(),
+            ..schema
+        });
         Ok(())
     }
 
     fn fetch_schema(
         &self,
         name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Schema>, SchemaStoreError> {
-        let inner = self
-            .inner
-            .lock()
-            .map_err(|_| SchemaStoreError::InternalError(
-                InternalError::with_message("Cannot access schemas: mutex lock poisoned".to_string()
-            ))?;
-
-        let key = if let Some(ref service_id) = service_id {
-            format!("{}:{}", name, service_id)
-        } else {
-            name.to_string()
-        };
-
-        Ok(inner.get(&key).map(Schema::clone))
-    }
-
-    fn list_schemas(&self, service_id: Option<&str>) -> Result<Vec<Schema>, SchemaStoreError> {
-        let inner = self
-            .inner
-            .lock()
-            .map_err(|_| SchemaStoreError::InternalError(
-                InternalError::with_message("Cannot access schemas: mutex lock poisoned".to_string()
-            ))?;
-
-        if let Some(service_id) = service_id {
-            Ok(inner
-                .values()
-                .map(Schema::clone)
-                .filter(|v| v.service_id.is_some() && v.service_id.as_ref().unwrap() == service_id)
-                .collect())
-        } else {
-            Ok(inner.values().map(Schema::clone).collect())
-        }
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        Ok(state
+            .schemas
+            .iter()
+            .find(|schema| {
+                schema.name == name
+                    && schema.service_id.as_deref() == service_id
+                    && schema.is_current_at(height)
+            })
+            .map(|schema| state.schema_at(schema, height, service_id)))
+    }
+
+    fn list_schemas(
+        &self,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<SchemaList, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let schemas = state
+            .schemas
+            .iter()
+            .filter(|schema| {
+                schema.service_id.as_deref() == service_id && schema.is_current_at(height)
+            })
+            .collect();
+        let (schemas, paging) = page_items(schemas, page, |schema| schema.name.clone());
+
+        Ok(SchemaList::new(
+            schemas
+                .into_iter()
+                .map(|schema| state.schema_at(schema, height, service_id))
+                .collect(),
+            paging,
+        ))
+    }
+
+    fn list_property_definitions(
+        &self,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        Ok(state
+            .definitions_at(height, service_id)
+            .map(|row| row.definition.clone())
+            .collect())
+    }
+
+    fn list_property_definitions_with_schema_name(
+        &self,
+        schema_name: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<PropertyDefinition>, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        Ok(state
+            .definitions_at(height, service_id)
+            .filter(|row| row.definition.schema_name == schema_name)
+            .map(|row| row.definition.clone())
+            .collect())
+    }
+
+    fn get_property_definition_by_name(
+        &self,
+        schema_name: &str,
+        definition_name: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Option<PropertyDefinition>, SchemaStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let definition = state
+            .definitions_at(height, service_id)
+            .find(|row| {
+                row.definition.schema_name == schema_name && row.definition.name == definition_name
+            })
+            .map(|row| row.definition.clone());
+        Ok(definition)
     }
 }
@@ -15,8 +15,10 @@
 #[cfg(feature = "diesel")]
 pub mod diesel;
 mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
 
-use crate::paging::Paging;
+use crate::paging::{PageRequest, Paging};
 
 pub use error::SchemaStoreError;
 
@@ -75,10 +77,13 @@ pub trait SchemaStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `name` - Name of schema being fetched
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - Service ID needed for when the source of the schema is a splinter circuit
     fn fetch_schema(
         &self,
         name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Schema>, SchemaStoreError>;
 
@@ -86,24 +91,28 @@ pub trait SchemaStore: Send + Sync {
     ///
     /// # Arguments
     ///
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - Service ID needed for when the source of the schema
     ///  is a splinter circuit
-    ///  * `offset` - The index of the first in storage to retrieve
-    ///  * `limit` - The number of items to retrieve from the offset
+    ///  * `page` - The page to retrieve, by offset or by key
     fn list_schemas(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<SchemaList, SchemaStoreError>;
 
     /// List all property definitions in underlying storage
     ///
     /// # Arguments
     ///
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - Service ID needed for when the source of the schema is a splinter circuit
     fn list_property_definitions(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError>;
 
@@ -112,10 +121,13 @@ pub trait SchemaStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `schema_name` - The name of the schema to list property definitions for
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - Service ID needed for when the source of the schema is a splinter circuit
     fn list_property_definitions_with_schema_name(
         &self,
         schema_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<PropertyDefinition>, SchemaStoreError>;
 
@@ -125,11 +137,14 @@ pub trait SchemaStore: Send + Sync {
     ///
     ///  * `schema_name` - The name of the schema to list property definitions for
     ///  * `definition_name` - The name of the property definition to fetch
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - Service ID needed for when the source of the schema is a splinter circuit
     fn get_property_definition_by_name(
         &self,
         schema_name: &str,
         definition_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<PropertyDefinition>, SchemaStoreError>;
 }
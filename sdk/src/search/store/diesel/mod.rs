@@ -0,0 +1,118 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub(in crate::search) mod models;
+mod operations;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use super::{SearchEntityType, SearchEntry, SearchHitList, SearchStore, SearchStoreError};
+use crate::error::ResourceTemporarilyUnavailableError;
+use crate::paging::PageRequest;
+
+use operations::index_entry::SearchStoreIndexEntryOperation as _;
+use operations::remove_entry::SearchStoreRemoveEntryOperation as _;
+use operations::search::SearchStoreSearchOperation as _;
+use operations::SearchStoreOperations;
+
+/// Manages the search index in the database
+#[derive(Clone)]
+pub struct DieselSearchStore<C: diesel::Connection + 'static> {
+    connection_pool: Pool<ConnectionManager<C>>,
+}
+
+impl<C: diesel::Connection> DieselSearchStore<C> {
+    /// Creates a new DieselSearchStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselSearchStore { connection_pool }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl SearchStore for DieselSearchStore<diesel::pg::PgConnection> {
+    fn index_entry(&self, entry: SearchEntry) -> Result<(), SearchStoreError> {
+        SearchStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            SearchStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .index_entry(entry)
+    }
+
+    fn remove_entry(&self, address: &str) -> Result<(), SearchStoreError> {
+        SearchStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            SearchStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .remove_entry(address)
+    }
+
+    fn search(
+        &self,
+        query: &str,
+        entity_type: Option<SearchEntityType>,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<SearchHitList, SearchStoreError> {
+        SearchStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            SearchStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .search(query, entity_type, org_id, service_id, page)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl SearchStore for DieselSearchStore<diesel::sqlite::SqliteConnection> {
+    fn index_entry(&self, entry: SearchEntry) -> Result<(), SearchStoreError> {
+        SearchStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            SearchStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .index_entry(entry)
+    }
+
+    fn remove_entry(&self, address: &str) -> Result<(), SearchStoreError> {
+        SearchStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            SearchStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .remove_entry(address)
+    }
+
+    fn search(
+        &self,
+        query: &str,
+        entity_type: Option<SearchEntityType>,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<SearchHitList, SearchStoreError> {
+        SearchStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            SearchStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .search(query, entity_type, org_id, service_id, page)
+    }
+}
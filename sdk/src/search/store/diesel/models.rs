@@ -0,0 +1,35 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::sql_types::{BigInt, Double, Nullable, Text};
+
+#[derive(QueryableByName, Debug)]
+pub struct SearchHitModel {
+    #[sql_type = "Text"]
+    pub entity_type: String,
+    #[sql_type = "Text"]
+    pub entity_id: String,
+    #[sql_type = "Text"]
+    pub title: String,
+    #[sql_type = "Double"]
+    pub rank: f64,
+    #[sql_type = "Nullable<Text>"]
+    pub service_id: Option<String>,
+}
+
+#[derive(QueryableByName, Debug)]
+pub struct SearchCountModel {
+    #[sql_type = "BigInt"]
+    pub total: i64,
+}
@@ -0,0 +1,93 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::SearchStoreOperations;
+
+use crate::search::store::{SearchEntry, SearchStoreError};
+
+use diesel::{
+    prelude::*,
+    sql_query,
+    sql_types::{Nullable, Text},
+};
+
+pub(in crate::search::store::diesel) trait SearchStoreIndexEntryOperation {
+    fn index_entry(&self, entry: SearchEntry) -> Result<(), SearchStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> SearchStoreIndexEntryOperation for SearchStoreOperations<'a, diesel::pg::PgConnection> {
+    fn index_entry(&self, entry: SearchEntry) -> Result<(), SearchStoreError> {
+        self.conn.transaction::<_, SearchStoreError, _>(|| {
+            sql_query(
+                "DELETE FROM search_index WHERE entity_type = $1 AND entity_id = $2 \
+                 AND service_id IS NOT DISTINCT FROM $3",
+            )
+            .bind::<Text, _>(entry.entity_type.as_str())
+            .bind::<Text, _>(&entry.entity_id)
+            .bind::<Nullable<Text>, _>(&entry.service_id)
+            .execute(self.conn)?;
+
+            sql_query(
+                "INSERT INTO search_index \
+                 (entity_type, entity_id, address, title, content, service_id, document) \
+                 VALUES ($1, $2, $3, $4, $5, $6, \
+                 setweight(to_tsvector('simple', $4), 'A') \
+                 || setweight(to_tsvector('simple', $5), 'B'))",
+            )
+            .bind::<Text, _>(entry.entity_type.as_str())
+            .bind::<Text, _>(&entry.entity_id)
+            .bind::<Text, _>(&entry.address)
+            .bind::<Text, _>(&entry.title)
+            .bind::<Text, _>(&entry.content)
+            .bind::<Nullable<Text>, _>(&entry.service_id)
+            .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> SearchStoreIndexEntryOperation
+    for SearchStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn index_entry(&self, entry: SearchEntry) -> Result<(), SearchStoreError> {
+        self.conn.transaction::<_, SearchStoreError, _>(|| {
+            sql_query(
+                "DELETE FROM search_index WHERE entity_type = ? AND entity_id = ? \
+                 AND service_id IS ?",
+            )
+            .bind::<Text, _>(entry.entity_type.as_str())
+            .bind::<Text, _>(&entry.entity_id)
+            .bind::<Nullable<Text>, _>(&entry.service_id)
+            .execute(self.conn)?;
+
+            sql_query(
+                "INSERT INTO search_index \
+                 (entity_type, entity_id, address, title, content, service_id) \
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind::<Text, _>(entry.entity_type.as_str())
+            .bind::<Text, _>(&entry.entity_id)
+            .bind::<Text, _>(&entry.address)
+            .bind::<Text, _>(&entry.title)
+            .bind::<Text, _>(&entry.content)
+            .bind::<Nullable<Text>, _>(&entry.service_id)
+            .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
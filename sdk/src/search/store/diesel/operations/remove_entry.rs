@@ -0,0 +1,47 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::SearchStoreOperations;
+
+use crate::search::store::SearchStoreError;
+
+use diesel::{prelude::*, sql_query, sql_types::Text};
+
+pub(in crate::search::store::diesel) trait SearchStoreRemoveEntryOperation {
+    fn remove_entry(&self, address: &str) -> Result<(), SearchStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> SearchStoreRemoveEntryOperation for SearchStoreOperations<'a, diesel::pg::PgConnection> {
+    fn remove_entry(&self, address: &str) -> Result<(), SearchStoreError> {
+        sql_query("DELETE FROM search_index WHERE address = $1")
+            .bind::<Text, _>(address)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> SearchStoreRemoveEntryOperation
+    for SearchStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_entry(&self, address: &str) -> Result<(), SearchStoreError> {
+        sql_query("DELETE FROM search_index WHERE address = ?")
+            .bind::<Text, _>(address)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
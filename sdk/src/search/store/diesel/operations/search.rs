@@ -0,0 +1,352 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::SearchStoreOperations;
+
+use crate::commits::MAX_COMMIT_NUM;
+use crate::error::InternalError;
+use crate::paging::{PageDirection, PageRequest, Paging};
+use crate::search::store::{
+    diesel::models::{SearchCountModel, SearchHitModel},
+    SearchEntityType, SearchHit, SearchHitList, SearchStoreError,
+};
+
+use diesel::{
+    prelude::*,
+    sql_query,
+    sql_types::{BigInt, Double, Nullable, Text},
+};
+
+pub(in crate::search::store::diesel) trait SearchStoreSearchOperation {
+    fn search(
+        &self,
+        query: &str,
+        entity_type: Option<SearchEntityType>,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<SearchHitList, SearchStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> SearchStoreSearchOperation for SearchStoreOperations<'a, diesel::pg::PgConnection> {
+    fn search(
+        &self,
+        query: &str,
+        entity_type: Option<SearchEntityType>,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<SearchHitList, SearchStoreError> {
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return empty_hit_list(page);
+        }
+
+        // Every term must match, as the prefix of a word
+        let ts_query = terms
+            .iter()
+            .map(|term| format!("{}:*", term))
+            .collect::<Vec<_>>()
+            .join(" & ");
+        let entity_type = entity_type.map(|entity_type| entity_type.as_str());
+
+        // Given an organization, products and locations are only found while it owns them
+        let hits = "SELECT entity_type, entity_id, title, service_id, \
+             ts_rank(document, query)::float8 AS rank \
+             FROM search_index, to_tsquery('simple', $1) AS query \
+             WHERE document @@ query \
+             AND service_id IS NOT DISTINCT FROM $2 \
+             AND entity_type = COALESCE($3, entity_type) \
+             AND ($4::text IS NULL OR entity_type = 'organization' \
+             OR EXISTS (SELECT 1 FROM product WHERE search_index.entity_type = 'product' \
+             AND product.product_id = search_index.entity_id AND product.owner = $4 \
+             AND product.end_commit_num = $5 \
+             AND product.service_id IS NOT DISTINCT FROM $2) \
+             OR EXISTS (SELECT 1 FROM location WHERE search_index.entity_type = 'location' \
+             AND location.location_id = search_index.entity_id AND location.owner = $4 \
+             AND location.end_commit_num = $5 \
+             AND location.service_id IS NOT DISTINCT FROM $2))";
+        let search = |sql: String| {
+            sql_query(sql)
+                .bind::<Text, _>(&ts_query)
+                .bind::<Nullable<Text>, _>(service_id)
+                .bind::<Nullable<Text>, _>(entity_type)
+                .bind::<Nullable<Text>, _>(org_id)
+                .bind::<BigInt, _>(MAX_COMMIT_NUM)
+        };
+
+        let rows = match page {
+            PageRequest::Offset { offset, limit } => search(paged_sql(hits, page, '$'))
+                .bind::<BigInt, _>(limit)
+                .bind::<BigInt, _>(offset)
+                .load::<SearchHitModel>(self.conn)?,
+            PageRequest::Keyset {
+                key: Some(key),
+                limit,
+                ..
+            } => {
+                let (rank, entity_type, entity_id) = parse_hit_key(key)?;
+                search(paged_sql(hits, page, '$'))
+                    .bind::<Double, _>(rank)
+                    .bind::<Text, _>(entity_type)
+                    .bind::<Text, _>(entity_id)
+                    .bind::<BigInt, _>(limit + 1)
+                    .load::<SearchHitModel>(self.conn)?
+            }
+            PageRequest::Keyset {
+                key: None, limit, ..
+            } => search(paged_sql(hits, page, '$'))
+                .bind::<BigInt, _>(limit + 1)
+                .load::<SearchHitModel>(self.conn)?,
+        };
+
+        let (hits, paging) = Paging::for_page(
+            to_search_hits(rows)?,
+            page,
+            || {
+                search(format!("SELECT COUNT(*) AS total FROM ({}) AS hits", hits))
+                    .get_result::<SearchCountModel>(self.conn)
+                    .map(|count| count.total)
+            },
+            hit_key,
+        )?;
+
+        Ok(SearchHitList::new(hits, paging))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> SearchStoreSearchOperation
+    for SearchStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn search(
+        &self,
+        query: &str,
+        entity_type: Option<SearchEntityType>,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<SearchHitList, SearchStoreError> {
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return empty_hit_list(page);
+        }
+
+        // Every term must match, as the prefix of a word
+        let fts_query = terms
+            .iter()
+            .map(|term| format!("\"{}\"*", term))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let entity_type = entity_type.map(|entity_type| entity_type.as_str());
+
+        // bm25 is lower for better matches; its weights rank the title above the content, in
+        // the column order of the search_index table. Given an organization, products and
+        // locations are only found while it owns them
+        let hits = "SELECT entity_type, entity_id, title, service_id, \
+             -bm25(search_index, 0.0, 0.0, 0.0, 2.0, 1.0, 0.0) AS rank \
+             FROM search_index \
+             WHERE search_index MATCH ?1 \
+             AND service_id IS ?2 \
+             AND entity_type = COALESCE(?3, entity_type) \
+             AND (?4 IS NULL OR entity_type = 'organization' \
+             OR EXISTS (SELECT 1 FROM product WHERE search_index.entity_type = 'product' \
+             AND product.product_id = search_index.entity_id AND product.owner = ?4 \
+             AND product.end_commit_num = ?5 AND product.service_id IS ?2) \
+             OR EXISTS (SELECT 1 FROM location WHERE search_index.entity_type = 'location' \
+             AND location.location_id = search_index.entity_id AND location.owner = ?4 \
+             AND location.end_commit_num = ?5 AND location.service_id IS ?2))";
+        let search = |sql: String| {
+            sql_query(sql)
+                .bind::<Text, _>(&fts_query)
+                .bind::<Nullable<Text>, _>(service_id)
+                .bind::<Nullable<Text>, _>(entity_type)
+                .bind::<Nullable<Text>, _>(org_id)
+                .bind::<BigInt, _>(MAX_COMMIT_NUM)
+        };
+
+        let rows = match page {
+            PageRequest::Offset { offset, limit } => search(paged_sql(hits, page, '?'))
+                .bind::<BigInt, _>(limit)
+                .bind::<BigInt, _>(offset)
+                .load::<SearchHitModel>(self.conn)?,
+            PageRequest::Keyset {
+                key: Some(key),
+                limit,
+                ..
+            } => {
+                let (rank, entity_type, entity_id) = parse_hit_key(key)?;
+                search(paged_sql(hits, page, '?'))
+                    .bind::<Double, _>(rank)
+                    .bind::<Text, _>(entity_type)
+                    .bind::<Text, _>(entity_id)
+                    .bind::<BigInt, _>(limit + 1)
+                    .load::<SearchHitModel>(self.conn)?
+            }
+            PageRequest::Keyset {
+                key: None, limit, ..
+            } => search(paged_sql(hits, page, '?'))
+                .bind::<BigInt, _>(limit + 1)
+                .load::<SearchHitModel>(self.conn)?,
+        };
+
+        let (hits, paging) = Paging::for_page(
+            to_search_hits(rows)?,
+            page,
+            || {
+                search(format!("SELECT COUNT(*) AS total FROM ({}) AS hits", hits))
+                    .get_result::<SearchCountModel>(self.conn)
+                    .map(|count| count.total)
+            },
+            hit_key,
+        )?;
+
+        Ok(SearchHitList::new(hits, paging))
+    }
+}
+
+/// Wraps the SQL of the hits of a search to read a page of them, best matches first. The
+/// search's own params are numbered from 1 to 5, so the page's params are numbered from 6 with
+/// the given prefix: the limit and offset of an offset page, or the rank, entity type and
+/// entity ID of the key and the limit of a keyset page.
+fn paged_sql(hits: &str, page: &PageRequest, prefix: char) -> String {
+    match page {
+        PageRequest::Offset { .. } => format!(
+            "SELECT * FROM ({hits}) AS hits ORDER BY rank DESC, entity_type, entity_id \
+             LIMIT {p}6 OFFSET {p}7",
+            hits = hits,
+            p = prefix
+        ),
+        PageRequest::Keyset {
+            key: Some(_),
+            direction: PageDirection::Next,
+            ..
+        } => format!(
+            "SELECT * FROM ({hits}) AS hits \
+             WHERE rank < {p}6 OR (rank = {p}6 AND (entity_type, entity_id) > ({p}7, {p}8)) \
+             ORDER BY rank DESC, entity_type, entity_id LIMIT {p}9",
+            hits = hits,
+            p = prefix
+        ),
+        PageRequest::Keyset {
+            key: Some(_),
+            direction: PageDirection::Previous,
+            ..
+        } => format!(
+            "SELECT * FROM ({hits}) AS hits \
+             WHERE rank > {p}6 OR (rank = {p}6 AND (entity_type, entity_id) < ({p}7, {p}8)) \
+             ORDER BY rank, entity_type DESC, entity_id DESC LIMIT {p}9",
+            hits = hits,
+            p = prefix
+        ),
+        PageRequest::Keyset {
+            key: None,
+            direction: PageDirection::Next,
+            ..
+        } => format!(
+            "SELECT * FROM ({hits}) AS hits ORDER BY rank DESC, entity_type, entity_id \
+             LIMIT {p}6",
+            hits = hits,
+            p = prefix
+        ),
+        PageRequest::Keyset {
+            key: None,
+            direction: PageDirection::Previous,
+            ..
+        } => format!(
+            "SELECT * FROM ({hits}) AS hits ORDER BY rank, entity_type DESC, entity_id DESC \
+             LIMIT {p}6",
+            hits = hits,
+            p = prefix
+        ),
+    }
+}
+
+/// Returns the key of a hit, by which keyset pages continue from it in rank order
+fn hit_key(hit: &SearchHit) -> String {
+    format!(
+        "{}:{}:{}",
+        hit.rank,
+        hit.entity_type.as_str(),
+        hit.entity_id
+    )
+}
+
+/// Splits a key made by `hit_key` into the rank, entity type and entity ID of its hit
+fn parse_hit_key(key: &str) -> Result<(f64, &str, &str), SearchStoreError> {
+    let invalid = || {
+        SearchStoreError::InternalError(InternalError::with_message(format!(
+            "Invalid search key: {}",
+            key
+        )))
+    };
+
+    let mut parts = key.splitn(3, ':');
+    let rank = parts
+        .next()
+        .and_then(|rank| rank.parse::<f64>().ok())
+        .ok_or_else(invalid)?;
+    let entity_type = parts.next().ok_or_else(invalid)?;
+    let entity_id = parts.next().ok_or_else(invalid)?;
+
+    Ok((rank, entity_type, entity_id))
+}
+
+fn empty_hit_list(page: &PageRequest) -> Result<SearchHitList, SearchStoreError> {
+    let (hits, paging) =
+        Paging::for_page::<_, SearchStoreError, _, _>(vec![], page, || Ok(0), hit_key)?;
+    Ok(SearchHitList::new(hits, paging))
+}
+
+/// Splits a query into lowercase words, dropping punctuation so that it cannot be read as
+/// search syntax by the database
+fn search_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn to_search_hits(hits: Vec<SearchHitModel>) -> Result<Vec<SearchHit>, SearchStoreError> {
+    hits.into_iter()
+        .map(|hit| {
+            Ok(SearchHit {
+                entity_type: hit.entity_type.parse().map_err(|err| {
+                    SearchStoreError::InternalError(InternalError::from_source(Box::new(err)))
+                })?,
+                entity_id: hit.entity_id,
+                title: hit.title,
+                rank: hit.rank,
+                service_id: hit.service_id,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test that queries are split into lowercase words without punctuation
+    fn test_search_terms() {
+        assert_eq!(
+            search_terms("Acme \"Deluxe\" bulb-60W*"),
+            vec!["acme", "deluxe", "bulb", "60w"]
+        );
+        assert!(search_terms(" :* & ").is_empty());
+    }
+}
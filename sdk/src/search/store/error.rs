@@ -0,0 +1,75 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "diesel")]
+use crate::error::ConstraintViolationType;
+use crate::error::{ConstraintViolationError, InternalError, ResourceTemporarilyUnavailableError};
+
+/// Represents SearchStore errors
+#[derive(Debug)]
+pub enum SearchStoreError {
+    InternalError(InternalError),
+    ConstraintViolationError(ConstraintViolationError),
+    ResourceTemporarilyUnavailableError(ResourceTemporarilyUnavailableError),
+}
+
+impl Error for SearchStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SearchStoreError::InternalError(err) => Some(err),
+            SearchStoreError::ConstraintViolationError(err) => Some(err),
+            SearchStoreError::ResourceTemporarilyUnavailableError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for SearchStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SearchStoreError::InternalError(err) => err.fmt(f),
+            SearchStoreError::ConstraintViolationError(err) => err.fmt(f),
+            SearchStoreError::ResourceTemporarilyUnavailableError(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for SearchStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        match err {
+            diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            ) => SearchStoreError::ConstraintViolationError(
+                ConstraintViolationError::from_source_with_violation_type(
+                    ConstraintViolationType::Unique,
+                    Box::new(err),
+                ),
+            ),
+            _ => SearchStoreError::InternalError(InternalError::from_source(Box::new(err))),
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for SearchStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> SearchStoreError {
+        SearchStoreError::ResourceTemporarilyUnavailableError(
+            ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+        )
+    }
+}
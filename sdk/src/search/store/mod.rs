@@ -0,0 +1,244 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+pub mod error;
+
+use std::str::FromStr;
+
+#[cfg(feature = "location")]
+use crate::locations::store::{Location, LocationAttribute};
+use crate::paging::{PageRequest, Paging};
+#[cfg(feature = "pike")]
+use crate::pike::store::Organization;
+#[cfg(feature = "product")]
+use crate::products::store::{Product, PropertyValue};
+
+pub use error::SearchStoreError;
+
+/// The kinds of entities kept in the search index
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SearchEntityType {
+    Product,
+    Location,
+    Organization,
+}
+
+impl SearchEntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchEntityType::Product => "product",
+            SearchEntityType::Location => "location",
+            SearchEntityType::Organization => "organization",
+        }
+    }
+}
+
+impl FromStr for SearchEntityType {
+    type Err = ParseSearchEntityTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(SearchEntityType::Product),
+            "location" => Ok(SearchEntityType::Location),
+            "organization" => Ok(SearchEntityType::Organization),
+            _ => Err(ParseSearchEntityTypeError(s.to_string())),
+        }
+    }
+}
+
+/// Errors raised by trying to parse a `SearchEntityType`
+#[derive(Debug)]
+pub struct ParseSearchEntityTypeError(pub String);
+
+impl std::error::Error for ParseSearchEntityTypeError {}
+
+impl std::fmt::Display for ParseSearchEntityTypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Unknown search entity type: {}", self.0)
+    }
+}
+
+/// An entity as it is kept in the search index
+///
+/// The title is ranked above the content when searching.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchEntry {
+    pub entity_type: SearchEntityType,
+    pub entity_id: String,
+    pub address: String,
+    pub title: String,
+    pub content: String,
+    pub service_id: Option<String>,
+}
+
+#[cfg(feature = "product")]
+fn product_property_strings(values: &[PropertyValue], strings: &mut Vec<String>) {
+    for value in values {
+        strings.extend(value.string_value.iter().cloned());
+        product_property_strings(&value.struct_values, strings);
+    }
+}
+
+#[cfg(feature = "product")]
+impl From<&Product> for SearchEntry {
+    fn from(product: &Product) -> Self {
+        let mut content = vec![product.product_namespace.clone(), product.owner.clone()];
+        product_property_strings(&product.properties, &mut content);
+
+        SearchEntry {
+            entity_type: SearchEntityType::Product,
+            entity_id: product.product_id.clone(),
+            address: product.product_address.clone(),
+            title: product.product_id.clone(),
+            content: content.join(" "),
+            service_id: product.service_id.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "location")]
+fn location_attribute_strings(attributes: &[LocationAttribute], strings: &mut Vec<String>) {
+    for attribute in attributes {
+        strings.extend(attribute.string_value.iter().cloned());
+        if let Some(struct_values) = &attribute.struct_values {
+            location_attribute_strings(struct_values, strings);
+        }
+    }
+}
+
+#[cfg(feature = "location")]
+impl From<&Location> for SearchEntry {
+    fn from(location: &Location) -> Self {
+        let mut content = vec![location.location_namespace.clone(), location.owner.clone()];
+        location_attribute_strings(&location.attributes, &mut content);
+
+        SearchEntry {
+            entity_type: SearchEntityType::Location,
+            entity_id: location.location_id.clone(),
+            address: location.location_address.clone(),
+            title: location.location_id.clone(),
+            content: content.join(" "),
+            service_id: location.service_id.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "pike")]
+impl From<&Organization> for SearchEntry {
+    fn from(organization: &Organization) -> Self {
+        let mut content = vec![organization.org_id.clone()];
+        content.extend(
+            organization
+                .metadata
+                .iter()
+                .map(|metadata| metadata.value.clone()),
+        );
+
+        SearchEntry {
+            entity_type: SearchEntityType::Organization,
+            entity_id: organization.org_id.clone(),
+            address: organization.address.clone(),
+            title: organization.name.clone(),
+            content: content.join(" "),
+            service_id: organization.service_id.clone(),
+        }
+    }
+}
+
+/// An entity matching a search, with its rank among the results
+///
+/// Hits with a higher rank match the search more closely.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit {
+    pub entity_type: SearchEntityType,
+    pub entity_id: String,
+    pub title: String,
+    pub rank: f64,
+    pub service_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchHitList {
+    pub data: Vec<SearchHit>,
+    pub paging: Paging,
+}
+
+impl SearchHitList {
+    pub fn new(data: Vec<SearchHit>, paging: Paging) -> Self {
+        Self { data, paging }
+    }
+}
+
+pub trait SearchStore: Send + Sync {
+    /// Adds an entity to the search index, replacing the entry it had before
+    ///
+    /// # Arguments
+    ///
+    ///  * `entry` - The entity to index
+    fn index_entry(&self, entry: SearchEntry) -> Result<(), SearchStoreError>;
+
+    /// Removes the entity at an address from the search index
+    ///
+    /// # Arguments
+    ///
+    ///  * `address` - The address of the entity to remove
+    fn remove_entry(&self, address: &str) -> Result<(), SearchStoreError>;
+
+    /// Searches the index for entities matching every word of a query, best matches first.
+    /// Words match any word of an entity that they are a prefix of.
+    ///
+    /// # Arguments
+    ///
+    ///  * `query` - The words to search for
+    ///  * `entity_type` - optional - The kind of entity to search for, or every kind if not
+    ///    provided
+    ///  * `org_id` - optional - The organization that must currently own the products and
+    ///    locations found; organizations are found whatever its value
+    ///  * `service_id` - optional - The service ID to search entities of
+    ///  * `page` - The page to retrieve, by offset or by the key of a hit in rank order
+    fn search(
+        &self,
+        query: &str,
+        entity_type: Option<SearchEntityType>,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<SearchHitList, SearchStoreError>;
+}
+
+impl<SS> SearchStore for Box<SS>
+where
+    SS: SearchStore + ?Sized,
+{
+    fn index_entry(&self, entry: SearchEntry) -> Result<(), SearchStoreError> {
+        (**self).index_entry(entry)
+    }
+
+    fn remove_entry(&self, address: &str) -> Result<(), SearchStoreError> {
+        (**self).remove_entry(address)
+    }
+
+    fn search(
+        &self,
+        query: &str,
+        entity_type: Option<SearchEntityType>,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<SearchHitList, SearchStoreError> {
+        (**self).search(query, entity_type, org_id, service_id, page)
+    }
+}
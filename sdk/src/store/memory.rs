@@ -0,0 +1,187 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::commits::{
+    store::{Commit, CommitEvent, CommitEventError, CommitStoreError},
+    CommitStore, MemoryCommitStore,
+};
+use crate::error::InternalError;
+
+use super::StoreFactory;
+
+/// A `StoreFactory` whose stores keep their state in memory.
+///
+/// All stores created by the same factory share their state, and resolving a fork with its
+/// `CommitStore` rolls back every store, as it would in a database.
+#[derive(Clone, Default)]
+pub struct MemoryStoreFactory {
+    commit_store: MemoryCommitStore,
+    pike_store: crate::pike::MemoryPikeStore,
+    schema_store: crate::schemas::MemorySchemaStore,
+    #[cfg(feature = "location")]
+    location_store: crate::locations::MemoryLocationStore,
+    #[cfg(feature = "product")]
+    product_store: crate::products::MemoryProductStore,
+    #[cfg(feature = "track-and-trace")]
+    track_and_trace_store: crate::track_and_trace::MemoryTrackAndTraceStore,
+    #[cfg(feature = "batch-store")]
+    batch_store: crate::batches::MemoryBatchStore,
+    #[cfg(feature = "change-log")]
+    change_log_store: crate::change_log::MemoryChangeLogStore,
+    #[cfg(feature = "webhooks")]
+    webhook_store: crate::webhooks::MemoryWebhookStore,
+}
+
+impl MemoryStoreFactory {
+    pub fn new() -> Self {
+        let pike_store = crate::pike::MemoryPikeStore::new();
+        let schema_store = crate::schemas::MemorySchemaStore::new();
+        Self {
+            commit_store: MemoryCommitStore::new(),
+            #[cfg(feature = "track-and-trace")]
+            track_and_trace_store: crate::track_and_trace::MemoryTrackAndTraceStore::new(
+                schema_store.clone(),
+                pike_store.clone(),
+            ),
+            pike_store,
+            schema_store,
+            #[cfg(feature = "location")]
+            location_store: crate::locations::MemoryLocationStore::new(),
+            #[cfg(feature = "product")]
+            product_store: crate::products::MemoryProductStore::new(),
+            #[cfg(feature = "batch-store")]
+            batch_store: crate::batches::MemoryBatchStore::new(),
+            #[cfg(feature = "change-log")]
+            change_log_store: crate::change_log::MemoryChangeLogStore::new(),
+            #[cfg(feature = "webhooks")]
+            webhook_store: crate::webhooks::MemoryWebhookStore::new(),
+        }
+    }
+
+    fn resolve_fork(&self, commit_num: i64) -> Result<(), CommitStoreError> {
+        fn internal_error<E: std::error::Error + 'static>(err: E) -> CommitStoreError {
+            CommitStoreError::InternalError(InternalError::from_source(Box::new(err)))
+        }
+
+        self.pike_store
+            .resolve_fork(commit_num)
+            .map_err(internal_error)?;
+        self.schema_store
+            .resolve_fork(commit_num)
+            .map_err(internal_error)?;
+        #[cfg(feature = "location")]
+        self.location_store
+            .resolve_fork(commit_num)
+            .map_err(internal_error)?;
+        #[cfg(feature = "product")]
+        self.product_store
+            .resolve_fork(commit_num)
+            .map_err(internal_error)?;
+        #[cfg(feature = "track-and-trace")]
+        self.track_and_trace_store
+            .resolve_fork(commit_num)
+            .map_err(internal_error)?;
+        self.commit_store.resolve_fork(commit_num)
+    }
+}
+
+impl StoreFactory for MemoryStoreFactory {
+    fn get_grid_commit_store(&self) -> Box<dyn CommitStore> {
+        Box::new(FactoryCommitStore(self.clone()))
+    }
+
+    #[cfg(feature = "pike")]
+    fn get_grid_pike_store(&self) -> Box<dyn crate::pike::PikeStore> {
+        Box::new(self.pike_store.clone())
+    }
+
+    #[cfg(feature = "location")]
+    fn get_grid_location_store(&self) -> Box<dyn crate::locations::LocationStore> {
+        Box::new(self.location_store.clone())
+    }
+
+    #[cfg(feature = "product")]
+    fn get_grid_product_store(&self) -> Box<dyn crate::products::ProductStore> {
+        Box::new(self.product_store.clone())
+    }
+
+    #[cfg(feature = "schema")]
+    fn get_grid_schema_store(&self) -> Box<dyn crate::schemas::SchemaStore> {
+        Box::new(self.schema_store.clone())
+    }
+
+    #[cfg(feature = "track-and-trace")]
+    fn get_grid_track_and_trace_store(
+        &self,
+    ) -> Box<dyn crate::track_and_trace::TrackAndTraceStore> {
+        Box::new(self.track_and_trace_store.clone())
+    }
+
+    #[cfg(feature = "batch-store")]
+    fn get_batch_store(&self) -> Box<dyn crate::batches::BatchStore> {
+        Box::new(self.batch_store.clone())
+    }
+
+    #[cfg(feature = "change-log")]
+    fn get_change_log_store(&self) -> Box<dyn crate::change_log::ChangeLogStore> {
+        Box::new(self.change_log_store.clone())
+    }
+
+    #[cfg(feature = "webhooks")]
+    fn get_webhook_store(&self) -> Box<dyn crate::webhooks::WebhookStore> {
+        Box::new(self.webhook_store.clone())
+    }
+}
+
+/// The `CommitStore` of a `MemoryStoreFactory`, which resolves forks in all of the factory's
+/// stores
+struct FactoryCommitStore(MemoryStoreFactory);
+
+impl CommitStore for FactoryCommitStore {
+    fn add_commit(&self, commit: Commit) -> Result<(), CommitStoreError> {
+        self.0.commit_store.add_commit(commit)
+    }
+
+    fn get_commit_by_commit_num(
+        &self,
+        commit_num: i64,
+    ) -> Result<Option<Commit>, CommitStoreError> {
+        self.0.commit_store.get_commit_by_commit_num(commit_num)
+    }
+
+    fn get_commit_by_commit_id(&self, commit_id: &str) -> Result<Option<Commit>, CommitStoreError> {
+        self.0.commit_store.get_commit_by_commit_id(commit_id)
+    }
+
+    fn get_current_commit_id(&self) -> Result<Option<String>, CommitStoreError> {
+        self.0.commit_store.get_current_commit_id()
+    }
+
+    fn get_next_commit_num(&self) -> Result<i64, CommitStoreError> {
+        self.0.commit_store.get_next_commit_num()
+    }
+
+    fn resolve_fork(&self, commit_num: i64) -> Result<(), CommitStoreError> {
+        self.0.resolve_fork(commit_num)
+    }
+
+    fn create_db_commit_from_commit_event(
+        &self,
+        event: &CommitEvent,
+    ) -> Result<Option<Commit>, CommitEventError> {
+        self.0
+            .commit_store
+            .create_db_commit_from_commit_event(event)
+    }
+}
@@ -12,6 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(all(
+    test,
+    feature = "memory",
+    feature = "sqlite",
+    feature = "location",
+    feature = "product",
+    feature = "track-and-trace",
+    feature = "batch-store"
+))]
+mod conformance;
+#[cfg(feature = "memory")]
+pub mod memory;
 #[cfg(feature = "postgres")]
 pub mod postgres;
 #[cfg(feature = "sqlite")]
@@ -44,6 +56,12 @@ pub trait StoreFactory {
         -> Box<dyn crate::track_and_trace::TrackAndTraceStore>;
     #[cfg(feature = "batch-store")]
     fn get_batch_store(&self) -> Box<dyn crate::batches::BatchStore>;
+    /// Get a new `ChangeLogStore`
+    #[cfg(feature = "change-log")]
+    fn get_change_log_store(&self) -> Box<dyn crate::change_log::ChangeLogStore>;
+    /// Get a new `WebhookStore`
+    #[cfg(feature = "webhooks")]
+    fn get_webhook_store(&self) -> Box<dyn crate::webhooks::WebhookStore>;
 }
 
 /// Creates a `StoreFactory` backed by the given connection
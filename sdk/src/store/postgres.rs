@@ -70,4 +70,16 @@ impl StoreFactory for PgStoreFactory {
     fn get_batch_store(&self) -> Box<dyn crate::batches::BatchStore> {
         Box::new(crate::batches::DieselBatchStore::new(self.pool.clone()))
     }
+
+    #[cfg(feature = "change-log")]
+    fn get_change_log_store(&self) -> Box<dyn crate::change_log::ChangeLogStore> {
+        Box::new(crate::change_log::DieselChangeLogStore::new(
+            self.pool.clone(),
+        ))
+    }
+
+    #[cfg(feature = "webhooks")]
+    fn get_webhook_store(&self) -> Box<dyn crate::webhooks::WebhookStore> {
+        Box::new(crate::webhooks::DieselWebhookStore::new(self.pool.clone()))
+    }
 }
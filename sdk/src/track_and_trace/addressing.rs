@@ -23,6 +23,10 @@ pub const TRACK_AND_TRACE_PROPERTY_NAMESPACE: &str = "a43b46ea";
 pub const TRACK_AND_TRACE_PROPOSAL_NAMESPACE: &str = "a43b46aa";
 pub const TRACK_AND_TRACE_RECORD_NAMESPACE: &str = "a43b46ec";
 
+/// The highest page number a property page address can hold. Page 0 is the property itself,
+/// so reported values live on the ring of pages 1 through 0xffff.
+pub const MAX_PROPERTY_PAGE: u32 = 0xffff;
+
 fn hash(to_hash: &str, num: usize) -> String {
     let mut sha = Sha512::new();
     sha.input_str(to_hash);
@@ -50,3 +54,28 @@ pub fn make_proposal_address(record_id: &str, agent_id: &str) -> String {
 pub fn num_to_page_number(page: u32) -> String {
     format!("{:01$x}", page, 4)
 }
+
+/// Returns the page that follows `page` on the property page ring
+pub fn next_page_number(page: u32) -> u32 {
+    if page >= MAX_PROPERTY_PAGE {
+        1
+    } else {
+        page + 1
+    }
+}
+
+/// Returns the page holding the earliest reported values of a property, given the property's
+/// current page and whether the ring has wrapped
+pub fn earliest_page_number(current_page: u32, wrapped: bool) -> u32 {
+    if wrapped {
+        next_page_number(current_page)
+    } else {
+        1
+    }
+}
+
+/// Returns how many pages after `earliest_page` the given page sits on the ring, where the
+/// earliest page itself is at position 0
+pub fn page_ring_position(page: u32, earliest_page: u32) -> u32 {
+    (page + MAX_PROPERTY_PAGE - earliest_page) % MAX_PROPERTY_PAGE
+}
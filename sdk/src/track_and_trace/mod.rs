@@ -17,4 +17,6 @@ pub mod store;
 
 #[cfg(feature = "diesel")]
 pub use store::diesel::DieselTrackAndTraceStore;
+#[cfg(feature = "memory")]
+pub use store::memory::MemoryTrackAndTraceStore;
 pub use store::TrackAndTraceStore;
@@ -24,13 +24,15 @@ use super::diesel::models::{
     RecordModel, ReportedValueReporterToAgentMetadataModel, ReporterModel,
 };
 use super::{
-    AssociatedAgent, LatLongValue, Property, Proposal, Record, RecordList, ReportedValue,
-    ReportedValueReporterToAgentMetadata, Reporter, TrackAndTraceStore, TrackAndTraceStoreError,
+    AssociatedAgent, LatLongValue, Property, Proposal, Record, RecordFilter, RecordList,
+    ReportedValue, ReportedValueReporterToAgentMetadata, Reporter, TrackAndTraceStore,
+    TrackAndTraceStoreError,
 };
 use crate::error::{
     ConstraintViolationError, ConstraintViolationType, InternalError,
     ResourceTemporarilyUnavailableError,
 };
+use crate::paging::PageRequest;
 use operations::add_associated_agents::TrackAndTraceStoreAddAssociatedAgentsOperation as _;
 use operations::add_properties::TrackAndTraceStoreAddPropertiesOperation as _;
 use operations::add_proposals::TrackAndTraceStoreAddProposalsOperation as _;
@@ -43,9 +45,15 @@ use operations::fetch_reported_value_reporter_to_agent_metadata::TrackAndTraceSt
 use operations::list_associated_agents::TrackAndTraceStoreListAssociatedAgentsOperation as _;
 use operations::list_properties_with_data_type::TrackAndTraceStoreListPropertiesWithDataTypeOperation as _;
 use operations::list_proposals::TrackAndTraceStoreListProposalsOperation as _;
+use operations::list_record_contents::TrackAndTraceStoreListRecordContentsOperation as _;
 use operations::list_records::TrackAndTraceStoreListRecordsOperation as _;
+use operations::list_records_by_location::TrackAndTraceStoreListRecordsByLocationOperation as _;
+use operations::list_records_by_product::TrackAndTraceStoreListRecordsByProductOperation as _;
+use operations::list_records_matching::TrackAndTraceStoreListRecordsMatchingOperation as _;
 use operations::list_reported_value_reporter_to_agent_metadata::TrackAndTraceStoreListReportedValueReporterToAgentMetadataOperation as _;
+use operations::list_reporter_history::TrackAndTraceStoreListReporterHistoryOperation as _;
 use operations::list_reporters::TrackAndTraceStoreListReportersOperation as _;
+use operations::prune_property_page::TrackAndTraceStorePrunePropertyPageOperation as _;
 use operations::TrackAndTraceStoreOperations;
 
 /// Manages creating track and trace elements in the database
@@ -132,6 +140,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<(Property, Option<String>)>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -139,12 +148,18 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_property_with_data_type(record_id, property_name, service_id)
+        .fetch_property_with_data_type(
+            record_id,
+            property_name,
+            commit_height,
+            service_id,
+        )
     }
 
     fn fetch_record(
         &self,
         record_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Record>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -152,7 +167,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_record(record_id, service_id)
+        .fetch_record(record_id, commit_height, service_id)
     }
 
     fn fetch_reported_value_reporter_to_agent_metadata(
@@ -178,6 +193,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
     fn list_associated_agents(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<AssociatedAgent>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -185,12 +201,13 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_associated_agents(record_ids, service_id)
+        .list_associated_agents(record_ids, commit_height, service_id)
     }
 
     fn list_properties_with_data_type(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<(Property, Option<String>)>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -198,12 +215,13 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_properties_with_data_type(record_ids, service_id)
+        .list_properties_with_data_type(record_ids, commit_height, service_id)
     }
 
     fn list_proposals(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Proposal>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -211,27 +229,87 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_proposals(record_ids, service_id)
+        .list_proposals(record_ids, commit_height, service_id)
+    }
+
+    fn list_record_contents(
+        &self,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_record_contents(record_id, commit_height, service_id)
     }
 
     fn list_records(
         &self,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_records(commit_height, service_id, page)
+    }
+
+    fn list_records_by_product(
+        &self,
+        product_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<RecordList, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_records(service_id, offset, limit)
+        .list_records_by_product(product_id, commit_height, service_id, page)
+    }
+
+    fn list_records_by_location(
+        &self,
+        location_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_records_by_location(location_id, commit_height, service_id, page)
+    }
+
+    fn list_records_matching(
+        &self,
+        filter: &RecordFilter,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_records_matching(filter, commit_height, service_id, page)
     }
 
     fn list_reported_value_reporter_to_agent_metadata(
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -242,6 +320,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
         .list_reported_value_reporter_to_agent_metadata(
             record_id,
             property_name,
+            commit_height,
             service_id,
         )
     }
@@ -250,6 +329,20 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_reporters(record_id, property_name, commit_height, service_id)
+    }
+
+    fn list_reporter_history(
+        &self,
+        record_id: &str,
         service_id: Option<&str>,
     ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -257,7 +350,22 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_reporters(record_id, property_name, service_id)
+        .list_reporter_history(record_id, service_id)
+    }
+
+    fn prune_property_page(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        page: i32,
+        service_id: Option<&str>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .prune_property_page(record_id, property_name, page, service_id)
     }
 }
 
@@ -327,6 +435,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<(Property, Option<String>)>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -334,12 +443,18 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_property_with_data_type(record_id, property_name, service_id)
+        .fetch_property_with_data_type(
+            record_id,
+            property_name,
+            commit_height,
+            service_id,
+        )
     }
 
     fn fetch_record(
         &self,
         record_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Record>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -347,7 +462,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .fetch_record(record_id, service_id)
+        .fetch_record(record_id, commit_height, service_id)
     }
 
     fn fetch_reported_value_reporter_to_agent_metadata(
@@ -373,6 +488,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
     fn list_associated_agents(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<AssociatedAgent>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -380,12 +496,13 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_associated_agents(record_ids, service_id)
+        .list_associated_agents(record_ids, commit_height, service_id)
     }
 
     fn list_properties_with_data_type(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<(Property, Option<String>)>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -393,12 +510,13 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_properties_with_data_type(record_ids, service_id)
+        .list_properties_with_data_type(record_ids, commit_height, service_id)
     }
 
     fn list_proposals(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Proposal>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -406,27 +524,87 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_proposals(record_ids, service_id)
+        .list_proposals(record_ids, commit_height, service_id)
+    }
+
+    fn list_record_contents(
+        &self,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_record_contents(record_id, commit_height, service_id)
     }
 
     fn list_records(
         &self,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_records(commit_height, service_id, page)
+    }
+
+    fn list_records_by_product(
+        &self,
+        product_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<RecordList, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_records(service_id, offset, limit)
+        .list_records_by_product(product_id, commit_height, service_id, page)
+    }
+
+    fn list_records_by_location(
+        &self,
+        location_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_records_by_location(location_id, commit_height, service_id, page)
+    }
+
+    fn list_records_matching(
+        &self,
+        filter: &RecordFilter,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_records_matching(filter, commit_height, service_id, page)
     }
 
     fn list_reported_value_reporter_to_agent_metadata(
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -437,6 +615,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
         .list_reported_value_reporter_to_agent_metadata(
             record_id,
             property_name,
+            commit_height,
             service_id,
         )
     }
@@ -445,6 +624,20 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .list_reporters(record_id, property_name, commit_height, service_id)
+    }
+
+    fn list_reporter_history(
+        &self,
+        record_id: &str,
         service_id: Option<&str>,
     ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -452,7 +645,22 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
                 ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
             )
         })?)
-        .list_reporters(record_id, property_name, service_id)
+        .list_reporter_history(record_id, service_id)
+    }
+
+    fn prune_property_page(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        page: i32,
+        service_id: Option<&str>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            TrackAndTraceStoreError::ResourceTemporarilyUnavailableError(
+                ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+            )
+        })?)
+        .prune_property_page(record_id, property_name, page, service_id)
     }
 }
 
@@ -520,6 +728,9 @@ impl Into<NewRecordModel> for Record {
             start_commit_num: self.start_commit_num,
             end_commit_num: self.end_commit_num,
             service_id: self.service_id,
+            parent: self.parent,
+            product_id: self.product_id,
+            location_id: self.location_id,
         }
     }
 }
@@ -638,6 +849,9 @@ impl From<RecordModel> for Record {
             final_: model.final_,
             owners: model.owners.split(',').map(String::from).collect(),
             custodians: model.custodians.split(',').map(String::from).collect(),
+            parent: model.parent,
+            product_id: model.product_id,
+            location_id: model.location_id,
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
             service_id: model.service_id,
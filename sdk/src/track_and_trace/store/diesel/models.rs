@@ -150,6 +150,9 @@ pub struct NewRecordModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub parent: Option<String>,
+    pub product_id: Option<String>,
+    pub location_id: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -166,6 +169,9 @@ pub struct RecordModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub parent: Option<String>,
+    pub product_id: Option<String>,
+    pub location_id: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -230,6 +236,7 @@ pub struct NewReportedValueReporterToAgentMetadataModel {
     pub public_key: Option<String>,
     pub authorized: Option<bool>,
     pub metadata: Option<Vec<u8>>,
+    pub reported_value_start_commit_num: i64,
     pub reported_value_end_commit_num: i64,
     pub reporter_end_commit_num: Option<i64>,
     pub service_id: Option<String>,
@@ -255,6 +262,7 @@ pub struct ReportedValueReporterToAgentMetadataModel {
     pub public_key: Option<String>,
     pub authorized: Option<bool>,
     pub metadata: Option<Vec<u8>>,
+    pub reported_value_start_commit_num: i64,
     pub reported_value_end_commit_num: i64,
     pub reporter_end_commit_num: Option<i64>,
     pub service_id: Option<String>,
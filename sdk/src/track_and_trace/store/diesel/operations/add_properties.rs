@@ -45,14 +45,23 @@ impl<'a> TrackAndTraceStoreAddPropertiesOperation
             .read_write()
             .run::<_, TrackAndTraceStoreError, _>(|| {
                 for prop in properties {
-                    let duplicate = property::table
+                    let mut query = property::table
+                        .into_boxed()
+                        .select(property::all_columns)
                         .filter(
                             property::name
                                 .eq(&prop.name)
                                 .and(property::record_id.eq(&prop.record_id))
-                                .and(property::end_commit_num.eq(MAX_COMMIT_NUM))
-                                .and(property::service_id.eq(&prop.service_id)),
-                        )
+                                .and(property::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        );
+
+                    if let Some(service_id) = &prop.service_id {
+                        query = query.filter(property::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(property::service_id.is_null());
+                    }
+
+                    let duplicate = query
                         .first::<PropertyModel>(self.conn)
                         .map(Some)
                         .or_else(|err| {
@@ -68,15 +77,9 @@ impl<'a> TrackAndTraceStoreAddPropertiesOperation
                             ))
                         })?;
 
-                    if duplicate.is_some() {
+                    if let Some(duplicate) = duplicate {
                         update(property::table)
-                            .filter(
-                                property::name
-                                    .eq(&prop.name)
-                                    .and(property::record_id.eq(&prop.record_id))
-                                    .and(property::end_commit_num.eq(MAX_COMMIT_NUM))
-                                    .and(property::service_id.eq(&prop.service_id)),
-                            )
+                            .filter(property::id.eq(duplicate.id))
                             .set(property::end_commit_num.eq(&prop.start_commit_num))
                             .execute(self.conn)
                             .map(|_| ())
@@ -147,14 +150,23 @@ impl<'a> TrackAndTraceStoreAddPropertiesOperation
         self.conn
             .immediate_transaction::<_, TrackAndTraceStoreError, _>(|| {
                 for prop in properties {
-                    let duplicate = property::table
+                    let mut query = property::table
+                        .into_boxed()
+                        .select(property::all_columns)
                         .filter(
                             property::name
                                 .eq(&prop.name)
                                 .and(property::record_id.eq(&prop.record_id))
-                                .and(property::end_commit_num.eq(MAX_COMMIT_NUM))
-                                .and(property::service_id.eq(&prop.service_id)),
-                        )
+                                .and(property::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        );
+
+                    if let Some(service_id) = &prop.service_id {
+                        query = query.filter(property::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(property::service_id.is_null());
+                    }
+
+                    let duplicate = query
                         .first::<PropertyModel>(self.conn)
                         .map(Some)
                         .or_else(|err| {
@@ -170,15 +182,9 @@ impl<'a> TrackAndTraceStoreAddPropertiesOperation
                             ))
                         })?;
 
-                    if duplicate.is_some() {
+                    if let Some(duplicate) = duplicate {
                         update(property::table)
-                            .filter(
-                                property::name
-                                    .eq(&prop.name)
-                                    .and(property::record_id.eq(&prop.record_id))
-                                    .and(property::end_commit_num.eq(MAX_COMMIT_NUM))
-                                    .and(property::service_id.eq(&prop.service_id)),
-                            )
+                            .filter(property::id.eq(duplicate.id))
                             .set(property::end_commit_num.eq(&prop.start_commit_num))
                             .execute(self.conn)
                             .map(|_| ())
@@ -39,13 +39,22 @@ impl<'a> TrackAndTraceStoreAddRecordsOperation
             .read_write()
             .run::<_, TrackAndTraceStoreError, _>(|| {
                 for rec in records {
-                    let duplicate = record::table
+                    let mut query = record::table
+                        .into_boxed()
+                        .select(record::all_columns)
                         .filter(
                             record::record_id
                                 .eq(&rec.record_id)
-                                .and(record::service_id.eq(&rec.service_id))
                                 .and(record::end_commit_num.eq(MAX_COMMIT_NUM)),
-                        )
+                        );
+
+                    if let Some(service_id) = &rec.service_id {
+                        query = query.filter(record::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(record::service_id.is_null());
+                    }
+
+                    let duplicate = query
                         .first::<RecordModel>(self.conn)
                         .map(Some)
                         .or_else(|err| {
@@ -61,14 +70,9 @@ impl<'a> TrackAndTraceStoreAddRecordsOperation
                             ))
                         })?;
 
-                    if duplicate.is_some() {
+                    if let Some(duplicate) = duplicate {
                         update(record::table)
-                            .filter(
-                                record::record_id
-                                    .eq(&rec.record_id)
-                                    .and(record::service_id.eq(&rec.service_id))
-                                    .and(record::end_commit_num.eq(MAX_COMMIT_NUM)),
-                            )
+                            .filter(record::id.eq(duplicate.id))
                             .set(record::end_commit_num.eq(&rec.start_commit_num))
                             .execute(self.conn)
                             .map(|_| ())
@@ -136,13 +140,22 @@ impl<'a> TrackAndTraceStoreAddRecordsOperation
         self.conn
             .immediate_transaction::<_, TrackAndTraceStoreError, _>(|| {
                 for rec in records {
-                    let duplicate = record::table
+                    let mut query = record::table
+                        .into_boxed()
+                        .select(record::all_columns)
                         .filter(
                             record::record_id
                                 .eq(&rec.record_id)
-                                .and(record::service_id.eq(&rec.service_id))
                                 .and(record::end_commit_num.eq(MAX_COMMIT_NUM)),
-                        )
+                        );
+
+                    if let Some(service_id) = &rec.service_id {
+                        query = query.filter(record::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(record::service_id.is_null());
+                    }
+
+                    let duplicate = query
                         .first::<RecordModel>(self.conn)
                         .map(Some)
                         .or_else(|err| {
@@ -158,14 +171,9 @@ impl<'a> TrackAndTraceStoreAddRecordsOperation
                             ))
                         })?;
 
-                    if duplicate.is_some() {
+                    if let Some(duplicate) = duplicate {
                         update(record::table)
-                            .filter(
-                                record::record_id
-                                    .eq(&rec.record_id)
-                                    .and(record::service_id.eq(&rec.service_id))
-                                    .and(record::end_commit_num.eq(MAX_COMMIT_NUM)),
-                            )
+                            .filter(record::id.eq(duplicate.id))
                             .set(record::end_commit_num.eq(&rec.start_commit_num))
                             .execute(self.conn)
                             .map(|_| ())
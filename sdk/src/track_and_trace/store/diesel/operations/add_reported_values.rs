@@ -45,14 +45,23 @@ impl<'a> TrackAndTraceStoreAddReportedValuesOperation
             .read_write()
             .run::<_, TrackAndTraceStoreError, _>(|| {
                 for val in values {
-                    let duplicate = reported_value::table
+                    let mut query = reported_value::table
+                        .into_boxed()
+                        .select(reported_value::all_columns)
                         .filter(
                             reported_value::record_id
                                 .eq(&val.record_id)
                                 .and(reported_value::property_name.eq(&val.property_name))
-                                .and(reported_value::service_id.eq(&val.service_id))
                                 .and(reported_value::end_commit_num.eq(MAX_COMMIT_NUM)),
-                        )
+                        );
+
+                    if let Some(service_id) = &val.service_id {
+                        query = query.filter(reported_value::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(reported_value::service_id.is_null());
+                    }
+
+                    let duplicate = query
                         .first::<ReportedValueModel>(self.conn)
                         .map(Some)
                         .or_else(|err| {
@@ -68,15 +77,9 @@ impl<'a> TrackAndTraceStoreAddReportedValuesOperation
                             ))
                         })?;
 
-                    if duplicate.is_some() {
+                    if let Some(duplicate) = duplicate {
                         update(reported_value::table)
-                            .filter(
-                                reported_value::record_id
-                                    .eq(&val.record_id)
-                                    .and(reported_value::property_name.eq(&val.property_name))
-                                    .and(reported_value::service_id.eq(&val.service_id))
-                                    .and(reported_value::end_commit_num.eq(MAX_COMMIT_NUM)),
-                            )
+                            .filter(reported_value::id.eq(duplicate.id))
                             .set(reported_value::end_commit_num.eq(&val.start_commit_num))
                             .execute(self.conn)
                             .map(|_| ())
@@ -147,14 +150,23 @@ impl<'a> TrackAndTraceStoreAddReportedValuesOperation
         self.conn
             .immediate_transaction::<_, TrackAndTraceStoreError, _>(|| {
                 for val in values {
-                    let duplicate = reported_value::table
+                    let mut query = reported_value::table
+                        .into_boxed()
+                        .select(reported_value::all_columns)
                         .filter(
                             reported_value::record_id
                                 .eq(&val.record_id)
                                 .and(reported_value::property_name.eq(&val.property_name))
-                                .and(reported_value::service_id.eq(&val.service_id))
                                 .and(reported_value::end_commit_num.eq(MAX_COMMIT_NUM)),
-                        )
+                        );
+
+                    if let Some(service_id) = &val.service_id {
+                        query = query.filter(reported_value::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(reported_value::service_id.is_null());
+                    }
+
+                    let duplicate = query
                         .first::<ReportedValueModel>(self.conn)
                         .map(Some)
                         .or_else(|err| {
@@ -170,15 +182,9 @@ impl<'a> TrackAndTraceStoreAddReportedValuesOperation
                             ))
                         })?;
 
-                    if duplicate.is_some() {
+                    if let Some(duplicate) = duplicate {
                         update(reported_value::table)
-                            .filter(
-                                reported_value::record_id
-                                    .eq(&val.record_id)
-                                    .and(reported_value::property_name.eq(&val.property_name))
-                                    .and(reported_value::service_id.eq(&val.service_id))
-                                    .and(reported_value::end_commit_num.eq(MAX_COMMIT_NUM)),
-                            )
+                            .filter(reported_value::id.eq(duplicate.id))
                             .set(reported_value::end_commit_num.eq(&val.start_commit_num))
                             .execute(self.conn)
                             .map(|_| ())
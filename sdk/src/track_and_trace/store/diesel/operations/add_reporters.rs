@@ -45,15 +45,24 @@ impl<'a> TrackAndTraceStoreAddReportersOperation
             .read_write()
             .run::<_, TrackAndTraceStoreError, _>(|| {
                 for rep in reporters {
-                    let duplicate = reporter::table
+                    let mut query = reporter::table
+                        .into_boxed()
+                        .select(reporter::all_columns)
                         .filter(
                             reporter::record_id
                                 .eq(&rep.record_id)
                                 .and(reporter::property_name.eq(&rep.property_name))
                                 .and(reporter::public_key.eq(&rep.public_key))
-                                .and(reporter::service_id.eq(&rep.service_id))
                                 .and(reporter::end_commit_num.eq(MAX_COMMIT_NUM)),
-                        )
+                        );
+
+                    if let Some(service_id) = &rep.service_id {
+                        query = query.filter(reporter::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(reporter::service_id.is_null());
+                    }
+
+                    let duplicate = query
                         .first::<ReporterModel>(self.conn)
                         .map(Some)
                         .or_else(|err| {
@@ -69,16 +78,9 @@ impl<'a> TrackAndTraceStoreAddReportersOperation
                             ))
                         })?;
 
-                    if duplicate.is_some() {
+                    if let Some(duplicate) = duplicate {
                         update(reporter::table)
-                            .filter(
-                                reporter::record_id
-                                    .eq(&rep.record_id)
-                                    .and(reporter::property_name.eq(&rep.property_name))
-                                    .and(reporter::public_key.eq(&rep.public_key))
-                                    .and(reporter::service_id.eq(&rep.service_id))
-                                    .and(reporter::end_commit_num.eq(MAX_COMMIT_NUM)),
-                            )
+                            .filter(reporter::id.eq(duplicate.id))
                             .set(reporter::end_commit_num.eq(&rep.start_commit_num))
                             .execute(self.conn)
                             .map(|_| ())
@@ -149,15 +151,24 @@ impl<'a> TrackAndTraceStoreAddReportersOperation
         self.conn
             .immediate_transaction::<_, TrackAndTraceStoreError, _>(|| {
                 for rep in reporters {
-                    let duplicate = reporter::table
+                    let mut query = reporter::table
+                        .into_boxed()
+                        .select(reporter::all_columns)
                         .filter(
                             reporter::record_id
                                 .eq(&rep.record_id)
                                 .and(reporter::property_name.eq(&rep.property_name))
                                 .and(reporter::public_key.eq(&rep.public_key))
-                                .and(reporter::service_id.eq(&rep.service_id))
                                 .and(reporter::end_commit_num.eq(MAX_COMMIT_NUM)),
-                        )
+                        );
+
+                    if let Some(service_id) = &rep.service_id {
+                        query = query.filter(reporter::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(reporter::service_id.is_null());
+                    }
+
+                    let duplicate = query
                         .first::<ReporterModel>(self.conn)
                         .map(Some)
                         .or_else(|err| {
@@ -173,16 +184,9 @@ impl<'a> TrackAndTraceStoreAddReportersOperation
                             ))
                         })?;
 
-                    if duplicate.is_some() {
+                    if let Some(duplicate) = duplicate {
                         update(reporter::table)
-                            .filter(
-                                reporter::record_id
-                                    .eq(&rep.record_id)
-                                    .and(reporter::property_name.eq(&rep.property_name))
-                                    .and(reporter::public_key.eq(&rep.public_key))
-                                    .and(reporter::service_id.eq(&rep.service_id))
-                                    .and(reporter::end_commit_num.eq(MAX_COMMIT_NUM)),
-                            )
+                            .filter(reporter::id.eq(duplicate.id))
                             .set(reporter::end_commit_num.eq(&rep.start_commit_num))
                             .execute(self.conn)
                             .map(|_| ())
@@ -19,7 +19,7 @@ use crate::track_and_trace::store::diesel::{
     TrackAndTraceStoreError,
 };
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::track_and_trace::store::diesel::models::PropertyModel;
 use crate::track_and_trace::store::Property;
@@ -32,6 +32,7 @@ pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreFetchPrope
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<(Property, Option<String>)>, TrackAndTraceStoreError>;
 }
@@ -44,8 +45,10 @@ impl<'a> TrackAndTraceStoreFetchPropertyWithDataTypeOperation
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<(Property, Option<String>)>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = property::table
             .into_boxed()
             .left_join(
@@ -63,7 +66,11 @@ impl<'a> TrackAndTraceStoreFetchPropertyWithDataTypeOperation
                 property::name
                     .eq(property_name)
                     .and(property::record_id.eq(record_id))
-                    .and(property::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        property::start_commit_num
+                            .le(height)
+                            .and(property::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
@@ -96,8 +103,10 @@ impl<'a> TrackAndTraceStoreFetchPropertyWithDataTypeOperation
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<(Property, Option<String>)>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = property::table
             .into_boxed()
             .left_join(
@@ -115,7 +124,11 @@ impl<'a> TrackAndTraceStoreFetchPropertyWithDataTypeOperation
                 property::name
                     .eq(property_name)
                     .and(property::record_id.eq(record_id))
-                    .and(property::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        property::start_commit_num
+                            .le(height)
+                            .and(property::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
@@ -15,7 +15,7 @@
 use super::TrackAndTraceStoreOperations;
 use crate::track_and_trace::store::diesel::{schema::record, TrackAndTraceStoreError};
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::track_and_trace::store::diesel::models::RecordModel;
 use crate::track_and_trace::store::Record;
@@ -26,6 +26,7 @@ pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreFetchRecor
     fn fetch_record(
         &self,
         record_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Record>, TrackAndTraceStoreError>;
 }
@@ -37,15 +38,19 @@ impl<'a> TrackAndTraceStoreFetchRecordOperation
     fn fetch_record(
         &self,
         record_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Record>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = record::table
             .into_boxed()
             .select(record::all_columns)
             .filter(
-                record::record_id
-                    .eq(record_id)
-                    .and(record::end_commit_num.eq(MAX_COMMIT_NUM)),
+                record::record_id.eq(record_id).and(
+                    record::start_commit_num
+                        .le(height)
+                        .and(record::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
@@ -73,15 +78,19 @@ impl<'a> TrackAndTraceStoreFetchRecordOperation
     fn fetch_record(
         &self,
         record_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Record>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = record::table
             .into_boxed()
             .select(record::all_columns)
             .filter(
-                record::record_id
-                    .eq(record_id)
-                    .and(record::end_commit_num.eq(MAX_COMMIT_NUM)),
+                record::record_id.eq(record_id).and(
+                    record::start_commit_num
+                        .le(height)
+                        .and(record::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
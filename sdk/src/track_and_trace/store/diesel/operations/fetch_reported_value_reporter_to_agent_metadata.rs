@@ -17,7 +17,7 @@ use crate::track_and_trace::store::diesel::{
     schema::reported_value_reporter_to_agent_metadata, TrackAndTraceStoreError,
 };
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::track_and_trace::store::diesel::models::ReportedValueReporterToAgentMetadataModel;
 use crate::track_and_trace::store::ReportedValueReporterToAgentMetadata;
@@ -60,16 +60,20 @@ impl<'a>
         commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
-        let height = commit_height.unwrap_or(MAX_COMMIT_NUM);
+        let height = commit_height_or_current(commit_height);
         let mut query = reported_value_reporter_to_agent_metadata::table
             .into_boxed()
             .filter(
                 reported_value_reporter_to_agent_metadata::property_name
                     .eq(property_name)
                     .and(reported_value_reporter_to_agent_metadata::record_id.eq(record_id))
+                    .and(
+                        reported_value_reporter_to_agent_metadata::reported_value_start_commit_num
+                            .le(height),
+                    )
                     .and(
                         reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
-                            .eq(height),
+                            .gt(height),
                     ),
             );
 
@@ -127,17 +131,15 @@ impl<'a>
             query = query.filter(reported_value_reporter_to_agent_metadata::service_id.is_null());
         }
 
-        if let Some(commit_height) = commit_height {
-            query = query.filter(
-                reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
-                    .eq(commit_height),
-            );
-        } else {
-            query = query.filter(
-                reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
-                    .eq(MAX_COMMIT_NUM),
-            );
-        }
+        let height = commit_height_or_current(commit_height);
+        query = query.filter(
+            reported_value_reporter_to_agent_metadata::reported_value_start_commit_num
+                .le(height)
+                .and(
+                    reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
+                        .gt(height),
+                ),
+        );
 
         query.load::<ReportedValueReporterToAgentMetadataModel>(conn)
     }
@@ -200,16 +202,20 @@ impl<'a>
         commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
-        let height = commit_height.unwrap_or(MAX_COMMIT_NUM);
+        let height = commit_height_or_current(commit_height);
         let mut query = reported_value_reporter_to_agent_metadata::table
             .into_boxed()
             .filter(
                 reported_value_reporter_to_agent_metadata::property_name
                     .eq(property_name)
                     .and(reported_value_reporter_to_agent_metadata::record_id.eq(record_id))
+                    .and(
+                        reported_value_reporter_to_agent_metadata::reported_value_start_commit_num
+                            .le(height),
+                    )
                     .and(
                         reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
-                            .eq(height),
+                            .gt(height),
                     ),
             );
 
@@ -267,17 +273,15 @@ impl<'a>
             query = query.filter(reported_value_reporter_to_agent_metadata::service_id.is_null());
         }
 
-        if let Some(commit_height) = commit_height {
-            query = query.filter(
-                reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
-                    .eq(commit_height),
-            );
-        } else {
-            query = query.filter(
-                reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
-                    .eq(MAX_COMMIT_NUM),
-            );
-        }
+        let height = commit_height_or_current(commit_height);
+        query = query.filter(
+            reported_value_reporter_to_agent_metadata::reported_value_start_commit_num
+                .le(height)
+                .and(
+                    reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
+                        .gt(height),
+                ),
+        );
 
         query.load::<ReportedValueReporterToAgentMetadataModel>(conn)
     }
@@ -15,7 +15,7 @@
 use super::TrackAndTraceStoreOperations;
 use crate::track_and_trace::store::diesel::{schema::associated_agent, TrackAndTraceStoreError};
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::track_and_trace::store::diesel::models::AssociatedAgentModel;
 use crate::track_and_trace::store::AssociatedAgent;
@@ -27,6 +27,7 @@ pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListAssoci
     fn list_associated_agents(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<AssociatedAgent>, TrackAndTraceStoreError>;
 }
@@ -38,14 +39,17 @@ impl<'a> TrackAndTraceStoreListAssociatedAgentsOperation
     fn list_associated_agents(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<AssociatedAgent>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = associated_agent::table
             .into_boxed()
             .select(associated_agent::all_columns)
             .filter(
-                associated_agent::end_commit_num
-                    .eq(MAX_COMMIT_NUM)
+                associated_agent::start_commit_num
+                    .le(height)
+                    .and(associated_agent::end_commit_num.gt(height))
                     .and(associated_agent::record_id.eq_any(record_ids)),
             );
 
@@ -80,14 +84,17 @@ impl<'a> TrackAndTraceStoreListAssociatedAgentsOperation
     fn list_associated_agents(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<AssociatedAgent>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = associated_agent::table
             .into_boxed()
             .select(associated_agent::all_columns)
             .filter(
-                associated_agent::end_commit_num
-                    .eq(MAX_COMMIT_NUM)
+                associated_agent::start_commit_num
+                    .le(height)
+                    .and(associated_agent::end_commit_num.gt(height))
                     .and(associated_agent::record_id.eq_any(record_ids)),
             );
 
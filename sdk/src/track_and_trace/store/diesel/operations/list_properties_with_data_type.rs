@@ -19,7 +19,7 @@ use crate::track_and_trace::store::diesel::{
     TrackAndTraceStoreError,
 };
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::track_and_trace::store::diesel::models::PropertyModel;
 use crate::track_and_trace::store::Property;
@@ -31,6 +31,7 @@ pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListProper
     fn list_properties_with_data_type(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<(Property, Option<String>)>, TrackAndTraceStoreError>;
 }
@@ -42,8 +43,10 @@ impl<'a> TrackAndTraceStoreListPropertiesWithDataTypeOperation
     fn list_properties_with_data_type(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<(Property, Option<String>)>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = property::table
             .into_boxed()
             .left_join(
@@ -58,9 +61,11 @@ impl<'a> TrackAndTraceStoreListPropertiesWithDataTypeOperation
                     .and(property::end_commit_num.eq(record::end_commit_num))),
             )
             .filter(
-                property::record_id
-                    .eq_any(record_ids)
-                    .and(property::end_commit_num.eq(MAX_COMMIT_NUM)),
+                property::record_id.eq_any(record_ids).and(
+                    property::start_commit_num
+                        .le(height)
+                        .and(property::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
@@ -101,8 +106,10 @@ impl<'a> TrackAndTraceStoreListPropertiesWithDataTypeOperation
     fn list_properties_with_data_type(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<(Property, Option<String>)>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = property::table
             .into_boxed()
             .left_join(
@@ -117,9 +124,11 @@ impl<'a> TrackAndTraceStoreListPropertiesWithDataTypeOperation
                     .and(property::end_commit_num.eq(record::end_commit_num))),
             )
             .filter(
-                property::record_id
-                    .eq_any(record_ids)
-                    .and(property::end_commit_num.eq(MAX_COMMIT_NUM)),
+                property::record_id.eq_any(record_ids).and(
+                    property::start_commit_num
+                        .le(height)
+                        .and(property::end_commit_num.gt(height)),
+                ),
             );
 
         if let Some(service_id) = service_id {
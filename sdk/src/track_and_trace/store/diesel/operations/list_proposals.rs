@@ -15,7 +15,7 @@
 use super::TrackAndTraceStoreOperations;
 use crate::track_and_trace::store::diesel::{schema::proposal, TrackAndTraceStoreError};
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::track_and_trace::store::diesel::models::ProposalModel;
 use crate::track_and_trace::store::Proposal;
@@ -26,6 +26,7 @@ pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListPropos
     fn list_proposals(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Proposal>, TrackAndTraceStoreError>;
 }
@@ -37,14 +38,17 @@ impl<'a> TrackAndTraceStoreListProposalsOperation
     fn list_proposals(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Proposal>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = proposal::table
             .into_boxed()
             .select(proposal::all_columns)
             .filter(
-                proposal::end_commit_num
-                    .eq(MAX_COMMIT_NUM)
+                proposal::start_commit_num
+                    .le(height)
+                    .and(proposal::end_commit_num.gt(height))
                     .and(proposal::record_id.eq_any(record_ids)),
             );
 
@@ -79,14 +83,17 @@ impl<'a> TrackAndTraceStoreListProposalsOperation
     fn list_proposals(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Proposal>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = proposal::table
             .into_boxed()
             .select(proposal::all_columns)
             .filter(
-                proposal::end_commit_num
-                    .eq(MAX_COMMIT_NUM)
+                proposal::start_commit_num
+                    .le(height)
+                    .and(proposal::end_commit_num.gt(height))
                     .and(proposal::record_id.eq_any(record_ids)),
             );
 
@@ -0,0 +1,122 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::track_and_trace::store::diesel::{schema::record, TrackAndTraceStoreError};
+
+use crate::commits::MAX_COMMIT_NUM;
+use crate::error::InternalError;
+use crate::track_and_trace::store::diesel::models::RecordModel;
+use crate::track_and_trace::store::Record;
+
+use diesel::prelude::*;
+
+pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListRecordContentsOperation {
+    fn list_record_contents(
+        &self,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Record>, TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreListRecordContentsOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_record_contents(
+        &self,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
+        let mut query = record::table
+            .into_boxed()
+            .select(record::all_columns)
+            .filter(record::parent.eq(record_id))
+            .order(record::record_id);
+
+        if let Some(commit_height) = commit_height {
+            query = query.filter(
+                record::start_commit_num
+                    .le(commit_height)
+                    .and(record::end_commit_num.gt(commit_height)),
+            );
+        } else {
+            query = query.filter(record::end_commit_num.eq(MAX_COMMIT_NUM));
+        }
+
+        if let Some(service_id) = service_id {
+            query = query.filter(record::service_id.eq(service_id));
+        } else {
+            query = query.filter(record::service_id.is_null());
+        }
+
+        let records = query
+            .load::<RecordModel>(self.conn)
+            .map_err(|err| {
+                TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(err)))
+            })?
+            .into_iter()
+            .map(Record::from)
+            .collect();
+
+        Ok(records)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreListRecordContentsOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_record_contents(
+        &self,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
+        let mut query = record::table
+            .into_boxed()
+            .select(record::all_columns)
+            .filter(record::parent.eq(record_id))
+            .order(record::record_id);
+
+        if let Some(commit_height) = commit_height {
+            query = query.filter(
+                record::start_commit_num
+                    .le(commit_height)
+                    .and(record::end_commit_num.gt(commit_height)),
+            );
+        } else {
+            query = query.filter(record::end_commit_num.eq(MAX_COMMIT_NUM));
+        }
+
+        if let Some(service_id) = service_id {
+            query = query.filter(record::service_id.eq(service_id));
+        } else {
+            query = query.filter(record::service_id.is_null());
+        }
+
+        let records = query
+            .load::<RecordModel>(self.conn)
+            .map_err(|err| {
+                TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(err)))
+            })?
+            .into_iter()
+            .map(Record::from)
+            .collect();
+
+        Ok(records)
+    }
+}
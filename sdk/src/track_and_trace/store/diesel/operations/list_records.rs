@@ -15,9 +15,9 @@
 use super::TrackAndTraceStoreOperations;
 use crate::track_and_trace::store::diesel::{schema::record, TrackAndTraceStoreError};
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
-use crate::paging::Paging;
+use crate::paging::{paged_query, PageRequest, Paging};
 use crate::track_and_trace::store::diesel::models::RecordModel;
 use crate::track_and_trace::store::{Record, RecordList};
 
@@ -26,9 +26,9 @@ use diesel::prelude::*;
 pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListRecordsOperation {
     fn list_records(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<RecordList, TrackAndTraceStoreError>;
 }
 
@@ -38,16 +38,19 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
 {
     fn list_records(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = record::table
             .into_boxed()
             .select(record::all_columns)
-            .offset(offset)
-            .limit(limit)
-            .filter(record::end_commit_num.eq(MAX_COMMIT_NUM));
+            .filter(
+                record::start_commit_num
+                    .le(height)
+                    .and(record::end_commit_num.gt(height)),
+            );
 
         if let Some(service_id) = service_id {
             query = query.filter(record::service_id.eq(service_id));
@@ -55,7 +58,9 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
             query = query.filter(record::service_id.is_null());
         }
 
-        let records = query
+        let query = paged_query(query, record::record_id, page);
+
+        let record_models = query
             .load::<RecordModel>(self.conn)
             .map(Some)
             .map_err(|err| {
@@ -65,12 +70,7 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
                 TrackAndTraceStoreError::NotFoundError(
                     "Could not get all records from storage".to_string(),
                 )
-            })?
-            .into_iter()
-            .collect::<Vec<RecordModel>>()
-            .into_iter()
-            .map(Record::from)
-            .collect();
+            })?;
 
         let mut count_query = record::table.into_boxed().select(record::all_columns);
 
@@ -80,9 +80,16 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
             count_query = count_query.filter(record::service_id.is_null());
         }
 
-        let total = count_query.count().get_result(self.conn)?;
+        let (record_models, paging) = Paging::for_page(
+            record_models,
+            page,
+            || count_query.count().get_result(self.conn),
+            |model| model.record_id.clone(),
+        )?;
+
+        let records = record_models.into_iter().map(Record::from).collect();
 
-        Ok(RecordList::new(records, Paging::new(offset, limit, total)))
+        Ok(RecordList::new(records, paging))
     }
 }
 
@@ -92,16 +99,19 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
 {
     fn list_records(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = record::table
             .into_boxed()
             .select(record::all_columns)
-            .offset(offset)
-            .limit(limit)
-            .filter(record::end_commit_num.eq(MAX_COMMIT_NUM));
+            .filter(
+                record::start_commit_num
+                    .le(height)
+                    .and(record::end_commit_num.gt(height)),
+            );
 
         if let Some(service_id) = service_id {
             query = query.filter(record::service_id.eq(service_id));
@@ -109,7 +119,9 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
             query = query.filter(record::service_id.is_null());
         }
 
-        let records = query
+        let query = paged_query(query, record::record_id, page);
+
+        let record_models = query
             .load::<RecordModel>(self.conn)
             .map(Some)
             .map_err(|err| {
@@ -119,12 +131,7 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
                 TrackAndTraceStoreError::NotFoundError(
                     "Could not get all records from storage".to_string(),
                 )
-            })?
-            .into_iter()
-            .collect::<Vec<RecordModel>>()
-            .into_iter()
-            .map(Record::from)
-            .collect();
+            })?;
 
         let mut count_query = record::table.into_boxed().select(record::all_columns);
 
@@ -134,8 +141,15 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
             count_query = count_query.filter(record::service_id.is_null());
         }
 
-        let total = count_query.count().get_result(self.conn)?;
+        let (record_models, paging) = Paging::for_page(
+            record_models,
+            page,
+            || count_query.count().get_result(self.conn),
+            |model| model.record_id.clone(),
+        )?;
+
+        let records = record_models.into_iter().map(Record::from).collect();
 
-        Ok(RecordList::new(records, Paging::new(offset, limit, total)))
+        Ok(RecordList::new(records, paging))
     }
 }
@@ -0,0 +1,159 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::track_and_trace::store::diesel::{schema::record, TrackAndTraceStoreError};
+
+use crate::commits::commit_height_or_current;
+use crate::error::InternalError;
+use crate::paging::{paged_query, PageRequest, Paging};
+use crate::track_and_trace::store::diesel::models::RecordModel;
+use crate::track_and_trace::store::{Record, RecordList};
+
+use diesel::prelude::*;
+
+pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListRecordsByLocationOperation
+{
+    fn list_records_by_location(
+        &self,
+        location_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreListRecordsByLocationOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_records_by_location(
+        &self,
+        location_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let mut query = record::table
+            .into_boxed()
+            .select(record::all_columns)
+            .filter(
+                record::location_id.eq(location_id).and(
+                    record::start_commit_num
+                        .le(height)
+                        .and(record::end_commit_num.gt(height)),
+                ),
+            )
+            .order(record::record_id);
+
+        let mut count_query = record::table
+            .into_boxed()
+            .select(record::all_columns)
+            .filter(
+                record::location_id.eq(location_id).and(
+                    record::start_commit_num
+                        .le(height)
+                        .and(record::end_commit_num.gt(height)),
+                ),
+            );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(record::service_id.eq(service_id));
+            count_query = count_query.filter(record::service_id.eq(service_id));
+        } else {
+            query = query.filter(record::service_id.is_null());
+            count_query = count_query.filter(record::service_id.is_null());
+        }
+
+        let record_models = paged_query(query, record::record_id, page)
+            .load::<RecordModel>(self.conn)
+            .map_err(|err| {
+                TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(err)))
+            })?;
+
+        let (record_models, paging) = Paging::for_page(
+            record_models,
+            page,
+            || count_query.count().get_result(self.conn),
+            |model| model.record_id.clone(),
+        )?;
+
+        let records = record_models.into_iter().map(Record::from).collect();
+
+        Ok(RecordList::new(records, paging))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreListRecordsByLocationOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_records_by_location(
+        &self,
+        location_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let mut query = record::table
+            .into_boxed()
+            .select(record::all_columns)
+            .filter(
+                record::location_id.eq(location_id).and(
+                    record::start_commit_num
+                        .le(height)
+                        .and(record::end_commit_num.gt(height)),
+                ),
+            )
+            .order(record::record_id);
+
+        let mut count_query = record::table
+            .into_boxed()
+            .select(record::all_columns)
+            .filter(
+                record::location_id.eq(location_id).and(
+                    record::start_commit_num
+                        .le(height)
+                        .and(record::end_commit_num.gt(height)),
+                ),
+            );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(record::service_id.eq(service_id));
+            count_query = count_query.filter(record::service_id.eq(service_id));
+        } else {
+            query = query.filter(record::service_id.is_null());
+            count_query = count_query.filter(record::service_id.is_null());
+        }
+
+        let record_models = paged_query(query, record::record_id, page)
+            .load::<RecordModel>(self.conn)
+            .map_err(|err| {
+                TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(err)))
+            })?;
+
+        let (record_models, paging) = Paging::for_page(
+            record_models,
+            page,
+            || count_query.count().get_result(self.conn),
+            |model| model.record_id.clone(),
+        )?;
+
+        let records = record_models.into_iter().map(Record::from).collect();
+
+        Ok(RecordList::new(records, paging))
+    }
+}
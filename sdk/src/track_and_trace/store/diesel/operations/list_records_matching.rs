@@ -0,0 +1,277 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::track_and_trace::store::diesel::{
+    schema::{associated_agent, record, reported_value},
+    TrackAndTraceStoreError,
+};
+
+use crate::commits::commit_height_or_current;
+use crate::error::InternalError;
+use crate::paging::{paged_query, PageRequest, Paging};
+use crate::track_and_trace::store::diesel::models::RecordModel;
+use crate::track_and_trace::store::{Record, RecordFilter, RecordList};
+
+use diesel::{
+    dsl::sql,
+    prelude::*,
+    sql_types::{BigInt, Bool, Nullable, Text},
+};
+
+pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListRecordsMatchingOperation {
+    fn list_records_matching(
+        &self,
+        filter: &RecordFilter,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreListRecordsMatchingOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_records_matching(
+        &self,
+        filter: &RecordFilter,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let records = || {
+            let mut query = record::table
+                .into_boxed()
+                .select(record::all_columns)
+                .filter(
+                    record::start_commit_num
+                        .le(height)
+                        .and(record::end_commit_num.gt(height)),
+                );
+
+            if let Some(service_id) = service_id {
+                query = query.filter(record::service_id.eq(service_id));
+            } else {
+                query = query.filter(record::service_id.is_null());
+            }
+
+            if let Some(product_id) = filter.product_id {
+                query = query.filter(record::product_id.eq(product_id));
+            }
+
+            if let Some(org_id) = filter.org_id {
+                // The owners and custodians columns list every holder in order, so the current
+                // holder is the last entry
+                query = query.filter(
+                    sql::<Bool>("EXISTS (SELECT 1 FROM pike_agent WHERE pike_agent.org_id = ")
+                        .bind::<Text, _>(org_id)
+                        .sql(" AND pike_agent.start_commit_num <= ")
+                        .bind::<BigInt, _>(height)
+                        .sql(" AND pike_agent.end_commit_num > ")
+                        .bind::<BigInt, _>(height)
+                        .sql(" AND pike_agent.service_id IS NOT DISTINCT FROM ")
+                        .bind::<Nullable<Text>, _>(service_id)
+                        .sql(
+                            " AND (record.owners = pike_agent.public_key \
+                             OR record.owners LIKE '%,' || pike_agent.public_key \
+                             OR record.custodians = pike_agent.public_key \
+                             OR record.custodians LIKE '%,' || pike_agent.public_key))",
+                        ),
+                );
+            }
+
+            if filter.is_span_set() {
+                let from = filter.updated_from.unwrap_or(i64::MIN);
+                let to = filter.updated_to.unwrap_or(i64::MAX);
+
+                let mut agent_updates = associated_agent::table
+                    .into_boxed()
+                    .select(associated_agent::record_id)
+                    .filter(
+                        associated_agent::timestamp
+                            .ge(from)
+                            .and(associated_agent::timestamp.lt(to))
+                            .and(associated_agent::start_commit_num.le(height))
+                            .and(associated_agent::end_commit_num.gt(height)),
+                    );
+                let mut value_updates = reported_value::table
+                    .into_boxed()
+                    .select(reported_value::record_id)
+                    .filter(
+                        reported_value::timestamp
+                            .ge(from)
+                            .and(reported_value::timestamp.lt(to))
+                            .and(reported_value::start_commit_num.le(height)),
+                    );
+
+                if let Some(service_id) = service_id {
+                    agent_updates =
+                        agent_updates.filter(associated_agent::service_id.eq(service_id));
+                    value_updates = value_updates.filter(reported_value::service_id.eq(service_id));
+                } else {
+                    agent_updates = agent_updates.filter(associated_agent::service_id.is_null());
+                    value_updates = value_updates.filter(reported_value::service_id.is_null());
+                }
+
+                query = query.filter(
+                    record::record_id
+                        .eq_any(agent_updates)
+                        .or(record::record_id.eq_any(value_updates)),
+                );
+            }
+
+            query
+        };
+
+        let record_models =
+            paged_query(records().order(record::record_id), record::record_id, page)
+                .load::<RecordModel>(self.conn)
+                .map_err(|err| {
+                    TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(
+                        err,
+                    )))
+                })?;
+
+        let (record_models, paging) = Paging::for_page(
+            record_models,
+            page,
+            || records().count().get_result(self.conn),
+            |model| model.record_id.clone(),
+        )?;
+
+        let records = record_models.into_iter().map(Record::from).collect();
+
+        Ok(RecordList::new(records, paging))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreListRecordsMatchingOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_records_matching(
+        &self,
+        filter: &RecordFilter,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let records = || {
+            let mut query = record::table
+                .into_boxed()
+                .select(record::all_columns)
+                .filter(
+                    record::start_commit_num
+                        .le(height)
+                        .and(record::end_commit_num.gt(height)),
+                );
+
+            if let Some(service_id) = service_id {
+                query = query.filter(record::service_id.eq(service_id));
+            } else {
+                query = query.filter(record::service_id.is_null());
+            }
+
+            if let Some(product_id) = filter.product_id {
+                query = query.filter(record::product_id.eq(product_id));
+            }
+
+            if let Some(org_id) = filter.org_id {
+                // The owners and custodians columns list every holder in order, so the current
+                // holder is the last entry
+                query = query.filter(
+                    sql::<Bool>("EXISTS (SELECT 1 FROM pike_agent WHERE pike_agent.org_id = ")
+                        .bind::<Text, _>(org_id)
+                        .sql(" AND pike_agent.start_commit_num <= ")
+                        .bind::<BigInt, _>(height)
+                        .sql(" AND pike_agent.end_commit_num > ")
+                        .bind::<BigInt, _>(height)
+                        .sql(" AND pike_agent.service_id IS ")
+                        .bind::<Nullable<Text>, _>(service_id)
+                        .sql(
+                            " AND (record.owners = pike_agent.public_key \
+                             OR record.owners LIKE '%,' || pike_agent.public_key \
+                             OR record.custodians = pike_agent.public_key \
+                             OR record.custodians LIKE '%,' || pike_agent.public_key))",
+                        ),
+                );
+            }
+
+            if filter.is_span_set() {
+                let from = filter.updated_from.unwrap_or(i64::MIN);
+                let to = filter.updated_to.unwrap_or(i64::MAX);
+
+                let mut agent_updates = associated_agent::table
+                    .into_boxed()
+                    .select(associated_agent::record_id)
+                    .filter(
+                        associated_agent::timestamp
+                            .ge(from)
+                            .and(associated_agent::timestamp.lt(to))
+                            .and(associated_agent::start_commit_num.le(height))
+                            .and(associated_agent::end_commit_num.gt(height)),
+                    );
+                let mut value_updates = reported_value::table
+                    .into_boxed()
+                    .select(reported_value::record_id)
+                    .filter(
+                        reported_value::timestamp
+                            .ge(from)
+                            .and(reported_value::timestamp.lt(to))
+                            .and(reported_value::start_commit_num.le(height)),
+                    );
+
+                if let Some(service_id) = service_id {
+                    agent_updates =
+                        agent_updates.filter(associated_agent::service_id.eq(service_id));
+                    value_updates = value_updates.filter(reported_value::service_id.eq(service_id));
+                } else {
+                    agent_updates = agent_updates.filter(associated_agent::service_id.is_null());
+                    value_updates = value_updates.filter(reported_value::service_id.is_null());
+                }
+
+                query = query.filter(
+                    record::record_id
+                        .eq_any(agent_updates)
+                        .or(record::record_id.eq_any(value_updates)),
+                );
+            }
+
+            query
+        };
+
+        let record_models =
+            paged_query(records().order(record::record_id), record::record_id, page)
+                .load::<RecordModel>(self.conn)
+                .map_err(|err| {
+                    TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(
+                        err,
+                    )))
+                })?;
+
+        let (record_models, paging) = Paging::for_page(
+            record_models,
+            page,
+            || records().count().get_result(self.conn),
+            |model| model.record_id.clone(),
+        )?;
+
+        let records = record_models.into_iter().map(Record::from).collect();
+
+        Ok(RecordList::new(records, paging))
+    }
+}
@@ -17,7 +17,7 @@ use crate::track_and_trace::store::diesel::{
     schema::reported_value_reporter_to_agent_metadata, TrackAndTraceStoreError,
 };
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::track_and_trace::store::diesel::models::ReportedValueReporterToAgentMetadataModel;
 use crate::track_and_trace::store::ReportedValueReporterToAgentMetadata;
@@ -32,6 +32,7 @@ pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListReport
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError>;
     fn get_root_rvs(
@@ -56,6 +57,7 @@ impl<'a>
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
         let query: Vec<ReportedValueReporterToAgentMetadataModel> = reported_value_reporter_to_agent_metadata::table
@@ -64,8 +66,8 @@ impl<'a>
                     .eq(property_name)
                     .and(reported_value_reporter_to_agent_metadata::record_id.eq(record_id))
                     .and(
-                        reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
-                            .le(MAX_COMMIT_NUM),
+                        reported_value_reporter_to_agent_metadata::reported_value_start_commit_num
+                            .le(commit_height_or_current(commit_height)),
                     ),
             )
             .load::<ReportedValueReporterToAgentMetadataModel>(self.conn)
@@ -187,6 +189,7 @@ impl<'a>
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
         let query: Vec<ReportedValueReporterToAgentMetadataModel> = reported_value_reporter_to_agent_metadata::table
@@ -195,8 +198,8 @@ impl<'a>
                     .eq(property_name)
                     .and(reported_value_reporter_to_agent_metadata::record_id.eq(record_id))
                     .and(
-                        reported_value_reporter_to_agent_metadata::reported_value_end_commit_num
-                            .le(MAX_COMMIT_NUM),
+                        reported_value_reporter_to_agent_metadata::reported_value_start_commit_num
+                            .le(commit_height_or_current(commit_height)),
                     ),
             )
             .load::<ReportedValueReporterToAgentMetadataModel>(self.conn)
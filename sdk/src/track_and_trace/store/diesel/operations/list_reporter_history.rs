@@ -0,0 +1,96 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::track_and_trace::store::diesel::{schema::reporter, TrackAndTraceStoreError};
+
+use crate::error::InternalError;
+use crate::track_and_trace::store::diesel::models::ReporterModel;
+use crate::track_and_trace::store::Reporter;
+
+use diesel::prelude::*;
+
+pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListReporterHistoryOperation {
+    fn list_reporter_history(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Reporter>, TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreListReporterHistoryOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_reporter_history(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
+        let mut query = reporter::table
+            .into_boxed()
+            .select(reporter::all_columns)
+            .filter(reporter::record_id.eq(record_id))
+            .order((
+                reporter::property_name.asc(),
+                reporter::public_key.asc(),
+                reporter::start_commit_num.asc(),
+            ));
+
+        if let Some(service_id) = service_id {
+            query = query.filter(reporter::service_id.eq(service_id));
+        } else {
+            query = query.filter(reporter::service_id.is_null());
+        }
+
+        let models = query.load::<ReporterModel>(self.conn).map_err(|err| {
+            TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(err)))
+        })?;
+
+        Ok(models.into_iter().map(Reporter::from).collect())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreListReporterHistoryOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_reporter_history(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
+        let mut query = reporter::table
+            .into_boxed()
+            .select(reporter::all_columns)
+            .filter(reporter::record_id.eq(record_id))
+            .order((
+                reporter::property_name.asc(),
+                reporter::public_key.asc(),
+                reporter::start_commit_num.asc(),
+            ));
+
+        if let Some(service_id) = service_id {
+            query = query.filter(reporter::service_id.eq(service_id));
+        } else {
+            query = query.filter(reporter::service_id.is_null());
+        }
+
+        let models = query.load::<ReporterModel>(self.conn).map_err(|err| {
+            TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(err)))
+        })?;
+
+        Ok(models.into_iter().map(Reporter::from).collect())
+    }
+}
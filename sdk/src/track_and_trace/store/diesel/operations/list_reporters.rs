@@ -15,7 +15,7 @@
 use super::TrackAndTraceStoreOperations;
 use crate::track_and_trace::store::diesel::{schema::reporter, TrackAndTraceStoreError};
 
-use crate::commits::MAX_COMMIT_NUM;
+use crate::commits::commit_height_or_current;
 use crate::error::InternalError;
 use crate::track_and_trace::store::diesel::models::ReporterModel;
 use crate::track_and_trace::store::Reporter;
@@ -27,6 +27,7 @@ pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStoreListReport
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Reporter>, TrackAndTraceStoreError>;
 }
@@ -39,8 +40,10 @@ impl<'a> TrackAndTraceStoreListReportersOperation
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = reporter::table
             .into_boxed()
             .select(reporter::all_columns)
@@ -48,7 +51,11 @@ impl<'a> TrackAndTraceStoreListReportersOperation
                 reporter::property_name
                     .eq(property_name)
                     .and(reporter::record_id.eq(record_id))
-                    .and(reporter::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        reporter::start_commit_num
+                            .le(height)
+                            .and(reporter::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
@@ -83,8 +90,10 @@ impl<'a> TrackAndTraceStoreListReportersOperation
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
         let mut query = reporter::table
             .into_boxed()
             .select(reporter::all_columns)
@@ -92,7 +101,11 @@ impl<'a> TrackAndTraceStoreListReportersOperation
                 reporter::property_name
                     .eq(property_name)
                     .and(reporter::record_id.eq(record_id))
-                    .and(reporter::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    .and(
+                        reporter::start_commit_num
+                            .le(height)
+                            .and(reporter::end_commit_num.gt(height)),
+                    ),
             );
 
         if let Some(service_id) = service_id {
@@ -24,9 +24,15 @@ pub(super) mod fetch_reported_value_reporter_to_agent_metadata;
 pub(super) mod list_associated_agents;
 pub(super) mod list_properties_with_data_type;
 pub(super) mod list_proposals;
+pub(super) mod list_record_contents;
 pub(super) mod list_records;
+pub(super) mod list_records_by_location;
+pub(super) mod list_records_by_product;
+pub(super) mod list_records_matching;
 pub(super) mod list_reported_value_reporter_to_agent_metadata;
+pub(super) mod list_reporter_history;
 pub(super) mod list_reporters;
+pub(super) mod prune_property_page;
 
 pub(super) struct TrackAndTraceStoreOperations<'a, C> {
     conn: &'a C,
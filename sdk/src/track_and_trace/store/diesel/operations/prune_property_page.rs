@@ -0,0 +1,285 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::track_and_trace::store::diesel::{
+    schema::{property, reported_value},
+    TrackAndTraceStoreError,
+};
+
+use crate::commits::MAX_COMMIT_NUM;
+use crate::error::InternalError;
+
+use diesel::{dsl::delete, prelude::*};
+
+pub(in crate::track_and_trace::store::diesel) trait TrackAndTraceStorePrunePropertyPageOperation<
+    C: Connection,
+>
+{
+    fn prune_property_page(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        page: i32,
+        service_id: Option<&str>,
+    ) -> Result<(), TrackAndTraceStoreError>;
+    fn prune_reported_values(
+        conn: &C,
+        record_id: &str,
+        property_name: &str,
+        parent_name: Option<&str>,
+        service_id: Option<&str>,
+        before_commit_num: i64,
+    ) -> QueryResult<()>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStorePrunePropertyPageOperation<diesel::pg::PgConnection>
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn prune_property_page(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        page: i32,
+        service_id: Option<&str>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, TrackAndTraceStoreError, _>(|| {
+                // The property moved off the page on its previous pass around the ring when its
+                // last version on the page ended
+                let mut query = property::table
+                    .into_boxed()
+                    .select(property::end_commit_num)
+                    .filter(
+                        property::record_id
+                            .eq(record_id)
+                            .and(property::name.eq(property_name))
+                            .and(property::current_page.eq(page))
+                            .and(property::end_commit_num.ne(MAX_COMMIT_NUM)),
+                    )
+                    .order(property::start_commit_num.desc());
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(property::service_id.eq(service_id));
+                } else {
+                    query = query.filter(property::service_id.is_null());
+                }
+
+                let page_left_commit_num =
+                    query.first::<i64>(self.conn).optional().map_err(|err| {
+                        TrackAndTraceStoreError::InternalError(InternalError::from_source(
+                            Box::new(err),
+                        ))
+                    })?;
+
+                match page_left_commit_num {
+                    Some(page_left_commit_num) => Self::prune_reported_values(
+                        self.conn,
+                        record_id,
+                        property_name,
+                        None,
+                        service_id,
+                        page_left_commit_num,
+                    )
+                    .map_err(|err| {
+                        TrackAndTraceStoreError::InternalError(InternalError::from_source(
+                            Box::new(err),
+                        ))
+                    }),
+                    None => Ok(()),
+                }
+            })
+    }
+
+    fn prune_reported_values(
+        conn: &diesel::pg::PgConnection,
+        record_id: &str,
+        property_name: &str,
+        parent_name: Option<&str>,
+        service_id: Option<&str>,
+        before_commit_num: i64,
+    ) -> QueryResult<()> {
+        let mut children_query = reported_value::table
+            .into_boxed()
+            .select(reported_value::property_name)
+            .distinct()
+            .filter(
+                reported_value::parent_name
+                    .eq(property_name)
+                    .and(reported_value::record_id.eq(record_id))
+                    .and(reported_value::start_commit_num.lt(before_commit_num)),
+            );
+
+        let mut delete_query = delete(reported_value::table).into_boxed().filter(
+            reported_value::record_id
+                .eq(record_id)
+                .and(reported_value::property_name.eq(property_name))
+                .and(reported_value::start_commit_num.lt(before_commit_num))
+                .and(reported_value::end_commit_num.ne(MAX_COMMIT_NUM)),
+        );
+
+        if let Some(parent_name) = parent_name {
+            delete_query = delete_query.filter(reported_value::parent_name.eq(parent_name));
+        } else {
+            delete_query = delete_query.filter(reported_value::parent_name.is_null());
+        }
+
+        if let Some(service_id) = service_id {
+            children_query = children_query.filter(reported_value::service_id.eq(service_id));
+            delete_query = delete_query.filter(reported_value::service_id.eq(service_id));
+        } else {
+            children_query = children_query.filter(reported_value::service_id.is_null());
+            delete_query = delete_query.filter(reported_value::service_id.is_null());
+        }
+
+        let child_names = children_query.load::<String>(conn)?;
+
+        delete_query.execute(conn)?;
+
+        for child_name in child_names {
+            Self::prune_reported_values(
+                conn,
+                record_id,
+                &child_name,
+                Some(property_name),
+                service_id,
+                before_commit_num,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStorePrunePropertyPageOperation<diesel::sqlite::SqliteConnection>
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn prune_property_page(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        page: i32,
+        service_id: Option<&str>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        self.conn
+            .immediate_transaction::<_, TrackAndTraceStoreError, _>(|| {
+                // The property moved off the page on its previous pass around the ring when its
+                // last version on the page ended
+                let mut query = property::table
+                    .into_boxed()
+                    .select(property::end_commit_num)
+                    .filter(
+                        property::record_id
+                            .eq(record_id)
+                            .and(property::name.eq(property_name))
+                            .and(property::current_page.eq(page))
+                            .and(property::end_commit_num.ne(MAX_COMMIT_NUM)),
+                    )
+                    .order(property::start_commit_num.desc());
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(property::service_id.eq(service_id));
+                } else {
+                    query = query.filter(property::service_id.is_null());
+                }
+
+                let page_left_commit_num =
+                    query.first::<i64>(self.conn).optional().map_err(|err| {
+                        TrackAndTraceStoreError::InternalError(InternalError::from_source(
+                            Box::new(err),
+                        ))
+                    })?;
+
+                match page_left_commit_num {
+                    Some(page_left_commit_num) => Self::prune_reported_values(
+                        self.conn,
+                        record_id,
+                        property_name,
+                        None,
+                        service_id,
+                        page_left_commit_num,
+                    )
+                    .map_err(|err| {
+                        TrackAndTraceStoreError::InternalError(InternalError::from_source(
+                            Box::new(err),
+                        ))
+                    }),
+                    None => Ok(()),
+                }
+            })
+    }
+
+    fn prune_reported_values(
+        conn: &diesel::sqlite::SqliteConnection,
+        record_id: &str,
+        property_name: &str,
+        parent_name: Option<&str>,
+        service_id: Option<&str>,
+        before_commit_num: i64,
+    ) -> QueryResult<()> {
+        let mut children_query = reported_value::table
+            .into_boxed()
+            .select(reported_value::property_name)
+            .distinct()
+            .filter(
+                reported_value::parent_name
+                    .eq(property_name)
+                    .and(reported_value::record_id.eq(record_id))
+                    .and(reported_value::start_commit_num.lt(before_commit_num)),
+            );
+
+        let mut delete_query = delete(reported_value::table).into_boxed().filter(
+            reported_value::record_id
+                .eq(record_id)
+                .and(reported_value::property_name.eq(property_name))
+                .and(reported_value::start_commit_num.lt(before_commit_num))
+                .and(reported_value::end_commit_num.ne(MAX_COMMIT_NUM)),
+        );
+
+        if let Some(parent_name) = parent_name {
+            delete_query = delete_query.filter(reported_value::parent_name.eq(parent_name));
+        } else {
+            delete_query = delete_query.filter(reported_value::parent_name.is_null());
+        }
+
+        if let Some(service_id) = service_id {
+            children_query = children_query.filter(reported_value::service_id.eq(service_id));
+            delete_query = delete_query.filter(reported_value::service_id.eq(service_id));
+        } else {
+            children_query = children_query.filter(reported_value::service_id.is_null());
+            delete_query = delete_query.filter(reported_value::service_id.is_null());
+        }
+
+        let child_names = children_query.load::<String>(conn)?;
+
+        delete_query.execute(conn)?;
+
+        for child_name in child_names {
+            Self::prune_reported_values(
+                conn,
+                record_id,
+                &child_name,
+                Some(property_name),
+                service_id,
+                before_commit_num,
+            )?;
+        }
+
+        Ok(())
+    }
+}
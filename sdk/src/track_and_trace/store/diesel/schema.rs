@@ -85,6 +85,9 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        parent -> Nullable<Text>,
+        product_id -> Nullable<Text>,
+        location_id -> Nullable<Text>,
     }
 }
 
@@ -130,6 +133,7 @@ table! {
         public_key ->  Nullable<Text>,
         authorized ->  Nullable<Bool>,
         metadata ->  Nullable<Binary>,
+        reported_value_start_commit_num -> Int8,
         reported_value_end_commit_num -> Int8,
         reporter_end_commit_num ->  Nullable<Int8>,
         service_id -> Nullable<Text>,
@@ -0,0 +1,837 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::commits::{
+    commit_height_or_current, end_current_rows, impl_versioned, resolve_fork_rows, Versioned,
+    MAX_COMMIT_NUM,
+};
+use crate::error::InternalError;
+use crate::paging::{page_items, PageRequest};
+use crate::pike::store::memory::MemoryPikeStore;
+use crate::schemas::store::memory::MemorySchemaStore;
+
+use super::{
+    AssociatedAgent, Property, Proposal, Record, RecordFilter, RecordList, ReportedValue,
+    ReportedValueReporterToAgentMetadata, Reporter, TrackAndTraceStore, TrackAndTraceStoreError,
+};
+
+impl_versioned!(AssociatedAgent, Property, Proposal, Record, Reporter);
+
+/// A reported value without its struct values, with the name of the property it is a struct
+/// value of, if any
+#[derive(Clone)]
+struct ReportedValueRow {
+    parent_name: Option<String>,
+    value: ReportedValue,
+}
+
+impl Versioned for ReportedValueRow {
+    fn start_commit_num(&self) -> i64 {
+        self.value.start_commit_num
+    }
+
+    fn end_commit_num(&self) -> i64 {
+        self.value.end_commit_num
+    }
+
+    fn set_end_commit_num(&mut self, end_commit_num: i64) {
+        self.value.end_commit_num = end_commit_num;
+    }
+}
+
+fn reported_value_rows(
+    values: &[ReportedValue],
+    parent_name: Option<&str>,
+    rows: &mut Vec<ReportedValueRow>,
+) {
+    for value in values {
+        rows.push(ReportedValueRow {
+            parent_name: parent_name.map(String::from),
+            value: ReportedValue {
+                struct_values: None,
+                ..value.clone()
+            },
+        });
+        if let Some(struct_values) = &value.struct_values {
+            reported_value_rows(struct_values, Some(&value.property_name), rows);
+        }
+    }
+}
+
+#[derive(Default)]
+struct TrackAndTraceState {
+    associated_agents: Vec<AssociatedAgent>,
+    properties: Vec<Property>,
+    proposals: Vec<Proposal>,
+    records: Vec<Record>,
+    reported_values: Vec<ReportedValueRow>,
+    reporters: Vec<Reporter>,
+}
+
+/// Implementation of `TrackAndTraceStore` that keeps records in memory, versioned by commit like
+/// the database-backed store. Useful for tests and when persistence isn't necessary.
+///
+/// Property data types are read from a `MemorySchemaStore`, and the metadata of reporting agents
+/// from a `MemoryPikeStore`, as the database-backed store joins them from their tables.
+#[derive(Clone, Default)]
+pub struct MemoryTrackAndTraceStore {
+    inner: Arc<Mutex<TrackAndTraceState>>,
+    schema_store: MemorySchemaStore,
+    pike_store: MemoryPikeStore,
+}
+
+impl MemoryTrackAndTraceStore {
+    /// Creates a `MemoryTrackAndTraceStore`
+    ///
+    /// # Arguments
+    ///
+    ///  * `schema_store` - The store of the schemas that record properties are defined by
+    ///  * `pike_store` - The store of the agents that report property values
+    pub fn new(schema_store: MemorySchemaStore, pike_store: MemoryPikeStore) -> Self {
+        MemoryTrackAndTraceStore {
+            inner: Arc::default(),
+            schema_store,
+            pike_store,
+        }
+    }
+
+    /// Rolls the records back to their state before the given commit
+    ///
+    /// # Arguments
+    ///
+    ///  * `commit_num` - The number of the first commit that is no longer on the chain
+    pub fn resolve_fork(&self, commit_num: i64) -> Result<(), TrackAndTraceStoreError> {
+        let mut state = self.state()?;
+        resolve_fork_rows(&mut state.associated_agents, commit_num);
+        resolve_fork_rows(&mut state.properties, commit_num);
+        resolve_fork_rows(&mut state.proposals, commit_num);
+        resolve_fork_rows(&mut state.records, commit_num);
+        resolve_fork_rows(&mut state.reported_values, commit_num);
+        resolve_fork_rows(&mut state.reporters, commit_num);
+        Ok(())
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, TrackAndTraceState>, TrackAndTraceStoreError> {
+        self.inner.lock().map_err(|_| {
+            TrackAndTraceStoreError::InternalError(InternalError::with_message(
+                "Cannot access track and trace state: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+
+    /// Pairs a property with the data type of its definition in the schema of its record
+    fn with_data_type(
+        &self,
+        state: &TrackAndTraceState,
+        property: &Property,
+    ) -> Result<(Property, Option<String>), TrackAndTraceStoreError> {
+        let schema = state.records.iter().find(|record| {
+            record.record_id == property.record_id
+                && record.end_commit_num == property.end_commit_num
+        });
+        let data_type = match schema {
+            Some(record) => self
+                .schema_store
+                .property_data_type(&record.schema, &property.name, property.end_commit_num)
+                .map_err(|err| {
+                    TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(
+                        err,
+                    )))
+                })?,
+            None => None,
+        };
+        Ok((property.clone(), data_type))
+    }
+
+    /// Joins a reported value with the reporter that reported it and that reporter's agent
+    fn with_reporter(
+        &self,
+        state: &TrackAndTraceState,
+        row: &ReportedValueRow,
+    ) -> Result<ReportedValueReporterToAgentMetadata, TrackAndTraceStoreError> {
+        let value = &row.value;
+        let reporter = state
+            .reporters
+            .iter()
+            .filter(|reporter| {
+                reporter.record_id == value.record_id
+                    && reporter.property_name == value.property_name
+                    && reporter.reporter_index == value.reporter_index
+                    && reporter.end_commit_num >= value.end_commit_num
+            })
+            .min_by_key(|reporter| reporter.end_commit_num);
+        let metadata = match reporter {
+            Some(reporter) => self
+                .pike_store
+                .reporter_metadata(&reporter.public_key, reporter.end_commit_num)
+                .map_err(|err| {
+                    TrackAndTraceStoreError::InternalError(InternalError::from_source(Box::new(
+                        err,
+                    )))
+                })?,
+            None => None,
+        };
+
+        Ok(ReportedValueReporterToAgentMetadata {
+            id: value.id,
+            property_name: value.property_name.clone(),
+            record_id: value.record_id.clone(),
+            reporter_index: value.reporter_index,
+            timestamp: value.timestamp,
+            data_type: value.data_type.clone(),
+            bytes_value: value.bytes_value.clone(),
+            boolean_value: value.boolean_value,
+            number_value: value.number_value,
+            string_value: value.string_value.clone(),
+            enum_value: value.enum_value,
+            struct_values: Vec::new(),
+            lat_long_value: value.lat_long_value.clone(),
+            public_key: reporter.map(|reporter| reporter.public_key.clone()),
+            authorized: reporter.map(|reporter| reporter.authorized),
+            metadata,
+            reported_value_end_commit_num: value.end_commit_num,
+            reporter_end_commit_num: reporter.map(|reporter| reporter.end_commit_num),
+            service_id: value.service_id.clone(),
+        })
+    }
+
+    /// Joins reported values with their reporters, and their struct values with theirs
+    fn with_struct_values(
+        &self,
+        state: &TrackAndTraceState,
+        rows: Vec<&ReportedValueRow>,
+    ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
+        rows.into_iter()
+            .map(|row| {
+                let children = state
+                    .reported_values
+                    .iter()
+                    .filter(|child| {
+                        child.parent_name.as_ref() == Some(&row.value.property_name)
+                            && child.value.record_id == row.value.record_id
+                            && child.value.end_commit_num == row.value.end_commit_num
+                            && child.value.service_id == row.value.service_id
+                    })
+                    .collect();
+                Ok(ReportedValueReporterToAgentMetadata {
+                    struct_values: self.with_struct_values(state, children)?,
+                    ..self.with_reporter(state, row)?
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the struct values of a property's reported value, as versions of the property's
+    /// root value ending with the given commit, as the database-backed store does
+    fn root_struct_values<F>(
+        &self,
+        state: &TrackAndTraceState,
+        record_id: &str,
+        property_name: &str,
+        service_id: Option<&str>,
+        is_version: F,
+    ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError>
+    where
+        F: Fn(&ReportedValueRow) -> bool,
+    {
+        let roots = state
+            .reported_values
+            .iter()
+            .filter(|row| {
+                row.parent_name.is_none()
+                    && row.value.record_id == record_id
+                    && row.value.property_name == property_name
+                    && row.value.service_id.as_deref() == service_id
+                    && is_version(row)
+            })
+            .collect();
+        self.with_struct_values(state, roots)
+    }
+}
+
+/// Removes the versions of a reported value that were reported before the given commit, along
+/// with the versions of its struct values
+fn prune_reported_value_rows(
+    rows: &mut Vec<ReportedValueRow>,
+    record_id: &str,
+    property_name: &str,
+    parent_name: Option<&str>,
+    service_id: Option<&str>,
+    before_commit_num: i64,
+) {
+    let is_pruned = |row: &ReportedValueRow, property_name: &str, parent_name: Option<&str>| {
+        row.parent_name.as_deref() == parent_name
+            && row.value.record_id == record_id
+            && row.value.property_name == property_name
+            && row.value.service_id.as_deref() == service_id
+            && row.value.start_commit_num < before_commit_num
+            && row.value.end_commit_num != MAX_COMMIT_NUM
+    };
+
+    let child_names = rows
+        .iter()
+        .filter(|row| {
+            row.parent_name.as_deref() == Some(property_name)
+                && row.value.record_id == record_id
+                && row.value.service_id.as_deref() == service_id
+                && row.value.start_commit_num < before_commit_num
+        })
+        .map(|row| row.value.property_name.clone())
+        .collect::<BTreeSet<_>>();
+
+    rows.retain(|row| !is_pruned(row, property_name, parent_name));
+
+    for child_name in child_names {
+        prune_reported_value_rows(
+            rows,
+            record_id,
+            &child_name,
+            Some(property_name),
+            service_id,
+            before_commit_num,
+        );
+    }
+}
+
+fn records_page(mut records: Vec<&Record>, page: &PageRequest) -> RecordList {
+    records.sort_by(|a, b| a.record_id.cmp(&b.record_id));
+    let (records, paging) = page_items(records, page, |record| record.record_id.clone());
+    RecordList::new(records.into_iter().cloned().collect(), paging)
+}
+
+impl TrackAndTraceStore for MemoryTrackAndTraceStore {
+    fn add_associated_agents(
+        &self,
+        agents: Vec<AssociatedAgent>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        let mut state = self.state()?;
+        for agent in agents {
+            end_current_rows(
+                &mut state.associated_agents,
+                agent.start_commit_num,
+                |current| {
+                    current.agent_id == agent.agent_id
+                        && current.record_id == agent.record_id
+                        && current.service_id == agent.service_id
+                },
+            );
+            state.associated_agents.push(agent);
+        }
+        Ok(())
+    }
+
+    fn add_properties(&self, properties: Vec<Property>) -> Result<(), TrackAndTraceStoreError> {
+        let mut state = self.state()?;
+        for property in properties {
+            end_current_rows(
+                &mut state.properties,
+                property.start_commit_num,
+                |current| {
+                    current.name == property.name
+                        && current.record_id == property.record_id
+                        && current.service_id == property.service_id
+                },
+            );
+            state.properties.push(property);
+        }
+        Ok(())
+    }
+
+    fn add_proposals(&self, proposals: Vec<Proposal>) -> Result<(), TrackAndTraceStoreError> {
+        let mut state = self.state()?;
+        for proposal in proposals {
+            end_current_rows(&mut state.proposals, proposal.start_commit_num, |current| {
+                current.record_id == proposal.record_id
+                    && current.receiving_agent == proposal.receiving_agent
+                    && current.role == proposal.role
+                    && current.service_id == proposal.service_id
+            });
+            state.proposals.push(proposal);
+        }
+        Ok(())
+    }
+
+    fn add_records(&self, records: Vec<Record>) -> Result<(), TrackAndTraceStoreError> {
+        let mut state = self.state()?;
+        for record in records {
+            end_current_rows(&mut state.records, record.start_commit_num, |current| {
+                current.record_id == record.record_id && current.service_id == record.service_id
+            });
+            state.records.push(record);
+        }
+        Ok(())
+    }
+
+    fn add_reported_values(
+        &self,
+        values: Vec<ReportedValue>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        let mut state = self.state()?;
+
+        let mut rows = Vec::new();
+        reported_value_rows(&values, None, &mut rows);
+        for row in rows {
+            end_current_rows(
+                &mut state.reported_values,
+                row.value.start_commit_num,
+                |current| {
+                    current.value.record_id == row.value.record_id
+                        && current.value.property_name == row.value.property_name
+                        && current.value.service_id == row.value.service_id
+                },
+            );
+            state.reported_values.push(row);
+        }
+        Ok(())
+    }
+
+    fn add_reporters(&self, reporters: Vec<Reporter>) -> Result<(), TrackAndTraceStoreError> {
+        let mut state = self.state()?;
+        for reporter in reporters {
+            end_current_rows(&mut state.reporters, reporter.start_commit_num, |current| {
+                current.record_id == reporter.record_id
+                    && current.property_name == reporter.property_name
+                    && current.public_key == reporter.public_key
+                    && current.service_id == reporter.service_id
+            });
+            state.reporters.push(reporter);
+        }
+        Ok(())
+    }
+
+    fn fetch_property_with_data_type(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Option<(Property, Option<String>)>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        state
+            .properties
+            .iter()
+            .find(|property| {
+                property.name == property_name
+                    && property.record_id == record_id
+                    && property.service_id.as_deref() == service_id
+                    && property.is_current_at(height)
+            })
+            .map(|property| self.with_data_type(&state, property))
+            .transpose()
+    }
+
+    fn fetch_record(
+        &self,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Option<Record>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        Ok(state
+            .records
+            .iter()
+            .find(|record| {
+                record.record_id == record_id
+                    && record.service_id.as_deref() == service_id
+                    && record.is_current_at(height)
+            })
+            .cloned())
+    }
+
+    fn fetch_reported_value_reporter_to_agent_metadata(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Option<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let row = match state.reported_values.iter().find(|row| {
+            row.value.property_name == property_name
+                && row.value.record_id == record_id
+                && row.value.service_id.as_deref() == service_id
+                && row.is_current_at(height)
+        }) {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let struct_values =
+            self.root_struct_values(&state, record_id, property_name, service_id, |root| {
+                root.is_current_at(height)
+            })?;
+
+        Ok(Some(ReportedValueReporterToAgentMetadata {
+            struct_values,
+            ..self.with_reporter(&state, row)?
+        }))
+    }
+
+    fn list_associated_agents(
+        &self,
+        record_ids: &[String],
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<AssociatedAgent>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        Ok(state
+            .associated_agents
+            .iter()
+            .filter(|agent| {
+                record_ids.contains(&agent.record_id)
+                    && agent.service_id.as_deref() == service_id
+                    && agent.is_current_at(height)
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn list_properties_with_data_type(
+        &self,
+        record_ids: &[String],
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<(Property, Option<String>)>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        state
+            .properties
+            .iter()
+            .filter(|property| {
+                record_ids.contains(&property.record_id)
+                    && property.service_id.as_deref() == service_id
+                    && property.is_current_at(height)
+            })
+            .map(|property| self.with_data_type(&state, property))
+            .collect()
+    }
+
+    fn list_proposals(
+        &self,
+        record_ids: &[String],
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Proposal>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        Ok(state
+            .proposals
+            .iter()
+            .filter(|proposal| {
+                record_ids.contains(&proposal.record_id)
+                    && proposal.service_id.as_deref() == service_id
+                    && proposal.is_current_at(height)
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn list_record_contents(
+        &self,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
+        let state = self.state()?;
+
+        let mut records = state
+            .records
+            .iter()
+            .filter(|record| {
+                record.parent.as_deref() == Some(record_id)
+                    && record.service_id.as_deref() == service_id
+                    && match commit_height {
+                        Some(height) => record.is_current_at(height),
+                        None => record.end_commit_num == MAX_COMMIT_NUM,
+                    }
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        records.sort_by(|a, b| a.record_id.cmp(&b.record_id));
+        Ok(records)
+    }
+
+    fn list_records(
+        &self,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let records = state
+            .records
+            .iter()
+            .filter(|record| {
+                record.service_id.as_deref() == service_id && record.is_current_at(height)
+            })
+            .collect();
+        Ok(records_page(records, page))
+    }
+
+    fn list_records_by_product(
+        &self,
+        product_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let records = state
+            .records
+            .iter()
+            .filter(|record| {
+                record.product_id.as_deref() == Some(product_id)
+                    && record.service_id.as_deref() == service_id
+                    && record.is_current_at(height)
+            })
+            .collect();
+        Ok(records_page(records, page))
+    }
+
+    fn list_records_by_location(
+        &self,
+        location_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let records = state
+            .records
+            .iter()
+            .filter(|record| {
+                record.location_id.as_deref() == Some(location_id)
+                    && record.service_id.as_deref() == service_id
+                    && record.is_current_at(height)
+            })
+            .collect();
+        Ok(records_page(records, page))
+    }
+
+    fn list_records_matching(
+        &self,
+        filter: &RecordFilter,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        let in_span = |timestamp: i64| {
+            filter
+                .updated_from
+                .map(|from| timestamp >= from)
+                .unwrap_or(true)
+                && filter.updated_to.map(|to| timestamp < to).unwrap_or(true)
+        };
+        let updated = |record: &Record| {
+            if !filter.is_span_set() {
+                return true;
+            }
+            state.associated_agents.iter().any(|agent| {
+                agent.record_id == record.record_id
+                    && agent.service_id.as_deref() == service_id
+                    && agent.is_current_at(height)
+                    && in_span(agent.timestamp)
+            }) || state.reported_values.iter().any(|row| {
+                row.value.record_id == record.record_id
+                    && row.value.service_id.as_deref() == service_id
+                    && row.value.start_commit_num <= height
+                    && in_span(row.value.timestamp)
+            })
+        };
+        let held_by_org = |record: &Record| -> Result<bool, TrackAndTraceStoreError> {
+            let org_id = match filter.org_id {
+                Some(org_id) => org_id,
+                None => return Ok(true),
+            };
+            for holder in record
+                .owners
+                .last()
+                .iter()
+                .chain(record.custodians.last().iter())
+            {
+                let holder_org_id = self
+                    .pike_store
+                    .agent_org_id(holder, height, service_id)
+                    .map_err(|err| {
+                        TrackAndTraceStoreError::InternalError(InternalError::from_source(
+                            Box::new(err),
+                        ))
+                    })?;
+                if holder_org_id.as_deref() == Some(org_id) {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        };
+
+        let mut records = Vec::new();
+        for record in state.records.iter().filter(|record| {
+            filter
+                .product_id
+                .map(|product_id| record.product_id.as_deref() == Some(product_id))
+                .unwrap_or(true)
+                && record.service_id.as_deref() == service_id
+                && record.is_current_at(height)
+                && updated(record)
+        }) {
+            if held_by_org(record)? {
+                records.push(record);
+            }
+        }
+        Ok(records_page(records, page))
+    }
+
+    fn list_reported_value_reporter_to_agent_metadata(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        state
+            .reported_values
+            .iter()
+            .filter(|row| {
+                row.value.property_name == property_name
+                    && row.value.record_id == record_id
+                    && row.value.start_commit_num <= height
+            })
+            .map(|row| {
+                let end_commit_num = row.value.end_commit_num;
+                let struct_values = self.root_struct_values(
+                    &state,
+                    record_id,
+                    property_name,
+                    service_id,
+                    |root| root.value.end_commit_num == end_commit_num,
+                )?;
+                Ok(ReportedValueReporterToAgentMetadata {
+                    struct_values,
+                    ..self.with_reporter(&state, row)?
+                })
+            })
+            .collect()
+    }
+
+    fn list_reporters(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
+        let height = commit_height_or_current(commit_height);
+        let state = self.state()?;
+
+        Ok(state
+            .reporters
+            .iter()
+            .filter(|reporter| {
+                reporter.property_name == property_name
+                    && reporter.record_id == record_id
+                    && reporter.service_id.as_deref() == service_id
+                    && reporter.is_current_at(height)
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn list_reporter_history(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
+        let state = self.state()?;
+
+        let mut reporters = state
+            .reporters
+            .iter()
+            .filter(|reporter| {
+                reporter.record_id == record_id && reporter.service_id.as_deref() == service_id
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        reporters.sort_by(|a, b| {
+            (&a.property_name, &a.public_key, a.start_commit_num).cmp(&(
+                &b.property_name,
+                &b.public_key,
+                b.start_commit_num,
+            ))
+        });
+        Ok(reporters)
+    }
+
+    fn prune_property_page(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        page: i32,
+        service_id: Option<&str>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        let mut state = self.state()?;
+
+        // The property moved off the page on its previous pass around the ring when its last
+        // version on the page ended
+        let page_left_commit_num = match state
+            .properties
+            .iter()
+            .filter(|property| {
+                property.record_id == record_id
+                    && property.name == property_name
+                    && property.service_id.as_deref() == service_id
+                    && property.current_page == page
+                    && property.end_commit_num != MAX_COMMIT_NUM
+            })
+            .max_by_key(|property| property.start_commit_num)
+        {
+            Some(property) => property.end_commit_num,
+            None => return Ok(()),
+        };
+
+        prune_reported_value_rows(
+            &mut state.reported_values,
+            record_id,
+            property_name,
+            None,
+            service_id,
+            page_left_commit_num,
+        );
+        Ok(())
+    }
+}
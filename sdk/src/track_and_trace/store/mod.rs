@@ -15,8 +15,10 @@
 #[cfg(feature = "diesel")]
 pub mod diesel;
 mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
 
-use crate::paging::Paging;
+use crate::paging::{PageRequest, Paging};
 
 pub use error::TrackAndTraceStoreError;
 
@@ -69,6 +71,9 @@ pub struct Record {
     pub final_: bool,
     pub owners: Vec<String>,
     pub custodians: Vec<String>,
+    pub parent: Option<String>,
+    pub product_id: Option<String>,
+    pub location_id: Option<String>,
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
@@ -86,6 +91,26 @@ impl RecordList {
     }
 }
 
+/// The conditions a record must meet to be listed by `list_records_matching`; the conditions
+/// left unset match every record
+#[derive(Clone, Debug, Default)]
+pub struct RecordFilter<'a> {
+    /// The GTIN of the product the records are linked to
+    pub product_id: Option<&'a str>,
+    /// The organization of the agent that is the current owner or custodian of the records
+    pub org_id: Option<&'a str>,
+    /// The time the records must have been updated at or after, in seconds since the epoch
+    pub updated_from: Option<i64>,
+    /// The time the records must have been updated before, in seconds since the epoch
+    pub updated_to: Option<i64>,
+}
+
+impl<'a> RecordFilter<'a> {
+    fn is_span_set(&self) -> bool {
+        self.updated_from.is_some() || self.updated_to.is_some()
+    }
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct ReportedValue {
     pub id: Option<i64>,
@@ -203,11 +228,14 @@ pub trait TrackAndTraceStore: Send + Sync {
     ///
     ///  * `record_id` - The record ID to fetch for
     ///  * `property_name` - The property name to fetch
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service ID to fetch for
     fn fetch_property_with_data_type(
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<(Property, Option<String>)>, TrackAndTraceStoreError>;
 
@@ -216,10 +244,13 @@ pub trait TrackAndTraceStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `record_id` - The record ID to fetch for
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service ID to fetch for
     fn fetch_record(
         &self,
         record_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Record>, TrackAndTraceStoreError>;
 
@@ -229,7 +260,8 @@ pub trait TrackAndTraceStore: Send + Sync {
     ///
     ///  * `record_id` - The record ID to fetch for
     ///  * `property_name` - The property name to fetch
-    ///  * `commit_height` - The commit height of the reported value to fetch
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service ID to fetch for
     fn fetch_reported_value_reporter_to_agent_metadata(
         &self,
@@ -244,10 +276,13 @@ pub trait TrackAndTraceStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `record_ids` - The record IDs to fetch for
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service ID to fetch for
     fn list_associated_agents(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<AssociatedAgent>, TrackAndTraceStoreError>;
 
@@ -256,10 +291,13 @@ pub trait TrackAndTraceStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `record_ids` - The list of record IDs to fetch for
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service ID to fetch for
     fn list_properties_with_data_type(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<(Property, Option<String>)>, TrackAndTraceStoreError>;
 
@@ -268,25 +306,97 @@ pub trait TrackAndTraceStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `record_ids` - The list of record IDs to fetch for
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service ID to fetch for
     fn list_proposals(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Proposal>, TrackAndTraceStoreError>;
 
+    /// Fetches the records aggregated into a record from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `record_id` - The record ID of the containing record
+    ///  * `commit_height` - The commit height at which to list the contents, or the current
+    ///    contents if not provided
+    ///  * `service_id` - The service ID to fetch for
+    fn list_record_contents(
+        &self,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Record>, TrackAndTraceStoreError>;
+
     /// Fetches a list of records from the underlying storage
     ///
     /// # Arguments
     ///
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service ID to fetch for
-    ///  * `offset` - The index of the first in storage to retrieve
-    ///  * `limit` - The number of items to retrieve from the offset
+    ///  * `page` - The page to retrieve, by offset or by key
     fn list_records(
         &self,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError>;
+
+    /// Fetches a list of the current records for a product from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `product_id` - The GTIN of the product the records are linked to
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
+    ///  * `service_id` - The service ID to fetch for
+    ///  * `page` - The page to retrieve, by offset or by key
+    fn list_records_by_product(
+        &self,
+        product_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError>;
+
+    /// Fetches a list of the current records at a location from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `location_id` - The GLN of the location the records are at
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
+    ///  * `service_id` - The service ID to fetch for
+    ///  * `page` - The page to retrieve, by offset or by key
+    fn list_records_by_location(
+        &self,
+        location_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError>;
+
+    /// Fetches a list of the current records that match a filter from the underlying storage.
+    /// A record is updated within the filter's span of time by a change of owner or custodian,
+    /// or by a reported value, timestamped within it
+    ///
+    /// # Arguments
+    ///
+    ///  * `filter` - The conditions the records must meet
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
+    ///  * `service_id` - The service ID to fetch for
+    ///  * `page` - The page to retrieve, by offset or by key
+    fn list_records_matching(
+        &self,
+        filter: &RecordFilter,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
     ) -> Result<RecordList, TrackAndTraceStoreError>;
 
     /// Fetches a list of reported value reported to agent metadata objects from the underlying
@@ -296,11 +406,14 @@ pub trait TrackAndTraceStore: Send + Sync {
     ///
     ///  * `record_id` - The record ID to fetch for
     ///  * `property_name` - The property name to fetch
+    ///  * `commit_height` - The commit height up to which to list reported values, or all
+    ///    reported values if not provided
     ///  * `service_id` - The service ID to fetch for
     fn list_reported_value_reporter_to_agent_metadata(
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError>;
 
@@ -310,13 +423,56 @@ pub trait TrackAndTraceStore: Send + Sync {
     ///
     ///  * `record_id` - The record ID to fetch for
     ///  * `property_name` - The property name to fetch
+    ///  * `commit_height` - The commit height at which to read state, or the current state if
+    ///    not provided
     ///  * `service_id` - The service ID to fetch for
     fn list_reporters(
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Reporter>, TrackAndTraceStoreError>;
+
+    /// Fetches every version of the reporters of a record from the underlying storage,
+    /// including reporters that have since been revoked or reauthorized
+    ///
+    /// Reporters are ordered by property name, public key, and start commit number.
+    ///
+    /// # Arguments
+    ///
+    ///  * `record_id` - The record ID to fetch for
+    ///  * `service_id` - The service ID to fetch for
+    fn list_reporter_history(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Reporter>, TrackAndTraceStoreError>;
+
+    /// Removes the reported values, and their struct values, that a property page held before
+    /// the property's page ring wrapped around and the property moved back on to the page. It
+    /// is called before the values reported to the page in the same commit are added.
+    ///
+    /// The values removed are those reported before the commit that moved the property off the
+    /// page on its previous pass around the ring. Values reported in that commit are kept, as
+    /// they may belong to the page that follows; they are removed when that page is overwritten.
+    ///
+    /// Only values that have been replaced by a later value are removed. They are deleted
+    /// rather than ended, so reads as of a commit before the prune no longer return them.
+    ///
+    /// # Arguments
+    ///
+    ///  * `record_id` - The record ID of the property
+    ///  * `property_name` - The name of the property
+    ///  * `page` - The number of the page being overwritten
+    ///  * `service_id` - The service ID of the property
+    fn prune_property_page(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        page: i32,
+        service_id: Option<&str>,
+    ) -> Result<(), TrackAndTraceStoreError>;
 }
 
 impl<TS> TrackAndTraceStore for Box<TS>
@@ -357,17 +513,19 @@ where
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<(Property, Option<String>)>, TrackAndTraceStoreError> {
-        (**self).fetch_property_with_data_type(record_id, property_name, service_id)
+        (**self).fetch_property_with_data_type(record_id, property_name, commit_height, service_id)
     }
 
     fn fetch_record(
         &self,
         record_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Option<Record>, TrackAndTraceStoreError> {
-        (**self).fetch_record(record_id, service_id)
+        (**self).fetch_record(record_id, commit_height, service_id)
     }
 
     fn fetch_reported_value_reporter_to_agent_metadata(
@@ -388,45 +546,89 @@ where
     fn list_associated_agents(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<AssociatedAgent>, TrackAndTraceStoreError> {
-        (**self).list_associated_agents(record_ids, service_id)
+        (**self).list_associated_agents(record_ids, commit_height, service_id)
     }
 
     fn list_properties_with_data_type(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<(Property, Option<String>)>, TrackAndTraceStoreError> {
-        (**self).list_properties_with_data_type(record_ids, service_id)
+        (**self).list_properties_with_data_type(record_ids, commit_height, service_id)
     }
 
     fn list_proposals(
         &self,
         record_ids: &[String],
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<Proposal>, TrackAndTraceStoreError> {
-        (**self).list_proposals(record_ids, service_id)
+        (**self).list_proposals(record_ids, commit_height, service_id)
+    }
+
+    fn list_record_contents(
+        &self,
+        record_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
+        (**self).list_record_contents(record_id, commit_height, service_id)
     }
 
     fn list_records(
         &self,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        (**self).list_records(commit_height, service_id, page)
+    }
+
+    fn list_records_by_product(
+        &self,
+        product_id: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        (**self).list_records_by_product(product_id, commit_height, service_id, page)
+    }
+
+    fn list_records_by_location(
+        &self,
+        location_id: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
-        offset: i64,
-        limit: i64,
+        page: &PageRequest,
     ) -> Result<RecordList, TrackAndTraceStoreError> {
-        (**self).list_records(service_id, offset, limit)
+        (**self).list_records_by_location(location_id, commit_height, service_id, page)
+    }
+
+    fn list_records_matching(
+        &self,
+        filter: &RecordFilter,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<RecordList, TrackAndTraceStoreError> {
+        (**self).list_records_matching(filter, commit_height, service_id, page)
     }
 
     fn list_reported_value_reporter_to_agent_metadata(
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
         service_id: Option<&str>,
     ) -> Result<Vec<ReportedValueReporterToAgentMetadata>, TrackAndTraceStoreError> {
         (**self).list_reported_value_reporter_to_agent_metadata(
             record_id,
             property_name,
+            commit_height,
             service_id,
         )
     }
@@ -435,8 +637,27 @@ where
         &self,
         record_id: &str,
         property_name: &str,
+        commit_height: Option<i64>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
+        (**self).list_reporters(record_id, property_name, commit_height, service_id)
+    }
+
+    fn list_reporter_history(
+        &self,
+        record_id: &str,
         service_id: Option<&str>,
     ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
-        (**self).list_reporters(record_id, property_name, service_id)
+        (**self).list_reporter_history(record_id, service_id)
+    }
+
+    fn prune_property_page(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        page: i32,
+        service_id: Option<&str>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        (**self).prune_property_page(record_id, property_name, page, service_id)
     }
 }
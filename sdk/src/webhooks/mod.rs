@@ -0,0 +1,30 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage for outbound webhook subscriptions and the deliveries queued for them.
+//!
+//! A subscription names the URL that Grid events are posted to, the secret used to sign them and
+//! the entities the subscriber is interested in. Every event that matches a subscription is
+//! queued as a delivery, which is kept until it has been delivered or has run out of attempts.
+
+pub mod store;
+
+#[cfg(feature = "diesel")]
+pub use store::diesel::DieselWebhookStore;
+#[cfg(feature = "memory")]
+pub use store::memory::MemoryWebhookStore;
+pub use store::{
+    DeliveryStatus, WebhookDelivery, WebhookDeliveryList, WebhookStore, WebhookStoreError,
+    WebhookSubscription, WebhookSubscriptionList,
+};
@@ -0,0 +1,340 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod models;
+mod operations;
+pub(in crate) mod schema;
+
+use std::convert::TryFrom;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use super::{
+    WebhookDelivery, WebhookDeliveryList, WebhookStore, WebhookStoreError, WebhookSubscription,
+    WebhookSubscriptionList,
+};
+use crate::error::InternalError;
+use crate::paging::Paging;
+
+use models::{
+    NewWebhookDeliveryModel, NewWebhookSubscriptionModel, WebhookDeliveryModel,
+    WebhookSubscriptionModel,
+};
+use operations::add_deliveries::AddDeliveriesOperation as _;
+use operations::add_subscription::AddSubscriptionOperation as _;
+use operations::fetch_delivery::FetchDeliveryOperation as _;
+use operations::fetch_subscription::FetchSubscriptionOperation as _;
+use operations::list_deliveries::ListDeliveriesOperation as _;
+use operations::list_due_deliveries::ListDueDeliveriesOperation as _;
+use operations::list_subscriptions::ListSubscriptionsOperation as _;
+use operations::remove_subscription::RemoveSubscriptionOperation as _;
+use operations::update_delivery::UpdateDeliveryOperation as _;
+use operations::WebhookStoreOperations;
+
+/// Manages webhook subscriptions and deliveries in the database
+#[derive(Clone)]
+pub struct DieselWebhookStore<C: diesel::Connection + 'static> {
+    connection_pool: Pool<ConnectionManager<C>>,
+}
+
+impl<C: diesel::Connection> DieselWebhookStore<C> {
+    /// Creates a new DieselWebhookStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselWebhookStore { connection_pool }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl WebhookStore for DieselWebhookStore<diesel::pg::PgConnection> {
+    fn add_subscription(
+        &self,
+        subscription: WebhookSubscription,
+    ) -> Result<i64, WebhookStoreError> {
+        let model = NewWebhookSubscriptionModel::try_from(subscription)?;
+        WebhookStoreOperations::new(&*self.connection_pool.get()?).add_subscription(model)
+    }
+
+    fn fetch_subscription(
+        &self,
+        id: i64,
+    ) -> Result<Option<WebhookSubscription>, WebhookStoreError> {
+        WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .fetch_subscription(id)?
+            .map(WebhookSubscription::try_from)
+            .transpose()
+    }
+
+    fn list_subscriptions(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookSubscriptionList, WebhookStoreError> {
+        let (models, total) = WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .list_subscriptions(offset, limit)?;
+        let data = models
+            .into_iter()
+            .map(WebhookSubscription::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(WebhookSubscriptionList::new(
+            data,
+            Paging::new(offset, limit, total),
+        ))
+    }
+
+    fn remove_subscription(&self, id: i64) -> Result<(), WebhookStoreError> {
+        WebhookStoreOperations::new(&*self.connection_pool.get()?).remove_subscription(id)
+    }
+
+    fn add_deliveries(&self, deliveries: Vec<WebhookDelivery>) -> Result<(), WebhookStoreError> {
+        let models = deliveries
+            .into_iter()
+            .map(NewWebhookDeliveryModel::from)
+            .collect();
+        WebhookStoreOperations::new(&*self.connection_pool.get()?).add_deliveries(models)
+    }
+
+    fn fetch_delivery(&self, id: i64) -> Result<Option<WebhookDelivery>, WebhookStoreError> {
+        WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .fetch_delivery(id)?
+            .map(WebhookDelivery::try_from)
+            .transpose()
+    }
+
+    fn list_deliveries(
+        &self,
+        subscription_id: i64,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookDeliveryList, WebhookStoreError> {
+        let (models, total) = WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .list_deliveries(subscription_id, offset, limit)?;
+        let data = models
+            .into_iter()
+            .map(WebhookDelivery::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(WebhookDeliveryList::new(
+            data,
+            Paging::new(offset, limit, total),
+        ))
+    }
+
+    fn list_due_deliveries(
+        &self,
+        now: i64,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookStoreError> {
+        WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .list_due_deliveries(now, limit)?
+            .into_iter()
+            .map(WebhookDelivery::try_from)
+            .collect()
+    }
+
+    fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<(), WebhookStoreError> {
+        let id = delivery.id.ok_or_else(|| {
+            WebhookStoreError::NotFoundError("delivery without an id".to_string())
+        })?;
+        WebhookStoreOperations::new(&*self.connection_pool.get()?).update_delivery(
+            id,
+            delivery.status.as_str(),
+            delivery.attempts,
+            delivery.next_attempt_at,
+            delivery.last_error.as_deref(),
+        )
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl WebhookStore for DieselWebhookStore<diesel::sqlite::SqliteConnection> {
+    fn add_subscription(
+        &self,
+        subscription: WebhookSubscription,
+    ) -> Result<i64, WebhookStoreError> {
+        let model = NewWebhookSubscriptionModel::try_from(subscription)?;
+        WebhookStoreOperations::new(&*self.connection_pool.get()?).add_subscription(model)
+    }
+
+    fn fetch_subscription(
+        &self,
+        id: i64,
+    ) -> Result<Option<WebhookSubscription>, WebhookStoreError> {
+        WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .fetch_subscription(id)?
+            .map(WebhookSubscription::try_from)
+            .transpose()
+    }
+
+    fn list_subscriptions(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookSubscriptionList, WebhookStoreError> {
+        let (models, total) = WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .list_subscriptions(offset, limit)?;
+        let data = models
+            .into_iter()
+            .map(WebhookSubscription::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(WebhookSubscriptionList::new(
+            data,
+            Paging::new(offset, limit, total),
+        ))
+    }
+
+    fn remove_subscription(&self, id: i64) -> Result<(), WebhookStoreError> {
+        WebhookStoreOperations::new(&*self.connection_pool.get()?).remove_subscription(id)
+    }
+
+    fn add_deliveries(&self, deliveries: Vec<WebhookDelivery>) -> Result<(), WebhookStoreError> {
+        let models = deliveries
+            .into_iter()
+            .map(NewWebhookDeliveryModel::from)
+            .collect();
+        WebhookStoreOperations::new(&*self.connection_pool.get()?).add_deliveries(models)
+    }
+
+    fn fetch_delivery(&self, id: i64) -> Result<Option<WebhookDelivery>, WebhookStoreError> {
+        WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .fetch_delivery(id)?
+            .map(WebhookDelivery::try_from)
+            .transpose()
+    }
+
+    fn list_deliveries(
+        &self,
+        subscription_id: i64,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookDeliveryList, WebhookStoreError> {
+        let (models, total) = WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .list_deliveries(subscription_id, offset, limit)?;
+        let data = models
+            .into_iter()
+            .map(WebhookDelivery::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(WebhookDeliveryList::new(
+            data,
+            Paging::new(offset, limit, total),
+        ))
+    }
+
+    fn list_due_deliveries(
+        &self,
+        now: i64,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookStoreError> {
+        WebhookStoreOperations::new(&*self.connection_pool.get()?)
+            .list_due_deliveries(now, limit)?
+            .into_iter()
+            .map(WebhookDelivery::try_from)
+            .collect()
+    }
+
+    fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<(), WebhookStoreError> {
+        let id = delivery.id.ok_or_else(|| {
+            WebhookStoreError::NotFoundError("delivery without an id".to_string())
+        })?;
+        WebhookStoreOperations::new(&*self.connection_pool.get()?).update_delivery(
+            id,
+            delivery.status.as_str(),
+            delivery.attempts,
+            delivery.next_attempt_at,
+            delivery.last_error.as_deref(),
+        )
+    }
+}
+
+impl TryFrom<WebhookSubscription> for NewWebhookSubscriptionModel {
+    type Error = WebhookStoreError;
+
+    fn try_from(subscription: WebhookSubscription) -> Result<Self, Self::Error> {
+        Ok(NewWebhookSubscriptionModel {
+            url: subscription.url,
+            secret: subscription.secret,
+            entity_types: to_json(&subscription.entity_types)?,
+            owner_orgs: to_json(&subscription.owner_orgs)?,
+            service_id: subscription.service_id,
+            created_at: subscription.created_at,
+        })
+    }
+}
+
+impl TryFrom<WebhookSubscriptionModel> for WebhookSubscription {
+    type Error = WebhookStoreError;
+
+    fn try_from(model: WebhookSubscriptionModel) -> Result<Self, Self::Error> {
+        Ok(WebhookSubscription {
+            id: Some(model.id),
+            url: model.url,
+            secret: model.secret,
+            entity_types: from_json(&model.entity_types)?,
+            owner_orgs: from_json(&model.owner_orgs)?,
+            service_id: model.service_id,
+            created_at: model.created_at,
+        })
+    }
+}
+
+impl From<WebhookDelivery> for NewWebhookDeliveryModel {
+    fn from(delivery: WebhookDelivery) -> Self {
+        NewWebhookDeliveryModel {
+            subscription_id: delivery.subscription_id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            payload: delivery.payload,
+            status: delivery.status.as_str().to_string(),
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            last_error: delivery.last_error,
+            created_at: delivery.created_at,
+        }
+    }
+}
+
+impl TryFrom<WebhookDeliveryModel> for WebhookDelivery {
+    type Error = WebhookStoreError;
+
+    fn try_from(model: WebhookDeliveryModel) -> Result<Self, Self::Error> {
+        Ok(WebhookDelivery {
+            id: Some(model.id),
+            subscription_id: model.subscription_id,
+            event_id: model.event_id,
+            event_type: model.event_type,
+            payload: model.payload,
+            status: model.status.parse()?,
+            attempts: model.attempts,
+            next_attempt_at: model.next_attempt_at,
+            last_error: model.last_error,
+            created_at: model.created_at,
+        })
+    }
+}
+
+fn to_json(values: &[String]) -> Result<String, WebhookStoreError> {
+    serde_json::to_string(values)
+        .map_err(|err| WebhookStoreError::InternalError(InternalError::from_source(Box::new(err))))
+}
+
+fn from_json(json: &str) -> Result<Vec<String>, WebhookStoreError> {
+    serde_json::from_str(json)
+        .map_err(|err| WebhookStoreError::InternalError(InternalError::from_source(Box::new(err))))
+}
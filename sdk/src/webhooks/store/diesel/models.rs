@@ -0,0 +1,65 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::schema::{webhook_delivery, webhook_subscription};
+
+#[derive(Insertable, PartialEq, Debug)]
+#[table_name = "webhook_subscription"]
+pub struct NewWebhookSubscriptionModel {
+    pub url: String,
+    pub secret: String,
+    pub entity_types: String,
+    pub owner_orgs: String,
+    pub service_id: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Queryable, PartialEq, Debug)]
+pub struct WebhookSubscriptionModel {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub entity_types: String,
+    pub owner_orgs: String,
+    pub service_id: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Insertable, PartialEq, Debug)]
+#[table_name = "webhook_delivery"]
+pub struct NewWebhookDeliveryModel {
+    pub subscription_id: i64,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Queryable, PartialEq, Debug)]
+pub struct WebhookDeliveryModel {
+    pub id: i64,
+    pub subscription_id: i64,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+}
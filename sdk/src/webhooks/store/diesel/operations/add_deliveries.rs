@@ -0,0 +1,57 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::WebhookStoreOperations;
+use crate::webhooks::store::diesel::{models::NewWebhookDeliveryModel, schema::webhook_delivery};
+use crate::webhooks::store::WebhookStoreError;
+
+use diesel::prelude::*;
+
+pub(in crate::webhooks::store::diesel) trait AddDeliveriesOperation {
+    fn add_deliveries(&self, models: Vec<NewWebhookDeliveryModel>)
+        -> Result<(), WebhookStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AddDeliveriesOperation for WebhookStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_deliveries(
+        &self,
+        models: Vec<NewWebhookDeliveryModel>,
+    ) -> Result<(), WebhookStoreError> {
+        diesel::insert_into(webhook_delivery::table)
+            .values(&models)
+            .on_conflict_do_nothing()
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AddDeliveriesOperation for WebhookStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn add_deliveries(
+        &self,
+        models: Vec<NewWebhookDeliveryModel>,
+    ) -> Result<(), WebhookStoreError> {
+        self.conn.transaction::<_, WebhookStoreError, _>(|| {
+            for model in &models {
+                diesel::insert_or_ignore_into(webhook_delivery::table)
+                    .values(model)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::WebhookStoreOperations;
+use crate::webhooks::store::diesel::{
+    models::NewWebhookSubscriptionModel, schema::webhook_subscription,
+};
+use crate::webhooks::store::WebhookStoreError;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+pub(in crate::webhooks::store::diesel) trait AddSubscriptionOperation {
+    fn add_subscription(
+        &self,
+        model: NewWebhookSubscriptionModel,
+    ) -> Result<i64, WebhookStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AddSubscriptionOperation for WebhookStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_subscription(
+        &self,
+        model: NewWebhookSubscriptionModel,
+    ) -> Result<i64, WebhookStoreError> {
+        Ok(insert_into(webhook_subscription::table)
+            .values(&model)
+            .returning(webhook_subscription::id)
+            .get_result(self.conn)?)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AddSubscriptionOperation for WebhookStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn add_subscription(
+        &self,
+        model: NewWebhookSubscriptionModel,
+    ) -> Result<i64, WebhookStoreError> {
+        self.conn
+            .immediate_transaction::<_, WebhookStoreError, _>(|| {
+                insert_into(webhook_subscription::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+
+                Ok(webhook_subscription::table
+                    .select(webhook_subscription::id)
+                    .order(webhook_subscription::id.desc())
+                    .first(self.conn)?)
+            })
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::WebhookStoreOperations;
+use crate::webhooks::store::diesel::{models::WebhookDeliveryModel, schema::webhook_delivery};
+use crate::webhooks::store::WebhookStoreError;
+
+use diesel::prelude::*;
+
+pub(in crate::webhooks::store::diesel) trait FetchDeliveryOperation {
+    fn fetch_delivery(&self, id: i64) -> Result<Option<WebhookDeliveryModel>, WebhookStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> FetchDeliveryOperation for WebhookStoreOperations<'a, diesel::pg::PgConnection> {
+    fn fetch_delivery(&self, id: i64) -> Result<Option<WebhookDeliveryModel>, WebhookStoreError> {
+        Ok(webhook_delivery::table
+            .filter(webhook_delivery::id.eq(id))
+            .first::<WebhookDeliveryModel>(self.conn)
+            .optional()?)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> FetchDeliveryOperation for WebhookStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn fetch_delivery(&self, id: i64) -> Result<Option<WebhookDeliveryModel>, WebhookStoreError> {
+        Ok(webhook_delivery::table
+            .filter(webhook_delivery::id.eq(id))
+            .first::<WebhookDeliveryModel>(self.conn)
+            .optional()?)
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::WebhookStoreOperations;
+use crate::webhooks::store::diesel::{models::WebhookDeliveryModel, schema::webhook_delivery};
+use crate::webhooks::store::WebhookStoreError;
+
+use diesel::prelude::*;
+
+pub(in crate::webhooks::store::diesel) trait ListDeliveriesOperation {
+    fn list_deliveries(
+        &self,
+        subscription_id: i64,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<WebhookDeliveryModel>, i64), WebhookStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ListDeliveriesOperation for WebhookStoreOperations<'a, diesel::pg::PgConnection> {
+    fn list_deliveries(
+        &self,
+        subscription_id: i64,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<WebhookDeliveryModel>, i64), WebhookStoreError> {
+        let models = webhook_delivery::table
+            .filter(webhook_delivery::subscription_id.eq(subscription_id))
+            .order(webhook_delivery::id.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<WebhookDeliveryModel>(self.conn)?;
+
+        let total = webhook_delivery::table
+            .filter(webhook_delivery::subscription_id.eq(subscription_id))
+            .count()
+            .get_result(self.conn)?;
+
+        Ok((models, total))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ListDeliveriesOperation for WebhookStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn list_deliveries(
+        &self,
+        subscription_id: i64,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<WebhookDeliveryModel>, i64), WebhookStoreError> {
+        let models = webhook_delivery::table
+            .filter(webhook_delivery::subscription_id.eq(subscription_id))
+            .order(webhook_delivery::id.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<WebhookDeliveryModel>(self.conn)?;
+
+        let total = webhook_delivery::table
+            .filter(webhook_delivery::subscription_id.eq(subscription_id))
+            .count()
+            .get_result(self.conn)?;
+
+        Ok((models, total))
+    }
+}
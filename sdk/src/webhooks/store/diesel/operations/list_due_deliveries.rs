@@ -0,0 +1,61 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::WebhookStoreOperations;
+use crate::webhooks::store::diesel::{models::WebhookDeliveryModel, schema::webhook_delivery};
+use crate::webhooks::store::{DeliveryStatus, WebhookStoreError};
+
+use diesel::prelude::*;
+
+pub(in crate::webhooks::store::diesel) trait ListDueDeliveriesOperation {
+    fn list_due_deliveries(
+        &self,
+        now: i64,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryModel>, WebhookStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ListDueDeliveriesOperation for WebhookStoreOperations<'a, diesel::pg::PgConnection> {
+    fn list_due_deliveries(
+        &self,
+        now: i64,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryModel>, WebhookStoreError> {
+        Ok(webhook_delivery::table
+            .filter(webhook_delivery::status.eq(DeliveryStatus::Pending.as_str()))
+            .filter(webhook_delivery::next_attempt_at.le(now))
+            .order(webhook_delivery::id.asc())
+            .limit(limit)
+            .load::<WebhookDeliveryModel>(self.conn)?)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ListDueDeliveriesOperation
+    for WebhookStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_due_deliveries(
+        &self,
+        now: i64,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryModel>, WebhookStoreError> {
+        Ok(webhook_delivery::table
+            .filter(webhook_delivery::status.eq(DeliveryStatus::Pending.as_str()))
+            .filter(webhook_delivery::next_attempt_at.le(now))
+            .order(webhook_delivery::id.asc())
+            .limit(limit)
+            .load::<WebhookDeliveryModel>(self.conn)?)
+    }
+}
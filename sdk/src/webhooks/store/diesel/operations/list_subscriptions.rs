@@ -0,0 +1,69 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::WebhookStoreOperations;
+use crate::webhooks::store::diesel::{
+    models::WebhookSubscriptionModel, schema::webhook_subscription,
+};
+use crate::webhooks::store::WebhookStoreError;
+
+use diesel::prelude::*;
+
+pub(in crate::webhooks::store::diesel) trait ListSubscriptionsOperation {
+    fn list_subscriptions(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<WebhookSubscriptionModel>, i64), WebhookStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ListSubscriptionsOperation for WebhookStoreOperations<'a, diesel::pg::PgConnection> {
+    fn list_subscriptions(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<WebhookSubscriptionModel>, i64), WebhookStoreError> {
+        let models = webhook_subscription::table
+            .order(webhook_subscription::id.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<WebhookSubscriptionModel>(self.conn)?;
+
+        let total = webhook_subscription::table.count().get_result(self.conn)?;
+
+        Ok((models, total))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ListSubscriptionsOperation
+    for WebhookStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_subscriptions(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<WebhookSubscriptionModel>, i64), WebhookStoreError> {
+        let models = webhook_subscription::table
+            .order(webhook_subscription::id.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<WebhookSubscriptionModel>(self.conn)?;
+
+        let total = webhook_subscription::table.count().get_result(self.conn)?;
+
+        Ok((models, total))
+    }
+}
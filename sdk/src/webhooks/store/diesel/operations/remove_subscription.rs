@@ -0,0 +1,69 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::WebhookStoreOperations;
+use crate::webhooks::store::diesel::schema::{webhook_delivery, webhook_subscription};
+use crate::webhooks::store::WebhookStoreError;
+
+use diesel::{dsl::delete, prelude::*};
+
+pub(in crate::webhooks::store::diesel) trait RemoveSubscriptionOperation {
+    fn remove_subscription(&self, id: i64) -> Result<(), WebhookStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RemoveSubscriptionOperation for WebhookStoreOperations<'a, diesel::pg::PgConnection> {
+    fn remove_subscription(&self, id: i64) -> Result<(), WebhookStoreError> {
+        self.conn.transaction::<_, WebhookStoreError, _>(|| {
+            delete(webhook_delivery::table.filter(webhook_delivery::subscription_id.eq(id)))
+                .execute(self.conn)?;
+            let deleted =
+                delete(webhook_subscription::table.filter(webhook_subscription::id.eq(id)))
+                    .execute(self.conn)?;
+
+            if deleted == 0 {
+                return Err(WebhookStoreError::NotFoundError(format!(
+                    "subscription {}",
+                    id
+                )));
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RemoveSubscriptionOperation
+    for WebhookStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_subscription(&self, id: i64) -> Result<(), WebhookStoreError> {
+        self.conn.transaction::<_, WebhookStoreError, _>(|| {
+            delete(webhook_delivery::table.filter(webhook_delivery::subscription_id.eq(id)))
+                .execute(self.conn)?;
+            let deleted =
+                delete(webhook_subscription::table.filter(webhook_subscription::id.eq(id)))
+                    .execute(self.conn)?;
+
+            if deleted == 0 {
+                return Err(WebhookStoreError::NotFoundError(format!(
+                    "subscription {}",
+                    id
+                )));
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::WebhookStoreOperations;
+use crate::webhooks::store::diesel::schema::webhook_delivery;
+use crate::webhooks::store::WebhookStoreError;
+
+use diesel::{dsl::update, prelude::*};
+
+pub(in crate::webhooks::store::diesel) trait UpdateDeliveryOperation {
+    fn update_delivery(
+        &self,
+        id: i64,
+        status: &str,
+        attempts: i32,
+        next_attempt_at: i64,
+        last_error: Option<&str>,
+    ) -> Result<(), WebhookStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> UpdateDeliveryOperation for WebhookStoreOperations<'a, diesel::pg::PgConnection> {
+    fn update_delivery(
+        &self,
+        id: i64,
+        status: &str,
+        attempts: i32,
+        next_attempt_at: i64,
+        last_error: Option<&str>,
+    ) -> Result<(), WebhookStoreError> {
+        let updated = update(webhook_delivery::table.filter(webhook_delivery::id.eq(id)))
+            .set((
+                webhook_delivery::status.eq(status),
+                webhook_delivery::attempts.eq(attempts),
+                webhook_delivery::next_attempt_at.eq(next_attempt_at),
+                webhook_delivery::last_error.eq(last_error),
+            ))
+            .execute(self.conn)?;
+
+        if updated == 0 {
+            return Err(WebhookStoreError::NotFoundError(format!("delivery {}", id)));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> UpdateDeliveryOperation for WebhookStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn update_delivery(
+        &self,
+        id: i64,
+        status: &str,
+        attempts: i32,
+        next_attempt_at: i64,
+        last_error: Option<&str>,
+    ) -> Result<(), WebhookStoreError> {
+        let updated = update(webhook_delivery::table.filter(webhook_delivery::id.eq(id)))
+            .set((
+                webhook_delivery::status.eq(status),
+                webhook_delivery::attempts.eq(attempts),
+                webhook_delivery::next_attempt_at.eq(next_attempt_at),
+                webhook_delivery::last_error.eq(last_error),
+            ))
+            .execute(self.conn)?;
+
+        if updated == 0 {
+            return Err(WebhookStoreError::NotFoundError(format!("delivery {}", id)));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+table! {
+    webhook_subscription (id) {
+        id -> Int8,
+        url -> Text,
+        secret -> Text,
+        entity_types -> Text,
+        owner_orgs -> Text,
+        service_id -> Nullable<Text>,
+        created_at -> Int8,
+    }
+}
+
+table! {
+    webhook_delivery (id) {
+        id -> Int8,
+        subscription_id -> Int8,
+        event_id -> Text,
+        event_type -> Text,
+        payload -> Text,
+        status -> Text,
+        attempts -> Integer,
+        next_attempt_at -> Int8,
+        last_error -> Nullable<Text>,
+        created_at -> Int8,
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::InternalError;
+use crate::paging::Paging;
+
+use super::{
+    DeliveryStatus, WebhookDelivery, WebhookDeliveryList, WebhookStore, WebhookStoreError,
+    WebhookSubscription, WebhookSubscriptionList,
+};
+
+#[derive(Default)]
+struct Inner {
+    next_subscription_id: i64,
+    next_delivery_id: i64,
+    subscriptions: Vec<WebhookSubscription>,
+    deliveries: Vec<WebhookDelivery>,
+}
+
+/// Implementation of `WebhookStore` that keeps subscriptions and deliveries in memory. Useful
+/// for tests and when persistence isn't necessary.
+#[derive(Clone, Default)]
+pub struct MemoryWebhookStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl MemoryWebhookStore {
+    pub fn new() -> Self {
+        MemoryWebhookStore::default()
+    }
+
+    fn inner(&self) -> Result<MutexGuard<'_, Inner>, WebhookStoreError> {
+        self.inner.lock().map_err(|_| {
+            WebhookStoreError::InternalError(InternalError::with_message(
+                "Cannot access webhooks: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+impl WebhookStore for MemoryWebhookStore {
+    fn add_subscription(
+        &self,
+        mut subscription: WebhookSubscription,
+    ) -> Result<i64, WebhookStoreError> {
+        let mut inner = self.inner()?;
+        inner.next_subscription_id += 1;
+        let id = inner.next_subscription_id;
+        subscription.id = Some(id);
+        inner.subscriptions.push(subscription);
+        Ok(id)
+    }
+
+    fn fetch_subscription(
+        &self,
+        id: i64,
+    ) -> Result<Option<WebhookSubscription>, WebhookStoreError> {
+        Ok(self
+            .inner()?
+            .subscriptions
+            .iter()
+            .find(|subscription| subscription.id == Some(id))
+            .cloned())
+    }
+
+    fn list_subscriptions(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookSubscriptionList, WebhookStoreError> {
+        let inner = self.inner()?;
+        let data = inner
+            .subscriptions
+            .iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect();
+        let total = inner.subscriptions.len() as i64;
+        Ok(WebhookSubscriptionList::new(
+            data,
+            Paging::new(offset, limit, total),
+        ))
+    }
+
+    fn remove_subscription(&self, id: i64) -> Result<(), WebhookStoreError> {
+        let mut inner = self.inner()?;
+        let len = inner.subscriptions.len();
+        inner
+            .subscriptions
+            .retain(|subscription| subscription.id != Some(id));
+        if inner.subscriptions.len() == len {
+            return Err(WebhookStoreError::NotFoundError(format!(
+                "subscription {}",
+                id
+            )));
+        }
+        inner
+            .deliveries
+            .retain(|delivery| delivery.subscription_id != id);
+        Ok(())
+    }
+
+    fn add_deliveries(&self, deliveries: Vec<WebhookDelivery>) -> Result<(), WebhookStoreError> {
+        let mut inner = self.inner()?;
+        for mut delivery in deliveries {
+            if inner.deliveries.iter().any(|queued| {
+                queued.subscription_id == delivery.subscription_id
+                    && queued.event_id == delivery.event_id
+            }) {
+                continue;
+            }
+            inner.next_delivery_id += 1;
+            delivery.id = Some(inner.next_delivery_id);
+            inner.deliveries.push(delivery);
+        }
+        Ok(())
+    }
+
+    fn fetch_delivery(&self, id: i64) -> Result<Option<WebhookDelivery>, WebhookStoreError> {
+        Ok(self
+            .inner()?
+            .deliveries
+            .iter()
+            .find(|delivery| delivery.id == Some(id))
+            .cloned())
+    }
+
+    fn list_deliveries(
+        &self,
+        subscription_id: i64,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookDeliveryList, WebhookStoreError> {
+        let inner = self.inner()?;
+        let deliveries = inner
+            .deliveries
+            .iter()
+            .filter(|delivery| delivery.subscription_id == subscription_id)
+            .collect::<Vec<_>>();
+        let total = deliveries.len() as i64;
+        let data = deliveries
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect();
+        Ok(WebhookDeliveryList::new(
+            data,
+            Paging::new(offset, limit, total),
+        ))
+    }
+
+    fn list_due_deliveries(
+        &self,
+        now: i64,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookStoreError> {
+        Ok(self
+            .inner()?
+            .deliveries
+            .iter()
+            .filter(|delivery| {
+                delivery.status == DeliveryStatus::Pending && delivery.next_attempt_at <= now
+            })
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+
+    fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<(), WebhookStoreError> {
+        let mut inner = self.inner()?;
+        let queued = inner
+            .deliveries
+            .iter_mut()
+            .find(|queued| delivery.id.is_some() && queued.id == delivery.id)
+            .ok_or_else(|| {
+                WebhookStoreError::NotFoundError(format!(
+                    "delivery {}",
+                    delivery.id.unwrap_or_default()
+                ))
+            })?;
+        queued.status = delivery.status;
+        queued.attempts = delivery.attempts;
+        queued.next_attempt_at = delivery.next_attempt_at;
+        queued.last_error = delivery.last_error.clone();
+        Ok(())
+    }
+}
@@ -0,0 +1,518 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+#[cfg(feature = "memory")]
+pub mod memory;
+
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::paging::Paging;
+
+pub use error::WebhookStoreError;
+
+/// A request to have Grid events posted to a URL
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookSubscription {
+    /// The identifier assigned by the store; `None` until the subscription has been added
+    pub id: Option<i64>,
+    /// The URL that events are posted to
+    pub url: String,
+    /// The secret used to sign each delivery
+    pub secret: String,
+    /// The entity types the subscriber is interested in; empty for all of them
+    pub entity_types: Vec<String>,
+    /// The organizations whose entities the subscriber is interested in; empty for all of them
+    pub owner_orgs: Vec<String>,
+    /// The service whose events the subscriber is interested in; `None` for all services
+    pub service_id: Option<String>,
+    /// Seconds since the Unix epoch at which the subscription was created
+    pub created_at: i64,
+}
+
+impl WebhookSubscription {
+    pub fn new(
+        url: &str,
+        secret: &str,
+        entity_types: Vec<String>,
+        owner_orgs: Vec<String>,
+        service_id: Option<String>,
+    ) -> Self {
+        WebhookSubscription {
+            id: None,
+            url: url.to_string(),
+            secret: secret.to_string(),
+            entity_types,
+            owner_orgs,
+            service_id,
+            created_at: now(),
+        }
+    }
+
+    /// Returns whether an event about an entity should be delivered to this subscription
+    ///
+    /// # Arguments
+    ///
+    ///  * `entity_type` - The type of the entity that changed
+    ///  * `owner` - The organization that owns the entity, if it has one
+    ///  * `service_id` - The service the change was committed to, if any
+    pub fn matches(
+        &self,
+        entity_type: &str,
+        owner: Option<&str>,
+        service_id: Option<&str>,
+    ) -> bool {
+        (self.entity_types.is_empty() || self.entity_types.iter().any(|t| t == entity_type))
+            && (self.owner_orgs.is_empty()
+                || owner
+                    .map(|owner| self.owner_orgs.iter().any(|org| org == owner))
+                    .unwrap_or(false))
+            && (self.service_id.is_none() || self.service_id.as_deref() == service_id)
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookSubscriptionList {
+    pub data: Vec<WebhookSubscription>,
+    pub paging: Paging,
+}
+
+impl WebhookSubscriptionList {
+    fn new(data: Vec<WebhookSubscription>, paging: Paging) -> Self {
+        Self { data, paging }
+    }
+}
+
+/// The state of a delivery
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeliveryStatus {
+    /// The delivery is waiting for its next attempt
+    Pending,
+    /// The subscriber accepted the delivery
+    Delivered,
+    /// Every attempt at the delivery failed
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for DeliveryStatus {
+    type Err = WebhookStoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(DeliveryStatus::Pending),
+            "delivered" => Ok(DeliveryStatus::Delivered),
+            "failed" => Ok(DeliveryStatus::Failed),
+            _ => Err(WebhookStoreError::InternalError(
+                crate::error::InternalError::with_message(format!(
+                    "Unknown delivery status: {}",
+                    s
+                )),
+            )),
+        }
+    }
+}
+
+/// An event queued for delivery to a subscription
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookDelivery {
+    /// The identifier assigned by the store; `None` until the delivery has been added
+    pub id: Option<i64>,
+    /// The subscription the event is delivered to
+    pub subscription_id: i64,
+    /// The identifier of the event, unique for each subscription
+    pub event_id: String,
+    /// The type of the event, such as `product.created`
+    pub event_type: String,
+    /// The JSON body that is posted to the subscription's URL
+    pub payload: String,
+    pub status: DeliveryStatus,
+    /// The number of attempts made so far
+    pub attempts: i32,
+    /// Seconds since the Unix epoch after which the next attempt may be made
+    pub next_attempt_at: i64,
+    /// The error from the most recent failed attempt
+    pub last_error: Option<String>,
+    /// Seconds since the Unix epoch at which the delivery was queued
+    pub created_at: i64,
+}
+
+impl WebhookDelivery {
+    /// Creates a pending delivery that is due immediately
+    pub fn new(subscription_id: i64, event_id: &str, event_type: &str, payload: &str) -> Self {
+        let created_at = now();
+
+        WebhookDelivery {
+            id: None,
+            subscription_id,
+            event_id: event_id.to_string(),
+            event_type: event_type.to_string(),
+            payload: payload.to_string(),
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: created_at,
+            last_error: None,
+            created_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookDeliveryList {
+    pub data: Vec<WebhookDelivery>,
+    pub paging: Paging,
+}
+
+impl WebhookDeliveryList {
+    fn new(data: Vec<WebhookDelivery>, paging: Paging) -> Self {
+        Self { data, paging }
+    }
+}
+
+pub trait WebhookStore: Send + Sync {
+    /// Adds a subscription to the store, returning the identifier assigned to it
+    ///
+    /// # Arguments
+    ///
+    ///  * `subscription` - The subscription to be added
+    fn add_subscription(&self, subscription: WebhookSubscription)
+        -> Result<i64, WebhookStoreError>;
+
+    /// Fetches a subscription from the store
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The identifier of the subscription
+    fn fetch_subscription(&self, id: i64)
+        -> Result<Option<WebhookSubscription>, WebhookStoreError>;
+
+    /// Lists subscriptions in the order they were added
+    ///
+    /// # Arguments
+    ///
+    ///  * `offset` - The index of the first subscription to return
+    ///  * `limit` - The maximum number of subscriptions to return
+    fn list_subscriptions(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookSubscriptionList, WebhookStoreError>;
+
+    /// Removes a subscription and all of its deliveries from the store
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The identifier of the subscription
+    fn remove_subscription(&self, id: i64) -> Result<(), WebhookStoreError>;
+
+    /// Queues deliveries. A delivery of an event that is already queued for the same
+    /// subscription is ignored, so an event that is handled twice is only delivered once.
+    ///
+    /// # Arguments
+    ///
+    ///  * `deliveries` - The deliveries to be added
+    fn add_deliveries(&self, deliveries: Vec<WebhookDelivery>) -> Result<(), WebhookStoreError>;
+
+    /// Fetches a delivery from the store
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The identifier of the delivery
+    fn fetch_delivery(&self, id: i64) -> Result<Option<WebhookDelivery>, WebhookStoreError>;
+
+    /// Lists the deliveries for a subscription in the order they were queued
+    ///
+    /// # Arguments
+    ///
+    ///  * `subscription_id` - The identifier of the subscription
+    ///  * `offset` - The index of the first delivery to return
+    ///  * `limit` - The maximum number of deliveries to return
+    fn list_deliveries(
+        &self,
+        subscription_id: i64,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookDeliveryList, WebhookStoreError>;
+
+    /// Lists pending deliveries whose next attempt is due, oldest first
+    ///
+    /// # Arguments
+    ///
+    ///  * `now` - Seconds since the Unix epoch
+    ///  * `limit` - The maximum number of deliveries to return
+    fn list_due_deliveries(
+        &self,
+        now: i64,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookStoreError>;
+
+    /// Updates the status, attempts, next attempt and error of a delivery
+    ///
+    /// # Arguments
+    ///
+    ///  * `delivery` - The delivery, with the identifier assigned by the store
+    fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<(), WebhookStoreError>;
+}
+
+impl<WS> WebhookStore for Box<WS>
+where
+    WS: WebhookStore + ?Sized,
+{
+    fn add_subscription(
+        &self,
+        subscription: WebhookSubscription,
+    ) -> Result<i64, WebhookStoreError> {
+        (**self).add_subscription(subscription)
+    }
+
+    fn fetch_subscription(
+        &self,
+        id: i64,
+    ) -> Result<Option<WebhookSubscription>, WebhookStoreError> {
+        (**self).fetch_subscription(id)
+    }
+
+    fn list_subscriptions(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookSubscriptionList, WebhookStoreError> {
+        (**self).list_subscriptions(offset, limit)
+    }
+
+    fn remove_subscription(&self, id: i64) -> Result<(), WebhookStoreError> {
+        (**self).remove_subscription(id)
+    }
+
+    fn add_deliveries(&self, deliveries: Vec<WebhookDelivery>) -> Result<(), WebhookStoreError> {
+        (**self).add_deliveries(deliveries)
+    }
+
+    fn fetch_delivery(&self, id: i64) -> Result<Option<WebhookDelivery>, WebhookStoreError> {
+        (**self).fetch_delivery(id)
+    }
+
+    fn list_deliveries(
+        &self,
+        subscription_id: i64,
+        offset: i64,
+        limit: i64,
+    ) -> Result<WebhookDeliveryList, WebhookStoreError> {
+        (**self).list_deliveries(subscription_id, offset, limit)
+    }
+
+    fn list_due_deliveries(
+        &self,
+        now: i64,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookStoreError> {
+        (**self).list_due_deliveries(now, limit)
+    }
+
+    fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<(), WebhookStoreError> {
+        (**self).update_delivery(delivery)
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(all(test, any(feature = "memory", feature = "sqlite")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test that subscriptions with empty filters match everything, and that each filter narrows
+    // the events that match
+    fn test_subscription_matches() {
+        let all = WebhookSubscription::new("http://localhost", "secret", vec![], vec![], None);
+        assert!(all.matches("product", Some("org-1"), Some("service-1")));
+        assert!(all.matches("agent", None, None));
+
+        let filtered = WebhookSubscription::new(
+            "http://localhost",
+            "secret",
+            vec!["product".to_string()],
+            vec!["org-1".to_string()],
+            Some("service-1".to_string()),
+        );
+        assert!(filtered.matches("product", Some("org-1"), Some("service-1")));
+        assert!(!filtered.matches("location", Some("org-1"), Some("service-1")));
+        assert!(!filtered.matches("product", Some("org-2"), Some("service-1")));
+        assert!(!filtered.matches("product", None, Some("service-1")));
+        assert!(!filtered.matches("product", Some("org-1"), Some("service-2")));
+        assert!(!filtered.matches("product", Some("org-1"), None));
+    }
+
+    #[cfg(feature = "memory")]
+    #[test]
+    // Test that the memory store adds and removes subscriptions and tracks their deliveries
+    fn test_memory_webhook_store() {
+        check_webhook_store(&memory::MemoryWebhookStore::new());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    // Test that the SQLite store adds and removes subscriptions and tracks their deliveries
+    fn test_sqlite_webhook_store() {
+        use ::diesel::r2d2::{ConnectionManager, Pool};
+
+        let connection_manager =
+            ConnectionManager::<::diesel::sqlite::SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+        crate::migrations::run_sqlite_migrations(&*pool.get().expect("Failed to get connection"))
+            .expect("Failed to run migrations");
+
+        check_webhook_store(&diesel::DieselWebhookStore::new(pool));
+    }
+
+    fn check_webhook_store(store: &dyn WebhookStore) {
+        let first = store
+            .add_subscription(WebhookSubscription::new(
+                "http://localhost:8080/hook",
+                "secret",
+                vec!["product".to_string(), "location".to_string()],
+                vec!["org-1".to_string()],
+                Some("service-1".to_string()),
+            ))
+            .expect("Failed to add subscription");
+        let second = store
+            .add_subscription(WebhookSubscription::new(
+                "http://localhost:8081/hook",
+                "other",
+                vec![],
+                vec![],
+                None,
+            ))
+            .expect("Failed to add subscription");
+        assert_ne!(first, second);
+
+        let subscription = store
+            .fetch_subscription(first)
+            .expect("Failed to fetch subscription")
+            .expect("Subscription not found");
+        assert_eq!(subscription.id, Some(first));
+        assert_eq!(subscription.url, "http://localhost:8080/hook");
+        assert_eq!(subscription.secret, "secret");
+        assert_eq!(subscription.entity_types, vec!["product", "location"]);
+        assert_eq!(subscription.owner_orgs, vec!["org-1"]);
+        assert_eq!(subscription.service_id.as_deref(), Some("service-1"));
+
+        let list = store
+            .list_subscriptions(1, 10)
+            .expect("Failed to list subscriptions");
+        assert_eq!(list.paging.total, 2);
+        assert_eq!(list.data.len(), 1);
+        assert_eq!(list.data[0].id, Some(second));
+
+        let mut due = WebhookDelivery::new(first, "commit-1-0", "product.created", "{}");
+        due.next_attempt_at = 100;
+        let mut later = WebhookDelivery::new(first, "commit-1-1", "product.updated", "{}");
+        later.next_attempt_at = 200;
+        let mut other = WebhookDelivery::new(second, "commit-1-0", "product.created", "{}");
+        other.next_attempt_at = 100;
+        store
+            .add_deliveries(vec![due.clone(), later, other])
+            .expect("Failed to add deliveries");
+        // Queuing the same event again is ignored
+        store
+            .add_deliveries(vec![due])
+            .expect("Failed to add deliveries");
+
+        let deliveries = store
+            .list_deliveries(first, 0, 10)
+            .expect("Failed to list deliveries");
+        assert_eq!(deliveries.paging.total, 2);
+        assert_eq!(deliveries.data[0].event_id, "commit-1-0");
+        assert_eq!(deliveries.data[0].status, DeliveryStatus::Pending);
+        assert_eq!(deliveries.data[1].event_id, "commit-1-1");
+
+        let due = store
+            .list_due_deliveries(150, 10)
+            .expect("Failed to list due deliveries");
+        assert_eq!(due.len(), 2);
+        assert!(due.iter().all(|delivery| delivery.next_attempt_at <= 150));
+        assert_eq!(
+            store
+                .list_due_deliveries(150, 1)
+                .expect("Failed to list due deliveries")
+                .len(),
+            1
+        );
+
+        let mut delivery = due[0].clone();
+        delivery.status = DeliveryStatus::Delivered;
+        delivery.attempts = 1;
+        delivery.last_error = Some("timed out".to_string());
+        store
+            .update_delivery(&delivery)
+            .expect("Failed to update delivery");
+        let fetched = store
+            .fetch_delivery(delivery.id.expect("Delivery has no id"))
+            .expect("Failed to fetch delivery")
+            .expect("Delivery not found");
+        assert_eq!(fetched, delivery);
+        assert_eq!(
+            store
+                .list_due_deliveries(150, 10)
+                .expect("Failed to list due deliveries")
+                .len(),
+            1
+        );
+
+        store
+            .remove_subscription(first)
+            .expect("Failed to remove subscription");
+        assert!(store
+            .fetch_subscription(first)
+            .expect("Failed to fetch subscription")
+            .is_none());
+        assert_eq!(
+            store
+                .list_deliveries(first, 0, 10)
+                .expect("Failed to list deliveries")
+                .paging
+                .total,
+            0
+        );
+        assert!(matches!(
+            store.remove_subscription(first),
+            Err(WebhookStoreError::NotFoundError(_))
+        ));
+        delivery.id = Some(-1);
+        assert!(matches!(
+            store.update_delivery(&delivery),
+            Err(WebhookStoreError::NotFoundError(_))
+        ));
+    }
+}
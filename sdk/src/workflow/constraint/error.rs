@@ -0,0 +1,56 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+/// Errors that may occur while parsing or evaluating a workflow state constraint
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConstraintError {
+    /// The constraint is not valid; `position` is the offset of the problem in the constraint
+    SyntaxError {
+        constraint: String,
+        position: usize,
+        message: String,
+    },
+    /// The constraint refers to a field the entity does not have
+    UnknownField(String),
+    /// The constraint compares a field with a value of a type it cannot be compared with
+    TypeMismatch(String),
+    /// The entity does not satisfy the constraint
+    Unsatisfied(String),
+}
+
+impl Error for ConstraintError {}
+
+impl fmt::Display for ConstraintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConstraintError::SyntaxError {
+                constraint,
+                position,
+                message,
+            } => write!(
+                f,
+                "Invalid constraint \"{}\": {} at position {}",
+                constraint, message, position
+            ),
+            ConstraintError::UnknownField(field) => write!(f, "Unknown field {}", field),
+            ConstraintError::TypeMismatch(msg) => write!(f, "Type mismatch: {}", msg),
+            ConstraintError::Unsatisfied(constraint) => {
+                write!(f, "Constraint \"{}\" is not satisfied", constraint)
+            }
+        }
+    }
+}
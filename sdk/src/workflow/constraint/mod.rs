@@ -0,0 +1,284 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Constraints on the entities in a workflow state.
+//!
+//! A constraint compares fields of the entity being transitioned with literal values, for
+//! example `active=None` or `is_draft = false and (current_revision_id > 1 or status != closed)`.
+//! Comparisons may be combined with `and`, `or` and `not` (or `&&`, `||` and `!`) and grouped
+//! with parentheses. A value is `None`, `true`, `false`, an integer, or a string, which may be
+//! quoted and must be if it contains whitespace or operators.
+//!
+//! Equality may compare any two values; `None` is only equal to `None`. Ordering comparisons
+//! are only defined between two integers or two strings.
+
+mod error;
+mod parser;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+pub use error::ConstraintError;
+
+/// The value of a field of an entity
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+    None,
+    Bool(bool),
+    Integer(i64),
+    String(String),
+}
+
+impl FieldValue {
+    fn type_name(&self) -> &'static str {
+        match self {
+            FieldValue::None => "None",
+            FieldValue::Bool(_) => "a boolean",
+            FieldValue::Integer(_) => "an integer",
+            FieldValue::String(_) => "a string",
+        }
+    }
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldValue::None => write!(f, "None"),
+            FieldValue::Bool(value) => write!(f, "{}", value),
+            FieldValue::Integer(value) => write!(f, "{}", value),
+            FieldValue::String(value) => write!(f, "\"{}\"", value),
+        }
+    }
+}
+
+impl From<Option<&str>> for FieldValue {
+    fn from(value: Option<&str>) -> Self {
+        match value {
+            Some(value) => FieldValue::String(value.to_string()),
+            None => FieldValue::None,
+        }
+    }
+}
+
+/// The fields of an entity that constraints are evaluated against
+pub trait ConstraintFields {
+    /// Returns the value of the named field, or `None` if the entity has no such field
+    fn field(&self, name: &str) -> Option<FieldValue>;
+}
+
+impl ConstraintFields for HashMap<String, FieldValue> {
+    fn field(&self, name: &str) -> Option<FieldValue> {
+        self.get(name).cloned()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CompareOp {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+#[derive(Debug)]
+enum Expression {
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Compare {
+        field: String,
+        op: CompareOp,
+        value: FieldValue,
+    },
+}
+
+/// A parsed constraint
+#[derive(Debug)]
+pub struct Constraint {
+    source: String,
+    expression: Expression,
+}
+
+impl Constraint {
+    /// Parses a constraint
+    ///
+    /// # Arguments
+    ///
+    /// * `constraint` - The text of the constraint
+    pub fn parse(constraint: &str) -> Result<Self, ConstraintError> {
+        Ok(Self {
+            source: constraint.to_string(),
+            expression: parser::parse(constraint)?,
+        })
+    }
+
+    /// Returns whether the given entity satisfies the constraint
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - The fields of the entity
+    pub fn evaluate(&self, fields: &dyn ConstraintFields) -> Result<bool, ConstraintError> {
+        evaluate(&self.expression, fields)
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+fn evaluate(
+    expression: &Expression,
+    fields: &dyn ConstraintFields,
+) -> Result<bool, ConstraintError> {
+    match expression {
+        Expression::And(left, right) => Ok(evaluate(left, fields)? && evaluate(right, fields)?),
+        Expression::Or(left, right) => Ok(evaluate(left, fields)? || evaluate(right, fields)?),
+        Expression::Not(inner) => Ok(!evaluate(inner, fields)?),
+        Expression::Compare { field, op, value } => {
+            let actual = fields
+                .field(field)
+                .ok_or_else(|| ConstraintError::UnknownField(field.to_string()))?;
+            compare(field, &actual, *op, value)
+        }
+    }
+}
+
+fn compare(
+    field: &str,
+    actual: &FieldValue,
+    op: CompareOp,
+    expected: &FieldValue,
+) -> Result<bool, ConstraintError> {
+    let mismatch = || {
+        ConstraintError::TypeMismatch(format!(
+            "field {} is {} and cannot be compared with {}",
+            field,
+            actual.type_name(),
+            expected
+        ))
+    };
+
+    let ordering = match (actual, expected) {
+        (FieldValue::Integer(a), FieldValue::Integer(b)) => a.cmp(b),
+        (FieldValue::String(a), FieldValue::String(b)) => a.cmp(b),
+        (FieldValue::None, _) | (_, FieldValue::None) => match op {
+            CompareOp::Equal => return Ok(actual == expected),
+            CompareOp::NotEqual => return Ok(actual != expected),
+            _ => return Err(mismatch()),
+        },
+        (FieldValue::Bool(a), FieldValue::Bool(b)) => match op {
+            CompareOp::Equal => return Ok(a == b),
+            CompareOp::NotEqual => return Ok(a != b),
+            _ => return Err(mismatch()),
+        },
+        _ => return Err(mismatch()),
+    };
+
+    Ok(match op {
+        CompareOp::Equal => ordering == Ordering::Equal,
+        CompareOp::NotEqual => ordering != Ordering::Equal,
+        CompareOp::Less => ordering == Ordering::Less,
+        CompareOp::LessOrEqual => ordering != Ordering::Greater,
+        CompareOp::Greater => ordering == Ordering::Greater,
+        CompareOp::GreaterOrEqual => ordering != Ordering::Less,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> HashMap<String, FieldValue> {
+        let mut fields = HashMap::new();
+        fields.insert("active".to_string(), FieldValue::None);
+        fields.insert("is_draft".to_string(), FieldValue::Bool(false));
+        fields.insert("revision".to_string(), FieldValue::Integer(3));
+        fields.insert(
+            "status".to_string(),
+            FieldValue::String("proposed".to_string()),
+        );
+        fields
+    }
+
+    fn check(constraint: &str) -> Result<bool, ConstraintError> {
+        Constraint::parse(constraint)?.evaluate(&fields())
+    }
+
+    #[test]
+    // Test comparisons of each type of value
+    fn test_comparisons() {
+        assert_eq!(Ok(true), check("active=None"));
+        assert_eq!(Ok(false), check("active != None"));
+        assert_eq!(Ok(false), check("status = None"));
+        assert_eq!(Ok(true), check("is_draft == false"));
+        assert_eq!(Ok(true), check("revision >= 3"));
+        assert_eq!(Ok(false), check("revision < 3"));
+        assert_eq!(Ok(true), check("status = proposed"));
+        assert_eq!(Ok(true), check("status != 'accepted'"));
+        assert_eq!(Ok(true), check("status < \"rejected\""));
+    }
+
+    #[test]
+    // Test that comparisons combine with and, or and not, with and binding tighter than or
+    fn test_boolean_operators() {
+        assert_eq!(Ok(true), check("is_draft = true or revision = 3"));
+        assert_eq!(
+            Ok(false),
+            check("is_draft = true || revision = 3 and status = accepted")
+        );
+        assert_eq!(
+            Ok(true),
+            check("(is_draft = true or revision = 3) && status = proposed")
+        );
+        assert_eq!(Ok(true), check("not is_draft = true"));
+        assert_eq!(Ok(false), check("!(active = None)"));
+    }
+
+    #[test]
+    // Test that evaluation errors are returned for unknown fields and mismatched types
+    fn test_evaluation_errors() {
+        assert_eq!(
+            Err(ConstraintError::UnknownField("missing".to_string())),
+            check("missing = 1")
+        );
+        match check("revision = proposed") {
+            Err(ConstraintError::TypeMismatch(_)) => (),
+            result => panic!("Expected a type mismatch, got {:?}", result),
+        }
+        match check("is_draft < true") {
+            Err(ConstraintError::TypeMismatch(_)) => (),
+            result => panic!("Expected a type mismatch, got {:?}", result),
+        }
+    }
+
+    #[test]
+    // Test that syntax errors report the position of the problem
+    fn test_syntax_errors() {
+        let position = |constraint: &str| match Constraint::parse(constraint) {
+            Err(ConstraintError::SyntaxError { position, .. }) => position,
+            result => panic!("Expected a syntax error, got {:?}", result),
+        };
+
+        assert_eq!(7, position("active None"));
+        assert_eq!(7, position("active="));
+        assert_eq!(16, position("(active=None and"));
+        assert_eq!(11, position("active=None)"));
+        assert_eq!(7, position("status='open"));
+        assert_eq!(0, position("= None"));
+        assert_eq!(12, position("active=None # comment"));
+    }
+}
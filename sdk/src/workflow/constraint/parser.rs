@@ -0,0 +1,223 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A recursive descent parser for constraints.
+//!
+//! ```text
+//! expression := conjunction (("or" | "||") conjunction)*
+//! conjunction := unary (("and" | "&&") unary)*
+//! unary := ("not" | "!") unary | "(" expression ")" | comparison
+//! comparison := field ("=" | "==" | "!=" | "<" | "<=" | ">" | ">=") value
+//! value := "None" | "true" | "false" | integer | quoted string | word
+//! ```
+
+use super::{CompareOp, ConstraintError, Expression, FieldValue};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    LeftParen,
+    RightParen,
+}
+
+pub(super) fn parse(constraint: &str) -> Result<Expression, ConstraintError> {
+    let tokens = tokenize(constraint)?;
+    let mut parser = Parser {
+        constraint,
+        tokens,
+        next: 0,
+    };
+
+    let expression = parser.expression()?;
+    if parser.next < parser.tokens.len() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+    Ok(expression)
+}
+
+fn syntax_error(constraint: &str, position: usize, message: &str) -> ConstraintError {
+    ConstraintError::SyntaxError {
+        constraint: constraint.to_string(),
+        position,
+        message: message.to_string(),
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '-'
+}
+
+fn tokenize(constraint: &str) -> Result<Vec<(Token, usize)>, ConstraintError> {
+    let mut tokens = vec![];
+    let mut chars = constraint.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '=' => {
+                chars.next_if(|(_, c)| *c == '=');
+                Token::Op(CompareOp::Equal)
+            }
+            '!' => match chars.next_if(|(_, c)| *c == '=') {
+                Some(_) => Token::Op(CompareOp::NotEqual),
+                None => Token::Not,
+            },
+            '<' => match chars.next_if(|(_, c)| *c == '=') {
+                Some(_) => Token::Op(CompareOp::LessOrEqual),
+                None => Token::Op(CompareOp::Less),
+            },
+            '>' => match chars.next_if(|(_, c)| *c == '=') {
+                Some(_) => Token::Op(CompareOp::GreaterOrEqual),
+                None => Token::Op(CompareOp::Greater),
+            },
+            '&' | '|' => match chars.next_if(|(_, next)| *next == c) {
+                Some(_) if c == '&' => Token::And,
+                Some(_) => Token::Or,
+                None => return Err(syntax_error(constraint, position, "unexpected character")),
+            },
+            '\'' | '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, next)) if next == c => break,
+                        Some((_, next)) => value.push(next),
+                        None => {
+                            return Err(syntax_error(constraint, position, "unterminated string"))
+                        }
+                    }
+                }
+                Token::Quoted(value)
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some((_, next)) = chars.next_if(|(_, c)| is_word_char(*c)) {
+                    word.push(next);
+                }
+                match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Word(word),
+                }
+            }
+            _ => return Err(syntax_error(constraint, position, "unexpected character")),
+        };
+        tokens.push((token, position));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    constraint: &'a str,
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> ConstraintError {
+        let position = match self.tokens.get(self.next) {
+            Some((_, position)) => *position,
+            None => self.constraint.len(),
+        };
+        syntax_error(self.constraint, position, message)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(token, _)| token)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.next += 1;
+        token
+    }
+
+    fn expression(&mut self) -> Result<Expression, ConstraintError> {
+        let mut left = self.conjunction()?;
+        while self.peek() == Some(&Token::Or) {
+            self.advance();
+            let right = self.conjunction()?;
+            left = Expression::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn conjunction(&mut self) -> Result<Expression, ConstraintError> {
+        let mut left = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.advance();
+            let right = self.unary()?;
+            left = Expression::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expression, ConstraintError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.advance();
+                Ok(Expression::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::LeftParen) => {
+                self.advance();
+                let expression = self.expression()?;
+                if self.peek() != Some(&Token::RightParen) {
+                    return Err(self.error("expected \")\""));
+                }
+                self.advance();
+                Ok(expression)
+            }
+            _ => self.comparison(),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expression, ConstraintError> {
+        let field = match self.peek() {
+            Some(Token::Word(field)) => field.clone(),
+            _ => return Err(self.error("expected a field name")),
+        };
+        self.advance();
+
+        let op = match self.peek() {
+            Some(Token::Op(op)) => *op,
+            _ => return Err(self.error("expected a comparison operator")),
+        };
+        self.advance();
+
+        let value = match self.peek() {
+            Some(Token::Word(word)) => match word.as_str() {
+                "None" => FieldValue::None,
+                "true" => FieldValue::Bool(true),
+                "false" => FieldValue::Bool(false),
+                _ => match word.parse::<i64>() {
+                    Ok(value) => FieldValue::Integer(value),
+                    Err(_) => FieldValue::String(word.clone()),
+                },
+            },
+            Some(Token::Quoted(value)) => FieldValue::String(value.clone()),
+            _ => return Err(self.error("expected a value")),
+        };
+        self.advance();
+
+        Ok(Expression::Compare { field, op, value })
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+/// A single problem found in a workflow definition, with the position it was found at
+#[derive(Clone, Debug, PartialEq)]
+pub struct DefinitionIssue {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl DefinitionIssue {
+    pub(super) fn new(line: usize, column: usize, message: String) -> Self {
+        Self {
+            line,
+            column,
+            message,
+        }
+    }
+
+    /// The line of the definition the issue was found on, starting at 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the definition the issue was found at, starting at 1
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for DefinitionIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Returned when a workflow definition cannot be parsed or fails validation
+///
+/// All of the issues found in the definition are reported, in the order they appear in the
+/// source.
+#[derive(Debug)]
+pub struct WorkflowDefinitionError {
+    issues: Vec<DefinitionIssue>,
+}
+
+impl WorkflowDefinitionError {
+    pub(super) fn new(mut issues: Vec<DefinitionIssue>) -> Self {
+        issues.sort_by_key(|issue| (issue.line, issue.column));
+        Self { issues }
+    }
+
+    pub fn issues(&self) -> &[DefinitionIssue] {
+        &self.issues
+    }
+}
+
+impl Error for WorkflowDefinitionError {}
+
+impl fmt::Display for WorkflowDefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid workflow definition")?;
+        for issue in &self.issues {
+            write!(f, "\n  {}", issue)?;
+        }
+        Ok(())
+    }
+}
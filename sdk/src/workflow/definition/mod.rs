@@ -0,0 +1,648 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workflows loaded from a declarative definition.
+//!
+//! A definition is a YAML document; since JSON is a subset of YAML, the same definition may also
+//! be written as JSON. It lists the subworkflows of the workflow, and for each one its starting
+//! states and its states:
+//!
+//! ```yaml
+//! subworkflows:
+//!   - name: po
+//!     starting_states: [issued]
+//!     states:
+//!       - name: issued
+//!         constraints: []
+//!         transitions: [closed]
+//!         permission_aliases:
+//!           - name: po.buyer
+//!             permissions: [po.update]
+//!             transitions: [closed]
+//!       - name: closed
+//! ```
+//!
+//! Before a workflow is built the definition is checked: every transition must target a state of
+//! its subworkflow, every starting state must be defined, every state must be reachable from a
+//! starting state, every transition an alias allows must be a transition of its state, and every
+//! constraint must be valid (see the [`constraint`](super::constraint) module). All problems are
+//! reported together, each with the line and column it was found at.
+
+mod error;
+mod node;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub use error::{DefinitionIssue, WorkflowDefinitionError};
+
+use super::constraint::Constraint;
+use super::{PermissionAlias, SubWorkflowBuilder, Workflow, WorkflowStateBuilder};
+use node::{Node, Value};
+
+/// Loads a workflow from a YAML or JSON definition
+///
+/// # Arguments
+///
+/// * `source` - The text of the definition
+pub fn load_workflow(source: &str) -> Result<Workflow, WorkflowDefinitionError> {
+    let root = match node::parse(source) {
+        Ok(Some(root)) => root,
+        Ok(None) => {
+            return Err(WorkflowDefinitionError::new(vec![DefinitionIssue::new(
+                1,
+                1,
+                "definition is empty".to_string(),
+            )]))
+        }
+        Err(issue) => return Err(WorkflowDefinitionError::new(vec![issue])),
+    };
+
+    let mut issues = vec![];
+    let subworkflows = decode_workflow(&root, &mut issues);
+    for subworkflow in &subworkflows {
+        validate_subworkflow(subworkflow, &mut issues);
+    }
+
+    if !issues.is_empty() {
+        return Err(WorkflowDefinitionError::new(issues));
+    }
+
+    Ok(Workflow::new(
+        subworkflows.into_iter().map(build_subworkflow).collect(),
+    ))
+}
+
+/// A string from the definition and where it was found
+struct Spanned {
+    value: String,
+    line: usize,
+    column: usize,
+}
+
+impl Spanned {
+    fn issue(&self, message: String) -> DefinitionIssue {
+        DefinitionIssue::new(self.line, self.column, message)
+    }
+}
+
+struct AliasDefinition {
+    name: Spanned,
+    permissions: Vec<Spanned>,
+    transitions: Vec<Spanned>,
+}
+
+struct StateDefinition {
+    name: Spanned,
+    constraints: Vec<Spanned>,
+    transitions: Vec<Spanned>,
+    permission_aliases: Vec<AliasDefinition>,
+}
+
+struct SubWorkflowDefinition {
+    name: Spanned,
+    starting_states: Vec<Spanned>,
+    states: Vec<StateDefinition>,
+}
+
+fn decode_workflow(node: &Node, issues: &mut Vec<DefinitionIssue>) -> Vec<SubWorkflowDefinition> {
+    let fields = match mapping(node, &["subworkflows"], issues) {
+        Some(fields) => fields,
+        None => return vec![],
+    };
+
+    let subworkflows = match fields.get("subworkflows") {
+        Some(subworkflows) => sequence(subworkflows, issues),
+        None => {
+            issues.push(node.issue("missing field \"subworkflows\"".to_string()));
+            &[]
+        }
+    };
+
+    let mut names = HashSet::new();
+    let mut definitions = vec![];
+    for node in subworkflows {
+        if let Some(subworkflow) = decode_subworkflow(node, issues) {
+            if names.insert(subworkflow.name.value.clone()) {
+                definitions.push(subworkflow);
+            } else {
+                issues.push(subworkflow.name.issue(format!(
+                    "subworkflow \"{}\" is defined more than once",
+                    subworkflow.name.value
+                )));
+            }
+        }
+    }
+
+    definitions
+}
+
+fn decode_subworkflow(
+    node: &Node,
+    issues: &mut Vec<DefinitionIssue>,
+) -> Option<SubWorkflowDefinition> {
+    let fields = mapping(node, &["name", "starting_states", "states"], issues)?;
+    let name = required_string(node, &fields, "name", issues)?;
+    let starting_states = string_list(fields.get("starting_states"), issues);
+
+    let nodes = match fields.get("states") {
+        Some(states) => sequence(states, issues),
+        None => &[],
+    };
+
+    let mut names = HashSet::new();
+    let mut states = vec![];
+    for node in nodes {
+        if let Some(state) = decode_state(node, issues) {
+            if names.insert(state.name.value.clone()) {
+                states.push(state);
+            } else {
+                issues.push(state.name.issue(format!(
+                    "state \"{}\" is defined more than once in subworkflow \"{}\"",
+                    state.name.value, name.value
+                )));
+            }
+        }
+    }
+
+    Some(SubWorkflowDefinition {
+        name,
+        starting_states,
+        states,
+    })
+}
+
+fn decode_state(node: &Node, issues: &mut Vec<DefinitionIssue>) -> Option<StateDefinition> {
+    let fields = mapping(
+        node,
+        &["name", "constraints", "transitions", "permission_aliases"],
+        issues,
+    )?;
+    let name = required_string(node, &fields, "name", issues)?;
+
+    let nodes = match fields.get("permission_aliases") {
+        Some(aliases) => sequence(aliases, issues),
+        None => &[],
+    };
+
+    let mut permission_aliases = vec![];
+    for node in nodes {
+        if let Some(alias) = decode_alias(node, issues) {
+            permission_aliases.push(alias);
+        }
+    }
+
+    Some(StateDefinition {
+        name,
+        constraints: string_list(fields.get("constraints"), issues),
+        transitions: string_list(fields.get("transitions"), issues),
+        permission_aliases,
+    })
+}
+
+fn decode_alias(node: &Node, issues: &mut Vec<DefinitionIssue>) -> Option<AliasDefinition> {
+    let fields = mapping(node, &["name", "permissions", "transitions"], issues)?;
+    let name = required_string(node, &fields, "name", issues)?;
+
+    Some(AliasDefinition {
+        name,
+        permissions: string_list(fields.get("permissions"), issues),
+        transitions: string_list(fields.get("transitions"), issues),
+    })
+}
+
+/// Returns the fields of a mapping node, reporting any key that is not one of `allowed`
+fn mapping<'a>(
+    node: &'a Node,
+    allowed: &[&str],
+    issues: &mut Vec<DefinitionIssue>,
+) -> Option<HashMap<&'a str, &'a Node>> {
+    let entries = match &node.value {
+        Value::Mapping(entries) => entries,
+        _ => {
+            issues.push(node.issue(format!("expected a mapping, found {}", node.kind())));
+            return None;
+        }
+    };
+
+    let mut fields = HashMap::new();
+    for (key, value) in entries {
+        match &key.value {
+            Value::Scalar(name) if allowed.contains(&name.as_str()) => {
+                if fields.insert(name.as_str(), value).is_some() {
+                    issues.push(key.issue(format!("field \"{}\" is repeated", name)));
+                }
+            }
+            Value::Scalar(name) => issues.push(key.issue(format!(
+                "unknown field \"{}\", expected one of: {}",
+                name,
+                allowed.join(", ")
+            ))),
+            _ => issues.push(key.issue(format!("expected a field name, found {}", key.kind()))),
+        }
+    }
+
+    Some(fields)
+}
+
+/// Returns the items of a sequence node; an empty value is treated as an empty sequence
+fn sequence<'a>(node: &'a Node, issues: &mut Vec<DefinitionIssue>) -> &'a [Node] {
+    match &node.value {
+        Value::Sequence(items) => items,
+        Value::Scalar(value) if is_null(value) => &[],
+        _ => {
+            issues.push(node.issue(format!("expected a list, found {}", node.kind())));
+            &[]
+        }
+    }
+}
+
+fn string(node: &Node, issues: &mut Vec<DefinitionIssue>) -> Option<Spanned> {
+    match &node.value {
+        Value::Scalar(value) if !is_null(value) => Some(Spanned {
+            value: value.to_string(),
+            line: node.line,
+            column: node.column,
+        }),
+        _ => {
+            issues.push(node.issue(format!("expected a string, found {}", node.kind())));
+            None
+        }
+    }
+}
+
+fn required_string(
+    node: &Node,
+    fields: &HashMap<&str, &Node>,
+    field: &str,
+    issues: &mut Vec<DefinitionIssue>,
+) -> Option<Spanned> {
+    match fields.get(field) {
+        Some(value) => string(value, issues),
+        None => {
+            issues.push(node.issue(format!("missing field \"{}\"", field)));
+            None
+        }
+    }
+}
+
+fn string_list(node: Option<&&Node>, issues: &mut Vec<DefinitionIssue>) -> Vec<Spanned> {
+    match node {
+        Some(node) => {
+            let mut strings = vec![];
+            for item in sequence(node, issues) {
+                if let Some(value) = string(item, issues) {
+                    strings.push(value);
+                }
+            }
+            strings
+        }
+        None => vec![],
+    }
+}
+
+fn is_null(value: &str) -> bool {
+    value.is_empty() || value == "~" || value == "null"
+}
+
+fn validate_subworkflow(subworkflow: &SubWorkflowDefinition, issues: &mut Vec<DefinitionIssue>) {
+    let name = &subworkflow.name.value;
+    let states: HashMap<&str, &StateDefinition> = subworkflow
+        .states
+        .iter()
+        .map(|state| (state.name.value.as_str(), state))
+        .collect();
+
+    if subworkflow.starting_states.is_empty() {
+        issues.push(
+            subworkflow
+                .name
+                .issue(format!("subworkflow \"{}\" has no starting states", name)),
+        );
+    }
+
+    for starting_state in &subworkflow.starting_states {
+        if !states.contains_key(starting_state.value.as_str()) {
+            issues.push(starting_state.issue(format!(
+                "starting state \"{}\" is not a state of subworkflow \"{}\"",
+                starting_state.value, name
+            )));
+        }
+    }
+
+    for state in &subworkflow.states {
+        for constraint in &state.constraints {
+            if let Err(err) = Constraint::parse(&constraint.value) {
+                issues.push(constraint.issue(err.to_string()));
+            }
+        }
+
+        for transition in &state.transitions {
+            if !states.contains_key(transition.value.as_str()) {
+                issues.push(transition.issue(format!(
+                    "transition \"{}\" of state \"{}\" is not a state of subworkflow \"{}\"",
+                    transition.value, state.name.value, name
+                )));
+            }
+        }
+
+        for alias in &state.permission_aliases {
+            for transition in &alias.transitions {
+                if !state
+                    .transitions
+                    .iter()
+                    .any(|t| t.value == transition.value)
+                {
+                    issues.push(transition.issue(format!(
+                        "permission alias \"{}\" allows transition \"{}\", which is not a \
+                         transition of state \"{}\"",
+                        alias.name.value, transition.value, state.name.value
+                    )));
+                }
+            }
+        }
+    }
+
+    let mut reachable: HashSet<&str> = HashSet::new();
+    let mut queue: VecDeque<&str> = subworkflow
+        .starting_states
+        .iter()
+        .map(|state| state.value.as_str())
+        .collect();
+    while let Some(next) = queue.pop_front() {
+        if let Some(state) = states.get(next) {
+            if reachable.insert(next) {
+                queue.extend(state.transitions.iter().map(|t| t.value.as_str()));
+            }
+        }
+    }
+
+    // Without a starting state every state is unreachable, which has already been reported
+    if !subworkflow.starting_states.is_empty() {
+        for state in &subworkflow.states {
+            if !reachable.contains(state.name.value.as_str()) {
+                issues.push(state.name.issue(format!(
+                    "state \"{}\" cannot be reached from a starting state of subworkflow \"{}\"",
+                    state.name.value, name
+                )));
+            }
+        }
+    }
+}
+
+fn build_subworkflow(definition: SubWorkflowDefinition) -> super::SubWorkflow {
+    let mut builder = SubWorkflowBuilder::new(&definition.name.value);
+    for starting_state in &definition.starting_states {
+        builder = builder.add_starting_state(&starting_state.value);
+    }
+
+    for state in definition.states {
+        let mut state_builder = WorkflowStateBuilder::new(&state.name.value);
+        for constraint in &state.constraints {
+            state_builder = state_builder.add_constraint(&constraint.value);
+        }
+        for transition in &state.transitions {
+            state_builder = state_builder.add_transition(&transition.value);
+        }
+        for alias_definition in &state.permission_aliases {
+            let mut alias = PermissionAlias::new(&alias_definition.name.value);
+            for permission in &alias_definition.permissions {
+                alias.add_permission(&permission.value);
+            }
+            for transition in &alias_definition.transitions {
+                alias.add_transition(&transition.value);
+            }
+            state_builder = state_builder.add_permission_alias(alias);
+        }
+        builder = builder.add_state(state_builder.build());
+    }
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PO_WORKFLOW: &str = "\
+subworkflows:
+  - name: po
+    starting_states: [issued]
+    states:
+      - name: issued
+        constraints: [\"active=None\"]
+        transitions: [issued, confirmed]
+        permission_aliases:
+          - name: po.seller
+            permissions: [po.create, po.update]
+            transitions: [confirmed]
+      - name: confirmed
+";
+
+    fn issues(source: &str) -> Vec<(usize, usize, String)> {
+        match load_workflow(source) {
+            Ok(_) => panic!("definition should not have loaded"),
+            Err(err) => err
+                .issues()
+                .iter()
+                .map(|issue| (issue.line(), issue.column(), issue.message().to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    // Test that a valid YAML definition loads into a workflow with the defined states and aliases
+    fn test_load_yaml() {
+        let workflow = load_workflow(PO_WORKFLOW).expect("Failed to load workflow");
+        let po = workflow.subworkflow("po").expect("Missing po subworkflow");
+        assert_eq!(&["issued".to_string()], po.starting_states());
+
+        let issued = po.state("issued").expect("Missing issued state");
+        assert_eq!(
+            vec!["po.create".to_string(), "po.update".to_string()],
+            issued.expand_permissions(&["po.seller".to_string()])
+        );
+        assert!(issued.can_transition("confirmed".into(), vec!["po.seller".to_string()]));
+        assert!(!issued.can_transition("issued".into(), vec!["po.seller".to_string()]));
+        assert!(po.state("confirmed").is_some());
+    }
+
+    #[test]
+    // Test that the same definition may be written as JSON
+    fn test_load_json() {
+        let source = r#"{
+  "subworkflows": [
+    {
+      "name": "po",
+      "starting_states": ["issued"],
+      "states": [
+        {
+          "name": "issued",
+          "transitions": ["closed"],
+          "permission_aliases": [
+            {"name": "po.buyer", "permissions": ["po.update"], "transitions": ["closed"]}
+          ]
+        },
+        {"name": "closed"}
+      ]
+    }
+  ]
+}"#;
+
+        let workflow = load_workflow(source).expect("Failed to load workflow");
+        let issued = workflow.subworkflow("po").unwrap().state("issued").unwrap();
+        assert!(issued.can_transition("closed".into(), vec!["po.buyer".to_string()]));
+    }
+
+    #[test]
+    // Test that a transition to an undefined state and an undefined starting state are reported
+    // at the line and column of the offending name
+    fn test_undefined_states() {
+        let source = "\
+subworkflows:
+  - name: po
+    starting_states: [issued, draft]
+    states:
+      - name: issued
+        transitions: [closed]
+";
+        assert_eq!(
+            vec![
+                (
+                    3,
+                    31,
+                    "starting state \"draft\" is not a state of subworkflow \"po\"".to_string()
+                ),
+                (
+                    6,
+                    23,
+                    "transition \"closed\" of state \"issued\" is not a state of subworkflow \
+                     \"po\""
+                        .to_string()
+                ),
+            ],
+            issues(source)
+        );
+    }
+
+    #[test]
+    // Test that a state that cannot be reached from a starting state is reported
+    fn test_unreachable_state() {
+        let source = "\
+subworkflows:
+  - name: po
+    starting_states: [issued]
+    states:
+      - name: issued
+        transitions: [issued]
+      - name: closed
+";
+        assert_eq!(
+            vec![(
+                7,
+                15,
+                "state \"closed\" cannot be reached from a starting state of subworkflow \"po\""
+                    .to_string()
+            )],
+            issues(source)
+        );
+    }
+
+    #[test]
+    // Test that an alias may only allow transitions of the state it is defined in
+    fn test_alias_transition_not_in_state() {
+        let source = "\
+subworkflows:
+  - name: po
+    starting_states: [issued]
+    states:
+      - name: issued
+        transitions: [confirmed]
+        permission_aliases:
+          - name: po.buyer
+            transitions: [closed]
+      - name: confirmed
+";
+        assert_eq!(
+            vec![(
+                9,
+                27,
+                "permission alias \"po.buyer\" allows transition \"closed\", which is not a \
+                 transition of state \"issued\""
+                    .to_string()
+            )],
+            issues(source)
+        );
+    }
+
+    #[test]
+    // Test that structural problems are reported together, in source order
+    fn test_structural_issues() {
+        let source = "\
+subworkflows:
+  - name: po
+    starting: [issued]
+    states:
+      - transitions: []
+      - name: [issued]
+";
+        assert_eq!(
+            vec![
+                (
+                    2,
+                    11,
+                    "subworkflow \"po\" has no starting states".to_string()
+                ),
+                (
+                    3,
+                    5,
+                    "unknown field \"starting\", expected one of: name, starting_states, states"
+                        .to_string()
+                ),
+                (5, 9, "missing field \"name\"".to_string()),
+                (6, 15, "expected a string, found a list".to_string()),
+            ],
+            issues(source)
+        );
+    }
+
+    #[test]
+    // Test that an invalid constraint is reported at the constraint
+    fn test_invalid_constraint() {
+        let source = "\
+subworkflows:
+  - name: po
+    starting_states: [issued]
+    states:
+      - name: issued
+        constraints: [\"active=None\", \"active None\"]
+";
+        assert_eq!(
+            vec![(
+                6,
+                38,
+                "Invalid constraint \"active None\": expected a comparison operator at position 7"
+                    .to_string()
+            )],
+            issues(source)
+        );
+    }
+
+    #[test]
+    // Test that a syntax error is reported at its position
+    fn test_syntax_error() {
+        let found = issues("subworkflows: [po\n");
+        assert_eq!(1, found.len());
+        assert_eq!(2, found[0].0);
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A YAML document tree that remembers where each node was found in the source.
+//!
+//! `yaml_rust::YamlLoader` discards positions, so the tree is built directly from the parser's
+//! marked events.
+
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::{Marker, ScanError};
+
+use super::error::DefinitionIssue;
+
+#[derive(Debug)]
+pub enum Value {
+    Scalar(String),
+    Sequence(Vec<Node>),
+    Mapping(Vec<(Node, Node)>),
+    Alias,
+}
+
+#[derive(Debug)]
+pub struct Node {
+    pub value: Value,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Node {
+    fn new(value: Value, mark: Marker) -> Self {
+        Self {
+            value,
+            line: mark.line(),
+            column: mark.col() + 1,
+        }
+    }
+
+    pub fn issue(&self, message: String) -> DefinitionIssue {
+        DefinitionIssue::new(self.line, self.column, message)
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self.value {
+            Value::Scalar(_) => "a string",
+            Value::Sequence(_) => "a list",
+            Value::Mapping(_) => "a mapping",
+            Value::Alias => "an alias",
+        }
+    }
+}
+
+/// Parses a YAML (or JSON) document into a tree of nodes.
+///
+/// An empty document is returned as `None`.
+pub fn parse(source: &str) -> Result<Option<Node>, DefinitionIssue> {
+    let mut builder = TreeBuilder::default();
+    Parser::new(source.chars())
+        .load(&mut builder, false)
+        .map_err(scan_issue)?;
+    Ok(builder.root)
+}
+
+fn scan_issue(err: ScanError) -> DefinitionIssue {
+    let marker = *err.marker();
+    // The display of a scan error ends with its position, which is reported separately
+    let message = err.to_string();
+    let message = match message.rsplit_once(" at line ") {
+        Some((info, _)) => info.to_string(),
+        None => message,
+    };
+    DefinitionIssue::new(marker.line(), marker.col() + 1, message)
+}
+
+enum Partial {
+    Sequence(Vec<Node>, Marker),
+    Mapping(Vec<(Node, Node)>, Option<Node>, Marker),
+}
+
+#[derive(Default)]
+struct TreeBuilder {
+    stack: Vec<Partial>,
+    root: Option<Node>,
+}
+
+impl TreeBuilder {
+    fn insert(&mut self, node: Node) {
+        match self.stack.last_mut() {
+            Some(Partial::Sequence(items, _)) => items.push(node),
+            Some(Partial::Mapping(entries, key, _)) => match key.take() {
+                Some(key) => entries.push((key, node)),
+                None => *key = Some(node),
+            },
+            None => self.root = Some(node),
+        }
+    }
+}
+
+impl MarkedEventReceiver for TreeBuilder {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::Scalar(value, _, _, _) => self.insert(Node::new(Value::Scalar(value), mark)),
+            Event::Alias(_) => self.insert(Node::new(Value::Alias, mark)),
+            Event::SequenceStart(_) => self.stack.push(Partial::Sequence(vec![], mark)),
+            Event::MappingStart(_) => self.stack.push(Partial::Mapping(vec![], None, mark)),
+            Event::SequenceEnd | Event::MappingEnd => {
+                let mut node = match self.stack.pop() {
+                    Some(Partial::Sequence(items, mark)) => Node::new(Value::Sequence(items), mark),
+                    Some(Partial::Mapping(entries, _, mark)) => {
+                        Node::new(Value::Mapping(entries), mark)
+                    }
+                    None => return,
+                };
+                // The start of a block collection is only marked once the token after its first
+                // item has been scanned, so its first item's position is used where earlier
+                let first = match &node.value {
+                    Value::Sequence(items) => items.first(),
+                    Value::Mapping(entries) => entries.first().map(|(key, _)| key),
+                    _ => None,
+                };
+                if let Some(first) = first {
+                    if (first.line, first.column) < (node.line, node.column) {
+                        node.line = first.line;
+                        node.column = first.column;
+                    }
+                }
+                self.insert(node);
+            }
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Renders workflows as state diagrams in the Graphviz DOT and Mermaid languages.
+//!
+//! States are drawn as nodes and transitions as edges, labelled with the permission aliases
+//! that allow the transition. Starting states are entered from an unlabelled start node.
+
+use super::{SubWorkflow, Workflow};
+
+struct Edge {
+    from: String,
+    to: String,
+    aliases: Vec<String>,
+}
+
+/// Returns the names of the states of a subworkflow, followed by any transition targets it does
+/// not define, and its transitions
+fn graph(subworkflow: &SubWorkflow) -> (Vec<String>, Vec<Edge>) {
+    let mut nodes: Vec<String> = subworkflow
+        .states()
+        .iter()
+        .map(|state| state.name().to_string())
+        .collect();
+    let mut edges = vec![];
+
+    for state in subworkflow.states() {
+        for transition in state.transitions() {
+            if !nodes.contains(transition) {
+                nodes.push(transition.to_string());
+            }
+            edges.push(Edge {
+                from: state.name().to_string(),
+                to: transition.to_string(),
+                aliases: state
+                    .permission_aliases()
+                    .iter()
+                    .filter(|alias| alias.transitions().contains(transition))
+                    .map(|alias| alias.name().to_string())
+                    .collect(),
+            });
+        }
+    }
+
+    (nodes, edges)
+}
+
+/// The id of the node starting states are entered from, matching Mermaid's notation
+const DOT_START: &str = "[*]";
+
+fn dot_id(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn write_dot(out: &mut String, subworkflow: &SubWorkflow, prefix: &str, indent: &str) {
+    let (nodes, edges) = graph(subworkflow);
+    let id = |name: &str| dot_id(&format!("{}{}", prefix, name));
+
+    if !subworkflow.starting_states().is_empty() {
+        out.push_str(&format!("{}{} [shape=point];\n", indent, id(DOT_START)));
+    }
+    for node in &nodes {
+        if prefix.is_empty() {
+            out.push_str(&format!("{}{};\n", indent, id(node)));
+        } else {
+            out.push_str(&format!(
+                "{}{} [label={}];\n",
+                indent,
+                id(node),
+                dot_id(node)
+            ));
+        }
+    }
+    for starting_state in subworkflow.starting_states() {
+        out.push_str(&format!(
+            "{}{} -> {};\n",
+            indent,
+            id(DOT_START),
+            id(starting_state)
+        ));
+    }
+    for edge in &edges {
+        out.push_str(&format!("{}{} -> {}", indent, id(&edge.from), id(&edge.to)));
+        if !edge.aliases.is_empty() {
+            out.push_str(&format!(" [label={}]", dot_id(&edge.aliases.join(", "))));
+        }
+        out.push_str(";\n");
+    }
+}
+
+/// Mermaid state ids may only contain letters, digits and underscores
+fn mermaid_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn mermaid_label(label: &str) -> String {
+    label.replace('"', "#quot;")
+}
+
+fn write_mermaid(out: &mut String, subworkflow: &SubWorkflow, prefix: &str, indent: &str) {
+    let (nodes, edges) = graph(subworkflow);
+    let id = |name: &str| mermaid_id(&format!("{}{}", prefix, name));
+
+    for node in &nodes {
+        out.push_str(&format!(
+            "{}state \"{}\" as {}\n",
+            indent,
+            mermaid_label(node),
+            id(node)
+        ));
+    }
+    for starting_state in subworkflow.starting_states() {
+        out.push_str(&format!("{}[*] --> {}\n", indent, id(starting_state)));
+    }
+    for edge in &edges {
+        out.push_str(&format!(
+            "{}{} --> {}",
+            indent,
+            id(&edge.from),
+            id(&edge.to)
+        ));
+        if !edge.aliases.is_empty() {
+            out.push_str(&format!(" : {}", mermaid_label(&edge.aliases.join(", "))));
+        }
+        out.push('\n');
+    }
+}
+
+impl SubWorkflow {
+    /// Renders the subworkflow as a Graphviz DOT digraph
+    pub fn to_dot(&self) -> String {
+        let mut out = format!("digraph {} {{\n", dot_id(self.name()));
+        write_dot(&mut out, self, "", "    ");
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the subworkflow as a Mermaid state diagram
+    pub fn to_mermaid(&self) -> String {
+        let mut out = "stateDiagram-v2\n".to_string();
+        write_mermaid(&mut out, self, "", "    ");
+        out
+    }
+}
+
+impl Workflow {
+    /// Renders the workflow as a Graphviz DOT digraph, with a cluster for each subworkflow
+    pub fn to_dot(&self) -> String {
+        let mut out = "digraph {\n".to_string();
+        for subworkflow in self.subworkflows() {
+            let prefix = format!("{}.", subworkflow.name());
+            out.push_str(&format!(
+                "    subgraph {} {{\n        label={};\n",
+                dot_id(&format!("cluster_{}", subworkflow.name())),
+                dot_id(subworkflow.name())
+            ));
+            write_dot(&mut out, subworkflow, &prefix, "        ");
+            out.push_str("    }\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the workflow as a Mermaid state diagram, with a composite state for each
+    /// subworkflow
+    pub fn to_mermaid(&self) -> String {
+        let mut out = "stateDiagram-v2\n".to_string();
+        for subworkflow in self.subworkflows() {
+            let prefix = format!("{}.", subworkflow.name());
+            out.push_str(&format!(
+                "    state \"{}\" as {} {{\n",
+                mermaid_label(subworkflow.name()),
+                mermaid_id(subworkflow.name())
+            ));
+            write_mermaid(&mut out, subworkflow, &prefix, "        ");
+            out.push_str("    }\n");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{PermissionAlias, SubWorkflowBuilder, WorkflowStateBuilder};
+    use super::*;
+
+    fn subworkflow() -> SubWorkflow {
+        let mut buyer = PermissionAlias::new("po.buyer");
+        buyer.add_transition("confirmed");
+        buyer.add_transition("closed");
+        let mut seller = PermissionAlias::new("po.seller");
+        seller.add_transition("confirmed");
+
+        SubWorkflowBuilder::new("po")
+            .add_state(
+                WorkflowStateBuilder::new("issued")
+                    .add_transition("confirmed")
+                    .add_transition("closed")
+                    .add_permission_alias(buyer)
+                    .add_permission_alias(seller)
+                    .build(),
+            )
+            .add_state(
+                WorkflowStateBuilder::new("confirmed")
+                    .add_transition("closed")
+                    .build(),
+            )
+            .add_starting_state("issued")
+            .build()
+    }
+
+    #[test]
+    // Test that a subworkflow renders as DOT with aliases labelling the transitions they allow
+    fn test_subworkflow_to_dot() {
+        assert_eq!(
+            subworkflow().to_dot(),
+            "digraph \"po\" {\n    \
+                \"[*]\" [shape=point];\n    \
+                \"issued\";\n    \
+                \"confirmed\";\n    \
+                \"closed\";\n    \
+                \"[*]\" -> \"issued\";\n    \
+                \"issued\" -> \"confirmed\" [label=\"po.buyer, po.seller\"];\n    \
+                \"issued\" -> \"closed\" [label=\"po.buyer\"];\n    \
+                \"confirmed\" -> \"closed\";\n\
+            }\n"
+        );
+    }
+
+    #[test]
+    // Test that a subworkflow renders as a Mermaid state diagram
+    fn test_subworkflow_to_mermaid() {
+        assert_eq!(
+            subworkflow().to_mermaid(),
+            "stateDiagram-v2\n    \
+                state \"issued\" as issued\n    \
+                state \"confirmed\" as confirmed\n    \
+                state \"closed\" as closed\n    \
+                [*] --> issued\n    \
+                issued --> confirmed : po.buyer, po.seller\n    \
+                issued --> closed : po.buyer\n    \
+                confirmed --> closed\n"
+        );
+    }
+
+    #[test]
+    // Test that the states of each subworkflow of a workflow are kept distinct
+    fn test_workflow_diagrams() {
+        let workflow = Workflow::new(vec![subworkflow()]);
+
+        let dot = workflow.to_dot();
+        assert!(
+            dot.starts_with("digraph {\n    subgraph \"cluster_po\" {\n        label=\"po\";\n")
+        );
+        assert!(dot.contains("        \"po.issued\" [label=\"issued\"];\n"));
+        assert!(dot.contains("        \"po.[*]\" -> \"po.issued\";\n"));
+
+        let mermaid = workflow.to_mermaid();
+        assert!(mermaid.starts_with("stateDiagram-v2\n    state \"po\" as po {\n"));
+        assert!(mermaid.contains("        state \"issued\" as po_issued\n"));
+        assert!(mermaid.contains("        po_issued --> po_closed : po.buyer\n"));
+    }
+}
@@ -12,9 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod constraint;
+#[cfg(feature = "workflow-definition")]
+pub mod definition;
+mod graph;
+mod registry;
 mod state;
 mod subworkflow;
 
+pub use constraint::{ConstraintError, ConstraintFields, FieldValue};
+pub use registry::{get_workflow, org_scoped_aliases, registered_workflows};
 pub use state::{PermissionAlias, WorkflowState, WorkflowStateBuilder};
 pub use subworkflow::{SubWorkflow, SubWorkflowBuilder};
 
@@ -36,6 +43,54 @@ impl Workflow {
 
         None
     }
+
+    pub fn subworkflows(&self) -> &[SubWorkflow] {
+        &self.subworkflow
+    }
+
+    /// Returns the transitions and permissions available to an agent holding the given Pike
+    /// roles, for an entity in the given state. Returns `None` if the workflow does not define
+    /// the subworkflow or state.
+    ///
+    /// # Arguments
+    ///
+    /// * `subworkflow` - The name of the subworkflow the entity follows
+    /// * `state` - The name of the entity's current state
+    /// * `roles` - The Pike roles held by the agent, which are matched against permission aliases
+    pub fn allowed_actions(
+        &self,
+        subworkflow: &str,
+        state: &str,
+        roles: &[String],
+    ) -> Option<AllowedActions> {
+        let state = self.subworkflow(subworkflow)?.state(state)?;
+
+        let mut permissions = state.expand_permissions(roles);
+        permissions.sort();
+        permissions.dedup();
+
+        Some(AllowedActions {
+            transitions: state.allowed_transitions(roles),
+            permissions,
+        })
+    }
+}
+
+/// The transitions and permissions available to an agent in a workflow state
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AllowedActions {
+    transitions: Vec<String>,
+    permissions: Vec<String>,
+}
+
+impl AllowedActions {
+    pub fn transitions(&self) -> &[String] {
+        &self.transitions
+    }
+
+    pub fn permissions(&self) -> &[String] {
+        &self.permissions
+    }
 }
 
 #[cfg(test)]
@@ -85,6 +140,16 @@ mod tests {
             false,
             state.can_transition("issued".to_string(), vec!["po.seller".to_string()]),
         );
+
+        let mut fields = std::collections::HashMap::new();
+        fields.insert("active".to_string(), FieldValue::None);
+        assert_eq!(Ok(()), state.check_constraints(&fields));
+
+        fields.insert("active".to_string(), FieldValue::String("1".to_string()));
+        assert_eq!(
+            Err(ConstraintError::Unsatisfied("active=None".to_string())),
+            state.check_constraints(&fields)
+        );
     }
 
     #[test]
@@ -138,5 +203,24 @@ mod tests {
         let workflow = Workflow::new(vec![subworkflow]);
 
         assert!(workflow.subworkflow("po").is_some());
+
+        let actions = workflow
+            .allowed_actions("po", "issued", &["po.seller".to_string()])
+            .expect("Missing issued state");
+        assert_eq!(&["confirm".to_string()], actions.transitions());
+        assert_eq!(
+            &["po.create".to_string(), "po.update".to_string()],
+            actions.permissions()
+        );
+
+        let actions = workflow
+            .allowed_actions("po", "issued", &["po.buyer".to_string()])
+            .expect("Missing issued state");
+        assert!(actions.transitions().is_empty());
+        assert!(actions.permissions().is_empty());
+
+        assert!(workflow
+            .allowed_actions("po", "proposed", &["po.seller".to_string()])
+            .is_none());
     }
 }
@@ -0,0 +1,49 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The workflows defined by Grid's smart contracts, by name.
+
+use super::Workflow;
+
+#[cfg(feature = "purchase-order")]
+use crate::purchase_order::workflow::{
+    purchase_order_workflow, BUYER_ALIAS, PURCHASE_ORDER_WORKFLOW, SELLER_ALIAS,
+};
+
+/// Returns the workflow registered with the given name
+pub fn get_workflow(name: &str) -> Option<Workflow> {
+    match name {
+        #[cfg(feature = "purchase-order")]
+        PURCHASE_ORDER_WORKFLOW => Some(purchase_order_workflow()),
+        _ => None,
+    }
+}
+
+/// Returns the aliases of a registered workflow that only apply to an agent whose organization
+/// is named on the entity, so they cannot be granted without the entity at hand
+pub fn org_scoped_aliases(name: &str) -> Vec<&'static str> {
+    match name {
+        #[cfg(feature = "purchase-order")]
+        PURCHASE_ORDER_WORKFLOW => vec![BUYER_ALIAS, SELLER_ALIAS],
+        _ => vec![],
+    }
+}
+
+/// Returns the names of all registered workflows
+pub fn registered_workflows() -> Vec<&'static str> {
+    vec![
+        #[cfg(feature = "purchase-order")]
+        PURCHASE_ORDER_WORKFLOW,
+    ]
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::constraint::{Constraint, ConstraintError, ConstraintFields};
+
 #[derive(Clone)]
 pub struct WorkflowState {
     name: String,
@@ -37,6 +39,17 @@ impl WorkflowState {
         false
     }
 
+    /// Returns the transitions out of the state that the given permission aliases allow
+    pub fn allowed_transitions(&self, pike_permissions: &[String]) -> Vec<String> {
+        self.transitions
+            .iter()
+            .filter(|transition| {
+                self.can_transition(transition.to_string(), pike_permissions.to_vec())
+            })
+            .cloned()
+            .collect()
+    }
+
     pub fn expand_permissions(&self, names: &[String]) -> Vec<String> {
         let mut perms = Vec::new();
 
@@ -51,9 +64,39 @@ impl WorkflowState {
         perms
     }
 
+    /// Checks that an entity satisfies every constraint of the state
+    ///
+    /// Returns `ConstraintError::Unsatisfied` with the first constraint the entity does not
+    /// satisfy.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - The fields of the entity, as they would be in this state
+    pub fn check_constraints(&self, fields: &dyn ConstraintFields) -> Result<(), ConstraintError> {
+        for constraint in &self.constraints {
+            if !Constraint::parse(constraint)?.evaluate(fields)? {
+                return Err(ConstraintError::Unsatisfied(constraint.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn constraints(&self) -> &[String] {
+        &self.constraints
+    }
+
+    pub fn transitions(&self) -> &[String] {
+        &self.transitions
+    }
+
+    pub fn permission_aliases(&self) -> &[PermissionAlias] {
+        &self.permission_aliases
+    }
 }
 
 #[derive(Default)]
@@ -39,6 +39,10 @@ impl SubWorkflow {
     pub fn starting_states(&self) -> &[String] {
         &self.starting_states
     }
+
+    pub fn states(&self) -> &[WorkflowState] {
+        &self.states
+    }
 }
 
 #[derive(Default)]